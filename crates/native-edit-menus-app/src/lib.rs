@@ -26,6 +26,129 @@ impl EditMenuTarget {
     }
 }
 
+/// Editable fields of a catalog entry, in the order the details view lists them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditMenuDetailField {
+    Command,
+    Description,
+    Icon,
+    Tags,
+    WorkingDirectory,
+    Arguments,
+    Environment,
+    PtyProfile,
+    RunMode,
+    RequiresNetwork,
+}
+
+pub const EDIT_MENU_DETAIL_FIELDS: [EditMenuDetailField; 10] = [
+    EditMenuDetailField::Command,
+    EditMenuDetailField::Description,
+    EditMenuDetailField::Icon,
+    EditMenuDetailField::Tags,
+    EditMenuDetailField::WorkingDirectory,
+    EditMenuDetailField::Arguments,
+    EditMenuDetailField::Environment,
+    EditMenuDetailField::PtyProfile,
+    EditMenuDetailField::RunMode,
+    EditMenuDetailField::RequiresNetwork,
+];
+
+impl EditMenuDetailField {
+    pub fn label(self) -> &'static str {
+        match self {
+            EditMenuDetailField::Command => "Command",
+            EditMenuDetailField::Description => "Description",
+            EditMenuDetailField::Icon => "Icon",
+            EditMenuDetailField::Tags => "Tags",
+            EditMenuDetailField::WorkingDirectory => "Working Directory",
+            EditMenuDetailField::Arguments => "Extra Arguments",
+            EditMenuDetailField::Environment => "Environment",
+            EditMenuDetailField::PtyProfile => "PTY Profile",
+            EditMenuDetailField::RunMode => "Run In",
+            EditMenuDetailField::RequiresNetwork => "Requires Network",
+        }
+    }
+
+    /// Toggles flip in place; the other fields are edited through a prompt.
+    pub fn is_toggle(self) -> bool {
+        matches!(
+            self,
+            EditMenuDetailField::RunMode | EditMenuDetailField::RequiresNetwork
+        )
+    }
+
+    fn prompt(self) -> &'static str {
+        match self {
+            EditMenuDetailField::Command => "Enter launch command:",
+            EditMenuDetailField::Description => "Enter description:",
+            EditMenuDetailField::Icon => "Enter icon path:",
+            EditMenuDetailField::Tags => "Enter tags, separated by commas:",
+            EditMenuDetailField::WorkingDirectory => "Enter working directory:",
+            EditMenuDetailField::Arguments => "Enter extra arguments:",
+            EditMenuDetailField::Environment => "Enter variables as KEY=VALUE KEY=VALUE:",
+            EditMenuDetailField::PtyProfile => "Enter PTY profile name (blank for automatic):",
+            EditMenuDetailField::RunMode | EditMenuDetailField::RequiresNetwork => "",
+        }
+    }
+}
+
+/// Current values of the entry shown in the details view, already in text
+/// form.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EditMenuEntryDetails {
+    pub command: String,
+    pub description: String,
+    pub icon: String,
+    pub tags: String,
+    pub working_dir: String,
+    pub arguments: String,
+    pub environment: String,
+    pub pty_profile: String,
+    pub run_in_pty: bool,
+    pub requires_network: bool,
+}
+
+impl EditMenuEntryDetails {
+    pub fn text(&self, field: EditMenuDetailField) -> &str {
+        match field {
+            EditMenuDetailField::Command => &self.command,
+            EditMenuDetailField::Description => &self.description,
+            EditMenuDetailField::Icon => &self.icon,
+            EditMenuDetailField::Tags => &self.tags,
+            EditMenuDetailField::WorkingDirectory => &self.working_dir,
+            EditMenuDetailField::Arguments => &self.arguments,
+            EditMenuDetailField::Environment => &self.environment,
+            EditMenuDetailField::PtyProfile => &self.pty_profile,
+            EditMenuDetailField::RunMode => {
+                if self.run_in_pty {
+                    "PTY"
+                } else {
+                    "Detached"
+                }
+            }
+            EditMenuDetailField::RequiresNetwork => {
+                if self.requires_network {
+                    "YES"
+                } else {
+                    "NO"
+                }
+            }
+        }
+    }
+
+    fn row(&self, field: EditMenuDetailField) -> String {
+        let value = self.text(field);
+        if field.is_toggle() {
+            format!("{}: {value} [toggle]", field.label())
+        } else if value.is_empty() {
+            format!("{}: (none)", field.label())
+        } else {
+            format!("{}: {value}", field.label())
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum EditMenusView {
     Root,
@@ -37,6 +160,9 @@ enum EditMenusView {
     DeleteDocuments,
     DeleteNetwork,
     DeleteGames,
+    DetailsPick(EditMenuTarget),
+    Details(EditMenuTarget),
+    DesktopImport(EditMenuTarget),
 }
 
 #[derive(Debug, Clone)]
@@ -51,6 +177,10 @@ pub struct TerminalEditMenusState {
     delete_documents_idx: usize,
     delete_network_idx: usize,
     delete_games_idx: usize,
+    details_pick_idx: usize,
+    details_idx: usize,
+    details_name: String,
+    desktop_import_idx: usize,
 }
 
 impl Default for TerminalEditMenusState {
@@ -66,6 +196,10 @@ impl Default for TerminalEditMenusState {
             delete_documents_idx: 0,
             delete_network_idx: 0,
             delete_games_idx: 0,
+            details_pick_idx: 0,
+            details_idx: 0,
+            details_name: String::new(),
+            desktop_import_idx: 0,
         }
     }
 }
@@ -74,6 +208,22 @@ impl TerminalEditMenusState {
     pub fn reset(&mut self) {
         *self = Self::default();
     }
+
+    /// Entry whose details are on screen; the caller supplies them through
+    /// [`EditMenusEntries::details`].
+    pub fn details_entry(&self) -> Option<(EditMenuTarget, &str)> {
+        match self.view {
+            EditMenusView::Details(target) => Some((target, self.details_name.as_str())),
+            _ => None,
+        }
+    }
+
+    /// Shows the desktop entries the caller scanned after a
+    /// [`TerminalEditMenusRequest::ScanDesktopApps`] request.
+    pub fn open_desktop_import(&mut self, target: EditMenuTarget) {
+        self.view = EditMenusView::DesktopImport(target);
+        self.desktop_import_idx = 0;
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -82,6 +232,20 @@ pub struct EditMenusEntries<'a> {
     pub documents: &'a [String],
     pub network: &'a [String],
     pub games: &'a [String],
+    pub details: Option<&'a EditMenuEntryDetails>,
+    /// Labels of scanned desktop entries, listed by the import view.
+    pub desktop_apps: &'a [String],
+}
+
+impl<'a> EditMenusEntries<'a> {
+    fn for_target(&self, target: EditMenuTarget) -> &'a [String] {
+        match target {
+            EditMenuTarget::Applications => self.applications,
+            EditMenuTarget::Documents => self.documents,
+            EditMenuTarget::Network => self.network,
+            EditMenuTarget::Games => self.games,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -113,6 +277,26 @@ pub enum TerminalEditMenusRequest {
         prompt: String,
         name: String,
     },
+    OpenPromptEditDetail {
+        target: EditMenuTarget,
+        name: String,
+        field: EditMenuDetailField,
+        title: String,
+        prompt: String,
+        current: String,
+    },
+    ToggleDetail {
+        target: EditMenuTarget,
+        name: String,
+        field: EditMenuDetailField,
+    },
+    ScanDesktopApps {
+        target: EditMenuTarget,
+    },
+    ImportDesktopApp {
+        target: EditMenuTarget,
+        idx: usize,
+    },
     Status(String),
 }
 
@@ -158,6 +342,8 @@ pub fn build_edit_menus_view_model(
                 ),
                 "---".to_string(),
                 "Add App".to_string(),
+                "Import Desktop Apps".to_string(),
+                "Edit App Details".to_string(),
                 "Delete App".to_string(),
                 "---".to_string(),
                 "Back".to_string(),
@@ -180,6 +366,7 @@ pub fn build_edit_menus_view_model(
             subtitle: None,
             items: vec![
                 "Add Network".to_string(),
+                "Edit Network Details".to_string(),
                 "Delete Network".to_string(),
                 "---".to_string(),
                 "Back".to_string(),
@@ -191,6 +378,8 @@ pub fn build_edit_menus_view_model(
             subtitle: None,
             items: vec![
                 "Add Game".to_string(),
+                "Import Desktop Apps".to_string(),
+                "Edit Game Details".to_string(),
                 "Delete Game".to_string(),
                 "---".to_string(),
                 "Back".to_string(),
@@ -221,6 +410,37 @@ pub fn build_edit_menus_view_model(
             items: delete_items(entries.games),
             selected_idx: state.delete_games_idx,
         },
+        EditMenusView::DetailsPick(target) => EditMenusViewModel {
+            title: "Edit Details",
+            subtitle: Some(format!("Choose {}", target.singular())),
+            items: delete_items(entries.for_target(target)),
+            selected_idx: state.details_pick_idx,
+        },
+        EditMenusView::DesktopImport(target) => EditMenusViewModel {
+            title: "Import Desktop Apps",
+            subtitle: Some(format!("Add to {}", target.title())),
+            items: delete_items(entries.desktop_apps),
+            selected_idx: state.desktop_import_idx,
+        },
+        EditMenusView::Details(_) => {
+            let mut items: Vec<String> = match entries.details {
+                Some(details) => EDIT_MENU_DETAIL_FIELDS
+                    .iter()
+                    .map(|field| details.row(*field))
+                    .collect(),
+                None => Vec::new(),
+            };
+            if !items.is_empty() {
+                items.push("---".to_string());
+            }
+            items.push("Back".to_string());
+            EditMenusViewModel {
+                title: "Edit Details",
+                subtitle: Some(state.details_name.clone()),
+                items,
+                selected_idx: state.details_idx,
+            }
+        }
     }
 }
 
@@ -264,7 +484,11 @@ pub fn apply_edit_menus_activation(
                     EditMenuTarget::Applications.singular()
                 ),
             },
-            4 => {
+            4 => TerminalEditMenusRequest::ScanDesktopApps {
+                target: EditMenuTarget::Applications,
+            },
+            5 => open_details_pick(state, entries, EditMenuTarget::Applications),
+            6 => {
                 if entries.applications.is_empty() {
                     TerminalEditMenusRequest::Status("Error: App list is empty.".to_string())
                 } else {
@@ -303,7 +527,8 @@ pub fn apply_edit_menus_activation(
                 title: format!("Edit {}", EditMenuTarget::Network.title()),
                 prompt: format!("Enter {} display name:", EditMenuTarget::Network.singular()),
             },
-            1 => {
+            1 => open_details_pick(state, entries, EditMenuTarget::Network),
+            2 => {
                 if entries.network.is_empty() {
                     TerminalEditMenusRequest::Status(
                         "Error: Network Program list is empty.".to_string(),
@@ -325,7 +550,11 @@ pub fn apply_edit_menus_activation(
                 title: format!("Edit {}", EditMenuTarget::Games.title()),
                 prompt: format!("Enter {} display name:", EditMenuTarget::Games.singular()),
             },
-            1 => {
+            1 => TerminalEditMenusRequest::ScanDesktopApps {
+                target: EditMenuTarget::Games,
+            },
+            2 => open_details_pick(state, entries, EditMenuTarget::Games),
+            3 => {
                 if entries.games.is_empty() {
                     TerminalEditMenusRequest::Status("Error: Game list is empty.".to_string())
                 } else {
@@ -391,7 +620,76 @@ pub fn apply_edit_menus_activation(
                 TerminalEditMenusRequest::None
             }
         }
+        EditMenusView::DetailsPick(target) => {
+            if let Some(name) = entries.for_target(target).get(idx) {
+                state.details_name = name.clone();
+                state.view = EditMenusView::Details(target);
+                state.details_idx = 0;
+            } else {
+                state.view = target_view(target);
+            }
+            TerminalEditMenusRequest::None
+        }
+        EditMenusView::DesktopImport(target) => {
+            if idx < entries.desktop_apps.len() {
+                TerminalEditMenusRequest::ImportDesktopApp { target, idx }
+            } else {
+                state.view = target_view(target);
+                TerminalEditMenusRequest::None
+            }
+        }
+        EditMenusView::Details(target) => {
+            let field = entries
+                .details
+                .and_then(|details| EDIT_MENU_DETAIL_FIELDS.get(idx).map(|f| (details, *f)));
+            let Some((details, field)) = field else {
+                state.view = EditMenusView::DetailsPick(target);
+                return TerminalEditMenusRequest::None;
+            };
+            let name = state.details_name.clone();
+            if field.is_toggle() {
+                TerminalEditMenusRequest::ToggleDetail {
+                    target,
+                    name,
+                    field,
+                }
+            } else {
+                TerminalEditMenusRequest::OpenPromptEditDetail {
+                    target,
+                    title: format!("{name}: {}", field.label()),
+                    name,
+                    field,
+                    prompt: field.prompt().to_string(),
+                    current: details.text(field).to_string(),
+                }
+            }
+        }
+    }
+}
+
+fn target_view(target: EditMenuTarget) -> EditMenusView {
+    match target {
+        EditMenuTarget::Applications => EditMenusView::Applications,
+        EditMenuTarget::Documents => EditMenusView::Documents,
+        EditMenuTarget::Network => EditMenusView::Network,
+        EditMenuTarget::Games => EditMenusView::Games,
+    }
+}
+
+fn open_details_pick(
+    state: &mut TerminalEditMenusState,
+    entries: EditMenusEntries<'_>,
+    target: EditMenuTarget,
+) -> TerminalEditMenusRequest {
+    if entries.for_target(target).is_empty() {
+        return TerminalEditMenusRequest::Status(format!(
+            "Error: {} list is empty.",
+            target.singular()
+        ));
     }
+    state.view = EditMenusView::DetailsPick(target);
+    state.details_pick_idx = 0;
+    TerminalEditMenusRequest::None
 }
 
 pub fn apply_edit_menus_selected_idx(state: &mut TerminalEditMenusState, idx: usize) {
@@ -405,6 +703,9 @@ pub fn apply_edit_menus_selected_idx(state: &mut TerminalEditMenusState, idx: us
         EditMenusView::DeleteDocuments => state.delete_documents_idx = idx,
         EditMenusView::DeleteNetwork => state.delete_network_idx = idx,
         EditMenusView::DeleteGames => state.delete_games_idx = idx,
+        EditMenusView::DetailsPick(_) => state.details_pick_idx = idx,
+        EditMenusView::Details(_) => state.details_idx = idx,
+        EditMenusView::DesktopImport(_) => state.desktop_import_idx = idx,
     }
 }
 
@@ -444,6 +745,8 @@ mod tests {
                 documents: &[],
                 network: &[],
                 games: &[],
+                details: None,
+                desktop_apps: &[],
             },
             true,
             true,
@@ -462,6 +765,8 @@ mod tests {
                 documents: &["Work".to_string()],
                 network: &[],
                 games: &[],
+                details: None,
+                desktop_apps: &[],
             },
             Some(0),
         );
@@ -473,4 +778,94 @@ mod tests {
             }
         ));
     }
+
+    #[test]
+    fn details_view_lists_fields_and_requests_edits() {
+        let mut state = TerminalEditMenusState {
+            view: EditMenusView::Network,
+            ..TerminalEditMenusState::default()
+        };
+        let network = ["Browser".to_string()];
+        let details = EditMenuEntryDetails {
+            command: "lynx".to_string(),
+            run_in_pty: true,
+            ..EditMenuEntryDetails::default()
+        };
+        let entries = EditMenusEntries {
+            applications: &[],
+            documents: &[],
+            network: &network,
+            games: &[],
+            details: Some(&details),
+            desktop_apps: &[],
+        };
+
+        apply_edit_menus_activation(&mut state, entries, Some(1));
+        apply_edit_menus_activation(&mut state, entries, Some(0));
+        assert_eq!(
+            state.details_entry(),
+            Some((EditMenuTarget::Network, "Browser"))
+        );
+
+        let model = build_edit_menus_view_model(&state, entries, true, true);
+        assert_eq!(model.items[0], "Command: lynx");
+        assert_eq!(model.items[1], "Description: (none)");
+        assert_eq!(model.items[8], "Run In: PTY [toggle]");
+
+        let request = apply_edit_menus_activation(&mut state, entries, Some(0));
+        assert!(matches!(
+            request,
+            TerminalEditMenusRequest::OpenPromptEditDetail {
+                field: EditMenuDetailField::Command,
+                ref current,
+                ..
+            } if current == "lynx"
+        ));
+        let request = apply_edit_menus_activation(&mut state, entries, Some(9));
+        assert_eq!(
+            request,
+            TerminalEditMenusRequest::ToggleDetail {
+                target: EditMenuTarget::Network,
+                name: "Browser".to_string(),
+                field: EditMenuDetailField::RequiresNetwork,
+            }
+        );
+    }
+
+    #[test]
+    fn desktop_import_requests_scan_then_imports_selected_app() {
+        let mut state = TerminalEditMenusState {
+            view: EditMenusView::Games,
+            ..TerminalEditMenusState::default()
+        };
+        let apps = ["Nethack".to_string()];
+        let entries = EditMenusEntries {
+            applications: &[],
+            documents: &[],
+            network: &[],
+            games: &[],
+            details: None,
+            desktop_apps: &apps,
+        };
+
+        let request = apply_edit_menus_activation(&mut state, entries, Some(1));
+        assert_eq!(
+            request,
+            TerminalEditMenusRequest::ScanDesktopApps {
+                target: EditMenuTarget::Games
+            }
+        );
+        state.open_desktop_import(EditMenuTarget::Games);
+        let model = build_edit_menus_view_model(&state, entries, true, true);
+        assert_eq!(model.items[0], "Nethack");
+
+        let request = apply_edit_menus_activation(&mut state, entries, Some(0));
+        assert_eq!(
+            request,
+            TerminalEditMenusRequest::ImportDesktopApp {
+                target: EditMenuTarget::Games,
+                idx: 0
+            }
+        );
+    }
 }
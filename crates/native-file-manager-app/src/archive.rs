@@ -0,0 +1,719 @@
+//! Read-only browsing of zip and tar archives as virtual folders, plus
+//! extraction and compression. Everything is pure Rust.
+//!
+//! A path inside an archive is the archive's own path with the entry path
+//! appended, e.g. `/home/u/photos.zip/2024/beach.png`.
+
+use anyhow::{anyhow, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufReader, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    Zip,
+    Tar,
+    TarGz,
+    TarXz,
+}
+
+impl ArchiveKind {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".tar.xz") || name.ends_with(".txz") {
+            Some(Self::TarXz)
+        } else if name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+
+    /// File name without the archive extension(s).
+    pub fn strip_extension(self, name: &str) -> String {
+        let lower = name.to_ascii_lowercase();
+        let suffixes: &[&str] = match self {
+            Self::Zip => &[".zip"],
+            Self::Tar => &[".tar"],
+            Self::TarGz => &[".tar.gz", ".tgz"],
+            Self::TarXz => &[".tar.xz", ".txz"],
+        };
+        suffixes
+            .iter()
+            .find(|suffix| lower.ends_with(*suffix) && lower.len() > suffix.len())
+            .map(|suffix| name[..name.len() - suffix.len()].to_string())
+            .unwrap_or_else(|| name.to_string())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveEntry {
+    /// Path relative to the archive root.
+    pub path: PathBuf,
+    pub is_dir: bool,
+    pub size: u64,
+}
+
+type ArchiveIndex = Arc<Vec<ArchiveEntry>>;
+
+struct CachedIndex {
+    modified: Option<SystemTime>,
+    len: u64,
+    entries: ArchiveIndex,
+}
+
+fn index_cache() -> &'static Mutex<HashMap<PathBuf, CachedIndex>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, CachedIndex>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+pub fn is_archive_file(path: &Path) -> bool {
+    ArchiveKind::from_path(path).is_some() && path.is_file()
+}
+
+/// Splits a path into the archive file it passes through and the entry path
+/// inside it. The entry path is empty for the archive root.
+pub fn split_archive_path(path: &Path) -> Option<(PathBuf, PathBuf)> {
+    for archive in path.ancestors() {
+        if is_archive_file(archive) {
+            let inner = path.strip_prefix(archive).ok()?.to_path_buf();
+            return Some((archive.to_path_buf(), inner));
+        }
+        if archive.is_dir() {
+            return None;
+        }
+    }
+    None
+}
+
+/// True for paths that live inside an archive (not the archive file itself).
+pub fn is_inside_archive(path: &Path) -> bool {
+    split_archive_path(path).is_some_and(|(_, inner)| !inner.as_os_str().is_empty())
+}
+
+/// True for the archive root and any folder inside it.
+pub fn is_virtual_dir(path: &Path) -> bool {
+    let Some((archive, inner)) = split_archive_path(path) else {
+        return false;
+    };
+    inner.as_os_str().is_empty()
+        || list_entries(&archive)
+            .map(|entries| entries.iter().any(|e| e.is_dir && e.path == inner))
+            .unwrap_or(false)
+}
+
+/// All entries of an archive, including folders that are only implied by
+/// the paths of the files inside them. Listings are cached until the archive
+/// file changes on disk.
+pub fn list_entries(archive: &Path) -> Result<ArchiveIndex> {
+    let meta = std::fs::metadata(archive)
+        .map_err(|e| anyhow!("Failed reading {}: {e}", archive.display()))?;
+    let modified = meta.modified().ok();
+    if let Ok(cache) = index_cache().lock() {
+        if let Some(cached) = cache.get(archive) {
+            if cached.modified == modified && cached.len == meta.len() {
+                return Ok(cached.entries.clone());
+            }
+        }
+    }
+    let kind = ArchiveKind::from_path(archive)
+        .ok_or_else(|| anyhow!("{} is not a supported archive.", archive.display()))?;
+    let raw = match kind {
+        ArchiveKind::Zip => read_zip_index(archive)?,
+        _ => read_tar_index(archive, kind)?,
+    };
+    let mut by_path: BTreeMap<PathBuf, ArchiveEntry> = BTreeMap::new();
+    for entry in raw {
+        for parent in entry.path.ancestors().skip(1) {
+            if parent.as_os_str().is_empty() {
+                break;
+            }
+            by_path
+                .entry(parent.to_path_buf())
+                .or_insert_with(|| ArchiveEntry {
+                    path: parent.to_path_buf(),
+                    is_dir: true,
+                    size: 0,
+                });
+        }
+        by_path.insert(entry.path.clone(), entry);
+    }
+    let entries: ArchiveIndex = Arc::new(by_path.into_values().collect());
+    if let Ok(mut cache) = index_cache().lock() {
+        cache.insert(
+            archive.to_path_buf(),
+            CachedIndex {
+                modified,
+                len: meta.len(),
+                entries: entries.clone(),
+            },
+        );
+    }
+    Ok(entries)
+}
+
+/// Direct children of `inner` (empty for the archive root).
+pub fn children(archive: &Path, inner: &Path) -> Result<Vec<ArchiveEntry>> {
+    Ok(list_entries(archive)?
+        .iter()
+        .filter(|entry| entry.path.parent() == Some(inner))
+        .cloned()
+        .collect())
+}
+
+/// Writes the entry at `inner` (a file or a whole folder) to exactly `dst`.
+/// An empty `inner` extracts the whole archive into the folder `dst`.
+/// Returns the number of entries written.
+pub fn extract_entry(archive: &Path, inner: &Path, dst: &Path) -> Result<usize> {
+    extract_entry_with_progress(archive, inner, dst, &mut |_| true)
+}
+
+/// Like [`extract_entry`], reporting the bytes written as it goes. Returning
+/// `false` from `progress` stops the extraction with an error.
+pub fn extract_entry_with_progress(
+    archive: &Path,
+    inner: &Path,
+    dst: &Path,
+    progress: &mut dyn FnMut(u64) -> bool,
+) -> Result<usize> {
+    let kind = ArchiveKind::from_path(archive)
+        .ok_or_else(|| anyhow!("{} is not a supported archive.", archive.display()))?;
+    let is_dir = inner.as_os_str().is_empty()
+        || list_entries(archive)?
+            .iter()
+            .any(|entry| entry.is_dir && entry.path == inner);
+    if is_dir {
+        std::fs::create_dir_all(dst)
+            .map_err(|e| anyhow!("Failed creating {}: {e}", dst.display()))?;
+    }
+    let target_for = |path: &Path| -> Option<PathBuf> {
+        let rest = path.strip_prefix(inner).ok()?;
+        if rest.as_os_str().is_empty() {
+            Some(dst.to_path_buf())
+        } else {
+            Some(dst.join(rest))
+        }
+    };
+    let written = match kind {
+        ArchiveKind::Zip => extract_zip(archive, target_for, progress)?,
+        _ => extract_tar(archive, kind, target_for, progress)?,
+    };
+    if written == 0 && !is_dir {
+        return Err(anyhow!(
+            "{} was not found in {}.",
+            inner.display(),
+            archive.display()
+        ));
+    }
+    Ok(written)
+}
+
+/// Bytes and entries that extracting `path` writes: the whole archive for
+/// an archive file, or the entry and everything under it for a path inside
+/// one.
+pub fn extract_size(path: &Path) -> (u64, usize) {
+    let Some((archive, inner)) = split_archive_path(path) else {
+        return (0, 1);
+    };
+    let Ok(entries) = list_entries(&archive) else {
+        return (0, 1);
+    };
+    entries
+        .iter()
+        .filter(|entry| entry.path.starts_with(&inner))
+        .fold((0, 0), |(bytes, items), entry| {
+            (bytes + entry.size, items + 1)
+        })
+}
+
+/// Extracts a single file from an archive into a private temp folder so it
+/// can be opened with a regular viewer or editor.
+pub fn extract_for_viewing(path: &Path) -> Result<PathBuf> {
+    let (archive, inner) = split_archive_path(path)
+        .ok_or_else(|| anyhow!("{} is not in an archive.", path.display()))?;
+    let name = inner
+        .file_name()
+        .ok_or_else(|| anyhow!("Cannot open the archive root as a file."))?;
+    let stamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let dir = std::env::temp_dir().join(format!(
+        "robcos_archive_view_{}_{stamp}",
+        std::process::id()
+    ));
+    let dst = dir.join(name);
+    extract_entry(&archive, &inner, &dst)?;
+    Ok(dst)
+}
+
+/// Creates `dst` from `sources`. The format follows the extension of `dst`;
+/// each source is stored under its own file name.
+pub fn create_archive(dst: &Path, sources: &[PathBuf]) -> Result<usize> {
+    create_archive_with_progress(dst, sources, &mut |_| true)
+}
+
+/// Like [`create_archive`], reporting the source bytes read as it goes.
+/// Returning `false` from `progress` stops and removes the archive.
+pub fn create_archive_with_progress(
+    dst: &Path,
+    sources: &[PathBuf],
+    progress: &mut dyn FnMut(u64) -> bool,
+) -> Result<usize> {
+    let kind = ArchiveKind::from_path(dst)
+        .ok_or_else(|| anyhow!("Unsupported archive type. Use .zip, .tar, .tar.gz or .tar.xz."))?;
+    if dst.exists() {
+        return Err(anyhow!("Destination already exists: {}", dst.display()));
+    }
+    if sources.is_empty() {
+        return Err(anyhow!("Select a file or folder first."));
+    }
+    let result = match kind {
+        ArchiveKind::Zip => write_zip(dst, sources, progress),
+        ArchiveKind::Tar => {
+            let file = create_file(dst)?;
+            write_tar(file, sources, progress).map(|(_, count)| count)
+        }
+        ArchiveKind::TarGz => {
+            let encoder =
+                flate2::write::GzEncoder::new(create_file(dst)?, flate2::Compression::default());
+            write_tar(encoder, sources, progress).and_then(|(encoder, count)| {
+                encoder
+                    .finish()
+                    .map_err(|e| anyhow!("Failed writing {}: {e}", dst.display()))?;
+                Ok(count)
+            })
+        }
+        ArchiveKind::TarXz => {
+            let options = lzma_rust2::XzOptions::with_preset(6);
+            let encoder = lzma_rust2::XzWriter::new(create_file(dst)?, options)
+                .map_err(|e| anyhow!("Failed writing {}: {e}", dst.display()))?;
+            write_tar(encoder, sources, progress).and_then(|(encoder, count)| {
+                encoder
+                    .finish()
+                    .map_err(|e| anyhow!("Failed writing {}: {e}", dst.display()))?;
+                Ok(count)
+            })
+        }
+    };
+    if result.is_err() {
+        let _ = std::fs::remove_file(dst);
+    }
+    result
+}
+
+fn create_file(path: &Path) -> Result<File> {
+    File::create(path).map_err(|e| anyhow!("Failed creating {}: {e}", path.display()))
+}
+
+/// Entry paths must stay inside the extraction folder.
+fn sanitize_entry_path(path: &Path) -> Option<PathBuf> {
+    let mut clean = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => clean.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    (!clean.as_os_str().is_empty()).then_some(clean)
+}
+
+fn read_zip_index(archive: &Path) -> Result<Vec<ArchiveEntry>> {
+    let mut zip = open_zip(archive)?;
+    let mut entries = Vec::new();
+    for idx in 0..zip.len() {
+        let file = zip
+            .by_index_raw(idx)
+            .map_err(|e| anyhow!("Failed reading {}: {e}", archive.display()))?;
+        let Some(path) = file.enclosed_name().and_then(|p| sanitize_entry_path(&p)) else {
+            continue;
+        };
+        entries.push(ArchiveEntry {
+            path,
+            is_dir: file.is_dir(),
+            size: file.size(),
+        });
+    }
+    Ok(entries)
+}
+
+fn open_zip(archive: &Path) -> Result<zip::ZipArchive<BufReader<File>>> {
+    let file =
+        File::open(archive).map_err(|e| anyhow!("Failed opening {}: {e}", archive.display()))?;
+    zip::ZipArchive::new(BufReader::new(file))
+        .map_err(|e| anyhow!("Failed reading {}: {e}", archive.display()))
+}
+
+fn open_tar_stream(archive: &Path, kind: ArchiveKind) -> Result<Box<dyn Read>> {
+    let file =
+        File::open(archive).map_err(|e| anyhow!("Failed opening {}: {e}", archive.display()))?;
+    let reader = BufReader::new(file);
+    Ok(match kind {
+        ArchiveKind::TarGz => Box::new(flate2::read::GzDecoder::new(reader)),
+        ArchiveKind::TarXz => Box::new(lzma_rust2::XzReader::new(reader, true)),
+        _ => Box::new(reader),
+    })
+}
+
+fn read_tar_index(archive: &Path, kind: ArchiveKind) -> Result<Vec<ArchiveEntry>> {
+    let mut tar = tar::Archive::new(open_tar_stream(archive, kind)?);
+    let mut entries = Vec::new();
+    for entry in tar
+        .entries()
+        .map_err(|e| anyhow!("Failed reading {}: {e}", archive.display()))?
+    {
+        let entry = entry.map_err(|e| anyhow!("Failed reading {}: {e}", archive.display()))?;
+        let entry_type = entry.header().entry_type();
+        if !entry_type.is_file() && !entry_type.is_dir() {
+            continue;
+        }
+        let Some(path) = entry.path().ok().and_then(|p| sanitize_entry_path(&p)) else {
+            continue;
+        };
+        entries.push(ArchiveEntry {
+            path,
+            is_dir: entry_type.is_dir(),
+            size: entry.size(),
+        });
+    }
+    Ok(entries)
+}
+
+/// Reader that hands the size of every chunk to a progress callback and
+/// fails once the callback returns `false`.
+struct Tracked<'a, R> {
+    inner: R,
+    progress: &'a mut dyn FnMut(u64) -> bool,
+}
+
+impl<R: Read> Read for Tracked<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        if read > 0 && !(self.progress)(read as u64) {
+            return Err(std::io::Error::other("cancelled"));
+        }
+        Ok(read)
+    }
+}
+
+fn write_extracted_file(
+    reader: &mut dyn Read,
+    target: &Path,
+    mode: Option<u32>,
+    progress: &mut dyn FnMut(u64) -> bool,
+) -> Result<()> {
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| anyhow!("Failed creating {}: {e}", parent.display()))?;
+    }
+    let mut out = create_file(target)?;
+    let mut reader = Tracked {
+        inner: reader,
+        progress,
+    };
+    std::io::copy(&mut reader, &mut out)
+        .map_err(|e| anyhow!("Failed writing {}: {e}", target.display()))?;
+    #[cfg(unix)]
+    if let Some(mode) = mode {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(target, std::fs::Permissions::from_mode(mode & 0o777));
+    }
+    #[cfg(not(unix))]
+    let _ = mode;
+    Ok(())
+}
+
+fn extract_zip(
+    archive: &Path,
+    target_for: impl Fn(&Path) -> Option<PathBuf>,
+    progress: &mut dyn FnMut(u64) -> bool,
+) -> Result<usize> {
+    let mut zip = open_zip(archive)?;
+    let mut written = 0usize;
+    for idx in 0..zip.len() {
+        let mut file = zip
+            .by_index(idx)
+            .map_err(|e| anyhow!("Failed reading {}: {e}", archive.display()))?;
+        let Some(path) = file.enclosed_name().and_then(|p| sanitize_entry_path(&p)) else {
+            continue;
+        };
+        let Some(target) = target_for(&path) else {
+            continue;
+        };
+        if file.is_dir() {
+            std::fs::create_dir_all(&target)
+                .map_err(|e| anyhow!("Failed creating {}: {e}", target.display()))?;
+        } else {
+            let mode = file.unix_mode();
+            write_extracted_file(&mut file, &target, mode, progress)?;
+        }
+        written += 1;
+    }
+    Ok(written)
+}
+
+fn extract_tar(
+    archive: &Path,
+    kind: ArchiveKind,
+    target_for: impl Fn(&Path) -> Option<PathBuf>,
+    progress: &mut dyn FnMut(u64) -> bool,
+) -> Result<usize> {
+    let mut tar = tar::Archive::new(open_tar_stream(archive, kind)?);
+    let mut written = 0usize;
+    for entry in tar
+        .entries()
+        .map_err(|e| anyhow!("Failed reading {}: {e}", archive.display()))?
+    {
+        let mut entry = entry.map_err(|e| anyhow!("Failed reading {}: {e}", archive.display()))?;
+        let entry_type = entry.header().entry_type();
+        if !entry_type.is_file() && !entry_type.is_dir() {
+            continue;
+        }
+        let Some(path) = entry.path().ok().and_then(|p| sanitize_entry_path(&p)) else {
+            continue;
+        };
+        let Some(target) = target_for(&path) else {
+            continue;
+        };
+        if entry_type.is_dir() {
+            std::fs::create_dir_all(&target)
+                .map_err(|e| anyhow!("Failed creating {}: {e}", target.display()))?;
+        } else {
+            let mode = entry.header().mode().ok();
+            write_extracted_file(&mut entry, &target, mode, progress)?;
+        }
+        written += 1;
+    }
+    Ok(written)
+}
+
+/// Files and folders under each source, as (disk path, archive name) pairs.
+fn collect_sources(sources: &[PathBuf]) -> Result<Vec<(PathBuf, String, bool)>> {
+    fn walk(path: &Path, name: String, out: &mut Vec<(PathBuf, String, bool)>) -> Result<()> {
+        let meta = std::fs::symlink_metadata(path)
+            .map_err(|e| anyhow!("Failed reading {}: {e}", path.display()))?;
+        if meta.is_dir() {
+            out.push((path.to_path_buf(), name.clone(), true));
+            let mut children: Vec<_> = std::fs::read_dir(path)
+                .map_err(|e| anyhow!("Failed listing {}: {e}", path.display()))?
+                .flatten()
+                .collect();
+            children.sort_by_key(|child| child.file_name());
+            for child in children {
+                let child_name = format!("{name}/{}", child.file_name().to_string_lossy());
+                walk(&child.path(), child_name, out)?;
+            }
+        } else if meta.is_file() {
+            out.push((path.to_path_buf(), name, false));
+        }
+        Ok(())
+    }
+
+    let mut out = Vec::new();
+    for source in sources {
+        let name = source
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .ok_or_else(|| anyhow!("Cannot compress {}.", source.display()))?;
+        walk(source, name, &mut out)?;
+    }
+    Ok(out)
+}
+
+#[cfg(unix)]
+fn unix_mode(path: &Path) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path)
+        .ok()
+        .map(|meta| meta.permissions().mode())
+}
+
+#[cfg(not(unix))]
+fn unix_mode(_path: &Path) -> Option<u32> {
+    None
+}
+
+fn write_zip(
+    dst: &Path,
+    sources: &[PathBuf],
+    progress: &mut dyn FnMut(u64) -> bool,
+) -> Result<usize> {
+    let items = collect_sources(sources)?;
+    let mut zip = zip::ZipWriter::new(create_file(dst)?);
+    let base_options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    for (path, name, is_dir) in &items {
+        let options = match unix_mode(path) {
+            Some(mode) => base_options.unix_permissions(mode & 0o777),
+            None => base_options,
+        };
+        let written = if *is_dir {
+            zip.add_directory(format!("{name}/"), options)
+        } else {
+            zip.start_file(name.as_str(), options)
+        };
+        written.map_err(|e| anyhow!("Failed writing {}: {e}", dst.display()))?;
+        if !*is_dir {
+            let file =
+                File::open(path).map_err(|e| anyhow!("Failed reading {}: {e}", path.display()))?;
+            let mut input = Tracked {
+                inner: file,
+                progress: &mut *progress,
+            };
+            std::io::copy(&mut input, &mut zip)
+                .map_err(|e| anyhow!("Failed writing {}: {e}", dst.display()))?;
+        }
+    }
+    zip.finish()
+        .map_err(|e| anyhow!("Failed writing {}: {e}", dst.display()))?;
+    Ok(items.len())
+}
+
+fn write_tar<W: Write>(
+    writer: W,
+    sources: &[PathBuf],
+    progress: &mut dyn FnMut(u64) -> bool,
+) -> Result<(W, usize)> {
+    let items = collect_sources(sources)?;
+    let mut builder = tar::Builder::new(writer);
+    builder.follow_symlinks(false);
+    for (path, name, is_dir) in &items {
+        let added = if *is_dir {
+            builder.append_dir(name, path)
+        } else {
+            File::open(path).and_then(|file| {
+                let mut header = tar::Header::new_gnu();
+                header.set_metadata(&file.metadata()?);
+                let input = Tracked {
+                    inner: file,
+                    progress: &mut *progress,
+                };
+                builder.append_data(&mut header, name, input)
+            })
+        };
+        added.map_err(|e| anyhow!("Failed adding {}: {e}", path.display()))?;
+    }
+    let writer = builder
+        .into_inner()
+        .map_err(|e| anyhow!("Failed finishing archive: {e}"))?;
+    Ok((writer, items.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDirGuard;
+
+    fn seed_sources(root: &Path) -> Vec<PathBuf> {
+        let docs = root.join("docs");
+        std::fs::create_dir_all(docs.join("deep")).expect("create docs");
+        std::fs::write(docs.join("a.txt"), "alpha").expect("write a");
+        std::fs::write(docs.join("deep").join("b.txt"), "beta").expect("write b");
+        let readme = root.join("README");
+        std::fs::write(&readme, "read me").expect("write readme");
+        vec![docs, readme]
+    }
+
+    #[test]
+    fn archive_kind_matches_compound_extensions() {
+        assert_eq!(
+            ArchiveKind::from_path(Path::new("x.tar.gz")),
+            Some(ArchiveKind::TarGz)
+        );
+        assert_eq!(
+            ArchiveKind::from_path(Path::new("X.TXZ")),
+            Some(ArchiveKind::TarXz)
+        );
+        assert_eq!(ArchiveKind::from_path(Path::new("x.gz")), None);
+        assert_eq!(
+            ArchiveKind::TarGz.strip_extension("backup.tar.gz"),
+            "backup"
+        );
+        assert_eq!(sanitize_entry_path(Path::new("./a/../../etc/passwd")), None);
+    }
+
+    #[test]
+    fn every_format_round_trips_listing_and_extraction() {
+        let temp = TempDirGuard::new("archive_round_trip");
+        let root = temp.path.clone();
+        let sources = seed_sources(&root);
+        for name in ["out.zip", "out.tar", "out.tar.gz", "out.tar.xz"] {
+            let archive = root.join(name);
+            assert_eq!(create_archive(&archive, &sources).expect(name), 5);
+
+            let top: Vec<String> = children(&archive, Path::new(""))
+                .expect("list root")
+                .into_iter()
+                .map(|entry| entry.path.display().to_string())
+                .collect();
+            assert_eq!(top, vec!["README".to_string(), "docs".to_string()]);
+            assert!(is_virtual_dir(&archive.join("docs").join("deep")));
+            assert!(!is_virtual_dir(&archive.join("README")));
+            assert_eq!(
+                split_archive_path(&archive.join("docs/a.txt")),
+                Some((archive.clone(), PathBuf::from("docs/a.txt")))
+            );
+
+            let single = root.join(format!("{name}-b.txt"));
+            extract_entry(&archive, Path::new("docs/deep/b.txt"), &single).expect("extract file");
+            assert_eq!(std::fs::read_to_string(&single).expect("read b"), "beta");
+
+            let all = root.join(format!("{name}-all"));
+            extract_entry(&archive, Path::new(""), &all).expect("extract all");
+            assert_eq!(
+                std::fs::read_to_string(all.join("docs/a.txt")).expect("read a"),
+                "alpha"
+            );
+            assert_eq!(
+                std::fs::read_to_string(all.join("README")).expect("read readme"),
+                "read me"
+            );
+        }
+    }
+
+    #[test]
+    fn progress_counts_source_bytes_and_stopping_removes_the_archive() {
+        let temp = TempDirGuard::new("archive_progress");
+        let root = temp.path.clone();
+        let sources = seed_sources(&root);
+        for name in ["out.zip", "out.tar.xz"] {
+            let archive = root.join(name);
+            let stopped = create_archive_with_progress(&archive, &sources, &mut |_| false);
+            assert!(stopped.is_err());
+            assert!(!archive.exists());
+
+            let mut written = 0;
+            create_archive_with_progress(&archive, &sources, &mut |bytes| {
+                written += bytes;
+                true
+            })
+            .expect(name);
+            assert_eq!(written, 16);
+            assert_eq!(extract_size(&archive), (16, 5));
+
+            let mut extracted = 0;
+            let dst = root.join(format!("{name}-all"));
+            extract_entry_with_progress(&archive, Path::new(""), &dst, &mut |bytes| {
+                extracted += bytes;
+                true
+            })
+            .expect("extract all");
+            assert_eq!(extracted, 16);
+        }
+
+        let big = root.join("zeros.bin");
+        std::fs::write(&big, vec![0u8; 1 << 20]).expect("write zeros");
+        let archive = root.join("zeros.tar.xz");
+        create_archive(&archive, &[big]).expect("create xz");
+        let packed = std::fs::metadata(&archive).expect("stat xz").len();
+        assert!(packed < 64 * 1024, "xz output was {packed} bytes");
+    }
+}
@@ -0,0 +1,360 @@
+//! Pattern-based renaming of several entries at once.
+//!
+//! A [`BatchRenameRule`] is turned into a preview first so the dialog can
+//! show every new name and flag conflicts before anything touches the disk.
+
+use crate::FileEntryRow;
+use anyhow::{anyhow, Result};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local};
+use regex_lite::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenameCase {
+    #[default]
+    Keep,
+    Lower,
+    Upper,
+    Title,
+}
+
+impl RenameCase {
+    pub const ALL: [Self; 4] = [Self::Keep, Self::Lower, Self::Upper, Self::Title];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Keep => "Keep",
+            Self::Lower => "lower",
+            Self::Upper => "UPPER",
+            Self::Title => "Title",
+        }
+    }
+
+    fn apply(self, text: &str) -> String {
+        match self {
+            Self::Keep => text.to_string(),
+            Self::Lower => text.to_lowercase(),
+            Self::Upper => text.to_uppercase(),
+            Self::Title => {
+                let mut out = String::with_capacity(text.len());
+                let mut word_start = true;
+                for ch in text.chars() {
+                    if word_start {
+                        out.extend(ch.to_uppercase());
+                    } else {
+                        out.extend(ch.to_lowercase());
+                    }
+                    word_start = matches!(ch, ' ' | '_' | '-' | '.');
+                }
+                out
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchRenameRule {
+    pub find: String,
+    pub replace: String,
+    pub use_regex: bool,
+    /// Builds each new name. Tokens: `{name}` (the name after find/replace,
+    /// without extension), `{ext}`, `{n}` / `{n:03}` (counter, optionally
+    /// zero-padded), `{date}` / `{date:%Y%m%d}` and `{time}` from the
+    /// modification time.
+    pub template: String,
+    /// First value of `{n}`.
+    pub start: usize,
+    pub case: RenameCase,
+    pub change_extension: bool,
+    /// Replacement extension without the dot; empty removes it.
+    pub extension: String,
+}
+
+impl Default for BatchRenameRule {
+    fn default() -> Self {
+        Self {
+            find: String::new(),
+            replace: String::new(),
+            use_regex: false,
+            template: "{name}".to_string(),
+            start: 1,
+            case: RenameCase::Keep,
+            change_extension: false,
+            extension: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchRenamePreview {
+    pub from: PathBuf,
+    pub to: PathBuf,
+    pub old_name: String,
+    pub new_name: String,
+    pub conflict: Option<String>,
+}
+
+impl BatchRenamePreview {
+    pub fn changed(&self) -> bool {
+        self.from != self.to
+    }
+}
+
+/// Computes the new name of every entry, in order. Fails only when the rule
+/// itself is invalid (bad regex or date format); per-entry problems are
+/// reported through [`BatchRenamePreview::conflict`].
+pub fn preview_batch_rename(
+    entries: &[FileEntryRow],
+    rule: &BatchRenameRule,
+) -> Result<Vec<BatchRenamePreview>> {
+    let regex = if rule.use_regex && !rule.find.is_empty() {
+        Some(Regex::new(&rule.find).map_err(|e| anyhow!("Invalid pattern: {e}"))?)
+    } else {
+        None
+    };
+    let mut rows = Vec::with_capacity(entries.len());
+    for (idx, entry) in entries.iter().enumerate() {
+        let old_name = entry.label.clone();
+        let (stem, ext) = if entry.is_dir {
+            (old_name.as_str(), "")
+        } else {
+            match old_name.rsplit_once('.') {
+                Some((stem, ext)) if !stem.is_empty() => (stem, ext),
+                _ => (old_name.as_str(), ""),
+            }
+        };
+        let stem = match &regex {
+            Some(regex) => regex.replace_all(stem, rule.replace.as_str()).into_owned(),
+            None if !rule.find.is_empty() => stem.replace(&rule.find, &rule.replace),
+            None => stem.to_string(),
+        };
+        let modified: Option<DateTime<Local>> = std::fs::metadata(&entry.path)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .map(DateTime::from);
+        let stem = expand_template(&rule.template, &stem, ext, rule.start + idx, modified)?;
+        let stem = rule.case.apply(&stem);
+        let ext = if rule.change_extension && !entry.is_dir {
+            rule.extension.trim().trim_start_matches('.')
+        } else {
+            ext
+        };
+        let new_name = if ext.is_empty() {
+            stem
+        } else {
+            format!("{stem}.{ext}")
+        };
+        let parent = entry.path.parent().unwrap_or(Path::new(""));
+        rows.push(BatchRenamePreview {
+            from: entry.path.clone(),
+            to: parent.join(&new_name),
+            old_name,
+            new_name,
+            conflict: None,
+        });
+    }
+
+    let sources: HashSet<&Path> = rows.iter().map(|row| row.from.as_path()).collect();
+    let mut targets: HashMap<PathBuf, usize> = HashMap::new();
+    for row in &rows {
+        *targets.entry(row.to.clone()).or_default() += 1;
+    }
+    let conflicts: Vec<Option<String>> = rows
+        .iter()
+        .map(|row| {
+            let name = row.new_name.trim();
+            if name.is_empty() || name == "." || name == ".." {
+                Some("Empty name".to_string())
+            } else if row.new_name.contains('/') || row.new_name.contains('\\') {
+                Some("Contains a path separator".to_string())
+            } else if targets.get(&row.to).copied().unwrap_or(0) > 1 {
+                Some("Duplicate name".to_string())
+            } else if row.changed() && row.to.exists() && !sources.contains(row.to.as_path()) {
+                Some("Already exists".to_string())
+            } else {
+                None
+            }
+        })
+        .collect();
+    for (row, conflict) in rows.iter_mut().zip(conflicts) {
+        row.conflict = conflict;
+    }
+    Ok(rows)
+}
+
+fn expand_template(
+    template: &str,
+    name: &str,
+    ext: &str,
+    counter: usize,
+    modified: Option<DateTime<Local>>,
+) -> Result<String> {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find('}').map(|close| open + close) else {
+            out.push_str(&rest[open..]);
+            return Ok(out);
+        };
+        let token = &rest[open + 1..close];
+        let (key, spec) = match token.split_once(':') {
+            Some((key, spec)) => (key, Some(spec)),
+            None => (token, None),
+        };
+        match key {
+            "name" => out.push_str(name),
+            "ext" => out.push_str(ext),
+            "n" => {
+                let width = spec
+                    .and_then(|spec| spec.parse::<usize>().ok())
+                    .unwrap_or(0);
+                out.push_str(&format!("{counter:0width$}"));
+            }
+            "date" | "time" => {
+                let default = if key == "date" {
+                    "%Y-%m-%d"
+                } else {
+                    "%H-%M-%S"
+                };
+                let format = spec.unwrap_or(default);
+                if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+                    return Err(anyhow!("Invalid date format: {format}"));
+                }
+                if let Some(modified) = modified {
+                    out.push_str(&modified.format(format).to_string());
+                }
+            }
+            _ => out.push_str(&rest[open..=close]),
+        }
+        rest = &rest[close + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Performs the renames in two passes through temporary names, so swaps and
+/// chains like `a -> b, b -> c` work. Partially applied batches are rolled
+/// back on failure.
+pub fn apply_renames(renames: &[(PathBuf, PathBuf)]) -> Result<()> {
+    let staged: Vec<PathBuf> = renames
+        .iter()
+        .enumerate()
+        .map(|(idx, (from, _))| {
+            let name = from
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            from.with_file_name(format!(".{name}.renaming-{}-{idx}", std::process::id()))
+        })
+        .collect();
+    for (idx, ((from, _), temp)) in renames.iter().zip(&staged).enumerate() {
+        if let Err(err) = std::fs::rename(from, temp) {
+            for ((from, _), temp) in renames.iter().zip(&staged).take(idx) {
+                let _ = std::fs::rename(temp, from);
+            }
+            return Err(anyhow!("Failed renaming {}: {err}", from.display()));
+        }
+    }
+    for (idx, ((_, to), temp)) in renames.iter().zip(&staged).enumerate() {
+        let result = if to.exists() {
+            Err(anyhow!("Destination already exists: {}", to.display()))
+        } else {
+            std::fs::rename(temp, to)
+                .map_err(|err| anyhow!("Failed renaming to {}: {err}", to.display()))
+        };
+        if let Err(err) = result {
+            for ((_, to), temp) in renames.iter().zip(&staged).take(idx) {
+                let _ = std::fs::rename(to, temp);
+            }
+            for ((from, _), temp) in renames.iter().zip(&staged) {
+                let _ = std::fs::rename(temp, from);
+            }
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDirGuard;
+
+    fn row(dir: &Path, name: &str) -> FileEntryRow {
+        FileEntryRow {
+            path: dir.join(name),
+            label: name.to_string(),
+            is_dir: false,
+        }
+    }
+
+    #[test]
+    fn preview_applies_replace_template_case_and_extension() {
+        let dir = std::env::temp_dir().join("robcos_batch_rename_preview_nonexistent");
+        let entries = vec![
+            row(&dir, "Holiday photo.JPG"),
+            row(&dir, "holiday clip.jpg"),
+        ];
+        let rule = BatchRenameRule {
+            find: r"(?i)holiday\s+(\w+)".to_string(),
+            replace: "trip_$1".to_string(),
+            use_regex: true,
+            template: "{n:03}-{name}".to_string(),
+            start: 7,
+            case: RenameCase::Lower,
+            change_extension: true,
+            extension: ".jpeg".to_string(),
+        };
+        let preview = preview_batch_rename(&entries, &rule).unwrap();
+        let names: Vec<&str> = preview.iter().map(|row| row.new_name.as_str()).collect();
+        assert_eq!(names, vec!["007-trip_photo.jpeg", "008-trip_clip.jpeg"]);
+        assert!(preview.iter().all(|row| row.conflict.is_none()));
+
+        let bad = BatchRenameRule {
+            find: "(".to_string(),
+            use_regex: true,
+            ..BatchRenameRule::default()
+        };
+        assert!(preview_batch_rename(&entries, &bad).is_err());
+        assert_eq!(RenameCase::Title.apply("my big-file"), "My Big-File");
+    }
+
+    #[test]
+    fn preview_flags_duplicates_and_existing_targets_but_allows_swaps() {
+        let temp = TempDirGuard::new("batch_rename_conflicts");
+        let dir = temp.path.clone();
+        for name in ["a.txt", "b.txt", "other.txt"] {
+            std::fs::write(dir.join(name), name).unwrap();
+        }
+        let entries = vec![row(&dir, "a.txt"), row(&dir, "b.txt")];
+
+        let same = BatchRenameRule {
+            template: "same".to_string(),
+            ..BatchRenameRule::default()
+        };
+        let preview = preview_batch_rename(&entries, &same).unwrap();
+        assert!(preview
+            .iter()
+            .all(|row| row.conflict.as_deref() == Some("Duplicate name")));
+
+        let existing = BatchRenameRule {
+            find: "a".to_string(),
+            replace: "other".to_string(),
+            ..BatchRenameRule::default()
+        };
+        let preview = preview_batch_rename(&entries, &existing).unwrap();
+        assert_eq!(preview[0].conflict.as_deref(), Some("Already exists"));
+        assert_eq!(preview[1].conflict, None);
+
+        let swap = vec![
+            (dir.join("a.txt"), dir.join("b.txt")),
+            (dir.join("b.txt"), dir.join("a.txt")),
+        ];
+        apply_renames(&swap).unwrap();
+        assert_eq!(std::fs::read_to_string(dir.join("a.txt")).unwrap(), "b.txt");
+        assert_eq!(std::fs::read_to_string(dir.join("b.txt")).unwrap(), "a.txt");
+    }
+}
@@ -0,0 +1,745 @@
+//! Checksums and duplicate finding.
+//!
+//! A [`ChecksumJob`] hashes files on a worker thread: it computes SHA-256
+//! or BLAKE3 sums for a selection, verifies the files listed in a
+//! `.sha256sum` / `.b3sum` file, or finds duplicate files under a folder.
+//! Duplicates are grouped by size first, so only files that share a size
+//! with another file are read at all.
+
+use crate::{format_bytes_human, path_display_name, unique_path_in_dir};
+use anyhow::{anyhow, Result};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+const HASH_CHUNK_BYTES: usize = 256 * 1024;
+const HASH_HEX_LEN: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChecksumAlgorithm {
+    #[default]
+    Sha256,
+    Blake3,
+}
+
+impl ChecksumAlgorithm {
+    pub const ALL: [Self; 2] = [Self::Sha256, Self::Blake3];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Sha256 => "SHA-256",
+            Self::Blake3 => "BLAKE3",
+        }
+    }
+
+    /// Extension of the sum files written for this algorithm, as used by
+    /// `sha256sum` and `b3sum`.
+    pub fn sum_extension(self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha256sum",
+            Self::Blake3 => "b3sum",
+        }
+    }
+
+    /// The algorithm a sum file was written with, judging by its extension.
+    pub fn for_sum_file(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "sha256sum" | "sha256" => Some(Self::Sha256),
+            "b3sum" | "blake3" => Some(Self::Blake3),
+            _ => None,
+        }
+    }
+}
+
+pub fn is_checksum_file(path: &Path) -> bool {
+    ChecksumAlgorithm::for_sum_file(path).is_some()
+}
+
+enum Hashing {
+    Sha256(Sha256),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl Hashing {
+    fn new(algorithm: ChecksumAlgorithm) -> Self {
+        match algorithm {
+            ChecksumAlgorithm::Sha256 => Self::Sha256(Sha256::new()),
+            ChecksumAlgorithm::Blake3 => Self::Blake3(Box::default()),
+        }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        match self {
+            Self::Sha256(hasher) => hasher.update(bytes),
+            Self::Blake3(hasher) => {
+                hasher.update(bytes);
+            }
+        }
+    }
+
+    fn finish_hex(self) -> String {
+        let bytes: Vec<u8> = match self {
+            Self::Sha256(hasher) => hasher.finalize().to_vec(),
+            Self::Blake3(hasher) => hasher.finalize().as_bytes().to_vec(),
+        };
+        bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+}
+
+/// Hashes a file, reporting each chunk's length to `on_bytes`.
+pub fn hash_file(
+    path: &Path,
+    algorithm: ChecksumAlgorithm,
+    cancelled: &AtomicBool,
+    on_bytes: &mut dyn FnMut(u64),
+) -> Result<String> {
+    let mut file =
+        std::fs::File::open(path).map_err(|e| anyhow!("Cannot read {}: {e}", path.display()))?;
+    let mut hashing = Hashing::new(algorithm);
+    let mut buf = vec![0; HASH_CHUNK_BYTES];
+    loop {
+        if cancelled.load(Ordering::Relaxed) {
+            return Err(anyhow!("Cancelled."));
+        }
+        let read = file
+            .read(&mut buf)
+            .map_err(|e| anyhow!("Cannot read {}: {e}", path.display()))?;
+        if read == 0 {
+            break;
+        }
+        hashing.update(&buf[..read]);
+        on_bytes(read as u64);
+    }
+    Ok(hashing.finish_hex())
+}
+
+/// One `<hash>  <name>` line of a sum file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SumEntry {
+    pub hash: String,
+    pub name: String,
+}
+
+/// Reads the lines `sha256sum` and `b3sum` write: the hash, a space, then
+/// a space (text mode) or `*` (binary mode) before the name. Comments and
+/// lines that are not sums are skipped.
+pub fn parse_sum_file(text: &str) -> Vec<SumEntry> {
+    text.lines()
+        .filter_map(|line| {
+            let line = line.trim_end_matches('\r');
+            if line.starts_with('#') {
+                return None;
+            }
+            let (hash, rest) = line.split_once(' ')?;
+            let name = rest.strip_prefix([' ', '*']).unwrap_or(rest);
+            let valid = hash.len() == HASH_HEX_LEN && hash.bytes().all(|b| b.is_ascii_hexdigit());
+            (valid && !name.is_empty()).then(|| SumEntry {
+                hash: hash.to_ascii_lowercase(),
+                name: name.to_string(),
+            })
+        })
+        .collect()
+}
+
+pub fn format_sum_line(hash: &str, name: &str) -> String {
+    format!("{hash}  {name}\n")
+}
+
+/// Writes the successful sums to a new sum file in `dir`, named after the
+/// file when there is only one. Paths inside `dir` are written relative to
+/// it so the sums can be checked from there.
+pub fn write_sum_file(
+    dir: &Path,
+    algorithm: ChecksumAlgorithm,
+    sums: &[FileChecksum],
+) -> Result<PathBuf> {
+    let ok: Vec<(&PathBuf, &String)> = sums
+        .iter()
+        .filter_map(|sum| Some((&sum.path, sum.hash.as_ref().ok()?)))
+        .collect();
+    let base = match ok.as_slice() {
+        [] => return Err(anyhow!("There are no checksums to save.")),
+        [(path, _)] => path_display_name(path),
+        _ => "checksums".to_string(),
+    };
+    let mut text = String::new();
+    for (path, hash) in &ok {
+        let name = path.strip_prefix(dir).unwrap_or(path);
+        text.push_str(&format_sum_line(hash, &name.to_string_lossy()));
+    }
+    let target = unique_path_in_dir(dir, &format!("{base}.{}", algorithm.sum_extension()));
+    std::fs::write(&target, text).map_err(|e| anyhow!("Cannot write {}: {e}", target.display()))?;
+    Ok(target)
+}
+
+/// What a [`ChecksumJob`] does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChecksumTask {
+    Compute {
+        paths: Vec<PathBuf>,
+        algorithm: ChecksumAlgorithm,
+    },
+    Verify {
+        sum_file: PathBuf,
+    },
+    FindDuplicates {
+        root: PathBuf,
+        include_hidden: bool,
+        algorithm: ChecksumAlgorithm,
+    },
+}
+
+impl ChecksumTask {
+    pub fn title(&self) -> String {
+        match self {
+            Self::Compute { paths, algorithm } => match paths.as_slice() {
+                [path] => format!("{} of {}", algorithm.label(), path_display_name(path)),
+                paths => format!("{} of {} files", algorithm.label(), paths.len()),
+            },
+            Self::Verify { sum_file } => format!("Verify {}", path_display_name(sum_file)),
+            Self::FindDuplicates { root, .. } => {
+                format!("Duplicates in {}", path_display_name(root))
+            }
+        }
+    }
+
+    pub fn algorithm(&self) -> ChecksumAlgorithm {
+        match self {
+            Self::Compute { algorithm, .. } | Self::FindDuplicates { algorithm, .. } => *algorithm,
+            Self::Verify { sum_file } => {
+                ChecksumAlgorithm::for_sum_file(sum_file).unwrap_or_default()
+            }
+        }
+    }
+
+    /// The same task using another algorithm. Verifying always uses the
+    /// sum file's own algorithm.
+    pub fn with_algorithm(&self, algorithm: ChecksumAlgorithm) -> Self {
+        let mut task = self.clone();
+        match &mut task {
+            Self::Compute { algorithm: a, .. } | Self::FindDuplicates { algorithm: a, .. } => {
+                *a = algorithm;
+            }
+            Self::Verify { .. } => {}
+        }
+        task
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChecksum {
+    pub path: PathBuf,
+    /// Lowercase hex digest, or why the file could not be read.
+    pub hash: std::result::Result<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyStatus {
+    Ok,
+    Mismatch,
+    Missing,
+    Unreadable(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyResult {
+    pub path: PathBuf,
+    pub status: VerifyStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateGroup {
+    pub size: u64,
+    pub hash: String,
+    /// Shallowest path first; that copy is the one suggested to keep.
+    pub paths: Vec<PathBuf>,
+}
+
+impl DuplicateGroup {
+    /// Bytes freed by keeping just one copy.
+    pub fn wasted(&self) -> u64 {
+        self.size * self.paths.len().saturating_sub(1) as u64
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChecksumResults {
+    Sums(Vec<FileChecksum>),
+    Verified(Vec<VerifyResult>),
+    Duplicates(Vec<DuplicateGroup>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChecksumPhase {
+    /// Walking the folder for duplicate candidates.
+    Scanning,
+    #[default]
+    Hashing,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ChecksumProgress {
+    pub phase: ChecksumPhase,
+    pub files_done: usize,
+    pub files_total: usize,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    pub current: Option<PathBuf>,
+    pub done: bool,
+}
+
+impl ChecksumProgress {
+    pub fn fraction(&self) -> f32 {
+        if self.done {
+            1.0
+        } else if self.bytes_total > 0 {
+            (self.bytes_done as f64 / self.bytes_total as f64) as f32
+        } else {
+            0.0
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ChecksumShared {
+    progress: ChecksumProgress,
+    results: ChecksumResults,
+}
+
+/// A running or finished checksum task. Dropping it stops the work.
+#[derive(Debug)]
+pub struct ChecksumJob {
+    task: ChecksumTask,
+    shared: Arc<Mutex<ChecksumShared>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl ChecksumJob {
+    pub fn start(task: ChecksumTask) -> Result<Self> {
+        let results = match &task {
+            ChecksumTask::Compute { paths, .. } => {
+                if paths.is_empty() {
+                    return Err(anyhow!("Select files to checksum."));
+                }
+                ChecksumResults::Sums(Vec::new())
+            }
+            ChecksumTask::Verify { sum_file } => {
+                if ChecksumAlgorithm::for_sum_file(sum_file).is_none() {
+                    return Err(anyhow!(
+                        "{} is not a .sha256sum or .b3sum file.",
+                        path_display_name(sum_file)
+                    ));
+                }
+                ChecksumResults::Verified(Vec::new())
+            }
+            ChecksumTask::FindDuplicates { root, .. } => {
+                if !root.is_dir() {
+                    return Err(anyhow!("Folder not found: {}", root.display()));
+                }
+                ChecksumResults::Duplicates(Vec::new())
+            }
+        };
+        let sum_text = match &task {
+            ChecksumTask::Verify { sum_file } => Some(
+                std::fs::read_to_string(sum_file)
+                    .map_err(|e| anyhow!("Cannot read {}: {e}", sum_file.display()))?,
+            ),
+            _ => None,
+        };
+        let job = Self {
+            task,
+            shared: Arc::new(Mutex::new(ChecksumShared {
+                progress: ChecksumProgress::default(),
+                results,
+            })),
+            cancelled: Arc::default(),
+        };
+        let task = job.task.clone();
+        let shared = Arc::clone(&job.shared);
+        let cancelled = Arc::clone(&job.cancelled);
+        std::thread::spawn(move || {
+            let results = match task {
+                ChecksumTask::Compute { paths, algorithm } => {
+                    ChecksumResults::Sums(compute(&paths, algorithm, &shared, &cancelled))
+                }
+                ChecksumTask::Verify { sum_file } => {
+                    let entries = parse_sum_file(sum_text.as_deref().unwrap_or_default());
+                    let algorithm = ChecksumAlgorithm::for_sum_file(&sum_file).unwrap_or_default();
+                    let dir = sum_file.parent().unwrap_or(Path::new("/"));
+                    ChecksumResults::Verified(verify(dir, &entries, algorithm, &shared, &cancelled))
+                }
+                ChecksumTask::FindDuplicates {
+                    root,
+                    include_hidden,
+                    algorithm,
+                } => ChecksumResults::Duplicates(find_duplicates(
+                    &root,
+                    include_hidden,
+                    algorithm,
+                    &shared,
+                    &cancelled,
+                )),
+            };
+            if let Ok(mut shared) = shared.lock() {
+                shared.results = results;
+                shared.progress.current = None;
+                shared.progress.done = true;
+            }
+        });
+        Ok(job)
+    }
+
+    pub fn task(&self) -> &ChecksumTask {
+        &self.task
+    }
+
+    pub fn progress(&self) -> ChecksumProgress {
+        self.shared
+            .lock()
+            .map(|shared| shared.progress.clone())
+            .unwrap_or_default()
+    }
+
+    /// Results are published when the job ends.
+    pub fn results(&self) -> Option<ChecksumResults> {
+        let shared = self.shared.lock().ok()?;
+        shared.progress.done.then(|| shared.results.clone())
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.shared.lock().is_ok_and(|shared| !shared.progress.done)
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn was_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// One-line progress, e.g. "Hashing 3 of 10 files (12.0 MB of 40.0 MB)".
+    pub fn summary(&self) -> String {
+        let Ok(shared) = self.shared.lock() else {
+            return String::new();
+        };
+        let progress = &shared.progress;
+        if !progress.done {
+            return match progress.phase {
+                ChecksumPhase::Scanning => {
+                    format!("Scanning... {} files found", progress.files_done)
+                }
+                ChecksumPhase::Hashing => format!(
+                    "Hashing {} of {} files ({} of {})",
+                    (progress.files_done + 1).min(progress.files_total),
+                    progress.files_total,
+                    format_bytes_human(progress.bytes_done),
+                    format_bytes_human(progress.bytes_total),
+                ),
+            };
+        }
+        let stopped = if self.was_cancelled() {
+            " - stopped"
+        } else {
+            ""
+        };
+        let text = match &shared.results {
+            ChecksumResults::Sums(sums) => {
+                let failed = sums.iter().filter(|sum| sum.hash.is_err()).count();
+                match failed {
+                    0 => format!("{} files hashed", sums.len()),
+                    failed => format!("{} files hashed, {failed} unreadable", sums.len() - failed),
+                }
+            }
+            ChecksumResults::Verified(results) => {
+                let ok = results
+                    .iter()
+                    .filter(|result| result.status == VerifyStatus::Ok)
+                    .count();
+                format!("{ok} OK, {} failed", results.len() - ok)
+            }
+            ChecksumResults::Duplicates(groups) => {
+                let wasted: u64 = groups.iter().map(DuplicateGroup::wasted).sum();
+                format!(
+                    "{} duplicate groups, {} reclaimable",
+                    groups.len(),
+                    format_bytes_human(wasted)
+                )
+            }
+        };
+        format!("{text}{stopped}")
+    }
+}
+
+impl Drop for ChecksumJob {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+fn update_progress(shared: &Mutex<ChecksumShared>, apply: impl FnOnce(&mut ChecksumProgress)) {
+    if let Ok(mut shared) = shared.lock() {
+        apply(&mut shared.progress);
+    }
+}
+
+/// Hashes each file in turn, keeping the byte counters current.
+fn hash_tracked(
+    path: &Path,
+    algorithm: ChecksumAlgorithm,
+    shared: &Mutex<ChecksumShared>,
+    cancelled: &AtomicBool,
+) -> std::result::Result<String, String> {
+    update_progress(shared, |progress| {
+        progress.current = Some(path.to_path_buf())
+    });
+    let hash = hash_file(path, algorithm, cancelled, &mut |bytes| {
+        update_progress(shared, |progress| progress.bytes_done += bytes);
+    })
+    .map_err(|e| e.to_string());
+    update_progress(shared, |progress| progress.files_done += 1);
+    hash
+}
+
+fn file_len(path: &Path) -> u64 {
+    std::fs::metadata(path).map(|meta| meta.len()).unwrap_or(0)
+}
+
+fn compute(
+    paths: &[PathBuf],
+    algorithm: ChecksumAlgorithm,
+    shared: &Mutex<ChecksumShared>,
+    cancelled: &AtomicBool,
+) -> Vec<FileChecksum> {
+    let total: u64 = paths.iter().map(|path| file_len(path)).sum();
+    update_progress(shared, |progress| {
+        progress.files_total = paths.len();
+        progress.bytes_total = total;
+    });
+    let mut sums = Vec::new();
+    for path in paths {
+        if cancelled.load(Ordering::Relaxed) {
+            break;
+        }
+        sums.push(FileChecksum {
+            path: path.clone(),
+            hash: hash_tracked(path, algorithm, shared, cancelled),
+        });
+    }
+    sums
+}
+
+fn verify(
+    dir: &Path,
+    entries: &[SumEntry],
+    algorithm: ChecksumAlgorithm,
+    shared: &Mutex<ChecksumShared>,
+    cancelled: &AtomicBool,
+) -> Vec<VerifyResult> {
+    let paths: Vec<PathBuf> = entries.iter().map(|entry| dir.join(&entry.name)).collect();
+    let total: u64 = paths.iter().map(|path| file_len(path)).sum();
+    update_progress(shared, |progress| {
+        progress.files_total = paths.len();
+        progress.bytes_total = total;
+    });
+    let mut results = Vec::new();
+    for (entry, path) in entries.iter().zip(paths) {
+        if cancelled.load(Ordering::Relaxed) {
+            break;
+        }
+        let status = if !path.is_file() {
+            update_progress(shared, |progress| progress.files_done += 1);
+            VerifyStatus::Missing
+        } else {
+            match hash_tracked(&path, algorithm, shared, cancelled) {
+                Ok(hash) if hash == entry.hash => VerifyStatus::Ok,
+                Ok(_) => VerifyStatus::Mismatch,
+                Err(error) => VerifyStatus::Unreadable(error),
+            }
+        };
+        results.push(VerifyResult { path, status });
+    }
+    results
+}
+
+fn find_duplicates(
+    root: &Path,
+    include_hidden: bool,
+    algorithm: ChecksumAlgorithm,
+    shared: &Mutex<ChecksumShared>,
+    cancelled: &AtomicBool,
+) -> Vec<DuplicateGroup> {
+    update_progress(shared, |progress| progress.phase = ChecksumPhase::Scanning);
+    let mut by_size: BTreeMap<u64, Vec<PathBuf>> = BTreeMap::new();
+    let mut pending_dirs = vec![root.to_path_buf()];
+    while let Some(dir) = pending_dirs.pop() {
+        let Ok(read_dir) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut found = 0;
+        for entry in read_dir.flatten() {
+            if cancelled.load(Ordering::Relaxed) {
+                return Vec::new();
+            }
+            if !include_hidden && entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            // Symlinks are skipped: following them would loop or report
+            // the link and its target as copies of each other.
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                pending_dirs.push(entry.path());
+            } else if file_type.is_file() {
+                let size = entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+                // Empty files are all "identical" and not worth reporting.
+                if size > 0 {
+                    by_size.entry(size).or_default().push(entry.path());
+                    found += 1;
+                }
+            }
+        }
+        update_progress(shared, |progress| progress.files_done += found);
+    }
+
+    let candidates: Vec<(u64, Vec<PathBuf>)> = by_size
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .collect();
+    update_progress(shared, |progress| {
+        progress.phase = ChecksumPhase::Hashing;
+        progress.files_done = 0;
+        progress.files_total = candidates.iter().map(|(_, paths)| paths.len()).sum();
+        progress.bytes_total = candidates
+            .iter()
+            .map(|(size, paths)| size * paths.len() as u64)
+            .sum();
+    });
+
+    let mut groups = Vec::new();
+    for (size, paths) in candidates {
+        let mut by_hash: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+        for path in paths {
+            if cancelled.load(Ordering::Relaxed) {
+                return Vec::new();
+            }
+            if let Ok(hash) = hash_tracked(&path, algorithm, shared, cancelled) {
+                by_hash.entry(hash).or_default().push(path);
+            }
+        }
+        for (hash, mut paths) in by_hash {
+            if paths.len() > 1 {
+                paths.sort_by(|a, b| (a.components().count(), a).cmp(&(b.components().count(), b)));
+                groups.push(DuplicateGroup { size, hash, paths });
+            }
+        }
+    }
+    groups.sort_by(|a, b| {
+        b.wasted()
+            .cmp(&a.wasted())
+            .then_with(|| a.paths.cmp(&b.paths))
+    });
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDirGuard;
+    use std::time::{Duration, Instant};
+
+    fn finish(task: ChecksumTask) -> ChecksumResults {
+        let job = ChecksumJob::start(task).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            if let Some(results) = job.results() {
+                return results;
+            }
+            assert!(Instant::now() < deadline, "checksum job did not finish");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn sums_round_trip_through_a_sum_file_and_catch_changes() {
+        let temp = TempDirGuard::new("checksum_verify");
+        let dir = temp.path.clone();
+        std::fs::write(dir.join("abc.txt"), "abc").unwrap();
+        std::fs::write(dir.join("notes.txt"), "notes").unwrap();
+
+        let ChecksumResults::Sums(sums) = finish(ChecksumTask::Compute {
+            paths: vec![dir.join("abc.txt"), dir.join("notes.txt")],
+            algorithm: ChecksumAlgorithm::Sha256,
+        }) else {
+            panic!("expected sums");
+        };
+        assert_eq!(
+            sums[0].hash.as_deref(),
+            Ok("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+        let mut blake3 = Hashing::new(ChecksumAlgorithm::Blake3);
+        blake3.update(b"abc");
+        assert_eq!(
+            blake3.finish_hex(),
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+        let sum_file = write_sum_file(&dir, ChecksumAlgorithm::Sha256, &sums).unwrap();
+        assert_eq!(sum_file, dir.join("checksums.sha256sum"));
+        let text = std::fs::read_to_string(&sum_file).unwrap();
+        assert!(text.starts_with("ba7816bf"));
+        assert!(text.contains("  abc.txt\n"));
+
+        std::fs::write(dir.join("notes.txt"), "changed").unwrap();
+        std::fs::remove_file(dir.join("abc.txt")).unwrap();
+        let ChecksumResults::Verified(results) = finish(ChecksumTask::Verify { sum_file }) else {
+            panic!("expected verification");
+        };
+        let statuses: Vec<VerifyStatus> = results.into_iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            vec![VerifyStatus::Missing, VerifyStatus::Mismatch]
+        );
+    }
+
+    #[test]
+    fn parse_sum_file_accepts_text_and_binary_markers() {
+        let hash = "a".repeat(64);
+        let text = format!("# comment\n{hash}  one.txt\n{hash} *two words.bin\r\nnot a sum\n");
+        let entries = parse_sum_file(&text);
+        let names: Vec<&str> = entries.iter().map(|entry| entry.name.as_str()).collect();
+        assert_eq!(names, vec!["one.txt", "two words.bin"]);
+    }
+
+    #[test]
+    fn duplicates_are_grouped_by_size_then_hash() {
+        let temp = TempDirGuard::new("checksum_dupes");
+        let dir = temp.path.clone();
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+        std::fs::write(dir.join("photo.jpg"), "same bytes").unwrap();
+        std::fs::write(dir.join("nested/photo copy.jpg"), "same bytes").unwrap();
+        // Same size, different contents.
+        std::fs::write(dir.join("other.jpg"), "diff bytes").unwrap();
+        std::fs::write(dir.join("empty-a"), "").unwrap();
+        std::fs::write(dir.join("empty-b"), "").unwrap();
+        std::fs::write(dir.join(".hidden.jpg"), "same bytes").unwrap();
+
+        let ChecksumResults::Duplicates(groups) = finish(ChecksumTask::FindDuplicates {
+            root: dir.clone(),
+            include_hidden: false,
+            algorithm: ChecksumAlgorithm::Blake3,
+        }) else {
+            panic!("expected duplicates");
+        };
+        assert_eq!(groups.len(), 1);
+        assert_eq!(
+            groups[0].paths,
+            vec![dir.join("photo.jpg"), dir.join("nested/photo copy.jpg")]
+        );
+        assert_eq!(groups[0].wasted(), 10);
+    }
+}
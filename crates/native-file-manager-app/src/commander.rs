@@ -0,0 +1,190 @@
+//! Two-pane "commander" layout.
+//!
+//! The focused pane is always the caller's regular [`NativeFileManagerState`],
+//! so every single-pane command keeps working on it unchanged. The other
+//! pane is kept here, and switching panes swaps the two states.
+
+use crate::transfers::TransferRequest;
+use crate::{ensure_dir_writable, ensure_entries_writable, NativeFileManagerState};
+use anyhow::{anyhow, Result};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CommanderSide {
+    #[default]
+    Left,
+    Right,
+}
+
+impl CommanderSide {
+    pub fn other(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Left => "Left",
+            Self::Right => "Right",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommanderCommand {
+    CopyToOtherPane,
+    MoveToOtherPane,
+    SwitchPane,
+}
+
+#[derive(Debug, Clone)]
+pub struct CommanderPanes {
+    pub inactive: NativeFileManagerState,
+    /// Which side the focused pane is drawn on.
+    pub active_side: CommanderSide,
+}
+
+impl CommanderPanes {
+    pub fn new(cwd: PathBuf) -> Self {
+        Self {
+            inactive: NativeFileManagerState::new(cwd),
+            active_side: CommanderSide::Left,
+        }
+    }
+
+    pub fn switch(&mut self, active: &mut NativeFileManagerState) {
+        std::mem::swap(active, &mut self.inactive);
+        // The window stays open whichever pane has focus.
+        std::mem::swap(&mut active.open, &mut self.inactive.open);
+        self.active_side = self.active_side.other();
+    }
+
+    /// The two panes in screen order, left first.
+    pub fn sides<'a>(
+        &'a self,
+        active: &'a NativeFileManagerState,
+    ) -> [(CommanderSide, &'a NativeFileManagerState); 2] {
+        match self.active_side {
+            CommanderSide::Left => [
+                (CommanderSide::Left, active),
+                (CommanderSide::Right, &self.inactive),
+            ],
+            CommanderSide::Right => [
+                (CommanderSide::Left, &self.inactive),
+                (CommanderSide::Right, active),
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommanderOutcome {
+    Status(String),
+    /// A copy or move for the background transfer queue.
+    Transfer(TransferRequest),
+}
+
+/// Runs a pane-to-pane command. Copies and moves become transfer jobs, so
+/// they report progress and land on the shared undo history when done.
+pub fn run_commander_command(
+    command: CommanderCommand,
+    active: &mut NativeFileManagerState,
+    panes: &mut CommanderPanes,
+) -> Result<CommanderOutcome> {
+    if command == CommanderCommand::SwitchPane {
+        panes.switch(active);
+        return Ok(CommanderOutcome::Status(format!(
+            "{} pane",
+            panes.active_side.label()
+        )));
+    }
+    let entries = active.selected_rows_for_action();
+    if entries.is_empty() {
+        return Err(anyhow!("Nothing selected."));
+    }
+    let target = panes.inactive.cwd.clone();
+    if target == active.cwd {
+        return Err(anyhow!("Both panes show the same folder."));
+    }
+    ensure_dir_writable(&target)?;
+    let paths = entries.iter().map(|row| row.path.clone()).collect();
+    Ok(CommanderOutcome::Transfer(match command {
+        CommanderCommand::CopyToOtherPane => TransferRequest::copy(paths, target),
+        CommanderCommand::MoveToOtherPane => {
+            ensure_entries_writable(&entries)?;
+            TransferRequest::move_to(paths, target)
+        }
+        CommanderCommand::SwitchPane => unreachable!(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDirGuard;
+    use crate::transfers::TransferQueue;
+    use crate::trash::Trash;
+    use crate::FileManagerEditRuntime;
+
+    #[test]
+    fn copy_and_move_target_the_other_pane_and_share_undo() {
+        let temp = TempDirGuard::new("commander");
+        let root = temp.path.clone();
+        let left_dir = root.join("left");
+        let right_dir = root.join("right");
+        std::fs::create_dir_all(&left_dir).unwrap();
+        std::fs::create_dir_all(&right_dir).unwrap();
+        std::fs::write(left_dir.join("a.txt"), "a").unwrap();
+        std::fs::write(left_dir.join("b.txt"), "b").unwrap();
+
+        let mut active = NativeFileManagerState::new(left_dir.clone());
+        active.open = true;
+        let mut panes = CommanderPanes::new(right_dir.clone());
+        let mut runtime = FileManagerEditRuntime::default();
+        let mut queue = TransferQueue::new(Trash::at(root.join("trash")));
+        let mut run = |command, active: &mut NativeFileManagerState, panes: &mut CommanderPanes| {
+            let CommanderOutcome::Transfer(request) =
+                run_commander_command(command, active, panes).unwrap()
+            else {
+                panic!("expected a transfer");
+            };
+            assert_eq!(request.target_dir, right_dir);
+            queue.enqueue(request);
+            let outcome = loop {
+                if let Some(outcome) = queue.poll() {
+                    break outcome;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(5));
+            };
+            runtime.finish_transfer(active, outcome, queue.trash())
+        };
+
+        active.select(Some(left_dir.join("a.txt")));
+        let status = run(CommanderCommand::CopyToOtherPane, &mut active, &mut panes);
+        assert_eq!(status, "Copied a.txt");
+        assert!(left_dir.join("a.txt").exists());
+        assert!(right_dir.join("a.txt").exists());
+
+        active.select(Some(left_dir.join("b.txt")));
+        run(CommanderCommand::MoveToOtherPane, &mut active, &mut panes);
+        assert!(!left_dir.join("b.txt").exists());
+        assert!(right_dir.join("b.txt").exists());
+
+        runtime.undo(&mut active).unwrap();
+        assert!(left_dir.join("b.txt").exists());
+        runtime.undo(&mut active).unwrap();
+        assert!(!right_dir.join("a.txt").exists());
+
+        assert_eq!(
+            run_commander_command(CommanderCommand::SwitchPane, &mut active, &mut panes).unwrap(),
+            CommanderOutcome::Status("Right pane".to_string())
+        );
+        assert_eq!(active.cwd, right_dir);
+        assert!(active.open);
+        assert!(!panes.inactive.open);
+        assert_eq!(panes.active_side, CommanderSide::Right);
+        assert_eq!(panes.sides(&active)[0].1.cwd, left_dir);
+    }
+}
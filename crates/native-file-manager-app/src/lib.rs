@@ -1,15 +1,33 @@
 use anyhow::{anyhow, Result};
+use batch_rename::{apply_renames, preview_batch_rename, BatchRenameRule};
 use robcos_native_services::shared_file_manager_settings::{
-    open_with_default_for_extension, open_with_history_for_extension,
+    open_with_default_for_extension, open_with_history_for_extension, open_with_override_for_path,
 };
 use robcos_shared::config::{
-    base_dir, get_settings, DesktopFileManagerSettings, FileManagerSortMode, FileManagerViewMode,
+    get_settings, DesktopFileManagerSettings, FileManagerSortMode, FileManagerViewMode,
 };
 use robcos_shared::default_apps::parse_custom_command_line;
 use robcos_shared::launcher::command_exists;
+use search::FileSearch;
 use std::cmp::Ordering;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use transfers::{TransferKind, TransferOutcome, TransferRequest};
+use trash::Trash;
+
+pub mod archive;
+pub mod batch_rename;
+pub mod checksum;
+pub mod commander;
+pub mod places;
+pub mod preview;
+pub mod properties;
+pub mod search;
+#[cfg(test)]
+mod test_support;
+pub mod transfers;
+pub mod trash;
+pub mod watch;
 
 pub const FILE_MANAGER_OPEN_WITH_NO_EXT_KEY: &str = "__no_ext__";
 
@@ -78,11 +96,31 @@ pub enum FileManagerCommand {
     Paste,
     Duplicate,
     Rename,
+    BatchRename,
     Move,
     Delete,
+    Compress,
+    Extract,
+    ExtractAll,
+    OpenTrash,
+    RestoreFromTrash,
+    EmptyTrash,
     Undo,
     Redo,
     ToggleTreePanel,
+    TogglePreviewPanel,
+    ToggleDualPane,
+    CopyToOtherPane,
+    MoveToOtherPane,
+    SwitchPane,
+    ShowTransfers,
+    FindFiles,
+    StopSearch,
+    RevealInFolder,
+    Properties,
+    Checksums,
+    FindDuplicates,
+    AddBookmark,
     ToggleHiddenFiles,
     SetViewMode(FileManagerViewMode),
     SetSortMode(FileManagerSortMode),
@@ -98,6 +136,11 @@ pub struct NativeFileManagerState {
     pub active_tab: usize,
     pub tree_selected: Option<PathBuf>,
     pub search_query: String,
+    /// Recursive search shown instead of the folder listing.
+    pub search: Option<FileSearch>,
+    /// Row index of `selected` when it was last seen, so the selection
+    /// stays in place when the entry disappears underneath it.
+    selected_index: Option<usize>,
 }
 
 impl NativeFileManagerState {
@@ -111,6 +154,8 @@ impl NativeFileManagerState {
             active_tab: 0,
             tree_selected: Some(cwd),
             search_query: String::new(),
+            search: None,
+            selected_index: None,
         }
     }
 
@@ -132,7 +177,9 @@ impl NativeFileManagerState {
 
     pub fn set_cwd(&mut self, path: PathBuf) {
         self.cwd = path.clone();
+        self.restart_saved_search();
         self.selected = None;
+        self.selected_index = None;
         self.selected_paths.clear();
         self.tree_selected = Some(path);
         self.sync_active_tab_path();
@@ -141,6 +188,7 @@ impl NativeFileManagerState {
 
     pub fn select(&mut self, path: Option<PathBuf>) {
         self.selected = path;
+        self.selected_index = None;
         self.selected_paths.clear();
         self.ensure_selection_valid();
     }
@@ -188,6 +236,7 @@ impl NativeFileManagerState {
 
     pub fn update_search_query(&mut self, query: String) {
         self.search_query = query;
+        self.selected_index = None;
         self.ensure_selection_valid();
     }
 
@@ -195,9 +244,98 @@ impl NativeFileManagerState {
         self.update_search_query(String::new());
     }
 
+    /// Starts a recursive search and shows its results in place of the
+    /// folder listing until the folder changes.
+    pub fn start_search(&mut self, spec: search::SearchSpec) -> Result<()> {
+        let search = FileSearch::start(spec)?;
+        self.set_cwd(search.spec().root.clone());
+        self.search = Some(search);
+        self.ensure_selection_valid();
+        Ok(())
+    }
+
+    /// Stops a running search and keeps what it found so far.
+    pub fn stop_search(&mut self) -> bool {
+        match &self.search {
+            Some(search) if search.is_running() => {
+                search.cancel();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn is_search_view(&self) -> bool {
+        self.search.is_some()
+    }
+
+    /// Leaves the search results and selects the chosen item in its folder.
+    pub fn reveal_selected(&mut self) -> Result<String> {
+        let Some(row) = self.selected_row().filter(|row| !row.is_parent_dir()) else {
+            return Err(anyhow!("Select a file or folder first."));
+        };
+        let Some(parent) = row.path.parent() else {
+            return Err(anyhow!("Cannot reveal this item."));
+        };
+        self.set_cwd(parent.to_path_buf());
+        self.select(Some(row.path.clone()));
+        Ok(format!("Showing {} in {}", row.label, parent.display()))
+    }
+
+    /// The folder Add Bookmark pins: a single selected folder (or saved
+    /// search), otherwise the folder being shown.
+    pub fn bookmark_target(&self) -> PathBuf {
+        match self.selected_rows_for_action().as_slice() {
+            [row] if row.is_dir || search::is_saved_search(&row.path) => row.path.clone(),
+            _ => self.cwd.clone(),
+        }
+    }
+
+    /// Saved searches open like folders: entering one starts it, and any
+    /// other folder change drops the previous search.
+    fn restart_saved_search(&mut self) {
+        if let Some(search) = self.search.take() {
+            search.cancel();
+        }
+        if search::is_saved_search(&self.cwd) {
+            self.search = search::load_saved_search(&self.cwd)
+                .and_then(FileSearch::start)
+                .ok();
+        }
+    }
+
+    fn search_rows(&self, search: &FileSearch) -> Vec<FileEntryRow> {
+        let settings = get_settings().desktop_file_manager;
+        // ".." leaves the results: a saved search goes back to the folder
+        // holding it, an ad hoc search to the folder it searched.
+        let exit = if search::is_saved_search(&self.cwd) {
+            self.cwd.parent().map(Path::to_path_buf)
+        } else {
+            Some(self.cwd.clone())
+        };
+        let mut rows: Vec<FileEntryRow> = exit
+            .map(|path| FileEntryRow {
+                path,
+                label: "..".to_string(),
+                is_dir: true,
+            })
+            .into_iter()
+            .collect();
+        rows.extend(search.results().into_iter().filter(|row| {
+            // Results moved or deleted since they were found drop out.
+            row.path.symlink_metadata().is_ok()
+                && (settings.show_hidden_files || !row.label.starts_with('.'))
+        }));
+        sort_rows(&mut rows, &settings);
+        rows
+    }
+
     pub fn rows(&self) -> Vec<FileEntryRow> {
         let q = self.search_query.trim().to_ascii_lowercase();
-        let rows = Self::read_rows(&self.cwd);
+        let rows = match &self.search {
+            Some(search) => self.search_rows(search),
+            None => Self::read_rows(&self.cwd),
+        };
         if q.is_empty() {
             rows
         } else {
@@ -209,21 +347,11 @@ impl NativeFileManagerState {
 
     pub fn tree_items(&self) -> Vec<FileTreeItem> {
         let show_hidden = get_settings().desktop_file_manager.show_hidden_files;
-        let mut items = vec![FileTreeItem {
-            line: "Drives".to_string(),
-            path: None,
-        }];
         let drives = Self::drive_roots();
-        for drive in &drives {
-            items.push(FileTreeItem {
-                line: format!("* {}", Self::drive_label(drive)),
-                path: Some(drive.clone()),
-            });
-        }
-        items.push(FileTreeItem {
+        let mut items = vec![FileTreeItem {
             line: "Folders".to_string(),
             path: None,
-        });
+        }];
         let current_drive = Self::current_drive_root_for_path(&self.cwd, &drives)
             .unwrap_or_else(|| PathBuf::from("/"));
         let rel = self.cwd.strip_prefix(&current_drive).unwrap_or(&self.cwd);
@@ -276,6 +404,18 @@ impl NativeFileManagerState {
         items
     }
 
+    pub fn open_trash(&mut self) -> Result<()> {
+        let trash = Trash::user();
+        trash.ensure_dirs()?;
+        self.set_cwd(trash.files_dir());
+        Ok(())
+    }
+
+    /// True while the Trash folder is being browsed.
+    pub fn is_trash_view(&self) -> bool {
+        Trash::user().is_view(&self.cwd)
+    }
+
     pub fn open_selected_tree_path(&mut self, path: PathBuf) {
         self.tree_selected = Some(path.clone());
         self.set_cwd(path);
@@ -295,6 +435,7 @@ impl NativeFileManagerState {
             self.active_tab = self.tabs.len().saturating_sub(1);
         }
         self.cwd = self.tabs[self.active_tab].clone();
+        self.restart_saved_search();
         self.selected = None;
         self.tree_selected = Some(self.cwd.clone());
         true
@@ -306,6 +447,7 @@ impl NativeFileManagerState {
         }
         self.active_tab = idx;
         self.cwd = self.tabs[idx].clone();
+        self.restart_saved_search();
         self.selected = None;
         self.selected_paths.clear();
         self.tree_selected = Some(self.cwd.clone());
@@ -355,30 +497,71 @@ impl NativeFileManagerState {
         });
         if rows.is_empty() {
             self.selected = None;
+            self.selected_index = None;
             return;
         }
-        if self
+        if let Some(index) = self
             .selected
             .as_ref()
-            .is_some_and(|selected| rows.iter().any(|row| &row.path == selected))
+            .and_then(|selected| rows.iter().position(|row| &row.path == selected))
         {
+            self.selected_index = Some(index);
+            return;
+        }
+        // A selected entry that vanished (deleted here or elsewhere) hands
+        // the selection to whatever now sits in its place.
+        let index = self
+            .selected_index
+            .filter(|_| self.selected.is_some())
+            .unwrap_or(0)
+            .min(rows.len() - 1);
+        self.selected = Some(rows[index].path.clone());
+        self.selected_index = Some(index);
+    }
+
+    /// Catches up with folders that changed on disk behind our back. A
+    /// shown folder that was removed gives way to its nearest surviving
+    /// parent; otherwise the selection keeps its place.
+    pub fn refresh_changed_dirs(&mut self, changed: &[PathBuf]) {
+        let affected = |path: &PathBuf| changed.contains(path) && !path.exists();
+        for tab in &mut self.tabs {
+            if affected(tab) {
+                *tab = nearest_existing_dir(tab);
+            }
+        }
+        if self.is_search_view() || !changed.contains(&self.cwd) {
             return;
         }
-        self.selected = rows.first().map(|row| row.path.clone());
+        if affected(&self.cwd) {
+            self.set_cwd(nearest_existing_dir(&self.cwd));
+        } else {
+            self.ensure_selection_valid();
+        }
     }
 
     pub fn activate_selected(&mut self) -> FileManagerAction {
         let Some(path) = self.selected.clone() else {
             return FileManagerAction::None;
         };
-        if path.is_dir() {
+        if path.is_dir() || archive::is_virtual_dir(&path) || search::is_saved_search(&path) {
             self.set_cwd(path);
             FileManagerAction::ChangedDir
+        } else if archive::is_inside_archive(&path) {
+            // Entries are opened from a temporary copy; the archive stays untouched.
+            match archive::extract_for_viewing(&path) {
+                Ok(copy) => FileManagerAction::OpenFile(copy),
+                Err(_) => FileManagerAction::None,
+            }
         } else {
             FileManagerAction::OpenFile(path)
         }
     }
 
+    /// True while browsing the contents of an archive.
+    pub fn is_archive_view(&self) -> bool {
+        archive::split_archive_path(&self.cwd).is_some()
+    }
+
     fn read_rows(path: &Path) -> Vec<FileEntryRow> {
         let settings = get_settings().desktop_file_manager;
         let mut rows = Vec::new();
@@ -389,57 +572,38 @@ impl NativeFileManagerState {
                 is_dir: true,
             });
         }
-        let read_dir = match std::fs::read_dir(path) {
-            Ok(rd) => rd,
-            Err(_) => return rows,
-        };
-        for entry in read_dir.flatten() {
-            let entry_path = entry.path();
-            let label = entry.file_name().to_string_lossy().to_string();
-            if !settings.show_hidden_files && label.starts_with('.') {
-                continue;
-            }
-            rows.push(FileEntryRow {
-                path: entry_path.clone(),
-                label,
-                is_dir: entry_path.is_dir(),
-            });
-        }
-
-        rows.sort_by(|a, b| {
-            if a.is_parent_dir() {
-                return Ordering::Less;
-            }
-            if b.is_parent_dir() {
-                return Ordering::Greater;
-            }
-            if settings.directories_first {
-                match (a.is_dir, b.is_dir) {
-                    (true, false) => return Ordering::Less,
-                    (false, true) => return Ordering::Greater,
-                    _ => {}
+        if let Some((archive_path, inner)) = archive::split_archive_path(path) {
+            for entry in archive::children(&archive_path, &inner).unwrap_or_default() {
+                let label = path_display_name(&entry.path);
+                if !settings.show_hidden_files && label.starts_with('.') {
+                    continue;
                 }
+                rows.push(FileEntryRow {
+                    path: path.join(&label),
+                    label,
+                    is_dir: entry.is_dir,
+                });
             }
-            match settings.sort_mode {
-                FileManagerSortMode::Name => a.label.to_lowercase().cmp(&b.label.to_lowercase()),
-                FileManagerSortMode::Type => {
-                    let a_ext = Path::new(&a.label)
-                        .extension()
-                        .and_then(|s| s.to_str())
-                        .unwrap_or("")
-                        .to_ascii_lowercase();
-                    let b_ext = Path::new(&b.label)
-                        .extension()
-                        .and_then(|s| s.to_str())
-                        .unwrap_or("")
-                        .to_ascii_lowercase();
-                    a_ext
-                        .cmp(&b_ext)
-                        .then_with(|| a.label.to_lowercase().cmp(&b.label.to_lowercase()))
+        } else {
+            let read_dir = match std::fs::read_dir(path) {
+                Ok(rd) => rd,
+                Err(_) => return rows,
+            };
+            for entry in read_dir.flatten() {
+                let entry_path = entry.path();
+                let label = entry.file_name().to_string_lossy().to_string();
+                if !settings.show_hidden_files && label.starts_with('.') {
+                    continue;
                 }
+                rows.push(FileEntryRow {
+                    path: entry_path.clone(),
+                    label,
+                    is_dir: entry_path.is_dir(),
+                });
             }
-        });
+        }
 
+        sort_rows(&mut rows, &settings);
         rows
     }
 
@@ -519,6 +683,44 @@ impl NativeFileManagerState {
     }
 }
 
+/// Orders a listing: ".." first, then folders if configured, then by the
+/// sort mode.
+fn sort_rows(rows: &mut [FileEntryRow], settings: &DesktopFileManagerSettings) {
+    rows.sort_by(|a, b| {
+        if a.is_parent_dir() {
+            return Ordering::Less;
+        }
+        if b.is_parent_dir() {
+            return Ordering::Greater;
+        }
+        if settings.directories_first {
+            match (a.is_dir, b.is_dir) {
+                (true, false) => return Ordering::Less,
+                (false, true) => return Ordering::Greater,
+                _ => {}
+            }
+        }
+        match settings.sort_mode {
+            FileManagerSortMode::Name => a.label.to_lowercase().cmp(&b.label.to_lowercase()),
+            FileManagerSortMode::Type => {
+                let a_ext = Path::new(&a.label)
+                    .extension()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("")
+                    .to_ascii_lowercase();
+                let b_ext = Path::new(&b.label)
+                    .extension()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("")
+                    .to_ascii_lowercase();
+                a_ext
+                    .cmp(&b_ext)
+                    .then_with(|| a.label.to_lowercase().cmp(&b.label.to_lowercase()))
+            }
+        }
+    });
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FileManagerClipboardMode {
     Copy,
@@ -538,8 +740,35 @@ pub struct NativeFileManagerDragPayload {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FileManagerEditOp {
-    CopyCreated { src: PathBuf, dst: PathBuf },
-    Moved { from: PathBuf, to: PathBuf },
+    CopyCreated {
+        src: PathBuf,
+        dst: PathBuf,
+    },
+    Moved {
+        from: PathBuf,
+        to: PathBuf,
+    },
+    Trashed {
+        trash_root: PathBuf,
+        original: PathBuf,
+        name: String,
+    },
+    Extracted {
+        archive: PathBuf,
+        dst: PathBuf,
+    },
+    ArchiveCreated {
+        sources: Vec<PathBuf>,
+        dst: PathBuf,
+    },
+    BatchRenamed {
+        renames: Vec<(PathBuf, PathBuf)>,
+    },
+    /// Several operations undone and redone as one step, e.g. everything a
+    /// background transfer did.
+    Group {
+        ops: Vec<FileManagerEditOp>,
+    },
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -660,11 +889,9 @@ pub fn default_open_with_launch_for_path(
     path: &Path,
     fm: &DesktopFileManagerSettings,
 ) -> Option<Result<OpenWithLaunchRequest>> {
-    let state = open_with_state_for_path(path, fm);
-    state
-        .current_default
-        .as_deref()
-        .map(|command| prepare_open_with_launch(path, command))
+    open_with_override_for_path(fm, &path.to_string_lossy())
+        .or_else(|| open_with_state_for_path(path, fm).current_default)
+        .map(|command| prepare_open_with_launch(path, &command))
 }
 
 pub fn selection_activation_for_selected_path(
@@ -746,6 +973,16 @@ pub fn commit_picker_selection(
     }
 }
 
+/// Suggested file name for "Compress": the item's own name for a single
+/// selection, "Archive" otherwise, made unique in `dir`.
+pub fn default_archive_name(dir: &Path, entries: &[FileEntryRow]) -> String {
+    let base = match entries {
+        [entry] => entry.label.clone(),
+        _ => "Archive".to_string(),
+    };
+    path_display_name(&unique_path_in_dir(dir, &format!("{base}.zip")))
+}
+
 pub fn selected_file(mut entries: Vec<FileEntryRow>) -> Option<FileEntryRow> {
     if entries.len() != 1 {
         return None;
@@ -777,6 +1014,9 @@ impl FileManagerEditRuntime {
         if entries.is_empty() {
             return Err(anyhow!("Select a file or folder first."));
         }
+        if mode == FileManagerClipboardMode::Cut {
+            ensure_entries_writable(entries)?;
+        }
         self.clipboard = Some(FileManagerClipboardItem {
             paths: entries.iter().map(|entry| entry.path.clone()).collect(),
             mode: mode.clone(),
@@ -796,6 +1036,7 @@ impl FileManagerEditRuntime {
         &mut self,
         file_manager: &mut NativeFileManagerState,
     ) -> Result<String> {
+        ensure_cwd_writable(file_manager)?;
         let dst = unique_path_in_dir(&file_manager.cwd, "New Folder");
         std::fs::create_dir_all(&dst)
             .map_err(|e| anyhow!("Failed creating {}: {e}", dst.display()))?;
@@ -811,6 +1052,7 @@ impl FileManagerEditRuntime {
         if entries.is_empty() {
             return Err(anyhow!("Select a file or folder first."));
         }
+        ensure_entries_writable(&entries)?;
         let mut created = Vec::new();
         for entry in entries {
             let Some(parent) = entry.path.parent() else {
@@ -842,6 +1084,7 @@ impl FileManagerEditRuntime {
         entry: FileEntryRow,
         new_name: String,
     ) -> Result<String> {
+        ensure_entries_writable(std::slice::from_ref(&entry))?;
         let Some(parent) = entry.path.parent() else {
             return Err(anyhow!("Cannot rename this item."));
         };
@@ -868,12 +1111,44 @@ impl FileManagerEditRuntime {
         Ok(format!("Renamed to {}", path_display_name(&dst)))
     }
 
+    pub fn batch_rename_selected(
+        &mut self,
+        file_manager: &mut NativeFileManagerState,
+        entries: Vec<FileEntryRow>,
+        rule: &BatchRenameRule,
+    ) -> Result<String> {
+        ensure_entries_writable(&entries)?;
+        let preview = preview_batch_rename(&entries, rule)?;
+        if let Some(row) = preview.iter().find(|row| row.conflict.is_some()) {
+            return Err(anyhow!(
+                "{} -> {}: {}",
+                row.old_name,
+                row.new_name,
+                row.conflict.as_deref().unwrap_or_default()
+            ));
+        }
+        let renames: Vec<(PathBuf, PathBuf)> = preview
+            .into_iter()
+            .filter(|row| row.changed())
+            .map(|row| (row.from, row.to))
+            .collect();
+        if renames.is_empty() {
+            return Ok("Names unchanged.".to_string());
+        }
+        apply_renames(&renames)?;
+        let count = renames.len();
+        file_manager.select(renames.first().map(|(_, to)| to.clone()));
+        self.record_edit_op(FileManagerEditOp::BatchRenamed { renames });
+        Ok(format!("Renamed {count} item(s)"))
+    }
+
     pub fn move_selected(
         &mut self,
         file_manager: &mut NativeFileManagerState,
         entry: FileEntryRow,
         raw_destination: String,
     ) -> Result<String> {
+        ensure_entries_writable(std::slice::from_ref(&entry))?;
         let mut dst = PathBuf::from(raw_destination.trim());
         if dst.as_os_str().is_empty() {
             return Err(anyhow!("Destination cannot be empty."));
@@ -881,6 +1156,9 @@ impl FileManagerEditRuntime {
         if dst.is_relative() {
             dst = file_manager.cwd.join(dst);
         }
+        if archive::split_archive_path(&dst).is_some_and(|(archive, _)| archive != dst) {
+            return Err(anyhow!(ARCHIVE_READ_ONLY));
+        }
         if dst.exists() && dst.is_dir() {
             dst = dst.join(path_display_name(&entry.path));
         }
@@ -899,160 +1177,270 @@ impl FileManagerEditRuntime {
         Ok(format!("Moved to {}", dst.display()))
     }
 
-    pub fn move_paths_to_dir(
-        &mut self,
-        file_manager: &mut NativeFileManagerState,
-        paths: Vec<PathBuf>,
-        target_dir: &Path,
-    ) -> Result<String> {
-        if !target_dir.is_dir() {
-            return Err(anyhow!("Destination folder does not exist."));
-        }
-        let mut seen = HashSet::new();
-        let mut moved = Vec::new();
-        let target_dir = target_dir.to_path_buf();
-        for src in paths {
-            if !seen.insert(src.clone()) || !src.exists() {
-                continue;
-            }
-            if !can_move_path_to_dir(&src, &target_dir) {
-                continue;
-            }
-            let source_name = path_display_name(&src);
-            let mut dst = target_dir.join(&source_name);
-            if dst.exists() {
-                dst = unique_path_in_dir(&target_dir, &source_name);
-            }
-            if dst == src {
-                continue;
-            }
-            move_path(&src, &dst)?;
-            self.record_edit_op(FileManagerEditOp::Moved {
-                from: src,
-                to: dst.clone(),
-            });
-            moved.push(dst);
-        }
-        if moved.is_empty() {
-            return Err(anyhow!("Nothing to move."));
-        }
-        if target_dir == file_manager.cwd {
-            if let Some(last) = moved.last().cloned() {
-                file_manager.select(Some(last));
-            }
-        } else {
-            file_manager.ensure_selection_valid();
-        }
-        if moved.len() == 1 {
-            Ok(format!("Moved {}", path_display_name(&moved[0])))
-        } else {
-            Ok(format!("Moved {} items", moved.len()))
-        }
-    }
-
     pub fn drop_allowed(paths: &[PathBuf], target_dir: &Path) -> bool {
-        paths
-            .iter()
-            .any(|src| src.exists() && can_move_path_to_dir(src, target_dir))
+        ensure_dir_writable(target_dir).is_ok()
+            && paths
+                .iter()
+                .any(|src| src.exists() && can_move_path_to_dir(src, target_dir))
     }
 
-    pub fn paste_clipboard(&mut self, file_manager: &mut NativeFileManagerState) -> Result<String> {
+    /// Pastes the clipboard into the current folder as a background copy or
+    /// move. A cut clipboard is consumed right away, like a finished paste.
+    pub fn paste_request(
+        &mut self,
+        file_manager: &NativeFileManagerState,
+    ) -> Result<TransferRequest> {
         let Some(clipboard) = self.clipboard.clone() else {
             return Err(anyhow!("Clipboard is empty."));
         };
+        ensure_cwd_writable(file_manager)?;
         let target_dir = file_manager.cwd.clone();
-        let mut changed = 0usize;
-        let mut last_dst: Option<PathBuf> = None;
-
-        match clipboard.mode {
-            FileManagerClipboardMode::Copy => {
-                for src in clipboard.paths {
-                    if !src.exists() {
-                        continue;
-                    }
-                    let source_name = path_display_name(&src);
-                    let mut dst = target_dir.join(&source_name);
-                    if dst.exists() {
-                        dst = unique_copy_path_in_dir(&target_dir, &source_name, false);
-                    }
-                    copy_path_recursive(&src, &dst)?;
-                    self.record_edit_op(FileManagerEditOp::CopyCreated {
-                        src,
-                        dst: dst.clone(),
-                    });
-                    changed += 1;
-                    last_dst = Some(dst);
-                }
-            }
+        let sources: Vec<PathBuf> = clipboard
+            .paths
+            .into_iter()
+            .filter(|src| src.exists() || archive::is_inside_archive(src))
+            .collect();
+        if sources.is_empty() {
+            return Err(anyhow!("Clipboard source no longer exists."));
+        }
+        Ok(match clipboard.mode {
+            FileManagerClipboardMode::Copy => TransferRequest::copy(sources, target_dir),
             FileManagerClipboardMode::Cut => {
-                for src in clipboard.paths {
-                    if !src.exists() {
-                        continue;
-                    }
-                    let source_name = path_display_name(&src);
-                    let source_parent = src.parent().map(Path::to_path_buf);
-                    if source_parent.as_deref() == Some(target_dir.as_path()) {
-                        continue;
-                    }
-                    let mut dst = target_dir.join(&source_name);
-                    if dst.exists() {
-                        dst = unique_path_in_dir(&target_dir, &source_name);
-                    }
-                    move_path(&src, &dst)?;
-                    self.record_edit_op(FileManagerEditOp::Moved {
-                        from: src,
-                        to: dst.clone(),
-                    });
-                    changed += 1;
-                    last_dst = Some(dst);
-                }
                 self.clipboard = None;
+                TransferRequest::move_to(sources, target_dir)
             }
+        })
+    }
+
+    /// Moves `entries` to the trash as a background job. The trash size
+    /// limit is applied by the job once it is done.
+    pub fn trash_request(entries: &[FileEntryRow]) -> Result<TransferRequest> {
+        if entries.is_empty() {
+            return Err(anyhow!("Select a file or folder first."));
         }
+        ensure_entries_writable(entries)?;
+        Ok(TransferRequest::trash(
+            entries.iter().map(|entry| entry.path.clone()).collect(),
+        ))
+    }
 
-        if changed == 0 {
-            return Err(anyhow!("Clipboard source no longer exists."));
+    /// Records a finished background job as one undo step and describes it.
+    /// Cancelled and failed jobs are recorded too: their ops cover only the
+    /// items that completed, so undo reverses exactly the partial work.
+    pub fn finish_transfer(
+        &mut self,
+        file_manager: &mut NativeFileManagerState,
+        outcome: TransferOutcome,
+        trash: &Trash,
+    ) -> String {
+        let mut last = None;
+        let mut count = 0usize;
+        for op in &outcome.ops {
+            match op {
+                FileManagerEditOp::Trashed { .. } if outcome.kind == TransferKind::Trash => {
+                    count += 1;
+                }
+                FileManagerEditOp::CopyCreated { dst: path, .. }
+                | FileManagerEditOp::Moved { to: path, .. }
+                | FileManagerEditOp::Extracted { dst: path, .. }
+                | FileManagerEditOp::ArchiveCreated { dst: path, .. } => {
+                    count += 1;
+                    last = Some(path.clone());
+                }
+                _ => {}
+            }
         }
-        if let Some(dst) = last_dst {
-            file_manager.select(Some(dst.clone()));
-            if changed == 1 {
-                Ok(format!("Pasted {}", path_display_name(&dst)))
-            } else {
-                Ok(format!("Pasted {changed} items"))
+        let mut ops = outcome.ops;
+        match ops.len() {
+            0 => {}
+            1 => self.record_edit_op(ops.remove(0)),
+            _ => self.record_edit_op(FileManagerEditOp::Group { ops }),
+        }
+        let purged = outcome.purged;
+        if purged > 0 {
+            self.drop_stale_trash_ops(trash);
+        }
+        match &last {
+            Some(last) if last.parent() == Some(file_manager.cwd.as_path()) => {
+                file_manager.select(Some(last.clone()))
             }
+            _ => file_manager.ensure_selection_valid(),
+        }
+
+        let (verb, suffix) = match outcome.kind {
+            TransferKind::Copy => ("Copied", ""),
+            TransferKind::Move => ("Moved", ""),
+            TransferKind::Trash => ("Moved", " to trash"),
+            TransferKind::Extract => ("Extracted", ""),
+            TransferKind::Compress => ("Created", ""),
+        };
+        let mut status = match (count, &last) {
+            (0, _) => format!("Nothing {}{suffix}", verb.to_ascii_lowercase()),
+            (1, Some(path)) => format!("{verb} {}{suffix}", path_display_name(path)),
+            (1, None) => format!("{verb} item{suffix}"),
+            (n, _) => format!("{verb} {n} items{suffix}"),
+        };
+        if outcome.skipped > 0 {
+            status.push_str(&format!(", skipped {}", outcome.skipped));
+        }
+        if purged > 0 {
+            status.push_str(&format!(" (purged {purged} old item(s) over size limit)"));
+        }
+        if let Some(err) = outcome.error {
+            format!("File action failed: {err} ({status})")
+        } else if outcome.cancelled {
+            format!("Cancelled ({status})")
         } else {
-            Err(anyhow!("Clipboard source no longer exists."))
+            status
         }
     }
 
-    pub fn delete_selected(
+    /// Restores trashed rows to their original locations.
+    pub fn restore_selected(
         &mut self,
         file_manager: &mut NativeFileManagerState,
         entries: Vec<FileEntryRow>,
+    ) -> Result<String> {
+        self.restore_from(file_manager, entries, &Trash::user())
+    }
+
+    pub fn restore_from(
+        &mut self,
+        file_manager: &mut NativeFileManagerState,
+        entries: Vec<FileEntryRow>,
+        trash: &Trash,
     ) -> Result<String> {
         if entries.is_empty() {
-            return Err(anyhow!("Select a file or folder first."));
+            return Err(anyhow!("Select an item in the trash first."));
         }
-        let trash_dir = base_dir().join(".fm_trash");
-        std::fs::create_dir_all(&trash_dir)
-            .map_err(|e| anyhow!("Failed creating trash dir {}: {e}", trash_dir.display()))?;
-        let mut moved = 0usize;
+        let mut restored = Vec::new();
         for entry in entries {
-            let name = path_display_name(&entry.path);
-            let trash_target = unique_path_in_dir(&trash_dir, &name);
-            move_path(&entry.path, &trash_target)?;
-            self.record_edit_op(FileManagerEditOp::Moved {
-                from: entry.path,
-                to: trash_target,
-            });
-            moved += 1;
+            if !trash.is_view(entry.path.parent().unwrap_or(&entry.path)) {
+                continue;
+            }
+            restored.push(trash.restore(&path_display_name(&entry.path))?);
         }
+        self.drop_stale_trash_ops(trash);
         file_manager.ensure_selection_valid();
-        if moved == 1 {
-            Ok("Moved item to trash".to_string())
+        match restored.as_slice() {
+            [] => Err(anyhow!("Selection is not in the trash.")),
+            [path] => Ok(format!("Restored {}", path.display())),
+            _ => Ok(format!("Restored {} items", restored.len())),
+        }
+    }
+
+    /// Permanently deletes the given items from the trash.
+    pub fn delete_from_trash(
+        file_manager: &mut NativeFileManagerState,
+        paths: &[PathBuf],
+        trash: &Trash,
+    ) -> Result<String> {
+        let mut deleted = 0usize;
+        for path in paths {
+            if !trash.is_view(path.parent().unwrap_or(path)) {
+                continue;
+            }
+            trash.delete(&path_display_name(path))?;
+            deleted += 1;
+        }
+        file_manager.ensure_selection_valid();
+        match deleted {
+            0 => Err(anyhow!("Nothing to delete.")),
+            1 => Ok("Deleted 1 item permanently".to_string()),
+            n => Ok(format!("Deleted {n} items permanently")),
+        }
+    }
+
+    pub fn empty_trash(
+        &mut self,
+        file_manager: &mut NativeFileManagerState,
+        trash: &Trash,
+    ) -> Result<String> {
+        let removed = trash.empty()?;
+        retain_edit_ops(
+            &mut self.undo_stack,
+            &|op| !matches!(op, FileManagerEditOp::Trashed { trash_root, .. } if trash_root == trash.root()),
+        );
+        self.redo_stack.clear();
+        file_manager.ensure_selection_valid();
+        if removed == 0 {
+            Ok("Trash is already empty".to_string())
         } else {
-            Ok(format!("Moved {moved} items to trash"))
+            Ok(format!("Emptied trash ({removed} item(s))"))
+        }
+    }
+
+    /// Extracts archive entries next to the archive they came from. Outside
+    /// an archive this is [`Self::extract_all_request`].
+    pub fn extract_request(
+        file_manager: &NativeFileManagerState,
+        entries: &[FileEntryRow],
+    ) -> Result<TransferRequest> {
+        let Some((archive_path, _)) = archive::split_archive_path(&file_manager.cwd) else {
+            return Self::extract_all_request(file_manager, entries);
+        };
+        if entries.is_empty() {
+            return Err(anyhow!("Select archive entries first."));
+        }
+        let target_dir = archive_path
+            .parent()
+            .map(Path::to_path_buf)
+            .ok_or_else(|| anyhow!("Cannot extract next to {}.", archive_path.display()))?;
+        Ok(TransferRequest::extract(
+            entries.iter().map(|entry| entry.path.clone()).collect(),
+            target_dir,
+        ))
+    }
+
+    /// Extracts a whole archive into a new folder named after it. Works for
+    /// the archive being browsed or a selected archive file.
+    pub fn extract_all_request(
+        file_manager: &NativeFileManagerState,
+        entries: &[FileEntryRow],
+    ) -> Result<TransferRequest> {
+        let archive_path = match archive::split_archive_path(&file_manager.cwd) {
+            Some((archive_path, _)) => archive_path,
+            None => match entries {
+                [entry] if archive::is_archive_file(&entry.path) => entry.path.clone(),
+                _ => return Err(anyhow!("Select an archive first.")),
+            },
+        };
+        let target_dir = archive_path
+            .parent()
+            .map(Path::to_path_buf)
+            .ok_or_else(|| anyhow!("Cannot extract next to {}.", archive_path.display()))?;
+        Ok(TransferRequest::extract(vec![archive_path], target_dir))
+    }
+
+    /// Packs the entries into a new archive in the current folder. The
+    /// format follows the extension of `name`; without one, zip is used.
+    pub fn compress_request(
+        file_manager: &NativeFileManagerState,
+        sources: Vec<PathBuf>,
+        name: String,
+    ) -> Result<TransferRequest> {
+        if sources.is_empty() {
+            return Err(anyhow!("Select a file or folder first."));
+        }
+        ensure_cwd_writable(file_manager)?;
+        if sources.iter().any(|path| archive::is_inside_archive(path)) {
+            return Err(anyhow!(ARCHIVE_READ_ONLY));
+        }
+        let mut name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(anyhow!("Name cannot be empty."));
+        }
+        if name.contains('/') || name.contains('\\') {
+            return Err(anyhow!("Name cannot contain path separators."));
+        }
+        if archive::ArchiveKind::from_path(Path::new(&name)).is_none() {
+            name.push_str(".zip");
+        }
+        let dst = file_manager.cwd.join(&name);
+        if dst.exists() {
+            return Err(anyhow!("Destination already exists: {}", dst.display()));
         }
+        Ok(TransferRequest::compress(sources, dst))
     }
 
     pub fn undo(&mut self, file_manager: &mut NativeFileManagerState) -> Result<String> {
@@ -1075,6 +1463,16 @@ impl FileManagerEditRuntime {
         Ok("Redo complete".to_string())
     }
 
+    /// Forgets trash undo entries whose items were restored or purged.
+    fn drop_stale_trash_ops(&mut self, trash: &Trash) {
+        retain_edit_ops(&mut self.undo_stack, &|op| match op {
+            FileManagerEditOp::Trashed {
+                trash_root, name, ..
+            } => trash_root != trash.root() || trash.files_dir().join(name).exists(),
+            _ => true,
+        });
+    }
+
     fn record_edit_op(&mut self, op: FileManagerEditOp) {
         self.undo_stack.push(op);
         self.redo_stack.clear();
@@ -1085,6 +1483,21 @@ impl FileManagerEditRuntime {
     }
 }
 
+pub fn format_bytes_human(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0usize;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} {}", UNITS[unit])
+    } else {
+        format!("{value:.2} {}", UNITS[unit])
+    }
+}
+
 fn split_file_name(name: &str) -> (&str, &str) {
     if let Some((stem, _ext)) = name.rsplit_once('.') {
         if !stem.is_empty() {
@@ -1101,6 +1514,13 @@ fn path_display_name(path: &Path) -> String {
         .unwrap_or_else(|| path.display().to_string())
 }
 
+fn nearest_existing_dir(path: &Path) -> PathBuf {
+    path.ancestors()
+        .find(|dir| dir.is_dir())
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("/"))
+}
+
 fn unique_copy_path_in_dir(dir: &Path, original_name: &str, prefer_copy_suffix: bool) -> PathBuf {
     let direct = dir.join(original_name);
     if !prefer_copy_suffix && !direct.exists() {
@@ -1162,10 +1582,11 @@ fn copy_path_recursive(src: &Path, dst: &Path) -> Result<()> {
 }
 
 fn remove_path_recursive(path: &Path) -> Result<()> {
-    if !path.exists() {
+    // Links are removed themselves, never what they point to.
+    let Ok(meta) = std::fs::symlink_metadata(path) else {
         return Ok(());
-    }
-    if path.is_dir() {
+    };
+    if meta.is_dir() {
         std::fs::remove_dir_all(path)
             .map_err(|e| anyhow!("Failed deleting {}: {e}", path.display()))?;
     } else {
@@ -1175,6 +1596,48 @@ fn remove_path_recursive(path: &Path) -> Result<()> {
     Ok(())
 }
 
+const ARCHIVE_READ_ONLY: &str = "Archives are read-only. Copy or extract items first.";
+const SEARCH_READ_ONLY: &str = "Search results are read-only. Open a folder first.";
+
+fn ensure_dir_writable(dir: &Path) -> Result<()> {
+    if archive::split_archive_path(dir).is_some() {
+        return Err(anyhow!(ARCHIVE_READ_ONLY));
+    }
+    if search::is_saved_search(dir) {
+        return Err(anyhow!(SEARCH_READ_ONLY));
+    }
+    Ok(())
+}
+
+/// Search results are a view over many folders, so nothing can be created
+/// "here" while they are shown.
+fn ensure_cwd_writable(file_manager: &NativeFileManagerState) -> Result<()> {
+    if file_manager.is_search_view() {
+        return Err(anyhow!(SEARCH_READ_ONLY));
+    }
+    ensure_dir_writable(&file_manager.cwd)
+}
+
+fn ensure_entries_writable(entries: &[FileEntryRow]) -> Result<()> {
+    if entries
+        .iter()
+        .any(|entry| archive::is_inside_archive(&entry.path))
+    {
+        return Err(anyhow!(ARCHIVE_READ_ONLY));
+    }
+    Ok(())
+}
+
+/// Copies a file or folder; sources inside an archive are extracted instead.
+fn copy_or_extract(src: &Path, dst: &Path) -> Result<()> {
+    match archive::split_archive_path(src) {
+        Some((archive_path, inner)) if !inner.as_os_str().is_empty() => {
+            archive::extract_entry(&archive_path, &inner, dst).map(|_| ())
+        }
+        _ => copy_path_recursive(src, dst),
+    }
+}
+
 fn move_path(src: &Path, dst: &Path) -> Result<()> {
     if src == dst {
         return Ok(());
@@ -1201,7 +1664,7 @@ fn apply_edit_op(op: &FileManagerEditOp, reverse: bool) -> Result<()> {
             if reverse {
                 remove_path_recursive(dst)
             } else {
-                copy_path_recursive(src, dst)
+                copy_or_extract(src, dst)
             }
         }
         FileManagerEditOp::Moved { from, to } => {
@@ -1211,9 +1674,64 @@ fn apply_edit_op(op: &FileManagerEditOp, reverse: bool) -> Result<()> {
                 move_path(from, to)
             }
         }
+        FileManagerEditOp::Extracted { archive, dst } => {
+            if reverse {
+                remove_path_recursive(dst)
+            } else {
+                archive::extract_entry(archive, Path::new(""), dst).map(|_| ())
+            }
+        }
+        FileManagerEditOp::ArchiveCreated { sources, dst } => {
+            if reverse {
+                remove_path_recursive(dst)
+            } else {
+                archive::create_archive(dst, sources).map(|_| ())
+            }
+        }
+        FileManagerEditOp::BatchRenamed { renames } => {
+            if reverse {
+                let reversed: Vec<(PathBuf, PathBuf)> = renames
+                    .iter()
+                    .map(|(from, to)| (to.clone(), from.clone()))
+                    .collect();
+                apply_renames(&reversed)
+            } else {
+                apply_renames(renames)
+            }
+        }
+        FileManagerEditOp::Trashed {
+            trash_root,
+            original,
+            name,
+        } => {
+            let trash = Trash::at(trash_root.clone());
+            if reverse {
+                trash.restore(name).map(|_| ())
+            } else {
+                trash.trash_as(original, name).map(|_| ())
+            }
+        }
+        FileManagerEditOp::Group { ops } => {
+            if reverse {
+                ops.iter().rev().try_for_each(|op| apply_edit_op(op, true))
+            } else {
+                ops.iter().try_for_each(|op| apply_edit_op(op, false))
+            }
+        }
     }
 }
 
+/// Filters an undo stack, looking inside groups; groups left empty go too.
+fn retain_edit_ops(ops: &mut Vec<FileManagerEditOp>, keep: &dyn Fn(&FileManagerEditOp) -> bool) {
+    ops.retain_mut(|op| match op {
+        FileManagerEditOp::Group { ops } => {
+            retain_edit_ops(ops, keep);
+            !ops.is_empty()
+        }
+        op => keep(op),
+    });
+}
+
 fn can_move_path_to_dir(src: &Path, target_dir: &Path) -> bool {
     if src == target_dir {
         return false;
@@ -1227,32 +1745,8 @@ fn can_move_path_to_dir(src: &Path, target_dir: &Path) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    struct TempDirGuard {
-        path: PathBuf,
-    }
-
-    impl TempDirGuard {
-        fn new(prefix: &str) -> Self {
-            let unique = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .expect("test clock")
-                .as_nanos();
-            let path = std::env::temp_dir().join(format!(
-                "robco_native_file_manager_app_{prefix}_{}_{}",
-                std::process::id(),
-                unique
-            ));
-            std::fs::create_dir_all(&path).expect("create temp test dir");
-            Self { path }
-        }
-    }
-
-    impl Drop for TempDirGuard {
-        fn drop(&mut self) {
-            let _ = std::fs::remove_dir_all(&self.path);
-        }
-    }
+    use crate::test_support::TempDirGuard;
+    use crate::transfers::TransferQueue;
 
     #[test]
     fn selected_rows_for_action_prefers_multi_selection() {
@@ -1279,6 +1773,31 @@ mod tests {
         assert!(selected.contains(&b));
     }
 
+    #[test]
+    fn refresh_keeps_selection_in_place_and_leaves_removed_folders() {
+        let temp = TempDirGuard::new("refresh");
+        let sub = temp.path.join("sub");
+        std::fs::create_dir_all(&sub).expect("create sub");
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            std::fs::write(sub.join(name), name).expect("write file");
+        }
+
+        let mut fm = NativeFileManagerState::new(sub.clone());
+        fm.select(Some(sub.join("b.txt")));
+        std::fs::write(sub.join("0.txt"), "new").expect("write new file");
+        fm.refresh_changed_dirs(std::slice::from_ref(&sub));
+        assert_eq!(fm.selected, Some(sub.join("b.txt")));
+
+        std::fs::remove_file(sub.join("b.txt")).expect("remove b");
+        fm.refresh_changed_dirs(std::slice::from_ref(&sub));
+        assert_eq!(fm.selected, Some(sub.join("c.txt")));
+
+        std::fs::remove_dir_all(&sub).expect("remove sub");
+        fm.refresh_changed_dirs(std::slice::from_ref(&sub));
+        assert_eq!(fm.cwd, temp.path);
+        assert_eq!(fm.tabs, vec![temp.path.clone()]);
+    }
+
     #[test]
     fn create_new_folder_uses_numbered_name_when_needed() {
         let temp = TempDirGuard::new("new_folder");
@@ -1294,6 +1813,190 @@ mod tests {
         assert_eq!(fm.selected, Some(temp.path.join("New Folder (1)")));
     }
 
+    /// Runs `request` on a queue and records it like the app does.
+    fn run_transfer(
+        runtime: &mut FileManagerEditRuntime,
+        fm: &mut NativeFileManagerState,
+        trash: &Trash,
+        request: TransferRequest,
+    ) -> String {
+        let mut queue = TransferQueue::new(trash.clone());
+        queue.enqueue(request);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+        loop {
+            if let Some(outcome) = queue.poll() {
+                return runtime.finish_transfer(fm, outcome, trash);
+            }
+            assert!(
+                std::time::Instant::now() < deadline,
+                "transfer did not finish"
+            );
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn trash_request_records_undoable_trash_op() {
+        let temp = TempDirGuard::new("trash_undo");
+        let trash = Trash::at(temp.path.join("Trash"));
+        let work = temp.path.join("work");
+        std::fs::create_dir_all(&work).expect("create work dir");
+        let file = work.join("notes.txt");
+        std::fs::write(&file, "notes").expect("write notes");
+        let mut fm = NativeFileManagerState::new(work.clone());
+        let mut runtime = FileManagerEditRuntime::default();
+        let row = FileEntryRow {
+            path: file.clone(),
+            label: "notes.txt".to_string(),
+            is_dir: false,
+        };
+
+        let request = FileManagerEditRuntime::trash_request(&[row]).expect("trash request");
+        let status = run_transfer(&mut runtime, &mut fm, &trash, request);
+        assert_eq!(status, "Moved item to trash");
+        assert!(!file.exists());
+        assert_eq!(trash.entries().len(), 1);
+
+        runtime.undo(&mut fm).expect("undo restores");
+        assert!(file.exists());
+        assert!(trash.entries().is_empty());
+
+        runtime.redo(&mut fm).expect("redo trashes again");
+        assert!(!file.exists());
+        assert_eq!(trash.entries()[0].original_path.as_ref(), Some(&file));
+
+        runtime
+            .empty_trash(&mut fm, &trash)
+            .expect("empty trash succeeds");
+        assert!(trash.entries().is_empty());
+        assert!(!runtime.can_undo());
+    }
+
+    #[test]
+    fn batch_rename_is_one_undoable_op() {
+        let temp = TempDirGuard::new("batch_rename");
+        let mut rows = Vec::new();
+        for name in ["b.txt", "a.txt"] {
+            std::fs::write(temp.path.join(name), name).expect("write file");
+            rows.push(FileEntryRow {
+                path: temp.path.join(name),
+                label: name.to_string(),
+                is_dir: false,
+            });
+        }
+        let mut fm = NativeFileManagerState::new(temp.path.clone());
+        let mut runtime = FileManagerEditRuntime::default();
+        let rule = BatchRenameRule {
+            template: "img_{n:02}".to_string(),
+            ..BatchRenameRule::default()
+        };
+
+        let status = runtime
+            .batch_rename_selected(&mut fm, rows, &rule)
+            .expect("batch rename succeeds");
+        assert_eq!(status, "Renamed 2 item(s)");
+        assert_eq!(
+            std::fs::read_to_string(temp.path.join("img_01.txt")).expect("first"),
+            "b.txt"
+        );
+        assert!(temp.path.join("img_02.txt").exists());
+        assert_eq!(runtime.undo_stack.len(), 1);
+
+        runtime.undo(&mut fm).expect("undo restores names");
+        assert!(temp.path.join("a.txt").exists());
+        assert!(temp.path.join("b.txt").exists());
+        assert!(!temp.path.join("img_01.txt").exists());
+
+        runtime.redo(&mut fm).expect("redo renames again");
+        assert!(temp.path.join("img_02.txt").exists());
+    }
+
+    #[test]
+    fn archives_browse_as_read_only_folders_and_paste_extracts() {
+        let temp = TempDirGuard::new("archive_browse");
+        let src = temp.path.join("src");
+        std::fs::create_dir_all(src.join("inner")).expect("create src");
+        std::fs::write(src.join("inner").join("note.txt"), "hi").expect("write note");
+        let zip_path = temp.path.join("bundle.zip");
+        archive::create_archive(&zip_path, std::slice::from_ref(&src)).expect("create zip");
+
+        let mut fm = NativeFileManagerState::new(temp.path.clone());
+        fm.select(Some(zip_path.clone()));
+        assert!(matches!(
+            fm.activate_selected(),
+            FileManagerAction::ChangedDir
+        ));
+        assert!(fm.is_archive_view());
+        fm.select(Some(zip_path.join("src")));
+        fm.activate_selected();
+        let rows = fm.rows();
+        let inner = rows
+            .iter()
+            .find(|row| row.label == "inner")
+            .expect("virtual folder row")
+            .clone();
+        assert!(inner.is_dir);
+
+        let mut runtime = FileManagerEditRuntime::default();
+        assert!(runtime
+            .rename_selected(&mut fm, inner.clone(), "renamed".to_string())
+            .is_err());
+        runtime
+            .set_clipboard_from_entries(&[inner], FileManagerClipboardMode::Copy)
+            .expect("copy from archive");
+        let out = temp.path.join("out");
+        std::fs::create_dir_all(&out).expect("create out");
+        fm.set_cwd(out.clone());
+        let request = runtime.paste_request(&fm).expect("paste request");
+        let trash = Trash::at(temp.path.join("Trash"));
+        let status = run_transfer(&mut runtime, &mut fm, &trash, request);
+        assert_eq!(status, "Copied inner");
+        assert_eq!(
+            std::fs::read_to_string(out.join("inner").join("note.txt")).expect("read note"),
+            "hi"
+        );
+    }
+
+    #[test]
+    fn saved_searches_open_as_folders_of_results() {
+        let temp = TempDirGuard::new("saved_search");
+        let docs = temp.path.join("docs");
+        std::fs::create_dir_all(docs.join("deep")).expect("create docs");
+        let note = docs.join("deep").join("note.md");
+        std::fs::write(&note, "hi").expect("write note");
+        std::fs::write(docs.join("other.txt"), "x").expect("write other");
+        let saved = search::save_search(
+            &temp.path,
+            "Notes",
+            &search::SearchSpec {
+                name: "*.md".to_string(),
+                ..search::SearchSpec::new(docs.clone())
+            },
+        )
+        .expect("save search");
+
+        let mut fm = NativeFileManagerState::new(temp.path.clone());
+        fm.select(Some(saved.clone()));
+        assert!(matches!(
+            fm.activate_selected(),
+            FileManagerAction::ChangedDir
+        ));
+        assert!(fm.is_search_view());
+        while fm.search.as_ref().is_some_and(FileSearch::is_running) {
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        let labels: Vec<String> = fm.rows().into_iter().map(|row| row.label).collect();
+        assert_eq!(labels, vec!["..", "note.md"]);
+
+        let mut runtime = FileManagerEditRuntime::default();
+        assert!(runtime.create_new_folder(&mut fm).is_err());
+        fm.select(Some(note.clone()));
+        fm.reveal_selected().expect("reveal");
+        assert!(!fm.is_search_view());
+        assert_eq!(fm.cwd, docs.join("deep"));
+        assert_eq!(fm.selected, Some(note));
+    }
+
     #[test]
     fn open_target_for_file_manager_action_prefers_default_open_with() {
         let mut settings = DesktopFileManagerSettings::default();
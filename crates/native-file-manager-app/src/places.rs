@@ -0,0 +1,188 @@
+//! The Places sidebar: bookmarks, standard folders, document categories,
+//! drives, recent folders and saved searches, in that order.
+
+use crate::search::{saved_search_name, saved_searches, saved_searches_dir};
+use crate::trash::Trash;
+use crate::{path_display_name, NativeFileManagerState};
+use robcos_native_services::desktop_documents_service::document_category_entries;
+use robcos_shared::config::get_settings;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaceSection {
+    Bookmarks,
+    Standard,
+    Documents,
+    Drives,
+    Recent,
+    Searches,
+}
+
+impl PlaceSection {
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Bookmarks => "Bookmarks",
+            Self::Standard => "Places",
+            Self::Documents => "Documents",
+            Self::Drives => "Drives",
+            Self::Recent => "Recent",
+            Self::Searches => "Searches",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Place {
+    pub section: PlaceSection,
+    pub label: String,
+    pub path: PathBuf,
+}
+
+/// The raw inputs for each section, gathered separately so tests can
+/// supply their own.
+#[derive(Debug, Clone)]
+struct PlaceSources {
+    bookmarks: Vec<String>,
+    standard: Vec<(String, PathBuf)>,
+    documents: Vec<(String, PathBuf)>,
+    drives: Vec<PathBuf>,
+    recent: Vec<String>,
+    searches: Vec<PathBuf>,
+}
+
+pub fn places() -> Vec<Place> {
+    let settings = get_settings();
+    collect_places(PlaceSources {
+        bookmarks: settings.desktop_file_manager.bookmarks,
+        standard: standard_folders(),
+        documents: document_category_entries(),
+        drives: NativeFileManagerState::drive_roots(),
+        recent: settings.desktop_session.recent_folders,
+        searches: saved_searches(&saved_searches_dir()),
+    })
+}
+
+/// Places that can be browsed as plain folders, for the terminal jump list.
+pub fn folder_places() -> Vec<Place> {
+    places()
+        .into_iter()
+        .filter(|place| place.path.is_dir())
+        .collect()
+}
+
+pub fn is_bookmarked(path: &Path) -> bool {
+    let path = path.to_string_lossy();
+    get_settings()
+        .desktop_file_manager
+        .bookmarks
+        .iter()
+        .any(|entry| *entry == path)
+}
+
+fn standard_folders() -> Vec<(String, PathBuf)> {
+    let mut folders: Vec<(String, PathBuf)> = [
+        ("Home", dirs::home_dir()),
+        ("Desktop", dirs::desktop_dir()),
+        ("Documents", dirs::document_dir()),
+        ("Downloads", dirs::download_dir()),
+        ("Music", dirs::audio_dir()),
+        ("Pictures", dirs::picture_dir()),
+        ("Videos", dirs::video_dir()),
+    ]
+    .into_iter()
+    .filter_map(|(label, path)| Some((label.to_string(), path.filter(|path| path.is_dir())?)))
+    .collect();
+    // XDG folders that are unset fall back to $HOME; list home only once.
+    let mut seen = Vec::new();
+    folders.retain(|(_, path)| {
+        let fresh = !seen.contains(path);
+        seen.push(path.clone());
+        fresh
+    });
+    folders.push(("Trash".to_string(), Trash::user().files_dir()));
+    folders
+}
+
+fn collect_places(sources: PlaceSources) -> Vec<Place> {
+    let place = |section, label: String, path: PathBuf| Place {
+        section,
+        label,
+        path,
+    };
+    let mut places = Vec::new();
+    for raw in sources.bookmarks {
+        let path = PathBuf::from(raw);
+        places.push(place(
+            PlaceSection::Bookmarks,
+            path_display_name(&path),
+            path,
+        ));
+    }
+    for (label, path) in sources.standard {
+        places.push(place(PlaceSection::Standard, label, path));
+    }
+    for (label, path) in sources.documents {
+        places.push(place(PlaceSection::Documents, label, path));
+    }
+    for path in sources.drives {
+        places.push(place(
+            PlaceSection::Drives,
+            NativeFileManagerState::drive_label(&path),
+            path,
+        ));
+    }
+    // Folders that have gone away since the visit are not worth a click.
+    for raw in sources.recent {
+        let path = PathBuf::from(raw);
+        if path.is_dir() {
+            places.push(place(PlaceSection::Recent, path_display_name(&path), path));
+        }
+    }
+    for path in sources.searches {
+        places.push(place(
+            PlaceSection::Searches,
+            saved_search_name(&path),
+            path,
+        ));
+    }
+    places
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn places_keep_section_order_and_drop_missing_recent_folders() {
+        let tmp = std::env::temp_dir();
+        let missing = tmp.join("robcos_places_missing_folder");
+        let places = collect_places(PlaceSources {
+            bookmarks: vec![tmp.join("projects").to_string_lossy().to_string()],
+            standard: vec![("Home".to_string(), tmp.clone())],
+            documents: vec![("Notes".to_string(), tmp.join("notes"))],
+            drives: vec![PathBuf::from("/")],
+            recent: vec![
+                missing.to_string_lossy().to_string(),
+                tmp.to_string_lossy().to_string(),
+            ],
+            searches: vec![tmp.join("Logs.search")],
+        });
+
+        let sections: Vec<PlaceSection> = places.iter().map(|place| place.section).collect();
+        assert_eq!(
+            sections,
+            vec![
+                PlaceSection::Bookmarks,
+                PlaceSection::Standard,
+                PlaceSection::Documents,
+                PlaceSection::Drives,
+                PlaceSection::Recent,
+                PlaceSection::Searches,
+            ]
+        );
+        assert_eq!(places[0].label, "projects");
+        assert_eq!(places[2].label, "Notes");
+        assert_eq!(places[4].path, tmp);
+        assert_eq!(places[5].label, "Logs");
+    }
+}
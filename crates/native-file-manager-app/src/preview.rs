@@ -0,0 +1,513 @@
+//! Preview content for the file manager's preview pane and the terminal
+//! document browser.
+//!
+//! Everything here does blocking IO and is meant to run off the UI thread.
+//! Images are only classified; decoding them is left to the caller, which
+//! owns the image stack.
+
+use crate::archive;
+use robcos_shared::default_apps::{slot_for_path, DefaultAppSlot};
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+/// How much of a file is read to build a text or hex preview.
+pub const PREVIEW_READ_LIMIT: usize = 64 * 1024;
+pub const PREVIEW_MAX_TEXT_LINES: usize = 400;
+pub const PREVIEW_HEX_BYTES: usize = 1024;
+const HEX_BYTES_PER_LINE: usize = 16;
+/// Directory summaries stop counting after this many entries.
+const DIR_SUMMARY_ENTRY_LIMIT: usize = 20_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreviewTokenKind {
+    Plain,
+    Keyword,
+    String,
+    Number,
+    Comment,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreviewSpan {
+    pub text: String,
+    pub kind: PreviewTokenKind,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DirectorySummary {
+    pub folders: usize,
+    pub files: usize,
+    pub hidden: usize,
+    /// Size of every file below the folder, counted recursively.
+    pub total_size: u64,
+    /// Set when the walk hit the entry limit and the totals are a lower bound.
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilePreview {
+    Text {
+        lines: Vec<Vec<PreviewSpan>>,
+        highlighted: bool,
+        truncated: bool,
+    },
+    Hex {
+        lines: Vec<String>,
+        size: u64,
+    },
+    Image {
+        size: u64,
+    },
+    Directory(DirectorySummary),
+    Archive {
+        files: usize,
+        folders: usize,
+        size: u64,
+    },
+    Unavailable(String),
+}
+
+impl FilePreview {
+    /// Plain text lines for character-cell displays.
+    pub fn summary_lines(&self) -> Vec<String> {
+        match self {
+            Self::Text {
+                lines, truncated, ..
+            } => {
+                let mut out: Vec<String> = lines
+                    .iter()
+                    .map(|spans| spans.iter().map(|span| span.text.as_str()).collect())
+                    .collect();
+                if *truncated {
+                    out.push("...".to_string());
+                }
+                out
+            }
+            Self::Hex { lines, size } => {
+                let mut out = vec![format!("Binary file, {}", crate::format_bytes_human(*size))];
+                out.extend(lines.iter().cloned());
+                out
+            }
+            Self::Image { size } => {
+                vec![format!("Image, {}", crate::format_bytes_human(*size))]
+            }
+            Self::Directory(summary) => {
+                let mut out = vec![
+                    format!("{} folder(s), {} file(s)", summary.folders, summary.files),
+                    format!(
+                        "Total size: {}{}",
+                        crate::format_bytes_human(summary.total_size),
+                        if summary.truncated { "+" } else { "" }
+                    ),
+                ];
+                if summary.hidden > 0 {
+                    out.push(format!("{} hidden item(s)", summary.hidden));
+                }
+                out
+            }
+            Self::Archive {
+                files,
+                folders,
+                size,
+            } => vec![
+                format!("Archive: {files} file(s), {folders} folder(s)"),
+                format!("Unpacked size: {}", crate::format_bytes_human(*size)),
+            ],
+            Self::Unavailable(reason) => vec![reason.clone()],
+        }
+    }
+}
+
+pub fn is_image_preview_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            matches!(
+                ext.to_ascii_lowercase().as_str(),
+                "png" | "jpg" | "jpeg" | "svg"
+            )
+        })
+}
+
+/// Builds the preview for `path`. Directories get a summary, archives an
+/// entry count, text a (possibly highlighted) listing and anything else a
+/// hex dump of its first bytes.
+pub fn build_preview(path: &Path) -> FilePreview {
+    if archive::is_inside_archive(path) {
+        return FilePreview::Unavailable("Extract the file to preview it.".to_string());
+    }
+    let meta = match fs::metadata(path) {
+        Ok(meta) => meta,
+        Err(err) => return FilePreview::Unavailable(format!("Cannot read: {err}")),
+    };
+    if meta.is_dir() {
+        return FilePreview::Directory(summarize_directory(path));
+    }
+    if archive::is_archive_file(path) {
+        return match archive::list_entries(path) {
+            Ok(entries) => FilePreview::Archive {
+                files: entries.iter().filter(|e| !e.is_dir).count(),
+                folders: entries.iter().filter(|e| e.is_dir).count(),
+                size: entries.iter().map(|e| e.size).sum(),
+            },
+            Err(err) => FilePreview::Unavailable(err.to_string()),
+        };
+    }
+    if is_image_preview_path(path) {
+        return FilePreview::Image { size: meta.len() };
+    }
+    let bytes = match read_head(path, PREVIEW_READ_LIMIT) {
+        Ok(bytes) => bytes,
+        Err(err) => return FilePreview::Unavailable(format!("Cannot read: {err}")),
+    };
+    match text_prefix(&bytes) {
+        Some(text) => {
+            let highlighted = slot_for_path(path) == Some(DefaultAppSlot::TextCode);
+            let comment = comment_prefix(path);
+            let mut lines = Vec::new();
+            let mut truncated = (bytes.len() as u64) < meta.len();
+            for (idx, line) in text.lines().enumerate() {
+                if idx == PREVIEW_MAX_TEXT_LINES {
+                    truncated = true;
+                    break;
+                }
+                let line = line.replace('\t', "    ");
+                lines.push(if highlighted {
+                    highlight_line(&line, comment)
+                } else {
+                    vec![PreviewSpan {
+                        text: line,
+                        kind: PreviewTokenKind::Plain,
+                    }]
+                });
+            }
+            FilePreview::Text {
+                lines,
+                highlighted,
+                truncated,
+            }
+        }
+        None => FilePreview::Hex {
+            lines: hex_dump(&bytes[..bytes.len().min(PREVIEW_HEX_BYTES)]),
+            size: meta.len(),
+        },
+    }
+}
+
+fn read_head(path: &Path, limit: usize) -> std::io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    fs::File::open(path)?
+        .take(limit as u64)
+        .read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Decodes `bytes` as UTF-8, tolerating a character cut off by the read
+/// limit. Returns `None` for anything that looks binary.
+fn text_prefix(bytes: &[u8]) -> Option<&str> {
+    if bytes.contains(&0) {
+        return None;
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Some(text),
+        Err(err) if err.error_len().is_none() && bytes.len() - err.valid_up_to() < 4 => {
+            std::str::from_utf8(&bytes[..err.valid_up_to()]).ok()
+        }
+        Err(_) => None,
+    }
+}
+
+/// Classic `offset  hex bytes  |ascii|` layout, 16 bytes per line.
+pub fn hex_dump(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .chunks(HEX_BYTES_PER_LINE)
+        .enumerate()
+        .map(|(idx, chunk)| {
+            let mut hex = String::with_capacity(HEX_BYTES_PER_LINE * 3);
+            for (pos, byte) in chunk.iter().enumerate() {
+                if pos == HEX_BYTES_PER_LINE / 2 {
+                    hex.push(' ');
+                }
+                hex.push_str(&format!("{byte:02x} "));
+            }
+            let ascii: String = chunk
+                .iter()
+                .map(|byte| {
+                    if byte.is_ascii_graphic() || *byte == b' ' {
+                        *byte as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            format!(
+                "{:08x}  {hex:<width$} |{ascii}|",
+                idx * HEX_BYTES_PER_LINE,
+                width = HEX_BYTES_PER_LINE * 3 + 1
+            )
+        })
+        .collect()
+}
+
+pub fn summarize_directory(path: &Path) -> DirectorySummary {
+    let mut summary = DirectorySummary::default();
+    let Ok(read) = fs::read_dir(path) else {
+        return summary;
+    };
+    let mut pending = Vec::new();
+    for entry in read.flatten() {
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            summary.hidden += 1;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            summary.folders += 1;
+            pending.push(entry.path());
+        } else {
+            summary.files += 1;
+            summary.total_size += entry.metadata().map(|m| m.len()).unwrap_or(0);
+        }
+    }
+    let mut visited = summary.folders + summary.files;
+    while let Some(dir) = pending.pop() {
+        let Ok(read) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in read.flatten() {
+            visited += 1;
+            if visited > DIR_SUMMARY_ENTRY_LIMIT {
+                summary.truncated = true;
+                return summary;
+            }
+            match entry.file_type() {
+                Ok(file_type) if file_type.is_dir() => pending.push(entry.path()),
+                Ok(file_type) if file_type.is_file() => {
+                    summary.total_size += entry.metadata().map(|m| m.len()).unwrap_or(0);
+                }
+                _ => {}
+            }
+        }
+    }
+    summary
+}
+
+fn comment_prefix(path: &Path) -> &'static str {
+    let ext = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    match ext.as_str() {
+        "py" | "sh" | "zsh" | "bash" | "rb" | "toml" | "yaml" | "yml" | "conf" => "#",
+        "ini" => ";",
+        "sql" | "lua" => "--",
+        _ => "//",
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "as",
+    "async",
+    "await",
+    "break",
+    "case",
+    "class",
+    "const",
+    "continue",
+    "def",
+    "default",
+    "do",
+    "elif",
+    "else",
+    "enum",
+    "export",
+    "extern",
+    "false",
+    "fi",
+    "fn",
+    "for",
+    "from",
+    "function",
+    "if",
+    "impl",
+    "import",
+    "in",
+    "interface",
+    "let",
+    "local",
+    "loop",
+    "match",
+    "mod",
+    "mut",
+    "None",
+    "null",
+    "pub",
+    "return",
+    "self",
+    "static",
+    "struct",
+    "switch",
+    "then",
+    "trait",
+    "true",
+    "True",
+    "False",
+    "type",
+    "use",
+    "var",
+    "void",
+    "where",
+    "while",
+];
+
+/// Splits one source line into keyword, string, number and comment spans.
+/// This is a lexical approximation shared by every language in the
+/// text/code slot, not a real parser.
+pub fn highlight_line(line: &str, comment: &str) -> Vec<PreviewSpan> {
+    let mut spans: Vec<PreviewSpan> = Vec::new();
+    let mut push = |text: &str, kind: PreviewTokenKind| {
+        if text.is_empty() {
+            return;
+        }
+        match spans.last_mut() {
+            Some(last) if last.kind == kind => last.text.push_str(text),
+            _ => spans.push(PreviewSpan {
+                text: text.to_string(),
+                kind,
+            }),
+        }
+    };
+    let mut rest = line;
+    while let Some(ch) = rest.chars().next() {
+        if rest.starts_with(comment) {
+            push(rest, PreviewTokenKind::Comment);
+            break;
+        }
+        if ch == '"' || ch == '\'' {
+            let mut end = rest.len();
+            let mut escaped = false;
+            for (idx, c) in rest.char_indices().skip(1) {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == ch {
+                    end = idx + c.len_utf8();
+                    break;
+                }
+            }
+            push(&rest[..end], PreviewTokenKind::String);
+            rest = &rest[end..];
+            continue;
+        }
+        if ch.is_alphanumeric() || ch == '_' {
+            let end = rest
+                .char_indices()
+                .find(|(_, c)| !(c.is_alphanumeric() || *c == '_' || *c == '.'))
+                .map(|(idx, _)| idx)
+                .unwrap_or(rest.len());
+            let word = &rest[..end];
+            let kind = if ch.is_ascii_digit() {
+                PreviewTokenKind::Number
+            } else if KEYWORDS.contains(&word) {
+                PreviewTokenKind::Keyword
+            } else {
+                PreviewTokenKind::Plain
+            };
+            // A dotted word like `self.name` only counts as a keyword when it
+            // is exactly one.
+            if kind == PreviewTokenKind::Keyword || kind == PreviewTokenKind::Number {
+                push(word, kind);
+            } else {
+                let head = word.split('.').next().unwrap_or(word);
+                let head_kind = if KEYWORDS.contains(&head) {
+                    PreviewTokenKind::Keyword
+                } else {
+                    PreviewTokenKind::Plain
+                };
+                push(head, head_kind);
+                push(&word[head.len()..], PreviewTokenKind::Plain);
+            }
+            rest = &rest[end..];
+            continue;
+        }
+        let len = ch.len_utf8();
+        push(&rest[..len], PreviewTokenKind::Plain);
+        rest = &rest[len..];
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDirGuard;
+
+    #[test]
+    fn highlight_line_marks_keywords_strings_numbers_and_comments() {
+        let spans = highlight_line("let name = \"a // b\"; // 42 later", "//");
+        let kinds: Vec<(&str, PreviewTokenKind)> = spans
+            .iter()
+            .map(|span| (span.text.as_str(), span.kind))
+            .collect();
+        assert_eq!(kinds[0], ("let", PreviewTokenKind::Keyword));
+        assert!(kinds.contains(&("\"a // b\"", PreviewTokenKind::String)));
+        assert_eq!(
+            kinds.last().copied(),
+            Some(("// 42 later", PreviewTokenKind::Comment))
+        );
+        let numbers = highlight_line("x = 3.5", "#");
+        assert!(numbers
+            .iter()
+            .any(|span| span.text == "3.5" && span.kind == PreviewTokenKind::Number));
+    }
+
+    #[test]
+    fn build_preview_picks_text_hex_and_directory_summaries() {
+        let temp = TempDirGuard::new("preview_kinds");
+        let dir = temp.path.clone();
+        fs::write(dir.join("main.rs"), "fn main() {}\n").unwrap();
+        fs::write(dir.join("notes.log2"), "plain words\n").unwrap();
+        fs::write(dir.join("blob.bin"), [0u8, 1, 2, b'A', 0xff]).unwrap();
+        fs::create_dir(dir.join("sub")).unwrap();
+        fs::write(dir.join("sub").join("inner.txt"), "12345").unwrap();
+
+        match build_preview(&dir.join("main.rs")) {
+            FilePreview::Text {
+                lines, highlighted, ..
+            } => {
+                assert!(highlighted);
+                assert_eq!(lines[0][0].kind, PreviewTokenKind::Keyword);
+            }
+            other => panic!("expected text preview, got {other:?}"),
+        }
+        assert!(matches!(
+            build_preview(&dir.join("notes.log2")),
+            FilePreview::Text {
+                highlighted: false,
+                ..
+            }
+        ));
+        match build_preview(&dir.join("blob.bin")) {
+            FilePreview::Hex { lines, size } => {
+                assert_eq!(size, 5);
+                assert_eq!(lines.len(), 1);
+                assert!(lines[0].starts_with("00000000  00 01 02 41 ff"));
+                assert!(lines[0].ends_with("|...A.|"));
+            }
+            other => panic!("expected hex preview, got {other:?}"),
+        }
+        match build_preview(&dir) {
+            FilePreview::Directory(summary) => {
+                assert_eq!(summary.folders, 1);
+                assert_eq!(summary.files, 3);
+                assert_eq!(summary.total_size, 13 + 12 + 5 + 5);
+                assert!(!summary.truncated);
+            }
+            other => panic!("expected directory summary, got {other:?}"),
+        }
+    }
+}
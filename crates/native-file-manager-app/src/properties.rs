@@ -0,0 +1,418 @@
+//! File and folder properties: metadata, ownership and Unix permissions.
+//!
+//! Folder sizes can take a while on large trees, so [`FolderSize`] walks the
+//! folder on a worker thread and the dialog polls it.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Local};
+use robcos_shared::default_apps::{slot_for_path, DefaultAppSlot};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertiesKind {
+    File,
+    Folder,
+    Symlink,
+}
+
+impl PropertiesKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::File => "File",
+            Self::Folder => "Folder",
+            Self::Symlink => "Symbolic link",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnixOwnership {
+    /// Permission bits, including setuid, setgid and sticky.
+    pub mode: u32,
+    pub uid: u32,
+    pub gid: u32,
+    pub owner: String,
+    pub group: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileProperties {
+    pub path: PathBuf,
+    pub kind: PropertiesKind,
+    pub link_target: Option<PathBuf>,
+    /// File length; folders are sized separately with [`FolderSize`].
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+    pub accessed: Option<SystemTime>,
+    pub created: Option<SystemTime>,
+    pub mime: &'static str,
+    pub slot: Option<DefaultAppSlot>,
+    /// `None` on platforms without Unix permissions.
+    pub ownership: Option<UnixOwnership>,
+}
+
+impl FileProperties {
+    pub fn read(path: &Path) -> Result<Self> {
+        let link_meta = std::fs::symlink_metadata(path)
+            .map_err(|e| anyhow!("Cannot read {}: {e}", path.display()))?;
+        let is_link = link_meta.file_type().is_symlink();
+        // Links report their target's details; a dangling link its own.
+        let meta = if is_link {
+            std::fs::metadata(path).unwrap_or_else(|_| link_meta.clone())
+        } else {
+            link_meta
+        };
+        let kind = if is_link {
+            PropertiesKind::Symlink
+        } else if meta.is_dir() {
+            PropertiesKind::Folder
+        } else {
+            PropertiesKind::File
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            kind,
+            link_target: is_link.then(|| std::fs::read_link(path).ok()).flatten(),
+            size: if meta.is_dir() { 0 } else { meta.len() },
+            modified: meta.modified().ok(),
+            accessed: meta.accessed().ok(),
+            created: meta.created().ok(),
+            mime: mime_type(path, meta.is_dir()),
+            slot: slot_for_path(path),
+            ownership: unix_ownership(&meta),
+        })
+    }
+}
+
+#[cfg(unix)]
+fn unix_ownership(meta: &std::fs::Metadata) -> Option<UnixOwnership> {
+    use std::os::unix::fs::MetadataExt;
+    Some(UnixOwnership {
+        mode: meta.mode() & 0o7777,
+        uid: meta.uid(),
+        gid: meta.gid(),
+        owner: user_name(meta.uid()),
+        group: group_name(meta.gid()),
+    })
+}
+
+#[cfg(not(unix))]
+fn unix_ownership(_meta: &std::fs::Metadata) -> Option<UnixOwnership> {
+    None
+}
+
+pub fn format_timestamp(time: Option<SystemTime>) -> String {
+    time.map(|time| {
+        DateTime::<Local>::from(time)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    })
+    .unwrap_or_else(|| "Unknown".to_string())
+}
+
+/// A best-effort MIME type from the extension.
+pub fn mime_type(path: &Path, is_dir: bool) -> &'static str {
+    if is_dir {
+        return "inode/directory";
+    }
+    let ext = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "txt" | "log" | "conf" | "ini" => "text/plain",
+        "md" => "text/markdown",
+        "csv" => "text/csv",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "xml" => "application/xml",
+        "json" => "application/json",
+        "toml" => "application/toml",
+        "yaml" | "yml" => "application/yaml",
+        "rs" => "text/rust",
+        "c" | "h" => "text/x-c",
+        "cpp" | "hpp" | "cc" => "text/x-c++",
+        "py" => "text/x-python",
+        "js" | "jsx" => "text/javascript",
+        "ts" | "tsx" => "text/typescript",
+        "sh" | "bash" | "zsh" => "application/x-shellscript",
+        "pdf" => "application/pdf",
+        "epub" => "application/epub+zip",
+        "mobi" => "application/x-mobipocket-ebook",
+        "azw3" => "application/vnd.amazon.ebook",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "webp" => "image/webp",
+        "mp3" => "audio/mpeg",
+        "ogg" => "audio/ogg",
+        "wav" => "audio/wav",
+        "flac" => "audio/flac",
+        "mp4" => "video/mp4",
+        "mkv" => "video/x-matroska",
+        "webm" => "video/webm",
+        "zip" => "application/zip",
+        "tar" => "application/x-tar",
+        "gz" | "tgz" => "application/gzip",
+        "xz" | "txz" => "application/x-xz",
+        _ => "application/octet-stream",
+    }
+}
+
+/// `rwxr-x---` style text, with setuid, setgid and sticky shown as `s`/`t`.
+pub fn mode_string(mode: u32) -> String {
+    let special = [(0o4000, 's'), (0o2000, 's'), (0o1000, 't')];
+    let mut out = String::with_capacity(9);
+    for (class, (special_bit, special_char)) in special.into_iter().enumerate() {
+        let bits = (mode >> (6 - class * 3)) & 0o7;
+        out.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        out.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        out.push(match (bits & 0o1 != 0, mode & special_bit != 0) {
+            (true, true) => special_char,
+            (false, true) => special_char.to_ascii_uppercase(),
+            (true, false) => 'x',
+            (false, false) => '-',
+        });
+    }
+    out
+}
+
+/// Parses `755`, `0644` or `4755`.
+pub fn parse_octal_mode(text: &str) -> Result<u32> {
+    let text = text.trim();
+    if text.is_empty() || text.len() > 4 {
+        return Err(anyhow!("Enter a mode of three or four octal digits."));
+    }
+    u32::from_str_radix(text, 8).map_err(|_| anyhow!("{text} is not an octal mode."))
+}
+
+/// Sets or clears the execute bits. Setting grants execute to each class
+/// that can read, and at least to the owner.
+pub fn with_executable(mode: u32, executable: bool) -> u32 {
+    if executable {
+        mode | ((mode & 0o444) >> 2) | 0o100
+    } else {
+        mode & !0o111
+    }
+}
+
+#[cfg(unix)]
+pub fn set_mode(path: &Path, mode: u32) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode & 0o7777))
+        .map_err(|e| anyhow!("Cannot change permissions of {}: {e}", path.display()))
+}
+
+#[cfg(not(unix))]
+pub fn set_mode(_path: &Path, _mode: u32) -> Result<()> {
+    Err(anyhow!("Permissions are not supported on this system."))
+}
+
+#[cfg(unix)]
+pub fn set_owner(path: &Path, uid: u32, gid: u32) -> Result<()> {
+    std::os::unix::fs::chown(path, Some(uid), Some(gid))
+        .map_err(|e| anyhow!("Cannot change owner of {}: {e}", path.display()))
+}
+
+#[cfg(not(unix))]
+pub fn set_owner(_path: &Path, _uid: u32, _gid: u32) -> Result<()> {
+    Err(anyhow!("Ownership is not supported on this system."))
+}
+
+const PASSWD_PATH: &str = "/etc/passwd";
+const GROUP_PATH: &str = "/etc/group";
+
+pub fn user_name(uid: u32) -> String {
+    account_name(&read_accounts(PASSWD_PATH), uid).unwrap_or_else(|| uid.to_string())
+}
+
+pub fn group_name(gid: u32) -> String {
+    account_name(&read_accounts(GROUP_PATH), gid).unwrap_or_else(|| gid.to_string())
+}
+
+/// A user name or numeric uid.
+pub fn resolve_user(text: &str) -> Result<u32> {
+    resolve_account(&read_accounts(PASSWD_PATH), text)
+        .ok_or_else(|| anyhow!("Unknown user: {}", text.trim()))
+}
+
+/// A group name or numeric gid.
+pub fn resolve_group(text: &str) -> Result<u32> {
+    resolve_account(&read_accounts(GROUP_PATH), text)
+        .ok_or_else(|| anyhow!("Unknown group: {}", text.trim()))
+}
+
+fn read_accounts(path: &str) -> String {
+    std::fs::read_to_string(path).unwrap_or_default()
+}
+
+/// `/etc/passwd` and `/etc/group` both start with `name:password:id:`.
+fn account_entries(contents: &str) -> impl Iterator<Item = (&str, u32)> {
+    contents.lines().filter_map(|line| {
+        let mut fields = line.split(':');
+        let name = fields.next()?;
+        let id = fields.nth(1)?.parse().ok()?;
+        Some((name, id))
+    })
+}
+
+fn account_name(contents: &str, id: u32) -> Option<String> {
+    account_entries(contents)
+        .find(|(_, entry_id)| *entry_id == id)
+        .map(|(name, _)| name.to_string())
+}
+
+fn resolve_account(contents: &str, text: &str) -> Option<u32> {
+    let text = text.trim();
+    if let Ok(id) = text.parse() {
+        return Some(id);
+    }
+    account_entries(contents)
+        .find(|(name, _)| *name == text)
+        .map(|(_, id)| id)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FolderSizeProgress {
+    pub bytes: u64,
+    pub files: usize,
+    pub folders: usize,
+    pub done: bool,
+}
+
+/// Recursive folder size, counted on a worker thread. Dropping it stops
+/// the walk.
+#[derive(Debug)]
+pub struct FolderSize {
+    shared: Arc<Mutex<FolderSizeProgress>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl FolderSize {
+    pub fn start(root: PathBuf) -> Self {
+        let size = Self {
+            shared: Arc::default(),
+            cancelled: Arc::default(),
+        };
+        let shared = Arc::clone(&size.shared);
+        let cancelled = Arc::clone(&size.cancelled);
+        std::thread::spawn(move || {
+            let mut pending = vec![root];
+            let mut local = FolderSizeProgress::default();
+            while let Some(dir) = pending.pop() {
+                if cancelled.load(Ordering::Relaxed) {
+                    return;
+                }
+                let Ok(read) = std::fs::read_dir(&dir) else {
+                    continue;
+                };
+                for entry in read.flatten() {
+                    // Symlinks count as themselves and are not followed.
+                    let Ok(meta) = entry.metadata() else {
+                        continue;
+                    };
+                    if meta.is_dir() {
+                        local.folders += 1;
+                        pending.push(entry.path());
+                    } else {
+                        local.files += 1;
+                        local.bytes += meta.len();
+                    }
+                }
+                if let Ok(mut shared) = shared.lock() {
+                    *shared = local;
+                }
+            }
+            if let Ok(mut shared) = shared.lock() {
+                *shared = FolderSizeProgress {
+                    done: true,
+                    ..local
+                };
+            }
+        });
+        size
+    }
+
+    pub fn progress(&self) -> FolderSizeProgress {
+        self.shared.lock().map(|shared| *shared).unwrap_or_default()
+    }
+}
+
+impl Drop for FolderSize {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDirGuard;
+
+    #[test]
+    fn modes_format_parse_and_toggle_execute() {
+        assert_eq!(mode_string(0o755), "rwxr-xr-x");
+        assert_eq!(mode_string(0o640), "rw-r-----");
+        assert_eq!(mode_string(0o4755), "rwsr-xr-x");
+        assert_eq!(mode_string(0o1777), "rwxrwxrwt");
+        assert_eq!(mode_string(0o2644), "rw-r-Sr--");
+        assert_eq!(parse_octal_mode(" 0644 ").unwrap(), 0o644);
+        assert!(parse_octal_mode("778").is_err());
+        assert!(parse_octal_mode("").is_err());
+        assert_eq!(with_executable(0o644, true), 0o755);
+        assert_eq!(with_executable(0o600, true), 0o700);
+        assert_eq!(with_executable(0o200, true), 0o300);
+        assert_eq!(with_executable(0o755, false), 0o644);
+    }
+
+    #[test]
+    fn accounts_resolve_by_name_or_id() {
+        let passwd = "root:x:0:0:root:/root:/bin/sh\nvault:x:1001:1001::/home/vault:/bin/sh\n";
+        assert_eq!(account_name(passwd, 1001).as_deref(), Some("vault"));
+        assert_eq!(account_name(passwd, 42), None);
+        assert_eq!(resolve_account(passwd, " vault "), Some(1001));
+        assert_eq!(resolve_account(passwd, "42"), Some(42));
+        assert_eq!(resolve_account(passwd, "nobody"), None);
+    }
+
+    #[test]
+    fn properties_and_folder_size_cover_nested_files() {
+        let temp = TempDirGuard::new("properties");
+        let root = temp.path.clone();
+        std::fs::create_dir_all(root.join("sub")).unwrap();
+        std::fs::write(root.join("notes.md"), "12345").unwrap();
+        std::fs::write(root.join("sub").join("data.bin"), [0u8; 10]).unwrap();
+
+        let file = FileProperties::read(&root.join("notes.md")).unwrap();
+        assert_eq!(file.kind, PropertiesKind::File);
+        assert_eq!(file.size, 5);
+        assert_eq!(file.mime, "text/markdown");
+        assert_eq!(file.slot, Some(DefaultAppSlot::TextCode));
+        #[cfg(unix)]
+        {
+            set_mode(&root.join("notes.md"), 0o640).unwrap();
+            let file = FileProperties::read(&root.join("notes.md")).unwrap();
+            assert_eq!(file.ownership.map(|o| o.mode), Some(0o640));
+        }
+
+        let folder = FileProperties::read(&root).unwrap();
+        assert_eq!(folder.kind, PropertiesKind::Folder);
+        assert_eq!(folder.mime, "inode/directory");
+        let size = FolderSize::start(root.clone());
+        let progress = loop {
+            let progress = size.progress();
+            if progress.done {
+                break progress;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        };
+        assert_eq!(
+            (progress.bytes, progress.files, progress.folders),
+            (15, 2, 1)
+        );
+    }
+}
@@ -0,0 +1,624 @@
+//! Recursive file search.
+//!
+//! A [`FileSearch`] walks a folder tree on a worker thread and publishes
+//! matches as it finds them, so the file list fills in while the search is
+//! still running. Searches can be saved as small `.search` files, which the
+//! file manager opens like folders showing the current results.
+
+use crate::{path_display_name, FileEntryRow};
+use anyhow::{anyhow, Result};
+use regex_lite::Regex;
+use robcos_shared::config::base_dir;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+pub const SAVED_SEARCH_EXT: &str = "search";
+const SAVED_SEARCH_HEADER: &str = "[Saved Search]";
+/// The list stops growing past this many matches.
+const MAX_RESULTS: usize = 5000;
+/// Only the start of large files is checked for content matches.
+const CONTENT_SCAN_LIMIT: u64 = 16 * 1024 * 1024;
+/// Files with a NUL byte in this prefix are treated as binary and skipped.
+const BINARY_SNIFF_LEN: usize = 8192;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchKind {
+    #[default]
+    Any,
+    Folders,
+    Files,
+    Documents,
+    Images,
+    Audio,
+    Video,
+    Archives,
+}
+
+impl SearchKind {
+    pub const ALL: [Self; 8] = [
+        Self::Any,
+        Self::Folders,
+        Self::Files,
+        Self::Documents,
+        Self::Images,
+        Self::Audio,
+        Self::Video,
+        Self::Archives,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Any => "Any",
+            Self::Folders => "Folders",
+            Self::Files => "Files",
+            Self::Documents => "Documents",
+            Self::Images => "Images",
+            Self::Audio => "Audio",
+            Self::Video => "Video",
+            Self::Archives => "Archives",
+        }
+    }
+
+    fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.label() == label)
+    }
+
+    fn matches(self, row: &FileEntryRow) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Folders => row.is_dir,
+            Self::Files => !row.is_dir,
+            Self::Documents => !row.is_dir && row.icon() == "[TXT]",
+            Self::Images => !row.is_dir && row.icon() == "[IMG]",
+            Self::Audio => !row.is_dir && row.icon() == "[AUD]",
+            Self::Video => !row.is_dir && row.icon() == "[VID]",
+            Self::Archives => !row.is_dir && row.icon() == "[ARC]",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchSpec {
+    pub root: PathBuf,
+    /// Name pattern. A glob (`*.rs`, `report-??.txt`) unless `use_regex` is
+    /// set; plain text without wildcards matches anywhere in the name.
+    pub name: String,
+    pub use_regex: bool,
+    /// Text to look for inside files, ignoring case; empty skips the check.
+    pub contents: String,
+    pub min_size: Option<u64>,
+    pub max_size: Option<u64>,
+    pub modified_within_days: Option<u64>,
+    pub kind: SearchKind,
+    pub include_hidden: bool,
+}
+
+impl SearchSpec {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            name: String::new(),
+            use_regex: false,
+            contents: String::new(),
+            min_size: None,
+            max_size: None,
+            modified_within_days: None,
+            kind: SearchKind::Any,
+            include_hidden: false,
+        }
+    }
+
+    /// Short description for titles, e.g. `"*.rs" in /home/user`.
+    pub fn describe(&self) -> String {
+        let what = match (self.name.trim(), self.contents.trim()) {
+            ("", "") => self.kind.label().to_string(),
+            (name, "") => format!("\"{name}\""),
+            ("", text) => format!("containing \"{text}\""),
+            (name, text) => format!("\"{name}\" containing \"{text}\""),
+        };
+        format!("{what} in {}", self.root.display())
+    }
+}
+
+/// Parses sizes like `500`, `10K`, `1.5 MB` or `2g`. Empty text means no limit.
+pub fn parse_size(text: &str) -> Result<Option<u64>> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    let split = text
+        .find(|ch: char| !(ch.is_ascii_digit() || ch == '.'))
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let value: f64 = number
+        .parse()
+        .map_err(|_| anyhow!("Invalid size: {text}"))?;
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" => 1024,
+        "m" | "mb" => 1024 * 1024,
+        "g" | "gb" => 1024 * 1024 * 1024,
+        _ => return Err(anyhow!("Invalid size unit: {text}")),
+    };
+    Ok(Some((value * multiplier as f64) as u64))
+}
+
+/// Turns a glob into an anchored, case-insensitive regex. Text without
+/// wildcards becomes an unanchored "contains" match.
+fn glob_to_regex(glob: &str) -> String {
+    let has_wildcard = glob.contains(['*', '?', '[']);
+    let mut out = String::from("(?i)");
+    if has_wildcard {
+        out.push('^');
+    }
+    let mut chars = glob.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '*' => out.push_str(".*"),
+            '?' => out.push('.'),
+            '[' => {
+                out.push('[');
+                if chars.peek() == Some(&'!') {
+                    chars.next();
+                    out.push('^');
+                }
+                for ch in chars.by_ref() {
+                    if ch == ']' {
+                        break;
+                    }
+                    if ch == '\\' || ch == '[' {
+                        out.push('\\');
+                    }
+                    out.push(ch);
+                }
+                out.push(']');
+            }
+            _ => out.push_str(&regex_lite::escape(&ch.to_string())),
+        }
+    }
+    if has_wildcard {
+        out.push('$');
+    }
+    out
+}
+
+struct SearchMatcher {
+    name: Option<Regex>,
+    contents: String,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+    modified_after: Option<SystemTime>,
+    kind: SearchKind,
+}
+
+impl SearchMatcher {
+    fn new(spec: &SearchSpec) -> Result<Self> {
+        let pattern = spec.name.trim();
+        let name = if pattern.is_empty() {
+            None
+        } else {
+            let source = if spec.use_regex {
+                pattern.to_string()
+            } else {
+                glob_to_regex(pattern)
+            };
+            Some(Regex::new(&source).map_err(|e| anyhow!("Invalid pattern: {e}"))?)
+        };
+        if let (Some(min), Some(max)) = (spec.min_size, spec.max_size) {
+            if min > max {
+                return Err(anyhow!("Minimum size is larger than the maximum."));
+            }
+        }
+        Ok(Self {
+            name,
+            contents: spec.contents.trim().to_lowercase(),
+            min_size: spec.min_size,
+            max_size: spec.max_size,
+            modified_after: spec
+                .modified_within_days
+                .and_then(|days| SystemTime::now().checked_sub(Duration::from_secs(days * 86_400))),
+            kind: spec.kind,
+        })
+    }
+
+    fn matches(&self, row: &FileEntryRow, meta: &std::fs::Metadata) -> bool {
+        if !self.kind.matches(row) {
+            return false;
+        }
+        if self
+            .name
+            .as_ref()
+            .is_some_and(|re| !re.is_match(&row.label))
+        {
+            return false;
+        }
+        let has_size_filter = self.min_size.is_some() || self.max_size.is_some();
+        if has_size_filter || !self.contents.is_empty() {
+            if row.is_dir {
+                return false;
+            }
+            let size = meta.len();
+            if self.min_size.is_some_and(|min| size < min)
+                || self.max_size.is_some_and(|max| size > max)
+            {
+                return false;
+            }
+        }
+        if let Some(after) = self.modified_after {
+            if meta.modified().map_or(true, |modified| modified < after) {
+                return false;
+            }
+        }
+        self.contents.is_empty() || file_contains(&row.path, &self.contents)
+    }
+}
+
+fn file_contains(path: &Path, needle: &str) -> bool {
+    let Ok(file) = std::fs::File::open(path) else {
+        return false;
+    };
+    let mut buf = Vec::new();
+    if file.take(CONTENT_SCAN_LIMIT).read_to_end(&mut buf).is_err() {
+        return false;
+    }
+    if buf.iter().take(BINARY_SNIFF_LEN).any(|byte| *byte == 0) {
+        return false;
+    }
+    String::from_utf8_lossy(&buf)
+        .to_lowercase()
+        .contains(needle)
+}
+
+#[derive(Debug, Default)]
+struct SearchShared {
+    results: Vec<FileEntryRow>,
+    scanned: usize,
+    done: bool,
+    truncated: bool,
+}
+
+/// A running or finished search. Clones share the same results.
+#[derive(Debug, Clone)]
+pub struct FileSearch {
+    spec: SearchSpec,
+    shared: Arc<Mutex<SearchShared>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl FileSearch {
+    /// Checks the spec and starts walking `spec.root` in the background.
+    pub fn start(spec: SearchSpec) -> Result<Self> {
+        let matcher = SearchMatcher::new(&spec)?;
+        if !spec.root.is_dir() {
+            return Err(anyhow!("Folder not found: {}", spec.root.display()));
+        }
+        let search = Self {
+            spec,
+            shared: Arc::default(),
+            cancelled: Arc::default(),
+        };
+        let root = search.spec.root.clone();
+        let include_hidden = search.spec.include_hidden;
+        let shared = Arc::clone(&search.shared);
+        let cancelled = Arc::clone(&search.cancelled);
+        std::thread::spawn(move || {
+            walk(&root, include_hidden, &matcher, &shared, &cancelled);
+            if let Ok(mut shared) = shared.lock() {
+                shared.done = true;
+            }
+        });
+        Ok(search)
+    }
+
+    pub fn spec(&self) -> &SearchSpec {
+        &self.spec
+    }
+
+    pub fn results(&self) -> Vec<FileEntryRow> {
+        self.shared
+            .lock()
+            .map(|shared| shared.results.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.shared.lock().is_ok_and(|shared| !shared.done)
+    }
+
+    /// Stops the walk; results found so far stay available.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// One-line progress, e.g. "12 found, 3400 scanned - searching...".
+    pub fn summary(&self) -> String {
+        let Ok(shared) = self.shared.lock() else {
+            return String::new();
+        };
+        let found = shared.results.len();
+        if !shared.done {
+            format!("{found} found, {} scanned - searching...", shared.scanned)
+        } else if shared.truncated {
+            format!("first {found} matches shown")
+        } else if self.cancelled.load(Ordering::Relaxed) {
+            format!("{found} found - stopped")
+        } else {
+            format!("{found} found")
+        }
+    }
+}
+
+fn walk(
+    root: &Path,
+    include_hidden: bool,
+    matcher: &SearchMatcher,
+    shared: &Mutex<SearchShared>,
+    cancelled: &AtomicBool,
+) {
+    let mut pending_dirs = vec![root.to_path_buf()];
+    while let Some(dir) = pending_dirs.pop() {
+        let Ok(read_dir) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut entries: Vec<_> = read_dir.flatten().collect();
+        entries.sort_by_key(|entry| entry.file_name());
+        let mut found = Vec::new();
+        let mut subdirs = Vec::new();
+        for entry in &entries {
+            if cancelled.load(Ordering::Relaxed) {
+                return;
+            }
+            let label = entry.file_name().to_string_lossy().to_string();
+            if !include_hidden && label.starts_with('.') {
+                continue;
+            }
+            let path = entry.path();
+            // Symlinked folders are listed but not followed, so links
+            // pointing back up the tree cannot loop the walk.
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                subdirs.push(path.clone());
+            }
+            let Ok(meta) = std::fs::metadata(&path) else {
+                continue;
+            };
+            let row = FileEntryRow {
+                path,
+                label,
+                is_dir: meta.is_dir(),
+            };
+            if matcher.matches(&row, &meta) {
+                found.push(row);
+            }
+        }
+        let Ok(mut shared) = shared.lock() else {
+            return;
+        };
+        shared.scanned += entries.len();
+        let room = MAX_RESULTS.saturating_sub(shared.results.len());
+        if found.len() > room {
+            found.truncate(room);
+            shared.truncated = true;
+        }
+        shared.results.extend(found);
+        if shared.truncated {
+            return;
+        }
+        drop(shared);
+        // Reversed so the stack visits subfolders in name order.
+        pending_dirs.extend(subdirs.into_iter().rev());
+    }
+}
+
+pub fn saved_searches_dir() -> PathBuf {
+    base_dir().join("saved_searches")
+}
+
+pub fn is_saved_search(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(SAVED_SEARCH_EXT))
+        && path.is_file()
+}
+
+/// Saved searches in `dir`, sorted by name.
+pub fn saved_searches(dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .ok()
+        .into_iter()
+        .flat_map(|iter| iter.flatten())
+        .map(|entry| entry.path())
+        .filter(|path| is_saved_search(path))
+        .collect();
+    paths.sort_by_key(|path| path_display_name(path).to_lowercase());
+    paths
+}
+
+/// Display name of a saved search: its file name without `.search`.
+pub fn saved_search_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| path_display_name(path))
+}
+
+/// Writes `spec` as `<dir>/<name>.search`, replacing a saved search with the
+/// same name.
+pub fn save_search(dir: &Path, name: &str, spec: &SearchSpec) -> Result<PathBuf> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(anyhow!("Name cannot be empty."));
+    }
+    if name.contains('/') || name.contains('\\') {
+        return Err(anyhow!("Name cannot contain path separators."));
+    }
+    std::fs::create_dir_all(dir).map_err(|e| anyhow!("Failed creating {}: {e}", dir.display()))?;
+    let size = |value: Option<u64>| value.map(|v| v.to_string()).unwrap_or_default();
+    let single_line = |text: &str| text.replace(['\n', '\r'], " ");
+    let body = format!(
+        "{SAVED_SEARCH_HEADER}\nRoot={}\nName={}\nRegex={}\nContents={}\nMinSize={}\nMaxSize={}\nModifiedWithinDays={}\nKind={}\nHidden={}\n",
+        spec.root.display(),
+        single_line(&spec.name),
+        spec.use_regex,
+        single_line(&spec.contents),
+        size(spec.min_size),
+        size(spec.max_size),
+        size(spec.modified_within_days),
+        spec.kind.label(),
+        spec.include_hidden,
+    );
+    let path = dir.join(format!("{name}.{SAVED_SEARCH_EXT}"));
+    std::fs::write(&path, body).map_err(|e| anyhow!("Failed saving {}: {e}", path.display()))?;
+    Ok(path)
+}
+
+pub fn load_saved_search(path: &Path) -> Result<SearchSpec> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Failed reading {}: {e}", path.display()))?;
+    let mut lines = text.lines();
+    if lines.next().map(str::trim) != Some(SAVED_SEARCH_HEADER) {
+        return Err(anyhow!("Not a saved search: {}", path.display()));
+    }
+    let mut spec = SearchSpec::new(PathBuf::new());
+    let number = |value: &str| value.trim().parse::<u64>().ok();
+    for line in lines {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match key.trim() {
+            "Root" => spec.root = PathBuf::from(value),
+            "Name" => spec.name = value.to_string(),
+            "Regex" => spec.use_regex = value.trim() == "true",
+            "Contents" => spec.contents = value.to_string(),
+            "MinSize" => spec.min_size = number(value),
+            "MaxSize" => spec.max_size = number(value),
+            "ModifiedWithinDays" => spec.modified_within_days = number(value),
+            "Kind" => spec.kind = SearchKind::from_label(value.trim()).unwrap_or_default(),
+            "Hidden" => spec.include_hidden = value.trim() == "true",
+            _ => {}
+        }
+    }
+    if spec.root.as_os_str().is_empty() {
+        return Err(anyhow!("Saved search has no folder: {}", path.display()));
+    }
+    Ok(spec)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDirGuard;
+
+    fn seeded_root(prefix: &str) -> TempDirGuard {
+        let temp = TempDirGuard::new(&format!("search_{prefix}"));
+        let root = &temp.path;
+        std::fs::create_dir_all(root.join("src/nested")).unwrap();
+        std::fs::create_dir_all(root.join(".hidden")).unwrap();
+        std::fs::write(root.join("README.md"), "Read me first").unwrap();
+        std::fs::write(root.join("src/main.rs"), "fn main() { todo!() }").unwrap();
+        std::fs::write(root.join("src/nested/lib.rs"), "// TODO: tidy this up").unwrap();
+        std::fs::write(root.join("src/nested/logo.png"), [0u8, 1, 2, 3]).unwrap();
+        std::fs::write(root.join(".hidden/secret.rs"), "todo").unwrap();
+        temp
+    }
+
+    fn finished(spec: SearchSpec) -> Vec<String> {
+        let search = FileSearch::start(spec).unwrap();
+        while search.is_running() {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        let mut labels: Vec<String> = search.results().into_iter().map(|row| row.label).collect();
+        labels.sort();
+        labels
+    }
+
+    #[test]
+    fn search_matches_names_contents_and_filters_recursively() {
+        let temp = seeded_root("filters");
+        let root = temp.path.clone();
+        let spec = |name: &str| SearchSpec {
+            name: name.to_string(),
+            ..SearchSpec::new(root.clone())
+        };
+
+        assert_eq!(finished(spec("*.RS")), vec!["lib.rs", "main.rs"]);
+        assert_eq!(finished(spec("nest")), vec!["nested"]);
+        assert_eq!(
+            finished(SearchSpec {
+                use_regex: true,
+                ..spec("^(main|logo)\\.")
+            }),
+            vec!["logo.png", "main.rs"]
+        );
+        assert_eq!(
+            finished(SearchSpec {
+                contents: "todo".to_string(),
+                ..spec("")
+            }),
+            vec!["lib.rs", "main.rs"]
+        );
+        assert_eq!(
+            finished(SearchSpec {
+                include_hidden: true,
+                contents: "todo".to_string(),
+                ..spec("")
+            }),
+            vec!["lib.rs", "main.rs", "secret.rs"]
+        );
+        assert_eq!(
+            finished(SearchSpec {
+                kind: SearchKind::Images,
+                ..spec("")
+            }),
+            vec!["logo.png"]
+        );
+        assert_eq!(
+            finished(SearchSpec {
+                max_size: Some(16),
+                modified_within_days: Some(1),
+                ..spec("")
+            }),
+            vec!["README.md", "logo.png"]
+        );
+        assert!(FileSearch::start(SearchSpec {
+            use_regex: true,
+            ..spec("(")
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn saved_search_round_trips_through_its_file() {
+        let temp = seeded_root("saved");
+        let root = temp.path.clone();
+        let spec = SearchSpec {
+            name: "*.rs".to_string(),
+            contents: "todo".to_string(),
+            min_size: Some(4),
+            kind: SearchKind::Documents,
+            ..SearchSpec::new(root.clone())
+        };
+        let dir = root.join("searches");
+        let path = save_search(&dir, "Rust TODOs", &spec).unwrap();
+
+        assert!(is_saved_search(&path));
+        assert_eq!(saved_search_name(&path), "Rust TODOs");
+        assert_eq!(saved_searches(&dir), vec![path.clone()]);
+        assert_eq!(load_saved_search(&path).unwrap(), spec);
+        assert!(save_search(&dir, "a/b", &spec).is_err());
+    }
+
+    #[test]
+    fn parse_size_accepts_units() {
+        assert_eq!(parse_size("").unwrap(), None);
+        assert_eq!(parse_size("512").unwrap(), Some(512));
+        assert_eq!(parse_size("10K").unwrap(), Some(10 * 1024));
+        assert_eq!(parse_size("1.5 MB").unwrap(), Some(1024 * 1024 * 3 / 2));
+        assert!(parse_size("ten").is_err());
+        assert!(parse_size("5 parsecs").is_err());
+    }
+}
@@ -0,0 +1,31 @@
+//! Helpers shared by the unit tests in this crate.
+
+use std::path::PathBuf;
+
+/// A fresh folder under the system temp dir, removed again on drop so a
+/// failing test does not leave it behind.
+pub(crate) struct TempDirGuard {
+    pub(crate) path: PathBuf,
+}
+
+impl TempDirGuard {
+    pub(crate) fn new(prefix: &str) -> Self {
+        let unique = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("test clock")
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!(
+            "robco_native_file_manager_app_{prefix}_{}_{}",
+            std::process::id(),
+            unique
+        ));
+        std::fs::create_dir_all(&path).expect("create temp test dir");
+        Self { path }
+    }
+}
+
+impl Drop for TempDirGuard {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
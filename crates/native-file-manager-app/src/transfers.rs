@@ -0,0 +1,1137 @@
+//! Background file operations.
+//!
+//! Copies, moves, trashing and archive work run as queued jobs, one at a time, on a worker
+//! thread. The UI polls the [`TransferQueue`] each frame for progress and
+//! conflict prompts, and gets a [`TransferOutcome`] when a job ends. Undo
+//! records are only written then, by `FileManagerEditRuntime::finish_transfer`.
+
+use crate::trash::Trash;
+use crate::{
+    archive, can_move_path_to_dir, copy_or_extract, format_bytes_human, path_display_name,
+    remove_path_recursive, unique_copy_path_in_dir, unique_path_in_dir, FileManagerEditOp,
+};
+use std::collections::VecDeque;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const COPY_CHUNK_BYTES: usize = 256 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferKind {
+    Copy,
+    Move,
+    Trash,
+    /// Unpacks archive files, or entries of an archive, into `target_dir`.
+    Extract,
+    /// Packs the sources into the new archive at `target_dir`.
+    Compress,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferRequest {
+    pub kind: TransferKind,
+    pub sources: Vec<PathBuf>,
+    /// Destination folder; the archive to create for
+    /// [`TransferKind::Compress`], unused for [`TransferKind::Trash`].
+    pub target_dir: PathBuf,
+}
+
+impl TransferRequest {
+    pub fn copy(sources: Vec<PathBuf>, target_dir: PathBuf) -> Self {
+        Self {
+            kind: TransferKind::Copy,
+            sources,
+            target_dir,
+        }
+    }
+
+    pub fn move_to(sources: Vec<PathBuf>, target_dir: PathBuf) -> Self {
+        Self {
+            kind: TransferKind::Move,
+            sources,
+            target_dir,
+        }
+    }
+
+    pub fn trash(sources: Vec<PathBuf>) -> Self {
+        Self {
+            kind: TransferKind::Trash,
+            sources,
+            target_dir: PathBuf::new(),
+        }
+    }
+
+    pub fn extract(sources: Vec<PathBuf>, target_dir: PathBuf) -> Self {
+        Self {
+            kind: TransferKind::Extract,
+            sources,
+            target_dir,
+        }
+    }
+
+    pub fn compress(sources: Vec<PathBuf>, archive: PathBuf) -> Self {
+        Self {
+            kind: TransferKind::Compress,
+            sources,
+            target_dir: archive,
+        }
+    }
+
+    pub fn title(&self) -> String {
+        let noun = match self.sources.as_slice() {
+            [source] => path_display_name(source),
+            sources => format!("{} items", sources.len()),
+        };
+        let target = path_display_name(&self.target_dir);
+        match self.kind {
+            TransferKind::Copy => format!("Copying {noun} to {target}"),
+            TransferKind::Move => format!("Moving {noun} to {target}"),
+            TransferKind::Trash => format!("Moving {noun} to trash"),
+            TransferKind::Extract => format!("Extracting {noun} to {target}"),
+            TransferKind::Compress => format!("Compressing {noun} into {target}"),
+        }
+    }
+}
+
+/// Answer to "the destination already exists".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictChoice {
+    Skip,
+    /// Replace the existing item; it is moved to the trash first.
+    Overwrite,
+    /// Keep both under a numbered name.
+    KeepBoth,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferConflict {
+    pub job_id: u64,
+    pub src: PathBuf,
+    pub dst: PathBuf,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransferProgress {
+    pub items_done: usize,
+    pub items_total: usize,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    /// Time spent working, not counting pauses and open prompts.
+    pub elapsed: Duration,
+}
+
+impl TransferProgress {
+    pub fn fraction(&self) -> f32 {
+        if self.bytes_total > 0 {
+            self.bytes_done as f32 / self.bytes_total as f32
+        } else if self.items_total > 0 {
+            self.items_done as f32 / self.items_total as f32
+        } else {
+            0.0
+        }
+    }
+
+    /// Remaining time at the average rate so far.
+    pub fn eta(&self) -> Option<Duration> {
+        if self.bytes_done == 0 || self.elapsed.is_zero() {
+            return None;
+        }
+        let rate = self.bytes_done as f64 / self.elapsed.as_secs_f64();
+        let remaining = self.bytes_total.saturating_sub(self.bytes_done) as f64;
+        Some(Duration::from_secs_f64(remaining / rate))
+    }
+
+    pub fn summary(&self) -> String {
+        let mut text = format!(
+            "{} of {} - {} of {} items",
+            format_bytes_human(self.bytes_done),
+            format_bytes_human(self.bytes_total),
+            self.items_done,
+            self.items_total
+        );
+        if let Some(eta) = self.eta() {
+            let secs = eta.as_secs();
+            if secs >= 60 {
+                text.push_str(&format!(" - {}m {}s left", secs / 60, secs % 60));
+            } else {
+                text.push_str(&format!(" - {secs}s left"));
+            }
+        }
+        text
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferState {
+    Queued,
+    Running,
+    Paused,
+    WaitingForAnswer,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferJobView {
+    pub id: u64,
+    pub title: String,
+    pub state: TransferState,
+    pub progress: TransferProgress,
+}
+
+/// What a finished job did. `ops` holds only the completed items, so a
+/// cancelled or failed job can still be undone as far as it got.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferOutcome {
+    /// Id returned by [`TransferQueue::enqueue`].
+    pub id: u64,
+    pub kind: TransferKind,
+    pub ops: Vec<FileManagerEditOp>,
+    pub skipped: usize,
+    /// Old trash items deleted because the trash grew past its limit.
+    pub purged: usize,
+    pub cancelled: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Default)]
+struct JobControl {
+    progress: TransferProgress,
+    paused: bool,
+    cancelled: bool,
+    conflict: Option<(PathBuf, PathBuf)>,
+    answer: Option<(ConflictChoice, bool)>,
+}
+
+#[derive(Default)]
+struct JobShared {
+    control: Mutex<JobControl>,
+    wake: Condvar,
+}
+
+impl JobShared {
+    fn lock(&self) -> MutexGuard<'_, JobControl> {
+        self.control
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+struct ActiveTransfer {
+    id: u64,
+    title: String,
+    kind: TransferKind,
+    shared: Arc<JobShared>,
+    done: Receiver<TransferOutcome>,
+}
+
+pub struct TransferQueue {
+    /// Receives trashed items and items replaced by "Overwrite".
+    trash: Trash,
+    /// Trash size limit applied after each job that trashed something;
+    /// 0 leaves the trash alone.
+    trash_max_bytes: u64,
+    next_id: u64,
+    pending: VecDeque<(u64, TransferRequest)>,
+    active: Option<ActiveTransfer>,
+}
+
+impl Default for TransferQueue {
+    fn default() -> Self {
+        Self::new(Trash::user())
+    }
+}
+
+impl TransferQueue {
+    pub fn new(trash: Trash) -> Self {
+        Self {
+            trash,
+            trash_max_bytes: 0,
+            next_id: 1,
+            pending: VecDeque::new(),
+            active: None,
+        }
+    }
+
+    pub fn trash(&self) -> &Trash {
+        &self.trash
+    }
+
+    /// Sets the trash size limit for jobs started from now on.
+    pub fn set_trash_max_bytes(&mut self, max_bytes: u64) {
+        self.trash_max_bytes = max_bytes;
+    }
+
+    pub fn enqueue(&mut self, request: TransferRequest) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.pending.push_back((id, request));
+        self.start_next();
+        id
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.active.is_none() && self.pending.is_empty()
+    }
+
+    /// Returns the outcome of a job that ended since the last call and
+    /// starts the next queued one.
+    pub fn poll(&mut self) -> Option<TransferOutcome> {
+        let active = self.active.as_ref()?;
+        let outcome = match active.done.try_recv() {
+            Ok(outcome) => outcome,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => TransferOutcome {
+                id: active.id,
+                kind: active.kind,
+                ops: Vec::new(),
+                skipped: 0,
+                purged: 0,
+                cancelled: false,
+                error: Some("Transfer stopped unexpectedly.".to_string()),
+            },
+        };
+        self.active = None;
+        self.start_next();
+        Some(outcome)
+    }
+
+    pub fn jobs(&self) -> Vec<TransferJobView> {
+        let mut jobs = Vec::new();
+        if let Some(active) = &self.active {
+            let control = active.shared.lock();
+            let state = if control.conflict.is_some() {
+                TransferState::WaitingForAnswer
+            } else if control.paused {
+                TransferState::Paused
+            } else {
+                TransferState::Running
+            };
+            jobs.push(TransferJobView {
+                id: active.id,
+                title: active.title.clone(),
+                state,
+                progress: control.progress.clone(),
+            });
+        }
+        jobs.extend(self.pending.iter().map(|(id, request)| TransferJobView {
+            id: *id,
+            title: request.title(),
+            state: TransferState::Queued,
+            progress: TransferProgress::default(),
+        }));
+        jobs
+    }
+
+    pub fn conflict(&self) -> Option<TransferConflict> {
+        let active = self.active.as_ref()?;
+        let control = active.shared.lock();
+        control
+            .conflict
+            .as_ref()
+            .map(|(src, dst)| TransferConflict {
+                job_id: active.id,
+                src: src.clone(),
+                dst: dst.clone(),
+            })
+    }
+
+    pub fn answer_conflict(&mut self, choice: ConflictChoice, apply_to_all: bool) {
+        if let Some(active) = &self.active {
+            let mut control = active.shared.lock();
+            if control.conflict.take().is_some() {
+                control.answer = Some((choice, apply_to_all));
+                active.shared.wake.notify_all();
+            }
+        }
+    }
+
+    pub fn toggle_pause(&mut self, id: u64) {
+        if let Some(active) = self.active.as_ref().filter(|active| active.id == id) {
+            let mut control = active.shared.lock();
+            control.paused = !control.paused;
+            active.shared.wake.notify_all();
+        }
+    }
+
+    /// Drops a queued job, or stops the running one after the current chunk.
+    /// A partly copied item is removed again.
+    pub fn cancel(&mut self, id: u64) {
+        self.pending.retain(|(pending_id, _)| *pending_id != id);
+        if let Some(active) = self.active.as_ref().filter(|active| active.id == id) {
+            let mut control = active.shared.lock();
+            control.cancelled = true;
+            active.shared.wake.notify_all();
+        }
+    }
+
+    fn start_next(&mut self) {
+        if self.active.is_some() {
+            return;
+        }
+        let Some((id, request)) = self.pending.pop_front() else {
+            return;
+        };
+        let shared = Arc::new(JobShared::default());
+        let (tx, rx) = mpsc::channel();
+        let title = request.title();
+        let kind = request.kind;
+        let worker = Worker {
+            id,
+            shared: shared.clone(),
+            trash: self.trash.clone(),
+            trash_max_bytes: self.trash_max_bytes,
+            progress: TransferProgress::default(),
+            started: Instant::now(),
+            waited: Duration::ZERO,
+            apply_to_all: None,
+            ops: Vec::new(),
+            skipped: 0,
+        };
+        thread::spawn(move || {
+            let _ = tx.send(worker.run(request));
+        });
+        self.active = Some(ActiveTransfer {
+            id,
+            title,
+            kind,
+            shared,
+            done: rx,
+        });
+    }
+}
+
+enum Interrupt {
+    Cancelled,
+    Failed(anyhow::Error),
+}
+
+impl From<anyhow::Error> for Interrupt {
+    fn from(err: anyhow::Error) -> Self {
+        Self::Failed(err)
+    }
+}
+
+struct Worker {
+    id: u64,
+    shared: Arc<JobShared>,
+    trash: Trash,
+    trash_max_bytes: u64,
+    progress: TransferProgress,
+    started: Instant,
+    waited: Duration,
+    apply_to_all: Option<ConflictChoice>,
+    ops: Vec<FileManagerEditOp>,
+    skipped: usize,
+}
+
+impl Worker {
+    fn run(mut self, request: TransferRequest) -> TransferOutcome {
+        let sizes: Vec<(u64, usize)> = request
+            .sources
+            .iter()
+            .map(|src| match request.kind {
+                TransferKind::Extract => archive::extract_size(src),
+                _ => scan(src),
+            })
+            .collect();
+        self.progress.bytes_total = sizes.iter().map(|(bytes, _)| bytes).sum();
+        self.progress.items_total = sizes.iter().map(|(_, items)| items).sum();
+        self.publish();
+
+        let mut interrupt = None;
+        if request.kind == TransferKind::Compress {
+            // One archive from all sources, so there is only one item.
+            match self.checkpoint().and_then(|_| self.compress(&request)) {
+                Ok(()) => {
+                    self.progress.bytes_done = self.progress.bytes_total;
+                    self.progress.items_done = self.progress.items_total;
+                    self.publish();
+                }
+                Err(err) => interrupt = Some(err),
+            }
+        } else {
+            for (src, size) in request.sources.iter().zip(sizes) {
+                let before = (self.progress.bytes_done, self.progress.items_done);
+                let result = self.checkpoint().and_then(|_| self.run_item(&request, src));
+                match result {
+                    Ok(()) => {
+                        // Keep totals exact when the scan and the copy disagree.
+                        self.progress.bytes_done = before.0 + size.0;
+                        self.progress.items_done = before.1 + size.1;
+                        self.publish();
+                    }
+                    Err(err) => {
+                        interrupt = Some(err);
+                        break;
+                    }
+                }
+            }
+        }
+        let (cancelled, error) = match interrupt {
+            None => (false, None),
+            Some(Interrupt::Cancelled) => (true, None),
+            Some(Interrupt::Failed(err)) => (false, Some(err.to_string())),
+        };
+        // Sizing the trash walks every item in it, so purge here rather
+        // than on the UI thread. Items this job trashed are kept.
+        let trashed: Vec<String> = self
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                FileManagerEditOp::Trashed { name, .. } => Some(name.clone()),
+                _ => None,
+            })
+            .collect();
+        let purged = if trashed.is_empty() {
+            0
+        } else {
+            self.trash.purge_to_limit(self.trash_max_bytes, &trashed)
+        };
+        TransferOutcome {
+            id: self.id,
+            kind: request.kind,
+            ops: self.ops,
+            skipped: self.skipped,
+            purged,
+            cancelled,
+            error,
+        }
+    }
+
+    fn run_item(&mut self, request: &TransferRequest, src: &Path) -> Result<(), Interrupt> {
+        let in_archive = archive::is_inside_archive(src);
+        if !path_exists(src) && !in_archive {
+            return Ok(());
+        }
+        let target_dir = request.target_dir.as_path();
+        match request.kind {
+            TransferKind::Trash => {
+                let name = self.trash.trash(src)?;
+                self.ops.push(FileManagerEditOp::Trashed {
+                    trash_root: self.trash.root().to_path_buf(),
+                    original: src.to_path_buf(),
+                    name,
+                });
+            }
+            TransferKind::Copy => {
+                if src.is_dir() && target_dir.starts_with(src) {
+                    return Err(anyhow::anyhow!("Cannot copy a folder into itself.").into());
+                }
+                let name = path_display_name(src);
+                let mut dst = target_dir.join(&name);
+                if dst == src {
+                    dst = unique_copy_path_in_dir(target_dir, &name, true);
+                } else if path_exists(&dst) {
+                    match self.resolve_conflict(src, &dst)? {
+                        None => return Ok(()),
+                        Some(ConflictChoice::KeepBoth) => {
+                            dst = unique_copy_path_in_dir(target_dir, &name, false);
+                        }
+                        Some(_) => {}
+                    }
+                }
+                if in_archive {
+                    copy_or_extract(src, &dst)?;
+                } else {
+                    self.copy_cleaning_up(src, &dst)?;
+                }
+                self.ops.push(FileManagerEditOp::CopyCreated {
+                    src: src.to_path_buf(),
+                    dst,
+                });
+            }
+            TransferKind::Move => {
+                if in_archive || !can_move_path_to_dir(src, target_dir) {
+                    return Ok(());
+                }
+                let name = path_display_name(src);
+                let mut dst = target_dir.join(&name);
+                if path_exists(&dst) {
+                    match self.resolve_conflict(src, &dst)? {
+                        None => return Ok(()),
+                        Some(ConflictChoice::KeepBoth) => {
+                            dst = unique_path_in_dir(target_dir, &name);
+                        }
+                        Some(_) => {}
+                    }
+                }
+                if std::fs::rename(src, &dst).is_err() {
+                    self.copy_cleaning_up(src, &dst)?;
+                    remove_path_recursive(src)?;
+                }
+                self.ops.push(FileManagerEditOp::Moved {
+                    from: src.to_path_buf(),
+                    to: dst,
+                });
+            }
+            TransferKind::Extract => {
+                let Some((archive_path, inner)) = archive::split_archive_path(src) else {
+                    return Err(anyhow::anyhow!("{} is not an archive.", src.display()).into());
+                };
+                let whole = inner.as_os_str().is_empty();
+                let name = match archive::ArchiveKind::from_path(src).filter(|_| whole) {
+                    Some(kind) => kind.strip_extension(&path_display_name(src)),
+                    None => path_display_name(src),
+                };
+                let dst = unique_path_in_dir(target_dir, &name);
+                let result = archive::extract_entry_with_progress(
+                    &archive_path,
+                    &inner,
+                    &dst,
+                    &mut |bytes| self.advance(bytes),
+                );
+                if let Err(err) = result {
+                    let _ = remove_path_recursive(&dst);
+                    return Err(self.interrupted(err));
+                }
+                self.ops.push(if whole {
+                    FileManagerEditOp::Extracted {
+                        archive: archive_path,
+                        dst,
+                    }
+                } else {
+                    FileManagerEditOp::CopyCreated {
+                        src: src.to_path_buf(),
+                        dst,
+                    }
+                });
+            }
+            TransferKind::Compress => self.compress(request)?,
+        }
+        Ok(())
+    }
+
+    /// Packs every source into the archive at `request.target_dir`. A
+    /// partly written archive is removed again.
+    fn compress(&mut self, request: &TransferRequest) -> Result<(), Interrupt> {
+        let dst = request.target_dir.clone();
+        let result = archive::create_archive_with_progress(&dst, &request.sources, &mut |bytes| {
+            self.advance(bytes)
+        });
+        if let Err(err) = result {
+            return Err(self.interrupted(err));
+        }
+        self.ops.push(FileManagerEditOp::ArchiveCreated {
+            sources: request.sources.clone(),
+            dst,
+        });
+        Ok(())
+    }
+
+    /// Progress callback for archive work: counts `bytes`, waits while
+    /// paused and returns `false` once the job is cancelled.
+    fn advance(&mut self, bytes: u64) -> bool {
+        self.progress.bytes_done += bytes;
+        self.publish();
+        self.checkpoint().is_ok()
+    }
+
+    /// Archive errors caused by a cancel become [`Interrupt::Cancelled`].
+    fn interrupted(&self, err: anyhow::Error) -> Interrupt {
+        if self.shared.lock().cancelled {
+            Interrupt::Cancelled
+        } else {
+            Interrupt::Failed(err)
+        }
+    }
+
+    /// Asks the UI what to do about an existing `dst`. `None` means skip;
+    /// for overwrite the existing item is already in the trash on return.
+    fn resolve_conflict(
+        &mut self,
+        src: &Path,
+        dst: &Path,
+    ) -> Result<Option<ConflictChoice>, Interrupt> {
+        let choice = match self.apply_to_all {
+            Some(choice) => choice,
+            None => {
+                let asked = Instant::now();
+                let mut control = self.shared.lock();
+                control.conflict = Some((src.to_path_buf(), dst.to_path_buf()));
+                let answer = loop {
+                    if control.cancelled {
+                        control.conflict = None;
+                        break None;
+                    }
+                    if let Some(answer) = control.answer.take() {
+                        break Some(answer);
+                    }
+                    control = self
+                        .shared
+                        .wake
+                        .wait(control)
+                        .unwrap_or_else(|poisoned| poisoned.into_inner());
+                };
+                drop(control);
+                self.waited += asked.elapsed();
+                let Some((choice, apply_to_all)) = answer else {
+                    return Err(Interrupt::Cancelled);
+                };
+                if apply_to_all {
+                    self.apply_to_all = Some(choice);
+                }
+                choice
+            }
+        };
+        match choice {
+            ConflictChoice::Skip => {
+                self.skipped += 1;
+                Ok(None)
+            }
+            ConflictChoice::Overwrite => {
+                let name = self.trash.trash(dst)?;
+                self.ops.push(FileManagerEditOp::Trashed {
+                    trash_root: self.trash.root().to_path_buf(),
+                    original: dst.to_path_buf(),
+                    name,
+                });
+                Ok(Some(choice))
+            }
+            ConflictChoice::KeepBoth => Ok(Some(choice)),
+        }
+    }
+
+    fn copy_cleaning_up(&mut self, src: &Path, dst: &Path) -> Result<(), Interrupt> {
+        let result = self.copy_tree(src, dst);
+        if result.is_err() {
+            let _ = remove_path_recursive(dst);
+        }
+        result
+    }
+
+    /// Copies `src` to `dst`. Symbolic links are recreated as links rather
+    /// than followed, so a link to a folder is not copied as the folder.
+    fn copy_tree(&mut self, src: &Path, dst: &Path) -> Result<(), Interrupt> {
+        let meta = std::fs::symlink_metadata(src)
+            .map_err(|e| anyhow::anyhow!("Failed reading {}: {e}", src.display()))?;
+        if meta.file_type().is_symlink() {
+            copy_symlink(src, dst)?;
+            self.progress.items_done += 1;
+            return Ok(());
+        }
+        if meta.is_dir() {
+            std::fs::create_dir_all(dst)
+                .map_err(|e| anyhow::anyhow!("Failed creating {}: {e}", dst.display()))?;
+            self.progress.items_done += 1;
+            let entries = std::fs::read_dir(src)
+                .map_err(|e| anyhow::anyhow!("Failed listing {}: {e}", src.display()))?;
+            for entry in entries {
+                let entry = entry
+                    .map_err(|e| anyhow::anyhow!("Failed reading {} entry: {e}", src.display()))?;
+                self.copy_tree(&entry.path(), &dst.join(entry.file_name()))?;
+            }
+            return Ok(());
+        }
+        let fail = |e: std::io::Error| {
+            anyhow::anyhow!("Failed copying {} -> {}: {e}", src.display(), dst.display())
+        };
+        let mut reader = std::fs::File::open(src).map_err(fail)?;
+        let mut writer = std::fs::File::create(dst).map_err(fail)?;
+        let mut buf = vec![0u8; COPY_CHUNK_BYTES];
+        loop {
+            self.checkpoint()?;
+            let read = reader.read(&mut buf).map_err(fail)?;
+            if read == 0 {
+                break;
+            }
+            writer.write_all(&buf[..read]).map_err(fail)?;
+            self.progress.bytes_done += read as u64;
+            self.publish();
+        }
+        let _ = writer.set_permissions(meta.permissions());
+        self.progress.items_done += 1;
+        Ok(())
+    }
+
+    /// Blocks while paused; fails once the job is cancelled.
+    fn checkpoint(&mut self) -> Result<(), Interrupt> {
+        let mut control = self.shared.lock();
+        if control.paused && !control.cancelled {
+            let paused_at = Instant::now();
+            while control.paused && !control.cancelled {
+                control = self
+                    .shared
+                    .wake
+                    .wait(control)
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+            }
+            self.waited += paused_at.elapsed();
+        }
+        if control.cancelled {
+            Err(Interrupt::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn publish(&mut self) {
+        self.progress.elapsed = self.started.elapsed().saturating_sub(self.waited);
+        self.shared.lock().progress = self.progress.clone();
+    }
+}
+
+/// Like [`Path::exists`], but true for a link whose target is missing.
+fn path_exists(path: &Path) -> bool {
+    std::fs::symlink_metadata(path).is_ok()
+}
+
+#[cfg(unix)]
+fn copy_symlink(src: &Path, dst: &Path) -> anyhow::Result<()> {
+    let target = std::fs::read_link(src)
+        .map_err(|e| anyhow::anyhow!("Failed reading link {}: {e}", src.display()))?;
+    std::os::unix::fs::symlink(&target, dst)
+        .map_err(|e| anyhow::anyhow!("Failed creating link {}: {e}", dst.display()))
+}
+
+/// Creating links needs extra privileges on Windows, so copy what the link
+/// points to instead.
+#[cfg(not(unix))]
+fn copy_symlink(src: &Path, dst: &Path) -> anyhow::Result<()> {
+    std::fs::copy(src, dst)
+        .map(|_| ())
+        .map_err(|e| anyhow::anyhow!("Failed copying {} -> {}: {e}", src.display(), dst.display()))
+}
+
+/// Bytes and entries (files and folders) under `path`. Archive members and
+/// symbolic links count as one item.
+fn scan(path: &Path) -> (u64, usize) {
+    let Ok(meta) = std::fs::symlink_metadata(path) else {
+        return (0, 1);
+    };
+    if !meta.is_dir() {
+        return (meta.len(), 1);
+    }
+    let mut total = (0, 1);
+    if let Ok(entries) = std::fs::read_dir(path) {
+        for entry in entries.flatten() {
+            let (bytes, items) = scan(&entry.path());
+            total.0 += bytes;
+            total.1 += items;
+        }
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDirGuard;
+
+    fn wait_for(queue: &mut TransferQueue) -> TransferOutcome {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            if let Some(outcome) = queue.poll() {
+                return outcome;
+            }
+            assert!(Instant::now() < deadline, "transfer did not finish");
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    fn wait_for_conflict(queue: &TransferQueue) -> TransferConflict {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            if let Some(conflict) = queue.conflict() {
+                return conflict;
+            }
+            assert!(Instant::now() < deadline, "no conflict prompt");
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn copy_job_reports_progress_and_asks_about_conflicts() {
+        let temp = TempDirGuard::new("transfers_copy");
+        let root = temp.path.clone();
+        let src_dir = root.join("src");
+        let dst_dir = root.join("dst");
+        std::fs::create_dir_all(src_dir.join("folder")).unwrap();
+        std::fs::create_dir_all(&dst_dir).unwrap();
+        std::fs::write(src_dir.join("a.txt"), "new a").unwrap();
+        std::fs::write(src_dir.join("b.txt"), "new b").unwrap();
+        std::fs::write(src_dir.join("folder/c.txt"), "c").unwrap();
+        std::fs::write(dst_dir.join("a.txt"), "old a").unwrap();
+        std::fs::write(dst_dir.join("b.txt"), "old b").unwrap();
+        let mut queue = TransferQueue::new(Trash::at(root.join("trash")));
+
+        let sources = ["a.txt", "b.txt", "folder"]
+            .iter()
+            .map(|name| src_dir.join(name))
+            .collect();
+        queue.enqueue(TransferRequest::copy(sources, dst_dir.clone()));
+        let conflict = wait_for_conflict(&queue);
+        assert_eq!(conflict.dst, dst_dir.join("a.txt"));
+        assert_eq!(queue.jobs()[0].state, TransferState::WaitingForAnswer);
+        queue.answer_conflict(ConflictChoice::KeepBoth, true);
+
+        let outcome = wait_for(&mut queue);
+        assert!(queue.is_idle());
+        assert_eq!(outcome.error, None);
+        assert_eq!(outcome.ops.len(), 3);
+        assert_eq!(
+            std::fs::read_to_string(dst_dir.join("a copy.txt")).unwrap(),
+            "new a"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dst_dir.join("b copy.txt")).unwrap(),
+            "new b"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dst_dir.join("a.txt")).unwrap(),
+            "old a"
+        );
+        assert!(dst_dir.join("folder/c.txt").exists());
+    }
+
+    #[test]
+    fn cancel_stops_the_running_job_and_the_queue_moves_on() {
+        let temp = TempDirGuard::new("transfers_cancel");
+        let root = temp.path.clone();
+        let dst_dir = root.join("dst");
+        std::fs::create_dir_all(&dst_dir).unwrap();
+        std::fs::write(root.join("a.txt"), "new").unwrap();
+        std::fs::write(dst_dir.join("a.txt"), "old").unwrap();
+        std::fs::write(root.join("b.txt"), "b").unwrap();
+        let mut queue = TransferQueue::new(Trash::at(root.join("trash")));
+
+        let first = queue.enqueue(TransferRequest::move_to(
+            vec![root.join("a.txt")],
+            dst_dir.clone(),
+        ));
+        let second = queue.enqueue(TransferRequest::move_to(
+            vec![root.join("b.txt")],
+            dst_dir.clone(),
+        ));
+        wait_for_conflict(&queue);
+        let jobs = queue.jobs();
+        assert_eq!(jobs[1].id, second);
+        assert_eq!(jobs[1].state, TransferState::Queued);
+
+        queue.cancel(first);
+        let outcome = wait_for(&mut queue);
+        assert!(outcome.cancelled);
+        assert!(outcome.ops.is_empty());
+        assert!(root.join("a.txt").exists());
+
+        let outcome = wait_for(&mut queue);
+        assert_eq!(outcome.kind, TransferKind::Move);
+        assert!(dst_dir.join("b.txt").exists());
+        assert!(!root.join("b.txt").exists());
+    }
+
+    #[test]
+    fn overwrite_trashes_the_old_item_and_undoes_as_one_step() {
+        let temp = TempDirGuard::new("transfers_overwrite");
+        let root = temp.path.clone();
+        let dst_dir = root.join("dst");
+        std::fs::create_dir_all(&dst_dir).unwrap();
+        std::fs::write(root.join("a.txt"), "new").unwrap();
+        std::fs::write(dst_dir.join("a.txt"), "old").unwrap();
+        let trash = Trash::at(root.join("trash"));
+        let mut queue = TransferQueue::new(trash.clone());
+        let mut runtime = crate::FileManagerEditRuntime::default();
+        let mut fm = crate::NativeFileManagerState::new(dst_dir.clone());
+
+        queue.enqueue(TransferRequest::copy(
+            vec![root.join("a.txt")],
+            dst_dir.clone(),
+        ));
+        wait_for_conflict(&queue);
+        queue.answer_conflict(ConflictChoice::Overwrite, false);
+        let outcome = wait_for(&mut queue);
+        assert_eq!(
+            runtime.finish_transfer(&mut fm, outcome, &trash),
+            "Copied a.txt"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dst_dir.join("a.txt")).unwrap(),
+            "new"
+        );
+        assert_eq!(fm.selected, Some(dst_dir.join("a.txt")));
+        assert_eq!(runtime.undo_stack.len(), 1);
+
+        runtime.undo(&mut fm).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dst_dir.join("a.txt")).unwrap(),
+            "old"
+        );
+        assert!(trash.entries().is_empty());
+        runtime.redo(&mut fm).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dst_dir.join("a.txt")).unwrap(),
+            "new"
+        );
+    }
+
+    #[test]
+    fn failed_job_records_finished_items_and_undo_reverses_them() {
+        let temp = TempDirGuard::new("transfers_partial");
+        let root = temp.path.clone();
+        let dst_dir = root.join("dst");
+        std::fs::create_dir_all(&dst_dir).unwrap();
+        std::fs::write(root.join("a.txt"), "a").unwrap();
+        let trash = Trash::at(root.join("trash"));
+        let mut queue = TransferQueue::new(trash.clone());
+        let mut runtime = crate::FileManagerEditRuntime::default();
+        let mut fm = crate::NativeFileManagerState::new(dst_dir.clone());
+
+        // The second source cannot be copied into itself, so the job stops.
+        queue.enqueue(TransferRequest::copy(
+            vec![root.join("a.txt"), dst_dir.clone()],
+            dst_dir.clone(),
+        ));
+        let outcome = wait_for(&mut queue);
+        assert!(outcome.error.is_some());
+        assert_eq!(outcome.ops.len(), 1);
+        let status = runtime.finish_transfer(&mut fm, outcome, &trash);
+        assert!(status.starts_with("File action failed:"), "{status}");
+        assert!(status.ends_with("(Copied a.txt)"), "{status}");
+        assert!(dst_dir.join("a.txt").exists());
+
+        runtime.undo(&mut fm).unwrap();
+        assert!(!dst_dir.join("a.txt").exists());
+        assert!(root.join("a.txt").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn copy_recreates_symlinks_instead_of_following_them() {
+        let temp = TempDirGuard::new("transfers_links");
+        let root = temp.path.clone();
+        let src_dir = root.join("src");
+        let dst_dir = root.join("dst");
+        std::fs::create_dir_all(src_dir.join("big")).unwrap();
+        std::fs::create_dir_all(&dst_dir).unwrap();
+        std::fs::write(src_dir.join("big/data.bin"), [0u8; 64]).unwrap();
+        std::os::unix::fs::symlink("big", src_dir.join("link")).unwrap();
+        std::os::unix::fs::symlink("missing", src_dir.join("dangling")).unwrap();
+        let mut queue = TransferQueue::new(Trash::at(root.join("trash")));
+
+        queue.enqueue(TransferRequest::copy(
+            vec![src_dir.join("link"), src_dir.join("dangling")],
+            dst_dir.clone(),
+        ));
+        let outcome = wait_for(&mut queue);
+        assert_eq!(outcome.error, None);
+        assert_eq!(outcome.ops.len(), 2);
+        for name in ["link", "dangling"] {
+            let meta = std::fs::symlink_metadata(dst_dir.join(name)).unwrap();
+            assert!(meta.file_type().is_symlink(), "{name} should stay a link");
+        }
+        assert_eq!(
+            std::fs::read_link(dst_dir.join("link")).unwrap(),
+            PathBuf::from("big")
+        );
+
+        remove_path_recursive(&dst_dir.join("link")).unwrap();
+        remove_path_recursive(&dst_dir.join("dangling")).unwrap();
+        assert!(std::fs::symlink_metadata(dst_dir.join("dangling")).is_err());
+        assert!(src_dir.join("big/data.bin").exists());
+    }
+
+    #[test]
+    fn trash_job_purges_older_items_past_the_limit_but_keeps_its_own() {
+        let temp = TempDirGuard::new("transfers_purge");
+        let root = temp.path.clone();
+        let trash = Trash::at(root.join("trash"));
+        for name in ["old.bin", "new.bin"] {
+            std::fs::write(root.join(name), [0u8; 10]).unwrap();
+        }
+        trash.trash(&root.join("old.bin")).unwrap();
+        let mut queue = TransferQueue::new(trash.clone());
+
+        queue.enqueue(TransferRequest::trash(vec![root.join("new.bin")]));
+        assert_eq!(wait_for(&mut queue).purged, 0);
+        assert_eq!(trash.entries().len(), 2);
+
+        std::fs::write(root.join("third.bin"), [0u8; 10]).unwrap();
+        queue.set_trash_max_bytes(15);
+        queue.enqueue(TransferRequest::trash(vec![root.join("third.bin")]));
+        let outcome = wait_for(&mut queue);
+        assert_eq!(outcome.purged, 2);
+        let names: Vec<String> = trash.entries().into_iter().map(|e| e.name).collect();
+        assert_eq!(names, vec!["third.bin".to_string()]);
+    }
+
+    #[test]
+    fn progress_summary_includes_eta_once_there_is_a_rate() {
+        let mut progress = TransferProgress {
+            items_done: 1,
+            items_total: 4,
+            bytes_done: 0,
+            bytes_total: 4096,
+            elapsed: Duration::from_secs(2),
+        };
+        assert_eq!(progress.eta(), None);
+        progress.bytes_done = 1024;
+        assert_eq!(progress.eta(), Some(Duration::from_secs(6)));
+        assert_eq!(progress.fraction(), 0.25);
+        assert_eq!(
+            progress.summary(),
+            "1.00 KB of 4.00 KB - 1 of 4 items - 6s left"
+        );
+    }
+
+    #[test]
+    fn archive_jobs_compress_and_extract_in_the_background() {
+        let temp = TempDirGuard::new("transfers_archive");
+        let root = temp.path.clone();
+        std::fs::create_dir_all(root.join("docs")).unwrap();
+        std::fs::write(root.join("docs/a.txt"), "alpha").unwrap();
+        let trash = Trash::at(root.join("trash"));
+        let mut queue = TransferQueue::new(trash.clone());
+        let mut runtime = crate::FileManagerEditRuntime::default();
+        let mut fm = crate::NativeFileManagerState::new(root.clone());
+
+        let archive = root.join("docs.tar.xz");
+        queue.enqueue(TransferRequest::compress(
+            vec![root.join("docs")],
+            archive.clone(),
+        ));
+        let outcome = wait_for(&mut queue);
+        assert_eq!(
+            runtime.finish_transfer(&mut fm, outcome, &trash),
+            "Created docs.tar.xz"
+        );
+        assert_eq!(fm.selected, Some(archive.clone()));
+
+        queue.enqueue(TransferRequest::extract(
+            vec![archive.clone()],
+            root.clone(),
+        ));
+        let outcome = wait_for(&mut queue);
+        assert_eq!(outcome.error, None);
+        assert_eq!(
+            runtime.finish_transfer(&mut fm, outcome, &trash),
+            "Extracted docs (1)"
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.join("docs (1)/docs/a.txt")).unwrap(),
+            "alpha"
+        );
+
+        queue.enqueue(TransferRequest::extract(
+            vec![archive.join("docs/a.txt")],
+            root.clone(),
+        ));
+        let outcome = wait_for(&mut queue);
+        assert_eq!(
+            runtime.finish_transfer(&mut fm, outcome, &trash),
+            "Extracted a.txt"
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.join("a.txt")).unwrap(),
+            "alpha"
+        );
+
+        runtime.undo(&mut fm).unwrap();
+        runtime.undo(&mut fm).unwrap();
+        runtime.undo(&mut fm).unwrap();
+        assert!(!root.join("a.txt").exists());
+        assert!(!root.join("docs (1)").exists());
+        assert!(!archive.exists());
+    }
+}
@@ -0,0 +1,566 @@
+//! Trash can following the freedesktop.org trash specification.
+//!
+//! Trashed items live in `<root>/files/<name>` and are described by
+//! `<root>/info/<name>.trashinfo`, which records the original location and
+//! the deletion time so the item can be restored later.
+
+use super::{move_path, path_display_name, remove_path_recursive, split_file_name};
+use anyhow::{anyhow, Result};
+use chrono::{Local, NaiveDateTime};
+use robcos_shared::config::base_dir;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const TRASH_INFO_EXT: &str = "trashinfo";
+const TRASH_INFO_HEADER: &str = "[Trash Info]";
+const DELETION_DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trash {
+    root: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashEntry {
+    /// File name inside `files/`; also the stem of the `.trashinfo` file.
+    pub name: String,
+    /// Where the item was deleted from; `None` for items adopted from the
+    /// old trash folder, which never recorded it.
+    pub original_path: Option<PathBuf>,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub size: u64,
+}
+
+/// Home trash location: `$XDG_DATA_HOME/Trash` on Linux, a private folder
+/// under the app data dir elsewhere.
+pub fn default_trash_root() -> PathBuf {
+    #[cfg(target_os = "linux")]
+    if let Some(data_dir) = dirs::data_dir() {
+        return data_dir.join("Trash");
+    }
+    legacy_trash_root()
+}
+
+/// Folder the file manager used before it followed the spec: trashed items
+/// were moved straight into it, with no record of where they came from.
+pub fn legacy_trash_root() -> PathBuf {
+    base_dir().join(".fm_trash")
+}
+
+/// Moves anything still sitting in [`legacy_trash_root`] into the user
+/// trash. Returns how many items were moved.
+pub fn migrate_legacy_trash() -> Result<usize> {
+    Trash::user().adopt_legacy(&legacy_trash_root())
+}
+
+impl Trash {
+    pub fn at(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    pub fn user() -> Self {
+        Self::at(default_trash_root())
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn files_dir(&self) -> PathBuf {
+        self.root.join("files")
+    }
+
+    pub fn info_dir(&self) -> PathBuf {
+        self.root.join("info")
+    }
+
+    pub fn ensure_dirs(&self) -> Result<()> {
+        for dir in [self.files_dir(), self.info_dir()] {
+            std::fs::create_dir_all(&dir)
+                .map_err(|e| anyhow!("Failed creating trash dir {}: {e}", dir.display()))?;
+        }
+        Ok(())
+    }
+
+    /// True when `path` is the trash listing itself (the folder shown as "Trash").
+    pub fn is_view(&self, path: &Path) -> bool {
+        path == self.files_dir()
+    }
+
+    pub fn contains(&self, path: &Path) -> bool {
+        path.starts_with(&self.root)
+    }
+
+    fn info_path(&self, name: &str) -> PathBuf {
+        self.info_dir().join(format!("{name}.{TRASH_INFO_EXT}"))
+    }
+
+    /// Moves `path` into the trash and returns the name it was stored under.
+    pub fn trash(&self, path: &Path) -> Result<String> {
+        self.trash_with_name(path, None)
+    }
+
+    /// Like [`Trash::trash`], but reuses a previously assigned name (used by redo).
+    pub fn trash_as(&self, path: &Path, name: &str) -> Result<String> {
+        self.trash_with_name(path, Some(name))
+    }
+
+    fn trash_with_name(&self, path: &Path, name: Option<&str>) -> Result<String> {
+        if self.contains(path) {
+            return Err(anyhow!("{} is already in the trash.", path.display()));
+        }
+        if std::fs::symlink_metadata(path).is_err() {
+            return Err(anyhow!("{} no longer exists.", path.display()));
+        }
+        self.ensure_dirs()?;
+        let original = absolute_path(path);
+        let (name, mut info_file) = match name {
+            Some(name) => (name.to_string(), self.reserve_info(name)?),
+            None => self.reserve_unique_info(&path_display_name(path))?,
+        };
+        self.store(path, &name, &mut info_file, Some(&original))?;
+        Ok(name)
+    }
+
+    /// Writes the `.trashinfo` record for `name` and moves `path` into
+    /// `files/`. The record is removed again if the move fails.
+    fn store(
+        &self,
+        path: &Path,
+        name: &str,
+        info_file: &mut std::fs::File,
+        original: Option<&Path>,
+    ) -> Result<()> {
+        let mut info = format!("{TRASH_INFO_HEADER}\n");
+        if let Some(original) = original {
+            info.push_str(&format!("Path={}\n", encode_trash_path(original)));
+        }
+        info.push_str(&format!(
+            "DeletionDate={}\n",
+            Local::now().format(DELETION_DATE_FORMAT)
+        ));
+        let info_path = self.info_path(name);
+        let written = info_file
+            .write_all(info.as_bytes())
+            .map_err(|e| anyhow!("Failed writing {}: {e}", info_path.display()));
+        if let Err(err) = written.and_then(|_| move_path(path, &self.files_dir().join(name))) {
+            let _ = std::fs::remove_file(&info_path);
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Moves the loose items of an old-style trash folder into this trash.
+    /// Their original location is unknown, so they can be deleted but not
+    /// restored. `legacy` is removed once it is empty; when it is this
+    /// trash's own root, only its `files/` and `info/` folders are kept.
+    pub fn adopt_legacy(&self, legacy: &Path) -> Result<usize> {
+        let Ok(read) = std::fs::read_dir(legacy) else {
+            return Ok(0);
+        };
+        let own_dirs = [self.files_dir(), self.info_dir()];
+        let mut adopted = 0usize;
+        for item in read.flatten() {
+            let path = item.path();
+            if own_dirs.contains(&path) {
+                continue;
+            }
+            self.ensure_dirs()?;
+            let (name, mut info_file) = self.reserve_unique_info(&path_display_name(&path))?;
+            self.store(&path, &name, &mut info_file, None)?;
+            adopted += 1;
+        }
+        if legacy != self.root {
+            let _ = std::fs::remove_dir(legacy);
+        }
+        Ok(adopted)
+    }
+
+    fn reserve_info(&self, name: &str) -> Result<std::fs::File> {
+        let info_path = self.info_path(name);
+        if self.files_dir().join(name).exists() {
+            return Err(anyhow!("Trash already holds an item named {name}."));
+        }
+        std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&info_path)
+            .map_err(|e| anyhow!("Failed creating {}: {e}", info_path.display()))
+    }
+
+    /// The `.trashinfo` file is created with `create_new`, so two writers
+    /// can never claim the same name.
+    fn reserve_unique_info(&self, original_name: &str) -> Result<(String, std::fs::File)> {
+        let (stem, ext) = split_file_name(original_name);
+        for index in 0..=9999usize {
+            let candidate = if index == 0 {
+                original_name.to_string()
+            } else {
+                format!("{stem} ({index}){ext}")
+            };
+            if self.files_dir().join(&candidate).exists() {
+                continue;
+            }
+            match std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(self.info_path(&candidate))
+            {
+                Ok(file) => return Ok((candidate, file)),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                Err(e) => {
+                    return Err(anyhow!(
+                        "Failed creating trash info in {}: {e}",
+                        self.info_dir().display()
+                    ))
+                }
+            }
+        }
+        Err(anyhow!(
+            "Too many items named {original_name} in the trash."
+        ))
+    }
+
+    /// Items currently in the trash, newest first. Files without a matching
+    /// `.trashinfo` are skipped.
+    pub fn entries(&self) -> Vec<TrashEntry> {
+        let mut entries: Vec<TrashEntry> = std::fs::read_dir(self.info_dir())
+            .ok()
+            .into_iter()
+            .flat_map(|iter| iter.flatten())
+            .filter_map(|item| {
+                let file_name = item.file_name().to_string_lossy().to_string();
+                let name = file_name.strip_suffix(&format!(".{TRASH_INFO_EXT}"))?;
+                self.entry(name)
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            b.deleted_at
+                .cmp(&a.deleted_at)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        entries
+    }
+
+    pub fn entry(&self, name: &str) -> Option<TrashEntry> {
+        let stored = self.files_dir().join(name);
+        std::fs::symlink_metadata(&stored).ok()?;
+        let text = std::fs::read_to_string(self.info_path(name)).ok()?;
+        let (original_path, deleted_at) = parse_trash_info(&text)?;
+        Some(TrashEntry {
+            name: name.to_string(),
+            original_path,
+            deleted_at,
+            size: path_size(&stored),
+        })
+    }
+
+    /// Moves an item back to where it was deleted from.
+    pub fn restore(&self, name: &str) -> Result<PathBuf> {
+        let entry = self
+            .entry(name)
+            .ok_or_else(|| anyhow!("{name} is not in the trash."))?;
+        let original = entry
+            .original_path
+            .ok_or_else(|| anyhow!("Cannot restore {name}: its original location is unknown."))?;
+        if std::fs::symlink_metadata(&original).is_ok() {
+            return Err(anyhow!(
+                "Cannot restore: {} already exists.",
+                original.display()
+            ));
+        }
+        move_path(&self.files_dir().join(name), &original)?;
+        let _ = std::fs::remove_file(self.info_path(name));
+        Ok(original)
+    }
+
+    /// Permanently deletes one item.
+    pub fn delete(&self, name: &str) -> Result<()> {
+        remove_path_recursive(&self.files_dir().join(name))?;
+        let info = self.info_path(name);
+        if info.exists() {
+            std::fs::remove_file(&info)
+                .map_err(|e| anyhow!("Failed deleting {}: {e}", info.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Permanently deletes everything, including orphaned files. Returns the
+    /// number of items removed.
+    pub fn empty(&self) -> Result<usize> {
+        let mut removed = 0usize;
+        for item in std::fs::read_dir(self.files_dir())
+            .ok()
+            .into_iter()
+            .flat_map(|iter| iter.flatten())
+        {
+            remove_path_recursive(&item.path())?;
+            removed += 1;
+        }
+        for item in std::fs::read_dir(self.info_dir())
+            .ok()
+            .into_iter()
+            .flat_map(|iter| iter.flatten())
+        {
+            let _ = std::fs::remove_file(item.path());
+        }
+        Ok(removed)
+    }
+
+    pub fn total_size(&self) -> u64 {
+        self.entries().iter().map(|entry| entry.size).sum()
+    }
+
+    /// Deletes the oldest items until the trash fits in `max_bytes`, never
+    /// touching names listed in `keep`. A limit of 0 disables purging.
+    /// Returns how many items were removed.
+    pub fn purge_to_limit(&self, max_bytes: u64, keep: &[String]) -> usize {
+        if max_bytes == 0 {
+            return 0;
+        }
+        let entries = self.entries();
+        let mut total: u64 = entries.iter().map(|entry| entry.size).sum();
+        let mut purged = 0usize;
+        for entry in entries.iter().rev() {
+            if total <= max_bytes {
+                break;
+            }
+            if keep.contains(&entry.name) {
+                continue;
+            }
+            if self.delete(&entry.name).is_ok() {
+                total = total.saturating_sub(entry.size);
+                purged += 1;
+            }
+        }
+        purged
+    }
+}
+
+fn absolute_path(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()
+            .map(|cwd| cwd.join(path))
+            .unwrap_or_else(|_| path.to_path_buf())
+    }
+}
+
+fn path_size(path: &Path) -> u64 {
+    let Ok(meta) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    std::fs::read_dir(path)
+        .ok()
+        .into_iter()
+        .flat_map(|iter| iter.flatten())
+        .map(|item| path_size(&item.path()))
+        .sum()
+}
+
+/// A missing `Path=` line means the original location is unknown; one that
+/// cannot be decoded makes the whole record invalid.
+fn parse_trash_info(text: &str) -> Option<(Option<PathBuf>, Option<NaiveDateTime>)> {
+    let mut lines = text.lines().map(str::trim);
+    if lines.next()? != TRASH_INFO_HEADER {
+        return None;
+    }
+    let mut original = None;
+    let mut deleted_at = None;
+    for line in lines {
+        if let Some(value) = line.strip_prefix("Path=") {
+            original = Some(decode_trash_path(value)?);
+        } else if let Some(value) = line.strip_prefix("DeletionDate=") {
+            deleted_at = NaiveDateTime::parse_from_str(value, DELETION_DATE_FORMAT).ok();
+        }
+    }
+    Some((original, deleted_at))
+}
+
+fn path_bytes(path: &Path) -> Vec<u8> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        path.as_os_str().as_bytes().to_vec()
+    }
+    #[cfg(not(unix))]
+    {
+        path.to_string_lossy().into_owned().into_bytes()
+    }
+}
+
+fn path_from_bytes(bytes: Vec<u8>) -> Option<PathBuf> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStringExt;
+        Some(PathBuf::from(std::ffi::OsString::from_vec(bytes)))
+    }
+    #[cfg(not(unix))]
+    {
+        String::from_utf8(bytes).ok().map(PathBuf::from)
+    }
+}
+
+/// Percent-encodes a path the way the spec asks for (RFC 2396 escaping,
+/// with `/` kept as the separator).
+fn encode_trash_path(path: &Path) -> String {
+    let mut out = String::new();
+    for byte in path_bytes(path) {
+        if byte.is_ascii_alphanumeric() || b"/-_.~!*'()".contains(&byte) {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    out
+}
+
+fn decode_trash_path(value: &str) -> Option<PathBuf> {
+    let raw = value.as_bytes();
+    let mut bytes = Vec::with_capacity(raw.len());
+    let mut idx = 0;
+    while idx < raw.len() {
+        if raw[idx] == b'%' {
+            let hex = value.get(idx + 1..idx + 3)?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            idx += 3;
+        } else {
+            bytes.push(raw[idx]);
+            idx += 1;
+        }
+    }
+    if bytes.is_empty() {
+        return None;
+    }
+    path_from_bytes(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDirGuard;
+
+    #[test]
+    fn trash_writes_info_and_restore_puts_item_back() {
+        let temp = TempDirGuard::new("trash_restore");
+        let root = temp.path.clone();
+        let trash = Trash::at(root.join("Trash"));
+        let docs = root.join("my docs");
+        std::fs::create_dir_all(&docs).expect("create docs");
+        let file = docs.join("report #1.txt");
+        std::fs::write(&file, "hello").expect("write file");
+
+        let name = trash.trash(&file).expect("trash file");
+        assert!(!file.exists());
+        assert!(trash.files_dir().join(&name).exists());
+        let info = std::fs::read_to_string(trash.info_dir().join(format!("{name}.trashinfo")))
+            .expect("read info");
+        assert!(info.starts_with("[Trash Info]\n"));
+        assert!(info.contains("my%20docs/report%20%231.txt"));
+
+        let entry = trash.entry(&name).expect("entry listed");
+        assert_eq!(entry.original_path.as_ref(), Some(&file));
+        assert_eq!(entry.size, 5);
+        assert!(entry.deleted_at.is_some());
+
+        std::fs::remove_dir_all(&docs).expect("remove parent");
+        assert_eq!(trash.restore(&name).expect("restore"), file);
+        assert_eq!(std::fs::read_to_string(&file).expect("read back"), "hello");
+        assert!(trash.entries().is_empty());
+    }
+
+    #[test]
+    fn duplicate_names_get_unique_slots_and_restore_refuses_to_overwrite() {
+        let temp = TempDirGuard::new("trash_dupes");
+        let root = temp.path.clone();
+        let trash = Trash::at(root.join("Trash"));
+        let file = root.join("a.txt");
+        std::fs::write(&file, "one").expect("write");
+        let first = trash.trash(&file).expect("trash first");
+        std::fs::write(&file, "two").expect("write again");
+        let second = trash.trash(&file).expect("trash second");
+
+        assert_eq!(first, "a.txt");
+        assert_eq!(second, "a (1).txt");
+        assert_eq!(trash.entries().len(), 2);
+
+        std::fs::write(&file, "three").expect("write blocker");
+        assert!(trash.restore(&first).is_err());
+        assert!(trash.files_dir().join(&first).exists());
+    }
+
+    #[test]
+    fn purge_removes_oldest_first_and_empty_clears_everything() {
+        let temp = TempDirGuard::new("trash_purge");
+        let root = temp.path.clone();
+        let trash = Trash::at(root.join("Trash"));
+        for (name, date) in [
+            ("old.bin", "2020-01-01T00:00:00"),
+            ("new.bin", "2024-01-01T00:00:00"),
+        ] {
+            let path = root.join(name);
+            std::fs::write(&path, vec![0u8; 10]).expect("write");
+            let stored = trash.trash(&path).expect("trash");
+            let info = trash.info_dir().join(format!("{stored}.trashinfo"));
+            let text = std::fs::read_to_string(&info).expect("read info");
+            let patched: Vec<String> = text
+                .lines()
+                .map(|line| {
+                    if line.starts_with("DeletionDate=") {
+                        format!("DeletionDate={date}")
+                    } else {
+                        line.to_string()
+                    }
+                })
+                .collect();
+            std::fs::write(&info, patched.join("\n")).expect("patch info");
+        }
+
+        assert_eq!(trash.total_size(), 20);
+        assert_eq!(trash.purge_to_limit(0, &[]), 0);
+        assert_eq!(trash.purge_to_limit(15, &[]), 1);
+        let names: Vec<String> = trash.entries().into_iter().map(|e| e.name).collect();
+        assert_eq!(names, vec!["new.bin".to_string()]);
+
+        assert_eq!(trash.empty().expect("empty"), 1);
+        assert!(trash.entries().is_empty());
+        assert_eq!(
+            std::fs::read_dir(trash.info_dir()).expect("info").count(),
+            0
+        );
+    }
+
+    #[test]
+    fn legacy_items_are_adopted_without_an_original_location() {
+        let temp = TempDirGuard::new("trash_legacy");
+        let root = temp.path.clone();
+        let legacy = root.join(".fm_trash");
+        std::fs::create_dir_all(legacy.join("folder")).expect("create legacy dir");
+        std::fs::write(legacy.join("note.txt"), "old").expect("write legacy file");
+        let trash = Trash::at(root.join("Trash"));
+        let file = root.join("note.txt");
+        std::fs::write(&file, "new").expect("write file");
+        trash.trash(&file).expect("trash file");
+
+        assert_eq!(trash.adopt_legacy(&legacy).expect("adopt"), 2);
+        assert!(!legacy.exists());
+        let adopted = trash.entry("note (1).txt").expect("adopted entry");
+        assert_eq!(adopted.original_path, None);
+        assert!(trash.entry("folder").is_some());
+        assert!(trash.restore("note (1).txt").is_err());
+        assert!(trash.files_dir().join("note (1).txt").exists());
+        assert_eq!(trash.adopt_legacy(&legacy).expect("adopt again"), 0);
+
+        // Where the legacy folder is the trash root, only loose items move.
+        let own = Trash::at(legacy.clone());
+        own.ensure_dirs().expect("create dirs");
+        std::fs::write(legacy.join("loose.txt"), "x").expect("write loose file");
+        assert_eq!(own.adopt_legacy(&legacy).expect("adopt own"), 1);
+        assert!(own.entry("loose.txt").is_some());
+        assert!(own.files_dir().is_dir() && own.info_dir().is_dir());
+    }
+}
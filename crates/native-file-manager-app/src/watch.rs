@@ -0,0 +1,231 @@
+//! Live folder watching.
+//!
+//! Listings are read straight from disk whenever they are drawn, so the
+//! watcher's job is to notice changes made elsewhere (a PTY build, another
+//! session), wake the UI, and say which folders changed once things have
+//! settled. Each folder is watched on its own, not recursively.
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Quiet time after the last change before a refresh is reported.
+pub const WATCH_DEBOUNCE: Duration = Duration::from_millis(250);
+/// Upper bound on the delay while changes keep arriving, e.g. during a build.
+const WATCH_MAX_DELAY: Duration = Duration::from_millis(1000);
+
+type Waker = Box<dyn Fn() + Send + Sync>;
+
+#[derive(Default)]
+struct PendingChanges {
+    dirs: HashSet<PathBuf>,
+    first: Option<Instant>,
+    last: Option<Instant>,
+}
+
+impl PendingChanges {
+    fn record(&mut self, dir: PathBuf, now: Instant) {
+        self.dirs.insert(dir);
+        self.first.get_or_insert(now);
+        self.last = Some(now);
+    }
+
+    /// The changed folders once the debounce has passed; empty otherwise.
+    fn take_settled(&mut self, now: Instant) -> Vec<PathBuf> {
+        let (Some(first), Some(last)) = (self.first, self.last) else {
+            return Vec::new();
+        };
+        if now.duration_since(last) < WATCH_DEBOUNCE && now.duration_since(first) < WATCH_MAX_DELAY
+        {
+            return Vec::new();
+        }
+        self.first = None;
+        self.last = None;
+        let mut dirs: Vec<PathBuf> = self.dirs.drain().collect();
+        dirs.sort();
+        dirs
+    }
+}
+
+/// Watches a set of folders. When the platform watcher cannot be created
+/// the set is still tracked but no changes are ever reported.
+pub struct DirWatcher {
+    watcher: Option<RecommendedWatcher>,
+    watched: HashSet<PathBuf>,
+    pending: Arc<Mutex<PendingChanges>>,
+    waker: Arc<Mutex<Option<Waker>>>,
+}
+
+impl std::fmt::Debug for DirWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DirWatcher")
+            .field("active", &self.watcher.is_some())
+            .field("watched", &self.watched)
+            .finish()
+    }
+}
+
+impl Default for DirWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DirWatcher {
+    pub fn new() -> Self {
+        let pending: Arc<Mutex<PendingChanges>> = Arc::default();
+        let waker: Arc<Mutex<Option<Waker>>> = Arc::default();
+        let handler_pending = Arc::clone(&pending);
+        let handler_waker = Arc::clone(&waker);
+        let watcher = notify::recommended_watcher(move |result: notify::Result<Event>| {
+            let Ok(event) = result else {
+                return;
+            };
+            if matches!(event.kind, EventKind::Access(_)) {
+                return;
+            }
+            let now = Instant::now();
+            if let Ok(mut pending) = handler_pending.lock() {
+                for path in &event.paths {
+                    // Events name the entry that changed; its folder is
+                    // what needs re-reading. A watched folder that is itself
+                    // removed is reported too, so callers can leave it.
+                    if let Some(parent) = path.parent() {
+                        pending.record(parent.to_path_buf(), now);
+                    }
+                    pending.record(path.clone(), now);
+                }
+            }
+            if let Ok(waker) = handler_waker.lock() {
+                if let Some(wake) = waker.as_ref() {
+                    wake();
+                }
+            }
+        })
+        .ok();
+        Self {
+            watcher,
+            watched: HashSet::new(),
+            pending,
+            waker,
+        }
+    }
+
+    /// Called from the watcher thread whenever a change arrives, e.g. to
+    /// request a repaint.
+    pub fn set_waker(&self, wake: impl Fn() + Send + Sync + 'static) {
+        if let Ok(mut waker) = self.waker.lock() {
+            *waker = Some(Box::new(wake));
+        }
+    }
+
+    pub fn has_waker(&self) -> bool {
+        self.waker.lock().is_ok_and(|waker| waker.is_some())
+    }
+
+    /// Watches exactly `dirs`, adding and dropping watches as needed.
+    /// Folders that do not exist are skipped.
+    pub fn set_dirs<I>(&mut self, dirs: I)
+    where
+        I: IntoIterator<Item = PathBuf>,
+    {
+        let wanted: HashSet<PathBuf> = dirs.into_iter().filter(|dir| dir.is_dir()).collect();
+        if wanted == self.watched {
+            return;
+        }
+        if let Some(watcher) = self.watcher.as_mut() {
+            for dir in self.watched.difference(&wanted) {
+                let _ = watcher.unwatch(dir);
+            }
+            for dir in wanted.difference(&self.watched) {
+                let _ = watcher.watch(dir, RecursiveMode::NonRecursive);
+            }
+        }
+        self.watched = wanted;
+    }
+
+    pub fn is_watching(&self, dir: &Path) -> bool {
+        self.watched.contains(dir)
+    }
+
+    /// Whether changes are waiting out the debounce.
+    pub fn has_pending(&self) -> bool {
+        self.pending
+            .lock()
+            .is_ok_and(|pending| pending.first.is_some())
+    }
+
+    /// Watched folders that changed, once changes have settled.
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        let Ok(mut pending) = self.pending.lock() else {
+            return Vec::new();
+        };
+        let mut dirs = pending.take_settled(Instant::now());
+        dirs.retain(|dir| self.watched.contains(dir));
+        dirs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDirGuard;
+
+    #[test]
+    fn pending_changes_wait_for_quiet_but_not_forever() {
+        let start = Instant::now();
+        let mut pending = PendingChanges::default();
+        pending.record(PathBuf::from("/a"), start);
+        pending.record(PathBuf::from("/b"), start + Duration::from_millis(100));
+        pending.record(PathBuf::from("/a"), start + Duration::from_millis(200));
+        assert!(pending
+            .take_settled(start + Duration::from_millis(300))
+            .is_empty());
+        assert_eq!(
+            pending.take_settled(start + Duration::from_millis(450)),
+            vec![PathBuf::from("/a"), PathBuf::from("/b")]
+        );
+        assert!(pending
+            .take_settled(start + Duration::from_secs(5))
+            .is_empty());
+
+        // A steady stream of changes still flushes after the max delay.
+        for step in 0..12 {
+            pending.record(
+                PathBuf::from("/build"),
+                start + Duration::from_millis(step * 100),
+            );
+        }
+        assert_eq!(
+            pending.take_settled(start + Duration::from_millis(1100)),
+            vec![PathBuf::from("/build")]
+        );
+    }
+
+    #[test]
+    fn watcher_reports_files_created_in_watched_folders() {
+        let temp = TempDirGuard::new("watch");
+        let root = temp.path.clone();
+        std::fs::create_dir_all(root.join("other")).unwrap();
+        let mut watcher = DirWatcher::new();
+        if watcher.watcher.is_none() {
+            return;
+        }
+        watcher.set_dirs([root.clone(), root.join("missing")]);
+        assert!(watcher.is_watching(&root));
+        assert!(!watcher.is_watching(&root.join("missing")));
+
+        std::fs::write(root.join("build.log"), "done").unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let changed = loop {
+            let changed = watcher.poll();
+            if !changed.is_empty() || Instant::now() > deadline {
+                break changed;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        };
+        assert_eq!(changed, vec![root.clone()]);
+    }
+}
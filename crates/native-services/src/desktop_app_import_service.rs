@@ -0,0 +1,401 @@
+//! Import of XDG desktop entries (`*.desktop` files) into the program catalogs.
+
+use super::desktop_launcher_service::{add_catalog_entry, catalog_names, ProgramCatalog};
+use crate::catalog::{CatalogEntry, CatalogRunMode};
+use crate::config::LaunchEnvironment;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// One launchable application found in a desktop entry directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DesktopAppEntry {
+    /// Desktop file ID, e.g. `org.gnome.Calculator.desktop`.
+    pub id: String,
+    pub name: String,
+    /// `Exec` split into argv with field codes removed.
+    pub command: Vec<String>,
+    pub comment: String,
+    /// Icon file when found, otherwise the raw `Icon` value.
+    pub icon: Option<String>,
+    pub categories: Vec<String>,
+    pub terminal: bool,
+    pub working_dir: Option<String>,
+}
+
+impl DesktopAppEntry {
+    /// Games catalog for entries in the `Game` category, Applications otherwise.
+    pub fn suggested_catalog(&self) -> ProgramCatalog {
+        if self.categories.iter().any(|category| category == "Game") {
+            ProgramCatalog::Games
+        } else {
+            ProgramCatalog::Applications
+        }
+    }
+
+    /// Catalog entry for this application. Terminal programs run in a PTY,
+    /// graphical ones detached.
+    pub fn to_catalog_entry(&self) -> CatalogEntry {
+        CatalogEntry {
+            command: self.command.clone(),
+            description: self.comment.clone(),
+            icon: self.icon.clone(),
+            tags: self.categories.clone(),
+            launch: LaunchEnvironment {
+                cwd: self.working_dir.clone(),
+                ..LaunchEnvironment::default()
+            },
+            run_mode: if self.terminal {
+                CatalogRunMode::Pty
+            } else {
+                CatalogRunMode::Detached
+            },
+            ..CatalogEntry::default()
+        }
+    }
+}
+
+/// Directories scanned for desktop entries, lowest precedence first.
+pub fn desktop_entry_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![PathBuf::from("/usr/share/applications")];
+    if let Some(data) = dirs::data_dir() {
+        dirs.push(data.join("applications"));
+    }
+    dirs
+}
+
+/// Applications from [`desktop_entry_dirs`], sorted by name. User entries
+/// replace system entries with the same ID. Empty on non-Linux systems.
+pub fn scan_desktop_apps() -> Vec<DesktopAppEntry> {
+    if !cfg!(target_os = "linux") {
+        return Vec::new();
+    }
+    scan_desktop_apps_in(&desktop_entry_dirs(), &icon_search_dirs())
+}
+
+fn scan_desktop_apps_in(dirs: &[PathBuf], icon_dirs: &[PathBuf]) -> Vec<DesktopAppEntry> {
+    let mut by_id: BTreeMap<String, Option<DesktopAppEntry>> = BTreeMap::new();
+    for dir in dirs {
+        let mut files = Vec::new();
+        collect_desktop_files(dir, dir, &mut files);
+        for (id, path) in files {
+            let entry = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|text| parse_desktop_entry(&id, &text))
+                .map(|mut entry| {
+                    entry.icon = entry
+                        .icon
+                        .map(|icon| resolve_icon(&icon, icon_dirs).unwrap_or(icon));
+                    entry
+                });
+            // A hidden user entry still masks the system one.
+            by_id.insert(id, entry);
+        }
+    }
+    let mut apps: Vec<DesktopAppEntry> = by_id.into_values().flatten().collect();
+    apps.sort_by_key(|app| app.name.to_lowercase());
+    apps
+}
+
+fn collect_desktop_files(root: &Path, dir: &Path, out: &mut Vec<(String, PathBuf)>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_desktop_files(root, &path, out);
+        } else if path.extension().is_some_and(|ext| ext == "desktop") {
+            let id = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('/', "-");
+            out.push((id, path));
+        }
+    }
+}
+
+/// Parses the `[Desktop Entry]` group. Returns `None` for entries that should
+/// not be offered: non-applications, hidden or `NoDisplay` entries, and
+/// entries without a usable `Exec`.
+pub fn parse_desktop_entry(id: &str, text: &str) -> Option<DesktopAppEntry> {
+    let mut keys: BTreeMap<&str, String> = BTreeMap::new();
+    let mut in_main_group = false;
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') {
+            in_main_group = line == "[Desktop Entry]";
+            continue;
+        }
+        if !in_main_group {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            // Localised keys (`Name[de]`) are skipped; the plain key is used.
+            keys.entry(key.trim())
+                .or_insert_with(|| unescape_value(value.trim()));
+        }
+    }
+    let flag = |key: &str| keys.get(key).is_some_and(|value| value == "true");
+    if keys.get("Type").map(String::as_str) != Some("Application")
+        || flag("NoDisplay")
+        || flag("Hidden")
+    {
+        return None;
+    }
+    let name = keys.get("Name")?.clone();
+    let icon = keys.get("Icon").filter(|icon| !icon.is_empty()).cloned();
+    let command = parse_exec(keys.get("Exec")?, &name, icon.as_deref())?;
+    Some(DesktopAppEntry {
+        id: id.to_string(),
+        name,
+        command,
+        comment: keys.get("Comment").cloned().unwrap_or_default(),
+        icon,
+        categories: keys
+            .get("Categories")
+            .map(|list| {
+                list.split(';')
+                    .map(str::trim)
+                    .filter(|category| !category.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        terminal: flag("Terminal"),
+        working_dir: keys.get("Path").filter(|path| !path.is_empty()).cloned(),
+    })
+}
+
+/// Undoes the string escapes of the desktop entry format (`\s`, `\n`, ...).
+fn unescape_value(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => out.push(' '),
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Splits an `Exec` value into argv and expands field codes: file and URL
+/// codes (`%f`, `%U`, ...) and `%k` are dropped, `%i`/`%c` are filled in from
+/// the entry and `%%` becomes `%`.
+fn parse_exec(exec: &str, name: &str, icon: Option<&str>) -> Option<Vec<String>> {
+    let mut words: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut in_word = false;
+    let mut chars = exec.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' => {
+                quoted = !quoted;
+                in_word = true;
+            }
+            '\\' if quoted => current.push(chars.next()?),
+            ch if ch.is_whitespace() && !quoted => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            ch => {
+                current.push(ch);
+                in_word = true;
+            }
+        }
+    }
+    if quoted {
+        return None;
+    }
+    if in_word {
+        words.push(current);
+    }
+
+    let mut argv = Vec::new();
+    for word in words {
+        match word.as_str() {
+            "%f" | "%F" | "%u" | "%U" | "%d" | "%D" | "%n" | "%N" | "%v" | "%m" => continue,
+            "%i" => {
+                if let Some(icon) = icon {
+                    argv.push("--icon".to_string());
+                    argv.push(icon.to_string());
+                }
+                continue;
+            }
+            _ => {}
+        }
+        let mut expanded = String::new();
+        let mut chars = word.chars();
+        while let Some(ch) = chars.next() {
+            if ch != '%' {
+                expanded.push(ch);
+                continue;
+            }
+            match chars.next() {
+                Some('%') => expanded.push('%'),
+                Some('c') => expanded.push_str(name),
+                _ => {}
+            }
+        }
+        if !expanded.is_empty() {
+            argv.push(expanded);
+        }
+    }
+    (!argv.is_empty()).then_some(argv)
+}
+
+fn icon_search_dirs() -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+    if let Some(data) = dirs::data_dir() {
+        roots.push(data.join("icons"));
+    }
+    if let Some(home) = dirs::home_dir() {
+        roots.push(home.join(".icons"));
+    }
+    roots.push(PathBuf::from("/usr/share/icons"));
+    let mut dirs = Vec::new();
+    for root in roots {
+        for size in ["48x48", "64x64", "128x128", "256x256", "32x32", "scalable"] {
+            dirs.push(root.join("hicolor").join(size).join("apps"));
+        }
+    }
+    dirs.push(PathBuf::from("/usr/share/pixmaps"));
+    dirs
+}
+
+/// Finds the file of an `Icon` value: absolute paths are used when they
+/// exist, names are looked up in the hicolor theme and pixmaps.
+fn resolve_icon(icon: &str, search_dirs: &[PathBuf]) -> Option<String> {
+    let path = Path::new(icon);
+    if path.is_absolute() {
+        return path.is_file().then(|| icon.to_string());
+    }
+    search_dirs
+        .iter()
+        .flat_map(|dir| ["png", "svg", "xpm"].map(|ext| dir.join(format!("{icon}.{ext}"))))
+        .find(|candidate| candidate.is_file())
+        .map(|found| found.to_string_lossy().into_owned())
+}
+
+/// Adds `app` to `catalog`. An existing entry with the same name is kept and
+/// reported instead of being overwritten.
+pub fn import_desktop_app(
+    app: &DesktopAppEntry,
+    catalog: ProgramCatalog,
+) -> Result<String, String> {
+    if catalog_names(catalog).contains(&app.name) {
+        return Err(format!("{} already exists.", app.name));
+    }
+    Ok(add_catalog_entry(
+        catalog,
+        app.name.clone(),
+        app.to_catalog_entry(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EDITOR_ENTRY: &str = "\
+[Desktop Entry]
+Type=Application
+Name=Text Editor
+Name[de]=Texteditor
+Comment=Edit text files
+Exec=gedit --new-window \"%c window\" %U
+Icon=accessories-text-editor
+Categories=Utility;TextEditor;
+Terminal=false
+
+[Desktop Action new-window]
+Name=New Window
+Exec=gedit --new-window
+";
+
+    #[test]
+    fn parse_desktop_entry_strips_field_codes_and_reads_metadata() {
+        let entry = parse_desktop_entry("gedit.desktop", EDITOR_ENTRY).expect("entry");
+
+        assert_eq!(entry.name, "Text Editor");
+        assert_eq!(
+            entry.command,
+            vec!["gedit", "--new-window", "Text Editor window"]
+        );
+        assert_eq!(entry.categories, vec!["Utility", "TextEditor"]);
+        assert_eq!(entry.suggested_catalog(), ProgramCatalog::Applications);
+        let catalog_entry = entry.to_catalog_entry();
+        assert_eq!(catalog_entry.run_mode, CatalogRunMode::Detached);
+        assert_eq!(catalog_entry.description, "Edit text files");
+    }
+
+    #[test]
+    fn terminal_games_route_to_pty_in_games_catalog() {
+        let text = "[Desktop Entry]\nType=Application\nName=Nethack\n\
+                    Exec=nethack --file=%f\nTerminal=true\nCategories=Game;RolePlaying;\n";
+        let entry = parse_desktop_entry("nethack.desktop", text).expect("entry");
+
+        assert_eq!(entry.command, vec!["nethack", "--file="]);
+        assert_eq!(entry.suggested_catalog(), ProgramCatalog::Games);
+        assert_eq!(entry.to_catalog_entry().run_mode, CatalogRunMode::Pty);
+    }
+
+    #[test]
+    fn hidden_and_non_application_entries_are_skipped() {
+        let hidden = "[Desktop Entry]\nType=Application\nName=X\nExec=x\nNoDisplay=true\n";
+        let link = "[Desktop Entry]\nType=Link\nName=X\nURL=https://example.com\n";
+        assert!(parse_desktop_entry("x.desktop", hidden).is_none());
+        assert!(parse_desktop_entry("x.desktop", link).is_none());
+    }
+
+    #[test]
+    fn user_entries_replace_system_entries_and_icons_resolve() {
+        let root =
+            std::env::temp_dir().join(format!("robcos_desktop_import_{}", std::process::id()));
+        let system = root.join("system");
+        let user = root.join("user");
+        let icons = root.join("icons");
+        for dir in [&system, &user, &icons] {
+            std::fs::create_dir_all(dir).expect("create dir");
+        }
+        std::fs::write(
+            system.join("tool.desktop"),
+            "[Desktop Entry]\nType=Application\nName=Tool\nExec=tool\nIcon=tool\n",
+        )
+        .expect("write system entry");
+        std::fs::write(
+            user.join("tool.desktop"),
+            "[Desktop Entry]\nType=Application\nName=My Tool\nExec=tool --mine\nIcon=tool\n",
+        )
+        .expect("write user entry");
+        std::fs::write(icons.join("tool.png"), b"png").expect("write icon");
+
+        let apps = scan_desktop_apps_in(&[system, user], std::slice::from_ref(&icons));
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(apps.len(), 1);
+        assert_eq!(apps[0].name, "My Tool");
+        assert_eq!(
+            apps[0].icon.as_deref(),
+            Some(icons.join("tool.png").to_string_lossy().as_ref())
+        );
+    }
+}
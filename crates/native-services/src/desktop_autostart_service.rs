@@ -0,0 +1,108 @@
+use crate::autostart::{AutostartItem, AutostartMode};
+use std::time::{Duration, Instant};
+
+/// Autostart items of the current login waiting for their delay or mode.
+#[derive(Debug, Default)]
+pub struct AutostartQueue {
+    pending: Vec<(Instant, AutostartItem)>,
+}
+
+impl AutostartQueue {
+    /// Queues the enabled items of a fresh login, replacing any left over
+    /// from an earlier one.
+    pub fn start(&mut self, items: &[AutostartItem], now: Instant) {
+        self.pending = items
+            .iter()
+            .filter(|item| item.enabled)
+            .map(|item| {
+                let due = now + Duration::from_secs(u64::from(item.delay_secs));
+                (due, item.clone())
+            })
+            .collect();
+    }
+
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Removes and returns, in list order, the items that are due and whose
+    /// mode matches the surface that is showing. Items for the other mode
+    /// keep waiting until the user switches.
+    pub fn take_ready(&mut self, now: Instant, desktop_open: bool) -> Vec<AutostartItem> {
+        let mode = if desktop_open {
+            AutostartMode::Desktop
+        } else {
+            AutostartMode::Terminal
+        };
+        let mut ready = Vec::new();
+        self.pending.retain(|(due, item)| {
+            if *due <= now && item.mode == mode {
+                ready.push(item.clone());
+                false
+            } else {
+                true
+            }
+        });
+        ready
+    }
+
+    /// Time until the next pending item is due, if one is still waiting on
+    /// its delay.
+    pub fn next_wakeup(&self, now: Instant) -> Option<Duration> {
+        self.pending
+            .iter()
+            .map(|(due, _)| *due)
+            .filter(|due| *due > now)
+            .min()
+            .map(|due| due - now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::autostart::{AutostartTarget, BuiltinWindow};
+
+    fn item(window: BuiltinWindow, mode: AutostartMode, delay_secs: u32) -> AutostartItem {
+        AutostartItem {
+            delay_secs,
+            ..AutostartItem::new(AutostartTarget::Window { window }, mode)
+        }
+    }
+
+    #[test]
+    fn releases_items_by_delay_and_mode_in_order() {
+        let now = Instant::now();
+        let mut disabled = item(BuiltinWindow::Editor, AutostartMode::Terminal, 0);
+        disabled.enabled = false;
+        let mut queue = AutostartQueue::default();
+        queue.start(
+            &[
+                item(BuiltinWindow::Scheduler, AutostartMode::Terminal, 5),
+                item(BuiltinWindow::FileManager, AutostartMode::Desktop, 0),
+                item(BuiltinWindow::TaskManager, AutostartMode::Terminal, 0),
+                disabled,
+            ],
+            now,
+        );
+
+        let ready = queue.take_ready(now, false);
+        assert_eq!(ready.len(), 1);
+        assert_eq!(
+            ready[0].target,
+            AutostartTarget::Window {
+                window: BuiltinWindow::TaskManager
+            }
+        );
+        assert_eq!(queue.next_wakeup(now), Some(Duration::from_secs(5)));
+
+        let later = now + Duration::from_secs(5);
+        assert_eq!(queue.take_ready(later, true).len(), 1);
+        assert_eq!(queue.take_ready(later, false).len(), 1);
+        assert!(queue.is_empty());
+    }
+}
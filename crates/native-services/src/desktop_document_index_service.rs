@@ -0,0 +1,162 @@
+use super::desktop_documents_service::document_category_entries;
+use super::desktop_search_service::word_processor_dir;
+use crate::doc_index::{load_document_index, save_document_index, ContentMatch, DocumentIndex};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long a refresh stays fresh before the folders are walked again.
+pub const DOCUMENT_INDEX_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+/// Content matches shown at most.
+pub const CONTENT_SEARCH_LIMIT: usize = 30;
+/// Shorter queries match too much text to be useful.
+pub const MIN_CONTENT_QUERY_CHARS: usize = 3;
+
+/// Folders covered by the index: the user's word-processor folder, the
+/// journal and every document category.
+pub fn document_index_roots(username: &str) -> Vec<PathBuf> {
+    let mut roots = vec![
+        word_processor_dir(username),
+        PathBuf::from("journal_entries"),
+    ];
+    roots.extend(
+        document_category_entries()
+            .into_iter()
+            .map(|(_, path)| path),
+    );
+    let mut roots: Vec<PathBuf> = roots
+        .into_iter()
+        .filter_map(|root| std::fs::canonicalize(root).ok())
+        .collect();
+    roots.sort();
+    roots.dedup();
+    roots
+}
+
+/// The signed-in user's document index, refreshed lazily. Refreshes walk
+/// and read the folders on a worker thread; searches use the last completed
+/// index until the new one arrives.
+#[derive(Default)]
+pub struct DocumentIndexService {
+    username: Option<String>,
+    index: DocumentIndex,
+    last_refresh: Option<Instant>,
+    refreshing: Option<Receiver<DocumentIndex>>,
+    /// Files saved while a refresh runs, re-indexed once it lands so the
+    /// older snapshot it started from does not undo them.
+    saved_during_refresh: Vec<PathBuf>,
+}
+
+impl DocumentIndexService {
+    pub fn sync_user(&mut self, username: Option<&str>) {
+        if self.username.as_deref() == username {
+            return;
+        }
+        self.username = username.map(str::to_string);
+        self.index = username.map(load_document_index).unwrap_or_default();
+        self.last_refresh = None;
+        self.refreshing = None;
+        self.saved_during_refresh.clear();
+    }
+
+    pub fn index(&self) -> &DocumentIndex {
+        &self.index
+    }
+
+    pub fn is_refreshing(&self) -> bool {
+        self.refreshing.is_some()
+    }
+
+    /// Starts a background refresh unless one is running or the last one is
+    /// recent.
+    pub fn refresh_if_stale(&mut self) {
+        if self.refreshing.is_some()
+            || self
+                .last_refresh
+                .is_some_and(|last| last.elapsed() < DOCUMENT_INDEX_REFRESH_INTERVAL)
+        {
+            return;
+        }
+        let Some(username) = self.username.clone() else {
+            return;
+        };
+        self.last_refresh = Some(Instant::now());
+        let mut index = self.index.clone();
+        let (tx, rx) = mpsc::channel();
+        self.refreshing = Some(rx);
+        thread::spawn(move || {
+            if index.refresh(&document_index_roots(&username)) {
+                save_document_index(&username, &index);
+            }
+            let _ = tx.send(index);
+        });
+    }
+
+    /// Takes in a finished background refresh. Returns true when the index
+    /// changed hands, so cached search results can be gathered again.
+    pub fn poll(&mut self) -> bool {
+        let Some(rx) = self.refreshing.as_ref() else {
+            return false;
+        };
+        match rx.try_recv() {
+            Ok(index) => {
+                self.refreshing = None;
+                self.index = index;
+                for path in std::mem::take(&mut self.saved_during_refresh) {
+                    self.note_saved(&path);
+                }
+                true
+            }
+            Err(TryRecvError::Empty) => false,
+            Err(TryRecvError::Disconnected) => {
+                self.refreshing = None;
+                self.saved_during_refresh.clear();
+                false
+            }
+        }
+    }
+
+    /// Re-indexes a file that was just saved.
+    pub fn note_saved(&mut self, path: &Path) {
+        let Some(username) = self.username.clone() else {
+            return;
+        };
+        let Ok(path) = std::fs::canonicalize(path) else {
+            return;
+        };
+        if self.refreshing.is_some() {
+            self.saved_during_refresh.push(path.clone());
+        }
+        if self
+            .index
+            .update_file(&path, &document_index_roots(&username))
+        {
+            save_document_index(&username, &self.index);
+        }
+    }
+
+    pub fn rebuild(&mut self) -> String {
+        let Some(username) = self.username.clone() else {
+            return "Sign in to index documents.".to_string();
+        };
+        // A full rebuild supersedes any refresh still running.
+        self.refreshing = None;
+        self.saved_during_refresh.clear();
+        self.index.rebuild(&document_index_roots(&username));
+        self.last_refresh = Some(Instant::now());
+        save_document_index(&username, &self.index);
+        format!(
+            "Indexed {} documents ({} KB).",
+            self.index.document_count(),
+            self.index.total_bytes() / 1024
+        )
+    }
+
+    /// Searches the last completed index, starting a refresh if it is stale.
+    pub fn search(&mut self, query: &str) -> Vec<ContentMatch> {
+        self.poll();
+        self.refresh_if_stale();
+        self.index.search(query, CONTENT_SEARCH_LIMIT)
+    }
+}
@@ -0,0 +1,123 @@
+use super::desktop_launcher_service::{catalog_names, ProgramCatalog};
+use crate::config::get_settings;
+use crate::history::{
+    clear_history, load_history, record_launch, unix_now, LaunchHistory, LaunchKind, LaunchRecord,
+};
+use std::path::{Path, PathBuf};
+
+pub fn launch_kind_for_catalog(catalog: ProgramCatalog) -> LaunchKind {
+    match catalog {
+        ProgramCatalog::Applications => LaunchKind::Application,
+        ProgramCatalog::Network => LaunchKind::Network,
+        ProgramCatalog::Games => LaunchKind::Game,
+    }
+}
+
+fn catalog_for_launch_kind(kind: LaunchKind) -> Option<ProgramCatalog> {
+    match kind {
+        LaunchKind::Application => Some(ProgramCatalog::Applications),
+        LaunchKind::Network => Some(ProgramCatalog::Network),
+        LaunchKind::Game => Some(ProgramCatalog::Games),
+        LaunchKind::Document | LaunchKind::File => None,
+    }
+}
+
+pub fn launch_history_enabled() -> bool {
+    get_settings().launch_history_enabled
+}
+
+pub fn record_catalog_launch(catalog: ProgramCatalog, name: &str) {
+    record_launch(launch_kind_for_catalog(catalog), name);
+}
+
+pub fn record_document_open(path: &Path) {
+    record_launch(LaunchKind::Document, &path.to_string_lossy());
+}
+
+pub fn record_file_open(path: &Path) {
+    record_launch(LaunchKind::File, &path.to_string_lossy());
+}
+
+/// The stored history, or an empty one while recording is disabled.
+pub fn launch_history() -> LaunchHistory {
+    if launch_history_enabled() {
+        load_history()
+    } else {
+        LaunchHistory::default()
+    }
+}
+
+pub fn clear_launch_history() -> String {
+    clear_history();
+    "Launch history cleared.".to_string()
+}
+
+/// One history item that can still be opened: a catalog entry that still
+/// exists or a path that is still on disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryLaunchTarget {
+    Catalog {
+        name: String,
+        catalog: ProgramCatalog,
+    },
+    Path {
+        path: PathBuf,
+        kind: LaunchKind,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub label: String,
+    pub target: HistoryLaunchTarget,
+}
+
+fn history_entries(records: Vec<LaunchRecord>, limit: usize) -> Vec<HistoryEntry> {
+    let applications = catalog_names(ProgramCatalog::Applications);
+    let network = catalog_names(ProgramCatalog::Network);
+    let games = catalog_names(ProgramCatalog::Games);
+    let known = |catalog: ProgramCatalog, name: &String| match catalog {
+        ProgramCatalog::Applications => applications.contains(name),
+        ProgramCatalog::Network => network.contains(name),
+        ProgramCatalog::Games => games.contains(name),
+    };
+    records
+        .into_iter()
+        .filter_map(|record| {
+            let label = record.label();
+            let target = match catalog_for_launch_kind(record.kind) {
+                Some(catalog) => {
+                    if !known(catalog, &record.target) {
+                        return None;
+                    }
+                    HistoryLaunchTarget::Catalog {
+                        name: record.target,
+                        catalog,
+                    }
+                }
+                None => {
+                    let path = PathBuf::from(&record.target);
+                    if !path.exists() {
+                        return None;
+                    }
+                    HistoryLaunchTarget::Path {
+                        path,
+                        kind: record.kind,
+                    }
+                }
+            };
+            Some(HistoryEntry { label, target })
+        })
+        .take(limit)
+        .collect()
+}
+
+/// Highest frecency first.
+pub fn frequent_history_entries(limit: usize) -> Vec<HistoryEntry> {
+    history_entries(launch_history().ranked(unix_now()), limit)
+}
+
+/// Most recently used first.
+pub fn recent_history_entries(limit: usize) -> Vec<HistoryEntry> {
+    history_entries(launch_history().recent(), limit)
+}
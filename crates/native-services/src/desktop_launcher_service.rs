@@ -1,6 +1,13 @@
+use crate::catalog::{tags_from_text, tags_to_text};
+use crate::catalog::{CatalogEntry, CatalogRunMode};
+use crate::config::LaunchEnvironment;
 use crate::config::{load_apps, load_games, load_networks, save_apps, save_games, save_networks};
+use crate::connections::network_available;
 use crate::default_apps::parse_custom_command_line;
-use crate::launcher::json_to_cmd;
+use crate::launcher::{
+    json_to_cmd, launch_args_from_text, launch_args_to_text, launch_env_from_text,
+    launch_env_to_text,
+};
 use serde_json::{Map, Value};
 
 fn resolve_program_command(name: &str, source: &Map<String, Value>) -> Result<Vec<String>, String> {
@@ -25,6 +32,63 @@ pub enum ProgramCatalog {
 pub struct ResolvedProgramLaunch {
     pub title: String,
     pub argv: Vec<String>,
+    /// Environment, working directory and extra arguments of the entry.
+    pub launch: LaunchEnvironment,
+    pub run_mode: CatalogRunMode,
+    /// Profile name that overrides the one picked by program name.
+    pub pty_profile: Option<String>,
+    pub requires_network: bool,
+}
+
+/// Text form of a catalog entry for editors. Environment variables are one
+/// `KEY=VALUE` per line; arguments and the command use shell quoting.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CatalogEntryDraft {
+    pub command: String,
+    pub description: String,
+    pub icon: String,
+    pub tags: String,
+    pub working_dir: String,
+    pub arguments: String,
+    pub environment: String,
+    pub pty_profile: String,
+    pub run_mode: CatalogRunMode,
+    pub requires_network: bool,
+}
+
+impl CatalogEntryDraft {
+    pub fn from_entry(entry: &CatalogEntry) -> Self {
+        Self {
+            command: launch_args_to_text(&entry.command),
+            description: entry.description.clone(),
+            icon: entry.icon.clone().unwrap_or_default(),
+            tags: tags_to_text(&entry.tags),
+            working_dir: entry.launch.cwd.clone().unwrap_or_default(),
+            arguments: launch_args_to_text(&entry.launch.args),
+            environment: launch_env_to_text(&entry.launch.env),
+            pty_profile: entry.pty_profile.clone().unwrap_or_default(),
+            run_mode: entry.run_mode,
+            requires_network: entry.requires_network,
+        }
+    }
+
+    pub fn to_entry(&self) -> Result<CatalogEntry, String> {
+        let optional = |text: &str| Some(text.trim().to_string()).filter(|text| !text.is_empty());
+        Ok(CatalogEntry {
+            command: parse_catalog_command_line(&self.command)?,
+            description: self.description.trim().to_string(),
+            icon: optional(&self.icon),
+            tags: tags_from_text(&self.tags),
+            launch: LaunchEnvironment {
+                env: launch_env_from_text(&self.environment),
+                cwd: optional(&self.working_dir),
+                args: launch_args_from_text(&self.arguments),
+            },
+            run_mode: self.run_mode,
+            requires_network: self.requires_network,
+            pty_profile: optional(&self.pty_profile),
+        })
+    }
 }
 
 fn load_catalog_source(catalog: ProgramCatalog) -> Map<String, Value> {
@@ -47,9 +111,18 @@ pub fn resolve_program_launch_from_source(
     name: &str,
     source: &Map<String, Value>,
 ) -> Result<ResolvedProgramLaunch, String> {
-    resolve_program_command(name, source).map(|argv| ResolvedProgramLaunch {
+    let argv = resolve_program_command(name, source)?;
+    let entry = source
+        .get(name)
+        .map(CatalogEntry::from_json)
+        .unwrap_or_default();
+    Ok(ResolvedProgramLaunch {
         title: name.to_string(),
         argv,
+        launch: entry.launch,
+        run_mode: entry.run_mode,
+        pty_profile: entry.pty_profile,
+        requires_network: entry.requires_network,
     })
 }
 
@@ -63,6 +136,24 @@ pub fn catalog_names(catalog: ProgramCatalog) -> Vec<String> {
     sorted_source_names(&load_catalog_source(catalog))
 }
 
+/// Entries of a catalog in name order, old and new format alike.
+pub fn catalog_entries(catalog: ProgramCatalog) -> Vec<(String, CatalogEntry)> {
+    let source = load_catalog_source(catalog);
+    sorted_source_names(&source)
+        .into_iter()
+        .map(|name| {
+            let entry = CatalogEntry::from_json(&source[&name]);
+            (name, entry)
+        })
+        .collect()
+}
+
+pub fn catalog_entry(catalog: ProgramCatalog, name: &str) -> Option<CatalogEntry> {
+    load_catalog_source(catalog)
+        .get(name)
+        .map(CatalogEntry::from_json)
+}
+
 pub fn resolve_catalog_launch(
     name: &str,
     catalog: ProgramCatalog,
@@ -70,6 +161,17 @@ pub fn resolve_catalog_launch(
     resolve_program_launch_from_source(name, &load_catalog_source(catalog))
 }
 
+/// Status shown when an entry that needs the network is launched offline.
+/// The launch still goes ahead.
+pub fn catalog_network_warning(program: &ResolvedProgramLaunch) -> Option<String> {
+    (program.requires_network && !network_available()).then(|| {
+        format!(
+            "{} needs a network connection, but none is up.",
+            program.title
+        )
+    })
+}
+
 pub fn resolve_catalog_command_line(name: &str, catalog: ProgramCatalog) -> Option<String> {
     resolve_catalog_launch(name, catalog)
         .ok()
@@ -89,12 +191,24 @@ pub fn parse_catalog_command_line(raw: &str) -> Result<Vec<String>, String> {
 fn insert_catalog_entry_into_source(
     source: &mut Map<String, Value>,
     name: String,
-    argv: Vec<String>,
+    entry: &CatalogEntry,
 ) {
-    source.insert(
-        name,
-        Value::Array(argv.into_iter().map(Value::String).collect()),
-    );
+    source.insert(name, entry.to_json());
+}
+
+fn update_catalog_entry_in_source(
+    source: &mut Map<String, Value>,
+    name: &str,
+    entry: &CatalogEntry,
+) -> Result<(), String> {
+    if entry.command.is_empty() {
+        return Err("Error: empty command.".to_string());
+    }
+    let Some(value) = source.get_mut(name) else {
+        return Err(format!("{name} was not found."));
+    };
+    *value = entry.to_json();
+    Ok(())
 }
 
 fn rename_catalog_entry_in_source(
@@ -112,13 +226,30 @@ fn rename_catalog_entry_in_source(
     Ok(())
 }
 
-pub fn add_catalog_entry(catalog: ProgramCatalog, name: String, argv: Vec<String>) -> String {
+/// Adds or replaces an entry. Accepts a bare argv or a full [`CatalogEntry`].
+pub fn add_catalog_entry(
+    catalog: ProgramCatalog,
+    name: String,
+    entry: impl Into<CatalogEntry>,
+) -> String {
     let mut source = load_catalog_source(catalog);
-    insert_catalog_entry_into_source(&mut source, name.clone(), argv);
+    insert_catalog_entry_into_source(&mut source, name.clone(), &entry.into());
     save_catalog_source(catalog, &source);
     format!("{name} added.")
 }
 
+/// Replaces the command and metadata of an existing entry.
+pub fn update_catalog_entry(
+    catalog: ProgramCatalog,
+    name: &str,
+    entry: &CatalogEntry,
+) -> Result<String, String> {
+    let mut source = load_catalog_source(catalog);
+    update_catalog_entry_in_source(&mut source, name, entry)?;
+    save_catalog_source(catalog, &source);
+    Ok(format!("{name} updated."))
+}
+
 pub fn delete_catalog_entry(catalog: ProgramCatalog, name: &str) -> String {
     let mut source = load_catalog_source(catalog);
     source.remove(name);
@@ -218,7 +349,7 @@ mod tests {
         insert_catalog_entry_into_source(
             &mut source,
             "Editor".to_string(),
-            vec!["hx".to_string(), "demo.txt".to_string()],
+            &CatalogEntry::from_command(vec!["hx".to_string(), "demo.txt".to_string()]),
         );
 
         assert_eq!(
@@ -244,4 +375,49 @@ mod tests {
         assert_eq!(err, "Beta already exists.");
         assert!(source.contains_key("Alpha"));
     }
+
+    #[test]
+    fn update_catalog_entry_in_source_keeps_metadata_and_old_entries() {
+        let mut source = source_with_command("Monitor", &["btop"]);
+        source.insert("Other".to_string(), Value::Array(vec![Value::from("top")]));
+        let entry = CatalogEntry {
+            description: "System monitor".to_string(),
+            run_mode: CatalogRunMode::Detached,
+            ..CatalogEntry::from_command(vec!["btop".to_string()])
+        };
+
+        update_catalog_entry_in_source(&mut source, "Monitor", &entry).expect("update entry");
+        let launch =
+            resolve_program_launch_from_source("Monitor", &source).expect("resolve program launch");
+
+        assert_eq!(launch.run_mode, CatalogRunMode::Detached);
+        assert!(source["Monitor"].is_object());
+        assert!(source["Other"].is_array());
+        assert!(update_catalog_entry_in_source(&mut source, "Missing", &entry).is_err());
+    }
+
+    #[test]
+    fn catalog_entry_draft_round_trips_entries() {
+        let entry = CatalogEntry {
+            description: "Mail client".to_string(),
+            tags: vec!["mail".to_string(), "net".to_string()],
+            launch: LaunchEnvironment {
+                env: [("TERM".to_string(), "xterm-256color".to_string())]
+                    .into_iter()
+                    .collect(),
+                cwd: Some("~/Mail".to_string()),
+                args: vec!["-F".to_string(), "my config".to_string()],
+            },
+            requires_network: true,
+            pty_profile: Some("default".to_string()),
+            ..CatalogEntry::from_command(vec!["neomutt".to_string()])
+        };
+
+        let draft = CatalogEntryDraft::from_entry(&entry);
+        assert_eq!(draft.tags, "mail, net");
+        assert_eq!(draft.to_entry(), Ok(entry));
+
+        let empty = CatalogEntryDraft::default();
+        assert!(empty.to_entry().is_err());
+    }
 }
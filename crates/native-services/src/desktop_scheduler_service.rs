@@ -0,0 +1,360 @@
+use super::desktop_launcher_service::{resolve_catalog_launch, ProgramCatalog};
+use crate::config::LaunchEnvironment;
+use crate::history::LaunchKind;
+use crate::launcher::resolve_launch_environment;
+use crate::pty::{PtyLaunchOptions, PtySession};
+use crate::scheduler::{
+    job_log_path, load_schedule, save_schedule, JobOutcome, JobRunResult, JobSchedule, JobTarget,
+    Schedule, ScheduledJob,
+};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// How often due jobs are checked.
+pub const SCHEDULER_TICK_INTERVAL: Duration = Duration::from_secs(1);
+/// Size of the hidden PTY; wide enough that most tools do not wrap.
+const HIDDEN_PTY_COLS: u16 = 160;
+const HIDDEN_PTY_ROWS: u16 = 48;
+
+/// A job to open in a visible terminal window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobLaunch {
+    pub title: String,
+    pub argv: Vec<String>,
+    pub launch: LaunchEnvironment,
+}
+
+pub fn resolve_job_launch(job: &ScheduledJob) -> Result<JobLaunch, String> {
+    match &job.target {
+        JobTarget::Catalog { kind, name } => {
+            let catalog = match kind {
+                LaunchKind::Network => ProgramCatalog::Network,
+                LaunchKind::Game => ProgramCatalog::Games,
+                _ => ProgramCatalog::Applications,
+            };
+            let program = resolve_catalog_launch(name, catalog)?;
+            Ok(JobLaunch {
+                title: job.name.clone(),
+                argv: program.argv,
+                launch: program.launch,
+            })
+        }
+        JobTarget::Command { argv } if !argv.is_empty() => Ok(JobLaunch {
+            title: job.name.clone(),
+            argv: argv.clone(),
+            launch: LaunchEnvironment::default(),
+        }),
+        JobTarget::Command { .. } => Err("Error: empty command.".to_string()),
+    }
+}
+
+/// A run in a hidden PTY whose output goes to `log`.
+struct HiddenJobRun {
+    username: String,
+    job_id: u64,
+    job_name: String,
+    started: u64,
+    log: PathBuf,
+    session: PtySession,
+}
+
+impl HiddenJobRun {
+    fn start(username: &str, job: &ScheduledJob, now: u64) -> Result<Self, String> {
+        let program = resolve_job_launch(job)?;
+        let resolved = resolve_launch_environment(&program.launch);
+        let argv: Vec<String> = program.argv.into_iter().chain(resolved.args).collect();
+        let log = job_log_path(username, job.id, now);
+        let options = PtyLaunchOptions {
+            env: resolved.env,
+            cwd: resolved.cwd,
+            transcript: Some(log.clone()),
+            ..PtyLaunchOptions::default()
+        };
+        let args: Vec<&str> = argv[1..].iter().map(String::as_str).collect();
+        let session =
+            PtySession::spawn(&argv[0], &args, HIDDEN_PTY_COLS, HIDDEN_PTY_ROWS, &options)
+                .map_err(|err| format!("Launch failed: {err}"))?;
+        Ok(Self {
+            username: username.to_string(),
+            job_id: job.id,
+            job_name: job.name.clone(),
+            started: now,
+            log,
+            session,
+        })
+    }
+
+    fn finished(&mut self, now: u64) -> Option<JobRunResult> {
+        if self.session.is_alive() {
+            return None;
+        }
+        let outcome = match self.session.exit_status() {
+            Some(status) if !status.success() => JobOutcome::Failed {
+                code: status.exit_code(),
+            },
+            _ => JobOutcome::Success,
+        };
+        Some(JobRunResult {
+            job_id: self.job_id,
+            job_name: self.job_name.clone(),
+            started: self.started,
+            finished: now,
+            outcome,
+            log: Some(self.log.clone()),
+        })
+    }
+}
+
+/// Scheduled jobs of the signed-in user and their hidden runs in progress.
+///
+/// Runs keep going when the user changes and their results are filed under
+/// the user who started them.
+#[derive(Default)]
+pub struct SchedulerRuntime {
+    username: Option<String>,
+    schedule: Schedule,
+    running: Vec<HiddenJobRun>,
+    last_tick: Option<Instant>,
+}
+
+impl SchedulerRuntime {
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    pub fn is_running(&self, job_id: u64) -> bool {
+        self.username.as_deref().is_some_and(|username| {
+            self.running
+                .iter()
+                .any(|run| run.job_id == job_id && run.username == username)
+        })
+    }
+
+    /// Loads the schedule of `username` when it differs from the one shown.
+    pub fn sync_user(&mut self, username: Option<&str>) {
+        if self.username.as_deref() == username {
+            return;
+        }
+        self.username = username.map(str::to_string);
+        self.schedule = username.map(load_schedule).unwrap_or_default();
+    }
+
+    fn save(&self) {
+        if let Some(username) = &self.username {
+            save_schedule(username, &self.schedule);
+        }
+    }
+
+    fn record(&mut self, username: &str, result: JobRunResult) {
+        if self.username.as_deref() == Some(username) {
+            self.schedule.record_result(result);
+            self.save();
+        } else {
+            let mut schedule = load_schedule(username);
+            schedule.record_result(result);
+            save_schedule(username, &schedule);
+        }
+    }
+
+    /// Collects finished runs and starts due jobs, at most once per
+    /// [`SCHEDULER_TICK_INTERVAL`]. Visible jobs are returned for the caller
+    /// to open; when `can_show_window` is false they run hidden instead.
+    pub fn tick(
+        &mut self,
+        username: Option<&str>,
+        now: u64,
+        can_show_window: bool,
+    ) -> Vec<JobLaunch> {
+        if self
+            .last_tick
+            .is_some_and(|last| last.elapsed() < SCHEDULER_TICK_INTERVAL)
+        {
+            return Vec::new();
+        }
+        self.last_tick = Some(Instant::now());
+        self.sync_user(username);
+
+        let mut finished = Vec::new();
+        self.running.retain_mut(|run| match run.finished(now) {
+            Some(result) => {
+                finished.push((run.username.clone(), result));
+                false
+            }
+            None => true,
+        });
+        for (username, result) in finished {
+            self.record(&username, result);
+        }
+
+        let mut launches = Vec::new();
+        for job_id in self.schedule.due_jobs(now) {
+            if self.is_running(job_id) {
+                continue;
+            }
+            if let Some(launch) = self.start_job(job_id, now, can_show_window) {
+                launches.push(launch);
+            }
+        }
+        launches
+    }
+
+    /// Starts a job right away regardless of its schedule. Does not move
+    /// the schedule of interval and cron jobs.
+    pub fn run_now(
+        &mut self,
+        job_id: u64,
+        now: u64,
+        can_show_window: bool,
+    ) -> Result<Option<JobLaunch>, String> {
+        if self.is_running(job_id) {
+            return Err("That job is already running.".to_string());
+        }
+        let last_run = self.schedule.job(job_id).map(|job| job.last_run);
+        let Some(last_run) = last_run else {
+            return Err("That job no longer exists.".to_string());
+        };
+        let launch = self.start_job(job_id, now, can_show_window);
+        let keeps_schedule = self
+            .schedule
+            .job(job_id)
+            .is_some_and(|job| !matches!(job.schedule, JobSchedule::Once { .. }));
+        if keeps_schedule {
+            if let Some(job) = self.schedule.job_mut(job_id) {
+                job.last_run = last_run;
+            }
+            self.save();
+        }
+        Ok(launch)
+    }
+
+    fn start_job(&mut self, job_id: u64, now: u64, can_show_window: bool) -> Option<JobLaunch> {
+        let username = self.username.clone()?;
+        let job = self.schedule.job_mut(job_id)?;
+        job.last_run = Some(now);
+        let job = job.clone();
+        self.save();
+
+        let error = |message: String| JobRunResult {
+            job_id,
+            job_name: job.name.clone(),
+            started: now,
+            finished: now,
+            outcome: JobOutcome::Error { message },
+            log: None,
+        };
+        if job.visible && can_show_window {
+            return match resolve_job_launch(&job) {
+                Ok(launch) => {
+                    let opened = JobRunResult {
+                        outcome: JobOutcome::Opened,
+                        ..error(String::new())
+                    };
+                    self.record(&username, opened);
+                    Some(launch)
+                }
+                Err(message) => {
+                    self.record(&username, error(message));
+                    None
+                }
+            };
+        }
+        match HiddenJobRun::start(&username, &job, now) {
+            Ok(run) => self.running.push(run),
+            Err(message) => self.record(&username, error(message)),
+        }
+        None
+    }
+
+    pub fn add_job(
+        &mut self,
+        name: &str,
+        target: &str,
+        schedule: &str,
+        visible: bool,
+        now: u64,
+    ) -> Result<String, String> {
+        if self.username.is_none() {
+            return Err("Sign in to schedule jobs.".to_string());
+        }
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Error: the job needs a name.".to_string());
+        }
+        let target = JobTarget::parse(target)?;
+        let schedule = JobSchedule::parse(schedule, now)?;
+        self.schedule.add_job(name, target, schedule, visible, now);
+        self.save();
+        Ok(format!("{name} scheduled."))
+    }
+
+    pub fn remove_job(&mut self, job_id: u64) -> String {
+        let name = self
+            .schedule
+            .job(job_id)
+            .map(|job| job.name.clone())
+            .unwrap_or_default();
+        self.schedule.remove_job(job_id);
+        self.save();
+        format!("{name} removed.")
+    }
+
+    pub fn toggle_enabled(&mut self, job_id: u64) -> String {
+        let Some(job) = self.schedule.job_mut(job_id) else {
+            return String::new();
+        };
+        job.enabled = !job.enabled;
+        let status = if job.enabled {
+            format!("{} enabled.", job.name)
+        } else {
+            format!("{} paused.", job.name)
+        };
+        self.save();
+        status
+    }
+
+    pub fn toggle_visible(&mut self, job_id: u64) -> String {
+        let Some(job) = self.schedule.job_mut(job_id) else {
+            return String::new();
+        };
+        job.visible = !job.visible;
+        let status = if job.visible {
+            format!("{} opens a terminal window.", job.name)
+        } else {
+            format!("{} runs hidden and logs its output.", job.name)
+        };
+        self.save();
+        status
+    }
+
+    /// When the next check is needed: soon while runs are in progress,
+    /// otherwise at the next due time.
+    pub fn next_wakeup(&self, now: u64) -> Option<Duration> {
+        if !self.running.is_empty() {
+            return Some(SCHEDULER_TICK_INTERVAL);
+        }
+        self.schedule
+            .jobs
+            .iter()
+            .filter_map(ScheduledJob::next_run)
+            .min()
+            .map(|next| Duration::from_secs(next.saturating_sub(now)).max(SCHEDULER_TICK_INTERVAL))
+    }
+
+    /// Stops all hidden runs, e.g. at logout. They are recorded as stopped.
+    pub fn terminate_all(&mut self, now: u64) {
+        for mut run in std::mem::take(&mut self.running) {
+            run.session.terminate();
+            let result = JobRunResult {
+                job_id: run.job_id,
+                job_name: run.job_name.clone(),
+                started: run.started,
+                finished: now,
+                outcome: JobOutcome::Error {
+                    message: "Stopped".to_string(),
+                },
+                log: Some(run.log.clone()),
+            };
+            self.record(&run.username, result);
+        }
+    }
+}
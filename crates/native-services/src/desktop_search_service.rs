@@ -1,5 +1,11 @@
 use super::desktop_documents_service::document_category_entries;
-use super::desktop_launcher_service::{catalog_names, ProgramCatalog};
+use super::desktop_history_service::launch_history;
+use super::desktop_launcher_service::{catalog_entries, ProgramCatalog};
+use super::desktop_spotlight_service::{spotlight_providers, SpotlightProvider};
+use crate::catalog::CatalogEntry;
+use crate::config::SpotlightProviderSettings;
+use crate::doc_index::{ContentMatch, ContentSnippet};
+use crate::history::{unix_now, LaunchHistory, LaunchKind};
 use std::collections::HashSet;
 use std::path::PathBuf;
 
@@ -12,7 +18,7 @@ fn documents_dir() -> PathBuf {
     dirs::document_dir().unwrap_or_else(home_dir_fallback)
 }
 
-fn word_processor_dir(username: &str) -> PathBuf {
+pub(crate) fn word_processor_dir(username: &str) -> PathBuf {
     let dir = documents_dir().join("ROBCO Word Processor").join(username);
     let _ = std::fs::create_dir_all(&dir);
     dir
@@ -33,6 +39,8 @@ pub enum NativeStartLeafAction {
 pub struct NativeStartLeafEntry {
     pub label: String,
     pub action: NativeStartLeafAction,
+    /// Catalog description and tags, shown on hover.
+    pub detail: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -43,6 +51,14 @@ pub enum NativeSpotlightCategory {
     File,
     System,
     Network,
+    /// Calculator answer; `name` holds the value.
+    Calculation,
+    /// Unit conversion answer; `name` holds the value with its unit.
+    Conversion,
+    /// Command line typed after `>`.
+    Command(Vec<String>),
+    /// User manual section; `name` holds its heading.
+    Manual,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -50,20 +66,75 @@ pub struct NativeSpotlightResult {
     pub name: String,
     pub category: NativeSpotlightCategory,
     pub path: Option<PathBuf>,
+    pub description: Option<String>,
+    /// Matching text inside the document, for content hits.
+    pub snippet: Option<ContentSnippet>,
 }
 
-fn game_program_names(builtin_game_name: &str) -> Vec<String> {
-    let mut names = vec![builtin_game_name.to_string()];
-    names.extend(
-        catalog_names(ProgramCatalog::Games)
+type CatalogEntries = Vec<(String, CatalogEntry)>;
+
+fn spotlight_frecency(result: &NativeSpotlightResult, history: &LaunchHistory, now: u64) -> u64 {
+    let kind = match result.category {
+        NativeSpotlightCategory::App => LaunchKind::Application,
+        NativeSpotlightCategory::Game => LaunchKind::Game,
+        NativeSpotlightCategory::Network => LaunchKind::Network,
+        NativeSpotlightCategory::Document | NativeSpotlightCategory::File => {
+            let Some(path) = result.path.as_ref() else {
+                return 0;
+            };
+            let path = path.to_string_lossy();
+            return history
+                .frecency(LaunchKind::Document, &path, now)
+                .max(history.frecency(LaunchKind::File, &path, now));
+        }
+        NativeSpotlightCategory::System
+        | NativeSpotlightCategory::Calculation
+        | NativeSpotlightCategory::Conversion
+        | NativeSpotlightCategory::Command(_)
+        | NativeSpotlightCategory::Manual => return 0,
+    };
+    history.frecency(kind, &result.name, now)
+}
+
+fn game_program_entries(builtin_game_name: &str) -> CatalogEntries {
+    let mut entries = vec![(builtin_game_name.to_string(), CatalogEntry::default())];
+    entries.extend(
+        catalog_entries(ProgramCatalog::Games)
             .into_iter()
-            .filter(|name| name != builtin_game_name),
+            .filter(|(name, _)| name != builtin_game_name),
     );
-    names
+    entries
+}
+
+/// Hover text of a catalog entry: description, tags and launch notes.
+pub fn catalog_entry_detail(entry: &CatalogEntry) -> Option<String> {
+    let mut lines = Vec::new();
+    if let Some(description) = entry.description() {
+        lines.push(description.to_string());
+    }
+    if !entry.tags.is_empty() {
+        lines.push(format!("Tags: {}", entry.tags.join(", ")));
+    }
+    if entry.requires_network {
+        lines.push("Requires network".to_string());
+    }
+    (!lines.is_empty()).then(|| lines.join("\n"))
 }
 
-fn start_application_entries_from_names(
-    application_names: Vec<String>,
+fn catalog_leaf_entry(
+    name: String,
+    entry: &CatalogEntry,
+    action: impl FnOnce(String) -> NativeStartLeafAction,
+) -> NativeStartLeafEntry {
+    NativeStartLeafEntry {
+        label: name.clone(),
+        action: action(name),
+        detail: catalog_entry_detail(entry),
+    }
+}
+
+fn start_application_entries_from_catalog(
+    application_entries: CatalogEntries,
     show_nuke_codes: bool,
     show_text_editor: bool,
     text_editor_name: &str,
@@ -74,27 +145,31 @@ fn start_application_entries_from_names(
         items.push(NativeStartLeafEntry {
             label: nuke_codes_name.to_string(),
             action: NativeStartLeafAction::LaunchNukeCodes,
+            detail: None,
         });
     }
     if show_text_editor {
         items.push(NativeStartLeafEntry {
             label: text_editor_name.to_string(),
             action: NativeStartLeafAction::OpenTextEditor,
+            detail: None,
         });
     }
-    for name in application_names {
+    for (name, entry) in application_entries {
         if name == nuke_codes_name || name == text_editor_name {
             continue;
         }
-        items.push(NativeStartLeafEntry {
-            label: name.clone(),
-            action: NativeStartLeafAction::LaunchConfiguredApp(name),
-        });
+        items.push(catalog_leaf_entry(
+            name,
+            &entry,
+            NativeStartLeafAction::LaunchConfiguredApp,
+        ));
     }
     if items.is_empty() {
         items.push(NativeStartLeafEntry {
             label: "(No applications)".to_string(),
             action: NativeStartLeafAction::None,
+            detail: None,
         });
     }
     items
@@ -106,8 +181,8 @@ pub fn start_application_entries(
     text_editor_name: &str,
     nuke_codes_name: &str,
 ) -> Vec<NativeStartLeafEntry> {
-    start_application_entries_from_names(
-        catalog_names(ProgramCatalog::Applications),
+    start_application_entries_from_catalog(
+        catalog_entries(ProgramCatalog::Applications),
         show_nuke_codes,
         show_text_editor,
         text_editor_name,
@@ -121,18 +196,21 @@ pub fn start_document_entries(username: Option<&str>) -> Vec<NativeStartLeafEntr
         items.push(NativeStartLeafEntry {
             label: "My Documents".to_string(),
             action: NativeStartLeafAction::OpenDocumentCategory(word_processor_dir(username)),
+            detail: None,
         });
     }
     for (name, path) in document_category_entries() {
         items.push(NativeStartLeafEntry {
             label: name,
             action: NativeStartLeafAction::OpenDocumentCategory(path),
+            detail: None,
         });
     }
     if items.is_empty() {
         items.push(NativeStartLeafEntry {
             label: "(No documents)".to_string(),
             action: NativeStartLeafAction::None,
+            detail: None,
         });
     }
     items
@@ -140,16 +218,18 @@ pub fn start_document_entries(username: Option<&str>) -> Vec<NativeStartLeafEntr
 
 pub fn start_network_entries() -> Vec<NativeStartLeafEntry> {
     let mut items = Vec::new();
-    for key in catalog_names(ProgramCatalog::Network) {
-        items.push(NativeStartLeafEntry {
-            label: key.clone(),
-            action: NativeStartLeafAction::LaunchNetworkProgram(key),
-        });
+    for (key, entry) in catalog_entries(ProgramCatalog::Network) {
+        items.push(catalog_leaf_entry(
+            key,
+            &entry,
+            NativeStartLeafAction::LaunchNetworkProgram,
+        ));
     }
     if items.is_empty() {
         items.push(NativeStartLeafEntry {
             label: "(No network apps)".to_string(),
             action: NativeStartLeafAction::None,
+            detail: None,
         });
     }
     items
@@ -157,16 +237,18 @@ pub fn start_network_entries() -> Vec<NativeStartLeafEntry> {
 
 pub fn start_game_entries(builtin_game_name: &str) -> Vec<NativeStartLeafEntry> {
     let mut items = Vec::new();
-    for key in game_program_names(builtin_game_name) {
-        items.push(NativeStartLeafEntry {
-            label: key.clone(),
-            action: NativeStartLeafAction::LaunchGameProgram(key),
-        });
+    for (key, entry) in game_program_entries(builtin_game_name) {
+        items.push(catalog_leaf_entry(
+            key,
+            &entry,
+            NativeStartLeafAction::LaunchGameProgram,
+        ));
     }
     if items.is_empty() {
         items.push(NativeStartLeafEntry {
             label: "(No games installed)".to_string(),
             action: NativeStartLeafAction::None,
+            detail: None,
         });
     }
     items
@@ -180,9 +262,17 @@ pub fn spotlight_category_tag(category: &NativeSpotlightCategory) -> &'static st
         NativeSpotlightCategory::File => "FILE",
         NativeSpotlightCategory::System => "SYS",
         NativeSpotlightCategory::Network => "NET",
+        NativeSpotlightCategory::Calculation => "CALC",
+        NativeSpotlightCategory::Conversion => "CONV",
+        NativeSpotlightCategory::Command(_) => "RUN",
+        NativeSpotlightCategory::Manual => "HELP",
     }
 }
 
+/// `content_matches` are hits from the document index for the same query;
+/// they are listed under Documents. `providers` adds the optional
+/// providers that are turned on.
+#[allow(clippy::too_many_arguments)]
 pub fn gather_spotlight_results(
     query: &str,
     tab: u8,
@@ -190,32 +280,71 @@ pub fn gather_spotlight_results(
     text_editor_name: &str,
     nuke_codes_name: &str,
     builtin_game_name: &str,
+    content_matches: Vec<ContentMatch>,
+    providers: &SpotlightProviderSettings,
 ) -> Vec<NativeSpotlightResult> {
-    gather_spotlight_results_with_names(
+    gather_spotlight_results_with_entries(
         query,
         tab,
+        &spotlight_providers(providers),
         active_username,
         text_editor_name,
         nuke_codes_name,
-        catalog_names(ProgramCatalog::Applications),
-        game_program_names(builtin_game_name),
-        catalog_names(ProgramCatalog::Network),
+        catalog_entries(ProgramCatalog::Applications),
+        game_program_entries(builtin_game_name),
+        catalog_entries(ProgramCatalog::Network),
+        content_matches,
+        &launch_history(),
     )
 }
 
-fn gather_spotlight_results_with_names(
+#[allow(clippy::too_many_arguments)]
+fn gather_spotlight_results_with_entries(
     query: &str,
     tab: u8,
+    providers: &[SpotlightProvider],
     active_username: Option<&str>,
     text_editor_name: &str,
     nuke_codes_name: &str,
-    application_names: Vec<String>,
-    game_names: Vec<String>,
-    network_names: Vec<String>,
+    application_entries: CatalogEntries,
+    game_entries: CatalogEntries,
+    network_entries: CatalogEntries,
+    content_matches: Vec<ContentMatch>,
+    history: &LaunchHistory,
 ) -> Vec<NativeSpotlightResult> {
+    let trimmed = query.trim_start();
+    for provider in providers {
+        if let Some(rest) = provider
+            .prefix()
+            .and_then(|prefix| trimmed.strip_prefix(prefix))
+        {
+            return provider.results(rest);
+        }
+    }
+    // Instant answers lead the All tab; frecency only orders the rest.
+    let mut answers = Vec::new();
+    if tab == 0 && !trimmed.is_empty() {
+        for provider in providers
+            .iter()
+            .filter(|provider| provider.prefix().is_none())
+        {
+            answers.extend(provider.results(query));
+        }
+    }
+
     let query = query.to_lowercase();
     let matches_query =
         |name: &str| -> bool { query.is_empty() || name.to_lowercase().contains(&query) };
+    let matches_entry = |name: &str, entry: &CatalogEntry| -> bool {
+        matches_query(name) || entry.matches_metadata(&query)
+    };
+    let catalog_result = |name: String, entry: &CatalogEntry, category| NativeSpotlightResult {
+        name,
+        category,
+        path: None,
+        description: entry.description().map(str::to_string),
+        snippet: None,
+    };
     let mut results = Vec::new();
 
     if tab == 0 || tab == 1 {
@@ -231,34 +360,28 @@ fn gather_spotlight_results_with_names(
                     name: (*name).to_string(),
                     category: NativeSpotlightCategory::System,
                     path: None,
+                    description: None,
+                    snippet: None,
                 });
             }
         }
-        for name in application_names {
-            if name != nuke_codes_name && name != text_editor_name && matches_query(&name) {
-                results.push(NativeSpotlightResult {
-                    name,
-                    category: NativeSpotlightCategory::App,
-                    path: None,
-                });
+        for (name, entry) in application_entries {
+            if name != nuke_codes_name && name != text_editor_name && matches_entry(&name, &entry) {
+                results.push(catalog_result(name, &entry, NativeSpotlightCategory::App));
             }
         }
-        for name in game_names {
-            if matches_query(&name) {
-                results.push(NativeSpotlightResult {
-                    name,
-                    category: NativeSpotlightCategory::Game,
-                    path: None,
-                });
+        for (name, entry) in game_entries {
+            if matches_entry(&name, &entry) {
+                results.push(catalog_result(name, &entry, NativeSpotlightCategory::Game));
             }
         }
-        for name in network_names {
-            if matches_query(&name) {
-                results.push(NativeSpotlightResult {
+        for (name, entry) in network_entries {
+            if matches_entry(&name, &entry) {
+                results.push(catalog_result(
                     name,
-                    category: NativeSpotlightCategory::Network,
-                    path: None,
-                });
+                    &entry,
+                    NativeSpotlightCategory::Network,
+                ));
             }
         }
     }
@@ -276,12 +399,38 @@ fn gather_spotlight_results_with_names(
                                 name,
                                 category: NativeSpotlightCategory::Document,
                                 path: Some(entry.path()),
+                                description: None,
+                                snippet: None,
                             });
                         }
                     }
                 }
             }
         }
+        let canonical =
+            |path: &PathBuf| std::fs::canonicalize(path).unwrap_or_else(|_| path.clone());
+        for hit in content_matches {
+            let existing = results.iter_mut().find(|result| {
+                result.category == NativeSpotlightCategory::Document
+                    && result.path.as_ref().map(canonical).as_ref() == Some(&hit.path)
+            });
+            if let Some(result) = existing {
+                result.snippet = Some(hit.snippet);
+                continue;
+            }
+            let name = hit
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| hit.path.display().to_string());
+            results.push(NativeSpotlightResult {
+                name,
+                category: NativeSpotlightCategory::Document,
+                path: Some(hit.path),
+                description: None,
+                snippet: Some(hit.snippet),
+            });
+        }
     }
 
     if tab == 0 || tab == 3 {
@@ -304,6 +453,8 @@ fn gather_spotlight_results_with_names(
                             name,
                             category: NativeSpotlightCategory::File,
                             path: Some(path),
+                            description: None,
+                            snippet: None,
                         });
                     }
                 }
@@ -311,8 +462,12 @@ fn gather_spotlight_results_with_names(
         }
     }
 
-    results.truncate(50);
+    let now = unix_now();
     results
+        .sort_by_cached_key(|result| std::cmp::Reverse(spotlight_frecency(result, history, now)));
+    answers.extend(results);
+    answers.truncate(50);
+    answers
 }
 
 #[cfg(test)]
@@ -321,8 +476,11 @@ mod tests {
 
     #[test]
     fn start_application_entries_hide_duplicate_builtins() {
-        let items = start_application_entries_from_names(
-            vec!["ROBCO Word Processor".to_string(), "Hex".to_string()],
+        let items = start_application_entries_from_catalog(
+            vec![
+                ("ROBCO Word Processor".to_string(), CatalogEntry::default()),
+                ("Hex".to_string(), CatalogEntry::default()),
+            ],
             true,
             true,
             "ROBCO Word Processor",
@@ -336,15 +494,18 @@ mod tests {
 
     #[test]
     fn gather_spotlight_results_includes_system_and_catalog_hits() {
-        let results = gather_spotlight_results_with_names(
+        let results = gather_spotlight_results_with_entries(
             "hel",
             1,
+            &[],
             None,
             "ROBCO Word Processor",
             "Nuke Codes",
-            vec!["Helix".to_string()],
-            vec!["Donkey Kong".to_string()],
+            vec![("Helix".to_string(), CatalogEntry::default())],
+            vec![("Donkey Kong".to_string(), CatalogEntry::default())],
             Vec::new(),
+            Vec::new(),
+            &LaunchHistory::default(),
         );
 
         assert_eq!(
@@ -353,7 +514,133 @@ mod tests {
                 name: "Helix".to_string(),
                 category: NativeSpotlightCategory::App,
                 path: None,
+                description: None,
+                snippet: None,
             }]
         );
     }
+
+    #[test]
+    fn gather_spotlight_results_match_catalog_descriptions_and_tags() {
+        let monitor = CatalogEntry {
+            description: "Resource monitor".to_string(),
+            tags: vec!["system".to_string()],
+            ..CatalogEntry::from_command(vec!["btop".to_string()])
+        };
+        let search = |query: &str| {
+            gather_spotlight_results_with_entries(
+                query,
+                1,
+                &[],
+                None,
+                "ROBCO Word Processor",
+                "Nuke Codes",
+                vec![("btop".to_string(), monitor.clone())],
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+                &LaunchHistory::default(),
+            )
+        };
+
+        let results = search("monitor");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].description.as_deref(), Some("Resource monitor"));
+        assert_eq!(search("SYSTEM").len(), 1);
+        assert!(search("editor").is_empty());
+    }
+
+    #[test]
+    fn gather_spotlight_results_list_content_hits_under_documents() {
+        let hit = ContentMatch {
+            path: PathBuf::from("/docs/vault-log.txt"),
+            snippet: ContentSnippet {
+                text: "the overseer lied".to_string(),
+                highlight: 4..12,
+            },
+        };
+        let search = |tab| {
+            gather_spotlight_results_with_entries(
+                "overseer",
+                tab,
+                &[],
+                None,
+                "ROBCO Word Processor",
+                "Nuke Codes",
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+                vec![hit.clone()],
+                &LaunchHistory::default(),
+            )
+        };
+
+        let results = search(2);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "vault-log.txt");
+        assert_eq!(results[0].category, NativeSpotlightCategory::Document);
+        assert_eq!(results[0].snippet.as_ref(), Some(&hit.snippet));
+        assert!(search(1).is_empty());
+    }
+
+    #[test]
+    fn gather_spotlight_results_put_answers_first_and_let_prefixes_take_over() {
+        let search = |query: &str, tab, providers: &[SpotlightProvider]| {
+            gather_spotlight_results_with_entries(
+                query,
+                tab,
+                providers,
+                None,
+                "ROBCO Word Processor",
+                "Nuke Codes",
+                vec![("calc 2+2".to_string(), CatalogEntry::default())],
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+                &LaunchHistory::default(),
+            )
+        };
+        let all = SpotlightProvider::ALL;
+
+        let results = search("2+2", 0, &all);
+        assert_eq!(results[0].name, "4");
+        assert_eq!(results[0].category, NativeSpotlightCategory::Calculation);
+        assert_eq!(results[1].name, "calc 2+2");
+        assert!(search("2+2", 1, &all)
+            .iter()
+            .all(|result| result.category == NativeSpotlightCategory::App));
+        assert_eq!(search("2+2", 0, &[]).len(), 1);
+
+        let results = search("> calc 2+2", 0, &all);
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].category,
+            NativeSpotlightCategory::Command(vec!["calc".to_string(), "2+2".to_string()])
+        );
+        assert!(search("> calc", 0, &[]).is_empty());
+    }
+
+    #[test]
+    fn gather_spotlight_results_rank_by_frecency() {
+        let mut history = LaunchHistory::default();
+        let now = unix_now();
+        history.record(LaunchKind::Game, "Helltris", now);
+        history.record(LaunchKind::Game, "Helltris", now);
+        let results = gather_spotlight_results_with_entries(
+            "hel",
+            1,
+            &[],
+            None,
+            "ROBCO Word Processor",
+            "Nuke Codes",
+            vec![("Helix".to_string(), CatalogEntry::default())],
+            vec![("Helltris".to_string(), CatalogEntry::default())],
+            Vec::new(),
+            Vec::new(),
+            &history,
+        );
+
+        let names: Vec<&str> = results.iter().map(|result| result.name.as_str()).collect();
+        assert_eq!(names, vec!["Helltris", "Helix"]);
+    }
 }
@@ -1,11 +1,11 @@
 use super::shared_file_manager_settings::{
-    FileManagerDisplaySettingsUpdate, FileManagerSettingsUpdate,
+    push_recent_folder, FileManagerDisplaySettingsUpdate, FileManagerSettingsUpdate,
 };
 use crate::config::{
-    cycle_hacking_difficulty, get_settings, persist_settings, reload_settings, update_settings,
-    DesktopFileManagerSettings, DesktopPtyProfileSettings, HackingDifficulty, Settings,
+    cycle_hacking_difficulty, get_settings, persist_settings, pty_profile_key, reload_settings,
+    update_settings, DesktopFileManagerSettings, DesktopPtyProfileSettings, HackingDifficulty,
+    Settings,
 };
-use std::path::Path;
 
 fn persist_settings_change<F>(apply: F)
 where
@@ -50,6 +50,13 @@ pub fn apply_file_manager_display_settings_update(
             settings.desktop_file_manager.show_tree_panel =
                 !settings.desktop_file_manager.show_tree_panel;
         }
+        FileManagerDisplaySettingsUpdate::TogglePreviewPanel => {
+            settings.desktop_file_manager.show_preview_panel =
+                !settings.desktop_file_manager.show_preview_panel;
+        }
+        FileManagerDisplaySettingsUpdate::ToggleDualPane => {
+            settings.desktop_file_manager.dual_pane = !settings.desktop_file_manager.dual_pane;
+        }
         FileManagerDisplaySettingsUpdate::ToggleHiddenFiles => {
             settings.desktop_file_manager.show_hidden_files =
                 !settings.desktop_file_manager.show_hidden_files;
@@ -71,6 +78,13 @@ pub fn apply_file_manager_settings_update(draft: &mut Settings, update: FileMana
     sync_live_file_manager_settings_to_draft(draft);
 }
 
+pub fn record_recent_folder(draft: &mut Settings, path: &str) {
+    persist_settings_change(|settings| {
+        push_recent_folder(&mut settings.desktop_session.recent_folders, path);
+    });
+    draft.desktop_session.recent_folders = load_settings_snapshot().desktop_session.recent_folders;
+}
+
 pub fn cycle_hacking_difficulty_in_settings(draft: &mut Settings) {
     persist_settings_change(|settings| {
         settings.hacking_difficulty = cycle_hacking_difficulty(settings.hacking_difficulty, true);
@@ -78,44 +92,18 @@ pub fn cycle_hacking_difficulty_in_settings(draft: &mut Settings) {
     draft.hacking_difficulty = load_settings_snapshot().hacking_difficulty;
 }
 
-fn pty_profile_key(raw: &str) -> Option<String> {
-    let trimmed = raw.trim();
-    if trimmed.is_empty() {
-        return None;
-    }
-    let base = Path::new(trimmed)
-        .file_name()
-        .and_then(|s| s.to_str())
-        .unwrap_or(trimmed)
-        .trim();
-    if base.is_empty() {
-        None
-    } else {
-        Some(base.to_ascii_lowercase())
-    }
+pub fn pty_profile_for_command(cmd: &[String]) -> DesktopPtyProfileSettings {
+    load_settings_snapshot()
+        .desktop_cli_profiles
+        .for_command(cmd)
 }
 
-pub fn pty_profile_for_command(cmd: &[String]) -> DesktopPtyProfileSettings {
-    let profiles = load_settings_snapshot().desktop_cli_profiles;
-    let Some(base) = cmd.first().and_then(|program| pty_profile_key(program)) else {
-        return profiles.default;
-    };
-    if let Some(custom) = profiles.custom.get(&base) {
-        return custom.clone();
-    }
-    match base.as_str() {
-        name if name.starts_with("calcurse") => profiles.calcurse,
-        name if name.starts_with("myman") => DesktopPtyProfileSettings {
-            live_resize: false,
-            preferred_w: Some(96),
-            preferred_h: Some(32),
-            ..profiles.default
-        },
-        "spotify_player" => profiles.spotify_player,
-        "ranger" => profiles.ranger,
-        "tuir" | "rtv" => profiles.reddit,
-        _ => profiles.default,
-    }
+/// Like [`pty_profile_for_command`], honouring a catalog entry's preferred
+/// profile when one is set and exists.
+pub fn pty_profile_for_entry(cmd: &[String], preferred: Option<&str>) -> DesktopPtyProfileSettings {
+    load_settings_snapshot()
+        .desktop_cli_profiles
+        .for_entry(cmd, preferred)
 }
 
 pub fn pty_force_render_mode(cmd: &[String]) -> Option<bool> {
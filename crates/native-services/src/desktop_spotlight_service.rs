@@ -0,0 +1,672 @@
+//! Optional Spotlight providers: instant answers and prefixed searches.
+//!
+//! Instant answers (calculator, unit conversion) are offered next to the
+//! regular results of the All tab. A provider with a prefix (`>` runs a
+//! command, `?` searches the user manual) takes over the whole result list
+//! while the query starts with it.
+
+use super::desktop_search_service::{NativeSpotlightCategory, NativeSpotlightResult};
+use crate::config::SpotlightProviderSettings;
+use crate::default_apps::parse_custom_command_line;
+use crate::doc_index::{find_ignore_case, snippet_around};
+use std::path::PathBuf;
+
+/// Same file the Help menu opens.
+pub const USER_MANUAL_PATH: &str = "USER_MANUAL.md";
+/// Manual sections listed at most.
+const MANUAL_RESULT_LIMIT: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpotlightProvider {
+    Calculator,
+    UnitConversion,
+    RunCommand,
+    Manual,
+}
+
+impl SpotlightProvider {
+    pub const ALL: [SpotlightProvider; 4] = [
+        SpotlightProvider::Calculator,
+        SpotlightProvider::UnitConversion,
+        SpotlightProvider::RunCommand,
+        SpotlightProvider::Manual,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SpotlightProvider::Calculator => "Calculator",
+            SpotlightProvider::UnitConversion => "Unit conversion",
+            SpotlightProvider::RunCommand => "Run command (>)",
+            SpotlightProvider::Manual => "Manual search (?)",
+        }
+    }
+
+    pub fn prefix(self) -> Option<char> {
+        match self {
+            SpotlightProvider::RunCommand => Some('>'),
+            SpotlightProvider::Manual => Some('?'),
+            SpotlightProvider::Calculator | SpotlightProvider::UnitConversion => None,
+        }
+    }
+
+    pub fn enabled_mut(self, settings: &mut SpotlightProviderSettings) -> &mut bool {
+        match self {
+            SpotlightProvider::Calculator => &mut settings.calculator,
+            SpotlightProvider::UnitConversion => &mut settings.unit_conversion,
+            SpotlightProvider::RunCommand => &mut settings.run_command,
+            SpotlightProvider::Manual => &mut settings.manual,
+        }
+    }
+
+    fn is_enabled(self, settings: &SpotlightProviderSettings) -> bool {
+        match self {
+            SpotlightProvider::Calculator => settings.calculator,
+            SpotlightProvider::UnitConversion => settings.unit_conversion,
+            SpotlightProvider::RunCommand => settings.run_command,
+            SpotlightProvider::Manual => settings.manual,
+        }
+    }
+
+    /// Results for `query`, with any prefix already removed.
+    pub fn results(self, query: &str) -> Vec<NativeSpotlightResult> {
+        match self {
+            SpotlightProvider::Calculator => calculator_results(query),
+            SpotlightProvider::UnitConversion => conversion_results(query),
+            SpotlightProvider::RunCommand => command_results(query),
+            SpotlightProvider::Manual => std::fs::read_to_string(USER_MANUAL_PATH)
+                .map(|manual| manual_results(&manual, query))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// The providers turned on in `settings`, in registry order.
+pub fn spotlight_providers(settings: &SpotlightProviderSettings) -> Vec<SpotlightProvider> {
+    SpotlightProvider::ALL
+        .into_iter()
+        .filter(|provider| provider.is_enabled(settings))
+        .collect()
+}
+
+fn answer(
+    name: String,
+    description: String,
+    category: NativeSpotlightCategory,
+) -> NativeSpotlightResult {
+    NativeSpotlightResult {
+        name,
+        category,
+        path: None,
+        description: Some(description),
+        snippet: None,
+    }
+}
+
+// ── Calculator ───────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Op(char),
+}
+
+fn tokenize(text: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(&ch) = chars.peek() {
+        if ch.is_whitespace() {
+            chars.next();
+        } else if ch.is_ascii_digit() || ch == '.' {
+            let mut number = String::new();
+            while let Some(&ch) = chars.peek() {
+                if ch.is_ascii_digit() || ch == '.' {
+                    number.push(ch);
+                    chars.next();
+                } else if ch == '_' || ch == ',' {
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token::Number(number.parse().ok()?));
+        } else if ch.is_ascii_alphabetic() {
+            let mut ident = String::new();
+            while let Some(&ch) = chars.peek() {
+                if !ch.is_ascii_alphanumeric() {
+                    break;
+                }
+                ident.push(ch.to_ascii_lowercase());
+                chars.next();
+            }
+            tokens.push(Token::Ident(ident));
+        } else if "+-*/%^()".contains(ch) {
+            tokens.push(Token::Op(ch));
+            chars.next();
+        } else {
+            return None;
+        }
+    }
+    Some(tokens)
+}
+
+/// Recursive-descent parser over `+ - * / % ^`, parentheses, `pi`, `e`
+/// and a few one-argument functions.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek_op(&self) -> Option<char> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Op(op)) => Some(*op),
+            _ => None,
+        }
+    }
+
+    fn expression(&mut self) -> Option<f64> {
+        let mut value = self.term()?;
+        while let Some(op @ ('+' | '-')) = self.peek_op() {
+            self.pos += 1;
+            let rhs = self.term()?;
+            value = if op == '+' { value + rhs } else { value - rhs };
+        }
+        Some(value)
+    }
+
+    fn term(&mut self) -> Option<f64> {
+        let mut value = self.unary()?;
+        while let Some(op @ ('*' | '/' | '%')) = self.peek_op() {
+            self.pos += 1;
+            let rhs = self.unary()?;
+            value = match op {
+                '*' => value * rhs,
+                '/' => value / rhs,
+                _ => value % rhs,
+            };
+        }
+        Some(value)
+    }
+
+    fn unary(&mut self) -> Option<f64> {
+        match self.peek_op() {
+            Some('-') => {
+                self.pos += 1;
+                Some(-self.unary()?)
+            }
+            Some('+') => {
+                self.pos += 1;
+                self.unary()
+            }
+            _ => self.power(),
+        }
+    }
+
+    fn power(&mut self) -> Option<f64> {
+        let base = self.atom()?;
+        if self.peek_op() == Some('^') {
+            self.pos += 1;
+            // Right-associative, and binds tighter than a leading minus.
+            let exponent = self.unary()?;
+            return Some(base.powf(exponent));
+        }
+        Some(base)
+    }
+
+    fn atom(&mut self) -> Option<f64> {
+        let token = self.tokens.get(self.pos)?.clone();
+        self.pos += 1;
+        match token {
+            Token::Number(value) => Some(value),
+            Token::Op('(') => {
+                let value = self.expression()?;
+                if self.peek_op() != Some(')') {
+                    return None;
+                }
+                self.pos += 1;
+                Some(value)
+            }
+            Token::Ident(name) => match name.as_str() {
+                "pi" => Some(std::f64::consts::PI),
+                "e" => Some(std::f64::consts::E),
+                _ => {
+                    let function: fn(f64) -> f64 = match name.as_str() {
+                        "sqrt" => f64::sqrt,
+                        "abs" => f64::abs,
+                        "round" => f64::round,
+                        "floor" => f64::floor,
+                        "ceil" => f64::ceil,
+                        "ln" => f64::ln,
+                        "log" => f64::log10,
+                        "sin" => f64::sin,
+                        "cos" => f64::cos,
+                        "tan" => f64::tan,
+                        _ => return None,
+                    };
+                    if self.peek_op() != Some('(') {
+                        return None;
+                    }
+                    Some(function(self.atom()?))
+                }
+            },
+            Token::Op(_) => None,
+        }
+    }
+}
+
+/// Value of an arithmetic expression. A single number or constant is not
+/// an expression, so typing `e` does not show Euler's number.
+pub fn evaluate_expression(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let text = text.strip_prefix('=').unwrap_or(text);
+    let tokens = tokenize(text)?;
+    if tokens.len() < 2 {
+        return None;
+    }
+    let mut parser = Parser { tokens, pos: 0 };
+    let value = parser.expression()?;
+    (parser.pos == parser.tokens.len() && value.is_finite()).then_some(value)
+}
+
+/// Short decimal form without float noise such as `0.30000000000000004`.
+pub fn format_number(value: f64) -> String {
+    if value == 0.0 {
+        return "0".to_string();
+    }
+    if value.abs() >= 1e15 || value.abs() < 1e-6 {
+        return format!("{value:e}");
+    }
+    let text = format!("{value:.10}");
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+fn calculator_results(query: &str) -> Vec<NativeSpotlightResult> {
+    evaluate_expression(query)
+        .map(|value| {
+            answer(
+                format_number(value),
+                format!("{} =", query.trim()),
+                NativeSpotlightCategory::Calculation,
+            )
+        })
+        .into_iter()
+        .collect()
+}
+
+// ── Unit conversion ──────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dimension {
+    Length,
+    Mass,
+    Volume,
+    Time,
+    Data,
+    Temperature,
+}
+
+struct Unit {
+    /// `|`-separated names; the first is the short name shown in answers.
+    names: &'static str,
+    dimension: Dimension,
+    /// Size in the dimension's base unit. Temperatures use their own table.
+    factor: f64,
+}
+
+impl Unit {
+    fn short_name(&self) -> &'static str {
+        self.names.split('|').next().unwrap_or(self.names)
+    }
+}
+
+const fn unit(names: &'static str, dimension: Dimension, factor: f64) -> Unit {
+    Unit {
+        names,
+        dimension,
+        factor,
+    }
+}
+
+const UNITS: &[Unit] = &[
+    unit(
+        "mm|millimeter|millimeters|millimetre|millimetres",
+        Dimension::Length,
+        0.001,
+    ),
+    unit(
+        "cm|centimeter|centimeters|centimetre|centimetres",
+        Dimension::Length,
+        0.01,
+    ),
+    unit("m|meter|meters|metre|metres", Dimension::Length, 1.0),
+    unit(
+        "km|kilometer|kilometers|kilometre|kilometres",
+        Dimension::Length,
+        1000.0,
+    ),
+    unit("in|inch|inches", Dimension::Length, 0.0254),
+    unit("ft|foot|feet", Dimension::Length, 0.3048),
+    unit("yd|yard|yards", Dimension::Length, 0.9144),
+    unit("mi|mile|miles", Dimension::Length, 1609.344),
+    unit(
+        "nmi|nautical mile|nautical miles",
+        Dimension::Length,
+        1852.0,
+    ),
+    unit("mg|milligram|milligrams", Dimension::Mass, 0.000_001),
+    unit("g|gram|grams", Dimension::Mass, 0.001),
+    unit("kg|kilogram|kilograms", Dimension::Mass, 1.0),
+    unit("t|tonne|tonnes", Dimension::Mass, 1000.0),
+    unit("oz|ounce|ounces", Dimension::Mass, 0.028_349_523_125),
+    unit("lb|lbs|pound|pounds", Dimension::Mass, 0.453_592_37),
+    unit("st|stone|stones", Dimension::Mass, 6.350_293_18),
+    unit(
+        "ml|milliliter|milliliters|millilitre|millilitres",
+        Dimension::Volume,
+        0.001,
+    ),
+    unit("l|liter|liters|litre|litres", Dimension::Volume, 1.0),
+    unit(
+        "tsp|teaspoon|teaspoons",
+        Dimension::Volume,
+        0.004_928_921_593_75,
+    ),
+    unit(
+        "tbsp|tablespoon|tablespoons",
+        Dimension::Volume,
+        0.014_786_764_781_25,
+    ),
+    unit(
+        "floz|fl oz|fluid ounce|fluid ounces",
+        Dimension::Volume,
+        0.029_573_529_562_5,
+    ),
+    unit("cup|cups", Dimension::Volume, 0.236_588_236_5),
+    unit("pt|pint|pints", Dimension::Volume, 0.473_176_473),
+    unit("qt|quart|quarts", Dimension::Volume, 0.946_352_946),
+    unit("gal|gallon|gallons", Dimension::Volume, 3.785_411_784),
+    unit("ms|millisecond|milliseconds", Dimension::Time, 0.001),
+    unit("s|sec|second|seconds", Dimension::Time, 1.0),
+    unit("min|minute|minutes", Dimension::Time, 60.0),
+    unit("h|hr|hour|hours", Dimension::Time, 3600.0),
+    unit("d|day|days", Dimension::Time, 86_400.0),
+    unit("wk|week|weeks", Dimension::Time, 604_800.0),
+    unit("b|byte|bytes", Dimension::Data, 1.0),
+    unit("kb|kilobyte|kilobytes", Dimension::Data, 1e3),
+    unit("mb|megabyte|megabytes", Dimension::Data, 1e6),
+    unit("gb|gigabyte|gigabytes", Dimension::Data, 1e9),
+    unit("tb|terabyte|terabytes", Dimension::Data, 1e12),
+    unit("kib|kibibyte|kibibytes", Dimension::Data, 1024.0),
+    unit("mib|mebibyte|mebibytes", Dimension::Data, 1_048_576.0),
+    unit("gib|gibibyte|gibibytes", Dimension::Data, 1_073_741_824.0),
+    unit(
+        "tib|tebibyte|tebibytes",
+        Dimension::Data,
+        1_099_511_627_776.0,
+    ),
+    unit("c|°c|celsius", Dimension::Temperature, 0.0),
+    unit("f|°f|fahrenheit", Dimension::Temperature, 0.0),
+    unit("k|kelvin", Dimension::Temperature, 0.0),
+];
+
+fn find_unit(name: &str) -> Option<&'static Unit> {
+    let name = name.trim().to_lowercase();
+    UNITS
+        .iter()
+        .find(|unit| unit.names.split('|').any(|candidate| candidate == name))
+}
+
+fn to_kelvin(value: f64, unit: &Unit) -> f64 {
+    match unit.short_name() {
+        "c" => value + 273.15,
+        "f" => (value - 32.0) * 5.0 / 9.0 + 273.15,
+        _ => value,
+    }
+}
+
+fn from_kelvin(value: f64, unit: &Unit) -> f64 {
+    match unit.short_name() {
+        "c" => value - 273.15,
+        "f" => (value - 273.15) * 9.0 / 5.0 + 32.0,
+        _ => value,
+    }
+}
+
+/// Converts queries like `10 km to mi`, `72f in c` or `2.5 gib -> mb`.
+/// Returns the value and the target unit's short name.
+pub fn convert_units(text: &str) -> Option<(f64, &'static str)> {
+    let lower = text.trim().to_lowercase();
+    let (from, to) = [" to ", " in ", " -> ", "->"]
+        .iter()
+        .filter_map(|separator| lower.rsplit_once(separator))
+        .next()?;
+    let from = from.trim();
+    let split = from
+        .char_indices()
+        .find(|(idx, ch)| !(ch.is_ascii_digit() || *ch == '.' || (*idx == 0 && *ch == '-')))
+        .map_or(from.len(), |(idx, _)| idx);
+    let value: f64 = from[..split].parse().ok()?;
+    let from_unit = find_unit(&from[split..])?;
+    let to_unit = find_unit(to)?;
+    if from_unit.dimension != to_unit.dimension {
+        return None;
+    }
+    let converted = if from_unit.dimension == Dimension::Temperature {
+        from_kelvin(to_kelvin(value, from_unit), to_unit)
+    } else {
+        value * from_unit.factor / to_unit.factor
+    };
+    converted
+        .is_finite()
+        .then_some((converted, to_unit.short_name()))
+}
+
+fn conversion_results(query: &str) -> Vec<NativeSpotlightResult> {
+    convert_units(query)
+        .map(|(value, unit)| {
+            let unit = match unit {
+                "c" | "f" => format!("°{}", unit.to_uppercase()),
+                "k" => "K".to_string(),
+                _ => unit.to_string(),
+            };
+            answer(
+                format!("{} {unit}", format_number(value)),
+                format!("{} =", query.trim()),
+                NativeSpotlightCategory::Conversion,
+            )
+        })
+        .into_iter()
+        .collect()
+}
+
+// ── Run command ──────────────────────────────────────────────────────────
+
+/// Characters that only mean something to a shell: pipes, redirects,
+/// variables, globs, command lists and subshells.
+const SHELL_SYNTAX: &[char] = &[
+    '|', '&', ';', '<', '>', '$', '`', '*', '?', '~', '(', ')', '{', '}', '[', ']',
+];
+
+/// A plain command line runs directly so terminal profiles still match the
+/// program; anything using shell syntax runs through `sh -c`.
+fn command_argv(line: &str) -> Option<Vec<String>> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+    if line.contains(SHELL_SYNTAX) {
+        return Some(vec!["sh".to_string(), "-c".to_string(), line.to_string()]);
+    }
+    parse_custom_command_line(line).filter(|argv| !argv.is_empty())
+}
+
+fn command_results(query: &str) -> Vec<NativeSpotlightResult> {
+    let Some(argv) = command_argv(query) else {
+        return Vec::new();
+    };
+    vec![answer(
+        query.trim().to_string(),
+        "Run in a new terminal window".to_string(),
+        NativeSpotlightCategory::Command(argv),
+    )]
+}
+
+// ── Manual search ────────────────────────────────────────────────────────
+
+/// `##` and `###` sections of a Markdown document as (heading, body).
+/// Lines inside code fences never start a section.
+fn manual_sections(manual: &str) -> Vec<(String, String)> {
+    let mut sections: Vec<(String, String)> = Vec::new();
+    let mut in_fence = false;
+    for line in manual.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        let heading = (!in_fence)
+            .then(|| {
+                line.strip_prefix("### ")
+                    .or_else(|| line.strip_prefix("## "))
+            })
+            .flatten();
+        match (heading, sections.last_mut()) {
+            (Some(heading), _) => sections.push((heading.trim().to_string(), String::new())),
+            (None, Some((_, body))) => {
+                body.push_str(line);
+                body.push('\n');
+            }
+            (None, None) => {}
+        }
+    }
+    sections
+}
+
+/// Sections of `manual` whose heading or text contains `query`. Heading
+/// matches come first; text matches carry a snippet.
+pub fn manual_results(manual: &str, query: &str) -> Vec<NativeSpotlightResult> {
+    let query = query.trim();
+    let result = |heading: &str| NativeSpotlightResult {
+        name: heading.to_string(),
+        category: NativeSpotlightCategory::Manual,
+        path: Some(PathBuf::from(USER_MANUAL_PATH)),
+        description: None,
+        snippet: None,
+    };
+    let sections = manual_sections(manual);
+    let mut results: Vec<NativeSpotlightResult> = sections
+        .iter()
+        .filter(|(heading, _)| query.is_empty() || find_ignore_case(heading, query).is_some())
+        .map(|(heading, _)| result(heading))
+        .collect();
+    if !query.is_empty() {
+        for (heading, body) in &sections {
+            if find_ignore_case(heading, query).is_some() {
+                continue;
+            }
+            if let Some(range) = find_ignore_case(body, query) {
+                results.push(NativeSpotlightResult {
+                    snippet: Some(snippet_around(body, range)),
+                    ..result(heading)
+                });
+            }
+        }
+    }
+    results.truncate(MANUAL_RESULT_LIMIT);
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluates_arithmetic_with_precedence() {
+        assert_eq!(evaluate_expression("2 + 3 * 4"), Some(14.0));
+        assert_eq!(evaluate_expression("(2 + 3) * 4"), Some(20.0));
+        assert_eq!(evaluate_expression("= 2 ^ 3 ^ 2"), Some(512.0));
+        assert_eq!(evaluate_expression("-2 ^ 2"), Some(-4.0));
+        assert_eq!(evaluate_expression("sqrt(16) + 10 % 4"), Some(6.0));
+        assert_eq!(
+            format_number(evaluate_expression("0.1 + 0.2").unwrap()),
+            "0.3"
+        );
+        assert_eq!(
+            format_number(evaluate_expression("1 / 3").unwrap()),
+            "0.3333333333"
+        );
+        assert_eq!(evaluate_expression("42"), None);
+        assert_eq!(evaluate_expression("1 / 0"), None);
+        assert_eq!(evaluate_expression("notes"), None);
+        assert_eq!(evaluate_expression("2 +"), None);
+    }
+
+    #[test]
+    fn converts_units_and_temperatures() {
+        let (miles, unit) = convert_units("10 km to mi").unwrap();
+        assert_eq!(
+            (format_number(miles).as_str(), unit),
+            ("6.2137119224", "mi")
+        );
+        assert_eq!(convert_units("212F in c"), Some((100.0, "c")));
+        assert_eq!(
+            convert_units("-40 celsius to fahrenheit"),
+            Some((-40.0, "f"))
+        );
+        assert_eq!(convert_units("2 kib -> b"), Some((2048.0, "b")));
+        assert_eq!(convert_units("5 kg to mi"), None);
+        assert_eq!(convert_units("walk to work"), None);
+    }
+
+    #[test]
+    fn manual_search_lists_heading_then_text_matches() {
+        let manual = "# Manual\n\n## Overview\n\nUse spotlight to search.\n\n```bash\n## Spotlight build\n```\n\n### Spotlight Search\n\nOpens with a shortcut.\n";
+        let results = manual_results(manual, "spotlight");
+        let names: Vec<&str> = results.iter().map(|result| result.name.as_str()).collect();
+        assert_eq!(names, vec!["Spotlight Search", "Overview"]);
+        assert!(results[0].snippet.is_none());
+        assert_eq!(
+            results[1]
+                .snippet
+                .as_ref()
+                .map(|snippet| snippet.text.as_str()),
+            Some("Use spotlight to search.")
+        );
+        assert_eq!(manual_results(manual, "").len(), 2);
+    }
+
+    #[test]
+    fn registry_follows_settings_and_command_prefix_parses_argv() {
+        let mut settings = SpotlightProviderSettings::default();
+        assert_eq!(
+            spotlight_providers(&settings),
+            SpotlightProvider::ALL.to_vec()
+        );
+        *SpotlightProvider::Manual.enabled_mut(&mut settings) = false;
+        assert!(!spotlight_providers(&settings).contains(&SpotlightProvider::Manual));
+
+        let results = SpotlightProvider::RunCommand.results(" htop -d 10");
+        assert_eq!(
+            results[0].category,
+            NativeSpotlightCategory::Command(vec![
+                "htop".to_string(),
+                "-d".to_string(),
+                "10".to_string()
+            ])
+        );
+        let results = SpotlightProvider::RunCommand.results(" ls -l $HOME | grep rs > out.txt");
+        assert_eq!(
+            results[0].category,
+            NativeSpotlightCategory::Command(vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                "ls -l $HOME | grep rs > out.txt".to_string()
+            ])
+        );
+        assert!(SpotlightProvider::RunCommand.results("  ").is_empty());
+    }
+}
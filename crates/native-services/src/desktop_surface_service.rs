@@ -13,6 +13,7 @@ pub enum DesktopBuiltinIconKind {
     Settings,
     NukeCodes,
     Terminal,
+    Trash,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -40,7 +41,7 @@ pub struct DesktopIconDragGrid {
     pub snap_to_grid: bool,
 }
 
-const DESKTOP_BUILTIN_ICONS: [DesktopBuiltinIconEntry; 6] = [
+const DESKTOP_BUILTIN_ICONS: [DesktopBuiltinIconEntry; 7] = [
     DesktopBuiltinIconEntry {
         kind: DesktopBuiltinIconKind::FileManager,
         key: "builtin_0",
@@ -83,6 +84,13 @@ const DESKTOP_BUILTIN_ICONS: [DesktopBuiltinIconEntry; 6] = [
         ascii: "[_]",
         target_window: None,
     },
+    DesktopBuiltinIconEntry {
+        kind: DesktopBuiltinIconKind::Trash,
+        key: "builtin_6",
+        label: "Trash",
+        ascii: "[DEL]",
+        target_window: Some(DesktopWindow::FileManager),
+    },
 ];
 
 pub fn desktop_builtin_icons() -> &'static [DesktopBuiltinIconEntry] {
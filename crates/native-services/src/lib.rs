@@ -1,14 +1,23 @@
-pub use robcos_shared::{config, connections, core, default_apps, launcher, session};
+pub use robcos_shared::{
+    autostart, catalog, config, connections, core, default_apps, doc_index, history, launcher,
+    processes, pty, scheduler, session,
+};
 
+pub mod desktop_app_import_service;
+pub mod desktop_autostart_service;
 pub mod desktop_connections_service;
 pub mod desktop_default_apps_service;
+pub mod desktop_document_index_service;
 pub mod desktop_documents_service;
 pub mod desktop_file_service;
+pub mod desktop_history_service;
 pub mod desktop_launcher_service;
+pub mod desktop_scheduler_service;
 pub mod desktop_search_service;
 pub mod desktop_session_service;
 pub mod desktop_settings_service;
 pub mod desktop_shortcuts_service;
+pub mod desktop_spotlight_service;
 pub mod desktop_status_service;
 pub mod desktop_surface_service;
 pub mod desktop_user_service;
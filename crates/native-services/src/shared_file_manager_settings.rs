@@ -1,10 +1,13 @@
 use crate::config::{DesktopFileManagerSettings, FileManagerSortMode, FileManagerViewMode};
 
 const FILE_MANAGER_OPEN_WITH_HISTORY_LIMIT: usize = 8;
+const FILE_MANAGER_RECENT_FOLDERS_LIMIT: usize = 10;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FileManagerDisplaySettingsUpdate {
     ToggleTreePanel,
+    TogglePreviewPanel,
+    ToggleDualPane,
     ToggleHiddenFiles,
     SetViewMode(FileManagerViewMode),
     SetSortMode(FileManagerSortMode),
@@ -29,6 +32,21 @@ pub enum FileManagerSettingsUpdate {
         ext_key: String,
         command: String,
     },
+    SetOpenWithOverride {
+        path: String,
+        ext_key: String,
+        command: Option<String>,
+    },
+    AddBookmark {
+        path: String,
+    },
+    RemoveBookmark {
+        path: String,
+    },
+    MoveBookmark {
+        path: String,
+        up: bool,
+    },
 }
 
 pub fn push_open_with_history(history: &mut Vec<String>, command: &str) {
@@ -43,6 +61,42 @@ pub fn push_open_with_history(history: &mut Vec<String>, command: &str) {
     }
 }
 
+/// Most-recent-first list of visited folders, capped like the open-with history.
+pub fn push_recent_folder(recent: &mut Vec<String>, path: &str) {
+    if path.is_empty() {
+        return;
+    }
+    recent.retain(|entry| entry != path);
+    recent.insert(0, path.to_string());
+    recent.truncate(FILE_MANAGER_RECENT_FOLDERS_LIMIT);
+}
+
+pub fn add_bookmark_in_settings(fm: &mut DesktopFileManagerSettings, path: &str) -> bool {
+    if path.is_empty() || fm.bookmarks.iter().any(|entry| entry == path) {
+        return false;
+    }
+    fm.bookmarks.push(path.to_string());
+    true
+}
+
+pub fn remove_bookmark_in_settings(fm: &mut DesktopFileManagerSettings, path: &str) {
+    fm.bookmarks.retain(|entry| entry != path);
+}
+
+pub fn move_bookmark_in_settings(fm: &mut DesktopFileManagerSettings, path: &str, up: bool) {
+    let Some(idx) = fm.bookmarks.iter().position(|entry| entry == path) else {
+        return;
+    };
+    let target = if up {
+        idx.checked_sub(1)
+    } else {
+        Some(idx + 1).filter(|next| *next < fm.bookmarks.len())
+    };
+    if let Some(target) = target {
+        fm.bookmarks.swap(idx, target);
+    }
+}
+
 pub fn set_open_with_default_in_settings(
     fm: &mut DesktopFileManagerSettings,
     ext_key: &str,
@@ -92,6 +146,33 @@ pub fn open_with_default_for_extension(
         .filter(|value| !value.is_empty())
 }
 
+pub fn open_with_override_for_path(fm: &DesktopFileManagerSettings, path: &str) -> Option<String> {
+    fm.open_with_by_path
+        .get(path)
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// A per-file command also joins its extension's history so it is offered
+/// again in Open With.
+pub fn set_open_with_override_in_settings(
+    fm: &mut DesktopFileManagerSettings,
+    path: &str,
+    ext_key: &str,
+    command: Option<&str>,
+) {
+    match command.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(normalized) => {
+            record_open_with_command_in_settings(fm, ext_key, normalized);
+            fm.open_with_by_path
+                .insert(path.to_string(), normalized.to_string());
+        }
+        None => {
+            fm.open_with_by_path.remove(path);
+        }
+    }
+}
+
 pub fn record_open_with_command_in_settings(
     fm: &mut DesktopFileManagerSettings,
     ext_key: &str,
@@ -136,6 +217,16 @@ impl FileManagerSettingsUpdate {
                 }
                 remove_open_with_command_in_settings(fm, ext_key, normalized);
             }
+            Self::SetOpenWithOverride {
+                path,
+                ext_key,
+                command,
+            } => set_open_with_override_in_settings(fm, path, ext_key, command.as_deref()),
+            Self::AddBookmark { path } => {
+                add_bookmark_in_settings(fm, path);
+            }
+            Self::RemoveBookmark { path } => remove_bookmark_in_settings(fm, path),
+            Self::MoveBookmark { path, up } => move_bookmark_in_settings(fm, path, *up),
         }
     }
 }
@@ -32,6 +32,8 @@ pub enum DesktopWindow {
     TerminalMode,
     PtyApp,
     Installer,
+    TaskManager,
+    Scheduler,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -41,16 +43,21 @@ pub enum TerminalScreen {
     Documents,
     Network,
     Games,
+    Recent,
     DonkeyKong,
     NukeCodes,
     PtyApp,
     ProgramInstaller,
+    TaskManager,
+    Scheduler,
     Logs,
     DocumentBrowser,
     Settings,
     EditMenus,
     Connections,
     DefaultApps,
+    Autostart,
     About,
     UserManagement,
+    Commander,
 }
@@ -18,8 +18,11 @@ pub enum TerminalSettingsEvent {
     OpenConnections,
     OpenEditMenus,
     OpenDefaultApps,
+    OpenAutostart,
     OpenAbout,
     EnterUserManagement,
+    ClearLaunchHistory,
+    RebuildDocumentIndex,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -28,6 +31,7 @@ pub enum NativeSettingsPanel {
     General,
     Appearance,
     DefaultApps,
+    Autostart,
     Connections,
     ConnectionsNetwork,
     ConnectionsBluetooth,
@@ -94,9 +98,13 @@ enum SettingsRowId {
     CustomThemeBlue,
     BorderGlyphs,
     DefaultOpenMode,
+    LaunchHistory,
+    ClearLaunchHistory,
+    RebuildDocumentIndex,
     Connections,
     EditMenus,
     DefaultApps,
+    Autostart,
     About,
     UserManagement,
     Back,
@@ -108,6 +116,7 @@ pub fn settings_panel_title(panel: NativeSettingsPanel) -> &'static str {
         NativeSettingsPanel::General => "General",
         NativeSettingsPanel::Appearance => "Appearance",
         NativeSettingsPanel::DefaultApps => "Default Apps",
+        NativeSettingsPanel::Autostart => "Autostart",
         NativeSettingsPanel::Connections => "Connections",
         NativeSettingsPanel::ConnectionsNetwork => "Network",
         NativeSettingsPanel::ConnectionsBluetooth => "Bluetooth",
@@ -219,12 +228,20 @@ pub fn desktop_settings_home_rows(is_admin: bool) -> Vec<Vec<SettingsHomeTile>>
                 enabled: true,
             },
         ],
-        vec![SettingsHomeTile {
-            action: SettingsHomeTileAction::CloseWindow,
-            label: "Close",
-            icon: "[X]",
-            enabled: true,
-        }],
+        vec![
+            SettingsHomeTile {
+                action: SettingsHomeTileAction::OpenPanel(NativeSettingsPanel::Autostart),
+                label: "Autostart",
+                icon: "[>]",
+                enabled: true,
+            },
+            SettingsHomeTile {
+                action: SettingsHomeTileAction::CloseWindow,
+                label: "Close",
+                icon: "[X]",
+                enabled: true,
+            },
+        ],
     ]
 }
 
@@ -347,9 +364,16 @@ pub fn handle_settings_activation(
             ));
             TerminalSettingsEvent::None
         }
+        SettingsRowId::LaunchHistory => {
+            draft.launch_history_enabled = !draft.launch_history_enabled;
+            TerminalSettingsEvent::Persist
+        }
+        SettingsRowId::ClearLaunchHistory => TerminalSettingsEvent::ClearLaunchHistory,
+        SettingsRowId::RebuildDocumentIndex => TerminalSettingsEvent::RebuildDocumentIndex,
         SettingsRowId::Connections => TerminalSettingsEvent::OpenConnections,
         SettingsRowId::EditMenus => TerminalSettingsEvent::OpenEditMenus,
         SettingsRowId::DefaultApps => TerminalSettingsEvent::OpenDefaultApps,
+        SettingsRowId::Autostart => TerminalSettingsEvent::OpenAutostart,
         SettingsRowId::About => TerminalSettingsEvent::OpenAbout,
         SettingsRowId::UserManagement => {
             if is_admin {
@@ -498,6 +522,25 @@ fn terminal_settings_rows_with_ids(
             ),
             SettingsRowId::DefaultOpenMode,
         ),
+        (
+            format!(
+                "Launch History: {} [toggle]",
+                if draft.launch_history_enabled {
+                    "ON"
+                } else {
+                    "OFF"
+                }
+            ),
+            SettingsRowId::LaunchHistory,
+        ),
+        (
+            "Clear Launch History".to_string(),
+            SettingsRowId::ClearLaunchHistory,
+        ),
+        (
+            "Rebuild Document Index".to_string(),
+            SettingsRowId::RebuildDocumentIndex,
+        ),
     ];
     if draft.theme == CUSTOM_THEME_NAME {
         let [r, g, b] = draft.custom_theme_rgb;
@@ -524,6 +567,10 @@ fn terminal_settings_rows_with_ids(
     }
     rows.push(("Edit Menus".to_string(), SettingsRowId::EditMenus));
     rows.push(("Default Apps".to_string(), SettingsRowId::DefaultApps));
+    rows.push((
+        format!("Autostart: {} items", draft.autostart.len()),
+        SettingsRowId::Autostart,
+    ));
     rows.push(("About".to_string(), SettingsRowId::About));
     if is_admin {
         rows.push(("User Management".to_string(), SettingsRowId::UserManagement));
@@ -586,6 +633,10 @@ mod tests {
             .iter()
             .position(|(_, id)| *id == SettingsRowId::EditMenus)
             .unwrap();
+        let autostart_idx = rows
+            .iter()
+            .position(|(_, id)| *id == SettingsRowId::Autostart)
+            .unwrap();
         let about_idx = rows
             .iter()
             .position(|(_, id)| *id == SettingsRowId::About)
@@ -603,6 +654,10 @@ mod tests {
             handle_settings_activation(&mut draft, default_apps_idx, &mut overlay, false),
             TerminalSettingsEvent::OpenDefaultApps
         ));
+        assert!(matches!(
+            handle_settings_activation(&mut draft, autostart_idx, &mut overlay, false),
+            TerminalSettingsEvent::OpenAutostart
+        ));
         assert!(matches!(
             handle_settings_activation(&mut draft, about_idx, &mut overlay, false),
             TerminalSettingsEvent::OpenAbout
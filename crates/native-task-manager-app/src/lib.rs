@@ -0,0 +1,413 @@
+use robcos_shared::processes::{owned_processes, OwnedProcess, OwnedProcessKind};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessesToUpdate, Signal, System};
+
+/// How often the process table is re-read while the task manager is visible.
+pub const TASK_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+/// Parent links followed when attributing a child process to an owned one.
+const MAX_OWNER_DEPTH: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskSortKey {
+    Pid,
+    Name,
+    Cpu,
+    Memory,
+}
+
+impl TaskSortKey {
+    pub const ALL: [TaskSortKey; 4] = [
+        TaskSortKey::Pid,
+        TaskSortKey::Name,
+        TaskSortKey::Cpu,
+        TaskSortKey::Memory,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TaskSortKey::Pid => "PID",
+            TaskSortKey::Name => "Name",
+            TaskSortKey::Cpu => "CPU",
+            TaskSortKey::Memory => "Memory",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            TaskSortKey::Pid => TaskSortKey::Name,
+            TaskSortKey::Name => TaskSortKey::Cpu,
+            TaskSortKey::Cpu => TaskSortKey::Memory,
+            TaskSortKey::Memory => TaskSortKey::Pid,
+        }
+    }
+
+    /// Numbers read best largest first, names and pids ascending.
+    pub fn default_descending(self) -> bool {
+        matches!(self, TaskSortKey::Cpu | TaskSortKey::Memory)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskSignal {
+    Term,
+    Kill,
+    Hangup,
+    Interrupt,
+    Stop,
+    Continue,
+}
+
+impl TaskSignal {
+    pub const ALL: [TaskSignal; 6] = [
+        TaskSignal::Term,
+        TaskSignal::Kill,
+        TaskSignal::Hangup,
+        TaskSignal::Interrupt,
+        TaskSignal::Stop,
+        TaskSignal::Continue,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TaskSignal::Term => "SIGTERM",
+            TaskSignal::Kill => "SIGKILL",
+            TaskSignal::Hangup => "SIGHUP",
+            TaskSignal::Interrupt => "SIGINT",
+            TaskSignal::Stop => "SIGSTOP",
+            TaskSignal::Continue => "SIGCONT",
+        }
+    }
+
+    fn to_sysinfo(self) -> Signal {
+        match self {
+            TaskSignal::Term => Signal::Term,
+            TaskSignal::Kill => Signal::Kill,
+            TaskSignal::Hangup => Signal::Hangup,
+            TaskSignal::Interrupt => Signal::Interrupt,
+            TaskSignal::Stop => Signal::Stop,
+            TaskSignal::Continue => Signal::Continue,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskRow {
+    pub pid: u32,
+    pub parent: Option<u32>,
+    pub name: String,
+    pub command: String,
+    /// Percent of one core.
+    pub cpu: f32,
+    /// Resident memory in bytes.
+    pub memory: u64,
+    /// Set when RobCoOS spawned this process or one of its ancestors.
+    pub owner: Option<OwnedProcessKind>,
+}
+
+impl TaskRow {
+    pub fn pty_session(&self) -> Option<robcos_shared::pty::PtySessionId> {
+        match self.owner {
+            Some(OwnedProcessKind::Pty(id)) => Some(id),
+            _ => None,
+        }
+    }
+}
+
+/// Attributes each row to the nearest owned ancestor (or itself).
+pub fn resolve_task_owners(rows: &mut [TaskRow], owned: &[OwnedProcess]) {
+    let owned: HashMap<u32, OwnedProcessKind> = owned
+        .iter()
+        .map(|process| (process.pid, process.kind))
+        .collect();
+    let parents: HashMap<u32, Option<u32>> = rows.iter().map(|row| (row.pid, row.parent)).collect();
+    for row in rows.iter_mut() {
+        let mut current = Some(row.pid);
+        for _ in 0..MAX_OWNER_DEPTH {
+            let Some(pid) = current else {
+                break;
+            };
+            if let Some(kind) = owned.get(&pid) {
+                row.owner = Some(*kind);
+                break;
+            }
+            current = parents.get(&pid).copied().flatten();
+        }
+    }
+}
+
+/// Rows matching `filter` (name, command or pid), sorted by `sort`.
+pub fn filter_and_sort_tasks(
+    rows: &[TaskRow],
+    filter: &str,
+    owned_only: bool,
+    sort: TaskSortKey,
+    descending: bool,
+) -> Vec<TaskRow> {
+    let needle = filter.trim().to_lowercase();
+    let mut visible: Vec<TaskRow> = rows
+        .iter()
+        .filter(|row| !owned_only || row.owner.is_some())
+        .filter(|row| {
+            needle.is_empty()
+                || row.name.to_lowercase().contains(&needle)
+                || row.command.to_lowercase().contains(&needle)
+                || row.pid.to_string() == needle
+        })
+        .cloned()
+        .collect();
+    visible.sort_by(|a, b| {
+        let ordering = match sort {
+            TaskSortKey::Pid => a.pid.cmp(&b.pid),
+            TaskSortKey::Name => a
+                .name
+                .to_lowercase()
+                .cmp(&b.name.to_lowercase())
+                .then(a.pid.cmp(&b.pid)),
+            TaskSortKey::Cpu => a.cpu.total_cmp(&b.cpu).then(a.pid.cmp(&b.pid)),
+            TaskSortKey::Memory => a.memory.cmp(&b.memory).then(a.pid.cmp(&b.pid)),
+        };
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+    visible
+}
+
+pub fn format_memory(bytes: u64) -> String {
+    const KIB: u64 = 1024;
+    const MIB: u64 = KIB * 1024;
+    const GIB: u64 = MIB * 1024;
+    if bytes >= GIB {
+        format!("{:.1} GB", bytes as f64 / GIB as f64)
+    } else if bytes >= MIB {
+        format!("{:.1} MB", bytes as f64 / MIB as f64)
+    } else {
+        format!("{} KB", bytes / KIB)
+    }
+}
+
+/// One fixed-width line for the terminal screen.
+pub fn task_row_line(row: &TaskRow, name_width: usize) -> String {
+    let owner = row.owner.map_or("", OwnedProcessKind::label);
+    let mut name: String = row.name.chars().take(name_width).collect();
+    let pad = name_width.saturating_sub(name.chars().count());
+    name.push_str(&" ".repeat(pad));
+    format!(
+        "{:>7} {name} {:>6.1}% {:>9} {owner}",
+        row.pid,
+        row.cpu,
+        format_memory(row.memory)
+    )
+}
+
+pub fn task_header_line(name_width: usize) -> String {
+    format!(
+        "{:>7} {:<name_width$} {:>7} {:>9} OWNER",
+        "PID", "NAME", "CPU", "MEMORY"
+    )
+}
+
+pub struct TaskManagerState {
+    pub open: bool,
+    system: System,
+    rows: Vec<TaskRow>,
+    last_refresh: Option<Instant>,
+    pub sort: TaskSortKey,
+    pub descending: bool,
+    pub filter: String,
+    pub owned_only: bool,
+    pub selected: usize,
+}
+
+impl Default for TaskManagerState {
+    fn default() -> Self {
+        Self {
+            open: false,
+            system: System::new(),
+            rows: Vec::new(),
+            last_refresh: None,
+            sort: TaskSortKey::Cpu,
+            descending: true,
+            filter: String::new(),
+            owned_only: false,
+            selected: 0,
+        }
+    }
+}
+
+impl TaskManagerState {
+    pub fn refresh(&mut self) {
+        self.system.refresh_memory();
+        self.system.refresh_processes(ProcessesToUpdate::All, true);
+        let mut rows: Vec<TaskRow> = self
+            .system
+            .processes()
+            .iter()
+            .filter(|(_, process)| process.thread_kind().is_none())
+            .map(|(pid, process)| TaskRow {
+                pid: pid.as_u32(),
+                parent: process.parent().map(Pid::as_u32),
+                name: process.name().to_string_lossy().into_owned(),
+                command: process
+                    .cmd()
+                    .iter()
+                    .map(|part| part.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                cpu: process.cpu_usage(),
+                memory: process.memory(),
+                owner: None,
+            })
+            .collect();
+        resolve_task_owners(&mut rows, &owned_processes());
+        self.rows = rows;
+        self.last_refresh = Some(Instant::now());
+    }
+
+    /// Refreshes when the table is older than [`TASK_REFRESH_INTERVAL`].
+    pub fn refresh_if_stale(&mut self) {
+        if self
+            .last_refresh
+            .is_none_or(|at| at.elapsed() >= TASK_REFRESH_INTERVAL)
+        {
+            self.refresh();
+        }
+    }
+
+    pub fn visible_rows(&self) -> Vec<TaskRow> {
+        filter_and_sort_tasks(
+            &self.rows,
+            &self.filter,
+            self.owned_only,
+            self.sort,
+            self.descending,
+        )
+    }
+
+    pub fn selected_row(&self) -> Option<TaskRow> {
+        self.visible_rows().get(self.selected).cloned()
+    }
+
+    pub fn total_memory(&self) -> u64 {
+        self.system.total_memory()
+    }
+
+    pub fn used_memory(&self) -> u64 {
+        self.system.used_memory()
+    }
+
+    /// Clicking the active column flips the direction; another column
+    /// switches to it with its natural direction.
+    pub fn sort_by(&mut self, key: TaskSortKey) {
+        if self.sort == key {
+            self.descending = !self.descending;
+        } else {
+            self.sort = key;
+            self.descending = key.default_descending();
+        }
+    }
+
+    pub fn cycle_sort(&mut self) {
+        self.sort_by(self.sort.next());
+    }
+
+    pub fn send_signal(&mut self, pid: u32, signal: TaskSignal) -> Result<String, String> {
+        let status = match self.system.process(Pid::from_u32(pid)) {
+            None => Err(format!("Process {pid} is no longer running.")),
+            Some(process) => match process.kill_with(signal.to_sysinfo()) {
+                None => Err(format!("{} is not supported here.", signal.label())),
+                Some(false) => Err(format!("Could not send {} to {pid}.", signal.label())),
+                Some(true) => Ok(format!("Sent {} to {pid}.", signal.label())),
+            },
+        };
+        self.refresh();
+        status
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(pid: u32, parent: Option<u32>, name: &str, cpu: f32, memory: u64) -> TaskRow {
+        TaskRow {
+            pid,
+            parent,
+            name: name.to_string(),
+            command: format!("/usr/bin/{name}"),
+            cpu,
+            memory,
+            owner: None,
+        }
+    }
+
+    #[test]
+    fn owners_are_inherited_from_ancestors() {
+        let mut rows = vec![
+            row(1, None, "init", 0.0, 10),
+            row(100, Some(1), "bash", 0.0, 20),
+            row(101, Some(100), "htop", 3.0, 30),
+            row(200, Some(1), "aplay", 0.0, 5),
+        ];
+        let owned = vec![
+            OwnedProcess {
+                pid: 100,
+                kind: OwnedProcessKind::Pty(7),
+                label: "bash".to_string(),
+                started: 0,
+            },
+            OwnedProcess {
+                pid: 200,
+                kind: OwnedProcessKind::Sound,
+                label: "click.wav".to_string(),
+                started: 0,
+            },
+        ];
+        resolve_task_owners(&mut rows, &owned);
+        assert_eq!(rows[0].owner, None);
+        assert_eq!(rows[2].pty_session(), Some(7));
+        assert_eq!(rows[3].owner, Some(OwnedProcessKind::Sound));
+    }
+
+    #[test]
+    fn filter_and_sort_respects_owned_only_and_direction() {
+        let mut rows = vec![
+            row(3, None, "zsh", 1.0, 300),
+            row(1, None, "Bash", 9.0, 100),
+            row(2, None, "htop", 4.0, 200),
+        ];
+        rows[2].owner = Some(OwnedProcessKind::Detached);
+
+        let by_cpu = filter_and_sort_tasks(&rows, "", false, TaskSortKey::Cpu, true);
+        assert_eq!(
+            by_cpu.iter().map(|row| row.pid).collect::<Vec<_>>(),
+            [1, 2, 3]
+        );
+        let by_name = filter_and_sort_tasks(&rows, "", false, TaskSortKey::Name, false);
+        assert_eq!(by_name[0].name, "Bash");
+        assert_eq!(
+            filter_and_sort_tasks(&rows, "sh", true, TaskSortKey::Pid, false).len(),
+            0
+        );
+        assert_eq!(
+            filter_and_sort_tasks(&rows, "2", false, TaskSortKey::Pid, false)[0].name,
+            "htop"
+        );
+    }
+
+    #[test]
+    fn sort_by_same_key_toggles_direction() {
+        let mut state = TaskManagerState::default();
+        state.sort_by(TaskSortKey::Name);
+        assert!(!state.descending);
+        state.sort_by(TaskSortKey::Name);
+        assert!(state.descending);
+        state.cycle_sort();
+        assert_eq!(state.sort, TaskSortKey::Cpu);
+        assert!(state.descending);
+        assert_eq!(format_memory(3 * 1024 * 1024), "3.0 MB");
+    }
+}
@@ -1,12 +1,19 @@
+mod pty_tabs;
 mod user_management;
 
+pub use pty_tabs::{resolve_terminal_tab_title, TerminalTab, TerminalTabId, TerminalTabs};
 pub use robcos_native_services::desktop_default_apps_service::DefaultAppSlot;
-use robcos_native_services::desktop_settings_service::pty_force_render_mode;
+use robcos_native_services::desktop_launcher_service::ResolvedProgramLaunch;
+use robcos_native_services::desktop_settings_service::{
+    pty_force_render_mode, pty_profile_for_entry,
+};
 use robcos_native_services::shared_types::FlashAction;
 pub use robcos_native_services::shared_types::TerminalScreen;
-use robcos_shared::config::HackingDifficulty;
+use robcos_shared::config::{HackingDifficulty, LaunchEnvironment};
 use robcos_shared::core::auth::AuthMethod;
 use robcos_shared::core::hacking::HackingGame;
+use robcos_shared::launcher::resolve_launch_environment;
+use std::path::PathBuf;
 pub use user_management::{
     handle_user_management_selection, plan_user_management_action, user_management_screen_for_mode,
     UserManagementAction, UserManagementExecutionPlan, UserManagementScreen,
@@ -50,7 +57,10 @@ pub enum MainMenuAction {
     Documents,
     Network,
     Games,
+    Recent,
     ProgramInstaller,
+    TaskManager,
+    Scheduler,
     Terminal,
     DesktopMode,
     Settings,
@@ -80,10 +90,22 @@ pub const MAIN_MENU_ENTRIES: &[MainMenuEntry] = &[
         label: "Games",
         action: Some(MainMenuAction::Games),
     },
+    MainMenuEntry {
+        label: "Recent",
+        action: Some(MainMenuAction::Recent),
+    },
     MainMenuEntry {
         label: "Program Installer",
         action: Some(MainMenuAction::ProgramInstaller),
     },
+    MainMenuEntry {
+        label: "Task Manager",
+        action: Some(MainMenuAction::TaskManager),
+    },
+    MainMenuEntry {
+        label: "Scheduler",
+        action: Some(MainMenuAction::Scheduler),
+    },
     MainMenuEntry {
         label: "Terminal",
         action: Some(MainMenuAction::Terminal),
@@ -228,9 +250,12 @@ pub struct TerminalPtyLaunchPlan {
     pub title: String,
     pub argv: Vec<String>,
     pub env: Vec<(String, String)>,
+    /// Working directory of the child; inherits ours when unset.
+    pub cwd: Option<PathBuf>,
+    /// Preferred PTY profile of a catalog entry.
+    pub pty_profile: Option<String>,
     pub return_screen: TerminalScreen,
     pub force_render_mode: Option<bool>,
-    pub replace_existing_pty: bool,
     pub use_fixed_terminal_metrics: bool,
     pub success_status: String,
 }
@@ -350,6 +375,7 @@ pub struct TerminalNavigationState {
     pub logs_idx: usize,
     pub network_idx: usize,
     pub games_idx: usize,
+    pub recent_idx: usize,
     pub nuke_codes_return_screen: TerminalScreen,
     pub settings_idx: usize,
     pub default_apps_idx: usize,
@@ -372,6 +398,7 @@ pub enum TerminalSelectionIndexTarget {
     Logs,
     Network,
     Games,
+    Recent,
     ProgramInstallerRoot,
     Settings,
     ConnectionsRoot,
@@ -548,24 +575,69 @@ where
     }
 }
 
+/// Builds the launch of `argv`. `launch` (a catalog entry's environment,
+/// working directory and extra arguments) is layered over the command's PTY
+/// profile and expanded.
 pub fn terminal_command_launch_plan(
     surface: TerminalShellSurface,
     title: &str,
     argv: &[String],
+    launch: &LaunchEnvironment,
+    return_screen: TerminalScreen,
+    force_render_mode: Option<bool>,
+) -> TerminalPtyLaunchPlan {
+    profile_launch_plan(
+        surface,
+        title,
+        argv,
+        launch,
+        None,
+        return_screen,
+        force_render_mode,
+    )
+}
+
+/// Builds the PTY launch of a catalog entry, using its preferred profile.
+pub fn terminal_catalog_launch_plan(
+    surface: TerminalShellSurface,
+    program: &ResolvedProgramLaunch,
     return_screen: TerminalScreen,
     force_render_mode: Option<bool>,
 ) -> TerminalPtyLaunchPlan {
-    let (replace_existing_pty, use_fixed_terminal_metrics, success_status) = match surface {
-        TerminalShellSurface::Embedded => (false, true, format!("Opened {title} in PTY.")),
-        TerminalShellSurface::Desktop => (true, false, format!("Opened {title} in PTY window.")),
+    profile_launch_plan(
+        surface,
+        &program.title,
+        &program.argv,
+        &program.launch,
+        program.pty_profile.clone(),
+        return_screen,
+        force_render_mode,
+    )
+}
+
+fn profile_launch_plan(
+    surface: TerminalShellSurface,
+    title: &str,
+    argv: &[String],
+    launch: &LaunchEnvironment,
+    pty_profile: Option<String>,
+    return_screen: TerminalScreen,
+    force_render_mode: Option<bool>,
+) -> TerminalPtyLaunchPlan {
+    let (use_fixed_terminal_metrics, success_status) = match surface {
+        TerminalShellSurface::Embedded => (true, format!("Opened {title} in PTY.")),
+        TerminalShellSurface::Desktop => (false, format!("Opened {title} in PTY window.")),
     };
+    let profile = pty_profile_for_entry(argv, pty_profile.as_deref());
+    let resolved = resolve_launch_environment(&profile.launch.layered(launch));
     TerminalPtyLaunchPlan {
         title: title.to_string(),
-        argv: argv.to_vec(),
-        env: Vec::new(),
+        argv: argv.iter().cloned().chain(resolved.args).collect(),
+        env: resolved.env,
+        cwd: resolved.cwd,
+        pty_profile,
         return_screen,
         force_render_mode,
-        replace_existing_pty,
         use_fixed_terminal_metrics,
         success_status,
     }
@@ -584,6 +656,7 @@ pub fn resolve_flash_pty_launch(
             TerminalShellSurface::Embedded,
             title,
             argv,
+            &LaunchEnvironment::default(),
             return_screen,
             force_render_mode,
         ),
@@ -708,8 +781,14 @@ pub fn terminal_shell_launch_plan(
         TerminalShellSurface::Embedded => "ROBCO MAINTENANCE TERMLINK",
         TerminalShellSurface::Desktop => "Terminal",
     };
-    let mut plan =
-        terminal_command_launch_plan(surface, title, &argv, TerminalScreen::MainMenu, Some(false));
+    let mut plan = terminal_command_launch_plan(
+        surface,
+        title,
+        &argv,
+        &LaunchEnvironment::default(),
+        TerminalScreen::MainMenu,
+        Some(false),
+    );
     plan.success_status = match surface {
         TerminalShellSurface::Embedded => "Opened terminal shell in PTY.".to_string(),
         TerminalShellSurface::Desktop => "Opened terminal shell in PTY window.".to_string(),
@@ -802,6 +881,7 @@ pub fn terminal_runtime_defaults() -> TerminalNavigationState {
         logs_idx: 0,
         network_idx: 0,
         games_idx: 0,
+        recent_idx: 0,
         nuke_codes_return_screen: TerminalScreen::Applications,
         settings_idx: 0,
         default_apps_idx: 0,
@@ -877,6 +957,17 @@ pub fn terminal_screen_open_plan(
             reset_user_management_to_root: false,
             clear_status,
         },
+        TerminalScreen::Recent => TerminalScreenOpenPlan {
+            screen,
+            index_target: TerminalSelectionIndexTarget::Recent,
+            selected_idx,
+            reset_installer: false,
+            reset_connections: false,
+            clear_settings_choice: false,
+            clear_default_app_slot: false,
+            reset_user_management_to_root: false,
+            clear_status,
+        },
         TerminalScreen::ProgramInstaller => TerminalScreenOpenPlan {
             screen,
             index_target: TerminalSelectionIndexTarget::ProgramInstallerRoot,
@@ -888,6 +979,20 @@ pub fn terminal_screen_open_plan(
             reset_user_management_to_root: false,
             clear_status,
         },
+        TerminalScreen::TaskManager
+        | TerminalScreen::Scheduler
+        | TerminalScreen::Autostart
+        | TerminalScreen::Commander => TerminalScreenOpenPlan {
+            screen,
+            index_target: TerminalSelectionIndexTarget::None,
+            selected_idx,
+            reset_installer: false,
+            reset_connections: false,
+            clear_settings_choice: false,
+            clear_default_app_slot: false,
+            reset_user_management_to_root: false,
+            clear_status,
+        },
         TerminalScreen::Settings => TerminalScreenOpenPlan {
             screen,
             index_target: TerminalSelectionIndexTarget::Settings,
@@ -1001,11 +1106,26 @@ pub fn resolve_main_menu_action(action: MainMenuAction) -> MainMenuSelectionActi
             selected_idx: 0,
             clear_status: true,
         },
+        MainMenuAction::Recent => MainMenuSelectionAction::OpenScreen {
+            screen: TerminalScreen::Recent,
+            selected_idx: 0,
+            clear_status: true,
+        },
         MainMenuAction::ProgramInstaller => MainMenuSelectionAction::OpenScreen {
             screen: TerminalScreen::ProgramInstaller,
             selected_idx: 0,
             clear_status: true,
         },
+        MainMenuAction::TaskManager => MainMenuSelectionAction::OpenScreen {
+            screen: TerminalScreen::TaskManager,
+            selected_idx: 0,
+            clear_status: true,
+        },
+        MainMenuAction::Scheduler => MainMenuSelectionAction::OpenScreen {
+            screen: TerminalScreen::Scheduler,
+            selected_idx: 0,
+            clear_status: true,
+        },
         MainMenuAction::Terminal => MainMenuSelectionAction::OpenTerminalMode,
         MainMenuAction::DesktopMode => MainMenuSelectionAction::EnterDesktopMode,
         MainMenuAction::Settings => MainMenuSelectionAction::RefreshSettingsAndOpen,
@@ -1033,6 +1153,9 @@ pub fn resolve_terminal_back_action(context: TerminalBackContext) -> TerminalBac
         | TerminalScreen::Documents
         | TerminalScreen::Network
         | TerminalScreen::Games
+        | TerminalScreen::Recent
+        | TerminalScreen::TaskManager
+        | TerminalScreen::Scheduler
         | TerminalScreen::Settings
         | TerminalScreen::UserManagement => TerminalBackAction::NavigateTo {
             screen: TerminalScreen::MainMenu,
@@ -1044,7 +1167,7 @@ pub fn resolve_terminal_back_action(context: TerminalBackContext) -> TerminalBac
             clear_status: true,
             reset_installer: false,
         },
-        TerminalScreen::Logs => TerminalBackAction::NavigateTo {
+        TerminalScreen::Logs | TerminalScreen::Commander => TerminalBackAction::NavigateTo {
             screen: TerminalScreen::Documents,
             clear_status: true,
             reset_installer: false,
@@ -1069,6 +1192,7 @@ pub fn resolve_terminal_back_action(context: TerminalBackContext) -> TerminalBac
         },
         TerminalScreen::Connections
         | TerminalScreen::DefaultApps
+        | TerminalScreen::Autostart
         | TerminalScreen::About
         | TerminalScreen::EditMenus => TerminalBackAction::NavigateTo {
             screen: TerminalScreen::Settings,
@@ -1094,13 +1218,13 @@ mod tests {
 
     #[test]
     fn selectable_menu_entries_skip_separator() {
-        assert_eq!(selectable_menu_count(), 9);
+        assert_eq!(selectable_menu_count(), 12);
         assert_eq!(
             entry_for_selectable_idx(0).action,
             Some(MainMenuAction::Applications)
         );
         assert_eq!(
-            entry_for_selectable_idx(8).action,
+            entry_for_selectable_idx(11).action,
             Some(MainMenuAction::Logout)
         );
     }
@@ -1264,7 +1388,6 @@ mod tests {
         assert_eq!(plan.argv, vec!["/bin/zsh".to_string(), "-l".to_string()]);
         assert!(plan.env.is_empty());
         assert!(plan.use_fixed_terminal_metrics);
-        assert!(!plan.replace_existing_pty);
     }
 
     #[test]
@@ -1273,7 +1396,6 @@ mod tests {
             terminal_shell_launch_plan(TerminalShellSurface::Desktop, Some("/usr/bin/fish"), true);
         assert_eq!(plan.argv, vec!["/bin/bash".to_string(), "-l".to_string()]);
         assert_eq!(plan.title, "Terminal");
-        assert!(plan.replace_existing_pty);
         assert!(!plan.use_fixed_terminal_metrics);
     }
 
@@ -1293,6 +1415,7 @@ mod tests {
             TerminalShellSurface::Embedded,
             "Program Installer",
             &["installer".to_string()],
+            &LaunchEnvironment::default(),
             TerminalScreen::ProgramInstaller,
             Some(true),
         );
@@ -1303,18 +1426,42 @@ mod tests {
     }
 
     #[test]
-    fn desktop_command_plan_replaces_existing_pty() {
+    fn desktop_command_plan_uses_window_metrics() {
         let plan = terminal_command_launch_plan(
             TerminalShellSurface::Desktop,
             "Terminal",
             &["sh".to_string()],
+            &LaunchEnvironment::default(),
             TerminalScreen::MainMenu,
             None,
         );
-        assert!(plan.replace_existing_pty);
         assert!(!plan.use_fixed_terminal_metrics);
     }
 
+    #[test]
+    fn command_plan_applies_entry_environment_cwd_and_args() {
+        let launch = LaunchEnvironment {
+            env: [("EDITOR".to_string(), "vim".to_string())]
+                .into_iter()
+                .collect(),
+            cwd: Some("/srv/projects".to_string()),
+            args: vec!["--verbose".to_string()],
+        };
+        let plan = terminal_command_launch_plan(
+            TerminalShellSurface::Desktop,
+            "Vault Tool",
+            &["vault-tool".to_string(), "status".to_string()],
+            &launch,
+            TerminalScreen::MainMenu,
+            None,
+        );
+        assert_eq!(plan.argv, vec!["vault-tool", "status", "--verbose"]);
+        assert!(plan
+            .env
+            .contains(&("EDITOR".to_string(), "vim".to_string())));
+        assert_eq!(plan.cwd, Some(PathBuf::from("/srv/projects")));
+    }
+
     #[test]
     fn flash_pty_launch_wraps_embedded_launch_plan_and_status() {
         let plan = resolve_flash_pty_launch(
@@ -0,0 +1,276 @@
+/// Stable identifier for a tab; survives reordering and closing neighbours.
+pub type TerminalTabId = u64;
+
+#[derive(Debug, Clone)]
+pub struct TerminalTab<T> {
+    pub id: TerminalTabId,
+    /// Title given at launch (program name or "Terminal").
+    pub launch_title: String,
+    /// Title set by the user through "Rename Tab"; wins over everything else.
+    pub custom_title: Option<String>,
+    pub state: T,
+}
+
+impl<T> TerminalTab<T> {
+    /// Resolves the label shown on the tab. A user-chosen name wins, then the
+    /// OSC 0/2 window title reported by the child, then the launch title.
+    pub fn display_title(&self, osc_title: Option<&str>) -> String {
+        resolve_terminal_tab_title(self.custom_title.as_deref(), osc_title, &self.launch_title)
+    }
+}
+
+pub fn resolve_terminal_tab_title(
+    custom_title: Option<&str>,
+    osc_title: Option<&str>,
+    launch_title: &str,
+) -> String {
+    [custom_title, osc_title]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|title| !title.is_empty())
+        .unwrap_or(launch_title)
+        .to_string()
+}
+
+/// Ordered tab strip for the desktop terminal window.
+#[derive(Debug, Clone)]
+pub struct TerminalTabs<T> {
+    tabs: Vec<TerminalTab<T>>,
+    active: usize,
+    next_id: TerminalTabId,
+}
+
+impl<T> Default for TerminalTabs<T> {
+    fn default() -> Self {
+        Self {
+            tabs: Vec::new(),
+            active: 0,
+            next_id: 1,
+        }
+    }
+}
+
+impl<T> TerminalTabs<T> {
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &TerminalTab<T>> {
+        self.tabs.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut TerminalTab<T>> {
+        self.tabs.iter_mut()
+    }
+
+    pub fn active_index(&self) -> Option<usize> {
+        (!self.tabs.is_empty()).then_some(self.active)
+    }
+
+    pub fn active(&self) -> Option<&TerminalTab<T>> {
+        self.tabs.get(self.active)
+    }
+
+    pub fn active_mut(&mut self) -> Option<&mut TerminalTab<T>> {
+        self.tabs.get_mut(self.active)
+    }
+
+    pub fn active_id(&self) -> Option<TerminalTabId> {
+        self.active().map(|tab| tab.id)
+    }
+
+    pub fn position(&self, id: TerminalTabId) -> Option<usize> {
+        self.tabs.iter().position(|tab| tab.id == id)
+    }
+
+    pub fn get(&self, id: TerminalTabId) -> Option<&TerminalTab<T>> {
+        self.tabs.iter().find(|tab| tab.id == id)
+    }
+
+    pub fn get_mut(&mut self, id: TerminalTabId) -> Option<&mut TerminalTab<T>> {
+        self.tabs.iter_mut().find(|tab| tab.id == id)
+    }
+
+    /// Appends a tab after the existing ones and makes it active.
+    pub fn push(&mut self, launch_title: impl Into<String>, state: T) -> TerminalTabId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.tabs.push(TerminalTab {
+            id,
+            launch_title: launch_title.into(),
+            custom_title: None,
+            state,
+        });
+        self.active = self.tabs.len() - 1;
+        id
+    }
+
+    pub fn activate(&mut self, id: TerminalTabId) -> bool {
+        match self.position(id) {
+            Some(idx) => {
+                self.active = idx;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Cycles the active tab by `delta`, wrapping at either end.
+    pub fn cycle(&mut self, delta: isize) {
+        let len = self.tabs.len() as isize;
+        if len > 0 {
+            self.active = (self.active as isize + delta).rem_euclid(len) as usize;
+        }
+    }
+
+    /// Sets or clears (`None` / blank) the user-chosen title.
+    pub fn rename(&mut self, id: TerminalTabId, title: Option<&str>) -> bool {
+        let Some(tab) = self.get_mut(id) else {
+            return false;
+        };
+        tab.custom_title = title
+            .map(str::trim)
+            .filter(|title| !title.is_empty())
+            .map(str::to_string);
+        true
+    }
+
+    /// Moves a tab by `delta` positions, clamped to the strip. The active tab
+    /// stays the same tab, wherever it ends up.
+    pub fn move_tab(&mut self, id: TerminalTabId, delta: isize) -> bool {
+        let Some(from) = self.position(id) else {
+            return false;
+        };
+        let to = (from as isize + delta).clamp(0, self.tabs.len() as isize - 1) as usize;
+        if from == to {
+            return false;
+        }
+        let active_id = self.active_id();
+        let tab = self.tabs.remove(from);
+        self.tabs.insert(to, tab);
+        if let Some(active_id) = active_id {
+            self.active = self.position(active_id).unwrap_or(0);
+        }
+        true
+    }
+
+    /// Removes a tab. When the active tab closes, its right neighbour (or the
+    /// new last tab) becomes active.
+    pub fn remove(&mut self, id: TerminalTabId) -> Option<TerminalTab<T>> {
+        let idx = self.position(id)?;
+        let tab = self.tabs.remove(idx);
+        if idx < self.active || self.active >= self.tabs.len() {
+            self.active = self.active.saturating_sub(1);
+        }
+        Some(tab)
+    }
+
+    pub fn drain(&mut self) -> impl Iterator<Item = TerminalTab<T>> + '_ {
+        self.active = 0;
+        self.tabs.drain(..)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tabs(names: &[&str]) -> TerminalTabs<()> {
+        let mut tabs = TerminalTabs::default();
+        for name in names {
+            tabs.push(*name, ());
+        }
+        tabs
+    }
+
+    fn order(tabs: &TerminalTabs<()>) -> Vec<String> {
+        tabs.iter().map(|tab| tab.launch_title.clone()).collect()
+    }
+
+    #[test]
+    fn tab_title_prefers_custom_then_osc_then_launch_title() {
+        assert_eq!(
+            resolve_terminal_tab_title(Some("build"), Some("vim"), "Terminal"),
+            "build"
+        );
+        assert_eq!(
+            resolve_terminal_tab_title(None, Some("vim"), "Terminal"),
+            "vim"
+        );
+        assert_eq!(
+            resolve_terminal_tab_title(Some("  "), Some(""), "Terminal"),
+            "Terminal"
+        );
+    }
+
+    #[test]
+    fn push_activates_new_tab_and_ids_stay_unique() {
+        let mut tabs = tabs(&["a", "b"]);
+        let removed = tabs.active_id().unwrap();
+        tabs.remove(removed);
+        let id = tabs.push("c", ());
+        assert_ne!(id, removed);
+        assert_eq!(tabs.active_id(), Some(id));
+        assert_eq!(order(&tabs), vec!["a", "c"]);
+    }
+
+    #[test]
+    fn move_tab_keeps_active_tab_selected() {
+        let mut tabs = tabs(&["a", "b", "c"]);
+        let a = tabs.iter().next().unwrap().id;
+        let c = tabs.active_id().unwrap();
+        assert!(tabs.move_tab(a, 1));
+        assert_eq!(order(&tabs), vec!["b", "a", "c"]);
+        assert_eq!(tabs.active_id(), Some(c));
+        assert!(tabs.move_tab(c, -5));
+        assert_eq!(order(&tabs), vec!["c", "b", "a"]);
+        assert_eq!(tabs.active_index(), Some(0));
+        assert!(!tabs.move_tab(c, -1));
+    }
+
+    #[test]
+    fn removing_tabs_picks_a_sensible_active_neighbour() {
+        let mut tabs = tabs(&["a", "b", "c"]);
+        let ids: Vec<_> = tabs.iter().map(|tab| tab.id).collect();
+        tabs.activate(ids[1]);
+        tabs.remove(ids[1]);
+        assert_eq!(tabs.active().unwrap().launch_title, "c");
+        tabs.remove(ids[2]);
+        assert_eq!(tabs.active().unwrap().launch_title, "a");
+        tabs.remove(ids[0]);
+        assert!(tabs.active().is_none());
+        assert_eq!(tabs.active_index(), None);
+    }
+
+    #[test]
+    fn removing_tab_left_of_active_keeps_active_tab() {
+        let mut tabs = tabs(&["a", "b", "c"]);
+        let first = tabs.iter().next().unwrap().id;
+        tabs.remove(first);
+        assert_eq!(tabs.active().unwrap().launch_title, "c");
+    }
+
+    #[test]
+    fn rename_trims_and_blank_clears_custom_title() {
+        let mut tabs = tabs(&["a"]);
+        let id = tabs.active_id().unwrap();
+        tabs.rename(id, Some("  logs  "));
+        assert_eq!(tabs.active().unwrap().display_title(Some("vim")), "logs");
+        tabs.rename(id, Some(" "));
+        assert_eq!(tabs.active().unwrap().display_title(Some("vim")), "vim");
+    }
+
+    #[test]
+    fn cycle_wraps_in_both_directions() {
+        let mut tabs = tabs(&["a", "b", "c"]);
+        tabs.cycle(1);
+        assert_eq!(tabs.active_index(), Some(0));
+        tabs.cycle(-1);
+        assert_eq!(tabs.active_index(), Some(2));
+    }
+}
@@ -0,0 +1,145 @@
+//! Measures the cost of committing PTY frames with and without row-level
+//! damage tracking, using an htop-like screen where only the clock and one
+//! meter change per refresh.
+//!
+//! The idle case replays ten seconds of an open htop window that redraws
+//! every 1.5 s. Before, a visible PTY window was redrawn (and its frame
+//! rebuilt) on every 33 ms wake-up; now the loop wakes every
+//! `PTY_OUTPUT_POLL_INTERVAL` and `PtyRepaintGate` lets a frame through only
+//! for output or a cursor blink. The commit count and the time spent
+//! committing stand in for idle CPU.
+//!
+//! Run with `cargo bench -p robcos-shared --bench pty_render`.
+
+use robcos_shared::pty::{
+    CommittedFrame, FrameTracker, PtyRepaintGate, PTY_CURSOR_BLINK_INTERVAL,
+    PTY_OUTPUT_POLL_INTERVAL,
+};
+use std::time::{Duration, Instant};
+
+const COLS: u16 = 200;
+const ROWS: u16 = 50;
+const ITERATIONS: u32 = 400;
+/// Simulated time an idle htop window stays open.
+const IDLE_SPAN: Duration = Duration::from_secs(10);
+/// htop's default refresh delay.
+const HTOP_REFRESH: Duration = Duration::from_millis(1500);
+/// Redraw interval of a visible PTY window before damage tracking.
+const OLD_PTY_REDRAW: Duration = Duration::from_millis(33);
+
+fn htop_like_screen() -> vt100::Parser {
+    let mut parser = vt100::Parser::new(ROWS, COLS, 0);
+    for row in 1..=ROWS {
+        let line = format!(
+            "\x1b[{row};1H\x1b[32m{row:>5}\x1b[0m root  20  0  {:>7}K  S  0.{}  /usr/bin/process-{row}",
+            row as u32 * 1337,
+            row % 10
+        );
+        parser.process(line.as_bytes());
+    }
+    parser
+}
+
+fn tick(parser: &mut vt100::Parser, n: u32) {
+    let clock = format!("\x1b[1;150HUptime: 00:{:02}:{:02}", (n / 60) % 60, n % 60);
+    let meter = format!(
+        "\x1b[2;1H\x1b[31m[{:<40}]\x1b[0m",
+        "|".repeat((n % 40) as usize)
+    );
+    parser.process(clock.as_bytes());
+    parser.process(meter.as_bytes());
+}
+
+fn run(label: &str, full_rebuild: bool, changing: bool) -> Duration {
+    let mut parser = htop_like_screen();
+    let mut tracker = FrameTracker::from_config();
+    let mut frame = CommittedFrame::blank(COLS, ROWS);
+    tracker.prepare(&parser, COLS, ROWS).apply(&mut frame);
+    let mut rebuilt = 0usize;
+    let started = Instant::now();
+    for n in 0..ITERATIONS {
+        if changing {
+            tick(&mut parser, n);
+        }
+        if full_rebuild {
+            tracker.invalidate();
+        }
+        let update = tracker.prepare(&parser, COLS, ROWS);
+        rebuilt += update.changed_rows();
+        update.apply(&mut frame);
+    }
+    let per_commit = started.elapsed() / ITERATIONS;
+    println!(
+        "{label:<28} {:>9.1}us/commit  {:>5.1} rows/commit",
+        per_commit.as_secs_f64() * 1_000_000.0,
+        rebuilt as f64 / ITERATIONS as f64
+    );
+    per_commit
+}
+
+/// Replays `IDLE_SPAN` of wake-ups; returns commits made and time spent.
+fn run_idle(label: &str, gated: bool) -> (u32, Duration) {
+    let wake = if gated {
+        PTY_OUTPUT_POLL_INTERVAL
+    } else {
+        OLD_PTY_REDRAW
+    };
+    let blink_on =
+        |at: Duration| (at.as_millis() / PTY_CURSOR_BLINK_INTERVAL.as_millis()).is_multiple_of(2);
+    let mut parser = htop_like_screen();
+    let mut tracker = FrameTracker::from_config();
+    let mut frame = CommittedFrame::blank(COLS, ROWS);
+    tracker.prepare(&parser, COLS, ROWS).apply(&mut frame);
+    let mut gate = PtyRepaintGate::new(true);
+    let mut next_refresh = HTOP_REFRESH;
+    let mut refreshes = 0u32;
+    let mut commits = 0u32;
+    let mut spent = Duration::ZERO;
+    let mut at = Duration::ZERO;
+    while at < IDLE_SPAN {
+        at += wake;
+        let output = at >= next_refresh;
+        if output {
+            next_refresh += HTOP_REFRESH;
+            refreshes += 1;
+            tick(&mut parser, refreshes);
+        }
+        if gated && !gate.observe(output, blink_on(at)) {
+            continue;
+        }
+        let started = Instant::now();
+        if !gated {
+            tracker.invalidate();
+        }
+        tracker.prepare(&parser, COLS, ROWS).apply(&mut frame);
+        spent += started.elapsed();
+        commits += 1;
+    }
+    println!(
+        "{label:<28} {commits:>5} commits  {:>9.1}us committing per idle second",
+        spent.as_secs_f64() * 1_000_000.0 / IDLE_SPAN.as_secs_f64()
+    );
+    (commits, spent)
+}
+
+fn main() {
+    println!("PTY frame commit, {COLS}x{ROWS}, {ITERATIONS} iterations");
+    let full = run("full rebuild (ticking)", true, true);
+    let incremental = run("damage tracked (ticking)", false, true);
+    let idle = run("damage tracked (idle)", false, false);
+    println!(
+        "speedup: {:.1}x ticking, {:.1}x idle",
+        full.as_secs_f64() / incremental.as_secs_f64().max(f64::EPSILON),
+        full.as_secs_f64() / idle.as_secs_f64().max(f64::EPSILON)
+    );
+
+    println!();
+    println!("Idle htop window, {}s simulated", IDLE_SPAN.as_secs());
+    let (before_commits, before) = run_idle("before (33ms redraw)", false);
+    let (after_commits, after) = run_idle("after (repaint gate)", true);
+    println!(
+        "idle: {:.1}x fewer commits, {:.1}x less time committing",
+        before_commits as f64 / after_commits.max(1) as f64,
+        before.as_secs_f64() / after.as_secs_f64().max(f64::EPSILON)
+    );
+}
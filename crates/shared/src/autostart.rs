@@ -0,0 +1,243 @@
+//! Per-user autostart list, stored in `Settings::autostart`.
+//!
+//! Items run in list order after a fresh login, each after its own delay
+//! and only once the session is in the mode the item asks for. Logins that
+//! restore a saved desktop session skip the list.
+
+use crate::history::LaunchKind;
+use crate::scheduler::JobTarget;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Longest delay accepted for a single item.
+pub const MAX_AUTOSTART_DELAY_SECS: u32 = 600;
+
+/// Which surface an item waits for before it opens.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutostartMode {
+    #[default]
+    Terminal,
+    Desktop,
+}
+
+impl AutostartMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            AutostartMode::Terminal => "Terminal",
+            AutostartMode::Desktop => "Desktop",
+        }
+    }
+
+    pub fn toggled(self) -> Self {
+        match self {
+            AutostartMode::Terminal => AutostartMode::Desktop,
+            AutostartMode::Desktop => AutostartMode::Terminal,
+        }
+    }
+}
+
+/// Built-in windows and screens an item can open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuiltinWindow {
+    FileManager,
+    Editor,
+    Applications,
+    Settings,
+    Terminal,
+    TaskManager,
+    Scheduler,
+    Installer,
+    NukeCodes,
+}
+
+impl BuiltinWindow {
+    pub const ALL: [BuiltinWindow; 9] = [
+        BuiltinWindow::FileManager,
+        BuiltinWindow::Editor,
+        BuiltinWindow::Applications,
+        BuiltinWindow::Settings,
+        BuiltinWindow::Terminal,
+        BuiltinWindow::TaskManager,
+        BuiltinWindow::Scheduler,
+        BuiltinWindow::Installer,
+        BuiltinWindow::NukeCodes,
+    ];
+
+    /// Name used after `window:`.
+    pub fn label(self) -> &'static str {
+        match self {
+            BuiltinWindow::FileManager => "files",
+            BuiltinWindow::Editor => "editor",
+            BuiltinWindow::Applications => "applications",
+            BuiltinWindow::Settings => "settings",
+            BuiltinWindow::Terminal => "terminal",
+            BuiltinWindow::TaskManager => "tasks",
+            BuiltinWindow::Scheduler => "scheduler",
+            BuiltinWindow::Installer => "installer",
+            BuiltinWindow::NukeCodes => "nuke-codes",
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().to_ascii_lowercase();
+        Self::ALL.into_iter().find(|window| window.label() == text)
+    }
+}
+
+/// What an item opens.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AutostartTarget {
+    /// A catalog entry by name. `kind` is one of the catalog launch kinds.
+    Catalog {
+        kind: LaunchKind,
+        name: String,
+    },
+    Command {
+        argv: Vec<String>,
+    },
+    Window {
+        window: BuiltinWindow,
+    },
+    /// A folder opened in the file manager.
+    Folder {
+        path: PathBuf,
+    },
+}
+
+impl AutostartTarget {
+    /// Reads `window:<name>`, `folder:<path>`, or anything
+    /// [`JobTarget::parse`] accepts.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        if let Some(name) = text.strip_prefix("window:") {
+            return BuiltinWindow::parse(name)
+                .map(|window| AutostartTarget::Window { window })
+                .ok_or_else(|| {
+                    let names: Vec<&str> = BuiltinWindow::ALL
+                        .iter()
+                        .map(|window| window.label())
+                        .collect();
+                    format!("Error: unknown window. Use one of: {}", names.join(", "))
+                });
+        }
+        if let Some(path) = text.strip_prefix("folder:") {
+            let path = path.trim();
+            if path.is_empty() {
+                return Err("Error: missing folder path.".to_string());
+            }
+            return Ok(AutostartTarget::Folder {
+                path: PathBuf::from(path),
+            });
+        }
+        Ok(match JobTarget::parse(text)? {
+            JobTarget::Catalog { kind, name } => AutostartTarget::Catalog { kind, name },
+            JobTarget::Command { argv } => AutostartTarget::Command { argv },
+        })
+    }
+
+    /// Text form accepted by [`AutostartTarget::parse`].
+    pub fn describe(&self) -> String {
+        match self {
+            AutostartTarget::Catalog { kind, name } => JobTarget::Catalog {
+                kind: *kind,
+                name: name.clone(),
+            }
+            .describe(),
+            AutostartTarget::Command { argv } => {
+                JobTarget::Command { argv: argv.clone() }.describe()
+            }
+            AutostartTarget::Window { window } => format!("window:{}", window.label()),
+            AutostartTarget::Folder { path } => format!("folder:{}", path.display()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutostartItem {
+    pub target: AutostartTarget,
+    /// Seconds to wait after login before opening.
+    #[serde(default)]
+    pub delay_secs: u32,
+    #[serde(default)]
+    pub mode: AutostartMode,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl AutostartItem {
+    pub fn new(target: AutostartTarget, mode: AutostartMode) -> Self {
+        Self {
+            target,
+            delay_secs: 0,
+            mode,
+            enabled: true,
+        }
+    }
+
+    /// Adds `delta` seconds to the delay, clamped to
+    /// `0..=MAX_AUTOSTART_DELAY_SECS`.
+    pub fn adjust_delay(&mut self, delta: i32) {
+        self.delay_secs = self
+            .delay_secs
+            .saturating_add_signed(delta)
+            .min(MAX_AUTOSTART_DELAY_SECS);
+    }
+
+    /// One-line summary for lists.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} {:<8} +{:>3}s  {}",
+            if self.enabled { "[x]" } else { "[ ]" },
+            self.mode.label(),
+            self.delay_secs,
+            self.target.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_describes_targets() {
+        for text in [
+            "app:Notes",
+            "window:files",
+            "window:nuke-codes",
+            "folder:/home/user/Documents",
+            "htop -d 10",
+        ] {
+            let target = AutostartTarget::parse(text).unwrap();
+            assert_eq!(target.describe(), text);
+        }
+        assert_eq!(
+            AutostartTarget::parse("window:Tasks").unwrap(),
+            AutostartTarget::Window {
+                window: BuiltinWindow::TaskManager
+            }
+        );
+        assert!(AutostartTarget::parse("window:nope").is_err());
+        assert!(AutostartTarget::parse("folder:").is_err());
+    }
+
+    #[test]
+    fn items_default_to_enabled_terminal_without_delay() {
+        let mut item: AutostartItem =
+            serde_json::from_str(r#"{"target":{"type":"window","window":"scheduler"}}"#).unwrap();
+        assert!(item.enabled);
+        assert_eq!(item.mode, AutostartMode::Terminal);
+        assert_eq!(item.delay_secs, 0);
+        item.adjust_delay(-5);
+        assert_eq!(item.delay_secs, 0);
+        item.adjust_delay(10_000);
+        assert_eq!(item.delay_secs, MAX_AUTOSTART_DELAY_SECS);
+    }
+}
@@ -0,0 +1,210 @@
+//! Program catalog entries (`apps.json`, `games.json`, `networks.json`).
+//!
+//! Version 1 files map a display name to a bare argv array. Version 2 entries
+//! are objects carrying the command plus metadata. Both forms are read
+//! transparently; entries without metadata are still written as arrays so the
+//! files stay readable by older builds.
+
+use crate::config::LaunchEnvironment;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// How a catalog program is started.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CatalogRunMode {
+    /// Inside a PTY session (terminal window or embedded terminal).
+    #[default]
+    Pty,
+    /// As a detached process with no terminal, e.g. a GUI program.
+    Detached,
+}
+
+impl CatalogRunMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            CatalogRunMode::Pty => "PTY",
+            CatalogRunMode::Detached => "Detached",
+        }
+    }
+
+    pub fn toggled(self) -> Self {
+        match self {
+            CatalogRunMode::Pty => CatalogRunMode::Detached,
+            CatalogRunMode::Detached => CatalogRunMode::Pty,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CatalogEntry {
+    pub command: Vec<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Categories and free-form tags, matched by search.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(flatten)]
+    pub launch: LaunchEnvironment,
+    #[serde(default, skip_serializing_if = "is_default_run_mode")]
+    pub run_mode: CatalogRunMode,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub requires_network: bool,
+    /// Name of the PTY profile to use instead of the one picked by program
+    /// name: a built-in slot (`default`, `calcurse`, ...) or a custom profile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pty_profile: Option<String>,
+}
+
+impl From<Vec<String>> for CatalogEntry {
+    fn from(command: Vec<String>) -> Self {
+        Self::from_command(command)
+    }
+}
+
+fn is_default_run_mode(mode: &CatalogRunMode) -> bool {
+    *mode == CatalogRunMode::default()
+}
+
+impl CatalogEntry {
+    pub fn from_command(command: Vec<String>) -> Self {
+        Self {
+            command,
+            ..Self::default()
+        }
+    }
+
+    /// Reads either catalog form. Unreadable values yield an empty command;
+    /// an object whose metadata does not parse keeps its command and drops
+    /// the metadata, so a hand-edit typo does not stop the program launching.
+    pub fn from_json(value: &Value) -> Self {
+        match value {
+            Value::Array(items) => Self::from_command(command_strings(items)),
+            Value::Object(object) => serde_json::from_value(value.clone()).unwrap_or_else(|_| {
+                Self::from_command(
+                    object
+                        .get("command")
+                        .and_then(Value::as_array)
+                        .map(|items| command_strings(items))
+                        .unwrap_or_default(),
+                )
+            }),
+            _ => Self::default(),
+        }
+    }
+
+    /// Writes the compact v1 array when there is no metadata, else an object.
+    pub fn to_json(&self) -> Value {
+        if self.has_metadata() {
+            serde_json::to_value(self).unwrap_or(Value::Null)
+        } else {
+            Value::Array(self.command.iter().cloned().map(Value::String).collect())
+        }
+    }
+
+    pub fn has_metadata(&self) -> bool {
+        *self != Self::from_command(self.command.clone())
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        Some(self.description.trim()).filter(|text| !text.is_empty())
+    }
+
+    /// Case-insensitive match against description and tags; `query` must
+    /// already be lower-case.
+    pub fn matches_metadata(&self, query: &str) -> bool {
+        self.description.to_lowercase().contains(query)
+            || self
+                .tags
+                .iter()
+                .any(|tag| tag.to_lowercase().contains(query))
+    }
+}
+
+fn command_strings(items: &[Value]) -> Vec<String> {
+    items
+        .iter()
+        .filter_map(|item| item.as_str().map(str::to_string))
+        .collect()
+}
+
+/// Comma-separated editable form of tags.
+pub fn tags_to_text(tags: &[String]) -> String {
+    tags.join(", ")
+}
+
+pub fn tags_from_text(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in text.split(',').map(str::trim).filter(|tag| !tag.is_empty()) {
+        if !tags.iter().any(|known| known.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn v1_arrays_read_as_plain_entries_and_write_back_unchanged() {
+        let value = json!(["ranger", "--choosedir=/tmp/x"]);
+        let entry = CatalogEntry::from_json(&value);
+        assert_eq!(entry.command, vec!["ranger", "--choosedir=/tmp/x"]);
+        assert!(!entry.has_metadata());
+        assert_eq!(entry.to_json(), value);
+    }
+
+    #[test]
+    fn v2_objects_round_trip_with_metadata() {
+        let value = json!({
+            "command": ["btop"],
+            "description": "System monitor",
+            "tags": ["system", "monitor"],
+            "cwd": "~",
+            "run_mode": "detached",
+            "requires_network": true,
+            "pty_profile": "default",
+        });
+        let entry = CatalogEntry::from_json(&value);
+        assert_eq!(entry.description(), Some("System monitor"));
+        assert_eq!(entry.run_mode, CatalogRunMode::Detached);
+        assert!(entry.requires_network);
+        assert_eq!(entry.launch.cwd.as_deref(), Some("~"));
+        assert_eq!(entry.to_json(), value);
+        assert!(entry.matches_metadata("monitor"));
+        assert!(!entry.matches_metadata("editor"));
+    }
+
+    #[test]
+    fn malformed_metadata_keeps_the_command() {
+        for value in [
+            json!({"command": ["btop", "-p", "1"], "tags": "dev"}),
+            json!({"command": ["btop", "-p", "1"], "run_mode": "sideways"}),
+            json!({"command": ["btop", "-p", "1"], "env": {"TERM": 256}}),
+        ] {
+            let entry = CatalogEntry::from_json(&value);
+            assert_eq!(
+                entry,
+                CatalogEntry::from_command(vec!["btop".into(), "-p".into(), "1".into()])
+            );
+        }
+        assert_eq!(
+            CatalogEntry::from_json(&json!({"tags": ["x"], "command": "btop"})),
+            CatalogEntry::default()
+        );
+    }
+
+    #[test]
+    fn tags_text_trims_and_dedupes() {
+        assert_eq!(
+            tags_from_text(" dev, Tools ,,dev ,tools"),
+            vec!["dev".to_string(), "Tools".to_string()]
+        );
+        assert_eq!(tags_to_text(&tags_from_text("a,b")), "a, b");
+    }
+}
@@ -1,3 +1,4 @@
+use crate::autostart::AutostartItem;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet};
@@ -243,7 +244,7 @@ pub fn save_json<T: Serialize>(path: &Path, data: &T) -> Result<()> {
 
 // ── User-aware file helpers ───────────────────────────────────────────────────
 
-fn user_file(filename: &str) -> PathBuf {
+pub(crate) fn user_file(filename: &str) -> PathBuf {
     if let Some(u) = get_current_user() {
         user_dir(&u).join(filename)
     } else {
@@ -462,6 +463,10 @@ pub struct DesktopFileManagerSettings {
     #[serde(default = "default_file_manager_tree_panel")]
     pub show_tree_panel: bool,
     #[serde(default)]
+    pub show_preview_panel: bool,
+    #[serde(default)]
+    pub dual_pane: bool,
+    #[serde(default)]
     pub view_mode: FileManagerViewMode,
     #[serde(default)]
     pub sort_mode: FileManagerSortMode,
@@ -473,6 +478,18 @@ pub struct DesktopFileManagerSettings {
     pub open_with_by_extension: BTreeMap<String, Vec<String>>,
     #[serde(default)]
     pub open_with_default_by_extension: BTreeMap<String, String>,
+    /// Per-file open-with commands, keyed by path; these win over the
+    /// extension default.
+    #[serde(default)]
+    pub open_with_by_path: BTreeMap<String, String>,
+    /// Trash size limit in MiB; the oldest items are purged past it. 0, the
+    /// default, disables the limit: the trash can be shared with other
+    /// programs, so purging is opt-in.
+    #[serde(default = "default_file_manager_trash_max_mb")]
+    pub trash_max_mb: u64,
+    /// Folders pinned to the top of the Places sidebar, in display order.
+    #[serde(default)]
+    pub bookmarks: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -495,17 +512,26 @@ const fn default_file_manager_tree_panel() -> bool {
     true
 }
 
+const fn default_file_manager_trash_max_mb() -> u64 {
+    0
+}
+
 impl Default for DesktopFileManagerSettings {
     fn default() -> Self {
         Self {
             show_hidden_files: false,
             show_tree_panel: true,
+            show_preview_panel: false,
+            dual_pane: false,
             view_mode: FileManagerViewMode::Grid,
             sort_mode: FileManagerSortMode::Name,
             directories_first: true,
             text_open_mode: FileManagerTextOpenMode::Viewer,
             open_with_by_extension: BTreeMap::new(),
             open_with_default_by_extension: BTreeMap::new(),
+            open_with_by_path: BTreeMap::new(),
+            trash_max_mb: default_file_manager_trash_max_mb(),
+            bookmarks: Vec::new(),
         }
     }
 }
@@ -567,6 +593,62 @@ pub struct DesktopPtyProfileSettings {
     pub open_fullscreen: bool,
     #[serde(default = "default_profile_live_resize")]
     pub live_resize: bool,
+    /// Alt acts as Meta: Alt+key is sent as ESC followed by the key.
+    #[serde(default)]
+    pub alt_sends_escape: bool,
+    #[serde(default)]
+    pub backspace: PtyBackspaceMode,
+    /// Keypad keys send application (SS3) sequences even when the child has
+    /// not enabled DECKPAM itself.
+    #[serde(default)]
+    pub application_keypad: bool,
+    /// Environment, working directory and extra arguments for every launch
+    /// that uses this profile.
+    #[serde(flatten)]
+    pub launch: LaunchEnvironment,
+}
+
+/// Per-launch process setup shared by catalog entries and PTY profiles.
+/// Values may start with `~` and reference `$VAR` / `${VAR}`; see
+/// [`crate::launcher::resolve_launch_environment`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchEnvironment {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// Appended to the command's own arguments.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+}
+
+impl LaunchEnvironment {
+    pub fn is_empty(&self) -> bool {
+        self.env.is_empty() && self.cwd.is_none() && self.args.is_empty()
+    }
+
+    /// Layers `over` on top of `self`: its variables win, its working
+    /// directory wins when set, and its arguments follow ours.
+    pub fn layered(&self, over: &LaunchEnvironment) -> LaunchEnvironment {
+        let mut env = self.env.clone();
+        env.extend(over.env.clone());
+        LaunchEnvironment {
+            env,
+            cwd: over.cwd.clone().or_else(|| self.cwd.clone()),
+            args: self.args.iter().chain(&over.args).cloned().collect(),
+        }
+    }
+}
+
+/// Byte sent by the Backspace key. Ctrl+Backspace sends the other one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PtyBackspaceMode {
+    /// `^?` (0x7f), the xterm default.
+    #[default]
+    Delete,
+    /// `^H` (0x08).
+    Backspace,
 }
 
 const fn default_profile_mouse_passthrough() -> bool {
@@ -591,6 +673,10 @@ impl Default for DesktopPtyProfileSettings {
             mouse_passthrough: true,
             open_fullscreen: false,
             live_resize: true,
+            alt_sends_escape: false,
+            backspace: PtyBackspaceMode::Delete,
+            application_keypad: false,
+            launch: LaunchEnvironment::default(),
         }
     }
 }
@@ -611,6 +697,80 @@ pub struct DesktopCliProfiles {
     pub custom: BTreeMap<String, DesktopPtyProfileSettings>,
 }
 
+impl DesktopCliProfiles {
+    /// Profile used to launch `cmd`: a custom profile named after the program
+    /// wins, then the built-in slots, then the default profile.
+    pub fn for_command(&self, cmd: &[String]) -> DesktopPtyProfileSettings {
+        let Some(base) = cmd.first().and_then(|program| pty_profile_key(program)) else {
+            return self.default.clone();
+        };
+        if let Some(custom) = self.custom.get(&base) {
+            return custom.clone();
+        }
+        match base.as_str() {
+            name if name.starts_with("calcurse") => self.calcurse.clone(),
+            name if name.starts_with("myman") => DesktopPtyProfileSettings {
+                live_resize: false,
+                preferred_w: Some(96),
+                preferred_h: Some(32),
+                ..self.default.clone()
+            },
+            "spotify_player" => self.spotify_player.clone(),
+            "ranger" => self.ranger.clone(),
+            "tuir" | "rtv" => self.reddit.clone(),
+            _ => self.default.clone(),
+        }
+    }
+
+    /// Profile by name: a built-in slot or a custom profile key.
+    pub fn named(&self, name: &str) -> Option<DesktopPtyProfileSettings> {
+        let key = pty_profile_key(name)?;
+        match key.as_str() {
+            "default" => Some(self.default.clone()),
+            "calcurse" => Some(self.calcurse.clone()),
+            "spotify_player" => Some(self.spotify_player.clone()),
+            "ranger" => Some(self.ranger.clone()),
+            "reddit" => Some(self.reddit.clone()),
+            _ => self.custom.get(&key).cloned(),
+        }
+    }
+
+    /// Profile for a catalog entry: its preferred profile when that exists,
+    /// otherwise the one picked by program name.
+    pub fn for_entry(&self, cmd: &[String], preferred: Option<&str>) -> DesktopPtyProfileSettings {
+        preferred
+            .and_then(|name| self.named(name))
+            .unwrap_or_else(|| self.for_command(cmd))
+    }
+
+    /// Names accepted by [`named`](Self::named), built-in slots first.
+    pub fn profile_names(&self) -> Vec<String> {
+        ["default", "calcurse", "spotify_player", "ranger", "reddit"]
+            .into_iter()
+            .map(str::to_string)
+            .chain(self.custom.keys().cloned())
+            .collect()
+    }
+}
+
+/// Lower-cased file name of a program, the key of custom PTY profiles.
+pub fn pty_profile_key(raw: &str) -> Option<String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    let base = Path::new(trimmed)
+        .file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or(trimmed)
+        .trim();
+    if base.is_empty() {
+        None
+    } else {
+        Some(base.to_ascii_lowercase())
+    }
+}
+
 fn default_calcurse_profile() -> DesktopPtyProfileSettings {
     DesktopPtyProfileSettings {
         min_w: 72,
@@ -620,6 +780,7 @@ fn default_calcurse_profile() -> DesktopPtyProfileSettings {
         mouse_passthrough: false,
         open_fullscreen: false,
         live_resize: true,
+        ..DesktopPtyProfileSettings::default()
     }
 }
 
@@ -632,6 +793,7 @@ fn default_spotify_profile() -> DesktopPtyProfileSettings {
         mouse_passthrough: true,
         open_fullscreen: false,
         live_resize: true,
+        ..DesktopPtyProfileSettings::default()
     }
 }
 
@@ -644,6 +806,7 @@ fn default_ranger_profile() -> DesktopPtyProfileSettings {
         mouse_passthrough: true,
         open_fullscreen: false,
         live_resize: true,
+        ..DesktopPtyProfileSettings::default()
     }
 }
 
@@ -656,6 +819,7 @@ fn default_reddit_profile() -> DesktopPtyProfileSettings {
         mouse_passthrough: true,
         open_fullscreen: false,
         live_resize: true,
+        ..DesktopPtyProfileSettings::default()
     }
 }
 
@@ -684,6 +848,31 @@ fn default_true() -> bool {
     true
 }
 
+/// Optional Spotlight providers. App, document and file search are always
+/// on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpotlightProviderSettings {
+    #[serde(default = "default_true")]
+    pub calculator: bool,
+    #[serde(default = "default_true")]
+    pub unit_conversion: bool,
+    #[serde(default = "default_true")]
+    pub run_command: bool,
+    #[serde(default = "default_true")]
+    pub manual: bool,
+}
+
+impl Default for SpotlightProviderSettings {
+    fn default() -> Self {
+        Self {
+            calculator: true,
+            unit_conversion: true,
+            run_command: true,
+            manual: true,
+        }
+    }
+}
+
 fn default_navigation_hints() -> bool {
     true
 }
@@ -792,6 +981,14 @@ pub struct Settings {
     pub desktop_hidden_builtin_icons: BTreeSet<String>,
     #[serde(default)]
     pub editor_recent_files: Vec<String>,
+    /// Record launches and opens for frecency ranking and recent lists.
+    #[serde(default = "default_launch_history_enabled")]
+    pub launch_history_enabled: bool,
+    /// Programs and windows opened after login, in order.
+    #[serde(default)]
+    pub autostart: Vec<AutostartItem>,
+    #[serde(default)]
+    pub spotlight_providers: SpotlightProviderSettings,
 }
 
 fn default_desktop_wallpaper() -> String {
@@ -802,6 +999,10 @@ const fn default_system_sound_volume() -> u8 {
     100
 }
 
+const fn default_launch_history_enabled() -> bool {
+    true
+}
+
 fn default_native_ui_scale() -> f32 {
     1.0
 }
@@ -845,6 +1046,9 @@ impl Default for Settings {
             desktop_icon_custom_positions: BTreeMap::new(),
             desktop_hidden_builtin_icons: BTreeSet::new(),
             editor_recent_files: Vec::new(),
+            launch_history_enabled: default_launch_history_enabled(),
+            autostart: Vec::new(),
+            spotlight_providers: SpotlightProviderSettings::default(),
         }
     }
 }
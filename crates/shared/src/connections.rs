@@ -68,6 +68,22 @@ pub fn macos_connections_disabled_hint() -> &'static str {
     "Connections are temporarily disabled on macOS due to unstable system command behavior."
 }
 
+/// Best-effort check for a usable network link. On Linux this looks for a
+/// non-loopback interface that is up; elsewhere it assumes one exists.
+pub fn network_available() -> bool {
+    if !cfg!(target_os = "linux") {
+        return true;
+    }
+    let Ok(entries) = std::fs::read_dir("/sys/class/net") else {
+        return true;
+    };
+    entries.flatten().any(|entry| {
+        entry.file_name() != "lo"
+            && std::fs::read_to_string(entry.path().join("operstate"))
+                .is_ok_and(|state| matches!(state.trim(), "up" | "unknown"))
+    })
+}
+
 pub fn bluetooth_installer_hint() -> &'static str {
     "Bluetooth tools require blueutil. Install it from Program Installer."
 }
@@ -0,0 +1,388 @@
+//! Per-user full-text index of documents (`users/<name>/document_index.json`).
+//!
+//! The index keeps the text of every readable document under a set of root
+//! folders. A refresh only re-reads files whose size or modification time
+//! changed and drops files that disappeared, so it stays cheap to run often.
+//! Only files accepted by [`is_allowed_extension`] are considered, and of
+//! those only ones that decode as UTF-8 text (PDF and e-book containers are
+//! skipped). The stored text is bounded per file and overall; when the
+//! total goes over budget the text of the least recently modified files is
+//! dropped until they change again.
+
+use crate::config::{is_allowed_extension, load_json, save_json, user_dir};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Files larger than this are not indexed.
+pub const MAX_INDEXED_FILE_BYTES: u64 = 512 * 1024;
+/// Total text kept across all files.
+pub const MAX_INDEX_BYTES: usize = 16 * 1024 * 1024;
+/// How deep a refresh descends below each root.
+const MAX_DEPTH: usize = 6;
+/// Characters of context on each side of a match in a snippet.
+const SNIPPET_CONTEXT_CHARS: usize = 40;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexedDocument {
+    pub modified: u64,
+    pub size: u64,
+    pub text: String,
+}
+
+/// A line of context around a match. `highlight` is the byte range of the
+/// match within `text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentSnippet {
+    pub text: String,
+    pub highlight: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentMatch {
+    pub path: PathBuf,
+    pub snippet: ContentSnippet,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DocumentIndex {
+    pub files: BTreeMap<PathBuf, IndexedDocument>,
+}
+
+fn file_stamp(path: &Path) -> Option<(u64, u64)> {
+    let meta = std::fs::metadata(path).ok()?;
+    if !meta.is_file() {
+        return None;
+    }
+    let modified = meta
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |elapsed| elapsed.as_secs());
+    Some((modified, meta.len()))
+}
+
+fn read_document_text(path: &Path) -> Option<String> {
+    let bytes = std::fs::read(path).ok()?;
+    if bytes.contains(&0) {
+        return None;
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn is_indexable(path: &Path) -> bool {
+    is_allowed_extension(path)
+        && !path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('.'))
+}
+
+fn collect_files(dir: &Path, depth: usize, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            if depth < MAX_DEPTH && !hidden {
+                collect_files(&path, depth + 1, out);
+            }
+        } else if file_type.is_file() && is_indexable(&path) {
+            out.push(path);
+        }
+    }
+}
+
+/// Byte range of the first case-insensitive occurrence of `needle`.
+pub fn find_ignore_case(haystack: &str, needle: &str) -> Option<Range<usize>> {
+    if needle.is_empty() {
+        return None;
+    }
+    if needle.is_ascii() {
+        let needle = needle.as_bytes();
+        return haystack
+            .as_bytes()
+            .windows(needle.len())
+            .position(|window| window.eq_ignore_ascii_case(needle))
+            .map(|start| start..start + needle.len());
+    }
+    let needle: Vec<char> = needle.chars().flat_map(char::to_lowercase).collect();
+    for (start, _) in haystack.char_indices() {
+        let mut wanted = needle.iter();
+        let mut end = start;
+        let mut pending = wanted.next();
+        for (offset, ch) in haystack[start..].char_indices() {
+            let Some(_) = pending else {
+                break;
+            };
+            let mut matched = true;
+            for lower in ch.to_lowercase() {
+                if pending != Some(&lower) {
+                    matched = false;
+                    break;
+                }
+                pending = wanted.next();
+            }
+            if !matched {
+                break;
+            }
+            end = start + offset + ch.len_utf8();
+        }
+        if pending.is_none() {
+            return Some(start..end);
+        }
+    }
+    None
+}
+
+/// The line around `range`, trimmed to a few words of context on each
+/// side, with `highlight` rebased onto the snippet.
+pub fn snippet_around(text: &str, range: Range<usize>) -> ContentSnippet {
+    let line_start = text[..range.start].rfind('\n').map_or(0, |idx| idx + 1);
+    let line_end = text[range.end..]
+        .find('\n')
+        .map_or(text.len(), |idx| range.end + idx);
+    let start = text[line_start..range.start]
+        .char_indices()
+        .rev()
+        .nth(SNIPPET_CONTEXT_CHARS - 1)
+        .map_or(line_start, |(idx, _)| line_start + idx);
+    let end = text[range.end..line_end]
+        .char_indices()
+        .nth(SNIPPET_CONTEXT_CHARS)
+        .map_or(line_end, |(idx, _)| range.end + idx);
+
+    let mut snippet = String::new();
+    if start > line_start {
+        snippet.push_str("...");
+    }
+    let raw = &text[start..end];
+    let lead = (raw.len() - raw.trim_start().len()).min(range.start - start);
+    let highlight_start = snippet.len() + range.start - start - lead;
+    snippet.push_str(raw[lead..].trim_end_matches('\r'));
+    let highlight = highlight_start..highlight_start + range.len();
+    if end < line_end {
+        snippet.push_str("...");
+    }
+    ContentSnippet {
+        text: snippet.replace('\t', " "),
+        highlight,
+    }
+}
+
+impl DocumentIndex {
+    pub fn total_bytes(&self) -> usize {
+        self.files.values().map(|doc| doc.text.len()).sum()
+    }
+
+    /// Files whose text is searchable; skipped files only keep a stamp.
+    pub fn document_count(&self) -> usize {
+        self.files
+            .values()
+            .filter(|doc| !doc.text.is_empty())
+            .count()
+    }
+
+    /// Re-reads changed files under `roots` and forgets files that are gone
+    /// or no longer under any root. Returns whether anything changed.
+    pub fn refresh(&mut self, roots: &[PathBuf]) -> bool {
+        let mut found = Vec::new();
+        for root in roots {
+            collect_files(root, 0, &mut found);
+        }
+        found.sort();
+        found.dedup();
+
+        let before = self.files.len();
+        self.files
+            .retain(|path, _| found.binary_search(path).is_ok());
+        let mut changed = self.files.len() != before;
+        for path in found {
+            changed |= self.index_file(&path);
+        }
+        if changed {
+            self.enforce_limit();
+        }
+        changed
+    }
+
+    /// Clears the index and reads every file again.
+    pub fn rebuild(&mut self, roots: &[PathBuf]) {
+        self.files.clear();
+        self.refresh(roots);
+    }
+
+    /// Re-indexes one file, e.g. after it was saved. Ignored unless it lies
+    /// under one of `roots`.
+    pub fn update_file(&mut self, path: &Path, roots: &[PathBuf]) -> bool {
+        if !roots.iter().any(|root| path.starts_with(root)) || !is_indexable(path) {
+            return false;
+        }
+        let changed = self.index_file(path);
+        if changed {
+            self.enforce_limit();
+        }
+        changed
+    }
+
+    fn index_file(&mut self, path: &Path) -> bool {
+        let Some((modified, size)) = file_stamp(path) else {
+            return self.files.remove(path).is_some();
+        };
+        if self
+            .files
+            .get(path)
+            .is_some_and(|doc| doc.modified == modified && doc.size == size)
+        {
+            return false;
+        }
+        // Files that are too big or not text keep an empty stamp, so the
+        // next refresh skips them until they change.
+        let text = (size <= MAX_INDEXED_FILE_BYTES)
+            .then(|| read_document_text(path))
+            .flatten()
+            .unwrap_or_default();
+        self.files.insert(
+            path.to_path_buf(),
+            IndexedDocument {
+                modified,
+                size,
+                text,
+            },
+        );
+        true
+    }
+
+    fn enforce_limit(&mut self) {
+        let mut total = self.total_bytes();
+        if total <= MAX_INDEX_BYTES {
+            return;
+        }
+        let mut by_age: Vec<(u64, PathBuf)> = self
+            .files
+            .iter()
+            .filter(|(_, doc)| !doc.text.is_empty())
+            .map(|(path, doc)| (doc.modified, path.clone()))
+            .collect();
+        by_age.sort();
+        for (_, path) in by_age {
+            if total <= MAX_INDEX_BYTES {
+                break;
+            }
+            // Keep the stamp so the next refresh does not read it again.
+            if let Some(doc) = self.files.get_mut(&path) {
+                total -= std::mem::take(&mut doc.text).len();
+            }
+        }
+    }
+
+    /// Files whose text contains `query`, ignoring case, with a snippet of
+    /// the first match. Newer files come first.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<ContentMatch> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let mut hits: Vec<(u64, ContentMatch)> = self
+            .files
+            .iter()
+            .filter_map(|(path, doc)| {
+                let range = find_ignore_case(&doc.text, query)?;
+                Some((
+                    doc.modified,
+                    ContentMatch {
+                        path: path.clone(),
+                        snippet: snippet_around(&doc.text, range),
+                    },
+                ))
+            })
+            .collect();
+        hits.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.path.cmp(&b.1.path)));
+        hits.into_iter().take(limit).map(|(_, hit)| hit).collect()
+    }
+}
+
+fn document_index_path(username: &str) -> PathBuf {
+    user_dir(username).join("document_index.json")
+}
+
+pub fn load_document_index(username: &str) -> DocumentIndex {
+    load_json(&document_index_path(username))
+}
+
+pub fn save_document_index(username: &str, index: &DocumentIndex) {
+    let _ = save_json(&document_index_path(username), index);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_dir(label: &str) -> PathBuf {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("robcos_doc_index_{label}_{unique}"));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn snippets_highlight_the_match_case_insensitively() {
+        let text = "first line\n    The Vault door opens at dawn.\nlast line";
+        let range = find_ignore_case(text, "vault").unwrap();
+        let snippet = snippet_around(text, range);
+        assert_eq!(snippet.text, "The Vault door opens at dawn.");
+        assert_eq!(&snippet.text[snippet.highlight.clone()], "Vault");
+
+        let text = "Überraschung im Bunker";
+        let range = find_ignore_case(text, "über").unwrap();
+        assert_eq!(&text[range], "Über");
+    }
+
+    #[test]
+    fn refresh_indexes_text_documents_and_skips_binary_ones() {
+        let root = temp_dir("refresh");
+        std::fs::create_dir_all(root.join("notes")).unwrap();
+        std::fs::write(root.join("notes/log.txt"), "Water chip status: broken").unwrap();
+        std::fs::write(root.join("manual.pdf"), b"%PDF-1.4\0\x01binary").unwrap();
+        std::fs::write(root.join("script.sh"), "echo water").unwrap();
+
+        let roots = vec![root.clone()];
+        let mut index = DocumentIndex::default();
+        assert!(index.refresh(&roots));
+        assert_eq!(index.document_count(), 1);
+        assert!(!index.refresh(&roots));
+
+        // The skipped PDF keeps a stamp: rewriting it with text of the same
+        // size and time is not noticed, so it was not read again.
+        let pdf = root.join("manual.pdf");
+        let modified = std::fs::metadata(&pdf).unwrap().modified().unwrap();
+        std::fs::write(&pdf, b"water water wate").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&pdf)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        assert!(!index.refresh(&roots));
+
+        let hits = index.search("WATER", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].path, root.join("notes/log.txt"));
+
+        std::fs::remove_file(root.join("notes/log.txt")).unwrap();
+        assert!(index.refresh(&roots));
+        assert!(index.search("water", 10).is_empty());
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
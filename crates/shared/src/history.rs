@@ -0,0 +1,222 @@
+//! Per-user launch history (`history.json`) and frecency ranking.
+//!
+//! Every catalog launch, document open and file open bumps a record. The
+//! frecency score weights the most recent visits by age and scales by the
+//! total count, so both often and recently used items rank high.
+
+use crate::config::{get_settings, load_json, save_json, user_file};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Visit timestamps kept per record for the recency part of the score.
+const MAX_VISITS: usize = 10;
+/// Records kept overall; the lowest scoring ones are dropped first.
+const MAX_RECORDS: usize = 200;
+const DAY_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LaunchKind {
+    Application,
+    Network,
+    Game,
+    Document,
+    File,
+}
+
+impl LaunchKind {
+    /// Whether `target` is a filesystem path rather than a catalog name.
+    pub fn is_path(self) -> bool {
+        matches!(self, LaunchKind::Document | LaunchKind::File)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchRecord {
+    pub kind: LaunchKind,
+    /// Catalog entry name, or the full path for documents and files.
+    pub target: String,
+    pub count: u32,
+    /// Unix timestamps of the latest visits, oldest first.
+    #[serde(default)]
+    pub visits: Vec<u64>,
+}
+
+impl LaunchRecord {
+    pub fn last_used(&self) -> u64 {
+        self.visits.last().copied().unwrap_or(0)
+    }
+
+    pub fn frecency(&self, now: u64) -> u64 {
+        if self.visits.is_empty() {
+            return 0;
+        }
+        let weights: u64 = self
+            .visits
+            .iter()
+            .map(|visit| recency_weight(now.saturating_sub(*visit) / DAY_SECS))
+            .sum();
+        u64::from(self.count) * weights / self.visits.len() as u64
+    }
+
+    /// Display label: the catalog name or the file name of a path.
+    pub fn label(&self) -> String {
+        if self.kind.is_path() {
+            std::path::Path::new(&self.target)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| self.target.clone())
+        } else {
+            self.target.clone()
+        }
+    }
+}
+
+fn recency_weight(age_days: u64) -> u64 {
+    match age_days {
+        0..=4 => 100,
+        5..=14 => 70,
+        15..=31 => 50,
+        32..=90 => 30,
+        _ => 10,
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchHistory {
+    #[serde(default)]
+    pub records: Vec<LaunchRecord>,
+}
+
+impl LaunchHistory {
+    pub fn record(&mut self, kind: LaunchKind, target: &str, now: u64) {
+        match self
+            .records
+            .iter_mut()
+            .find(|record| record.kind == kind && record.target == target)
+        {
+            Some(record) => {
+                record.count = record.count.saturating_add(1);
+                record.visits.push(now);
+                if record.visits.len() > MAX_VISITS {
+                    record.visits.remove(0);
+                }
+            }
+            None => self.records.push(LaunchRecord {
+                kind,
+                target: target.to_string(),
+                count: 1,
+                visits: vec![now],
+            }),
+        }
+        if self.records.len() > MAX_RECORDS {
+            self.records = self.ranked(now).into_iter().take(MAX_RECORDS).collect();
+        }
+    }
+
+    /// Score of one item, 0 when it was never used.
+    pub fn frecency(&self, kind: LaunchKind, target: &str, now: u64) -> u64 {
+        self.records
+            .iter()
+            .find(|record| record.kind == kind && record.target == target)
+            .map_or(0, |record| record.frecency(now))
+    }
+
+    /// Records by descending frecency, most recent first on ties.
+    pub fn ranked(&self, now: u64) -> Vec<LaunchRecord> {
+        let mut records = self.records.clone();
+        records.sort_by(|a, b| {
+            b.frecency(now)
+                .cmp(&a.frecency(now))
+                .then_with(|| b.last_used().cmp(&a.last_used()))
+        });
+        records
+    }
+
+    /// Records by descending last use.
+    pub fn recent(&self) -> Vec<LaunchRecord> {
+        let mut records = self.records.clone();
+        records.sort_by_key(|record| std::cmp::Reverse(record.last_used()));
+        records
+    }
+
+    pub fn forget(&mut self, kind: LaunchKind, target: &str) {
+        self.records
+            .retain(|record| record.kind != kind || record.target != target);
+    }
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+pub fn load_history() -> LaunchHistory {
+    load_json(&user_file("history.json"))
+}
+
+pub fn save_history(history: &LaunchHistory) {
+    let _ = save_json(&user_file("history.json"), history);
+}
+
+/// Records one use unless launch history is disabled in settings.
+pub fn record_launch(kind: LaunchKind, target: &str) {
+    if !get_settings().launch_history_enabled || target.trim().is_empty() {
+        return;
+    }
+    let mut history = load_history();
+    history.record(kind, target, unix_now());
+    save_history(&history);
+}
+
+pub fn clear_history() {
+    let _ = std::fs::remove_file(user_file("history.json"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_000 * DAY_SECS;
+
+    #[test]
+    fn frequent_items_outrank_single_recent_ones() {
+        let mut history = LaunchHistory::default();
+        for day in 0..5 {
+            history.record(LaunchKind::Application, "btop", NOW - day * DAY_SECS);
+        }
+        history.record(LaunchKind::Game, "nethack", NOW);
+
+        let ranked = history.ranked(NOW);
+        assert_eq!(ranked[0].target, "btop");
+        assert_eq!(ranked[0].count, 5);
+        assert_eq!(history.recent()[0].target, "nethack");
+        assert_eq!(history.frecency(LaunchKind::Application, "nethack", NOW), 0);
+    }
+
+    #[test]
+    fn old_visits_decay_and_visit_list_is_capped() {
+        let mut history = LaunchHistory::default();
+        history.record(LaunchKind::File, "/tmp/old.txt", NOW - 200 * DAY_SECS);
+        history.record(LaunchKind::File, "/tmp/new.txt", NOW);
+        assert!(
+            history.frecency(LaunchKind::File, "/tmp/new.txt", NOW)
+                > history.frecency(LaunchKind::File, "/tmp/old.txt", NOW)
+        );
+
+        for _ in 0..(MAX_VISITS + 5) {
+            history.record(LaunchKind::Document, "/tmp/doc.txt", NOW);
+        }
+        let record = history
+            .records
+            .iter()
+            .find(|record| record.kind == LaunchKind::Document)
+            .expect("document record");
+        assert_eq!(record.visits.len(), MAX_VISITS);
+        assert_eq!(record.label(), "doc.txt");
+
+        history.forget(LaunchKind::Document, "/tmp/doc.txt");
+        assert_eq!(history.records.len(), 2);
+    }
+}
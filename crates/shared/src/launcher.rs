@@ -4,11 +4,15 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use std::collections::BTreeMap;
 use std::io::stdout;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::{Duration, Instant};
 
-use crate::config::{get_settings, update_settings};
+use crate::catalog::{CatalogEntry, CatalogRunMode};
+use crate::config::{get_settings, update_settings, LaunchEnvironment};
+use crate::processes::{register_owned_process, unregister_owned_process, OwnedProcessKind};
 use crate::ui::Term;
 
 /// Suspend the TUI, run a closure in normal terminal mode, then resume.
@@ -168,6 +172,49 @@ pub fn launch_in_pty(terminal: &mut Term, cmd: &[String]) -> Result<()> {
     crate::pty::launch_in_pty(terminal, cmd)
 }
 
+/// Launch a catalog value (argv array or object form) the way its entry asks:
+/// in a PTY session with its environment, working directory, extra arguments
+/// and preferred profile, or as a detached process.
+pub fn launch_catalog_value_in_pty(terminal: &mut Term, value: &serde_json::Value) -> Result<()> {
+    let entry = CatalogEntry::from_json(value);
+    match entry.run_mode {
+        CatalogRunMode::Detached => spawn_detached(&entry.command, &entry.launch),
+        CatalogRunMode::Pty => crate::pty::launch_in_pty_with_environment(
+            terminal,
+            &entry.command,
+            &entry.launch,
+            entry.pty_profile.as_deref(),
+        ),
+    }
+}
+
+/// Start `cmd` without a terminal, detached from the session's stdio.
+pub fn spawn_detached(cmd: &[String], launch: &LaunchEnvironment) -> Result<()> {
+    let Some(program) = cmd.first() else {
+        return Ok(());
+    };
+    let resolved = resolve_launch_environment(launch);
+    let mut command = Command::new(program);
+    command
+        .args(&cmd[1..])
+        .args(&resolved.args)
+        .envs(resolved.env)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null());
+    if let Some(cwd) = resolved.cwd {
+        command.current_dir(cwd);
+    }
+    let mut child = command.spawn()?;
+    let pid = child.id();
+    register_owned_process(pid, OwnedProcessKind::Detached, &cmd.join(" "));
+    std::thread::spawn(move || {
+        let _ = child.wait();
+        unregister_owned_process(pid);
+    });
+    Ok(())
+}
+
 pub fn launch_argv(terminal: &mut Term, cmd: &[String]) -> Result<()> {
     if cmd.is_empty() {
         return Ok(());
@@ -178,15 +225,148 @@ pub fn launch_argv(terminal: &mut Term, cmd: &[String]) -> Result<()> {
     })
 }
 
-/// Parse a JSON array of strings into a Vec<String> command.
+/// Parse a catalog value into a Vec<String> command. Accepts a plain JSON
+/// array of strings or an object with a `command` array.
 pub fn json_to_cmd(val: &serde_json::Value) -> Vec<String> {
-    val.as_array()
-        .map(|a| {
-            a.iter()
-                .filter_map(|v| v.as_str().map(str::to_string))
-                .collect()
+    CatalogEntry::from_json(val).command
+}
+
+/// Environment, working directory and extra arguments of an object-form
+/// catalog value. Plain argv arrays have none.
+pub fn json_to_launch_environment(val: &serde_json::Value) -> LaunchEnvironment {
+    CatalogEntry::from_json(val).launch
+}
+
+/// A [`LaunchEnvironment`] with `~` and `$VAR` expanded, ready to spawn.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResolvedLaunchEnvironment {
+    pub env: Vec<(String, String)>,
+    pub cwd: Option<PathBuf>,
+    pub args: Vec<String>,
+}
+
+/// Expands a launch environment against the current process environment.
+pub fn resolve_launch_environment(launch: &LaunchEnvironment) -> ResolvedLaunchEnvironment {
+    resolve_launch_environment_with(launch, dirs::home_dir().as_deref(), |name| {
+        std::env::var(name).ok()
+    })
+}
+
+/// Variables expand against the process environment only, so
+/// `PATH=$HOME/bin:$PATH` extends the inherited value. The working directory
+/// and arguments also see the launch's own variables.
+pub fn resolve_launch_environment_with(
+    launch: &LaunchEnvironment,
+    home: Option<&Path>,
+    process_var: impl Fn(&str) -> Option<String>,
+) -> ResolvedLaunchEnvironment {
+    let env: Vec<(String, String)> = launch
+        .env
+        .iter()
+        .filter(|(key, _)| !key.trim().is_empty())
+        .map(|(key, value)| (key.clone(), expand_launch_value(value, home, &process_var)))
+        .collect();
+    let launch_var = |name: &str| {
+        env.iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+            .or_else(|| process_var(name))
+    };
+    let cwd = launch
+        .cwd
+        .as_deref()
+        .map(str::trim)
+        .filter(|cwd| !cwd.is_empty())
+        .map(|cwd| PathBuf::from(expand_launch_value(cwd, home, launch_var)));
+    let args = launch
+        .args
+        .iter()
+        .map(|arg| expand_launch_value(arg, home, launch_var))
+        .collect();
+    ResolvedLaunchEnvironment { env, cwd, args }
+}
+
+/// Editable text form of launch variables: one `KEY=VALUE` per line.
+pub fn launch_env_to_text(env: &BTreeMap<String, String>) -> String {
+    env.iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parses [`launch_env_to_text`] output. Blank lines and `#` comments are
+/// skipped; a line without `=` sets an empty value.
+pub fn launch_env_from_text(text: &str) -> BTreeMap<String, String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=').unwrap_or((line, ""));
+            let key = key.trim();
+            (!key.is_empty()).then(|| (key.to_string(), value.trim().to_string()))
         })
-        .unwrap_or_default()
+        .collect()
+}
+
+/// Editable text form of extra arguments, quoted like a shell command line.
+pub fn launch_args_to_text(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| shell_quote(arg))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parses [`launch_args_to_text`] output; unbalanced quotes yield no args.
+pub fn launch_args_from_text(text: &str) -> Vec<String> {
+    crate::default_apps::parse_custom_command_line(text.trim()).unwrap_or_default()
+}
+
+/// Shell-style expansion of a leading `~` / `~/` and of `$NAME` / `${NAME}`.
+/// Unset variables expand to nothing; `$$` is a literal `$`.
+pub fn expand_launch_value(
+    raw: &str,
+    home: Option<&Path>,
+    var: impl Fn(&str) -> Option<String>,
+) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut rest = raw;
+    if let Some(home) = home {
+        if rest == "~" || rest.starts_with("~/") {
+            out.push_str(&home.to_string_lossy());
+            rest = &rest[1..];
+        }
+    }
+    let mut chars = rest.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch != '$' {
+            out.push(ch);
+            continue;
+        }
+        match chars.peek() {
+            Some('$') => {
+                chars.next();
+                out.push('$');
+            }
+            Some('{') => {
+                chars.next();
+                let name: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                out.push_str(&var(&name).unwrap_or_default());
+            }
+            Some(c) if c.is_ascii_alphabetic() || *c == '_' => {
+                let mut name = String::new();
+                while let Some(c) = chars
+                    .peek()
+                    .filter(|c| c.is_ascii_alphanumeric() || **c == '_')
+                {
+                    name.push(*c);
+                    chars.next();
+                }
+                out.push_str(&var(&name).unwrap_or_default());
+            }
+            _ => out.push('$'),
+        }
+    }
+    out
 }
 
 #[cfg(test)]
@@ -236,4 +416,106 @@ mod tests {
         let built = build_shell_fallback_command(&sv(&["vim", "file.txt"]));
         assert!(built.is_some());
     }
+
+    fn test_var(name: &str) -> Option<String> {
+        match name {
+            "HOME" => Some("/home/vault".to_string()),
+            "PATH" => Some("/usr/bin".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn launch_values_expand_tilde_and_variables() {
+        let home = Path::new("/home/vault");
+        let expand = |raw: &str| expand_launch_value(raw, Some(home), test_var);
+        assert_eq!(expand("~/projects"), "/home/vault/projects");
+        assert_eq!(expand("~"), "/home/vault");
+        assert_eq!(expand("a~b"), "a~b");
+        assert_eq!(expand("$HOME/bin:${PATH}"), "/home/vault/bin:/usr/bin");
+        assert_eq!(expand("x$MISSING-y"), "x-y");
+        assert_eq!(expand("cost $$5 $"), "cost $5 $");
+    }
+
+    #[test]
+    fn resolved_cwd_and_args_see_launch_variables() {
+        let launch = LaunchEnvironment {
+            env: [
+                ("PATH".to_string(), "~/bin:$PATH".to_string()),
+                ("PROJECT".to_string(), "~/src/robco".to_string()),
+            ]
+            .into_iter()
+            .collect(),
+            cwd: Some("$PROJECT".to_string()),
+            args: sv(&["--root", "${PROJECT}/docs"]),
+        };
+        let resolved =
+            resolve_launch_environment_with(&launch, Some(Path::new("/home/vault")), test_var);
+        assert_eq!(
+            resolved.env,
+            vec![
+                ("PATH".to_string(), "/home/vault/bin:/usr/bin".to_string()),
+                ("PROJECT".to_string(), "/home/vault/src/robco".to_string()),
+            ]
+        );
+        assert_eq!(resolved.cwd, Some(PathBuf::from("/home/vault/src/robco")));
+        assert_eq!(resolved.args, sv(&["--root", "/home/vault/src/robco/docs"]));
+    }
+
+    #[test]
+    fn catalog_values_accept_argv_arrays_and_objects() {
+        let plain = serde_json::json!(["vim"]);
+        assert_eq!(json_to_cmd(&plain), sv(&["vim"]));
+        assert!(json_to_launch_environment(&plain).is_empty());
+
+        let object = serde_json::json!({
+            "command": ["vim"],
+            "env": {"TERM": "xterm-direct", "EDITOR": "vim"},
+            "cwd": "~/projects",
+        });
+        assert_eq!(json_to_cmd(&object), sv(&["vim"]));
+        let launch = json_to_launch_environment(&object);
+        assert_eq!(launch.cwd.as_deref(), Some("~/projects"));
+        assert_eq!(
+            launch.env.get("TERM").map(String::as_str),
+            Some("xterm-direct")
+        );
+    }
+
+    #[test]
+    fn launch_text_forms_round_trip() {
+        let env = launch_env_from_text("TERM=xterm-direct\n\n# note\nEDITOR = vim\nEMPTY");
+        assert_eq!(env.get("EDITOR").map(String::as_str), Some("vim"));
+        assert_eq!(env.get("EMPTY").map(String::as_str), Some(""));
+        assert_eq!(launch_env_from_text(&launch_env_to_text(&env)), env);
+
+        let args = sv(&["--title", "Vault 111", "it's"]);
+        assert_eq!(launch_args_from_text(&launch_args_to_text(&args)), args);
+        assert!(launch_args_from_text("'open").is_empty());
+    }
+
+    #[test]
+    fn layered_launch_environment_prefers_the_upper_layer() {
+        let profile = LaunchEnvironment {
+            env: [("TERM".to_string(), "xterm".to_string())]
+                .into_iter()
+                .collect(),
+            cwd: Some("~".to_string()),
+            args: sv(&["-a"]),
+        };
+        let entry = LaunchEnvironment {
+            env: [("TERM".to_string(), "xterm-direct".to_string())]
+                .into_iter()
+                .collect(),
+            cwd: None,
+            args: sv(&["-b"]),
+        };
+        let merged = profile.layered(&entry);
+        assert_eq!(
+            merged.env.get("TERM").map(String::as_str),
+            Some("xterm-direct")
+        );
+        assert_eq!(merged.cwd.as_deref(), Some("~"));
+        assert_eq!(merged.args, sv(&["-a", "-b"]));
+    }
 }
@@ -1,10 +1,16 @@
+pub mod autostart;
+pub mod catalog;
 pub mod config;
 pub mod connections;
 pub mod core;
 pub mod default_apps;
 pub mod diag;
+pub mod doc_index;
+pub mod history;
 pub mod launcher;
+pub mod processes;
 pub mod pty;
+pub mod scheduler;
 pub mod session;
 pub mod sound;
 pub mod status;
@@ -0,0 +1,101 @@
+//! Registry of child processes spawned by RobCoOS itself.
+//!
+//! PTY sessions, detached catalog launches and sound players register their
+//! pid here so the task manager can tell them apart from the rest of the
+//! system process table and jump to the owning PTY window.
+
+use crate::pty::PtySessionId;
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OwnedProcessKind {
+    Pty(PtySessionId),
+    Detached,
+    Sound,
+}
+
+impl OwnedProcessKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            OwnedProcessKind::Pty(_) => "PTY",
+            OwnedProcessKind::Detached => "Detached",
+            OwnedProcessKind::Sound => "Sound",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedProcess {
+    pub pid: u32,
+    pub kind: OwnedProcessKind,
+    /// Program name or command line the process was started with.
+    pub label: String,
+    /// Unix timestamp of the spawn.
+    pub started: u64,
+}
+
+fn registry() -> &'static Mutex<Vec<OwnedProcess>> {
+    static REGISTRY: OnceLock<Mutex<Vec<OwnedProcess>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+pub fn register_owned_process(pid: u32, kind: OwnedProcessKind, label: &str) {
+    let started = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    if let Ok(mut processes) = registry().lock() {
+        processes.retain(|process| process.pid != pid);
+        processes.push(OwnedProcess {
+            pid,
+            kind,
+            label: label.to_string(),
+            started,
+        });
+    }
+}
+
+pub fn unregister_owned_process(pid: u32) {
+    if let Ok(mut processes) = registry().lock() {
+        processes.retain(|process| process.pid != pid);
+    }
+}
+
+/// Snapshot of every registered process, oldest first.
+pub fn owned_processes() -> Vec<OwnedProcess> {
+    registry()
+        .lock()
+        .map(|processes| processes.clone())
+        .unwrap_or_default()
+}
+
+pub fn owned_process(pid: u32) -> Option<OwnedProcess> {
+    registry()
+        .lock()
+        .ok()
+        .and_then(|processes| processes.iter().find(|process| process.pid == pid).cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registering_same_pid_replaces_entry() {
+        let pid = u32::MAX - 7;
+        register_owned_process(pid, OwnedProcessKind::Detached, "first");
+        register_owned_process(pid, OwnedProcessKind::Sound, "second");
+        let process = owned_process(pid).expect("registered");
+        assert_eq!(process.kind, OwnedProcessKind::Sound);
+        assert_eq!(process.label, "second");
+        assert_eq!(
+            owned_processes()
+                .iter()
+                .filter(|process| process.pid == pid)
+                .count(),
+            1
+        );
+        unregister_owned_process(pid);
+        assert!(owned_process(pid).is_none());
+    }
+}
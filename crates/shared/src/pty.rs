@@ -11,7 +11,7 @@
 
 use anyhow::Result;
 use crossterm::event::{
-    self, Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind,
+    self, Event, KeyCode, KeyEventKind, KeyEventState, KeyModifiers, MouseButton, MouseEventKind,
 };
 use portable_pty::{native_pty_system, CommandBuilder, ExitStatus, PtySize};
 use ratatui::{
@@ -22,18 +22,38 @@ use ratatui::{
 };
 use std::collections::HashMap;
 use std::io::Write;
-use std::sync::atomic::{AtomicU16, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicU16, AtomicU64, AtomicU8, Ordering};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, Instant};
 
+use crate::config::LaunchEnvironment;
+use crate::launcher::resolve_launch_environment;
+use crate::processes::{register_owned_process, unregister_owned_process, OwnedProcessKind};
 use crate::status::render_status_bar;
 use crate::ui::Term;
 
+mod broadcast;
+mod keys;
+mod transcript;
+
+pub use broadcast::{PtyBroadcast, PtyInput, PtySessionId};
+pub use keys::{
+    encode_key, KeyEncoding, KittyKeyboardState, PtyKeyOptions, KITTY_DISAMBIGUATE,
+    KITTY_REPORT_ALL_KEYS, KITTY_REPORT_ALTERNATE_KEYS, KITTY_REPORT_EVENT_TYPES,
+    KITTY_REPORT_TEXT, KITTY_SUPPORTED_FLAGS,
+};
+use transcript::Transcript;
+
 #[derive(Debug, Clone, Default)]
 pub struct PtyLaunchOptions {
     pub env: Vec<(String, String)>,
+    /// Working directory of the child; inherits ours when unset.
+    pub cwd: Option<std::path::PathBuf>,
     pub top_bar: Option<String>,
     pub force_render_mode: Option<bool>, // Some(true)=plain, Some(false)=styled
+    pub keys: PtyKeyOptions,
+    /// Output is also appended here as plain text, escape sequences removed.
+    pub transcript: Option<std::path::PathBuf>,
 }
 
 static SUSPENDED_PTY: OnceLock<Mutex<HashMap<usize, PtySession>>> = OnceLock::new();
@@ -522,9 +542,14 @@ impl DecSpecialGraphics {
 
 // ── PTY Session ───────────────────────────────────────────────────────────────
 
+static NEXT_PTY_SESSION_ID: AtomicU64 = AtomicU64::new(1);
+
 pub struct PtySession {
-    /// Write end — send keyboard input to the child
-    writer: Box<dyn Write + Send>,
+    /// Process-unique id, stable while the session moves between views.
+    id: PtySessionId,
+    /// Write end — send keyboard input to the child.  Shared with the reader
+    /// thread, which answers kitty keyboard protocol queries.
+    writer: Arc<Mutex<Box<dyn Write + Send>>>,
     /// Shared vt100 parser — updated by reader thread, read by render loop
     parser: Arc<Mutex<vt100::Parser>>,
     /// Child handle — check if still alive
@@ -540,13 +565,15 @@ pub struct PtySession {
     acs_mode: AcsGlyphMode,
     /// Optional top banner shown above PTY content.
     top_bar: Option<String>,
+    /// Per-launch keyboard options (Alt-as-Meta, backspace, keypad).
+    key_options: PtyKeyOptions,
+    /// Kitty keyboard flags currently negotiated by the child.
+    kitty_flags: Arc<AtomicU8>,
     /// Master — kept alive so the PTY stays open; also used for resize
     master: Box<dyn portable_pty::MasterPty + Send>,
     /// Monotonic counter incremented by reader thread when new PTY output arrives.
-    #[allow(dead_code)]
     output_epoch: Arc<AtomicU64>,
     /// Last observed output epoch on UI/render side.
-    #[allow(dead_code)]
     last_seen_output_epoch: u64,
     /// Committed display buffer — updated by reader thread after each
     /// coalesced I/O batch.  The renderer reads from this, never from
@@ -558,8 +585,19 @@ pub struct PtySession {
     shared_rows: Arc<AtomicU16>,
     /// Cached child exit status once the process has terminated.
     last_exit_status: Option<ExitStatus>,
+    /// Optional callback fired by the reader thread after each commit so
+    /// event-driven front ends can repaint instead of polling.
+    output_waker: Arc<Mutex<Option<OutputWaker>>>,
+    /// Shaped ratatui rows keyed by the committed row epochs.
+    line_cache: Mutex<RenderLineCache>,
+    /// Row damage tracker shared with the reader thread; resize forces a
+    /// full rebuild through it.
+    tracker: Arc<Mutex<FrameTracker>>,
 }
 
+/// Callback invoked from the reader thread when new output is committed.
+pub type OutputWaker = Arc<dyn Fn() + Send + Sync>;
+
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct PtyTextSnapshot {
@@ -601,10 +639,16 @@ pub struct CommittedFrame {
     pub plain: PtyTextSnapshot,
     pub cols: u16,
     pub rows: u16,
+    /// Commit epoch that last rebuilt each row.  Renderers key their row
+    /// caches on this so unchanged rows are never reshaped.
+    pub row_epochs: Vec<u64>,
+    /// Epoch of the most recent commit.
+    pub epoch: u64,
 }
 
 impl CommittedFrame {
-    fn blank(cols: u16, rows: u16) -> Self {
+    /// Empty frame of the given size; every row starts at epoch 0.
+    pub fn blank(cols: u16, rows: u16) -> Self {
         let default_cell = PtyStyledCell {
             ch: ' ',
             fg: Color::Reset,
@@ -629,13 +673,79 @@ impl CommittedFrame {
             },
             cols,
             rows,
+            row_epochs: vec![0; rows as usize],
+            epoch: 0,
         }
     }
+
+    /// Copy rows whose epoch differs from `self` out of `source`.
+    /// Returns the number of rows copied, or `None` when nothing changed
+    /// (including the cursor).
+    pub fn sync_from(&mut self, source: &CommittedFrame) -> Option<usize> {
+        if self.epoch == source.epoch && self.cols == source.cols && self.rows == source.rows {
+            return None;
+        }
+        if self.cols != source.cols || self.rows != source.rows {
+            *self = source.clone();
+            return Some(source.rows as usize);
+        }
+        let mut copied = 0;
+        for row in 0..source.rows as usize {
+            if self.row_epochs.get(row) == source.row_epochs.get(row) {
+                continue;
+            }
+            self.styled.cells[row].clone_from(&source.styled.cells[row]);
+            self.plain.lines[row].clone_from(&source.plain.lines[row]);
+            self.row_epochs[row] = source.row_epochs[row];
+            copied += 1;
+        }
+        self.styled.cursor_row = source.styled.cursor_row;
+        self.styled.cursor_col = source.styled.cursor_col;
+        self.styled.cursor_hidden = source.styled.cursor_hidden;
+        self.plain.cursor_row = source.plain.cursor_row;
+        self.plain.cursor_col = source.plain.cursor_col;
+        self.plain.cursor_hidden = source.plain.cursor_hidden;
+        self.epoch = source.epoch;
+        Some(copied)
+    }
+}
+
+/// Build one styled row from the screen.
+fn build_styled_row(
+    screen: &vt100::Screen,
+    row: u16,
+    cols: u16,
+    acs_mode: AcsGlyphMode,
+    color_mode: PtyColorMode,
+) -> Vec<PtyStyledCell> {
+    let mut out = Vec::with_capacity(cols as usize);
+    for col in 0..cols {
+        let cell = screen.cell(row, col);
+        let ch = cell
+            .and_then(|c| c.contents().chars().next())
+            .unwrap_or(' ');
+        let ch = if matches!(acs_mode, AcsGlyphMode::Unicode) {
+            smooth_ascii_border_char(screen, row, col, ch)
+        } else {
+            ch
+        };
+        let style = cell
+            .map(|c| vt100_style(c, color_mode))
+            .unwrap_or_else(|| vt100_default_style(color_mode));
+        out.push(PtyStyledCell {
+            ch,
+            fg: style.fg.unwrap_or(crate::config::current_theme_color()),
+            bg: style.bg.unwrap_or(Color::Black),
+            bold: style.add_modifier.contains(Modifier::BOLD),
+            italic: style.add_modifier.contains(Modifier::ITALIC),
+            underline: style.add_modifier.contains(Modifier::UNDERLINED),
+            reversed: style.add_modifier.contains(Modifier::REVERSED),
+        });
+    }
+    out
 }
 
 /// Build a styled cell snapshot from a locked parser.
-/// Called by the reader thread while holding the parser lock — guaranteed
-/// to see a consistent post-batch state.
 fn build_styled_snapshot(
     parser: &vt100::Parser,
     cols: u16,
@@ -644,43 +754,40 @@ fn build_styled_snapshot(
     color_mode: PtyColorMode,
 ) -> PtyStyledSnapshot {
     let screen = parser.screen();
-    let mut lines = Vec::with_capacity(rows as usize);
-    for row in 0..rows {
-        let mut out = Vec::with_capacity(cols as usize);
-        for col in 0..cols {
-            let cell = screen.cell(row, col);
-            let ch = cell
-                .and_then(|c| c.contents().chars().next())
-                .unwrap_or(' ');
-            let ch = if matches!(acs_mode, AcsGlyphMode::Unicode) {
-                smooth_ascii_border_char(screen, row, col, ch)
-            } else {
-                ch
-            };
-            let style = cell
-                .map(|c| vt100_style(c, color_mode))
-                .unwrap_or_else(|| vt100_default_style(color_mode));
-            out.push(PtyStyledCell {
-                ch,
-                fg: style.fg.unwrap_or(crate::config::current_theme_color()),
-                bg: style.bg.unwrap_or(Color::Black),
-                bold: style.add_modifier.contains(Modifier::BOLD),
-                italic: style.add_modifier.contains(Modifier::ITALIC),
-                underline: style.add_modifier.contains(Modifier::UNDERLINED),
-                reversed: style.add_modifier.contains(Modifier::REVERSED),
-            });
-        }
-        lines.push(out);
-    }
+    let cells = (0..rows)
+        .map(|row| build_styled_row(screen, row, cols, acs_mode, color_mode))
+        .collect();
     let (cursor_row, cursor_col) = screen.cursor_position();
     PtyStyledSnapshot {
-        cells: lines,
+        cells,
         cursor_row: cursor_row.min(rows.saturating_sub(1)),
         cursor_col: cursor_col.min(cols.saturating_sub(1)),
         cursor_hidden: screen.hide_cursor(),
     }
 }
 
+/// Plain text of one row, matching `Screen::rows` (trailing blanks trimmed).
+fn build_plain_line(screen: &vt100::Screen, row: u16, cols: u16) -> String {
+    let mut line = String::new();
+    let mut pending_blanks = 0usize;
+    for col in 0..cols {
+        let Some(cell) = screen.cell(row, col) else {
+            break;
+        };
+        if cell.is_wide_continuation() {
+            continue;
+        }
+        if cell.has_contents() {
+            line.extend(std::iter::repeat_n(' ', pending_blanks));
+            pending_blanks = 0;
+            line.push_str(&cell.contents());
+        } else {
+            pending_blanks += 1;
+        }
+    }
+    line
+}
+
 /// Build a plain text snapshot from a locked parser.
 fn build_plain_snapshot(parser: &vt100::Parser, cols: u16, rows: u16) -> PtyTextSnapshot {
     let screen = parser.screen();
@@ -697,6 +804,258 @@ fn build_plain_snapshot(parser: &vt100::Parser, cols: u16, rows: u16) -> PtyText
     }
 }
 
+/// Rows rebuilt by one [`FrameTracker::prepare`] call.  Built while the
+/// parser is locked, then applied to the shared frame under a short
+/// display lock.
+pub struct FrameUpdate {
+    cols: u16,
+    rows: u16,
+    epoch: u64,
+    resized: bool,
+    changed: Vec<(usize, Vec<PtyStyledCell>, String)>,
+    cursor_row: u16,
+    cursor_col: u16,
+    cursor_hidden: bool,
+}
+
+impl FrameUpdate {
+    /// Number of rows that were rebuilt.
+    pub fn changed_rows(&self) -> usize {
+        self.changed.len()
+    }
+
+    /// Write the rebuilt rows into `frame`.  Returns true when anything
+    /// visible (a row or the cursor) changed.
+    pub fn apply(self, frame: &mut CommittedFrame) -> bool {
+        if self.resized {
+            *frame = CommittedFrame::blank(self.cols, self.rows);
+        }
+        let cursor_moved = frame.styled.cursor_row != self.cursor_row
+            || frame.styled.cursor_col != self.cursor_col
+            || frame.styled.cursor_hidden != self.cursor_hidden;
+        let changed = self.resized || cursor_moved || !self.changed.is_empty();
+        for (row, cells, line) in self.changed {
+            frame.styled.cells[row] = cells;
+            frame.plain.lines[row] = line;
+            frame.row_epochs[row] = self.epoch;
+        }
+        frame.styled.cursor_row = self.cursor_row;
+        frame.styled.cursor_col = self.cursor_col;
+        frame.styled.cursor_hidden = self.cursor_hidden;
+        frame.plain.cursor_row = self.cursor_row;
+        frame.plain.cursor_col = self.cursor_col;
+        frame.plain.cursor_hidden = self.cursor_hidden;
+        if changed {
+            frame.epoch = self.epoch;
+        }
+        changed
+    }
+}
+
+/// Row-level damage tracking on top of the vt100 parser.  Keeps the screen
+/// as of the previous commit and uses `Screen::rows_diff` to find the rows
+/// a batch actually touched, so idle or mostly-static programs (htop's
+/// header, a shell prompt) only pay for the rows that moved.
+pub struct FrameTracker {
+    prev_screen: Option<vt100::Screen>,
+    prev_dims: (u16, u16),
+    epoch: u64,
+    acs_mode: AcsGlyphMode,
+    color_mode: PtyColorMode,
+}
+
+impl FrameTracker {
+    fn new(acs_mode: AcsGlyphMode, color_mode: PtyColorMode) -> Self {
+        Self {
+            prev_screen: None,
+            prev_dims: (0, 0),
+            epoch: 0,
+            acs_mode,
+            color_mode,
+        }
+    }
+
+    /// Tracker using the configured border glyph and color modes.
+    pub fn from_config() -> Self {
+        Self::new(AcsGlyphMode::from_config(), pty_color_mode())
+    }
+
+    /// Forget the previous screen so the next commit rebuilds every row.
+    pub fn invalidate(&mut self) {
+        self.prev_screen = None;
+    }
+
+    /// Collect the rows of `parser` that changed since the previous call.
+    pub fn prepare(&mut self, parser: &vt100::Parser, cols: u16, rows: u16) -> FrameUpdate {
+        let screen = parser.screen();
+        let resized = self.prev_dims != (cols, rows);
+        let mut dirty = vec![false; rows as usize];
+        match self.prev_screen.as_ref() {
+            Some(prev) if !resized && prev.size() == screen.size() => {
+                let (_, screen_cols) = screen.size();
+                for (row, diff) in screen
+                    .rows_diff(prev, 0, screen_cols)
+                    .take(rows as usize)
+                    .enumerate()
+                {
+                    dirty[row] = !diff.is_empty();
+                }
+            }
+            _ => dirty.iter_mut().for_each(|d| *d = true),
+        }
+        if matches!(self.acs_mode, AcsGlyphMode::Unicode) {
+            // Border smoothing looks one row up and down, so the neighbours
+            // of a changed row may render differently too.
+            let touched = dirty.clone();
+            for (row, was_dirty) in touched.into_iter().enumerate() {
+                if was_dirty {
+                    if row > 0 {
+                        dirty[row - 1] = true;
+                    }
+                    if row + 1 < dirty.len() {
+                        dirty[row + 1] = true;
+                    }
+                }
+            }
+        }
+        self.epoch += 1;
+        let changed = dirty
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| **d)
+            .map(|(row, _)| {
+                let row_u16 = row as u16;
+                (
+                    row,
+                    build_styled_row(screen, row_u16, cols, self.acs_mode, self.color_mode),
+                    build_plain_line(screen, row_u16, cols),
+                )
+            })
+            .collect();
+        let (cursor_row, cursor_col) = screen.cursor_position();
+        self.prev_screen = Some(screen.clone());
+        self.prev_dims = (cols, rows);
+        FrameUpdate {
+            cols,
+            rows,
+            epoch: self.epoch,
+            resized,
+            changed,
+            cursor_row: cursor_row.min(rows.saturating_sub(1)),
+            cursor_col: cursor_col.min(cols.saturating_sub(1)),
+            cursor_hidden: screen.hide_cursor(),
+        }
+    }
+}
+
+/// Interval between PTY cursor blink phases.
+pub const PTY_CURSOR_BLINK_INTERVAL: Duration = Duration::from_millis(530);
+
+fn cursor_blink_origin() -> Instant {
+    static ORIGIN: OnceLock<Instant> = OnceLock::new();
+    *ORIGIN.get_or_init(Instant::now)
+}
+
+/// Whether the PTY cursor is in its visible blink phase.  All sessions share
+/// one clock so multiple windows blink in step.
+pub fn cursor_blink_on() -> bool {
+    let elapsed = cursor_blink_origin().elapsed().as_millis();
+    (elapsed / PTY_CURSOR_BLINK_INTERVAL.as_millis()).is_multiple_of(2)
+}
+
+/// Time until the cursor blink phase flips.
+pub fn until_next_cursor_blink() -> Duration {
+    let interval = PTY_CURSOR_BLINK_INTERVAL.as_millis();
+    let elapsed = cursor_blink_origin().elapsed().as_millis();
+    Duration::from_millis((interval - elapsed % interval) as u64)
+}
+
+/// How often a loop showing a PTY wakes to look for output.  Waking is
+/// cheap; frames are only drawn when [`PtyRepaintGate`] says so.
+pub const PTY_OUTPUT_POLL_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Decides whether a wake-up of a loop showing a PTY needs a new frame:
+/// only when output arrived or the cursor blink phase flipped.
+#[derive(Debug, Clone, Copy)]
+pub struct PtyRepaintGate {
+    last_blink: bool,
+}
+
+impl Default for PtyRepaintGate {
+    fn default() -> Self {
+        Self::new(cursor_blink_on())
+    }
+}
+
+impl PtyRepaintGate {
+    pub fn new(blink_on: bool) -> Self {
+        Self {
+            last_blink: blink_on,
+        }
+    }
+
+    /// `output_activity` is what `PtySession::take_output_activity` returned
+    /// for this wake-up and `blink_on` the current [`cursor_blink_on`].
+    pub fn observe(&mut self, output_activity: bool, blink_on: bool) -> bool {
+        let blinked = blink_on != self.last_blink;
+        self.last_blink = blink_on;
+        output_activity || blinked
+    }
+}
+
+#[derive(Default)]
+struct RenderLineCache {
+    plain: bool,
+    width: u16,
+    lines: Vec<(u64, Line<'static>)>,
+}
+
+fn styled_cell_style(cell: &PtyStyledCell) -> Style {
+    let mut style = Style::default().fg(cell.fg).bg(cell.bg);
+    if cell.bold {
+        style = style.add_modifier(Modifier::BOLD);
+    }
+    if cell.italic {
+        style = style.add_modifier(Modifier::ITALIC);
+    }
+    if cell.underline {
+        style = style.add_modifier(Modifier::UNDERLINED);
+    }
+    if cell.reversed {
+        style = style.add_modifier(Modifier::REVERSED);
+    }
+    style
+}
+
+/// Shape one committed row into a ratatui line, merging runs of cells that
+/// share a style into a single span.
+fn shape_styled_line(cells: &[PtyStyledCell], width: usize) -> Line<'static> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut run = String::new();
+    let mut run_style: Option<Style> = None;
+    for cell in cells.iter().take(width) {
+        let style = styled_cell_style(cell);
+        if run_style.is_some_and(|s| s != style) {
+            spans.push(Span::styled(
+                std::mem::take(&mut run),
+                run_style.unwrap_or_default(),
+            ));
+        }
+        run_style = Some(style);
+        run.push(cell.ch);
+    }
+    if let Some(style) = run_style {
+        spans.push(Span::styled(run, style));
+    }
+    Line::from(spans)
+}
+
+impl Drop for PtySession {
+    fn drop(&mut self) {
+        self.unregister_process();
+    }
+}
+
 impl PtySession {
     pub fn spawn(
         program: &str,
@@ -720,6 +1079,9 @@ impl PtySession {
         for (key, value) in &options.env {
             cmd.env(key, value);
         }
+        if let Some(cwd) = &options.cwd {
+            cmd.cwd(cwd);
+        }
         let acs_mode = AcsGlyphMode::from_config();
         // Calcurse renders more reliably in Fixedsys/embedded PTY with ASCII ACS.
         if matches!(acs_mode, AcsGlyphMode::Ascii)
@@ -742,8 +1104,14 @@ impl PtySession {
         };
         let color_mode = pty_color_mode();
 
+        let mut transcript = match &options.transcript {
+            Some(path) => Some(Transcript::open(path)?),
+            None => None,
+        };
         let child = pair.slave.spawn_command(cmd)?;
-        let writer = pair.master.take_writer()?;
+        let writer: Arc<Mutex<Box<dyn Write + Send>>> =
+            Arc::new(Mutex::new(pair.master.take_writer()?));
+        let reader_writer = Arc::clone(&writer);
         let reader = pair.master.try_clone_reader()?;
 
         // Grab the master fd for poll()-based coalescing in the reader thread.
@@ -766,8 +1134,12 @@ impl PtySession {
         let shared_rows = Arc::new(AtomicU16::new(rows));
         let reader_cols = Arc::clone(&shared_cols);
         let reader_rows = Arc::clone(&shared_rows);
-        let reader_acs_mode = acs_mode;
-        let reader_color_mode = color_mode;
+        let output_waker: Arc<Mutex<Option<OutputWaker>>> = Arc::new(Mutex::new(None));
+        let reader_waker = Arc::clone(&output_waker);
+        let tracker = Arc::new(Mutex::new(FrameTracker::new(acs_mode, color_mode)));
+        let reader_tracker = Arc::clone(&tracker);
+        let kitty_flags = Arc::new(AtomicU8::new(0));
+        let reader_kitty_flags = Arc::clone(&kitty_flags);
 
         // Reader thread: pump PTY output into the vt100 parser continuously.
         // Uses poll()-based I/O coalescing to prevent mid-frame tearing from
@@ -784,12 +1156,33 @@ impl PtySession {
                     glyph_mode: acs_mode,
                     ..DecSpecialGraphics::default()
                 };
+                let mut kitty = KittyKeyboardState::default();
+                let mut negotiate = |bytes: &[u8]| {
+                    if let Some(transcript) = transcript.as_mut() {
+                        transcript.write(bytes);
+                    }
+                    let replies = kitty.process(bytes);
+                    reader_kitty_flags.store(kitty.flags(), Ordering::Relaxed);
+                    if !replies.is_empty() {
+                        if let Ok(mut writer) = reader_writer.lock() {
+                            let _ = writer.write_all(&replies);
+                            let _ = writer.flush();
+                        }
+                    }
+                };
+                let wake = || {
+                    let waker = reader_waker.lock().ok().and_then(|w| w.clone());
+                    if let Some(waker) = waker {
+                        waker();
+                    }
+                };
 
                 loop {
                     // Phase 1: blocking read — wait for first bytes
                     match std::io::Read::read(&mut reader, &mut buf) {
                         Ok(0) | Err(_) => break,
                         Ok(n) => {
+                            negotiate(&buf[..n]);
                             let mut all_bytes = dec_special.process(&buf[..n]);
 
                             // Phase 2: poll + read loop — coalesce any queued data
@@ -810,6 +1203,7 @@ impl PtySession {
                                         match std::io::Read::read(&mut reader, &mut buf) {
                                             Ok(0) | Err(_) => break,
                                             Ok(extra_n) => {
+                                                negotiate(&buf[..extra_n]);
                                                 let extra = dec_special.process(&buf[..extra_n]);
                                                 all_bytes.extend_from_slice(&extra);
                                             }
@@ -825,35 +1219,45 @@ impl PtySession {
                             }
                             if let Ok(mut p) = parser_clone.lock() {
                                 p.process(&all_bytes);
-                                // Build display frame while holding the parser lock.
-                                // This guarantees the snapshot is consistent — taken
-                                // after the full coalesced batch has been processed.
+                                // Collect damaged rows while holding the parser
+                                // lock so the update reflects the full coalesced
+                                // batch, then apply it under a short display lock.
                                 let snap_cols = reader_cols.load(Ordering::Relaxed);
                                 let snap_rows = reader_rows.load(Ordering::Relaxed);
-                                let frame = CommittedFrame {
-                                    styled: build_styled_snapshot(
-                                        &p,
-                                        snap_cols,
-                                        snap_rows,
-                                        reader_acs_mode,
-                                        reader_color_mode,
-                                    ),
-                                    plain: build_plain_snapshot(&p, snap_cols, snap_rows),
-                                    cols: snap_cols,
-                                    rows: snap_rows,
+                                let Ok(mut tracker) = reader_tracker.lock() else {
+                                    continue;
                                 };
+                                let update = tracker.prepare(&p, snap_cols, snap_rows);
+                                drop(tracker);
                                 drop(p); // release parser lock before display lock
-                                if let Ok(mut d) = display_clone.lock() {
-                                    *d = frame;
+                                let changed = display_clone
+                                    .lock()
+                                    .map(|mut d| update.apply(&mut d))
+                                    .unwrap_or(false);
+                                if changed {
+                                    output_epoch_clone.fetch_add(1, Ordering::Relaxed);
+                                    wake();
                                 }
-                                output_epoch_clone.fetch_add(1, Ordering::Relaxed);
                             }
                         }
                     }
                 }
+                // Child closed the PTY: wake the front end so it notices the exit.
+                output_epoch_clone.fetch_add(1, Ordering::Relaxed);
+                wake();
             })?;
 
+        let id = NEXT_PTY_SESSION_ID.fetch_add(1, Ordering::Relaxed);
+        if let Some(pid) = child.process_id() {
+            let label = std::iter::once(program)
+                .chain(args.iter().copied())
+                .collect::<Vec<_>>()
+                .join(" ");
+            register_owned_process(pid, OwnedProcessKind::Pty(id), &label);
+        }
+
         Ok(Self {
+            id,
             writer,
             parser,
             child,
@@ -863,6 +1267,8 @@ impl PtySession {
             color_mode,
             acs_mode,
             top_bar: options.top_bar.clone(),
+            key_options: options.keys,
+            kitty_flags,
             master: pair.master,
             output_epoch,
             last_seen_output_epoch: 0,
@@ -870,23 +1276,79 @@ impl PtySession {
             shared_cols,
             shared_rows,
             last_exit_status: None,
+            output_waker,
+            line_cache: Mutex::new(RenderLineCache::default()),
+            tracker,
         })
     }
 
+    /// Register a callback fired whenever the reader thread commits new
+    /// output (or the child closes the PTY).  GUI front ends use this to
+    /// request a repaint instead of redrawing on a fixed timer.
+    pub fn set_output_waker(&self, waker: OutputWaker) {
+        if let Ok(mut slot) = self.output_waker.lock() {
+            *slot = Some(waker);
+        }
+    }
+
+    /// True once an output waker has been registered.
+    pub fn has_output_waker(&self) -> bool {
+        self.output_waker
+            .lock()
+            .map(|slot| slot.is_some())
+            .unwrap_or(false)
+    }
+
+    pub fn id(&self) -> PtySessionId {
+        self.id
+    }
+
+    /// Deliver one unit of user input (typed text, paste or key press).
+    pub fn send_input(&mut self, input: &PtyInput) {
+        match input {
+            PtyInput::Text(text) => self.write(text.as_bytes()),
+            PtyInput::Paste(text) => self.send_paste(text),
+            PtyInput::Key { code, mods, keypad } => {
+                self.send_key_with_keypad(*code, *mods, *keypad)
+            }
+        }
+    }
+
     /// Send raw bytes to the child's stdin (keyboard input)
     pub fn write(&mut self, data: &[u8]) {
-        let _ = self.writer.write_all(data);
-        let _ = self.writer.flush();
+        if let Ok(mut writer) = self.writer.lock() {
+            let _ = writer.write_all(data);
+            let _ = writer.flush();
+        }
+    }
+
+    /// Kitty keyboard protocol flags the child has enabled (0 = legacy).
+    pub fn kitty_keyboard_flags(&self) -> u8 {
+        self.kitty_flags.load(Ordering::Relaxed)
+    }
+
+    /// Current key encoding: launch options plus the modes the child set.
+    pub fn key_encoding(&self) -> KeyEncoding {
+        let mut encoding = KeyEncoding::new(self.key_options);
+        if let Ok(parser) = self.parser.lock() {
+            let screen = parser.screen();
+            encoding.application_cursor = screen.application_cursor();
+            encoding.application_keypad |= screen.application_keypad();
+        }
+        encoding.kitty_flags = self.kitty_keyboard_flags();
+        encoding
     }
 
     /// Translate a terminal key event and send it to the PTY child.
     pub fn send_key(&mut self, code: KeyCode, mods: KeyModifiers) {
-        let application_cursor = self
-            .parser
-            .lock()
-            .map(|p| p.screen().application_cursor())
-            .unwrap_or(false);
-        if let Some(bytes) = key_to_bytes(code, mods, application_cursor) {
+        self.send_key_with_keypad(code, mods, false);
+    }
+
+    /// Like [`send_key`](Self::send_key), for keys that may come from the
+    /// numeric keypad.
+    pub fn send_key_with_keypad(&mut self, code: KeyCode, mods: KeyModifiers, keypad: bool) {
+        let encoding = self.key_encoding();
+        if let Some(bytes) = encode_key(code, mods, keypad, &encoding) {
             self.write(&bytes);
         }
     }
@@ -965,6 +1427,17 @@ impl PtySession {
         });
         if let Ok(mut p) = self.parser.lock() {
             p.set_size(rows, cols);
+            // Recommit at the new size right away so renderers never clip
+            // against a stale frame while waiting for the child to redraw.
+            if let Ok(mut tracker) = self.tracker.lock() {
+                let update = tracker.prepare(&p, cols, rows);
+                drop(tracker);
+                drop(p);
+                if let Ok(mut d) = self.display.lock() {
+                    update.apply(&mut d);
+                }
+                self.output_epoch.fetch_add(1, Ordering::Relaxed);
+            }
         }
     }
 
@@ -977,12 +1450,24 @@ impl PtySession {
             Ok(None) => true,
             Ok(Some(status)) => {
                 self.last_exit_status = Some(status);
+                self.unregister_process();
                 false
             }
             Err(_) => false,
         }
     }
 
+    /// Operating system pid of the child, when the platform reports one.
+    pub fn process_id(&self) -> Option<u32> {
+        self.child.process_id()
+    }
+
+    fn unregister_process(&self) {
+        if let Some(pid) = self.child.process_id() {
+            unregister_owned_process(pid);
+        }
+    }
+
     #[allow(dead_code)]
     pub fn exit_status(&mut self) -> Option<ExitStatus> {
         if self.last_exit_status.is_none() {
@@ -1008,8 +1493,15 @@ impl PtySession {
         self.top_bar.as_deref()
     }
 
+    /// Window title most recently set by the child through OSC 0 or OSC 2,
+    /// if any.
+    pub fn window_title(&self) -> Option<String> {
+        let parser = self.parser.lock().ok()?;
+        let title = parser.screen().title().trim();
+        (!title.is_empty()).then(|| title.to_string())
+    }
+
     /// Returns true if new PTY output arrived since the last check.
-    #[allow(dead_code)]
     pub fn take_output_activity(&mut self) -> bool {
         let epoch = self.output_epoch.load(Ordering::Relaxed);
         if epoch != self.last_seen_output_epoch {
@@ -1038,9 +1530,22 @@ impl PtySession {
             .clone()
     }
 
+    /// Bring a renderer-owned copy of the committed frame up to date,
+    /// copying only the rows whose epoch changed.  Returns the number of
+    /// rows copied, or `None` when the frame (and cursor) are unchanged.
+    pub fn sync_frame(&self, target: &mut CommittedFrame) -> Option<usize> {
+        let display = self.display.lock().unwrap_or_else(|e| e.into_inner());
+        target.sync_from(&display)
+    }
+
     /// Snapshot the current screen as plain text for non-ratatui renderers.
     #[allow(dead_code)]
     pub fn snapshot_plain(&self, cols: u16, rows: u16) -> PtyTextSnapshot {
+        if let Ok(display) = self.display.lock() {
+            if display.cols == cols && display.rows == rows {
+                return display.plain.clone();
+            }
+        }
         let Ok(parser) = self.parser.lock() else {
             return PtyTextSnapshot {
                 lines: vec![String::new(); rows as usize],
@@ -1049,22 +1554,18 @@ impl PtySession {
                 cursor_hidden: false,
             };
         };
-        let screen = parser.screen();
-        let mut lines: Vec<String> = screen.rows(0, cols).take(rows as usize).collect();
-        while lines.len() < rows as usize {
-            lines.push(String::new());
-        }
-        let (cursor_row, cursor_col) = screen.cursor_position();
-        PtyTextSnapshot {
-            lines,
-            cursor_row: cursor_row.min(rows.saturating_sub(1)),
-            cursor_col: cursor_col.min(cols.saturating_sub(1)),
-            cursor_hidden: screen.hide_cursor(),
-        }
+        build_plain_snapshot(&parser, cols, rows)
     }
 
+    /// Styled snapshot of the screen.  Served from the committed frame when
+    /// the size matches, so it no longer walks every parser cell per call.
     #[allow(dead_code)]
     pub fn snapshot_styled(&self, cols: u16, rows: u16) -> PtyStyledSnapshot {
+        if let Ok(display) = self.display.lock() {
+            if display.cols == cols && display.rows == rows {
+                return display.styled.clone();
+            }
+        }
         let Ok(parser) = self.parser.lock() else {
             return PtyStyledSnapshot {
                 cells: vec![vec![]; rows as usize],
@@ -1073,46 +1574,12 @@ impl PtySession {
                 cursor_hidden: false,
             };
         };
-        let screen = parser.screen();
-        let mut lines = Vec::with_capacity(rows as usize);
-        for row in 0..rows {
-            let mut out = Vec::with_capacity(cols as usize);
-            for col in 0..cols {
-                let cell = screen.cell(row, col);
-                let ch = cell
-                    .and_then(|c| c.contents().chars().next())
-                    .unwrap_or(' ');
-                let ch = if matches!(self.acs_mode, AcsGlyphMode::Unicode) {
-                    smooth_ascii_border_char(screen, row, col, ch)
-                } else {
-                    ch
-                };
-                let style = cell
-                    .map(|c| vt100_style(c, self.color_mode))
-                    .unwrap_or_else(|| vt100_default_style(self.color_mode));
-                out.push(PtyStyledCell {
-                    ch,
-                    fg: style.fg.unwrap_or(crate::config::current_theme_color()),
-                    bg: style.bg.unwrap_or(Color::Black),
-                    bold: style.add_modifier.contains(Modifier::BOLD),
-                    italic: style.add_modifier.contains(Modifier::ITALIC),
-                    underline: style.add_modifier.contains(Modifier::UNDERLINED),
-                    reversed: style.add_modifier.contains(Modifier::REVERSED),
-                });
-            }
-            lines.push(out);
-        }
-        let (cursor_row, cursor_col) = screen.cursor_position();
-        PtyStyledSnapshot {
-            cells: lines,
-            cursor_row: cursor_row.min(rows.saturating_sub(1)),
-            cursor_col: cursor_col.min(cols.saturating_sub(1)),
-            cursor_hidden: screen.hide_cursor(),
-        }
+        build_styled_snapshot(&parser, cols, rows, self.acs_mode, self.color_mode)
     }
 
     /// Force-stop the PTY child process and release its resources.
     pub fn terminate(&mut self) {
+        self.unregister_process();
         if matches!(self.child.try_wait(), Ok(None)) {
             let _ = self.child.kill();
             self.last_exit_status = self.child.try_wait().ok().flatten();
@@ -1127,56 +1594,116 @@ impl PtySession {
     /// Render with a caller-provided performance hint.
     /// `force_plain=true` bypasses per-cell styling and uses faster line rendering.
     pub fn render_with_hint(&self, f: &mut ratatui::Frame, area: Rect, force_plain: bool) {
-        let Ok(parser) = self.parser.lock() else {
+        self.render_with_cursor(f, area, force_plain, false);
+    }
+
+    /// Render the committed frame, reusing shaped rows whose epoch has not
+    /// changed since the last call.  `show_cursor` paints the PTY cursor as a
+    /// reversed cell (callers pass the blink phase).
+    pub fn render_with_cursor(
+        &self,
+        f: &mut ratatui::Frame,
+        area: Rect,
+        force_plain: bool,
+        show_cursor: bool,
+    ) {
+        let plain = force_plain || matches!(self.render_mode, PtyRenderMode::Plain);
+        let Ok(display) = self.display.lock() else {
             return;
         };
-        let screen = parser.screen();
-
-        let rows = area.height as usize;
-        let cols = area.width as usize;
-
-        if force_plain || matches!(self.render_mode, PtyRenderMode::Plain) {
-            let mut lines: Vec<Line> = screen
-                .rows(0, area.width)
-                .take(rows)
-                .map(Line::from)
-                .collect();
-            while lines.len() < rows {
-                lines.push(Line::from(""));
-            }
-            let para = Paragraph::new(lines).style(vt100_default_style(self.color_mode));
-            f.render_widget(para, area);
+        let Ok(mut cache) = self.line_cache.lock() else {
             return;
+        };
+        if cache.plain != plain || cache.width != area.width {
+            cache.lines.clear();
+            cache.plain = plain;
+            cache.width = area.width;
         }
-
-        let lines: Vec<Line> = (0..rows)
-            .map(|row| {
-                let spans: Vec<Span> = (0..cols)
-                    .map(|col| {
-                        let row_u16 = row as u16;
-                        let col_u16 = col as u16;
-                        let cell = screen.cell(row_u16, col_u16);
-                        let ch = cell
-                            .and_then(|c| c.contents().chars().next())
-                            .unwrap_or(' ');
-                        let ch = if matches!(self.acs_mode, AcsGlyphMode::Unicode) {
-                            smooth_ascii_border_char(screen, row_u16, col_u16, ch)
-                        } else {
-                            ch
-                        };
-                        let text = ch.to_string();
-
-                        let style = cell
-                            .map(|c| vt100_style(c, self.color_mode))
-                            .unwrap_or_else(|| vt100_default_style(self.color_mode));
-                        Span::styled(text, style)
-                    })
-                    .collect();
-                Line::from(spans)
-            })
-            .collect();
-
-        f.render_widget(Paragraph::new(lines), area);
+        let rows = (area.height as usize).min(display.rows as usize);
+        cache.lines.resize(rows, (u64::MAX, Line::default()));
+        for row in 0..rows {
+            let epoch = display.row_epochs[row];
+            if cache.lines[row].0 == epoch {
+                continue;
+            }
+            let line = if plain {
+                Line::from(display.plain.lines[row].clone())
+            } else {
+                shape_styled_line(&display.styled.cells[row], area.width as usize)
+            };
+            cache.lines[row] = (epoch, line);
+        }
+        let mut lines: Vec<Line> = cache.lines.iter().map(|(_, line)| line.clone()).collect();
+        drop(cache);
+        let snap = &display.styled;
+        if show_cursor && !snap.cursor_hidden && (snap.cursor_row as usize) < rows {
+            let row = snap.cursor_row as usize;
+            let col = snap.cursor_col as usize;
+            if col < area.width as usize {
+                let cell = snap.cells[row].get(col).copied();
+                let ch = cell.map(|c| c.ch).unwrap_or(' ');
+                let style = cell
+                    .map(|c| styled_cell_style(&c))
+                    .unwrap_or_else(|| vt100_default_style(self.color_mode));
+                lines[row] = cursor_line(
+                    &snap.cells[row],
+                    &display.plain.lines[row],
+                    plain,
+                    col,
+                    ch,
+                    style,
+                    area.width as usize,
+                );
+            }
+        }
+        drop(display);
+        while lines.len() < area.height as usize {
+            lines.push(Line::from(""));
+        }
+        let para = Paragraph::new(lines);
+        let para = if plain {
+            para.style(vt100_default_style(self.color_mode))
+        } else {
+            para
+        };
+        f.render_widget(para, area);
+    }
+}
+
+/// Rebuild the cursor row with the cursor cell reversed.  Only the one row
+/// is reshaped; the cached copy stays cursor-free.
+fn cursor_line(
+    cells: &[PtyStyledCell],
+    plain_line: &str,
+    plain: bool,
+    col: usize,
+    ch: char,
+    style: Style,
+    width: usize,
+) -> Line<'static> {
+    if plain {
+        let mut chars: Vec<char> = plain_line.chars().collect();
+        chars.resize(width.max(chars.len()), ' ');
+        let before: String = chars[..col].iter().collect();
+        let after: String = chars.get(col + 1..).unwrap_or(&[]).iter().collect();
+        Line::from(vec![
+            Span::raw(before),
+            Span::styled(
+                ch.to_string(),
+                Style::default().add_modifier(Modifier::REVERSED),
+            ),
+            Span::raw(after),
+        ])
+    } else {
+        let left = shape_styled_line(&cells[..col.min(cells.len())], width);
+        let right = shape_styled_line(cells.get(col + 1..).unwrap_or(&[]), width);
+        let mut spans = left.spans;
+        spans.push(Span::styled(
+            ch.to_string(),
+            style.add_modifier(Modifier::REVERSED),
+        ));
+        spans.extend(right.spans);
+        Line::from(spans)
     }
 }
 
@@ -1678,81 +2205,17 @@ fn mouse_to_bytes(
     }
 }
 
+/// Legacy xterm encoding with default launch options; see [`encode_key`].
 pub fn key_to_bytes(
     code: KeyCode,
     mods: KeyModifiers,
     application_cursor: bool,
 ) -> Option<Vec<u8>> {
-    // Ctrl+<letter>
-    if mods.contains(KeyModifiers::CONTROL) {
-        if let KeyCode::Char(c) = code {
-            let lc = c.to_ascii_lowercase();
-            let byte = (lc as u8).wrapping_sub(b'a').wrapping_add(1);
-            if byte < 32 {
-                return Some(vec![byte]);
-            }
-        }
-    }
-
-    Some(match code {
-        KeyCode::Char(c) => c.to_string().into_bytes(),
-        KeyCode::Enter => b"\r".to_vec(),
-        KeyCode::Backspace => b"\x7f".to_vec(),
-        KeyCode::Tab => b"\t".to_vec(),
-        KeyCode::Esc => b"\x1b".to_vec(),
-        KeyCode::Up => {
-            if application_cursor {
-                b"\x1bOA".to_vec()
-            } else {
-                b"\x1b[A".to_vec()
-            }
-        }
-        KeyCode::Down => {
-            if application_cursor {
-                b"\x1bOB".to_vec()
-            } else {
-                b"\x1b[B".to_vec()
-            }
-        }
-        KeyCode::Right => {
-            if application_cursor {
-                b"\x1bOC".to_vec()
-            } else {
-                b"\x1b[C".to_vec()
-            }
-        }
-        KeyCode::Left => {
-            if application_cursor {
-                b"\x1bOD".to_vec()
-            } else {
-                b"\x1b[D".to_vec()
-            }
-        }
-        KeyCode::Home => {
-            if application_cursor {
-                b"\x1bOH".to_vec()
-            } else {
-                b"\x1b[H".to_vec()
-            }
-        }
-        KeyCode::End => {
-            if application_cursor {
-                b"\x1bOF".to_vec()
-            } else {
-                b"\x1b[F".to_vec()
-            }
-        }
-        KeyCode::PageUp => b"\x1b[5~".to_vec(),
-        KeyCode::PageDown => b"\x1b[6~".to_vec(),
-        KeyCode::Delete => b"\x1b[3~".to_vec(),
-        KeyCode::Insert => b"\x1b[2~".to_vec(),
-        KeyCode::F(1) => b"\x1bOP".to_vec(),
-        KeyCode::F(2) => b"\x1bOQ".to_vec(),
-        KeyCode::F(3) => b"\x1bOR".to_vec(),
-        KeyCode::F(4) => b"\x1bOS".to_vec(),
-        KeyCode::F(n) => format!("\x1b[{}~", n + 10).into_bytes(),
-        _ => return None,
-    })
+    let encoding = KeyEncoding {
+        application_cursor,
+        ..KeyEncoding::default()
+    };
+    encode_key(code, mods, false, &encoding)
 }
 
 // ── Interactive run loop ──────────────────────────────────────────────────────
@@ -1804,6 +2267,18 @@ pub fn run_pty_session_with_options(
 
 /// Convenience wrapper: launch an arbitrary command in a PTY session.
 pub fn launch_in_pty(terminal: &mut Term, cmd: &[String]) -> Result<()> {
+    launch_in_pty_with_environment(terminal, cmd, &LaunchEnvironment::default(), None)
+}
+
+/// Launch a command in a PTY session with a catalog entry's environment,
+/// working directory and extra arguments layered over its PTY profile's.
+/// `preferred_profile` overrides the profile picked by program name.
+pub fn launch_in_pty_with_environment(
+    terminal: &mut Term,
+    cmd: &[String],
+    launch: &LaunchEnvironment,
+    preferred_profile: Option<&str>,
+) -> Result<()> {
     if cmd.is_empty() {
         return Ok(());
     }
@@ -1811,6 +2286,16 @@ pub fn launch_in_pty(terminal: &mut Term, cmd: &[String]) -> Result<()> {
     if cmd.is_empty() {
         return Ok(());
     }
+    let profile = crate::config::get_settings()
+        .desktop_cli_profiles
+        .for_entry(&cmd, preferred_profile);
+    let resolved = resolve_launch_environment(&profile.launch.layered(launch));
+    let cmd: Vec<String> = cmd.into_iter().chain(resolved.args).collect();
+    let options = PtyLaunchOptions {
+        env: resolved.env,
+        cwd: resolved.cwd,
+        ..PtyLaunchOptions::default()
+    };
     let cmdline = cmd.join(" ");
     if crate::launcher::is_shell_preferred(&cmd) {
         if let Some(shell_cmd) = crate::launcher::build_shell_fallback_command(&cmd) {
@@ -1820,7 +2305,7 @@ pub fn launch_in_pty(terminal: &mut Term, cmd: &[String]) -> Result<()> {
                 "pty-cli",
                 &format!("Using saved shell launch preference for command: {cmdline}"),
             );
-            return run_pty_session(terminal, shell_program, &shell_args);
+            return run_pty_session_with_options(terminal, shell_program, &shell_args, options);
         }
     }
     let program = &cmd[0];
@@ -1830,7 +2315,7 @@ pub fn launch_in_pty(terminal: &mut Term, cmd: &[String]) -> Result<()> {
         &format!("Launching command directly in PTY: {cmdline}"),
     );
     let started = Instant::now();
-    run_pty_session(terminal, program, &args)?;
+    run_pty_session_with_options(terminal, program, &args, options.clone())?;
     let elapsed = started.elapsed();
 
     if crate::launcher::should_retry_with_shell_after_fast_exit(&cmd, elapsed) {
@@ -1841,7 +2326,8 @@ pub fn launch_in_pty(terminal: &mut Term, cmd: &[String]) -> Result<()> {
                 "pty-cli",
                 &format!("Fast-exit retry via shell for command: {cmdline}"),
             );
-            let retried = run_pty_session(terminal, shell_program, &shell_args);
+            let retried =
+                run_pty_session_with_options(terminal, shell_program, &shell_args, options);
             if retried.is_ok() {
                 crate::launcher::remember_shell_preferred(&cmd);
             }
@@ -1871,8 +2357,15 @@ pub fn resume_suspended_for_active(terminal: &mut Term) -> Result<bool> {
     Ok(true)
 }
 
+/// The status bar shows a clock, so redraw at least this often even when the
+/// PTY is idle.
+const PTY_STATUS_REFRESH: Duration = Duration::from_secs(1);
+
 fn run_pty_loop(terminal: &mut Term, session: &mut PtySession) -> Result<PtyLoopOutcome> {
     let mut tilde_state = TildeChordState::None;
+    let mut needs_draw = true;
+    let mut last_draw = Instant::now();
+    let mut repaint = PtyRepaintGate::default();
 
     loop {
         maybe_flush_expired_tilde_state(&mut tilde_state, session);
@@ -1881,41 +2374,55 @@ fn run_pty_loop(terminal: &mut Term, session: &mut PtySession) -> Result<PtyLoop
         let sz = terminal.size()?;
         let pr = pty_content_rows(sz.height, session.top_bar.is_some());
         let pc = sz.width;
-        session.resize(pc, pr);
-
-        // Render
-        terminal.draw(|f| {
-            let area = f.area();
-            let show_top_bar = session.top_bar.is_some() && area.height > 1;
-            let top_h = if show_top_bar { 1 } else { 0 };
-            let pty_area = Rect {
-                x: 0,
-                y: top_h,
-                width: area.width,
-                height: pty_content_rows(area.height, show_top_bar),
-            };
-            let status_area = Rect {
-                x: 0,
-                y: area.height.saturating_sub(1),
-                width: area.width,
-                height: 1,
-            };
+        if (pc, pr) != (session.cols, session.rows) {
+            session.resize(pc, pr);
+            needs_draw = true;
+        }
+        if repaint.observe(session.take_output_activity(), cursor_blink_on()) {
+            needs_draw = true;
+        }
+        if last_draw.elapsed() >= PTY_STATUS_REFRESH {
+            needs_draw = true;
+        }
 
-            if let Some(label) = session.top_bar.as_deref() {
-                render_top_bar(
-                    f,
-                    Rect {
-                        x: 0,
-                        y: 0,
-                        width: area.width,
-                        height: 1,
-                    },
-                    label,
-                );
-            }
-            session.render(f, pty_area);
-            render_status_bar(f, status_area);
-        })?;
+        // Render only when something visible changed; idle programs such as
+        // an unchanging htop screen cost a poll() and nothing else.
+        if needs_draw {
+            needs_draw = false;
+            last_draw = Instant::now();
+            terminal.draw(|f| {
+                let area = f.area();
+                let show_top_bar = session.top_bar.is_some() && area.height > 1;
+                let top_h = if show_top_bar { 1 } else { 0 };
+                let pty_area = Rect {
+                    x: 0,
+                    y: top_h,
+                    width: area.width,
+                    height: pty_content_rows(area.height, show_top_bar),
+                };
+                let status_area = Rect {
+                    x: 0,
+                    y: area.height.saturating_sub(1),
+                    width: area.width,
+                    height: 1,
+                };
+
+                if let Some(label) = session.top_bar.as_deref() {
+                    render_top_bar(
+                        f,
+                        Rect {
+                            x: 0,
+                            y: 0,
+                            width: area.width,
+                            height: 1,
+                        },
+                        label,
+                    );
+                }
+                session.render_with_cursor(f, pty_area, false, cursor_blink_on());
+                render_status_bar(f, status_area);
+            })?;
+        }
 
         // Check if child exited
         if !session.is_alive() {
@@ -1929,6 +2436,7 @@ fn run_pty_loop(terminal: &mut Term, session: &mut PtySession) -> Result<PtyLoop
                 if key.kind == KeyEventKind::Release {
                     continue;
                 }
+                needs_draw = true;
 
                 if !matches!(tilde_state, TildeChordState::None)
                     && !matches!(key.code, KeyCode::Char('~') | KeyCode::Char('1'..='9'))
@@ -1949,14 +2457,8 @@ fn run_pty_loop(terminal: &mut Term, session: &mut PtySession) -> Result<PtyLoop
                     }
                     continue;
                 }
-                let application_cursor = session
-                    .parser
-                    .lock()
-                    .map(|p| p.screen().application_cursor())
-                    .unwrap_or(false);
-                if let Some(bytes) = key_to_bytes(key.code, key.modifiers, application_cursor) {
-                    session.write(&bytes);
-                }
+                let keypad = key.state.contains(KeyEventState::KEYPAD);
+                session.send_key_with_keypad(key.code, key.modifiers, keypad);
             }
         }
     }
@@ -1966,7 +2468,8 @@ fn run_pty_loop(terminal: &mut Term, session: &mut PtySession) -> Result<PtyLoop
 mod tests {
     use super::{
         format_paste_bytes, key_to_bytes, mouse_to_bytes, needs_ncurses_ascii_acs,
-        smooth_ascii_border_char, DecSpecialGraphics,
+        shape_styled_line, smooth_ascii_border_char, AcsGlyphMode, CommittedFrame,
+        DecSpecialGraphics, FrameTracker, PtyColorMode, PtyRepaintGate,
     };
     use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEventKind};
     use vt100::{MouseProtocolEncoding, MouseProtocolMode};
@@ -2096,4 +2599,104 @@ mod tests {
             b"\x1b[200~abc\n\x1b[201~".to_vec()
         );
     }
+
+    fn tracked_frame(tracker: &mut FrameTracker, p: &vt100::Parser) -> (CommittedFrame, usize) {
+        let mut frame = CommittedFrame::blank(20, 4);
+        let update = tracker.prepare(p, 20, 4);
+        let changed = update.changed_rows();
+        update.apply(&mut frame);
+        (frame, changed)
+    }
+
+    #[test]
+    fn frame_tracker_rebuilds_only_damaged_rows() {
+        let mut tracker = FrameTracker::new(AcsGlyphMode::Ascii, PtyColorMode::Monochrome);
+        let mut p = vt100::Parser::new(4, 20, 0);
+        p.process(b"top\r\nmiddle\r\nbottom");
+        let (mut frame, first) = tracked_frame(&mut tracker, &p);
+        assert_eq!(first, 4, "first commit rebuilds every row");
+        assert_eq!(frame.plain.lines[1], "middle");
+
+        p.process(b"\x1b[2;1Hmiddle");
+        let update = tracker.prepare(&p, 20, 4);
+        assert_eq!(
+            update.changed_rows(),
+            0,
+            "rewriting identical text is not damage"
+        );
+        update.apply(&mut frame);
+
+        let before = frame.row_epochs.clone();
+        p.process(b"\x1b[3;1HBOTTOM");
+        let update = tracker.prepare(&p, 20, 4);
+        assert_eq!(update.changed_rows(), 1);
+        assert!(update.apply(&mut frame));
+        assert_eq!(frame.plain.lines[2], "BOTTOM");
+        assert_eq!(frame.row_epochs[0], before[0]);
+        assert_ne!(frame.row_epochs[2], before[2]);
+    }
+
+    #[test]
+    fn frame_tracker_unicode_mode_rebuilds_border_neighbours() {
+        let mut tracker = FrameTracker::new(AcsGlyphMode::Unicode, PtyColorMode::Monochrome);
+        let mut p = vt100::Parser::new(4, 20, 0);
+        p.process(b"+--+\r\n|  |\r\n+--+");
+        let (mut frame, _) = tracked_frame(&mut tracker, &p);
+        p.process(b"\x1b[2;2Hx");
+        let update = tracker.prepare(&p, 20, 4);
+        assert_eq!(update.changed_rows(), 3);
+        update.apply(&mut frame);
+        assert_eq!(frame.styled.cells[1][1].ch, 'x');
+    }
+
+    #[test]
+    fn frame_tracker_resize_rebuilds_everything() {
+        let mut tracker = FrameTracker::new(AcsGlyphMode::Ascii, PtyColorMode::Monochrome);
+        let mut p = vt100::Parser::new(4, 20, 0);
+        p.process(b"hello");
+        let (mut frame, _) = tracked_frame(&mut tracker, &p);
+        p.set_size(6, 30);
+        let update = tracker.prepare(&p, 30, 6);
+        assert_eq!(update.changed_rows(), 6);
+        update.apply(&mut frame);
+        assert_eq!((frame.cols, frame.rows), (30, 6));
+        assert_eq!(frame.row_epochs.len(), 6);
+    }
+
+    #[test]
+    fn repaint_gate_passes_only_output_and_blink_flips() {
+        let mut gate = PtyRepaintGate::new(true);
+        assert!(!gate.observe(false, true));
+        assert!(gate.observe(true, true));
+        assert!(gate.observe(false, false));
+        assert!(!gate.observe(false, false));
+        assert!(gate.observe(false, true));
+    }
+
+    #[test]
+    fn sync_from_copies_only_changed_rows() {
+        let mut tracker = FrameTracker::new(AcsGlyphMode::Ascii, PtyColorMode::Monochrome);
+        let mut p = vt100::Parser::new(4, 20, 0);
+        p.process(b"a\r\nb\r\nc\r\nd");
+        let (mut source, _) = tracked_frame(&mut tracker, &p);
+        let mut mirror = CommittedFrame::blank(20, 4);
+        assert_eq!(mirror.sync_from(&source), Some(4));
+        assert_eq!(mirror.sync_from(&source), None);
+
+        p.process(b"\x1b[4;1HD");
+        tracker.prepare(&p, 20, 4).apply(&mut source);
+        assert_eq!(mirror.sync_from(&source), Some(1));
+        assert_eq!(mirror.plain.lines[3], "D");
+    }
+
+    #[test]
+    fn shaped_lines_merge_runs_with_equal_style() {
+        let mut tracker = FrameTracker::new(AcsGlyphMode::Ascii, PtyColorMode::Ansi);
+        let mut p = vt100::Parser::new(4, 20, 0);
+        p.process(b"ab\x1b[31mcd\x1b[0mef");
+        let (frame, _) = tracked_frame(&mut tracker, &p);
+        let line = shape_styled_line(&frame.styled.cells[0], 6);
+        let texts: Vec<&str> = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(texts, vec!["ab", "cd", "ef"]);
+    }
 }
@@ -0,0 +1,147 @@
+//! Input broadcast — mirror what is typed into one PTY session to others.
+//!
+//! Sessions are addressed by [`PtySessionId`], which stays the same while a
+//! session moves between tabs, panes and parked desktop sessions. Keys are
+//! mirrored as key events rather than bytes so every target encodes them with
+//! its own negotiated modes (DECCKM, kitty keyboard, bracketed paste).
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::collections::HashSet;
+
+/// Process-unique identifier of a [`PtySession`](super::PtySession).
+pub type PtySessionId = u64;
+
+/// One unit of user input delivered to a PTY session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PtyInput {
+    /// Text typed or composed by the input method, written as-is.
+    Text(String),
+    /// Clipboard paste; bracketed when the target asked for it.
+    Paste(String),
+    /// Key press, encoded by the receiving session.
+    Key {
+        code: KeyCode,
+        mods: KeyModifiers,
+        keypad: bool,
+    },
+}
+
+impl PtyInput {
+    pub fn key(code: KeyCode, mods: KeyModifiers) -> Self {
+        Self::Key {
+            code,
+            mods,
+            keypad: false,
+        }
+    }
+}
+
+/// Broadcast mode: when enabled, input sent to one participating session is
+/// mirrored to every other live session that has not opted out.
+#[derive(Debug, Clone, Default)]
+pub struct PtyBroadcast {
+    enabled: bool,
+    opted_out: HashSet<PtySessionId>,
+}
+
+impl PtyBroadcast {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Flips broadcast mode and returns the new state.
+    pub fn toggle(&mut self) -> bool {
+        self.enabled = !self.enabled;
+        self.enabled
+    }
+
+    pub fn is_opted_out(&self, id: PtySessionId) -> bool {
+        self.opted_out.contains(&id)
+    }
+
+    pub fn set_opted_out(&mut self, id: PtySessionId, opted_out: bool) {
+        if opted_out {
+            self.opted_out.insert(id);
+        } else {
+            self.opted_out.remove(&id);
+        }
+    }
+
+    /// Flips the opt-out of one session and returns whether it now takes part.
+    pub fn toggle_opt_out(&mut self, id: PtySessionId) -> bool {
+        let opted_out = !self.is_opted_out(id);
+        self.set_opted_out(id, opted_out);
+        !opted_out
+    }
+
+    /// True when broadcast is on and `id` has not opted out.
+    pub fn includes(&self, id: PtySessionId) -> bool {
+        self.enabled && !self.is_opted_out(id)
+    }
+
+    /// Forgets opt-outs of sessions that no longer exist.
+    pub fn retain_sessions(&mut self, live: impl IntoIterator<Item = PtySessionId>) {
+        let live: HashSet<PtySessionId> = live.into_iter().collect();
+        self.opted_out.retain(|id| live.contains(id));
+    }
+
+    /// Sessions that should receive a copy of input typed into `source`.
+    /// Empty when broadcast is off or the source itself opted out.
+    pub fn mirror_targets(
+        &self,
+        source: PtySessionId,
+        candidates: impl IntoIterator<Item = PtySessionId>,
+    ) -> Vec<PtySessionId> {
+        if !self.includes(source) {
+            return Vec::new();
+        }
+        candidates
+            .into_iter()
+            .filter(|id| *id != source && self.includes(*id))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_broadcast_mirrors_nothing() {
+        let broadcast = PtyBroadcast::default();
+        assert!(broadcast.mirror_targets(1, [1, 2, 3]).is_empty());
+    }
+
+    #[test]
+    fn mirror_targets_skip_source_and_opted_out_sessions() {
+        let mut broadcast = PtyBroadcast::default();
+        broadcast.set_enabled(true);
+        assert!(!broadcast.toggle_opt_out(3));
+        assert_eq!(broadcast.mirror_targets(1, [1, 2, 3, 4]), vec![2, 4]);
+        assert!(broadcast.toggle_opt_out(3));
+        assert_eq!(broadcast.mirror_targets(1, [1, 2, 3]), vec![2, 3]);
+    }
+
+    #[test]
+    fn opted_out_source_does_not_broadcast() {
+        let mut broadcast = PtyBroadcast::default();
+        broadcast.toggle();
+        broadcast.set_opted_out(1, true);
+        assert!(broadcast.mirror_targets(1, [1, 2]).is_empty());
+        assert_eq!(broadcast.mirror_targets(2, [1, 2, 3]), vec![3]);
+    }
+
+    #[test]
+    fn retain_sessions_drops_stale_opt_outs() {
+        let mut broadcast = PtyBroadcast::default();
+        broadcast.set_opted_out(1, true);
+        broadcast.set_opted_out(2, true);
+        broadcast.retain_sessions([2]);
+        assert!(!broadcast.is_opted_out(1));
+        assert!(broadcast.is_opted_out(2));
+    }
+}
@@ -0,0 +1,725 @@
+//! Keyboard encoding for PTY children.
+//!
+//! Keys are encoded as legacy xterm sequences unless the child negotiated the
+//! kitty keyboard protocol (`CSI > flags u`), in which case the progressive
+//! enhancement flags it pushed decide how keys are reported.
+
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::config::{DesktopPtyProfileSettings, PtyBackspaceMode};
+
+/// Kitty progressive enhancement flags.
+pub const KITTY_DISAMBIGUATE: u8 = 0b1;
+pub const KITTY_REPORT_EVENT_TYPES: u8 = 0b10;
+pub const KITTY_REPORT_ALTERNATE_KEYS: u8 = 0b100;
+pub const KITTY_REPORT_ALL_KEYS: u8 = 0b1000;
+pub const KITTY_REPORT_TEXT: u8 = 0b1_0000;
+
+/// Flags we can honour. Only key presses reach the PTY, so event types,
+/// alternate keys and associated text are never reported.
+pub const KITTY_SUPPORTED_FLAGS: u8 = KITTY_DISAMBIGUATE | KITTY_REPORT_ALL_KEYS;
+
+/// Kitty caps each screen's flag stack; older entries are evicted first.
+const KITTY_STACK_LIMIT: usize = 16;
+
+/// Per-launch keyboard options, usually taken from the PTY profile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PtyKeyOptions {
+    pub alt_sends_escape: bool,
+    pub backspace: PtyBackspaceMode,
+    pub application_keypad: bool,
+}
+
+impl From<&DesktopPtyProfileSettings> for PtyKeyOptions {
+    fn from(profile: &DesktopPtyProfileSettings) -> Self {
+        Self {
+            alt_sends_escape: profile.alt_sends_escape,
+            backspace: profile.backspace,
+            application_keypad: profile.application_keypad,
+        }
+    }
+}
+
+/// Everything that decides how a single key press is encoded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KeyEncoding {
+    pub application_cursor: bool,
+    pub application_keypad: bool,
+    pub kitty_flags: u8,
+    pub alt_sends_escape: bool,
+    pub backspace: PtyBackspaceMode,
+}
+
+impl KeyEncoding {
+    pub fn new(options: PtyKeyOptions) -> Self {
+        Self {
+            application_keypad: options.application_keypad,
+            alt_sends_escape: options.alt_sends_escape,
+            backspace: options.backspace,
+            ..Self::default()
+        }
+    }
+}
+
+/// Encodes a key press. `keypad` marks keys that came from the numeric
+/// keypad (digits, operators, Enter).
+pub fn encode_key(
+    code: KeyCode,
+    mods: KeyModifiers,
+    keypad: bool,
+    encoding: &KeyEncoding,
+) -> Option<Vec<u8>> {
+    let kitty = encoding.kitty_flags & KITTY_SUPPORTED_FLAGS;
+    if kitty != 0 {
+        if let Some(bytes) = encode_kitty(code, mods, keypad, kitty, encoding) {
+            return Some(bytes);
+        }
+    }
+    encode_legacy(code, mods, keypad, encoding)
+}
+
+/// xterm modifier parameter: 1 + shift(1) + alt(2) + ctrl(4) + super(8) +
+/// hyper(16) + meta(32).
+fn modifier_param(mods: KeyModifiers) -> u8 {
+    let mut bits = 0;
+    for (flag, bit) in [
+        (KeyModifiers::SHIFT, 1),
+        (KeyModifiers::ALT, 2),
+        (KeyModifiers::CONTROL, 4),
+        (KeyModifiers::SUPER, 8),
+        (KeyModifiers::HYPER, 16),
+        (KeyModifiers::META, 32),
+    ] {
+        if mods.contains(flag) {
+            bits |= bit;
+        }
+    }
+    1 + bits
+}
+
+/// How a functional key is spelled: `CSI 1;m X` / `SS3 X` letter keys or
+/// `CSI n;m ~` tilde keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FunctionalKey {
+    Letter(u8),
+    Tilde(u16),
+}
+
+fn functional_key(code: KeyCode) -> Option<FunctionalKey> {
+    use FunctionalKey::{Letter, Tilde};
+    Some(match code {
+        KeyCode::Up => Letter(b'A'),
+        KeyCode::Down => Letter(b'B'),
+        KeyCode::Right => Letter(b'C'),
+        KeyCode::Left => Letter(b'D'),
+        KeyCode::Home => Letter(b'H'),
+        KeyCode::End => Letter(b'F'),
+        KeyCode::Insert => Tilde(2),
+        KeyCode::Delete => Tilde(3),
+        KeyCode::PageUp => Tilde(5),
+        KeyCode::PageDown => Tilde(6),
+        KeyCode::F(1) => Letter(b'P'),
+        KeyCode::F(2) => Letter(b'Q'),
+        KeyCode::F(3) => Letter(b'R'),
+        KeyCode::F(4) => Letter(b'S'),
+        KeyCode::F(n @ 5..=20) => Tilde(
+            [
+                15, 17, 18, 19, 20, 21, 23, 24, 25, 26, 28, 29, 31, 32, 33, 34,
+            ][usize::from(n) - 5],
+        ),
+        _ => return None,
+    })
+}
+
+/// SS3 final byte of a keypad key in application keypad mode.
+fn application_keypad_final(code: KeyCode) -> Option<u8> {
+    Some(match code {
+        KeyCode::Char(c @ '0'..='9') => b'p' + (c as u8 - b'0'),
+        KeyCode::Char('.') => b'n',
+        KeyCode::Char('+') => b'k',
+        KeyCode::Char('-') => b'm',
+        KeyCode::Char('*') => b'j',
+        KeyCode::Char('/') => b'o',
+        KeyCode::Char('=') => b'X',
+        KeyCode::Enter => b'M',
+        _ => return None,
+    })
+}
+
+/// Control byte produced by Ctrl+`c` in xterm's legacy encoding.
+fn legacy_control_byte(c: char) -> Option<u8> {
+    Some(match c.to_ascii_lowercase() {
+        c @ 'a'..='z' => c as u8 - b'a' + 1,
+        ' ' | '@' | '2' => 0x00,
+        '[' | '3' => 0x1b,
+        '\\' | '4' => 0x1c,
+        ']' | '5' => 0x1d,
+        '^' | '6' => 0x1e,
+        '_' | '/' | '7' => 0x1f,
+        '?' | '8' => 0x7f,
+        _ => return None,
+    })
+}
+
+fn encode_legacy(
+    code: KeyCode,
+    mods: KeyModifiers,
+    keypad: bool,
+    encoding: &KeyEncoding,
+) -> Option<Vec<u8>> {
+    let param = modifier_param(mods);
+    if let Some(key) = functional_key(code) {
+        return Some(match key {
+            FunctionalKey::Letter(letter) if param > 1 => {
+                format!("\x1b[1;{param}{}", letter as char).into_bytes()
+            }
+            FunctionalKey::Letter(letter) => {
+                // F1-F4 always use SS3; cursor keys follow DECCKM.
+                let ss3 = matches!(code, KeyCode::F(_)) || encoding.application_cursor;
+                if ss3 {
+                    vec![0x1b, b'O', letter]
+                } else {
+                    vec![0x1b, b'[', letter]
+                }
+            }
+            FunctionalKey::Tilde(n) if param > 1 => format!("\x1b[{n};{param}~").into_bytes(),
+            FunctionalKey::Tilde(n) => format!("\x1b[{n}~").into_bytes(),
+        });
+    }
+
+    if keypad && encoding.application_keypad && param == 1 {
+        if let Some(final_byte) = application_keypad_final(code) {
+            return Some(vec![0x1b, b'O', final_byte]);
+        }
+    }
+
+    let ctrl = mods.contains(KeyModifiers::CONTROL);
+    let mut out = match code {
+        KeyCode::Char(c) => match legacy_control_byte(c).filter(|_| ctrl) {
+            Some(byte) => vec![byte],
+            None => c.to_string().into_bytes(),
+        },
+        KeyCode::Enter => b"\r".to_vec(),
+        KeyCode::Tab if mods.contains(KeyModifiers::SHIFT) => b"\x1b[Z".to_vec(),
+        KeyCode::Tab => b"\t".to_vec(),
+        KeyCode::BackTab => b"\x1b[Z".to_vec(),
+        KeyCode::Backspace => {
+            let del = matches!(encoding.backspace, PtyBackspaceMode::Delete) != ctrl;
+            vec![if del { 0x7f } else { 0x08 }]
+        }
+        KeyCode::Esc => b"\x1b".to_vec(),
+        _ => return None,
+    };
+    if mods.contains(KeyModifiers::ALT) && encoding.alt_sends_escape {
+        out.insert(0, 0x1b);
+    }
+    Some(out)
+}
+
+/// Kitty key number for keys reported as `CSI number ; mods u`.
+fn kitty_key_number(code: KeyCode, keypad: bool) -> Option<u32> {
+    if keypad {
+        let number = match code {
+            KeyCode::Char(c @ '0'..='9') => 57399 + (c as u32 - '0' as u32),
+            KeyCode::Char('.') => 57409,
+            KeyCode::Char('/') => 57410,
+            KeyCode::Char('*') => 57411,
+            KeyCode::Char('-') => 57412,
+            KeyCode::Char('+') => 57413,
+            KeyCode::Enter => 57414,
+            KeyCode::Char('=') => 57415,
+            _ => 0,
+        };
+        if number != 0 {
+            return Some(number);
+        }
+    }
+    Some(match code {
+        KeyCode::Esc => 27,
+        KeyCode::Enter => 13,
+        KeyCode::Tab | KeyCode::BackTab => 9,
+        KeyCode::Backspace => 127,
+        KeyCode::Char(c) => c.to_lowercase().next().unwrap_or(c) as u32,
+        KeyCode::F(n @ 13..=35) => 57376 + u32::from(n - 13),
+        _ => return None,
+    })
+}
+
+fn encode_kitty(
+    code: KeyCode,
+    mods: KeyModifiers,
+    keypad: bool,
+    flags: u8,
+    encoding: &KeyEncoding,
+) -> Option<Vec<u8>> {
+    let all_keys = flags & KITTY_REPORT_ALL_KEYS != 0;
+    let mods = if code == KeyCode::BackTab {
+        mods | KeyModifiers::SHIFT
+    } else {
+        mods
+    };
+    let param = modifier_param(mods);
+
+    // F13 and up have no legacy spelling in kitty; they use private-use
+    // key numbers below.
+    let functional = functional_key(code).filter(|_| !matches!(code, KeyCode::F(13..)));
+    if let Some(key) = functional {
+        // Same spelling as legacy, except F3 (CSI R clashes with cursor
+        // position reports) and cursor keys always use CSI.
+        let key = if code == KeyCode::F(3) {
+            FunctionalKey::Tilde(13)
+        } else {
+            key
+        };
+        return Some(match key {
+            FunctionalKey::Letter(letter) if param > 1 => {
+                format!("\x1b[1;{param}{}", letter as char).into_bytes()
+            }
+            FunctionalKey::Letter(letter) if matches!(code, KeyCode::F(_)) => {
+                vec![0x1b, b'O', letter]
+            }
+            FunctionalKey::Letter(letter) if all_keys || !encoding.application_cursor => {
+                vec![0x1b, b'[', letter]
+            }
+            FunctionalKey::Letter(letter) => vec![0x1b, b'O', letter],
+            FunctionalKey::Tilde(n) if param > 1 => format!("\x1b[{n};{param}~").into_bytes(),
+            FunctionalKey::Tilde(n) => format!("\x1b[{n}~").into_bytes(),
+        });
+    }
+
+    let number = kitty_key_number(code, keypad)?;
+    if !all_keys {
+        // Disambiguate only: text without modifiers (or with Shift alone) and
+        // unmodified Enter/Tab/Backspace keep their legacy bytes so a shell
+        // stays usable if the program that enabled the protocol crashes.
+        let text_key = matches!(code, KeyCode::Char(_)) && !keypad;
+        let plain_control_key =
+            matches!(code, KeyCode::Enter | KeyCode::Tab | KeyCode::Backspace) && param == 1;
+        let shifted_text = text_key && mods.difference(KeyModifiers::SHIFT).is_empty();
+        if shifted_text || plain_control_key || (keypad && param == 1) {
+            return None;
+        }
+    }
+    Some(if param > 1 {
+        format!("\x1b[{number};{param}u").into_bytes()
+    } else {
+        format!("\x1b[{number}u").into_bytes()
+    })
+}
+
+/// Tracks kitty keyboard protocol negotiation in PTY output.
+///
+/// Each screen (main and alternate) keeps its own flag stack, as the spec
+/// requires. Sequences may be split across reads, so the scanner keeps its
+/// parse state between calls.
+#[derive(Debug, Clone, Default)]
+pub struct KittyKeyboardState {
+    main_stack: Vec<u8>,
+    alt_stack: Vec<u8>,
+    alternate_screen: bool,
+    scan: ScanState,
+    params: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum ScanState {
+    #[default]
+    Ground,
+    Escape,
+    Csi,
+}
+
+impl KittyKeyboardState {
+    /// Flags currently in effect, masked to what we support.
+    pub fn flags(&self) -> u8 {
+        self.stack().last().copied().unwrap_or(0) & KITTY_SUPPORTED_FLAGS
+    }
+
+    fn stack(&self) -> &Vec<u8> {
+        if self.alternate_screen {
+            &self.alt_stack
+        } else {
+            &self.main_stack
+        }
+    }
+
+    fn stack_mut(&mut self) -> &mut Vec<u8> {
+        if self.alternate_screen {
+            &mut self.alt_stack
+        } else {
+            &mut self.main_stack
+        }
+    }
+
+    /// Scans child output and returns any replies (to `CSI ? u` queries)
+    /// that must be written back to the child.
+    pub fn process(&mut self, bytes: &[u8]) -> Vec<u8> {
+        let mut replies = Vec::new();
+        for &b in bytes {
+            match self.scan {
+                ScanState::Ground => {
+                    if b == 0x1b {
+                        self.scan = ScanState::Escape;
+                    }
+                }
+                ScanState::Escape => match b {
+                    b'[' => {
+                        self.params.clear();
+                        self.scan = ScanState::Csi;
+                    }
+                    b'c' => {
+                        // RIS resets the terminal, including both stacks.
+                        *self = Self::default();
+                    }
+                    0x1b => {}
+                    _ => self.scan = ScanState::Ground,
+                },
+                ScanState::Csi => match b {
+                    0x30..=0x3f if self.params.len() < 32 => self.params.push(b),
+                    0x20..=0x3f => {}
+                    0x40..=0x7e => {
+                        self.scan = ScanState::Ground;
+                        let params = std::mem::take(&mut self.params);
+                        self.dispatch_csi(&params, b, &mut replies);
+                    }
+                    0x1b => self.scan = ScanState::Escape,
+                    _ => self.scan = ScanState::Ground,
+                },
+            }
+        }
+        replies
+    }
+
+    fn dispatch_csi(&mut self, params: &[u8], final_byte: u8, replies: &mut Vec<u8>) {
+        let (marker, rest) = match params.first() {
+            Some(&m @ (b'?' | b'>' | b'<' | b'=')) => (Some(m), &params[1..]),
+            _ => (None, params),
+        };
+        let numbers: Vec<Option<u32>> = std::str::from_utf8(rest)
+            .unwrap_or("")
+            .split(';')
+            .map(|part| part.parse().ok())
+            .collect();
+        let first = numbers.first().copied().flatten();
+        match (marker, final_byte) {
+            (Some(b'?'), b'h' | b'l') => {
+                let switches_screen = numbers
+                    .iter()
+                    .flatten()
+                    .any(|mode| matches!(mode, 47 | 1047 | 1049));
+                if switches_screen {
+                    self.alternate_screen = final_byte == b'h';
+                    if !self.alternate_screen {
+                        self.alt_stack.clear();
+                    }
+                }
+            }
+            (Some(b'?'), b'u') => {
+                replies.extend_from_slice(format!("\x1b[?{}u", self.flags()).as_bytes());
+            }
+            (Some(b'>'), b'u') => {
+                let flags = first.unwrap_or(0) as u8;
+                let stack = self.stack_mut();
+                if stack.len() >= KITTY_STACK_LIMIT {
+                    stack.remove(0);
+                }
+                stack.push(flags);
+            }
+            (Some(b'<'), b'u') => {
+                let count = first.unwrap_or(1).max(1) as usize;
+                let stack = self.stack_mut();
+                let keep = stack.len().saturating_sub(count);
+                stack.truncate(keep);
+            }
+            (Some(b'='), b'u') => {
+                let flags = first.unwrap_or(0) as u8;
+                let mode = numbers.get(1).copied().flatten().unwrap_or(1);
+                let stack = self.stack_mut();
+                if stack.is_empty() {
+                    stack.push(0);
+                }
+                if let Some(top) = stack.last_mut() {
+                    *top = match mode {
+                        2 => *top | flags,
+                        3 => *top & !flags,
+                        _ => flags,
+                    };
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enc() -> KeyEncoding {
+        KeyEncoding::default()
+    }
+
+    fn kitty(flags: u8) -> KeyEncoding {
+        KeyEncoding {
+            kitty_flags: flags,
+            ..KeyEncoding::default()
+        }
+    }
+
+    fn key(code: KeyCode, mods: KeyModifiers, encoding: &KeyEncoding) -> Vec<u8> {
+        encode_key(code, mods, false, encoding).unwrap()
+    }
+
+    #[test]
+    fn legacy_functional_key_table() {
+        let none = KeyModifiers::NONE;
+        let cases: &[(KeyCode, &[u8])] = &[
+            (KeyCode::Up, b"\x1b[A"),
+            (KeyCode::Down, b"\x1b[B"),
+            (KeyCode::Right, b"\x1b[C"),
+            (KeyCode::Left, b"\x1b[D"),
+            (KeyCode::Home, b"\x1b[H"),
+            (KeyCode::End, b"\x1b[F"),
+            (KeyCode::Insert, b"\x1b[2~"),
+            (KeyCode::Delete, b"\x1b[3~"),
+            (KeyCode::PageUp, b"\x1b[5~"),
+            (KeyCode::PageDown, b"\x1b[6~"),
+            (KeyCode::F(1), b"\x1bOP"),
+            (KeyCode::F(4), b"\x1bOS"),
+            (KeyCode::F(5), b"\x1b[15~"),
+            (KeyCode::F(6), b"\x1b[17~"),
+            (KeyCode::F(10), b"\x1b[21~"),
+            (KeyCode::F(11), b"\x1b[23~"),
+            (KeyCode::F(12), b"\x1b[24~"),
+            (KeyCode::BackTab, b"\x1b[Z"),
+        ];
+        for (code, expected) in cases {
+            assert_eq!(key(*code, none, &enc()), expected.to_vec(), "{code:?}");
+        }
+    }
+
+    #[test]
+    fn legacy_modified_functional_keys_carry_modifier_param() {
+        assert_eq!(key(KeyCode::Up, KeyModifiers::SHIFT, &enc()), b"\x1b[1;2A");
+        assert_eq!(
+            key(KeyCode::Right, KeyModifiers::CONTROL, &enc()),
+            b"\x1b[1;5C"
+        );
+        assert_eq!(
+            key(
+                KeyCode::Left,
+                KeyModifiers::CONTROL | KeyModifiers::ALT,
+                &enc()
+            ),
+            b"\x1b[1;7D"
+        );
+        assert_eq!(
+            key(KeyCode::Delete, KeyModifiers::ALT, &enc()),
+            b"\x1b[3;3~"
+        );
+        assert_eq!(
+            key(KeyCode::F(2), KeyModifiers::SHIFT, &enc()),
+            b"\x1b[1;2Q"
+        );
+        let app = KeyEncoding {
+            application_cursor: true,
+            ..enc()
+        };
+        assert_eq!(key(KeyCode::Up, KeyModifiers::CONTROL, &app), b"\x1b[1;5A");
+        assert_eq!(key(KeyCode::Home, KeyModifiers::NONE, &app), b"\x1bOH");
+    }
+
+    #[test]
+    fn legacy_control_characters() {
+        let ctrl = KeyModifiers::CONTROL;
+        assert_eq!(key(KeyCode::Char('a'), ctrl, &enc()), vec![0x01]);
+        assert_eq!(key(KeyCode::Char('Z'), ctrl, &enc()), vec![0x1a]);
+        assert_eq!(key(KeyCode::Char(' '), ctrl, &enc()), vec![0x00]);
+        assert_eq!(key(KeyCode::Char('['), ctrl, &enc()), vec![0x1b]);
+        assert_eq!(key(KeyCode::Char('/'), ctrl, &enc()), vec![0x1f]);
+        assert_eq!(key(KeyCode::Char('i'), ctrl, &enc()), b"\t");
+        assert_eq!(key(KeyCode::Enter, KeyModifiers::SHIFT, &enc()), b"\r");
+    }
+
+    #[test]
+    fn alt_as_meta_prefixes_escape_only_when_enabled() {
+        let alt = KeyModifiers::ALT;
+        assert_eq!(key(KeyCode::Char('x'), alt, &enc()), b"x");
+        let meta = KeyEncoding {
+            alt_sends_escape: true,
+            ..enc()
+        };
+        assert_eq!(key(KeyCode::Char('x'), alt, &meta), b"\x1bx");
+        assert_eq!(
+            key(KeyCode::Char('c'), alt | KeyModifiers::CONTROL, &meta),
+            b"\x1b\x03"
+        );
+        assert_eq!(key(KeyCode::Backspace, alt, &meta), b"\x1b\x7f");
+        assert_eq!(key(KeyCode::Esc, KeyModifiers::NONE, &meta), b"\x1b");
+    }
+
+    #[test]
+    fn backspace_mode_selects_del_or_bs_and_ctrl_flips_it() {
+        let none = KeyModifiers::NONE;
+        let ctrl = KeyModifiers::CONTROL;
+        assert_eq!(key(KeyCode::Backspace, none, &enc()), vec![0x7f]);
+        assert_eq!(key(KeyCode::Backspace, ctrl, &enc()), vec![0x08]);
+        let bs = KeyEncoding {
+            backspace: PtyBackspaceMode::Backspace,
+            ..enc()
+        };
+        assert_eq!(key(KeyCode::Backspace, none, &bs), vec![0x08]);
+        assert_eq!(key(KeyCode::Backspace, ctrl, &bs), vec![0x7f]);
+    }
+
+    #[test]
+    fn application_keypad_table() {
+        let app = KeyEncoding {
+            application_keypad: true,
+            ..enc()
+        };
+        let none = KeyModifiers::NONE;
+        let cases: &[(KeyCode, &[u8])] = &[
+            (KeyCode::Char('0'), b"\x1bOp"),
+            (KeyCode::Char('9'), b"\x1bOy"),
+            (KeyCode::Char('.'), b"\x1bOn"),
+            (KeyCode::Char('+'), b"\x1bOk"),
+            (KeyCode::Char('-'), b"\x1bOm"),
+            (KeyCode::Char('*'), b"\x1bOj"),
+            (KeyCode::Char('/'), b"\x1bOo"),
+            (KeyCode::Enter, b"\x1bOM"),
+        ];
+        for (code, expected) in cases {
+            assert_eq!(
+                encode_key(*code, none, true, &app).unwrap(),
+                expected.to_vec(),
+                "{code:?}"
+            );
+            // Not from the keypad, or numeric keypad mode: plain text.
+            assert_ne!(
+                encode_key(*code, none, false, &app).unwrap(),
+                expected.to_vec()
+            );
+            assert_ne!(
+                encode_key(*code, none, true, &enc()).unwrap(),
+                expected.to_vec()
+            );
+        }
+    }
+
+    #[test]
+    fn kitty_disambiguate_table() {
+        let k = kitty(KITTY_DISAMBIGUATE);
+        let none = KeyModifiers::NONE;
+        let ctrl = KeyModifiers::CONTROL;
+        let shift = KeyModifiers::SHIFT;
+        let cases: &[(KeyCode, KeyModifiers, &[u8])] = &[
+            (KeyCode::Esc, none, b"\x1b[27u"),
+            (KeyCode::Char('a'), none, b"a"),
+            (KeyCode::Char('A'), shift, b"A"),
+            (KeyCode::Char('i'), ctrl, b"\x1b[105;5u"),
+            (KeyCode::Tab, none, b"\t"),
+            (KeyCode::Tab, shift, b"\x1b[9;2u"),
+            (KeyCode::BackTab, shift, b"\x1b[9;2u"),
+            (KeyCode::Enter, none, b"\r"),
+            (KeyCode::Enter, shift, b"\x1b[13;2u"),
+            (KeyCode::Backspace, none, b"\x7f"),
+            (KeyCode::Backspace, ctrl, b"\x1b[127;5u"),
+            (KeyCode::Char('x'), KeyModifiers::ALT, b"\x1b[120;3u"),
+            (KeyCode::Char('C'), ctrl | shift, b"\x1b[99;6u"),
+            (KeyCode::Up, none, b"\x1b[A"),
+            (KeyCode::Up, ctrl, b"\x1b[1;5A"),
+            (KeyCode::F(3), none, b"\x1b[13~"),
+            (KeyCode::F(1), none, b"\x1bOP"),
+            (KeyCode::F(13), none, b"\x1b[57376u"),
+        ];
+        for (code, mods, expected) in cases {
+            assert_eq!(
+                key(*code, *mods, &k),
+                expected.to_vec(),
+                "{code:?} {mods:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn kitty_report_all_keys_table() {
+        let k = kitty(KITTY_DISAMBIGUATE | KITTY_REPORT_ALL_KEYS);
+        let none = KeyModifiers::NONE;
+        let shift = KeyModifiers::SHIFT;
+        let cases: &[(KeyCode, KeyModifiers, &[u8])] = &[
+            (KeyCode::Char('a'), none, b"\x1b[97u"),
+            (KeyCode::Char('A'), shift, b"\x1b[97;2u"),
+            (KeyCode::Enter, none, b"\x1b[13u"),
+            (KeyCode::Tab, none, b"\x1b[9u"),
+            (KeyCode::Backspace, none, b"\x1b[127u"),
+            (KeyCode::Esc, none, b"\x1b[27u"),
+            (KeyCode::PageUp, none, b"\x1b[5~"),
+        ];
+        for (code, mods, expected) in cases {
+            assert_eq!(
+                key(*code, *mods, &k),
+                expected.to_vec(),
+                "{code:?} {mods:?}"
+            );
+        }
+        assert_eq!(
+            encode_key(KeyCode::Char('5'), none, true, &k).unwrap(),
+            b"\x1b[57404u"
+        );
+        assert_eq!(
+            encode_key(KeyCode::Enter, none, true, &k).unwrap(),
+            b"\x1b[57414u"
+        );
+    }
+
+    #[test]
+    fn unsupported_kitty_flags_fall_back_to_legacy() {
+        let k = kitty(KITTY_REPORT_EVENT_TYPES | KITTY_REPORT_TEXT);
+        assert_eq!(key(KeyCode::Esc, KeyModifiers::NONE, &k), b"\x1b");
+    }
+
+    #[test]
+    fn kitty_state_tracks_push_pop_set_and_query() {
+        let mut state = KittyKeyboardState::default();
+        assert_eq!(state.process(b"\x1b[?u"), b"\x1b[?0u");
+        state.process(b"\x1b[>1u");
+        assert_eq!(state.flags(), KITTY_DISAMBIGUATE);
+        state.process(b"\x1b[>31u");
+        assert_eq!(state.flags(), KITTY_SUPPORTED_FLAGS);
+        assert_eq!(state.process(b"\x1b[?u"), b"\x1b[?9u");
+        state.process(b"\x1b[<u");
+        assert_eq!(state.flags(), KITTY_DISAMBIGUATE);
+        state.process(b"\x1b[=8;2u");
+        assert_eq!(state.flags(), KITTY_DISAMBIGUATE | KITTY_REPORT_ALL_KEYS);
+        state.process(b"\x1b[=1;3u");
+        assert_eq!(state.flags(), KITTY_REPORT_ALL_KEYS);
+        state.process(b"\x1b[<5u");
+        assert_eq!(state.flags(), 0);
+    }
+
+    #[test]
+    fn kitty_state_handles_split_sequences_and_ignores_other_csi() {
+        let mut state = KittyKeyboardState::default();
+        state.process(b"hello\x1b[3");
+        state.process(b"1mtext\x1b[");
+        state.process(b">");
+        state.process(b"1");
+        assert_eq!(state.flags(), 0);
+        state.process(b"u");
+        assert_eq!(state.flags(), KITTY_DISAMBIGUATE);
+    }
+
+    #[test]
+    fn kitty_state_keeps_separate_stacks_per_screen() {
+        let mut state = KittyKeyboardState::default();
+        state.process(b"\x1b[>1u");
+        state.process(b"\x1b[?1049h\x1b[>9u");
+        assert_eq!(state.flags(), KITTY_DISAMBIGUATE | KITTY_REPORT_ALL_KEYS);
+        // Leaving the alternate screen without popping restores main flags.
+        state.process(b"\x1b[?1049l");
+        assert_eq!(state.flags(), KITTY_DISAMBIGUATE);
+        state.process(b"\x1bc");
+        assert_eq!(state.flags(), 0);
+    }
+}
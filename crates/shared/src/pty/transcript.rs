@@ -0,0 +1,104 @@
+//! Plain-text transcript of a PTY session's output.
+//!
+//! Escape sequences (CSI, OSC, DCS and two-byte escapes) and control bytes
+//! other than newline and tab are dropped, so a log written from a hidden
+//! session reads like the command's plain output. The filter keeps its state
+//! between reads because a sequence may be split across them.
+
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum FilterState {
+    #[default]
+    Text,
+    Escape,
+    Csi,
+    /// OSC, DCS and friends: runs until BEL or ESC `\`.
+    String,
+    StringEscape,
+}
+
+#[derive(Debug, Default)]
+pub struct TranscriptFilter {
+    state: FilterState,
+}
+
+impl TranscriptFilter {
+    pub fn filter(&mut self, bytes: &[u8]) -> Vec<u8> {
+        let mut out = Vec::with_capacity(bytes.len());
+        for &byte in bytes {
+            self.state = match (self.state, byte) {
+                (FilterState::Text, 0x1b) => FilterState::Escape,
+                (FilterState::Text, b'\n' | b'\t') => {
+                    out.push(byte);
+                    FilterState::Text
+                }
+                (FilterState::Text, byte) if byte < 0x20 || byte == 0x7f => FilterState::Text,
+                (FilterState::Text, byte) => {
+                    out.push(byte);
+                    FilterState::Text
+                }
+                (FilterState::Escape, b'[') => FilterState::Csi,
+                (FilterState::Escape, b']' | b'P' | b'_' | b'^' | b'X') => FilterState::String,
+                // Charset designations carry one more byte.
+                (FilterState::Escape, b'(' | b')' | b'*' | b'+') => FilterState::Csi,
+                (FilterState::Escape, _) => FilterState::Text,
+                (FilterState::Csi, 0x40..=0x7e) => FilterState::Text,
+                (FilterState::Csi, _) => FilterState::Csi,
+                (FilterState::String, 0x07) => FilterState::Text,
+                (FilterState::String, 0x1b) => FilterState::StringEscape,
+                (FilterState::String, _) => FilterState::String,
+                (FilterState::StringEscape, b'\\') => FilterState::Text,
+                (FilterState::StringEscape, _) => FilterState::String,
+            };
+        }
+        out
+    }
+}
+
+/// Appends filtered output to a log file.
+pub struct Transcript {
+    file: File,
+    filter: TranscriptFilter,
+}
+
+impl Transcript {
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        Ok(Self {
+            file,
+            filter: TranscriptFilter::default(),
+        })
+    }
+
+    pub fn write(&mut self, bytes: &[u8]) {
+        let text = self.filter.filter(bytes);
+        if !text.is_empty() {
+            let _ = self.file.write_all(&text);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_escape_sequences_split_across_reads() {
+        let mut filter = TranscriptFilter::default();
+        let mut out = filter.filter(b"\x1b[1;32mAlready\x1b[");
+        out.extend(filter.filter(b"0m up to date.\r\n\x1b]0;title\x07done\x1b(B\n"));
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Already up to date.\ndone\n"
+        );
+    }
+}
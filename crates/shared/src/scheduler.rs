@@ -0,0 +1,592 @@
+//! Per-user scheduled jobs (`users/<name>/schedule.json`).
+//!
+//! A job runs a catalog entry or a command line once at a given time, on a
+//! fixed interval or on a cron expression. Times are evaluated in local time.
+//! A run that was due while RobCoOS was not running happens once at the next
+//! check, after which the job is rescheduled from that run. Results of
+//! finished runs are kept next to the jobs; hidden runs write their output to
+//! a log under the user's `scheduler_logs` directory.
+
+use crate::config::{load_json, save_json, user_dir};
+use crate::default_apps::parse_custom_command_line;
+use crate::history::LaunchKind;
+use crate::launcher::launch_args_to_text;
+use chrono::{
+    Datelike, Duration, Local, LocalResult, NaiveDate, NaiveDateTime, TimeZone, Timelike,
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Run results kept overall, oldest dropped first.
+const MAX_RESULTS: usize = 100;
+/// Shortest interval accepted by `every`.
+const MIN_INTERVAL_SECS: u64 = 60;
+/// How far ahead a cron expression is searched before it counts as never
+/// matching (e.g. `0 0 31 2 *`).
+const CRON_SEARCH_DAYS: i64 = 4 * 366;
+
+/// What a job starts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JobTarget {
+    /// A catalog entry by name. `kind` is one of the catalog launch kinds.
+    Catalog {
+        kind: LaunchKind,
+        name: String,
+    },
+    Command {
+        argv: Vec<String>,
+    },
+}
+
+impl JobTarget {
+    /// Reads `app:Name`, `net:Name`, `game:Name` or a shell-quoted command.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        for (prefix, kind) in [
+            ("app:", LaunchKind::Application),
+            ("net:", LaunchKind::Network),
+            ("game:", LaunchKind::Game),
+        ] {
+            if let Some(name) = text.strip_prefix(prefix) {
+                let name = name.trim();
+                if name.is_empty() {
+                    return Err("Error: missing catalog entry name.".to_string());
+                }
+                return Ok(JobTarget::Catalog {
+                    kind,
+                    name: name.to_string(),
+                });
+            }
+        }
+        match parse_custom_command_line(text) {
+            Some(argv) if !argv.is_empty() => Ok(JobTarget::Command { argv }),
+            _ => Err("Error: invalid command line".to_string()),
+        }
+    }
+
+    /// Text form accepted by [`JobTarget::parse`].
+    pub fn describe(&self) -> String {
+        match self {
+            JobTarget::Catalog { kind, name } => {
+                let prefix = match kind {
+                    LaunchKind::Network => "net",
+                    LaunchKind::Game => "game",
+                    _ => "app",
+                };
+                format!("{prefix}:{name}")
+            }
+            JobTarget::Command { argv } => launch_args_to_text(argv),
+        }
+    }
+}
+
+/// When a job runs. Timestamps are Unix seconds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JobSchedule {
+    Once { at: u64 },
+    Interval { every: u64 },
+    Cron { expr: String },
+}
+
+impl JobSchedule {
+    /// Reads `every 15m` (units `m`, `h`, `d`), `at HH:MM`,
+    /// `at YYYY-MM-DD HH:MM`, a five-field cron expression or one of
+    /// `@hourly`, `@daily`, `@weekly`, `@monthly`. `now` anchors `at HH:MM`
+    /// to its next occurrence.
+    pub fn parse(text: &str, now: u64) -> Result<Self, String> {
+        let text = text.trim();
+        if let Some(interval) = text.strip_prefix("every ") {
+            let every = parse_interval(interval.trim())?;
+            return Ok(JobSchedule::Interval { every });
+        }
+        if let Some(at) = text.strip_prefix("at ") {
+            return parse_at(at.trim(), now).map(|at| JobSchedule::Once { at });
+        }
+        CronExpr::parse(text)?;
+        Ok(JobSchedule::Cron {
+            expr: text.to_string(),
+        })
+    }
+
+    /// Text form accepted by [`JobSchedule::parse`].
+    pub fn describe(&self) -> String {
+        match self {
+            JobSchedule::Once { at } => format!("at {}", format_job_time(*at)),
+            JobSchedule::Interval { every } => {
+                let (value, unit) = if every % 86_400 == 0 {
+                    (every / 86_400, "d")
+                } else if every % 3_600 == 0 {
+                    (every / 3_600, "h")
+                } else {
+                    (every / 60, "m")
+                };
+                format!("every {value}{unit}")
+            }
+            JobSchedule::Cron { expr } => expr.clone(),
+        }
+    }
+}
+
+fn parse_interval(text: &str) -> Result<u64, String> {
+    let split = text
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(text.len());
+    let (value, unit) = text.split_at(split);
+    let value: u64 = value
+        .parse()
+        .map_err(|_| format!("Error: invalid interval '{text}'."))?;
+    let scale = match unit.trim() {
+        "m" | "min" => 60,
+        "h" => 3_600,
+        "d" => 86_400,
+        _ => return Err(format!("Error: invalid interval '{text}'.")),
+    };
+    let every = value.saturating_mul(scale);
+    if every < MIN_INTERVAL_SECS {
+        return Err("Error: interval must be at least one minute.".to_string());
+    }
+    Ok(every)
+}
+
+fn parse_at(text: &str, now: u64) -> Result<u64, String> {
+    let invalid = || format!("Error: invalid time '{text}'.");
+    if let Ok(naive) = NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M") {
+        return local_timestamp(naive).ok_or_else(invalid);
+    }
+    let time = chrono::NaiveTime::parse_from_str(text, "%H:%M").map_err(|_| invalid())?;
+    let today = local_naive(now).ok_or_else(invalid)?.date();
+    let mut date = today;
+    loop {
+        if let Some(at) = local_timestamp(date.and_time(time)).filter(|at| *at > now) {
+            return Ok(at);
+        }
+        date = date.succ_opt().ok_or_else(invalid)?;
+        if date > today + Duration::days(2) {
+            return Err(invalid());
+        }
+    }
+}
+
+fn local_naive(timestamp: u64) -> Option<NaiveDateTime> {
+    Local
+        .timestamp_opt(i64::try_from(timestamp).ok()?, 0)
+        .single()
+        .map(|time| time.naive_local())
+}
+
+/// `None` for times skipped by a daylight saving change.
+fn local_timestamp(naive: NaiveDateTime) -> Option<u64> {
+    match Local.from_local_datetime(&naive) {
+        LocalResult::Single(time) | LocalResult::Ambiguous(time, _) => {
+            u64::try_from(time.timestamp()).ok()
+        }
+        LocalResult::None => None,
+    }
+}
+
+/// `YYYY-MM-DD HH:MM` in local time.
+pub fn format_job_time(timestamp: u64) -> String {
+    local_naive(timestamp)
+        .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// A parsed five-field cron expression (minute, hour, day of month, month,
+/// day of week). Fields accept `*`, numbers, ranges `a-b`, lists `a,b` and
+/// steps `*/n` or `a-b/n`. Day of week runs 0-7 with both 0 and 7 meaning
+/// Sunday. As in cron, when both day fields are restricted a day matching
+/// either one matches; a field starting with `*` does not count as
+/// restricted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CronExpr {
+    minutes: u64,
+    hours: u32,
+    days: u32,
+    months: u16,
+    weekdays: u8,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl CronExpr {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = match text.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+        let fields: Vec<&str> = text.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(format!("Error: invalid schedule '{text}'."));
+        };
+        let weekdays = parse_cron_field(weekday, 0, 7)?;
+        Ok(Self {
+            minutes: parse_cron_field(minute, 0, 59)?,
+            hours: parse_cron_field(hour, 0, 23)? as u32,
+            days: parse_cron_field(day, 1, 31)? as u32,
+            months: parse_cron_field(month, 1, 12)? as u16,
+            // Fold 7 onto Sunday.
+            weekdays: ((weekdays | (weekdays >> 7)) & 0x7f) as u8,
+            // `*/n` still counts as unrestricted for the either-day rule.
+            any_day: day.starts_with('*'),
+            any_weekday: weekday.starts_with('*'),
+        })
+    }
+
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        let day = self.days & (1 << date.day()) != 0;
+        let weekday = self.weekdays & (1 << date.weekday().num_days_from_sunday()) != 0;
+        match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            _ => day && weekday,
+        }
+    }
+
+    /// First matching minute strictly after `after`.
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let mut time = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = time + Duration::days(CRON_SEARCH_DAYS);
+        while time <= limit {
+            let date = time.date();
+            if self.months & (1 << date.month()) == 0 {
+                let (year, month) = if date.month() == 12 {
+                    (date.year() + 1, 1)
+                } else {
+                    (date.year(), date.month() + 1)
+                };
+                time = NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)?;
+            } else if !self.day_matches(date) {
+                time = date.succ_opt()?.and_hms_opt(0, 0, 0)?;
+            } else if self.hours & (1 << time.hour()) == 0 {
+                time = date.and_hms_opt(time.hour(), 0, 0)? + Duration::hours(1);
+            } else if self.minutes & (1 << time.minute()) == 0 {
+                time += Duration::minutes(1);
+            } else {
+                return Some(time);
+            }
+        }
+        None
+    }
+
+    /// Next matching local time after `after`, skipping times that do not
+    /// exist because of a daylight saving change.
+    pub fn next_run(&self, after: u64) -> Option<u64> {
+        let mut naive = local_naive(after)?;
+        loop {
+            naive = self.next_after(naive)?;
+            if let Some(timestamp) = local_timestamp(naive).filter(|time| *time > after) {
+                return Some(timestamp);
+            }
+        }
+    }
+}
+
+fn parse_cron_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+    let invalid = || format!("Error: invalid schedule field '{field}'.");
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().map_err(|_| invalid())?),
+            None => (part, 1),
+        };
+        if step == 0 {
+            return Err(invalid());
+        }
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (
+                start.parse().map_err(|_| invalid())?,
+                end.parse().map_err(|_| invalid())?,
+            )
+        } else {
+            let value = range.parse().map_err(|_| invalid())?;
+            (value, if part.contains('/') { max } else { value })
+        };
+        if start < min || end > max || start > end {
+            return Err(invalid());
+        }
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledJob {
+    pub id: u64,
+    pub name: String,
+    pub target: JobTarget,
+    pub schedule: JobSchedule,
+    /// Open a terminal window instead of running in a hidden PTY.
+    #[serde(default)]
+    pub visible: bool,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Start of interval and cron schedules before the first run.
+    #[serde(default)]
+    pub created: u64,
+    #[serde(default)]
+    pub last_run: Option<u64>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl ScheduledJob {
+    /// When the job is due next; `None` once a one-shot job has run, for
+    /// disabled jobs and for cron expressions that never match.
+    pub fn next_run(&self) -> Option<u64> {
+        if !self.enabled {
+            return None;
+        }
+        match &self.schedule {
+            JobSchedule::Once { at } => self.last_run.is_none().then_some(*at),
+            JobSchedule::Interval { every } => {
+                Some(self.last_run.unwrap_or(self.created).saturating_add(*every))
+            }
+            JobSchedule::Cron { expr } => CronExpr::parse(expr)
+                .ok()?
+                .next_run(self.last_run.unwrap_or(self.created)),
+        }
+    }
+
+    pub fn is_due(&self, now: u64) -> bool {
+        self.next_run().is_some_and(|next| next <= now)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum JobOutcome {
+    Success,
+    Failed {
+        code: u32,
+    },
+    /// The job could not be started.
+    Error {
+        message: String,
+    },
+    /// Handed to a terminal window; its exit is not tracked.
+    Opened,
+}
+
+impl JobOutcome {
+    pub fn label(&self) -> String {
+        match self {
+            JobOutcome::Success => "OK".to_string(),
+            JobOutcome::Failed { code } => format!("Exit {code}"),
+            JobOutcome::Error { message } => message.clone(),
+            JobOutcome::Opened => "Opened".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobRunResult {
+    pub job_id: u64,
+    pub job_name: String,
+    pub started: u64,
+    pub finished: u64,
+    pub outcome: JobOutcome,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Schedule {
+    #[serde(default)]
+    pub jobs: Vec<ScheduledJob>,
+    /// Oldest first.
+    #[serde(default)]
+    pub results: Vec<JobRunResult>,
+    #[serde(default)]
+    next_id: u64,
+}
+
+impl Schedule {
+    pub fn add_job(
+        &mut self,
+        name: &str,
+        target: JobTarget,
+        schedule: JobSchedule,
+        visible: bool,
+        now: u64,
+    ) -> u64 {
+        self.next_id = self
+            .next_id
+            .max(self.jobs.iter().map(|job| job.id).max().unwrap_or(0))
+            + 1;
+        self.jobs.push(ScheduledJob {
+            id: self.next_id,
+            name: name.to_string(),
+            target,
+            schedule,
+            visible,
+            enabled: true,
+            created: now,
+            last_run: None,
+        });
+        self.next_id
+    }
+
+    pub fn job(&self, id: u64) -> Option<&ScheduledJob> {
+        self.jobs.iter().find(|job| job.id == id)
+    }
+
+    pub fn job_mut(&mut self, id: u64) -> Option<&mut ScheduledJob> {
+        self.jobs.iter_mut().find(|job| job.id == id)
+    }
+
+    pub fn remove_job(&mut self, id: u64) {
+        self.jobs.retain(|job| job.id != id);
+    }
+
+    /// Ids of enabled jobs due at `now`, earliest first.
+    pub fn due_jobs(&self, now: u64) -> Vec<u64> {
+        let mut due: Vec<(u64, u64)> = self
+            .jobs
+            .iter()
+            .filter_map(|job| {
+                job.next_run()
+                    .filter(|next| *next <= now)
+                    .map(|next| (next, job.id))
+            })
+            .collect();
+        due.sort_unstable();
+        due.into_iter().map(|(_, id)| id).collect()
+    }
+
+    pub fn record_result(&mut self, result: JobRunResult) {
+        self.results.push(result);
+        if self.results.len() > MAX_RESULTS {
+            let excess = self.results.len() - MAX_RESULTS;
+            self.results.drain(..excess);
+        }
+    }
+
+    pub fn last_result(&self, id: u64) -> Option<&JobRunResult> {
+        self.results.iter().rev().find(|result| result.job_id == id)
+    }
+}
+
+pub fn load_schedule(username: &str) -> Schedule {
+    load_json(&user_dir(username).join("schedule.json"))
+}
+
+pub fn save_schedule(username: &str, schedule: &Schedule) {
+    let _ = save_json(&user_dir(username).join("schedule.json"), schedule);
+}
+
+/// Log file for one hidden run of a job.
+pub fn job_log_path(username: &str, job_id: u64, started: u64) -> PathBuf {
+    user_dir(username)
+        .join("scheduler_logs")
+        .join(format!("job-{job_id}-{started}.log"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive(text: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M").expect("valid time")
+    }
+
+    #[test]
+    fn cron_next_after_handles_steps_ranges_and_day_fields() {
+        let nightly = CronExpr::parse("30 2 * * *").expect("nightly");
+        assert_eq!(
+            nightly.next_after(naive("2026-10-18 02:30")),
+            Some(naive("2026-10-19 02:30"))
+        );
+
+        let quarter = CronExpr::parse("*/15 9-17 * * 1-5").expect("work hours");
+        // Saturday evening rolls over to Monday morning.
+        assert_eq!(
+            quarter.next_after(naive("2026-10-17 17:50")),
+            Some(naive("2026-10-19 09:00"))
+        );
+        assert_eq!(
+            quarter.next_after(naive("2026-10-19 09:05")),
+            Some(naive("2026-10-19 09:15"))
+        );
+
+        // Either the 1st or any Sunday (7 is Sunday too).
+        let either = CronExpr::parse("0 0 1 * 7").expect("either day");
+        assert_eq!(
+            either.next_after(naive("2026-10-19 00:00")),
+            Some(naive("2026-10-25 00:00"))
+        );
+
+        // A stepped `*` day field is still ANDed with the weekday.
+        let odd_mondays = CronExpr::parse("0 0 */2 * 1").expect("odd mondays");
+        assert_eq!(
+            odd_mondays.next_after(naive("2026-10-19 00:00")),
+            Some(naive("2026-11-09 00:00"))
+        );
+
+        assert_eq!(CronExpr::parse("@monthly"), CronExpr::parse("0 0 1 * *"));
+        assert!(CronExpr::parse("0 0 31 2 *")
+            .expect("never matches")
+            .next_after(naive("2026-01-01 00:00"))
+            .is_none());
+        assert!(CronExpr::parse("60 * * * *").is_err());
+        assert!(CronExpr::parse("* * *").is_err());
+    }
+
+    #[test]
+    fn schedules_parse_describe_and_compute_next_runs() {
+        let now = 1_800_000_000;
+        let every = JobSchedule::parse("every 90m", now).expect("interval");
+        assert_eq!(every, JobSchedule::Interval { every: 5_400 });
+        assert_eq!(every.describe(), "every 90m");
+        assert!(JobSchedule::parse("every 0m", now).is_err());
+        assert!(JobSchedule::parse("sometimes", now).is_err());
+
+        let mut schedule = Schedule::default();
+        let interval = schedule.add_job(
+            "pull",
+            JobTarget::parse("git -C '/srv/shared repo' pull").expect("command"),
+            every,
+            false,
+            now,
+        );
+        let once = schedule.add_job(
+            "backup",
+            JobTarget::parse("app:Journal Backup").expect("catalog"),
+            JobSchedule::Once { at: now + 60 },
+            true,
+            now,
+        );
+        assert_eq!(
+            schedule.job(interval).unwrap().target.describe(),
+            "git -C '/srv/shared repo' pull"
+        );
+        assert_eq!(
+            schedule.job(once).unwrap().target.describe(),
+            "app:Journal Backup"
+        );
+
+        assert!(schedule.due_jobs(now + 59).is_empty());
+        assert_eq!(schedule.due_jobs(now + 5_400), vec![once, interval]);
+
+        schedule.job_mut(once).unwrap().last_run = Some(now + 60);
+        schedule.job_mut(interval).unwrap().last_run = Some(now + 6_000);
+        assert_eq!(schedule.job(once).unwrap().next_run(), None);
+        assert_eq!(
+            schedule.job(interval).unwrap().next_run(),
+            Some(now + 11_400)
+        );
+
+        schedule.job_mut(interval).unwrap().enabled = false;
+        assert!(schedule.due_jobs(u64::MAX).is_empty());
+    }
+}
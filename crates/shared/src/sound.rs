@@ -14,6 +14,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use rand::seq::SliceRandom;
 
 use crate::config::get_settings;
+use crate::processes::{register_owned_process, unregister_owned_process, OwnedProcessKind};
 
 static STOPPED: AtomicBool = AtomicBool::new(false);
 static ACTIVE: AtomicUsize = AtomicUsize::new(0);
@@ -309,9 +310,16 @@ fn run_spawn(path: PathBuf) {
 
     match child {
         Ok(mut child) => {
+            let pid = child.id();
+            let label = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            register_owned_process(pid, OwnedProcessKind::Sound, &label);
             // Reap the child asynchronously.
             std::thread::spawn(move || {
                 let _ = child.wait();
+                unregister_owned_process(pid);
                 ACTIVE.fetch_sub(1, Ordering::Relaxed);
             });
         }
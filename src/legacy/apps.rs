@@ -5,7 +5,8 @@ use crate::config::{
     get_settings, load_apps, load_games, load_networks, persist_settings, save_apps, save_games,
     save_networks, update_settings,
 };
-use crate::launcher::{json_to_cmd, launch_in_pty};
+use crate::history::{record_launch, LaunchKind};
+use crate::launcher::launch_catalog_value_in_pty;
 use crate::ui::{
     confirm, flash_message, input_prompt, is_back_menu_label, run_menu, MenuResult, Term,
 };
@@ -122,7 +123,8 @@ pub fn apps_menu(terminal: &mut Term) -> Result<()> {
             }
             MenuResult::Selected(s) => {
                 if let Some(v) = apps.get(&s) {
-                    launch_in_pty(terminal, &json_to_cmd(v))?;
+                    record_launch(LaunchKind::Application, &s);
+                    launch_catalog_value_in_pty(terminal, v)?;
                     if crate::session::has_switch_request() {
                         break;
                     }
@@ -149,7 +151,8 @@ pub fn games_menu(terminal: &mut Term) -> Result<()> {
             MenuResult::Selected(s) if s == "Back" => break,
             MenuResult::Selected(s) => {
                 if let Some(v) = games.get(&s) {
-                    launch_in_pty(terminal, &json_to_cmd(v))?;
+                    record_launch(LaunchKind::Game, &s);
+                    launch_catalog_value_in_pty(terminal, v)?;
                     if crate::session::has_switch_request() {
                         break;
                     }
@@ -176,7 +179,8 @@ pub fn network_menu(terminal: &mut Term) -> Result<()> {
             MenuResult::Selected(s) if s == "Back" => break,
             MenuResult::Selected(s) => {
                 if let Some(v) = nets.get(&s) {
-                    launch_in_pty(terminal, &json_to_cmd(v))?;
+                    record_launch(LaunchKind::Network, &s);
+                    launch_catalog_value_in_pty(terminal, v)?;
                     if crate::session::has_switch_request() {
                         break;
                     }
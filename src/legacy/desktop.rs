@@ -18,14 +18,15 @@ use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
 use crate::auth::{hash_password, is_admin, load_users, save_users, AuthMethod};
+use crate::catalog::{CatalogEntry, CatalogRunMode};
 use crate::config::{
     cycle_hacking_difficulty, get_current_user, get_settings, hacking_difficulty_label, load_apps,
     load_categories, load_games, load_networks, mark_default_apps_prompt_pending, persist_settings,
     save_apps, save_categories, save_games, save_networks, update_settings, CliAcsMode,
     CliColorMode, ConnectionKind, DesktopCliProfiles, DesktopFileManagerSettings,
     DesktopIconPosition, DesktopIconStyle, DesktopPtyProfileSettings, FileManagerSortMode,
-    FileManagerTextOpenMode, FileManagerViewMode, OpenMode, WallpaperSizeMode, CUSTOM_THEME_NAME,
-    THEMES,
+    FileManagerTextOpenMode, FileManagerViewMode, LaunchEnvironment, OpenMode, WallpaperSizeMode,
+    CUSTOM_THEME_NAME, THEMES,
 };
 use crate::connections::{
     bluetooth_installer_hint, choose_discovered_connection, connect_connection,
@@ -41,7 +42,7 @@ use crate::default_apps::{
     set_binding_for_slot, slot_label, DefaultAppChoiceAction, DefaultAppSlot, ResolvedDocumentOpen,
 };
 use crate::documents;
-use crate::launcher::{json_to_cmd, with_suspended};
+use crate::launcher::{resolve_launch_environment, spawn_detached, with_suspended};
 use crate::ui::{
     dim_style, flash_message, input_prompt, is_back_menu_label, normal_style, run_menu_compact,
     sel_style, session_switch_scope, title_style, MenuResult, Term,
@@ -100,7 +101,7 @@ enum DesktopHubKind {
 enum StartAction {
     None,
     Launch(StartLaunch),
-    LaunchCommand { title: String, cmd: Vec<String> },
+    LaunchCommand { title: String, entry: CatalogEntry },
     LaunchNukeCodes,
     OpenTextEditorApp,
     OpenDocumentLogs,
@@ -770,7 +771,7 @@ enum DesktopHubItemAction {
     ToggleBuiltinTextEditorVisibility,
     LaunchCommand {
         title: String,
-        cmd: Vec<String>,
+        entry: CatalogEntry,
     },
     LaunchNukeCodes,
     LaunchTextEditor,
@@ -1328,11 +1329,11 @@ fn build_command_leaf_items(
     let mut items = Vec::new();
     for key in sorted_json_keys(&map) {
         if let Some(v) = map.get(&key) {
-            let cmd = json_to_cmd(v);
-            if !cmd.is_empty() {
+            let entry = CatalogEntry::from_json(v);
+            if !entry.command.is_empty() {
                 items.push(StartLeafItem {
                     label: key.clone(),
-                    action: StartAction::LaunchCommand { title: key, cmd },
+                    action: StartAction::LaunchCommand { title: key, entry },
                 });
             }
         }
@@ -1368,11 +1369,11 @@ fn refresh_start_leaf_items(state: &mut StartState) {
             continue;
         }
         if let Some(v) = apps.get(&key) {
-            let cmd = json_to_cmd(v);
-            if !cmd.is_empty() {
+            let entry = CatalogEntry::from_json(v);
+            if !entry.command.is_empty() {
                 app_items.push(StartLeafItem {
                     label: key.clone(),
-                    action: StartAction::LaunchCommand { title: key, cmd },
+                    action: StartAction::LaunchCommand { title: key, entry },
                 });
             }
         }
@@ -1656,11 +1657,11 @@ fn desktop_hub_items(hub: &DesktopHubState, current_user: &str) -> Vec<DesktopHu
                     continue;
                 }
                 if let Some(value) = apps.get(&key) {
-                    let cmd = json_to_cmd(value);
-                    if !cmd.is_empty() {
+                    let entry = CatalogEntry::from_json(value);
+                    if !entry.command.is_empty() {
                         items.push(DesktopHubItem {
                             label: key.clone(),
-                            action: DesktopHubItemAction::LaunchCommand { title: key, cmd },
+                            action: DesktopHubItemAction::LaunchCommand { title: key, entry },
                             enabled: true,
                         });
                     }
@@ -1874,11 +1875,11 @@ fn desktop_hub_items(hub: &DesktopHubState, current_user: &str) -> Vec<DesktopHu
             let networks = load_networks();
             for key in sorted_json_keys(&networks) {
                 if let Some(value) = networks.get(&key) {
-                    let cmd = json_to_cmd(value);
-                    if !cmd.is_empty() {
+                    let entry = CatalogEntry::from_json(value);
+                    if !entry.command.is_empty() {
                         items.push(DesktopHubItem {
                             label: key.clone(),
-                            action: DesktopHubItemAction::LaunchCommand { title: key, cmd },
+                            action: DesktopHubItemAction::LaunchCommand { title: key, entry },
                             enabled: true,
                         });
                     }
@@ -2157,11 +2158,11 @@ fn desktop_hub_items(hub: &DesktopHubState, current_user: &str) -> Vec<DesktopHu
             let games = load_games();
             for key in sorted_json_keys(&games) {
                 if let Some(value) = games.get(&key) {
-                    let cmd = json_to_cmd(value);
-                    if !cmd.is_empty() {
+                    let entry = CatalogEntry::from_json(value);
+                    if !entry.command.is_empty() {
                         items.push(DesktopHubItem {
                             label: key.clone(),
-                            action: DesktopHubItemAction::LaunchCommand { title: key, cmd },
+                            action: DesktopHubItemAction::LaunchCommand { title: key, entry },
                             enabled: true,
                         });
                     }
@@ -3054,7 +3055,7 @@ struct WallpaperRenderCache {
     rendered: Vec<String>,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 struct PtyCompatibilityProfile {
     min_w: u16,
     min_h: u16,
@@ -3062,7 +3063,9 @@ struct PtyCompatibilityProfile {
     preferred_h: Option<u16>,
     mouse_passthrough: bool,
     open_fullscreen: bool,
+    keys: crate::pty::PtyKeyOptions,
     env: &'static [(&'static str, &'static str)],
+    launch: LaunchEnvironment,
 }
 
 fn queue_start_hover(state: &mut StartState, target: StartHoverTarget) {
@@ -3133,11 +3136,34 @@ fn has_visible_pty_window(state: &DesktopState) -> bool {
         .any(|w| !w.minimized && matches!(w.kind, WindowKind::PtyApp(_)))
 }
 
+/// Drain output activity from every visible PTY window.  Returns true when
+/// any of them committed new output since the last call.
+fn take_pty_output_activity(state: &mut DesktopState) -> bool {
+    let mut activity = false;
+    for win in &mut state.windows {
+        if win.minimized {
+            continue;
+        }
+        if let WindowKind::PtyApp(app) = &mut win.kind {
+            activity |= app.session.take_output_activity();
+        }
+    }
+    activity
+}
+
+fn desktop_poll_interval(state: &DesktopState) -> Duration {
+    let interval = desktop_redraw_interval(state);
+    if has_visible_pty_window(state) {
+        interval.min(crate::pty::PTY_OUTPUT_POLL_INTERVAL)
+    } else {
+        interval
+    }
+}
+
 fn desktop_redraw_interval(state: &DesktopState) -> Duration {
     if state.dragging.is_some() || state.icon_dragging.is_some() {
         Duration::from_millis(16)
-    } else if has_visible_pty_window(state)
-        || state.start.open
+    } else if state.start.open
         || state.top_menu.open.is_some()
         || state.spotlight.open
         || state.help_popup.is_some()
@@ -3179,9 +3205,18 @@ fn run_desktop_loop(terminal: &mut Term, current_user: &str) -> Result<DesktopEx
     let mut last_draw = Instant::now();
     let mut last_motion_event = Instant::now() - Duration::from_secs(1);
     let mut pending_event: Option<Event> = None;
+    let mut repaint = crate::pty::PtyRepaintGate::default();
 
     loop {
         reap_closed_pty_windows(&mut state);
+        if has_visible_pty_window(&state)
+            && repaint.observe(
+                take_pty_output_activity(&mut state),
+                crate::pty::cursor_blink_on(),
+            )
+        {
+            needs_redraw = true;
+        }
         if advance_start_hover(&mut state) {
             needs_redraw = true;
         }
@@ -3196,7 +3231,8 @@ fn run_desktop_loop(terminal: &mut Term, current_user: &str) -> Result<DesktopEx
             last_draw = Instant::now();
         }
 
-        let timeout = interval.saturating_sub(last_draw.elapsed());
+        let timeout =
+            desktop_poll_interval(&state).min(interval.saturating_sub(last_draw.elapsed()));
         let next_event = if let Some(evt) = pending_event.take() {
             Some(evt)
         } else if event::poll(timeout)? {
@@ -4563,8 +4599,8 @@ fn run_start_action(
             }
             Ok(None)
         }
-        StartAction::LaunchCommand { title, cmd } => {
-            if let Err(err) = open_pty_window_named(terminal, state, &cmd, Some(title.as_str())) {
+        StartAction::LaunchCommand { title, entry } => {
+            if let Err(err) = launch_catalog_entry_window(terminal, state, &title, &entry) {
                 flash_message(terminal, &format!("Launch failed: {err}"), 1200)?;
             }
             Ok(None)
@@ -5408,6 +5444,47 @@ fn open_pty_window_named(
     state: &mut DesktopState,
     cmd: &[String],
     title_override: Option<&str>,
+) -> Result<()> {
+    open_pty_window_with_environment(
+        terminal,
+        state,
+        cmd,
+        title_override,
+        &LaunchEnvironment::default(),
+        None,
+    )
+}
+
+/// Opens a PTY window with a catalog entry's environment, working directory
+/// and extra arguments layered over those of the command's profile.
+/// Starts a catalog entry: detached entries run without a window, the rest
+/// open in a PTY window with the entry's environment and preferred profile.
+fn launch_catalog_entry_window(
+    terminal: &mut Term,
+    state: &mut DesktopState,
+    title: &str,
+    entry: &CatalogEntry,
+) -> Result<()> {
+    match entry.run_mode {
+        CatalogRunMode::Detached => spawn_detached(&entry.command, &entry.launch),
+        CatalogRunMode::Pty => open_pty_window_with_environment(
+            terminal,
+            state,
+            &entry.command,
+            Some(title),
+            &entry.launch,
+            entry.pty_profile.as_deref(),
+        ),
+    }
+}
+
+fn open_pty_window_with_environment(
+    terminal: &mut Term,
+    state: &mut DesktopState,
+    cmd: &[String],
+    title_override: Option<&str>,
+    launch: &LaunchEnvironment,
+    preferred_profile: Option<&str>,
 ) -> Result<()> {
     if cmd.is_empty() {
         return Ok(());
@@ -5421,7 +5498,10 @@ fn open_pty_window_named(
     }
 
     let cmd = rewrite_legacy_command(cmd);
-    let profile = pty_profile_for_program(&cmd[0]);
+    let profile = preferred_profile
+        .and_then(|name| get_settings().desktop_cli_profiles.named(name))
+        .map(|settings| profile_from_settings(&settings, NO_ENV_OVERRIDES))
+        .unwrap_or_else(|| pty_profile_for_program(&cmd[0]));
     let offset = ((state.windows.len() % 6) as i32) * 2;
     let base_w = profile
         .preferred_w
@@ -5446,14 +5526,20 @@ fn open_pty_window_named(
 
     let cols = rect.w.saturating_sub(2).max(1);
     let rows = rect.h.saturating_sub(2).max(1);
+    let resolved = resolve_launch_environment(&profile.launch.layered(launch));
+    let cmd: Vec<String> = cmd.into_iter().chain(resolved.args).collect();
     let options = crate::pty::PtyLaunchOptions {
         env: profile
             .env
             .iter()
             .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .chain(resolved.env)
             .collect(),
+        cwd: resolved.cwd,
         top_bar: None,
         force_render_mode: None,
+        keys: profile.keys,
+        transcript: None,
     };
     let session = spawn_desktop_pty_with_fallback(&cmd, cols, rows, &options)?;
 
@@ -5655,7 +5741,9 @@ fn profile_from_settings(
         preferred_h,
         mouse_passthrough: profile.mouse_passthrough,
         open_fullscreen: profile.open_fullscreen,
+        keys: crate::pty::PtyKeyOptions::from(profile),
         env,
+        launch: profile.launch.clone(),
     }
 }
 
@@ -6773,6 +6861,9 @@ fn move_path(src: &Path, dst: &Path) -> Result<()> {
     }
 }
 
+/// The legacy desktop keeps its own per-user trash: deleted items are moved
+/// loose into `<user dir>/.fm_trash` and cannot be restored to where they
+/// came from. It is separate from the native file manager's trash.
 fn file_manager_trash_dir() -> PathBuf {
     if let Some(user) = get_current_user() {
         crate::config::user_dir(&user).join(".fm_trash")
@@ -8313,7 +8404,7 @@ fn draw_window(f: &mut ratatui::Frame, win: &DesktopWindow, focused: bool, pty_f
             draw_desktop_settings_window(f, area, settings, focused)
         }
         WindowKind::TextEditor(editor) => draw_desktop_text_editor_window(f, area, editor, focused),
-        WindowKind::PtyApp(app) => draw_pty_window(f, area, app, pty_force_plain),
+        WindowKind::PtyApp(app) => draw_pty_window(f, area, app, pty_force_plain, focused),
     }
 }
 
@@ -10783,7 +10874,13 @@ fn draw_desktop_settings_window(
     }
 }
 
-fn draw_pty_window(f: &mut ratatui::Frame, area: Rect, app: &PtyWindowState, force_plain: bool) {
+fn draw_pty_window(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    app: &PtyWindowState,
+    force_plain: bool,
+    focused: bool,
+) {
     let inner = Rect {
         x: area.x + 1,
         y: area.y + 1,
@@ -10793,7 +10890,9 @@ fn draw_pty_window(f: &mut ratatui::Frame, area: Rect, app: &PtyWindowState, for
     if inner.height == 0 || inner.width == 0 {
         return;
     }
-    app.session.render_with_hint(f, inner, force_plain);
+    let show_cursor = focused && crate::pty::cursor_blink_on();
+    app.session
+        .render_with_cursor(f, inner, force_plain, show_cursor);
 }
 
 fn draw_start_menu(f: &mut ratatui::Frame, size: Rect, state: &DesktopState) {
@@ -13024,8 +13123,8 @@ fn run_desktop_hub_action(
             refresh_desktop_hub_windows(state, DesktopHubKind::EditApps);
             refresh_desktop_hub_windows(state, DesktopHubKind::Applications);
         }
-        DesktopHubItemAction::LaunchCommand { title, cmd } => {
-            if let Err(err) = open_pty_window_named(terminal, state, &cmd, Some(title.as_str())) {
+        DesktopHubItemAction::LaunchCommand { title, entry } => {
+            if let Err(err) = launch_catalog_entry_window(terminal, state, &title, &entry) {
                 flash_message(terminal, &format!("Launch failed: {err}"), 1200)?;
             }
         }
@@ -26,6 +26,7 @@ pub fn embedded_terminal(terminal: &mut Term) -> Result<()> {
         ],
         top_bar: Some("ROBCO MAINTENANCE TERMLINK".into()),
         force_render_mode: Some(true),
+        ..Default::default()
     };
 
     crate::pty::run_pty_session_with_options(terminal, &shell, args, options)
@@ -1,5 +1,6 @@
 pub use robcos_shared::{
-    config, connections, core, default_apps, diag, launcher, pty, session, sound, status, ui,
+    autostart, catalog, config, connections, core, default_apps, diag, doc_index, history,
+    launcher, processes, pty, scheduler, session, sound, status, ui,
 };
 
 pub mod legacy;
@@ -1,4 +1,10 @@
 use super::about_screen::{draw_about_screen, TerminalAboutRequest};
+use super::autostart_screen::{draw_autostart_screen, TerminalAutostartRequest};
+use super::command_palette::{
+    filter_palette_entries, palette_entries, PaletteCommand, PaletteContext, PaletteEntry,
+    PaletteSessionCommand, COMMAND_PALETTE_KEYBINDING,
+};
+use super::commander_screen::{draw_terminal_commander, TerminalCommanderRequest};
 use super::connections_screen::{
     apply_search_query as apply_connection_search_query, draw_terminal_connections_screen,
     resolve_terminal_connections_request, TerminalConnectionsRequest, TerminalConnectionsState,
@@ -11,6 +17,8 @@ use super::desktop_app::{
     taskbar_window_order, DesktopHostedApp, DesktopMenuAction, DesktopMenuBuildContext,
     DesktopMenuItem, DesktopMenuSection, DesktopShellAction, DesktopWindow, DesktopWindowMenuEntry,
 };
+use super::desktop_app_import_service::{import_desktop_app, scan_desktop_apps, DesktopAppEntry};
+use super::desktop_autostart_service::AutostartQueue;
 use super::desktop_connections_service::{
     connect_connection_and_refresh_settings, connection_requires_password,
     connections_macos_disabled, connections_macos_disabled_hint, discovered_connection_label,
@@ -21,6 +29,7 @@ use super::desktop_default_apps_service::{
     apply_default_app_binding, binding_label_for_slot, default_app_slot_label,
     resolve_custom_default_app_binding, DefaultAppSlot,
 };
+use super::desktop_document_index_service::{DocumentIndexService, MIN_CONTENT_QUERY_CHARS};
 use super::desktop_documents_service::{
     add_document_category as add_desktop_document_category,
     delete_document_category as delete_desktop_document_category, document_category_names,
@@ -29,10 +38,16 @@ use super::desktop_documents_service::{
 use super::desktop_file_service::{
     load_text_document, open_directory_location, reveal_path_location, FileManagerLocation,
 };
+use super::desktop_history_service::{
+    clear_launch_history, frequent_history_entries, recent_history_entries, record_catalog_launch,
+    record_document_open, record_file_open, HistoryEntry, HistoryLaunchTarget,
+};
 use super::desktop_launcher_service::{
-    add_catalog_entry, catalog_names, delete_catalog_entry, parse_catalog_command_line,
-    rename_catalog_entry, resolve_catalog_launch, ProgramCatalog,
+    add_catalog_entry, catalog_entry, catalog_names, catalog_network_warning, delete_catalog_entry,
+    parse_catalog_command_line, rename_catalog_entry, resolve_catalog_launch, update_catalog_entry,
+    CatalogEntryDraft, ProgramCatalog, ResolvedProgramLaunch,
 };
+use super::desktop_scheduler_service::SchedulerRuntime;
 use super::desktop_search_service::{
     gather_spotlight_results, spotlight_category_tag, start_application_entries,
     start_document_entries, start_game_entries, start_network_entries, NativeSpotlightCategory,
@@ -62,7 +77,8 @@ use super::desktop_settings_service::{
     cycle_hacking_difficulty_in_settings, load_desktop_file_manager_settings,
     load_hacking_difficulty, load_settings_snapshot, persist_settings_draft,
     pty_force_render_mode as desktop_pty_force_render_mode,
-    pty_profile_for_command as desktop_pty_profile_for_command, reload_settings_snapshot,
+    pty_profile_for_entry as desktop_pty_profile_for_entry, record_recent_folder,
+    reload_settings_snapshot,
 };
 use super::desktop_shortcuts_service::{
     create_shortcut_from_start_action, delete_shortcut as delete_desktop_shortcut,
@@ -71,6 +87,7 @@ use super::desktop_shortcuts_service::{
     toggle_snap_to_grid as toggle_desktop_snap_to_grid,
     update_shortcut_properties as update_desktop_shortcut_properties, ShortcutPropertiesUpdate,
 };
+use super::desktop_spotlight_service::{SpotlightProvider, USER_MANUAL_PATH};
 use super::desktop_status_service::{
     cancelled_shell_status, clear_settings_status, clear_shell_status,
     invalid_input_settings_status, invalid_input_shell_status, mirror_shell_to_settings,
@@ -91,29 +108,35 @@ use super::desktop_user_service::{
     user_auth_method_label, user_exists,
 };
 use super::document_browser::{
-    activate_browser_selection, draw_terminal_document_browser, TerminalDocumentBrowserRequest,
+    activate_browser_selection, browser_preview_path, browser_rows, draw_terminal_document_browser,
+    draw_terminal_document_search, draw_terminal_places_jump, DocumentSearchRequest,
+    DocumentSearchView, PlacesJumpView, TerminalDocumentBrowserRequest,
 };
 use super::donkey_kong::{
     input_from_ctx as donkey_kong_input_from_ctx, DonkeyKongConfig, DonkeyKongGame,
     DonkeyKongTheme, BUILTIN_DONKEY_KONG_GAME,
 };
 use super::edit_menus_screen::{
-    draw_edit_menus_screen, EditMenuTarget, EditMenusEntries, TerminalEditMenusRequest,
-    TerminalEditMenusState,
+    apply_entry_detail, draw_edit_menus_screen, entry_details_from_draft, EditMenuDetailField,
+    EditMenuTarget, EditMenusEntries, TerminalEditMenusRequest, TerminalEditMenusState,
 };
 use super::editor_app::{
     EditorCommand, EditorTextAlign, EditorTextCommand, EditorWindow, EDITOR_APP_TITLE,
 };
 use super::file_manager::{FileManagerCommand, NativeFileManagerState};
 use super::file_manager_app::{
-    self, FileManagerCommandRequest, FileManagerDisplaySettingsUpdate, FileManagerEditRuntime,
-    FileManagerOpenTarget, FileManagerPickMode, FileManagerPickerCommit, FileManagerPromptAction,
+    self, folder_places, BatchRenameDialog, BatchRenameRule, ChecksumDialog, CommanderCommand,
+    CommanderOutcome, CommanderPanes, DirWatcher, FileManagerAction, FileManagerCommandRequest,
+    FileManagerDisplaySettingsUpdate, FileManagerEditRuntime, FileManagerOpenTarget,
+    FileManagerPickMode, FileManagerPickerCommit, FileManagerPromptAction,
     FileManagerPromptRequest, FileManagerSelectionActivation, FileManagerSettingsUpdate,
-    NativeFileManagerDragPayload, OpenWithLaunchRequest,
+    FileSearch, FindFilesDialog, NativeFileManagerDragPayload, OpenWithLaunchRequest,
+    PropertiesDialog, TransferQueue, TransferRequest, WATCH_DEBOUNCE,
 };
 use super::file_manager_desktop::{
     self, FileManagerDesktopFooterAction, FileManagerDesktopFooterRequest,
 };
+use super::file_manager_preview::{FilePreview, FilePreviewCache, PreviewTokenKind};
 use super::hacking_screen::{draw_hacking_screen, draw_locked_screen, HackingScreenEvent};
 use super::installer_screen::{
     add_package_to_menu, apply_filter as apply_installer_filter,
@@ -130,42 +153,62 @@ use super::menu::{
     resolve_embedded_pty_exit, resolve_hacking_screen_event, resolve_login_password_submission,
     resolve_login_selection_plan, resolve_main_menu_action, resolve_terminal_back_action,
     resolve_terminal_flash_action, resolve_user_password_confirm_prompt,
-    resolve_user_password_first_prompt, terminal_command_launch_plan, terminal_runtime_defaults,
-    terminal_screen_open_plan, terminal_settings_refresh_plan, terminal_shell_launch_plan,
-    user_management_screen_for_mode, MainMenuSelectionAction, TerminalBackAction,
-    TerminalBackContext, TerminalDesktopPtyExitPlan, TerminalEmbeddedPtyExitPlan,
-    TerminalFlashActionPlan, TerminalFlashPtyLaunchPlan, TerminalHackingPlan,
-    TerminalHackingUiEvent, TerminalLoginPasswordPlan, TerminalLoginScreenMode,
-    TerminalLoginSelectionPlan, TerminalLoginState, TerminalLoginSubmitAction,
-    TerminalNavigationState, TerminalPtyLaunchPlan, TerminalScreen, TerminalScreenOpenPlan,
-    TerminalSelectionIndexTarget, TerminalShellSurface, TerminalUserManagementPromptPlan,
-    TerminalUserPasswordFlow, UserManagementExecutionPlan, UserManagementMode,
+    resolve_user_password_first_prompt, terminal_catalog_launch_plan, terminal_command_launch_plan,
+    terminal_runtime_defaults, terminal_screen_open_plan, terminal_settings_refresh_plan,
+    terminal_shell_launch_plan, user_management_screen_for_mode, MainMenuSelectionAction,
+    TerminalBackAction, TerminalBackContext, TerminalDesktopPtyExitPlan,
+    TerminalEmbeddedPtyExitPlan, TerminalFlashActionPlan, TerminalFlashPtyLaunchPlan,
+    TerminalHackingPlan, TerminalHackingUiEvent, TerminalLoginPasswordPlan,
+    TerminalLoginScreenMode, TerminalLoginSelectionPlan, TerminalLoginState,
+    TerminalLoginSubmitAction, TerminalNavigationState, TerminalPtyLaunchPlan, TerminalScreen,
+    TerminalScreenOpenPlan, TerminalSelectionIndexTarget, TerminalShellSurface, TerminalTab,
+    TerminalTabId, TerminalUserManagementPromptPlan, TerminalUserPasswordFlow,
+    UserManagementExecutionPlan, UserManagementMode,
 };
 use super::nuke_codes_screen::{
     draw_nuke_codes_screen, fetch_nuke_codes, NukeCodesEvent, NukeCodesView,
 };
-use super::programs_screen::draw_programs_menu;
+use super::programs_screen::{draw_programs_menu, ProgramMenuEvent};
 use super::prompt::{
     draw_terminal_flash, draw_terminal_flash_boxed, draw_terminal_prompt_overlay, FlashAction,
     TerminalFlash, TerminalPrompt, TerminalPromptAction, TerminalPromptKind,
 };
 use super::prompt_flow::{handle_prompt_input, PromptOutcome};
 use super::pty_screen::{
-    draw_embedded_pty, draw_embedded_pty_in_ui_focused, handle_pty_input,
-    spawn_embedded_pty_with_options, NativePtyState, PtyScreenEvent, TERMINAL_MODE_PTY_CELL_H,
-    TERMINAL_MODE_PTY_CELL_W,
+    draw_embedded_pty, draw_embedded_pty_in_ui_focused, handle_pty_input, native_pty_tab_title,
+    spawn_embedded_pty_with_options, NativePtyState, NativePtyTabs, PtyScreenEvent,
+    TERMINAL_MODE_PTY_CELL_H, TERMINAL_MODE_PTY_CELL_W,
 };
 use super::retro_ui::{
     configure_visuals, current_palette, RetroScreen, FIXED_PTY_CELL_H, FIXED_PTY_CELL_W,
 };
+use super::scheduler_screen::{
+    draw_scheduler_screen, job_last_result_label, job_next_run_label, SchedulerUiState,
+    TerminalSchedulerRequest,
+};
 use super::settings_screen::{run_terminal_settings_screen, TerminalSettingsEvent};
 use super::shell_screen::{draw_login_screen, draw_main_menu_screen};
+use super::task_manager_screen::{
+    draw_task_manager_screen, format_memory, TaskManagerState, TaskSignal, TaskSortKey,
+    TerminalTaskManagerRequest, TASK_REFRESH_INTERVAL,
+};
+use crate::autostart::{
+    AutostartItem, AutostartMode, AutostartTarget, BuiltinWindow, MAX_AUTOSTART_DELAY_SECS,
+};
+use crate::catalog::CatalogRunMode;
 use crate::config::ConnectionKind;
 use crate::config::{
-    CliAcsMode, CliColorMode, DesktopIconSortMode, DesktopIconStyle, OpenMode, Settings,
-    WallpaperSizeMode, CUSTOM_THEME_NAME, THEMES,
+    CliAcsMode, CliColorMode, DesktopIconSortMode, DesktopIconStyle, LaunchEnvironment, OpenMode,
+    PtyBackspaceMode, Settings, WallpaperSizeMode, CUSTOM_THEME_NAME, THEMES,
 };
 use crate::core::auth::{AuthMethod, UserRecord};
+use crate::doc_index::ContentSnippet;
+use crate::history::{unix_now, LaunchKind};
+use crate::launcher::{
+    launch_args_from_text, launch_args_to_text, launch_env_from_text, launch_env_to_text,
+};
+use crate::pty::{PtyBroadcast, PtyInput, PtySessionId};
+use crate::scheduler::format_job_time;
 use crate::session;
 use anyhow::Result;
 use chrono::Local;
@@ -202,6 +245,21 @@ struct SessionState {
     is_admin: bool,
 }
 
+/// Catalog entry open in the Edit Menus details editor.
+#[derive(Debug, Clone)]
+struct CatalogEntryDetailsEditor {
+    target: EditMenuTarget,
+    name: String,
+    draft: CatalogEntryDraft,
+}
+
+#[derive(Debug, Clone)]
+struct DesktopAppImportRow {
+    app: DesktopAppEntry,
+    selected: bool,
+    catalog: ProgramCatalog,
+}
+
 #[derive(Debug, Clone)]
 struct SettingsWindow {
     open: bool,
@@ -210,12 +268,16 @@ struct SettingsWindow {
     panel: NativeSettingsPanel,
     default_app_custom_text_code: String,
     default_app_custom_ebook: String,
+    autostart_input: String,
+    autostart_mode: AutostartMode,
     scanned_networks: Vec<DiscoveredConnection>,
     scanned_bluetooth: Vec<DiscoveredConnection>,
     connection_password: String,
     edit_target: EditMenuTarget,
     edit_name_input: String,
     edit_value_input: String,
+    edit_details: Option<CatalogEntryDetailsEditor>,
+    desktop_import: Option<Vec<DesktopAppImportRow>>,
     cli_profile_slot: GuiCliProfileSlot,
     user_selected: String,
     user_selected_loaded_for: String,
@@ -250,6 +312,7 @@ struct AssetCache {
     icon_settings: TextureHandle,
     icon_file_manager: TextureHandle,
     icon_terminal: TextureHandle,
+    icon_trash: TextureHandle,
     icon_applications: TextureHandle,
     icon_installer: TextureHandle,
     icon_nuke_codes: TextureHandle,
@@ -302,6 +365,7 @@ enum ContextMenuAction {
     Paste,
     Duplicate,
     Delete,
+    RevealInFolder,
     Properties,
     PasteToDesktop,
     NewFolder,
@@ -322,6 +386,10 @@ enum ContextMenuAction {
         target: EditMenuTarget,
         name: String,
     },
+    EditStartMenuEntryDetails {
+        target: EditMenuTarget,
+        name: String,
+    },
     DeleteShortcut(usize),
     SortDesktopIcons(DesktopIconSortMode),
     ToggleSnapToGrid,
@@ -348,6 +416,27 @@ enum DesktopHeaderAction {
     Close,
 }
 
+struct DesktopPtyTabStripEntry {
+    id: TerminalTabId,
+    title: String,
+    session_id: PtySessionId,
+    /// Input typed into any broadcasting session reaches this tab.
+    broadcast: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DesktopPtyTabAction {
+    None,
+    Activate(TerminalTabId),
+    MoveLeft(TerminalTabId),
+    MoveRight(TerminalTabId),
+    Rename(TerminalTabId),
+    Close(TerminalTabId),
+    NewTab,
+    ToggleBroadcast,
+    ToggleBroadcastOptOut(PtySessionId),
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum StartSubmenu {
     System,
@@ -364,6 +453,8 @@ enum StartLeaf {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum StartSystemAction {
     ProgramInstaller,
+    TaskManager,
+    Scheduler,
     Terminal,
     FileManager,
     Settings,
@@ -400,8 +491,10 @@ const START_ROOT_VIS_ROWS: [Option<usize>; 9] = [
     Some(7),
 ];
 
-const START_SYSTEM_ITEMS: [(&str, StartSystemAction); 5] = [
+const START_SYSTEM_ITEMS: [(&str, StartSystemAction); 7] = [
     ("Program Installer", StartSystemAction::ProgramInstaller),
+    ("Task Manager", StartSystemAction::TaskManager),
+    ("Scheduler", StartSystemAction::Scheduler),
     ("Terminal", StartSystemAction::Terminal),
     ("File Manager", StartSystemAction::FileManager),
     ("Settings", StartSystemAction::Settings),
@@ -436,6 +529,8 @@ fn start_root_action_for_idx(idx: usize) -> Option<StartRootAction> {
 }
 
 const BUILTIN_NUKE_CODES_APP: &str = "Nuke Codes";
+const RECENT_HISTORY_LIMIT: usize = 12;
+const FREQUENT_HISTORY_LIMIT: usize = 5;
 const BUILTIN_TEXT_EDITOR_APP: &str = EDITOR_APP_TITLE;
 
 const TERMINAL_SCREEN_COLS: usize = 92;
@@ -482,6 +577,33 @@ fn terminal_layout_for_scale(_scale: f32) -> TerminalLayout {
     }
 }
 
+fn terminate_native_pty_tabs(tabs: &mut NativePtyTabs) {
+    for mut tab in tabs.drain() {
+        tab.state.session.terminate();
+    }
+}
+
+/// Every PTY the native shell owns: the terminal-mode PTY and desktop tabs of
+/// the active session plus those of parked sessions.
+fn native_pty_states_mut<'a>(
+    terminal_pty: &'a mut Option<NativePtyState>,
+    desktop_pty_tabs: &'a mut NativePtyTabs,
+    parked: &'a mut HashMap<usize, ParkedSessionState>,
+) -> Vec<&'a mut NativePtyState> {
+    let mut states: Vec<&'a mut NativePtyState> = terminal_pty.iter_mut().collect();
+    states.extend(desktop_pty_tabs.iter_mut().map(|tab| &mut tab.state));
+    for session in parked.values_mut() {
+        states.extend(session.terminal_pty.iter_mut());
+        states.extend(
+            session
+                .desktop_pty_tabs
+                .iter_mut()
+                .map(|tab| &mut tab.state),
+        );
+    }
+    states
+}
+
 fn retro_footer_height() -> f32 {
     31.0
 }
@@ -566,6 +688,7 @@ pub struct RobcoNativeApp {
     login: TerminalLoginState,
     session: Option<SessionState>,
     file_manager: NativeFileManagerState,
+    file_manager_commander: CommanderPanes,
     editor: EditorWindow,
     settings: SettingsWindow,
     applications: ApplicationsWindow,
@@ -573,6 +696,14 @@ pub struct RobcoNativeApp {
     donkey_kong: Option<DonkeyKongGame>,
     desktop_nuke_codes_open: bool,
     desktop_installer: DesktopInstallerState,
+    task_manager: TaskManagerState,
+    scheduler: SchedulerRuntime,
+    scheduler_ui: SchedulerUiState,
+    autostart: AutostartQueue,
+    autostart_selected: usize,
+    document_index: DocumentIndexService,
+    document_search: Option<DocumentSearchView>,
+    document_places: Option<PlacesJumpView>,
     terminal_mode: TerminalModeWindow,
     desktop_window_states: HashMap<DesktopWindow, DesktopWindowState>,
     desktop_active_window: Option<DesktopWindow>,
@@ -588,6 +719,7 @@ pub struct RobcoNativeApp {
     terminal_nav: TerminalNavigationState,
     terminal_nuke_codes: NukeCodesView,
     terminal_pty: Option<NativePtyState>,
+    desktop_pty_tabs: NativePtyTabs,
     terminal_installer: TerminalInstallerState,
     terminal_edit_menus: TerminalEditMenusState,
     terminal_connections: TerminalConnectionsState,
@@ -595,13 +727,29 @@ pub struct RobcoNativeApp {
     terminal_flash: Option<TerminalFlash>,
     session_leader_until: Option<Instant>,
     session_runtime: HashMap<usize, ParkedSessionState>,
+    /// Broadcast mode spans sessions, so it is never parked.
+    pty_broadcast: PtyBroadcast,
     desktop_window_generation_seed: u64,
     file_manager_runtime: FileManagerEditRuntime,
+    file_manager_previews: FilePreviewCache,
+    file_manager_transfers: TransferQueue,
+    file_manager_transfers_open: bool,
+    file_manager_transfer_apply_all: bool,
+    /// Last folder written to the recent-folders list.
+    file_manager_recent_cwd: PathBuf,
+    file_manager_watcher: DirWatcher,
+    /// Document browser entry under the cursor before the last change, so
+    /// a refresh can follow it to its new row.
+    document_browser_watch_path: Option<PathBuf>,
     asset_cache: Option<AssetCache>,
     context_menu_action: Option<ContextMenuAction>,
     shell_status: String,
     shortcut_properties: Option<ShortcutPropertiesState>,
     start_menu_rename: Option<StartMenuRenameState>,
+    file_manager_batch_rename: Option<BatchRenameDialog>,
+    file_manager_find: Option<FindFilesDialog>,
+    file_manager_properties: Option<PropertiesDialog>,
+    file_manager_checksums: Option<ChecksumDialog>,
     picking_icon_for_shortcut: Option<usize>,
     picking_wallpaper: bool,
     shortcut_icon_cache: HashMap<String, egui::TextureHandle>,
@@ -614,10 +762,15 @@ pub struct RobcoNativeApp {
     spotlight_results: Vec<NativeSpotlightResult>,
     spotlight_last_query: String,
     spotlight_last_tab: u8,
+    // Command palette
+    palette_open: bool,
+    palette_query: String,
+    palette_selected: usize,
 }
 
 struct ParkedSessionState {
     file_manager: NativeFileManagerState,
+    file_manager_commander: CommanderPanes,
     editor: EditorWindow,
     settings: SettingsWindow,
     applications: ApplicationsWindow,
@@ -639,6 +792,7 @@ struct ParkedSessionState {
     terminal_nav: TerminalNavigationState,
     terminal_nuke_codes: NukeCodesView,
     terminal_pty: Option<NativePtyState>,
+    desktop_pty_tabs: NativePtyTabs,
     terminal_installer: TerminalInstallerState,
     terminal_edit_menus: TerminalEditMenusState,
     terminal_connections: TerminalConnectionsState,
@@ -649,11 +803,17 @@ struct ParkedSessionState {
     file_manager_runtime: FileManagerEditRuntime,
     shell_status: String,
     start_menu_rename: Option<StartMenuRenameState>,
+    file_manager_batch_rename: Option<BatchRenameDialog>,
+    file_manager_find: Option<FindFilesDialog>,
+    file_manager_properties: Option<PropertiesDialog>,
+    file_manager_checksums: Option<ChecksumDialog>,
 }
 
 impl Default for RobcoNativeApp {
     fn default() -> Self {
         restore_current_user_from_last_session();
+        // Anything left behind fails again quietly and is retried next launch.
+        let _ = robcos_native_file_manager_app::trash::migrate_legacy_trash();
         session::clear_sessions();
         session::take_switch_request();
         let settings_draft = load_settings_snapshot();
@@ -663,6 +823,7 @@ impl Default for RobcoNativeApp {
             login: TerminalLoginState::default(),
             session: None,
             file_manager: NativeFileManagerState::new(home_dir_fallback()),
+            file_manager_commander: CommanderPanes::new(home_dir_fallback()),
             editor: EditorWindow::default(),
             settings: SettingsWindow {
                 open: false,
@@ -671,12 +832,16 @@ impl Default for RobcoNativeApp {
                 panel: settings_ui_defaults.panel,
                 default_app_custom_text_code: settings_ui_defaults.default_app_custom_text_code,
                 default_app_custom_ebook: settings_ui_defaults.default_app_custom_ebook,
+                autostart_input: String::new(),
+                autostart_mode: AutostartMode::default(),
                 scanned_networks: Vec::new(),
                 scanned_bluetooth: Vec::new(),
                 connection_password: String::new(),
                 edit_target: EditMenuTarget::Applications,
                 edit_name_input: String::new(),
                 edit_value_input: String::new(),
+                edit_details: None,
+                desktop_import: None,
                 cli_profile_slot: settings_ui_defaults.cli_profile_slot,
                 user_selected: settings_ui_defaults.user_selected,
                 user_selected_loaded_for: settings_ui_defaults.user_selected_loaded_for,
@@ -694,6 +859,14 @@ impl Default for RobcoNativeApp {
             donkey_kong: None,
             desktop_nuke_codes_open: false,
             desktop_installer: DesktopInstallerState::default(),
+            task_manager: TaskManagerState::default(),
+            scheduler: SchedulerRuntime::default(),
+            scheduler_ui: SchedulerUiState::default(),
+            autostart: AutostartQueue::default(),
+            autostart_selected: 0,
+            document_index: DocumentIndexService::default(),
+            document_search: None,
+            document_places: None,
             terminal_mode: TerminalModeWindow::default(),
             desktop_window_states: HashMap::new(),
             desktop_active_window: None,
@@ -709,6 +882,7 @@ impl Default for RobcoNativeApp {
             terminal_nav: terminal_defaults,
             terminal_nuke_codes: NukeCodesView::default(),
             terminal_pty: None,
+            desktop_pty_tabs: NativePtyTabs::default(),
             terminal_installer: TerminalInstallerState::default(),
             terminal_edit_menus: TerminalEditMenusState::default(),
             terminal_connections: TerminalConnectionsState::default(),
@@ -716,13 +890,25 @@ impl Default for RobcoNativeApp {
             terminal_flash: None,
             session_leader_until: None,
             session_runtime: HashMap::new(),
+            pty_broadcast: PtyBroadcast::default(),
             desktop_window_generation_seed: 1,
             file_manager_runtime: FileManagerEditRuntime::default(),
+            file_manager_previews: FilePreviewCache::default(),
+            file_manager_transfers: TransferQueue::default(),
+            file_manager_transfers_open: false,
+            file_manager_transfer_apply_all: false,
+            file_manager_recent_cwd: PathBuf::new(),
+            file_manager_watcher: DirWatcher::new(),
+            document_browser_watch_path: None,
             asset_cache: None,
             context_menu_action: None,
             shell_status: String::new(),
             shortcut_properties: None,
             start_menu_rename: None,
+            file_manager_batch_rename: None,
+            file_manager_find: None,
+            file_manager_properties: None,
+            file_manager_checksums: None,
             picking_icon_for_shortcut: None,
             picking_wallpaper: false,
             shortcut_icon_cache: HashMap::new(),
@@ -734,6 +920,9 @@ impl Default for RobcoNativeApp {
             spotlight_results: Vec::new(),
             spotlight_last_query: String::new(),
             spotlight_last_tab: u8::MAX,
+            palette_open: false,
+            palette_query: String::new(),
+            palette_selected: 0,
         }
     }
 }
@@ -828,6 +1017,12 @@ impl RobcoNativeApp {
                 include_bytes!("../Icons/pixel--code-block-solid.svg"),
                 Some(ICON_SIZE),
             ),
+            icon_trash: Self::load_svg_icon(
+                ctx,
+                "icon_trash",
+                include_bytes!("../Icons/pixel--trash-solid.svg"),
+                Some(ICON_SIZE),
+            ),
             icon_applications: Self::load_svg_icon(
                 ctx,
                 "icon_applications",
@@ -1059,12 +1254,14 @@ impl RobcoNativeApp {
         self.settings.panel = defaults.panel;
         self.settings.default_app_custom_text_code = defaults.default_app_custom_text_code;
         self.settings.default_app_custom_ebook = defaults.default_app_custom_ebook;
+        self.settings.autostart_input.clear();
         self.settings.scanned_networks.clear();
         self.settings.scanned_bluetooth.clear();
         self.settings.connection_password.clear();
         self.settings.edit_target = EditMenuTarget::Applications;
         self.settings.edit_name_input.clear();
         self.settings.edit_value_input.clear();
+        self.settings.edit_details = None;
         self.settings.cli_profile_slot = defaults.cli_profile_slot;
         self.settings.user_create_username.clear();
         self.settings.user_create_auth = defaults.user_create_auth;
@@ -1130,8 +1327,11 @@ impl RobcoNativeApp {
         let Some(idx) = active_native_session_index() else {
             return;
         };
+        // Pending autostart items belong to the login being left.
+        self.autostart.clear();
         let parked = ParkedSessionState {
             file_manager: self.file_manager.clone(),
+            file_manager_commander: self.file_manager_commander.clone(),
             editor: self.editor.clone(),
             settings: self.settings.clone(),
             applications: self.applications.clone(),
@@ -1153,6 +1353,7 @@ impl RobcoNativeApp {
             terminal_nav: self.current_terminal_navigation_state(),
             terminal_nuke_codes: self.terminal_nuke_codes.clone(),
             terminal_pty: self.terminal_pty.take(),
+            desktop_pty_tabs: std::mem::take(&mut self.desktop_pty_tabs),
             terminal_installer: std::mem::take(&mut self.terminal_installer),
             terminal_edit_menus: std::mem::take(&mut self.terminal_edit_menus),
             terminal_connections: std::mem::take(&mut self.terminal_connections),
@@ -1163,6 +1364,10 @@ impl RobcoNativeApp {
             file_manager_runtime: self.file_manager_runtime.clone(),
             shell_status: std::mem::take(&mut self.shell_status),
             start_menu_rename: self.start_menu_rename.take(),
+            file_manager_batch_rename: self.file_manager_batch_rename.take(),
+            file_manager_find: self.file_manager_find.take(),
+            file_manager_properties: self.file_manager_properties.take(),
+            file_manager_checksums: self.file_manager_checksums.take(),
         };
         self.session_runtime.insert(idx, parked);
     }
@@ -1197,6 +1402,7 @@ impl RobcoNativeApp {
             return false;
         };
         self.file_manager = parked.file_manager;
+        self.file_manager_commander = parked.file_manager_commander;
         self.editor = parked.editor;
         self.settings = parked.settings;
         self.applications = parked.applications;
@@ -1218,6 +1424,7 @@ impl RobcoNativeApp {
         self.apply_terminal_navigation_state(parked.terminal_nav);
         self.terminal_nuke_codes = parked.terminal_nuke_codes;
         self.terminal_pty = parked.terminal_pty;
+        self.desktop_pty_tabs = parked.desktop_pty_tabs;
         self.terminal_installer = parked.terminal_installer;
         self.terminal_edit_menus = parked.terminal_edit_menus;
         self.terminal_connections = parked.terminal_connections;
@@ -1229,6 +1436,10 @@ impl RobcoNativeApp {
         self.context_menu_action = None;
         self.shell_status = parked.shell_status;
         self.start_menu_rename = parked.start_menu_rename;
+        self.file_manager_batch_rename = parked.file_manager_batch_rename;
+        self.file_manager_find = parked.file_manager_find;
+        self.file_manager_properties = parked.file_manager_properties;
+        self.file_manager_checksums = parked.file_manager_checksums;
         true
     }
 
@@ -1280,10 +1491,13 @@ impl RobcoNativeApp {
         if let Some(mut pty) = self.terminal_pty.take() {
             pty.session.terminate();
         }
+        terminate_native_pty_tabs(&mut self.desktop_pty_tabs);
+        self.scheduler.terminate_all(unix_now());
         for parked in self.session_runtime.values_mut() {
             if let Some(mut pty) = parked.terminal_pty.take() {
                 pty.session.terminate();
             }
+            terminate_native_pty_tabs(&mut parked.desktop_pty_tabs);
         }
     }
 
@@ -1305,10 +1519,12 @@ impl RobcoNativeApp {
         if let Some(mut pty) = self.terminal_pty.take() {
             pty.session.terminate();
         }
+        terminate_native_pty_tabs(&mut self.desktop_pty_tabs);
         if let Some(mut parked) = self.session_runtime.remove(&closing_idx) {
             if let Some(mut pty) = parked.terminal_pty.take() {
                 pty.session.terminate();
             }
+            terminate_native_pty_tabs(&mut parked.desktop_pty_tabs);
         }
 
         // Session indexes are contiguous; shift parked state keys down after removal.
@@ -1432,8 +1648,10 @@ impl RobcoNativeApp {
             DesktopWindow::DonkeyKong => self.donkey_kong_window.open,
             DesktopWindow::NukeCodes => self.desktop_nuke_codes_open,
             DesktopWindow::Installer => self.desktop_installer.open,
+            DesktopWindow::TaskManager => self.task_manager.open,
+            DesktopWindow::Scheduler => self.scheduler_ui.open,
             DesktopWindow::TerminalMode => self.terminal_mode.open,
-            DesktopWindow::PtyApp => self.terminal_pty.is_some(),
+            DesktopWindow::PtyApp => !self.desktop_pty_tabs.is_empty(),
         }
     }
 
@@ -1465,6 +1683,8 @@ impl RobcoNativeApp {
             DesktopWindow::DonkeyKong => Id::new(("native_donkey_kong", gen)),
             DesktopWindow::NukeCodes => Id::new(("native_nuke_codes", gen)),
             DesktopWindow::Installer => Id::new(("native_installer", gen)),
+            DesktopWindow::TaskManager => Id::new(("native_task_manager", gen)),
+            DesktopWindow::Scheduler => Id::new(("native_scheduler", gen)),
             DesktopWindow::PtyApp => Id::new(("native_desktop_pty", gen)),
             DesktopWindow::TerminalMode => Id::new(("native_terminal_mode", gen)),
         }
@@ -1568,6 +1788,8 @@ impl RobcoNativeApp {
             DesktopWindow::DonkeyKong => egui::vec2(820.0, 720.0),
             DesktopWindow::NukeCodes => egui::vec2(640.0, 420.0),
             DesktopWindow::Installer => egui::vec2(800.0, 600.0),
+            DesktopWindow::TaskManager => egui::vec2(760.0, 520.0),
+            DesktopWindow::Scheduler => egui::vec2(780.0, 480.0),
             DesktopWindow::TerminalMode => egui::vec2(720.0, 500.0),
             DesktopWindow::PtyApp => egui::vec2(960.0, 600.0),
         }
@@ -1628,12 +1850,12 @@ impl RobcoNativeApp {
             DesktopWindow::DonkeyKong => self.donkey_kong_window.open = open,
             DesktopWindow::NukeCodes => self.desktop_nuke_codes_open = open,
             DesktopWindow::Installer => self.desktop_installer.open = open,
+            DesktopWindow::TaskManager => self.task_manager.open = open,
+            DesktopWindow::Scheduler => self.scheduler_ui.open = open,
             DesktopWindow::TerminalMode => self.terminal_mode.open = open,
             DesktopWindow::PtyApp => {
                 if !open {
-                    if let Some(mut pty) = self.terminal_pty.take() {
-                        pty.session.terminate();
-                    }
+                    terminate_native_pty_tabs(&mut self.desktop_pty_tabs);
                 }
             }
         }
@@ -1652,7 +1874,7 @@ impl RobcoNativeApp {
     }
 
     fn first_open_desktop_window(&self) -> Option<DesktopWindow> {
-        const ORDER: [DesktopWindow; 9] = [
+        const ORDER: [DesktopWindow; 11] = [
             DesktopWindow::FileManager,
             DesktopWindow::Editor,
             DesktopWindow::Settings,
@@ -1660,6 +1882,8 @@ impl RobcoNativeApp {
             DesktopWindow::DonkeyKong,
             DesktopWindow::NukeCodes,
             DesktopWindow::Installer,
+            DesktopWindow::TaskManager,
+            DesktopWindow::Scheduler,
             DesktopWindow::TerminalMode,
             DesktopWindow::PtyApp,
         ];
@@ -1722,6 +1946,10 @@ impl RobcoNativeApp {
     }
 
     fn close_desktop_window(&mut self, window: DesktopWindow) {
+        if window == DesktopWindow::PtyApp {
+            self.request_close_desktop_pty_window();
+            return;
+        }
         let was_open = self.desktop_window_is_open(window);
         self.set_desktop_window_open(window, false);
         if was_open {
@@ -1882,6 +2110,7 @@ impl RobcoNativeApp {
 
     fn launch_open_with_command(&mut self, path: &Path, command_line: &str) -> Result<String> {
         let launch = file_manager_app::prepare_open_with_launch(path, command_line)?;
+        record_file_open(path);
         Ok(self.launch_open_with_request(launch))
     }
 
@@ -1907,6 +2136,9 @@ impl RobcoNativeApp {
                 FileManagerPromptAction::ApplySettingsUpdate(update) => {
                     self.apply_file_manager_settings_update(update);
                 }
+                FileManagerPromptAction::QueueTransfer(request) => {
+                    self.queue_file_manager_transfer(request);
+                }
                 FileManagerPromptAction::ReportStatus(status) => {
                     self.shell_status = status;
                 }
@@ -1934,24 +2166,158 @@ impl RobcoNativeApp {
         direct
     }
 
-    fn file_manager_move_paths_to_dir(
-        &mut self,
-        paths: Vec<PathBuf>,
-        target_dir: &Path,
-    ) -> Result<String> {
-        self.file_manager_runtime
-            .move_paths_to_dir(&mut self.file_manager, paths, target_dir)
-    }
-
     fn file_manager_drop_allowed(paths: &[PathBuf], target_dir: &Path) -> bool {
         FileManagerEditRuntime::drop_allowed(paths, target_dir)
     }
 
     fn file_manager_handle_drop_to_dir(&mut self, paths: Vec<PathBuf>, target_dir: PathBuf) {
-        self.shell_status = match self.file_manager_move_paths_to_dir(paths, &target_dir) {
-            Ok(message) => message,
-            Err(err) => format!("File action failed: {err}"),
+        self.queue_file_manager_transfer(TransferRequest::move_to(paths, target_dir));
+    }
+
+    fn queue_file_manager_transfer(&mut self, request: TransferRequest) -> u64 {
+        self.shell_status = request.title();
+        let trash_max_mb = crate::config::get_settings()
+            .desktop_file_manager
+            .trash_max_mb;
+        self.file_manager_transfers
+            .set_trash_max_bytes(trash_max_mb.saturating_mul(1024 * 1024));
+        let id = self.file_manager_transfers.enqueue(request);
+        self.file_manager_transfers_open = true;
+        id
+    }
+
+    /// Applies finished transfers; keeps repainting while jobs run so the
+    /// progress stays live.
+    fn poll_file_manager_transfers(&mut self, ctx: &Context) {
+        while let Some(outcome) = self.file_manager_transfers.poll() {
+            if let Some(dialog) = self.file_manager_checksums.as_mut() {
+                dialog.finish_trash(&outcome);
+            }
+            self.shell_status = self.file_manager_runtime.finish_transfer(
+                &mut self.file_manager,
+                outcome,
+                self.file_manager_transfers.trash(),
+            );
+        }
+        if !self.file_manager_transfers.is_idle() {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+    }
+
+    /// Keeps frames coming while search results stream in.
+    fn poll_file_manager_search(&self, ctx: &Context) {
+        if self
+            .file_manager
+            .search
+            .as_ref()
+            .is_some_and(FileSearch::is_running)
+        {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+    }
+
+    fn track_file_manager_recent_folder(&mut self) {
+        let cwd = &self.file_manager.cwd;
+        if *cwd == self.file_manager_recent_cwd {
+            return;
+        }
+        self.file_manager_recent_cwd = cwd.clone();
+        if cwd.is_dir()
+            && !self.file_manager.is_trash_view()
+            && !self.file_manager.is_archive_view()
+        {
+            record_recent_folder(&mut self.settings.draft, &cwd.to_string_lossy());
+        }
+    }
+
+    /// Folders currently on screen: the file manager tabs (and the other
+    /// commander pane) while browsing, plus the folders desktop icons are
+    /// loaded from.
+    fn file_manager_watch_dirs(&self) -> Vec<PathBuf> {
+        let browsing = if self.desktop_mode_open {
+            self.file_manager.open
+        } else {
+            matches!(
+                self.terminal_nav.screen,
+                TerminalScreen::DocumentBrowser | TerminalScreen::Commander
+            )
         };
+        let mut dirs = Vec::new();
+        if browsing {
+            let mut panes = vec![&self.file_manager];
+            if self.file_manager_commander_active() {
+                panes.push(&self.file_manager_commander.inactive);
+            }
+            for pane in panes {
+                dirs.extend(pane.tabs.iter().cloned());
+                if !pane.is_search_view() {
+                    dirs.push(pane.cwd.clone());
+                }
+            }
+        }
+        if self.desktop_mode_open {
+            dirs.extend(
+                self.settings
+                    .draft
+                    .desktop_shortcuts
+                    .iter()
+                    .filter_map(|shortcut| shortcut.icon_path.as_deref())
+                    .filter_map(|path| Path::new(path).parent().map(Path::to_path_buf)),
+            );
+        }
+        dirs
+    }
+
+    /// Refreshes views whose folders were changed by something else, such
+    /// as a build in a PTY window or another session.
+    fn sync_file_manager_watches(&mut self, ctx: &Context) {
+        if !self.file_manager_watcher.has_waker() {
+            let ctx = ctx.clone();
+            self.file_manager_watcher
+                .set_waker(move || ctx.request_repaint_after(WATCH_DEBOUNCE));
+        }
+        let dirs = self.file_manager_watch_dirs();
+        self.file_manager_watcher.set_dirs(dirs);
+        let changed = self.file_manager_watcher.poll();
+        if changed.is_empty() {
+            if self.file_manager_watcher.has_pending() {
+                ctx.request_repaint_after(WATCH_DEBOUNCE);
+            } else {
+                self.document_browser_watch_path = self.document_browser_selected_path();
+            }
+            return;
+        }
+        self.file_manager.refresh_changed_dirs(&changed);
+        self.file_manager_commander
+            .inactive
+            .refresh_changed_dirs(&changed);
+        if self.terminal_nav.screen == TerminalScreen::DocumentBrowser {
+            let rows = browser_rows(&self.file_manager);
+            let followed = self
+                .document_browser_watch_path
+                .as_ref()
+                .and_then(|path| rows.iter().position(|row| row.path.as_ref() == Some(path)));
+            self.terminal_nav.browser_idx = followed
+                .unwrap_or(self.terminal_nav.browser_idx)
+                .min(rows.len().saturating_sub(1));
+        }
+        // Custom desktop icons and SVG thumbnails are cached by file path.
+        self.shortcut_icon_cache.retain(|path, _| {
+            Path::new(path)
+                .parent()
+                .is_none_or(|parent| !changed.iter().any(|dir| dir == parent))
+        });
+        ctx.request_repaint();
+    }
+
+    fn document_browser_selected_path(&self) -> Option<PathBuf> {
+        if self.terminal_nav.screen != TerminalScreen::DocumentBrowser {
+            return None;
+        }
+        browser_rows(&self.file_manager)
+            .into_iter()
+            .nth(self.terminal_nav.browser_idx)
+            .and_then(|row| row.path)
     }
 
     fn run_file_manager_command(&mut self, command: FileManagerCommand) {
@@ -1969,15 +2335,74 @@ impl RobcoNativeApp {
             FileManagerCommandRequest::OpenPrompt(request) => {
                 self.open_file_manager_prompt(request);
             }
+            FileManagerCommandRequest::OpenBatchRename(entries) => {
+                self.file_manager_batch_rename = Some(BatchRenameDialog {
+                    entries,
+                    rule: BatchRenameRule::default(),
+                });
+            }
+            FileManagerCommandRequest::OpenFindFiles(dialog) => {
+                self.file_manager_find = Some(dialog);
+            }
+            FileManagerCommandRequest::OpenProperties(path) => {
+                match PropertiesDialog::open(&path, &load_desktop_file_manager_settings()) {
+                    Ok(dialog) => self.file_manager_properties = Some(dialog),
+                    Err(err) => self.shell_status = format!("Properties failed: {err}"),
+                }
+            }
+            FileManagerCommandRequest::OpenChecksums(task) => match ChecksumDialog::start(task) {
+                Ok(dialog) => self.file_manager_checksums = Some(dialog),
+                Err(err) => self.shell_status = format!("Checksums failed: {err}"),
+            },
+            FileManagerCommandRequest::Commander(command) => {
+                self.run_file_manager_commander_command(command);
+            }
+            FileManagerCommandRequest::QueueTransfer(request) => {
+                self.queue_file_manager_transfer(request);
+            }
+            FileManagerCommandRequest::ShowTransfers => {
+                if self.file_manager_transfers.is_idle() {
+                    self.shell_status = "No transfers running.".to_string();
+                } else {
+                    self.file_manager_transfers_open = true;
+                }
+            }
             FileManagerCommandRequest::ApplyDisplaySettings(update) => {
                 self.apply_file_manager_display_settings_update(update);
             }
+            FileManagerCommandRequest::ApplySettings { update, status } => {
+                self.apply_file_manager_settings_update(update);
+                self.shell_status = status;
+            }
             FileManagerCommandRequest::ReportStatus(status) => {
                 self.shell_status = status;
             }
         }
     }
 
+    fn file_manager_commander_active(&self) -> bool {
+        matches!(self.terminal_nav.screen, TerminalScreen::Commander)
+            || (self.desktop_mode_open && load_desktop_file_manager_settings().dual_pane)
+    }
+
+    fn run_file_manager_commander_command(&mut self, command: CommanderCommand) {
+        if !self.file_manager_commander_active() {
+            self.shell_status = "Turn on dual-pane mode first.".to_string();
+            return;
+        }
+        match file_manager_app::run_commander_command(
+            command,
+            &mut self.file_manager,
+            &mut self.file_manager_commander,
+        ) {
+            Ok(CommanderOutcome::Status(status)) => self.shell_status = status,
+            Ok(CommanderOutcome::Transfer(request)) => {
+                self.queue_file_manager_transfer(request);
+            }
+            Err(err) => self.shell_status = format!("File action failed: {err}"),
+        }
+    }
+
     fn dispatch_context_menu_action(&mut self, _ctx: &Context) {
         let Some(action) = self.context_menu_action.take() else {
             return;
@@ -2004,8 +2429,11 @@ impl RobcoNativeApp {
                 self.run_file_manager_command(FileManagerCommand::Duplicate)
             }
             ContextMenuAction::Delete => self.run_file_manager_command(FileManagerCommand::Delete),
+            ContextMenuAction::RevealInFolder => {
+                self.run_file_manager_command(FileManagerCommand::RevealInFolder)
+            }
             ContextMenuAction::Properties => {
-                self.shell_status = "Properties dialog is not implemented yet.".to_string();
+                self.run_file_manager_command(FileManagerCommand::Properties)
             }
             ContextMenuAction::PasteToDesktop => {
                 self.shell_status = "Desktop paste is not implemented yet.".to_string();
@@ -2038,6 +2466,21 @@ impl RobcoNativeApp {
                 self.delete_program_entry(target, &name);
                 self.close_start_menu();
             }
+            ContextMenuAction::EditStartMenuEntryDetails { target, name } => {
+                self.close_start_menu();
+                let Some(draft) = Self::catalog_entry_draft(target, &name) else {
+                    self.shell_status = format!("{name} was not found.");
+                    return;
+                };
+                self.open_desktop_window(DesktopWindow::Settings);
+                self.settings.panel = NativeSettingsPanel::EditMenus;
+                self.settings.edit_target = target;
+                self.settings.edit_details = Some(CatalogEntryDetailsEditor {
+                    target,
+                    name,
+                    draft,
+                });
+            }
             ContextMenuAction::DeleteShortcut(idx) => {
                 if delete_desktop_shortcut(&mut self.settings.draft, idx) {
                     self.persist_native_settings();
@@ -2242,6 +2685,7 @@ impl RobcoNativeApp {
         let tex_settings = cache.icon_settings.clone();
         let tex_nuke_codes = cache.icon_nuke_codes.clone();
         let tex_terminal = cache.icon_terminal.clone();
+        let tex_trash = cache.icon_trash.clone();
         let tex_shortcut_badge = cache.icon_shortcut_badge.clone();
         let tex_app = cache.icon_app.clone();
         let tex_connections = cache.icon_connections.clone();
@@ -2281,6 +2725,7 @@ impl RobcoNativeApp {
         );
         let mut open_window: Option<DesktopWindow> = None;
         let mut open_terminal = false;
+        let mut open_trash = false;
         let mut shortcut_action: Option<ContextMenuAction> = None;
         let mut needs_persist = false;
 
@@ -2295,6 +2740,7 @@ impl RobcoNativeApp {
                 DesktopBuiltinIconKind::Settings => &tex_settings,
                 DesktopBuiltinIconKind::NukeCodes => &tex_nuke_codes,
                 DesktopBuiltinIconKind::Terminal => &tex_terminal,
+                DesktopBuiltinIconKind::Trash => &tex_trash,
             };
             let top_left = {
                 let [x, y] = icon_position(
@@ -2362,7 +2808,9 @@ impl RobcoNativeApp {
             }
 
             if response.double_clicked() {
-                if let Some(window) = entry.target_window {
+                if entry.kind == DesktopBuiltinIconKind::Trash {
+                    open_trash = true;
+                } else if let Some(window) = entry.target_window {
                     open_window = Some(window);
                 } else {
                     open_terminal = true;
@@ -2522,6 +2970,9 @@ impl RobcoNativeApp {
 
         if open_terminal {
             self.open_desktop_terminal_shell();
+        } else if open_trash {
+            self.run_file_manager_command(FileManagerCommand::OpenTrash);
+            self.open_desktop_window(DesktopWindow::FileManager);
         } else if let Some(window) = open_window {
             self.open_desktop_window(window);
         }
@@ -2532,7 +2983,8 @@ impl RobcoNativeApp {
     // ── SPOTLIGHT SEARCH ─────────────────────────────────────────────────
 
     fn spotlight_gather_results(&mut self) {
-        let query = self.spotlight_query.to_lowercase();
+        // Command lines after `>` keep their case, so cache the raw query.
+        let query = self.spotlight_query.clone();
         let tab = self.spotlight_tab;
         // Skip if query+tab haven't changed
         if query == self.spotlight_last_query && tab == self.spotlight_last_tab {
@@ -2541,6 +2993,13 @@ impl RobcoNativeApp {
         self.spotlight_last_query = query.clone();
         self.spotlight_last_tab = tab;
         let active_username = active_native_session_username();
+        let content_matches =
+            if (tab == 0 || tab == 2) && query.trim().chars().count() >= MIN_CONTENT_QUERY_CHARS {
+                self.document_index.sync_user(active_username.as_deref());
+                self.document_index.search(&query)
+            } else {
+                Vec::new()
+            };
         self.spotlight_results = gather_spotlight_results(
             &query,
             tab,
@@ -2548,18 +3007,103 @@ impl RobcoNativeApp {
             BUILTIN_TEXT_EDITOR_APP,
             BUILTIN_NUKE_CODES_APP,
             BUILTIN_DONKEY_KONG_GAME,
+            content_matches,
+            &self.settings.draft.spotlight_providers,
         );
         self.spotlight_selected = 0;
     }
 
-    fn spotlight_activate_result(&mut self, result: &NativeSpotlightResult) {
+    fn spotlight_activate_result(&mut self, ctx: &Context, result: &NativeSpotlightResult) {
         self.spotlight_open = false;
         self.spotlight_query.clear();
-        if let Some(action) = self.spotlight_action_for_result(result) {
-            self.execute_desktop_shell_action(action);
+        match &result.category {
+            NativeSpotlightCategory::Calculation | NativeSpotlightCategory::Conversion => {
+                ctx.copy_text(result.name.clone());
+                self.shell_status = format!("Copied {} to the clipboard.", result.name);
+            }
+            NativeSpotlightCategory::Command(argv) => {
+                // Name the window after the program, not the `sh` wrapper.
+                let title = result.name.split_whitespace().next().unwrap_or(&argv[0]);
+                self.open_desktop_pty(title, argv);
+            }
+            NativeSpotlightCategory::Manual => self.open_manual_section(&result.name),
+            _ => {
+                if let Some(action) = self.spotlight_action_for_result(result) {
+                    self.execute_desktop_shell_action(action);
+                }
+            }
         }
     }
 
+    fn apply_search_overlay_style(ui: &mut egui::Ui) {
+        let palette = current_palette();
+        let v = ui.visuals_mut();
+        v.override_text_color = Some(palette.fg);
+        v.extreme_bg_color = palette.bg;
+        v.selection.bg_fill = palette.fg;
+        v.selection.stroke = egui::Stroke::new(1.0, palette.fg);
+        // noninteractive (labels, frames)
+        v.widgets.noninteractive.fg_stroke = egui::Stroke::new(1.0, palette.fg);
+        v.widgets.noninteractive.bg_fill = Color32::TRANSPARENT;
+        v.widgets.noninteractive.weak_bg_fill = Color32::TRANSPARENT;
+        v.widgets.noninteractive.bg_stroke = egui::Stroke::NONE;
+        // inactive (buttons at rest)
+        v.widgets.inactive.fg_stroke = egui::Stroke::new(1.0, palette.fg);
+        v.widgets.inactive.bg_fill = Color32::TRANSPARENT;
+        v.widgets.inactive.weak_bg_fill = Color32::TRANSPARENT;
+        v.widgets.inactive.bg_stroke = egui::Stroke::new(1.0, palette.fg);
+        // hovered
+        v.widgets.hovered.fg_stroke = egui::Stroke::new(1.0, palette.fg);
+        v.widgets.hovered.bg_fill = palette.panel;
+        v.widgets.hovered.weak_bg_fill = palette.panel;
+        v.widgets.hovered.bg_stroke = egui::Stroke::new(1.0, palette.fg);
+        v.widgets.hovered.expansion = 0.0;
+        // active (pressed)
+        v.widgets.active.fg_stroke = egui::Stroke::new(1.0, Color32::BLACK);
+        v.widgets.active.bg_fill = palette.fg;
+        v.widgets.active.weak_bg_fill = palette.fg;
+        v.widgets.active.bg_stroke = egui::Stroke::new(1.0, palette.fg);
+    }
+
+    /// A result line followed by an indented snippet with the match
+    /// highlighted in inverse colors.
+    fn content_snippet_job(
+        ui: &egui::Ui,
+        title: &str,
+        snippet: &ContentSnippet,
+        text_color: Color32,
+        selected: bool,
+    ) -> egui::text::LayoutJob {
+        let palette = current_palette();
+        let font = egui::TextStyle::Body.resolve(ui.style());
+        let plain = egui::TextFormat::simple(font.clone(), text_color);
+        let highlight = egui::TextFormat {
+            color: if selected { palette.fg } else { palette.bg },
+            background: if selected { palette.bg } else { palette.fg },
+            ..egui::TextFormat::simple(font, text_color)
+        };
+        let range = snippet.highlight.clone();
+        let mut job = egui::text::LayoutJob::default();
+        job.append(title, 0.0, plain.clone());
+        job.append("\n    ", 0.0, plain.clone());
+        job.append(&snippet.text[..range.start], 0.0, plain.clone());
+        job.append(&snippet.text[range.clone()], 0.0, highlight);
+        job.append(&snippet.text[range.end..], 0.0, plain);
+        job
+    }
+
+    fn rebuild_document_index(&mut self) -> String {
+        let username = self
+            .session
+            .as_ref()
+            .map(|session| session.username.clone());
+        self.document_index.sync_user(username.as_deref());
+        let status = self.document_index.rebuild();
+        // Show fresh content hits the next time Spotlight searches.
+        self.spotlight_last_query.clear();
+        status
+    }
+
     fn draw_spotlight(&mut self, ctx: &Context) {
         if !self.spotlight_open {
             return;
@@ -2596,7 +3140,12 @@ impl RobcoNativeApp {
             activate_idx = Some(self.spotlight_selected);
         }
 
-        // Gather results
+        // Gather results, again once a background index refresh lands.
+        if self.document_index.poll() {
+            self.spotlight_last_query.clear();
+        } else if self.document_index.is_refreshing() {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
         let prev_query = self.spotlight_last_query.clone();
         let prev_tab = self.spotlight_last_tab;
         self.spotlight_gather_results();
@@ -2624,32 +3173,7 @@ impl RobcoNativeApp {
                     .inner_margin(egui::Margin::same(12.0)),
             )
             .show(ctx, |ui| {
-                let v = ui.visuals_mut();
-                v.override_text_color = Some(palette.fg);
-                v.extreme_bg_color = palette.bg;
-                v.selection.bg_fill = palette.fg;
-                v.selection.stroke = egui::Stroke::new(1.0, palette.fg);
-                // noninteractive (labels, frames)
-                v.widgets.noninteractive.fg_stroke = egui::Stroke::new(1.0, palette.fg);
-                v.widgets.noninteractive.bg_fill = Color32::TRANSPARENT;
-                v.widgets.noninteractive.weak_bg_fill = Color32::TRANSPARENT;
-                v.widgets.noninteractive.bg_stroke = egui::Stroke::NONE;
-                // inactive (buttons at rest)
-                v.widgets.inactive.fg_stroke = egui::Stroke::new(1.0, palette.fg);
-                v.widgets.inactive.bg_fill = Color32::TRANSPARENT;
-                v.widgets.inactive.weak_bg_fill = Color32::TRANSPARENT;
-                v.widgets.inactive.bg_stroke = egui::Stroke::new(1.0, palette.fg);
-                // hovered
-                v.widgets.hovered.fg_stroke = egui::Stroke::new(1.0, palette.fg);
-                v.widgets.hovered.bg_fill = palette.panel;
-                v.widgets.hovered.weak_bg_fill = palette.panel;
-                v.widgets.hovered.bg_stroke = egui::Stroke::new(1.0, palette.fg);
-                v.widgets.hovered.expansion = 0.0;
-                // active (pressed)
-                v.widgets.active.fg_stroke = egui::Stroke::new(1.0, Color32::BLACK);
-                v.widgets.active.bg_fill = palette.fg;
-                v.widgets.active.weak_bg_fill = palette.fg;
-                v.widgets.active.bg_stroke = egui::Stroke::new(1.0, palette.fg);
+                Self::apply_search_overlay_style(ui);
 
                 // Search input
                 let search_resp = ui.add(
@@ -2707,12 +3231,21 @@ impl RobcoNativeApp {
                             for (i, result) in self.spotlight_results.iter().enumerate() {
                                 let selected = i == self.spotlight_selected;
                                 let cat_label = spotlight_category_tag(&result.category);
-                                let display = format!("[{cat_label}]  {}", result.name);
+                                let display = match result.description.as_deref() {
+                                    Some(description) => {
+                                        format!("[{cat_label}]  {} - {description}", result.name)
+                                    }
+                                    None => format!("[{cat_label}]  {}", result.name),
+                                };
                                 let text_color = if selected { Color32::BLACK } else { palette.fg };
-                                let resp = ui.add(egui::SelectableLabel::new(
-                                    selected,
-                                    RichText::new(display).color(text_color),
-                                ));
+                                let label: egui::WidgetText = match &result.snippet {
+                                    Some(snippet) => Self::content_snippet_job(
+                                        ui, &display, snippet, text_color, selected,
+                                    )
+                                    .into(),
+                                    None => RichText::new(display).color(text_color).into(),
+                                };
+                                let resp = ui.add(egui::SelectableLabel::new(selected, label));
                                 if resp.clicked() {
                                     activate_idx = Some(i);
                                 }
@@ -2728,9 +3261,205 @@ impl RobcoNativeApp {
         if let Some(idx) = activate_idx {
             if idx < self.spotlight_results.len() {
                 let result = self.spotlight_results[idx].clone();
-                self.spotlight_activate_result(&result);
+                self.spotlight_activate_result(ctx, &result);
+            }
+        }
+    }
+
+    // ── COMMAND PALETTE ──────────────────────────────────────────────────
+
+    fn palette_context(&self) -> PaletteContext {
+        PaletteContext {
+            desktop_mode: self.desktop_mode_open,
+            is_admin: self
+                .session
+                .as_ref()
+                .is_some_and(|session| session.is_admin),
+            editor_open: self.editor.open,
+            file_manager_open: self.file_manager.open,
+            session_count: native_session_count(),
+            active_session: active_native_session_index(),
+        }
+    }
+
+    fn toggle_command_palette(&mut self) {
+        self.palette_open = !self.palette_open;
+        self.palette_query.clear();
+        self.palette_selected = 0;
+        if self.palette_open {
+            self.spotlight_open = false;
+        }
+    }
+
+    fn run_palette_command(&mut self, ctx: &Context, command: PaletteCommand) {
+        match command {
+            PaletteCommand::Shell(action) => self.execute_desktop_shell_action(action),
+            PaletteCommand::Menu(action) => self.apply_desktop_menu_action(ctx, &action),
+            PaletteCommand::SettingsPanel(panel) => {
+                self.open_desktop_window(DesktopWindow::Settings);
+                self.settings.panel = panel;
+            }
+            PaletteCommand::TerminalScreen(TerminalScreen::Settings) => {
+                self.apply_main_menu_selection_action(
+                    MainMenuSelectionAction::RefreshSettingsAndOpen,
+                );
+            }
+            PaletteCommand::TerminalScreen(screen) => {
+                self.apply_terminal_screen_open_plan(terminal_screen_open_plan(screen, 0, true));
+            }
+            PaletteCommand::MainMenu(action) => {
+                self.apply_main_menu_selection_action(resolve_main_menu_action(action));
+            }
+            PaletteCommand::Session(PaletteSessionCommand::ReturnToTerminal) => {
+                self.run_start_root_action(StartRootAction::ReturnToTerminal);
+            }
+            PaletteCommand::Session(PaletteSessionCommand::Logout) => {
+                self.run_start_root_action(StartRootAction::Logout);
+            }
+            PaletteCommand::Session(PaletteSessionCommand::Shutdown) => {
+                self.run_start_root_action(StartRootAction::Shutdown);
+            }
+            PaletteCommand::Session(PaletteSessionCommand::SwitchTo(idx)) => {
+                self.request_session_switch_if_valid(idx);
+            }
+            PaletteCommand::Session(PaletteSessionCommand::CloseActive) => {
+                self.close_active_session_window();
+            }
+        }
+    }
+
+    fn draw_command_palette(&mut self, ctx: &Context) {
+        if !self.palette_open {
+            return;
+        }
+
+        if ctx.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Escape)) {
+            self.palette_open = false;
+            return;
+        }
+
+        let entries: Vec<PaletteEntry> = filter_palette_entries(
+            &palette_entries(&self.palette_context()),
+            &self.palette_query,
+        );
+
+        let mut scroll_selected_into_view = false;
+        if ctx.input(|i| i.key_pressed(Key::ArrowDown)) && !entries.is_empty() {
+            let next = (self.palette_selected + 1).min(entries.len() - 1);
+            if next != self.palette_selected {
+                self.palette_selected = next;
+                scroll_selected_into_view = true;
+            }
+        }
+        if ctx.input(|i| i.key_pressed(Key::ArrowUp)) {
+            let next = self.palette_selected.saturating_sub(1);
+            if next != self.palette_selected {
+                self.palette_selected = next;
+                scroll_selected_into_view = true;
             }
         }
+        self.palette_selected = self.palette_selected.min(entries.len().saturating_sub(1));
+
+        let mut activate_idx: Option<usize> = None;
+        if ctx.input(|i| i.key_pressed(Key::Enter)) && !entries.is_empty() {
+            activate_idx = Some(self.palette_selected);
+        }
+
+        let palette = current_palette();
+        let screen = ctx.screen_rect();
+        let box_width = 600.0_f32.min(screen.width() - 40.0);
+        let box_height = 420.0_f32.min(screen.height() - 80.0);
+        let previous_query = self.palette_query.clone();
+
+        egui::Window::new("command_palette_window")
+            .title_bar(false)
+            .resizable(false)
+            .collapsible(false)
+            .fixed_size(egui::vec2(box_width, box_height))
+            .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 60.0))
+            .order(egui::Order::Foreground)
+            .frame(
+                egui::Frame::none()
+                    .fill(palette.bg)
+                    .stroke(egui::Stroke::new(2.0, palette.fg))
+                    .shadow(egui::epaint::Shadow::NONE)
+                    .inner_margin(egui::Margin::same(12.0)),
+            )
+            .show(ctx, |ui| {
+                Self::apply_search_overlay_style(ui);
+
+                let search_resp = ui.add(
+                    TextEdit::singleline(&mut self.palette_query)
+                        .desired_width(box_width - 48.0)
+                        .hint_text(format!(
+                            "Run a command…  ({COMMAND_PALETTE_KEYBINDING} to close)"
+                        ))
+                        .font(egui::TextStyle::Body),
+                );
+                if search_resp.gained_focus() || !search_resp.has_focus() {
+                    search_resp.request_focus();
+                }
+
+                ui.add_space(6.0);
+
+                egui::ScrollArea::vertical()
+                    .max_height(ui.available_height())
+                    .auto_shrink(false)
+                    .show(ui, |ui| {
+                        if entries.is_empty() {
+                            ui.label(RichText::new("No matching commands.").color(palette.dim));
+                        }
+                        for (i, entry) in entries.iter().enumerate() {
+                            let selected = i == self.palette_selected;
+                            let text_color = if selected { Color32::BLACK } else { palette.fg };
+                            let resp = ui
+                                .horizontal(|ui| {
+                                    let resp = ui.add(egui::SelectableLabel::new(
+                                        selected,
+                                        RichText::new(&entry.label).color(text_color),
+                                    ));
+                                    if let Some(keybinding) = entry.keybinding {
+                                        ui.with_layout(
+                                            Layout::right_to_left(egui::Align::Center),
+                                            |ui| {
+                                                ui.label(
+                                                    RichText::new(keybinding).color(palette.dim),
+                                                );
+                                            },
+                                        );
+                                    }
+                                    resp
+                                })
+                                .inner;
+                            if resp.clicked() {
+                                activate_idx = Some(i);
+                            }
+                            if selected && scroll_selected_into_view {
+                                resp.scroll_to_me(None);
+                            }
+                        }
+                    });
+            });
+
+        if self.palette_query != previous_query {
+            self.palette_selected = 0;
+        }
+
+        // The palette is modal: keys typed into it must not reach the screen below.
+        ctx.input_mut(|i| {
+            i.events.retain(|e| {
+                !matches!(
+                    e,
+                    egui::Event::Key { .. } | egui::Event::Text(_) | egui::Event::Paste(_)
+                )
+            });
+        });
+
+        if let Some(entry) = activate_idx.and_then(|idx| entries.get(idx)) {
+            self.palette_open = false;
+            self.palette_query.clear();
+            self.run_palette_command(ctx, entry.command.clone());
+        }
     }
 
     fn draw_shortcut_properties_window(&mut self, ctx: &egui::Context) {
@@ -3029,6 +3758,7 @@ impl RobcoNativeApp {
                     .to_string();
                 self.editor.path = Some(target.clone());
                 self.editor.dirty = false;
+                self.document_index.note_saved(&target);
                 self.editor.status = if renamed_to_avoid_collision {
                     format!("Name already existed. Saved as {label}.")
                 } else {
@@ -3047,7 +3777,14 @@ impl RobcoNativeApp {
 
     fn open_desktop_catalog_launch(&mut self, name: &str, catalog: ProgramCatalog) {
         match resolve_catalog_launch(name, catalog) {
-            Ok(launch) => self.open_desktop_pty(&launch.title, &launch.argv),
+            Ok(program) => {
+                record_catalog_launch(catalog, name);
+                self.launch_catalog_program(
+                    &program,
+                    TerminalShellSurface::Desktop,
+                    TerminalScreen::MainMenu,
+                )
+            }
             Err(err) => self.shell_status = err,
         }
     }
@@ -3059,11 +3796,46 @@ impl RobcoNativeApp {
         return_screen: TerminalScreen,
     ) {
         match resolve_catalog_launch(name, catalog) {
-            Ok(launch) => self.open_embedded_pty(&launch.title, &launch.argv, return_screen),
+            Ok(program) => {
+                record_catalog_launch(catalog, name);
+                self.launch_catalog_program(&program, TerminalShellSurface::Embedded, return_screen)
+            }
             Err(err) => self.shell_status = err,
         }
     }
 
+    fn launch_catalog_program(
+        &mut self,
+        program: &ResolvedProgramLaunch,
+        surface: TerminalShellSurface,
+        return_screen: TerminalScreen,
+    ) {
+        match program.run_mode {
+            CatalogRunMode::Detached => {
+                self.shell_status =
+                    match crate::launcher::spawn_detached(&program.argv, &program.launch) {
+                        Ok(()) => format!("Started {}.", program.title),
+                        Err(err) => format!("Launch failed: {err}"),
+                    };
+            }
+            CatalogRunMode::Pty => {
+                let plan = terminal_catalog_launch_plan(
+                    surface,
+                    program,
+                    return_screen,
+                    desktop_pty_force_render_mode(&program.argv),
+                );
+                self.apply_terminal_pty_launch_plan(
+                    plan,
+                    matches!(surface, TerminalShellSurface::Desktop),
+                );
+            }
+        }
+        if let Some(warning) = catalog_network_warning(program) {
+            self.shell_status = warning;
+        }
+    }
+
     fn open_desktop_nuke_codes(&mut self) {
         if matches!(self.terminal_nuke_codes, NukeCodesView::Unloaded) {
             self.terminal_nuke_codes = fetch_nuke_codes();
@@ -3145,6 +3917,12 @@ impl RobcoNativeApp {
             StartSystemAction::ProgramInstaller => {
                 DesktopShellAction::OpenWindow(DesktopWindow::Installer)
             }
+            StartSystemAction::TaskManager => {
+                DesktopShellAction::OpenWindow(DesktopWindow::TaskManager)
+            }
+            StartSystemAction::Scheduler => {
+                DesktopShellAction::OpenWindow(DesktopWindow::Scheduler)
+            }
             StartSystemAction::Terminal => DesktopShellAction::OpenDesktopTerminalShell,
             StartSystemAction::FileManager => {
                 DesktopShellAction::OpenWindow(DesktopWindow::FileManager)
@@ -3208,10 +3986,15 @@ impl RobcoNativeApp {
                 .path
                 .clone()
                 .map(DesktopShellAction::RevealPathInFileManager),
+            // Handled by `spotlight_activate_result`.
+            NativeSpotlightCategory::Calculation
+            | NativeSpotlightCategory::Conversion
+            | NativeSpotlightCategory::Command(_)
+            | NativeSpotlightCategory::Manual => None,
         }
     }
 
-    fn open_manual_file(&mut self, path: &str, status_label: &str) {
+    fn open_manual_file(&mut self, path: &str, status_label: &str) -> bool {
         let manual = PathBuf::from(path);
         match load_text_document(manual) {
             Ok(document) => {
@@ -3220,22 +4003,36 @@ impl RobcoNativeApp {
                 self.editor.dirty = false;
                 self.editor.status = format!("Opened {status_label}.");
                 self.open_desktop_window(DesktopWindow::Editor);
+                true
             }
             Err(status) => {
                 self.shell_status = format!("{status_label} unavailable: {status}");
+                false
             }
         }
     }
 
-    fn draw_desktop_window_by_kind(&mut self, ctx: &Context, window: DesktopWindow) {
-        match window {
-            DesktopWindow::FileManager => self.draw_file_manager(ctx),
+    /// Opens the user manual with Find primed on a section heading.
+    fn open_manual_section(&mut self, heading: &str) {
+        if !self.open_manual_file(USER_MANUAL_PATH, "User Manual") {
+            return;
+        }
+        self.editor.ui.open_find();
+        self.editor.ui.find_query = heading.to_string();
+        self.editor.status = format!("Opened User Manual. Find Next jumps to {heading}.");
+    }
+
+    fn draw_desktop_window_by_kind(&mut self, ctx: &Context, window: DesktopWindow) {
+        match window {
+            DesktopWindow::FileManager => self.draw_file_manager(ctx),
             DesktopWindow::Editor => self.draw_editor(ctx),
             DesktopWindow::Settings => self.draw_settings(ctx),
             DesktopWindow::Applications => self.draw_applications(ctx),
             DesktopWindow::DonkeyKong => self.draw_desktop_donkey_kong(ctx),
             DesktopWindow::NukeCodes => self.draw_nuke_codes_window(ctx),
             DesktopWindow::Installer => self.draw_installer(ctx),
+            DesktopWindow::TaskManager => self.draw_task_manager_window(ctx),
+            DesktopWindow::Scheduler => self.draw_scheduler_window(ctx),
             DesktopWindow::TerminalMode => self.draw_terminal_mode(ctx),
             DesktopWindow::PtyApp => self.draw_desktop_pty_window(ctx),
         }
@@ -3243,7 +4040,7 @@ impl RobcoNativeApp {
 
     fn draw_desktop_windows(&mut self, ctx: &Context) {
         self.sync_desktop_active_window();
-        const ORDER: [DesktopWindow; 9] = [
+        const ORDER: [DesktopWindow; 11] = [
             DesktopWindow::FileManager,
             DesktopWindow::Editor,
             DesktopWindow::Settings,
@@ -3251,6 +4048,8 @@ impl RobcoNativeApp {
             DesktopWindow::DonkeyKong,
             DesktopWindow::NukeCodes,
             DesktopWindow::Installer,
+            DesktopWindow::TaskManager,
+            DesktopWindow::Scheduler,
             DesktopWindow::TerminalMode,
             DesktopWindow::PtyApp,
         ];
@@ -3310,6 +4109,7 @@ impl RobcoNativeApp {
         self.apply_terminal_navigation_state(terminal_defaults);
         self.terminal_nuke_codes = NukeCodesView::default();
         self.terminal_pty = None;
+        self.desktop_pty_tabs = NativePtyTabs::default();
         self.terminal_installer.reset();
         self.terminal_edit_menus.reset();
         self.terminal_connections.reset();
@@ -3484,6 +4284,7 @@ impl RobcoNativeApp {
         };
         crate::sound::play_logout();
         self.persist_snapshot();
+        self.autostart.clear();
         self.terminate_all_native_pty_children();
         self.terminal_prompt = None;
         self.terminal_nav.screen = TerminalScreen::MainMenu;
@@ -3516,6 +4317,7 @@ impl RobcoNativeApp {
     fn reset_shell_runtime_for_logout(&mut self) {
         self.reset_shell_runtime_for_session(false);
         self.terminal_pty = None;
+        self.desktop_pty_tabs = NativePtyTabs::default();
     }
 
     fn open_password_prompt(&mut self, title: impl Into<String>, prompt: impl Into<String>) {
@@ -3596,12 +4398,7 @@ impl RobcoNativeApp {
         plan: TerminalPtyLaunchPlan,
         desktop_window: bool,
     ) {
-        if plan.replace_existing_pty {
-            if let Some(mut previous) = self.terminal_pty.take() {
-                previous.session.terminate();
-            }
-        }
-        let profile = desktop_pty_profile_for_command(&plan.argv);
+        let profile = desktop_pty_profile_for_entry(&plan.argv, plan.pty_profile.as_deref());
         let pty_cols = profile
             .preferred_w
             .unwrap_or(96)
@@ -3614,8 +4411,11 @@ impl RobcoNativeApp {
             .clamp(10, 60);
         let options = crate::pty::PtyLaunchOptions {
             env: plan.env,
+            cwd: plan.cwd,
             top_bar: None,
             force_render_mode: plan.force_render_mode,
+            keys: crate::pty::PtyKeyOptions::from(&profile),
+            transcript: None,
         };
         match spawn_embedded_pty_with_options(
             &plan.title,
@@ -3635,12 +4435,16 @@ impl RobcoNativeApp {
                     state.fixed_font_scale = Some(0.94);
                     state.fixed_font_width_divisor = Some(0.44);
                 }
-                self.terminal_pty = Some(state);
                 if desktop_window {
+                    let first_tab = self.desktop_pty_tabs.is_empty();
+                    self.desktop_pty_tabs.push(plan.title, state);
                     self.open_desktop_window(DesktopWindow::PtyApp);
-                    let window = self.desktop_window_state_mut(DesktopWindow::PtyApp);
-                    window.maximized = profile.open_fullscreen;
+                    if first_tab {
+                        let window = self.desktop_window_state_mut(DesktopWindow::PtyApp);
+                        window.maximized = profile.open_fullscreen;
+                    }
                 } else {
+                    self.terminal_pty = Some(state);
                     self.navigate_to_screen(TerminalScreen::PtyApp);
                 }
                 self.shell_status = plan.success_status;
@@ -3698,22 +4502,21 @@ impl RobcoNativeApp {
         }
     }
 
-    fn open_embedded_pty(&mut self, title: &str, cmd: &[String], return_screen: TerminalScreen) {
-        let plan = terminal_command_launch_plan(
-            TerminalShellSurface::Embedded,
-            title,
-            cmd,
-            return_screen,
-            desktop_pty_force_render_mode(cmd),
-        );
-        self.apply_terminal_pty_launch_plan(plan, false);
+    fn open_desktop_pty(&mut self, title: &str, cmd: &[String]) {
+        self.open_desktop_pty_with_environment(title, cmd, &LaunchEnvironment::default());
     }
 
-    fn open_desktop_pty(&mut self, title: &str, cmd: &[String]) {
+    fn open_desktop_pty_with_environment(
+        &mut self,
+        title: &str,
+        cmd: &[String],
+        launch: &LaunchEnvironment,
+    ) {
         let plan = terminal_command_launch_plan(
             TerminalShellSurface::Desktop,
             title,
             cmd,
+            launch,
             TerminalScreen::MainMenu,
             desktop_pty_force_render_mode(cmd),
         );
@@ -3742,6 +4545,161 @@ impl RobcoNativeApp {
         self.apply_terminal_pty_launch_plan(plan, true);
     }
 
+    fn desktop_pty_title(&self) -> Option<String> {
+        self.desktop_pty_tabs.active().map(native_pty_tab_title)
+    }
+
+    fn apply_desktop_pty_tab_action(&mut self, action: DesktopPtyTabAction) {
+        match action {
+            DesktopPtyTabAction::None => {}
+            DesktopPtyTabAction::Activate(id) => {
+                self.desktop_pty_tabs.activate(id);
+            }
+            DesktopPtyTabAction::MoveLeft(id) => {
+                self.desktop_pty_tabs.move_tab(id, -1);
+            }
+            DesktopPtyTabAction::MoveRight(id) => {
+                self.desktop_pty_tabs.move_tab(id, 1);
+            }
+            DesktopPtyTabAction::Rename(id) => {
+                let Some(tab) = self.desktop_pty_tabs.get(id) else {
+                    return;
+                };
+                let current = tab.custom_title.clone().unwrap_or_default();
+                self.open_input_prompt(
+                    "Rename Tab",
+                    "Tab name (blank follows the program title):",
+                    TerminalPromptAction::RenamePtyTab { tab_id: id },
+                );
+                if let Some(prompt) = self.terminal_prompt.as_mut() {
+                    prompt.buffer = current;
+                }
+            }
+            DesktopPtyTabAction::Close(id) => self.request_close_desktop_pty_tab(id),
+            DesktopPtyTabAction::NewTab => self.open_desktop_terminal_shell(),
+            DesktopPtyTabAction::ToggleBroadcast => self.toggle_pty_broadcast(),
+            DesktopPtyTabAction::ToggleBroadcastOptOut(session_id) => {
+                self.toggle_pty_broadcast_opt_out(session_id)
+            }
+        }
+    }
+
+    fn toggle_pty_broadcast(&mut self) {
+        self.shell_status = if self.pty_broadcast.toggle() {
+            "Broadcast on: input is mirrored to every PTY session.".to_string()
+        } else {
+            "Broadcast off.".to_string()
+        };
+    }
+
+    fn toggle_pty_broadcast_opt_out(&mut self, session_id: PtySessionId) {
+        self.shell_status = if self.pty_broadcast.toggle_opt_out(session_id) {
+            "Session included in broadcast.".to_string()
+        } else {
+            "Session excluded from broadcast.".to_string()
+        };
+    }
+
+    /// Mirrors input typed into any PTY session this frame to the other live
+    /// sessions taking part in broadcast, including parked sessions.
+    fn flush_pty_broadcast(&mut self) {
+        let mut states = native_pty_states_mut(
+            &mut self.terminal_pty,
+            &mut self.desktop_pty_tabs,
+            &mut self.session_runtime,
+        );
+        let sent: Vec<(PtySessionId, Vec<PtyInput>)> = states
+            .iter_mut()
+            .filter(|state| !state.sent_input.is_empty())
+            .map(|state| (state.session.id(), std::mem::take(&mut state.sent_input)))
+            .collect();
+        if sent.is_empty() || !self.pty_broadcast.is_enabled() {
+            return;
+        }
+        let live: Vec<PtySessionId> = states
+            .iter_mut()
+            .filter_map(|state| state.session.is_alive().then(|| state.session.id()))
+            .collect();
+        self.pty_broadcast.retain_sessions(live.iter().copied());
+        for (source, inputs) in sent {
+            let targets = self
+                .pty_broadcast
+                .mirror_targets(source, live.iter().copied());
+            for state in states
+                .iter_mut()
+                .filter(|state| targets.contains(&state.session.id()))
+            {
+                for input in &inputs {
+                    state.session.send_input(input);
+                }
+            }
+        }
+    }
+
+    /// Status line shown under the embedded PTY while broadcast is on.
+    fn pty_broadcast_status(&self, session_id: PtySessionId) -> Option<String> {
+        if !self.pty_broadcast.is_enabled() {
+            return None;
+        }
+        Some(if self.pty_broadcast.is_opted_out(session_id) {
+            "[BROADCAST] on, this session excluded (Ctrl+Shift+E to include)".to_string()
+        } else {
+            "[BROADCAST] typing goes to all sessions (Ctrl+Shift+B to stop)".to_string()
+        })
+    }
+
+    /// Closes a tab right away when its child has exited, otherwise asks first.
+    fn request_close_desktop_pty_tab(&mut self, id: TerminalTabId) {
+        let Some(tab) = self.desktop_pty_tabs.get_mut(id) else {
+            return;
+        };
+        if !tab.state.session.is_alive() {
+            self.close_desktop_pty_tab(id);
+            return;
+        }
+        let title = native_pty_tab_title(tab);
+        self.open_confirm_prompt(
+            "Close Tab",
+            format!("{title} is still running. Close it?"),
+            TerminalPromptAction::ConfirmClosePtyTab { tab_id: id },
+        );
+    }
+
+    /// Closes the PTY window and all its tabs, asking first when any tab
+    /// still has a running child.
+    fn request_close_desktop_pty_window(&mut self) {
+        let running = self
+            .desktop_pty_tabs
+            .iter_mut()
+            .filter_map(|tab| tab.state.session.is_alive().then_some(()))
+            .count();
+        if running == 0 {
+            self.update_desktop_window_state(DesktopWindow::PtyApp, false);
+            return;
+        }
+        let message = if running == 1 {
+            "1 tab is still running. Close the terminal?".to_string()
+        } else {
+            format!("{running} tabs are still running. Close the terminal?")
+        };
+        self.open_confirm_prompt(
+            "Close Terminal",
+            message,
+            TerminalPromptAction::ConfirmClosePtyWindow,
+        );
+    }
+
+    fn close_desktop_pty_tab(&mut self, id: TerminalTabId) {
+        if let Some(mut tab) = self.desktop_pty_tabs.remove(id) {
+            let title = native_pty_tab_title(&tab);
+            tab.state.session.terminate();
+            self.shell_status = format!("Closed {title}.");
+        }
+        if self.desktop_pty_tabs.is_empty() {
+            self.update_desktop_window_state(DesktopWindow::PtyApp, false);
+        }
+    }
+
     fn open_path_in_editor(&mut self, path: PathBuf) {
         match load_text_document(path.clone()) {
             Ok(document) => {
@@ -3750,6 +4708,7 @@ impl RobcoNativeApp {
                 self.editor.dirty = false;
                 self.editor.status = "Opened document.".to_string();
                 self.push_editor_recent_file(&document.path);
+                record_document_open(&document.path);
                 self.open_desktop_window(DesktopWindow::Editor);
             }
             Err(status) => {
@@ -3761,12 +4720,20 @@ impl RobcoNativeApp {
 
     fn activate_file_manager_selection(&mut self) {
         let settings = load_settings_snapshot();
+        let action = self.file_manager.activate_selected();
+        let opened_file = match &action {
+            FileManagerAction::OpenFile(path) => Some(path.clone()),
+            _ => None,
+        };
         match file_manager_app::open_target_for_file_manager_action(
-            self.file_manager.activate_selected(),
+            action,
             &settings.desktop_file_manager,
         ) {
             Ok(FileManagerOpenTarget::NoOp) => {}
             Ok(FileManagerOpenTarget::Launch(launch)) => {
+                if let Some(path) = opened_file {
+                    record_file_open(&path);
+                }
                 self.shell_status = self.launch_open_with_request(launch);
             }
             Ok(FileManagerOpenTarget::OpenInEditor(path)) => self.open_path_in_editor(path),
@@ -3980,6 +4947,7 @@ impl RobcoNativeApp {
         match save_text_file(&path, &self.editor.text) {
             Ok(()) => {
                 self.editor.dirty = false;
+                self.document_index.note_saved(&path);
                 self.editor.status = format!(
                     "Saved {}.",
                     path.file_name()
@@ -4035,6 +5003,89 @@ impl RobcoNativeApp {
         }
     }
 
+    fn catalog_entry_draft(target: EditMenuTarget, name: &str) -> Option<CatalogEntryDraft> {
+        let catalog = Self::program_catalog_for_edit_target(target)?;
+        catalog_entry(catalog, name).map(|entry| CatalogEntryDraft::from_entry(&entry))
+    }
+
+    fn save_catalog_entry_draft(
+        &mut self,
+        target: EditMenuTarget,
+        name: &str,
+        draft: &CatalogEntryDraft,
+    ) {
+        let Some(catalog) = Self::program_catalog_for_edit_target(target) else {
+            self.shell_status = "Error: invalid target for command entry.".to_string();
+            return;
+        };
+        self.shell_status = match draft
+            .to_entry()
+            .and_then(|entry| update_catalog_entry(catalog, name, &entry))
+        {
+            Ok(status) | Err(status) => status,
+        };
+    }
+
+    /// Scans desktop entries into the import list shared by the terminal and
+    /// GUI editors. Returns `false` (with a status) when nothing was found.
+    fn scan_desktop_import(&mut self) -> bool {
+        let rows: Vec<DesktopAppImportRow> = scan_desktop_apps()
+            .into_iter()
+            .map(|app| DesktopAppImportRow {
+                catalog: app.suggested_catalog(),
+                selected: false,
+                app,
+            })
+            .collect();
+        if rows.is_empty() {
+            self.settings.desktop_import = None;
+            self.shell_status = "No desktop entries found.".to_string();
+            return false;
+        }
+        self.settings.desktop_import = Some(rows);
+        true
+    }
+
+    fn desktop_import_labels(&self) -> Vec<String> {
+        let Some(rows) = self.settings.desktop_import.as_ref() else {
+            return Vec::new();
+        };
+        let applications = catalog_names(ProgramCatalog::Applications);
+        let games = catalog_names(ProgramCatalog::Games);
+        rows.iter()
+            .map(|row| {
+                let name = &row.app.name;
+                if applications.contains(name) || games.contains(name) {
+                    format!("{name} (added)")
+                } else {
+                    name.clone()
+                }
+            })
+            .collect()
+    }
+
+    fn import_selected_desktop_apps(&mut self) {
+        let Some(rows) = self.settings.desktop_import.as_mut() else {
+            return;
+        };
+        let mut imported = 0usize;
+        let mut skipped = 0usize;
+        for row in rows.iter_mut().filter(|row| row.selected) {
+            match import_desktop_app(&row.app, row.catalog) {
+                Ok(_) => imported += 1,
+                Err(_) => skipped += 1,
+            }
+            row.selected = false;
+        }
+        self.shell_status = match (imported, skipped) {
+            (0, 0) => "No desktop entries selected.".to_string(),
+            (imported, 0) => format!("Imported {imported} desktop entries."),
+            (imported, skipped) => {
+                format!("Imported {imported} desktop entries, skipped {skipped} existing.")
+            }
+        };
+    }
+
     fn program_catalog_for_edit_target(target: EditMenuTarget) -> Option<ProgramCatalog> {
         match target {
             EditMenuTarget::Applications => Some(ProgramCatalog::Applications),
@@ -4133,6 +5184,8 @@ impl RobcoNativeApp {
         self.file_manager.selected = None;
         self.terminal_nav.browser_idx = 0;
         self.terminal_nav.browser_return_screen = return_screen;
+        self.document_search = None;
+        self.document_places = None;
         self.navigate_to_screen(TerminalScreen::DocumentBrowser);
     }
 
@@ -4347,6 +5400,9 @@ impl RobcoNativeApp {
                 self.terminal_nav.network_idx = plan.selected_idx
             }
             TerminalSelectionIndexTarget::Games => self.terminal_nav.games_idx = plan.selected_idx,
+            TerminalSelectionIndexTarget::Recent => {
+                self.terminal_nav.recent_idx = plan.selected_idx
+            }
             TerminalSelectionIndexTarget::ProgramInstallerRoot => {
                 self.terminal_installer.root_idx = plan.selected_idx;
             }
@@ -4372,6 +5428,12 @@ impl RobcoNativeApp {
     }
 
     fn handle_terminal_back(&mut self) {
+        if self.terminal_nav.screen == TerminalScreen::DocumentBrowser
+            && (self.document_search.take().is_some() || self.document_places.take().is_some())
+        {
+            crate::sound::play_navigate();
+            return;
+        }
         let action = resolve_terminal_back_action(TerminalBackContext {
             screen: self.terminal_nav.screen,
             has_settings_choice: self.terminal_nav.settings_choice.is_some(),
@@ -4595,6 +5657,15 @@ impl RobcoNativeApp {
                     TerminalPromptAction::EditMenuAddCategoryPath { name },
                 );
             }
+            PromptOutcome::EditMenuEntryDetail {
+                target,
+                name,
+                field,
+                value,
+            } => {
+                self.terminal_prompt = None;
+                self.edit_catalog_entry_detail(target, &name, field, &value);
+            }
             PromptOutcome::EditMenuAddCategoryPath { name, path } => {
                 self.terminal_prompt = None;
                 if path.trim().is_empty() {
@@ -4606,7 +5677,10 @@ impl RobcoNativeApp {
             PromptOutcome::FileManagerRename { .. }
             | PromptOutcome::FileManagerMoveTo { .. }
             | PromptOutcome::FileManagerOpenWithNewCommand { .. }
-            | PromptOutcome::FileManagerOpenWithEditCommand { .. } => {
+            | PromptOutcome::FileManagerOpenWithEditCommand { .. }
+            | PromptOutcome::FileManagerCompress { .. }
+            | PromptOutcome::FileManagerEmptyTrash { .. }
+            | PromptOutcome::FileManagerDeleteFromTrash { .. } => {
                 unreachable!("file manager prompt outcomes are handled before this match")
             }
             PromptOutcome::ConfirmEditMenuDelete {
@@ -4625,6 +5699,26 @@ impl RobcoNativeApp {
                 self.terminal_prompt = None;
                 self.create_or_open_log(&name);
             }
+            PromptOutcome::RenamePtyTab { tab_id, name } => {
+                self.terminal_prompt = None;
+                self.desktop_pty_tabs.rename(tab_id, Some(&name));
+            }
+            PromptOutcome::ConfirmClosePtyTab { tab_id, confirmed } => {
+                self.terminal_prompt = None;
+                if confirmed {
+                    self.close_desktop_pty_tab(tab_id);
+                } else {
+                    self.apply_status_update(cancelled_shell_status());
+                }
+            }
+            PromptOutcome::ConfirmClosePtyWindow { confirmed } => {
+                self.terminal_prompt = None;
+                if confirmed {
+                    self.update_desktop_window_state(DesktopWindow::PtyApp, false);
+                } else {
+                    self.apply_status_update(cancelled_shell_status());
+                }
+            }
             PromptOutcome::Noop => {
                 self.terminal_prompt = None;
             }
@@ -4649,6 +5743,70 @@ impl RobcoNativeApp {
                 self.terminal_prompt = None;
                 apply_installer_filter(&mut self.terminal_installer, &filter);
             }
+            PromptOutcome::TaskManagerFilter(filter) => {
+                self.terminal_prompt = None;
+                self.task_manager.filter = filter.trim().to_string();
+                self.task_manager.selected = 0;
+            }
+            PromptOutcome::SchedulerJobName(name) => {
+                self.terminal_prompt = None;
+                let name = name.trim().to_string();
+                if name.is_empty() {
+                    self.apply_status_update(invalid_input_shell_status());
+                    return;
+                }
+                self.open_input_prompt(
+                    "Scheduler",
+                    "Run (app:Name, net:Name, game:Name or a command):",
+                    TerminalPromptAction::SchedulerJobTarget { name },
+                );
+            }
+            PromptOutcome::SchedulerJobTarget { name, target } => {
+                self.terminal_prompt = None;
+                if target.trim().is_empty() {
+                    self.apply_status_update(invalid_input_shell_status());
+                    return;
+                }
+                self.open_input_prompt(
+                    "Scheduler",
+                    "When (every 15m, at 03:00, or cron like 0 3 * * *):",
+                    TerminalPromptAction::SchedulerJobSchedule { name, target },
+                );
+            }
+            PromptOutcome::SchedulerJobSchedule {
+                name,
+                target,
+                schedule,
+            } => {
+                self.terminal_prompt = None;
+                self.shell_status =
+                    match self
+                        .scheduler
+                        .add_job(&name, &target, &schedule, false, unix_now())
+                    {
+                        Ok(status) | Err(status) => status,
+                    };
+            }
+            PromptOutcome::DocumentContentSearch(query) => {
+                self.terminal_prompt = None;
+                self.run_document_content_search(&query);
+            }
+            PromptOutcome::AutostartTarget(target) => {
+                self.terminal_prompt = None;
+                match AutostartTarget::parse(&target) {
+                    Ok(target) => {
+                        let status = format!("Added {} to autostart.", target.describe());
+                        self.settings
+                            .draft
+                            .autostart
+                            .push(AutostartItem::new(target, AutostartMode::Terminal));
+                        self.autostart_selected = self.settings.draft.autostart.len() - 1;
+                        self.persist_native_settings();
+                        self.shell_status = status;
+                    }
+                    Err(err) => self.shell_status = err,
+                }
+            }
             PromptOutcome::InstallerDisplayName {
                 pkg,
                 target,
@@ -5038,6 +6196,8 @@ impl RobcoNativeApp {
                 DesktopWindow::Applications,
                 DesktopWindow::DonkeyKong,
                 DesktopWindow::NukeCodes,
+                DesktopWindow::TaskManager,
+                DesktopWindow::Scheduler,
                 DesktopWindow::PtyApp,
             ]
             .into_iter()
@@ -5047,10 +6207,8 @@ impl RobcoNativeApp {
                 active: self.desktop_active_window == Some(window),
             })
             .collect();
-            let items = build_window_menu_section(
-                &entries,
-                self.terminal_pty.as_ref().map(|pty| pty.title.as_str()),
-            );
+            let pty_title = self.desktop_pty_title();
+            let items = build_window_menu_section(&entries, pty_title.as_deref());
             self.draw_desktop_menu_items(ui, ctx, &items);
         });
     }
@@ -5081,10 +6239,8 @@ impl RobcoNativeApp {
 
     fn draw_top_bar(&mut self, ctx: &Context) {
         Self::apply_global_retro_menu_chrome(ctx);
-        let app_menu_name = desktop_app_menu_name(
-            self.desktop_active_window,
-            self.terminal_pty.as_ref().map(|pty| pty.title.as_str()),
-        );
+        let pty_title = self.desktop_pty_title();
+        let app_menu_name = desktop_app_menu_name(self.desktop_active_window, pty_title.as_deref());
         let active_app = self.active_desktop_app();
         TopBottomPanel::top("native_top_bar")
             .exact_height(30.0)
@@ -5147,6 +6303,8 @@ impl RobcoNativeApp {
         let mut branch_anchor_y = screen.top() + EDGE_PAD;
         let mut branch_x = root_x + ROOT_W - 2.0;
         let mut root_rect: Option<egui::Rect> = None;
+        let frequent = frequent_history_entries(FREQUENT_HISTORY_LIMIT);
+        let mut frequent_action: Option<DesktopShellAction> = None;
 
         egui::Area::new(Id::new("native_start_root_panel"))
             .fixed_pos([root_x, root_y])
@@ -5163,6 +6321,18 @@ impl RobcoNativeApp {
                     ui.label(RichText::new("Start").strong().color(palette.fg));
                     Self::retro_separator(ui);
 
+                    if !frequent.is_empty() {
+                        ui.label(RichText::new("Frequently Used").color(palette.dim));
+                        for entry in &frequent {
+                            if Self::start_menu_row(ui, &entry.label, false, ROOT_W - 16.0)
+                                .clicked()
+                            {
+                                frequent_action = Some(Self::history_entry_shell_action(entry));
+                            }
+                        }
+                        Self::retro_separator(ui);
+                    }
+
                     for row in START_ROOT_VIS_ROWS {
                         match row {
                             Some(idx) => {
@@ -5201,6 +6371,11 @@ impl RobcoNativeApp {
                 self.start_root_panel_height = frame_response.response.rect.height();
                 branch_x = frame_response.response.rect.right() - 2.0;
             });
+        if let Some(action) = frequent_action {
+            self.close_start_menu();
+            self.execute_desktop_shell_action(action);
+            return;
+        }
         let Some(root_rect) = root_rect else {
             return;
         };
@@ -5261,8 +6436,11 @@ impl RobcoNativeApp {
                             ui.set_max_width(LEAF_W);
                             for (idx, item) in items.iter().enumerate() {
                                 let selected = self.start_leaf_selected == idx;
-                                let response =
+                                let mut response =
                                     Self::start_menu_row(ui, &item.label, selected, LEAF_W - 16.0);
+                                if let Some(detail) = item.detail.as_deref() {
+                                    response = response.on_hover_text(detail);
+                                }
                                 if response.hovered() {
                                     self.start_leaf_selected = idx;
                                 }
@@ -5290,6 +6468,15 @@ impl RobcoNativeApp {
                                                     });
                                                 ui.close_menu();
                                             }
+                                            if ui.button("Edit Details...").clicked() {
+                                                leaf_context_action = Some(
+                                                    ContextMenuAction::EditStartMenuEntryDetails {
+                                                        target: *target,
+                                                        name: name.clone(),
+                                                    },
+                                                );
+                                                ui.close_menu();
+                                            }
                                             Self::retro_separator(ui);
                                         }
                                         if ui.button("Create Shortcut").clicked() {
@@ -5466,10 +6653,11 @@ impl RobcoNativeApp {
                         .copied()
                         .filter(|window| self.desktop_window_is_open(*window))
                         .collect();
+                    let pty_title = self.desktop_pty_title();
                     let entries = build_taskbar_entries(
                         &open_windows,
                         self.desktop_active_window,
-                        self.terminal_pty.as_ref().map(|pty| pty.title.as_str()),
+                        pty_title.as_deref(),
                     );
                     for entry in entries {
                         if Self::desktop_bar_button(ui, entry.label, entry.inactive, false)
@@ -5610,7 +6798,7 @@ impl RobcoNativeApp {
 
     fn draw_terminal_documents(&mut self, ctx: &Context) {
         let layout = self.terminal_layout();
-        let mut items = vec!["Logs".to_string()];
+        let mut items = vec!["Logs".to_string(), "File Commander".to_string()];
         items.extend(Self::sorted_document_categories());
         items.push("---".to_string());
         items.push("Back".to_string());
@@ -5645,6 +6833,10 @@ impl RobcoNativeApp {
                     self.terminal_nav.logs_idx = 0;
                     self.apply_status_update(clear_shell_status());
                 }
+                "File Commander" => {
+                    self.navigate_to_screen(TerminalScreen::Commander);
+                    self.apply_status_update(clear_shell_status());
+                }
                 "Back" => {
                     self.navigate_to_screen(TerminalScreen::MainMenu);
                     self.apply_status_update(clear_shell_status());
@@ -5714,10 +6906,88 @@ impl RobcoNativeApp {
 
     fn draw_terminal_document_browser(&mut self, ctx: &Context) {
         let layout = self.terminal_layout();
+        if let Some(view) = self.document_search.as_mut() {
+            let request = draw_terminal_document_search(
+                ctx,
+                view,
+                &self.shell_status,
+                layout.cols,
+                layout.rows,
+                layout.header_start_row,
+                layout.separator_top_row,
+                layout.title_row,
+                layout.separator_bottom_row,
+                layout.subtitle_row,
+                layout.menu_start_row,
+                layout.status_row,
+                layout.status_row_alt,
+                layout.content_col,
+            );
+            match request {
+                DocumentSearchRequest::None => {}
+                DocumentSearchRequest::NewSearch => self.open_document_content_search_prompt(),
+                DocumentSearchRequest::Open(path) => {
+                    self.file_manager.select(Some(path));
+                    self.activate_file_manager_selection();
+                }
+            }
+            return;
+        }
+        if let Some(view) = self.document_places.as_mut() {
+            let opened = draw_terminal_places_jump(
+                ctx,
+                view,
+                &self.shell_status,
+                layout.cols,
+                layout.rows,
+                layout.header_start_row,
+                layout.separator_top_row,
+                layout.title_row,
+                layout.separator_bottom_row,
+                layout.subtitle_row,
+                layout.menu_start_row,
+                layout.status_row,
+                layout.status_row_alt,
+                layout.content_col,
+            );
+            if let Some(path) = opened {
+                self.document_places = None;
+                self.file_manager.set_cwd(path);
+                self.file_manager.selected = None;
+                self.terminal_nav.browser_idx = 0;
+            }
+            return;
+        }
+        if self.terminal_prompt.is_none() && ctx.input(|i| i.key_pressed(Key::Slash)) {
+            self.open_document_content_search_prompt();
+        }
+        if self.terminal_prompt.is_none() && ctx.input(|i| i.key_pressed(Key::J)) {
+            self.document_places = Some(PlacesJumpView {
+                places: folder_places(),
+                selected: 0,
+            });
+            return;
+        }
+        if self.terminal_prompt.is_none() && ctx.input(|i| i.key_pressed(Key::P)) {
+            self.run_file_manager_command(FileManagerCommand::TogglePreviewPanel);
+        }
+        let preview = if load_desktop_file_manager_settings().show_preview_panel {
+            self.file_manager_previews.poll(ctx);
+            let rows = browser_rows(&self.file_manager);
+            browser_preview_path(&rows, self.terminal_nav.browser_idx).map(|path| {
+                self.file_manager_previews
+                    .get(&path)
+                    .map(|cached| cached.preview.summary_lines())
+                    .unwrap_or_else(|| vec!["Loading preview...".to_string()])
+            })
+        } else {
+            None
+        };
         let activated = draw_terminal_document_browser(
             ctx,
             &self.file_manager,
             &mut self.terminal_nav.browser_idx,
+            preview.as_deref(),
             &self.shell_status,
             layout.cols,
             layout.rows,
@@ -5746,6 +7016,66 @@ impl RobcoNativeApp {
         }
     }
 
+    fn draw_terminal_commander(&mut self, ctx: &Context) {
+        let layout = self.terminal_layout();
+        let request = draw_terminal_commander(
+            ctx,
+            &mut self.file_manager,
+            &self.file_manager_commander,
+            &self.shell_status,
+            layout.cols,
+            layout.rows,
+            layout.header_start_row,
+            layout.separator_top_row,
+            layout.title_row,
+            layout.separator_bottom_row,
+            layout.subtitle_row,
+            layout.menu_start_row,
+            layout.status_row,
+            layout.status_row_alt,
+            layout.content_col,
+        );
+        match request {
+            TerminalCommanderRequest::None => {}
+            TerminalCommanderRequest::Command(command) => self.run_file_manager_command(command),
+            TerminalCommanderRequest::Activate => self.activate_file_manager_selection(),
+            TerminalCommanderRequest::Select(path) => self.file_manager.select(Some(path)),
+            TerminalCommanderRequest::FocusOther(path) => {
+                self.file_manager_commander.switch(&mut self.file_manager);
+                self.file_manager.select(Some(path));
+            }
+        }
+    }
+
+    fn open_document_content_search_prompt(&mut self) {
+        self.open_input_prompt(
+            "Search Documents",
+            "Find text in documents:",
+            TerminalPromptAction::DocumentContentSearch,
+        );
+    }
+
+    fn run_document_content_search(&mut self, query: &str) {
+        let query = query.trim();
+        if query.chars().count() < MIN_CONTENT_QUERY_CHARS {
+            self.shell_status =
+                format!("Error: enter at least {MIN_CONTENT_QUERY_CHARS} characters.");
+            return;
+        }
+        let username = self
+            .session
+            .as_ref()
+            .map(|session| session.username.clone());
+        self.document_index.sync_user(username.as_deref());
+        let matches = self.document_index.search(query);
+        self.document_search = Some(DocumentSearchView {
+            query: query.to_string(),
+            matches,
+            selected: 0,
+        });
+        self.apply_status_update(clear_shell_status());
+    }
+
     fn draw_terminal_settings(&mut self, ctx: &Context) {
         let layout = self.terminal_layout();
         let event = run_terminal_settings_screen(
@@ -5795,7 +7125,15 @@ impl RobcoNativeApp {
                     true,
                 ));
             }
-            TerminalSettingsEvent::OpenAbout => {
+            TerminalSettingsEvent::OpenAutostart => {
+                self.autostart_selected = 0;
+                self.apply_terminal_screen_open_plan(terminal_screen_open_plan(
+                    TerminalScreen::Autostart,
+                    0,
+                    true,
+                ));
+            }
+            TerminalSettingsEvent::OpenAbout => {
                 self.apply_terminal_screen_open_plan(terminal_screen_open_plan(
                     TerminalScreen::About,
                     0,
@@ -5809,6 +7147,12 @@ impl RobcoNativeApp {
                     true,
                 ));
             }
+            TerminalSettingsEvent::ClearLaunchHistory => {
+                self.shell_status = clear_launch_history();
+            }
+            TerminalSettingsEvent::RebuildDocumentIndex => {
+                self.shell_status = self.rebuild_document_index();
+            }
         }
     }
 
@@ -5818,6 +7162,12 @@ impl RobcoNativeApp {
         let documents = self.edit_program_entries(EditMenuTarget::Documents);
         let network = self.edit_program_entries(EditMenuTarget::Network);
         let games = self.edit_program_entries(EditMenuTarget::Games);
+        let details = self
+            .terminal_edit_menus
+            .details_entry()
+            .and_then(|(target, name)| Self::catalog_entry_draft(target, name))
+            .map(|draft| entry_details_from_draft(&draft));
+        let desktop_apps = self.desktop_import_labels();
         let event = draw_edit_menus_screen(
             ctx,
             &mut self.terminal_edit_menus,
@@ -5826,6 +7176,8 @@ impl RobcoNativeApp {
                 documents: &documents,
                 network: &network,
                 games: &games,
+                details: details.as_ref(),
+                desktop_apps: &desktop_apps,
             },
             self.settings.draft.builtin_menu_visibility.nuke_codes,
             self.settings.draft.builtin_menu_visibility.text_editor,
@@ -5886,12 +7238,73 @@ impl RobcoNativeApp {
                     TerminalPromptAction::ConfirmEditMenuDelete { target, name },
                 );
             }
+            TerminalEditMenusRequest::OpenPromptEditDetail {
+                target,
+                name,
+                field,
+                title,
+                prompt,
+                current,
+            } => {
+                self.open_input_prompt(
+                    title,
+                    prompt,
+                    TerminalPromptAction::EditMenuEntryDetail {
+                        target,
+                        name,
+                        field,
+                    },
+                );
+                if let Some(prompt) = self.terminal_prompt.as_mut() {
+                    prompt.buffer = current;
+                }
+            }
+            TerminalEditMenusRequest::ToggleDetail {
+                target,
+                name,
+                field,
+            } => self.edit_catalog_entry_detail(target, &name, field, ""),
+            TerminalEditMenusRequest::ScanDesktopApps { target } => {
+                if self.scan_desktop_import() {
+                    self.terminal_edit_menus.open_desktop_import(target);
+                }
+            }
+            TerminalEditMenusRequest::ImportDesktopApp { target, idx } => {
+                let app = self
+                    .settings
+                    .desktop_import
+                    .as_ref()
+                    .and_then(|rows| rows.get(idx))
+                    .map(|row| row.app.clone());
+                if let (Some(app), Some(catalog)) =
+                    (app, Self::program_catalog_for_edit_target(target))
+                {
+                    self.shell_status = match import_desktop_app(&app, catalog) {
+                        Ok(status) | Err(status) => status,
+                    };
+                }
+            }
             TerminalEditMenusRequest::Status(status) => {
                 self.shell_status = status;
             }
         }
     }
 
+    fn edit_catalog_entry_detail(
+        &mut self,
+        target: EditMenuTarget,
+        name: &str,
+        field: EditMenuDetailField,
+        value: &str,
+    ) {
+        let Some(mut draft) = Self::catalog_entry_draft(target, name) else {
+            self.shell_status = format!("{name} was not found.");
+            return;
+        };
+        apply_entry_detail(&mut draft, field, value);
+        self.save_catalog_entry_draft(target, name, &draft);
+    }
+
     fn apply_terminal_connections_request(&mut self, request: TerminalConnectionsRequest) {
         match request {
             TerminalConnectionsRequest::None => {}
@@ -5995,6 +7408,40 @@ impl RobcoNativeApp {
             });
     }
 
+    fn draw_terminal_autostart(&mut self, ctx: &Context) {
+        let layout = self.terminal_layout();
+        let request = draw_autostart_screen(
+            ctx,
+            &mut self.settings.draft.autostart,
+            &mut self.autostart_selected,
+            &self.shell_status,
+            layout.cols,
+            layout.rows,
+            layout.header_start_row,
+            layout.separator_top_row,
+            layout.title_row,
+            layout.separator_bottom_row,
+            layout.subtitle_row,
+            layout.menu_start_row,
+            layout.status_row,
+            layout.content_col,
+        );
+        match request {
+            TerminalAutostartRequest::None => {}
+            TerminalAutostartRequest::Back => {
+                self.apply_terminal_screen_open_plan(terminal_settings_refresh_plan());
+            }
+            TerminalAutostartRequest::Add => {
+                self.open_input_prompt(
+                    "Autostart",
+                    "Open (app:Name, window:files, folder:/path or a command):",
+                    TerminalPromptAction::AutostartTarget,
+                );
+            }
+            TerminalAutostartRequest::Persist => self.persist_native_settings(),
+        }
+    }
+
     fn draw_terminal_default_apps(&mut self, ctx: &Context) {
         let layout = self.terminal_layout();
         let event = draw_default_apps_screen(
@@ -6119,6 +7566,79 @@ impl RobcoNativeApp {
         self.apply_terminal_program_request(request, TerminalScreen::Games);
     }
 
+    fn draw_terminal_recent(&mut self, ctx: &Context) {
+        let layout = self.terminal_layout();
+        let history = recent_history_entries(RECENT_HISTORY_LIMIT);
+        let entries: Vec<String> = history.iter().map(|entry| entry.label.clone()).collect();
+        let event = draw_programs_menu(
+            ctx,
+            "Recent",
+            Some(if entries.is_empty() {
+                "Nothing used yet"
+            } else {
+                "Recently Used"
+            }),
+            &entries,
+            &mut self.terminal_nav.recent_idx,
+            &self.shell_status,
+            layout.cols,
+            layout.rows,
+            layout.header_start_row,
+            layout.separator_top_row,
+            layout.title_row,
+            layout.separator_bottom_row,
+            layout.subtitle_row,
+            layout.menu_start_row,
+            layout.status_row,
+            layout.content_col,
+        );
+        match event {
+            ProgramMenuEvent::None => {}
+            ProgramMenuEvent::Back => {
+                self.apply_terminal_program_request(
+                    TerminalProgramRequest::BackToMainMenu,
+                    TerminalScreen::Recent,
+                );
+            }
+            ProgramMenuEvent::Launch(_) => {
+                if let Some(entry) = history.get(self.terminal_nav.recent_idx) {
+                    self.open_history_entry_in_terminal(entry.clone());
+                }
+            }
+        }
+    }
+
+    fn open_history_entry_in_terminal(&mut self, entry: HistoryEntry) {
+        match entry.target {
+            HistoryLaunchTarget::Catalog { name, catalog } => {
+                self.open_embedded_catalog_launch(&name, catalog, TerminalScreen::Recent);
+            }
+            HistoryLaunchTarget::Path { path, .. } => {
+                self.file_manager.select(Some(path));
+                self.activate_file_manager_selection();
+            }
+        }
+    }
+
+    fn history_entry_shell_action(entry: &HistoryEntry) -> DesktopShellAction {
+        match &entry.target {
+            HistoryLaunchTarget::Catalog { name, catalog } => match catalog {
+                ProgramCatalog::Applications => {
+                    DesktopShellAction::LaunchConfiguredApp(name.clone())
+                }
+                ProgramCatalog::Network => DesktopShellAction::LaunchNetworkProgram(name.clone()),
+                ProgramCatalog::Games => DesktopShellAction::LaunchGameProgram(name.clone()),
+            },
+            HistoryLaunchTarget::Path { path, kind } => {
+                if *kind == LaunchKind::Document {
+                    DesktopShellAction::OpenPathInEditor(path.clone())
+                } else {
+                    DesktopShellAction::RevealPathInFileManager(path.clone())
+                }
+            }
+        }
+    }
+
     fn draw_terminal_donkey_kong(&mut self, ctx: &Context) {
         ctx.request_repaint();
         let theme = self.current_donkey_kong_theme();
@@ -6177,17 +7697,303 @@ impl RobcoNativeApp {
         }
     }
 
+    fn tick_scheduler(&mut self, ctx: &Context) {
+        let username = self
+            .session
+            .as_ref()
+            .map(|session| session.username.clone());
+        let now = unix_now();
+        for launch in self
+            .scheduler
+            .tick(username.as_deref(), now, self.desktop_mode_open)
+        {
+            self.open_desktop_pty_with_environment(&launch.title, &launch.argv, &launch.launch);
+        }
+        if let Some(wakeup) = self.scheduler.next_wakeup(now) {
+            ctx.request_repaint_after(wakeup);
+        }
+    }
+
+    /// Opens queued autostart items once their delay has passed and the
+    /// session is in the mode they ask for. Waits while a flash or prompt
+    /// is showing.
+    fn tick_autostart(&mut self, ctx: &Context) {
+        if self.autostart.is_empty()
+            || self.terminal_flash.is_some()
+            || self.terminal_prompt.is_some()
+        {
+            return;
+        }
+        let now = Instant::now();
+        for item in self.autostart.take_ready(now, self.desktop_mode_open) {
+            self.run_autostart_item(&item);
+        }
+        if let Some(wakeup) = self.autostart.next_wakeup(now) {
+            ctx.request_repaint_after(wakeup);
+        }
+    }
+
+    fn run_autostart_item(&mut self, item: &AutostartItem) {
+        let desktop = matches!(item.mode, AutostartMode::Desktop);
+        match &item.target {
+            AutostartTarget::Catalog { kind, name } => {
+                let catalog = match kind {
+                    LaunchKind::Network => ProgramCatalog::Network,
+                    LaunchKind::Game => ProgramCatalog::Games,
+                    _ => ProgramCatalog::Applications,
+                };
+                if desktop {
+                    self.open_desktop_catalog_launch(name, catalog);
+                } else {
+                    self.open_embedded_catalog_launch(name, catalog, TerminalScreen::MainMenu);
+                }
+            }
+            AutostartTarget::Command { argv } if !argv.is_empty() => {
+                let launch = LaunchEnvironment::default();
+                if desktop {
+                    self.open_desktop_pty_with_environment(&argv[0], argv, &launch);
+                } else {
+                    let plan = terminal_command_launch_plan(
+                        TerminalShellSurface::Embedded,
+                        &argv[0],
+                        argv,
+                        &launch,
+                        TerminalScreen::MainMenu,
+                        desktop_pty_force_render_mode(argv),
+                    );
+                    self.apply_terminal_pty_launch_plan(plan, false);
+                }
+            }
+            AutostartTarget::Command { .. } => {}
+            AutostartTarget::Folder { path } => {
+                if desktop {
+                    self.open_file_manager_at(path.clone());
+                } else {
+                    self.open_document_browser_at(path.clone(), TerminalScreen::MainMenu);
+                }
+            }
+            AutostartTarget::Window { window } if desktop => match window {
+                BuiltinWindow::FileManager => self.open_desktop_window(DesktopWindow::FileManager),
+                BuiltinWindow::Editor => self.open_desktop_window(DesktopWindow::Editor),
+                BuiltinWindow::Applications => {
+                    self.open_desktop_window(DesktopWindow::Applications)
+                }
+                BuiltinWindow::Settings => self.open_desktop_window(DesktopWindow::Settings),
+                BuiltinWindow::Terminal => self.open_desktop_terminal_shell(),
+                BuiltinWindow::TaskManager => self.open_desktop_window(DesktopWindow::TaskManager),
+                BuiltinWindow::Scheduler => self.open_desktop_window(DesktopWindow::Scheduler),
+                BuiltinWindow::Installer => self.open_desktop_window(DesktopWindow::Installer),
+                BuiltinWindow::NukeCodes => self.open_desktop_nuke_codes(),
+            },
+            AutostartTarget::Window { window } => {
+                let screen = match window {
+                    BuiltinWindow::FileManager => {
+                        let cwd = self.file_manager.cwd.clone();
+                        self.open_document_browser_at(cwd, TerminalScreen::MainMenu);
+                        return;
+                    }
+                    BuiltinWindow::Editor => {
+                        self.shell_status =
+                            "Autostart: the editor opens in desktop mode only.".to_string();
+                        return;
+                    }
+                    BuiltinWindow::Settings => {
+                        self.apply_main_menu_selection_action(
+                            MainMenuSelectionAction::RefreshSettingsAndOpen,
+                        );
+                        return;
+                    }
+                    BuiltinWindow::Terminal => {
+                        self.open_embedded_terminal_shell();
+                        return;
+                    }
+                    BuiltinWindow::NukeCodes => {
+                        self.open_nuke_codes_screen(TerminalScreen::MainMenu);
+                        return;
+                    }
+                    BuiltinWindow::Applications => TerminalScreen::Applications,
+                    BuiltinWindow::TaskManager => TerminalScreen::TaskManager,
+                    BuiltinWindow::Scheduler => TerminalScreen::Scheduler,
+                    BuiltinWindow::Installer => TerminalScreen::ProgramInstaller,
+                };
+                self.apply_terminal_screen_open_plan(terminal_screen_open_plan(screen, 0, true));
+            }
+        }
+    }
+
+    fn apply_scheduler_request(&mut self, request: TerminalSchedulerRequest) {
+        match request {
+            TerminalSchedulerRequest::None => {}
+            TerminalSchedulerRequest::Back => {
+                self.navigate_to_screen(TerminalScreen::MainMenu);
+                self.apply_status_update(clear_shell_status());
+            }
+            TerminalSchedulerRequest::Add => {
+                self.open_input_prompt(
+                    "Scheduler",
+                    "Job name:",
+                    TerminalPromptAction::SchedulerJobName,
+                );
+            }
+            TerminalSchedulerRequest::Remove(job_id) => {
+                self.shell_status = self.scheduler.remove_job(job_id);
+            }
+            TerminalSchedulerRequest::ToggleEnabled(job_id) => {
+                self.shell_status = self.scheduler.toggle_enabled(job_id);
+            }
+            TerminalSchedulerRequest::ToggleVisible(job_id) => {
+                self.shell_status = self.scheduler.toggle_visible(job_id);
+            }
+            TerminalSchedulerRequest::RunNow(job_id) => {
+                let name = self
+                    .scheduler
+                    .schedule()
+                    .job(job_id)
+                    .map(|job| job.name.clone())
+                    .unwrap_or_default();
+                match self
+                    .scheduler
+                    .run_now(job_id, unix_now(), self.desktop_mode_open)
+                {
+                    Ok(Some(launch)) => {
+                        self.open_desktop_pty_with_environment(
+                            &launch.title,
+                            &launch.argv,
+                            &launch.launch,
+                        );
+                    }
+                    Ok(None) => self.shell_status = format!("Started {name}."),
+                    Err(err) => self.shell_status = err,
+                }
+            }
+        }
+    }
+
+    fn draw_terminal_scheduler(&mut self, ctx: &Context) {
+        let layout = self.terminal_layout();
+        let request = draw_scheduler_screen(
+            ctx,
+            &mut self.scheduler_ui,
+            &self.scheduler,
+            &self.shell_status,
+            layout.cols,
+            layout.rows,
+            layout.header_start_row,
+            layout.separator_top_row,
+            layout.title_row,
+            layout.separator_bottom_row,
+            layout.subtitle_row,
+            layout.menu_start_row,
+            layout.status_row,
+            layout.content_col,
+        );
+        self.apply_scheduler_request(request);
+    }
+
+    fn draw_terminal_task_manager(&mut self, ctx: &Context) {
+        let layout = self.terminal_layout();
+        match draw_task_manager_screen(
+            ctx,
+            &mut self.task_manager,
+            &self.shell_status,
+            layout.cols,
+            layout.rows,
+            layout.header_start_row,
+            layout.separator_top_row,
+            layout.title_row,
+            layout.separator_bottom_row,
+            layout.subtitle_row,
+            layout.menu_start_row,
+            layout.status_row,
+            layout.content_col,
+        ) {
+            TerminalTaskManagerRequest::None => {}
+            TerminalTaskManagerRequest::Back => {
+                self.navigate_to_screen(TerminalScreen::MainMenu);
+                self.apply_status_update(clear_shell_status());
+            }
+            TerminalTaskManagerRequest::EditFilter => {
+                self.open_input_prompt(
+                    "Task Manager",
+                    "Filter:",
+                    TerminalPromptAction::TaskManagerFilter,
+                );
+                if let Some(prompt) = self.terminal_prompt.as_mut() {
+                    prompt.buffer = self.task_manager.filter.clone();
+                }
+            }
+            TerminalTaskManagerRequest::Signal { pid, signal } => {
+                self.send_task_signal(pid, signal);
+            }
+            TerminalTaskManagerRequest::JumpToPty(session) => {
+                self.jump_to_pty_session(session);
+            }
+        }
+    }
+
+    fn send_task_signal(&mut self, pid: u32, signal: TaskSignal) {
+        self.shell_status = match self.task_manager.send_signal(pid, signal) {
+            Ok(status) | Err(status) => status,
+        };
+    }
+
+    /// Brings the PTY that owns `session` to the front in whichever mode
+    /// hosts it.
+    fn jump_to_pty_session(&mut self, session: PtySessionId) {
+        if self
+            .terminal_pty
+            .as_ref()
+            .is_some_and(|pty| pty.session.id() == session)
+        {
+            if self.desktop_mode_open {
+                self.open_desktop_window(DesktopWindow::TerminalMode);
+            } else {
+                self.navigate_to_screen(TerminalScreen::PtyApp);
+            }
+            return;
+        }
+        let tab_id = self
+            .desktop_pty_tabs
+            .iter()
+            .find(|tab| tab.state.session.id() == session)
+            .map(|tab| tab.id);
+        let Some(tab_id) = tab_id else {
+            self.shell_status = "That PTY is no longer open.".to_string();
+            return;
+        };
+        if !self.desktop_mode_open {
+            self.apply_main_menu_selection_action(MainMenuSelectionAction::EnterDesktopMode);
+        }
+        self.desktop_pty_tabs.activate(tab_id);
+        self.set_desktop_window_minimized(DesktopWindow::PtyApp, false);
+        self.desktop_active_window = Some(DesktopWindow::PtyApp);
+        self.close_start_menu();
+    }
+
     fn draw_terminal_pty(&mut self, ctx: &Context) {
         let layout = self.terminal_layout();
-        let Some(state) = self.terminal_pty.as_mut() else {
+        let Some(session_id) = self.terminal_pty.as_ref().map(|pty| pty.session.id()) else {
             self.navigate_to_screen(TerminalScreen::MainMenu);
             self.shell_status = "No embedded PTY session.".to_string();
             return;
         };
+        if self.terminal_prompt.is_none() {
+            if ctx.input_mut(|i| i.consume_key(Modifiers::CTRL | Modifiers::SHIFT, Key::B)) {
+                self.toggle_pty_broadcast();
+            } else if ctx.input_mut(|i| i.consume_key(Modifiers::CTRL | Modifiers::SHIFT, Key::E)) {
+                self.toggle_pty_broadcast_opt_out(session_id);
+            }
+        }
+        let status = self
+            .pty_broadcast_status(session_id)
+            .unwrap_or_else(|| self.shell_status.clone());
+        let Some(state) = self.terminal_pty.as_mut() else {
+            return;
+        };
         let event = draw_embedded_pty(
             ctx,
             state,
-            &self.shell_status,
+            &status,
             layout.cols,
             layout.rows,
             layout.header_start_row,
@@ -6199,6 +8005,7 @@ impl RobcoNativeApp {
             layout.status_row,
             layout.content_col,
         );
+        self.flush_pty_broadcast();
         match event {
             PtyScreenEvent::None => {}
             PtyScreenEvent::CloseRequested => self.handle_terminal_back(),
@@ -7144,6 +8951,7 @@ impl RobcoNativeApp {
         let footer_model = file_manager_desktop::build_footer_model(&desktop_model);
 
         self.preload_file_manager_svg_previews(ctx, &desktop_model.rows);
+        self.file_manager_previews.poll(ctx);
 
         let search_id = Id::new(("native_file_manager_search", generation));
         let shown = window.show(ctx, |ui| {
@@ -7160,6 +8968,7 @@ impl RobcoNativeApp {
             );
             self.draw_file_manager_footer_panel(ui, generation, save_picker_mode, &footer_model);
             self.draw_file_manager_tree_panel(ui, generation, save_picker_mode, &desktop_model);
+            self.draw_file_manager_preview_panel(ui, generation, &desktop_model);
             self.draw_file_manager_content_panel(
                 ctx,
                 ui,
@@ -7648,33 +9457,133 @@ impl RobcoNativeApp {
                                             changed = true;
                                         }
                                     });
-                                });
-                            }
-                            NativeSettingsPanel::Appearance => {
-                                let palette = current_palette();
-                                // ── Tab bar ───────────────────────────────────────────────────
-                                let tabs = ["Background", "Colors", "Icons", "Terminal"];
-                                ui.horizontal(|ui| {
-                                    for (i, label) in tabs.iter().enumerate() {
-                                        let active = self.appearance_tab == i as u8;
-                                        let color = if active { palette.fg } else { palette.dim };
-                                        let btn = ui.add(
-                                            egui::Button::new(
-                                                RichText::new(*label).color(color).strong(),
-                                            )
-                                            .stroke(egui::Stroke::new(
-                                                if active { 2.0 } else { 1.0 },
-                                                color,
-                                            ))
-                                            .fill(if active { palette.panel } else { palette.bg }),
-                                        );
-                                        if btn.clicked() {
-                                            self.appearance_tab = i as u8;
-                                        }
-                                    }
-                                });
-                                ui.add_space(10.0);
-                                Self::retro_separator(ui);
+
+                                    Self::settings_section(left, "Privacy", |left| {
+                                        if Self::retro_checkbox_row(
+                                            left,
+                                            &mut self.settings.draft.launch_history_enabled,
+                                            "Remember launch history",
+                                        )
+                                        .clicked()
+                                        {
+                                            changed = true;
+                                        }
+                                        left.add_space(8.0);
+                                        if Self::retro_full_width_button(
+                                            left,
+                                            "Clear Launch History",
+                                        )
+                                        .clicked()
+                                        {
+                                            self.shell_status = clear_launch_history();
+                                            self.apply_status_update(mirror_shell_to_settings(
+                                                &self.shell_status,
+                                            ));
+                                        }
+                                        left.add_space(8.0);
+                                        left.small(
+                                            "Ranks search results and fills Frequently Used and Recent.",
+                                        );
+                                    });
+
+                                    Self::settings_section(left, "Search", |left| {
+                                        if Self::retro_full_width_button(
+                                            left,
+                                            "Rebuild Document Index",
+                                        )
+                                        .clicked()
+                                        {
+                                            self.shell_status = self.rebuild_document_index();
+                                            self.apply_status_update(mirror_shell_to_settings(
+                                                &self.shell_status,
+                                            ));
+                                        }
+                                        left.add_space(8.0);
+                                        left.small(
+                                            "Spotlight and Open Documents search the text of \
+                                             your documents, journal and document categories.",
+                                        );
+                                        left.add_space(8.0);
+                                        left.label("Spotlight providers");
+                                        for provider in SpotlightProvider::ALL {
+                                            if Self::retro_checkbox_row(
+                                                left,
+                                                provider.enabled_mut(
+                                                    &mut self.settings.draft.spotlight_providers,
+                                                ),
+                                                provider.label(),
+                                            )
+                                            .clicked()
+                                            {
+                                                changed = true;
+                                                self.spotlight_last_query.clear();
+                                            }
+                                        }
+                                    });
+
+                                    Self::settings_section(left, "Trash", |left| {
+                                        left.horizontal(|ui| {
+                                            ui.label("Size limit");
+                                            if ui
+                                                .add(
+                                                    egui::DragValue::new(
+                                                        &mut self
+                                                            .settings
+                                                            .draft
+                                                            .desktop_file_manager
+                                                            .trash_max_mb,
+                                                    )
+                                                    .range(0..=1024 * 1024)
+                                                    .suffix(" MB"),
+                                                )
+                                                .changed()
+                                            {
+                                                changed = true;
+                                            }
+                                        });
+                                        left.add_space(8.0);
+                                        if Self::retro_full_width_button(left, "Empty Trash")
+                                            .clicked()
+                                        {
+                                            self.open_file_manager_prompt(
+                                                FileManagerPromptRequest::EmptyTrash,
+                                            );
+                                        }
+                                        left.add_space(8.0);
+                                        left.small(
+                                            "The oldest items are purged once the trash grows \
+                                             past the limit. 0 keeps everything. On Linux the \
+                                             trash is shared with other programs, so purging and \
+                                             emptying also remove items they deleted.",
+                                        );
+                                    });
+                                });
+                            }
+                            NativeSettingsPanel::Appearance => {
+                                let palette = current_palette();
+                                // ── Tab bar ───────────────────────────────────────────────────
+                                let tabs = ["Background", "Colors", "Icons", "Terminal"];
+                                ui.horizontal(|ui| {
+                                    for (i, label) in tabs.iter().enumerate() {
+                                        let active = self.appearance_tab == i as u8;
+                                        let color = if active { palette.fg } else { palette.dim };
+                                        let btn = ui.add(
+                                            egui::Button::new(
+                                                RichText::new(*label).color(color).strong(),
+                                            )
+                                            .stroke(egui::Stroke::new(
+                                                if active { 2.0 } else { 1.0 },
+                                                color,
+                                            ))
+                                            .fill(if active { palette.panel } else { palette.bg }),
+                                        );
+                                        if btn.clicked() {
+                                            self.appearance_tab = i as u8;
+                                        }
+                                    }
+                                });
+                                ui.add_space(10.0);
+                                Self::retro_separator(ui);
                                 ui.add_space(8.0);
                                 // ── Tab content ───────────────────────────────────────────────
                                 match self.appearance_tab {
@@ -8003,6 +9912,9 @@ impl RobcoNativeApp {
                             NativeSettingsPanel::DefaultApps => {
                                 changed |= self.draw_settings_default_apps_panel(ui);
                             }
+                            NativeSettingsPanel::Autostart => {
+                                changed |= self.draw_settings_autostart_panel(ui);
+                            }
                             NativeSettingsPanel::Connections => {
                                 ui.vertical(|ui| {
                                     for item in desktop_settings_connections_nav_items() {
@@ -8228,6 +10140,110 @@ impl RobcoNativeApp {
         changed
     }
 
+    fn draw_settings_autostart_panel(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
+        ui.small(
+            "Opened in order after login, unless a saved session is restored. \
+             Targets: app:, net:, game:, window:, folder: or a command line.",
+        );
+        ui.add_space(6.0);
+        let mut move_item: Option<(usize, usize)> = None;
+        let mut remove_item = None;
+        let count = self.settings.draft.autostart.len();
+        egui::ScrollArea::vertical()
+            .max_height((ui.available_height() - 90.0).max(120.0))
+            .show(ui, |ui| {
+                if count == 0 {
+                    ui.label("No autostart items.");
+                }
+                for (idx, item) in self.settings.draft.autostart.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        if Self::retro_checkbox_row(ui, &mut item.enabled, "").clicked() {
+                            changed = true;
+                        }
+                        for mode in [AutostartMode::Terminal, AutostartMode::Desktop] {
+                            if Self::retro_choice_button(ui, mode.label(), item.mode == mode)
+                                .clicked()
+                                && item.mode != mode
+                            {
+                                item.mode = mode;
+                                changed = true;
+                            }
+                        }
+                        ui.label("Delay");
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut item.delay_secs)
+                                    .range(0..=MAX_AUTOSTART_DELAY_SECS)
+                                    .suffix("s"),
+                            )
+                            .changed()
+                        {
+                            changed = true;
+                        }
+                        if ui.add_enabled(idx > 0, egui::Button::new("Up")).clicked() {
+                            move_item = Some((idx, idx - 1));
+                        }
+                        if ui
+                            .add_enabled(idx + 1 < count, egui::Button::new("Down"))
+                            .clicked()
+                        {
+                            move_item = Some((idx, idx + 1));
+                        }
+                        if ui.button("Remove").clicked() {
+                            remove_item = Some(idx);
+                        }
+                        ui.label(item.target.describe());
+                    });
+                }
+            });
+        if let Some((from, to)) = move_item {
+            self.settings.draft.autostart.swap(from, to);
+            changed = true;
+        }
+        if let Some(idx) = remove_item {
+            self.settings.draft.autostart.remove(idx);
+            changed = true;
+        }
+
+        ui.add_space(8.0);
+        Self::settings_section(ui, "Add Item", |ui| {
+            let field_width = Self::responsive_input_width(ui, 0.6, 220.0, 520.0);
+            ui.horizontal(|ui| {
+                ui.add(
+                    TextEdit::singleline(&mut self.settings.autostart_input)
+                        .desired_width(field_width)
+                        .hint_text("window:files"),
+                );
+                for mode in [AutostartMode::Terminal, AutostartMode::Desktop] {
+                    if Self::retro_choice_button(
+                        ui,
+                        mode.label(),
+                        self.settings.autostart_mode == mode,
+                    )
+                    .clicked()
+                    {
+                        self.settings.autostart_mode = mode;
+                    }
+                }
+                if ui.button("Add").clicked() {
+                    match AutostartTarget::parse(&self.settings.autostart_input) {
+                        Ok(target) => {
+                            self.settings
+                                .draft
+                                .autostart
+                                .push(AutostartItem::new(target, self.settings.autostart_mode));
+                            self.settings.autostart_input.clear();
+                            changed = true;
+                        }
+                        Err(err) => self.settings.status = err,
+                    }
+                }
+            });
+        });
+        changed
+    }
+
     fn draw_settings_connections_kind_panel(&mut self, ui: &mut egui::Ui, kind: ConnectionKind) {
         if connections_macos_disabled() {
             ui.small(connections_macos_disabled_hint());
@@ -8339,6 +10355,35 @@ impl RobcoNativeApp {
         });
     }
 
+    /// Text field for a value edited through its text form. While focused the
+    /// typed text is kept as-is, so a half-typed line or trailing space is not
+    /// reformatted away; returns the text whenever it changes.
+    fn settings_buffered_text_field(
+        ui: &mut egui::Ui,
+        id_salt: impl std::hash::Hash,
+        current: String,
+        multiline: bool,
+        width: f32,
+    ) -> Option<String> {
+        let id = ui.make_persistent_id(id_salt);
+        let mut buffer = ui
+            .data_mut(|data| data.get_temp::<String>(id))
+            .unwrap_or(current);
+        let edit = if multiline {
+            TextEdit::multiline(&mut buffer).desired_rows(3)
+        } else {
+            TextEdit::singleline(&mut buffer)
+        };
+        let response = ui.add(edit.desired_width(width));
+        if response.has_focus() {
+            let kept = buffer.clone();
+            ui.data_mut(|data| data.insert_temp(id, kept));
+        } else {
+            ui.data_mut(|data| data.remove::<String>(id));
+        }
+        response.changed().then_some(buffer)
+    }
+
     fn draw_settings_cli_profiles_panel(&mut self, ui: &mut egui::Ui) -> bool {
         let mut changed = false;
         let custom_profile_count = self.settings.draft.desktop_cli_profiles.custom.len();
@@ -8452,6 +10497,35 @@ impl RobcoNativeApp {
                 {
                     changed = true;
                 }
+                if Self::retro_checkbox_row(
+                    right,
+                    &mut profile.alt_sends_escape,
+                    "Alt sends Escape (Meta)",
+                )
+                .clicked()
+                {
+                    changed = true;
+                }
+                let mut backspace_bs = profile.backspace == PtyBackspaceMode::Backspace;
+                if Self::retro_checkbox_row(right, &mut backspace_bs, "Backspace sends ^H")
+                    .clicked()
+                {
+                    profile.backspace = if backspace_bs {
+                        PtyBackspaceMode::Backspace
+                    } else {
+                        PtyBackspaceMode::Delete
+                    };
+                    changed = true;
+                }
+                if Self::retro_checkbox_row(
+                    right,
+                    &mut profile.application_keypad,
+                    "Application keypad",
+                )
+                .clicked()
+                {
+                    changed = true;
+                }
                 right.add_space(8.0);
                 right.small(format!(
                     "Custom profiles currently stored: {}",
@@ -8459,6 +10533,52 @@ impl RobcoNativeApp {
                 ));
             });
         });
+        ui.add_space(8.0);
+        let launch_salt = format!(
+            "native_settings_cli_launch_{:?}",
+            self.settings.cli_profile_slot
+        );
+        Self::settings_section(ui, "Launch", |ui| {
+            let field_width = Self::responsive_input_width(ui, 0.95, 320.0, 760.0);
+            ui.label("Working Directory");
+            let cwd = profile.launch.cwd.clone().unwrap_or_default();
+            if let Some(text) = Self::settings_buffered_text_field(
+                ui,
+                (&launch_salt, "cwd"),
+                cwd,
+                false,
+                field_width,
+            ) {
+                let text = text.trim();
+                profile.launch.cwd = (!text.is_empty()).then(|| text.to_string());
+                changed = true;
+            }
+            ui.add_space(6.0);
+            ui.label("Extra Arguments");
+            if let Some(text) = Self::settings_buffered_text_field(
+                ui,
+                (&launch_salt, "args"),
+                launch_args_to_text(&profile.launch.args),
+                false,
+                field_width,
+            ) {
+                profile.launch.args = launch_args_from_text(&text);
+                changed = true;
+            }
+            ui.add_space(6.0);
+            ui.label("Environment (KEY=VALUE per line)");
+            if let Some(text) = Self::settings_buffered_text_field(
+                ui,
+                (&launch_salt, "env"),
+                launch_env_to_text(&profile.launch.env),
+                true,
+                field_width,
+            ) {
+                profile.launch.env = launch_env_from_text(&text);
+                changed = true;
+            }
+            ui.small("~ and $VAR are expanded when the program starts.");
+        });
         if min_w != profile.min_w {
             profile.min_w = min_w;
             if let Some(preferred) = profile.preferred_w.as_mut() {
@@ -8498,6 +10618,8 @@ impl RobcoNativeApp {
                         .clicked()
                         {
                             self.settings.edit_target = target;
+                            self.settings.edit_details = None;
+                            self.settings.desktop_import = None;
                             ui.close_menu();
                         }
                     }
@@ -8533,6 +10655,18 @@ impl RobcoNativeApp {
                         for name in self.edit_program_entries(self.settings.edit_target) {
                             ui.horizontal(|ui| {
                                 ui.label(&name);
+                                let target = self.settings.edit_target;
+                                if let Some(draft) = Self::catalog_entry_draft(target, &name) {
+                                    if ui.button("Details").clicked() {
+                                        self.settings.desktop_import = None;
+                                        self.settings.edit_details =
+                                            Some(CatalogEntryDetailsEditor {
+                                                target,
+                                                name: name.clone(),
+                                                draft,
+                                            });
+                                    }
+                                }
                                 if ui.button("Delete").clicked() {
                                     self.delete_program_entry(self.settings.edit_target, &name);
                                     self.apply_status_update(mirror_shell_to_settings(
@@ -8542,8 +10676,30 @@ impl RobcoNativeApp {
                             });
                         }
                     });
+                if matches!(
+                    self.settings.edit_target,
+                    EditMenuTarget::Applications | EditMenuTarget::Games
+                ) && Self::retro_full_width_button(left, "Import Desktop Entries...").clicked()
+                {
+                    self.settings.edit_details = None;
+                    if !self.scan_desktop_import() {
+                        self.apply_status_update(mirror_shell_to_settings(&self.shell_status));
+                    }
+                }
             });
 
+            if self.settings.desktop_import.is_some() {
+                Self::settings_section(right, "Import Desktop Entries", |right| {
+                    self.draw_settings_desktop_import(right);
+                });
+                return;
+            }
+            if self.settings.edit_details.is_some() {
+                Self::settings_section(right, "Entry Details", |right| {
+                    self.draw_settings_entry_details(right);
+                });
+                return;
+            }
             Self::settings_section(right, "Add Entry", |right| {
                 let name_width = Self::responsive_input_width(right, 0.9, 220.0, 420.0);
                 let value_width = Self::responsive_input_width(right, 0.95, 320.0, 760.0);
@@ -8592,6 +10748,124 @@ impl RobcoNativeApp {
         changed
     }
 
+    fn draw_settings_desktop_import(&mut self, ui: &mut egui::Ui) {
+        let Some(rows) = self.settings.desktop_import.as_mut() else {
+            return;
+        };
+        egui::ScrollArea::vertical()
+            .id_salt("native_settings_desktop_import")
+            .max_height((ui.available_height() * 0.7).clamp(180.0, 380.0))
+            .show(ui, |ui| {
+                for row in rows.iter_mut() {
+                    ui.horizontal(|ui| {
+                        Self::retro_checkbox_row(ui, &mut row.selected, &row.app.name);
+                        let games = row.catalog == ProgramCatalog::Games;
+                        let label = if games { "Games" } else { "Applications" };
+                        if ui.button(label).clicked() {
+                            row.catalog = if games {
+                                ProgramCatalog::Applications
+                            } else {
+                                ProgramCatalog::Games
+                            };
+                        }
+                    })
+                    .response
+                    .on_hover_text(row.app.command.join(" "));
+                }
+            });
+        ui.add_space(8.0);
+        let mut import = false;
+        let mut close = false;
+        ui.horizontal(|ui| {
+            import = ui.button("Import Selected").clicked();
+            close = ui.button("Close").clicked();
+        });
+        if import {
+            self.import_selected_desktop_apps();
+            self.apply_status_update(mirror_shell_to_settings(&self.shell_status));
+        }
+        if close {
+            self.settings.desktop_import = None;
+        }
+    }
+
+    fn draw_settings_entry_details(&mut self, ui: &mut egui::Ui) {
+        let Some(editor) = self.settings.edit_details.as_mut() else {
+            return;
+        };
+        let width = Self::responsive_input_width(ui, 0.95, 320.0, 760.0);
+        let profile_names = self.settings.draft.desktop_cli_profiles.profile_names();
+        let draft = &mut editor.draft;
+        ui.label(RichText::new(&editor.name).strong());
+        ui.add_space(6.0);
+        for (label, value) in [
+            ("Command", &mut draft.command),
+            ("Description", &mut draft.description),
+            ("Icon Path", &mut draft.icon),
+            ("Tags (comma separated)", &mut draft.tags),
+            ("Working Directory", &mut draft.working_dir),
+            ("Extra Arguments", &mut draft.arguments),
+        ] {
+            ui.label(label);
+            ui.add(TextEdit::singleline(value).desired_width(width));
+            ui.add_space(4.0);
+        }
+        ui.label("Environment (KEY=VALUE per line)");
+        ui.add(
+            TextEdit::multiline(&mut draft.environment)
+                .desired_rows(3)
+                .desired_width(width),
+        );
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            ui.label("PTY Profile");
+            let selected = if draft.pty_profile.trim().is_empty() {
+                "Automatic".to_string()
+            } else {
+                draft.pty_profile.clone()
+            };
+            egui::ComboBox::from_id_salt("native_settings_entry_pty_profile")
+                .selected_text(RichText::new(selected).color(current_palette().fg))
+                .show_ui(ui, |ui| {
+                    Self::apply_settings_control_style(ui);
+                    if Self::retro_choice_button(ui, "Automatic", draft.pty_profile.is_empty())
+                        .clicked()
+                    {
+                        draft.pty_profile.clear();
+                        ui.close_menu();
+                    }
+                    for profile in &profile_names {
+                        if Self::retro_choice_button(ui, profile, draft.pty_profile == *profile)
+                            .clicked()
+                        {
+                            draft.pty_profile = profile.clone();
+                            ui.close_menu();
+                        }
+                    }
+                });
+        });
+        let mut detached = draft.run_mode == CatalogRunMode::Detached;
+        if Self::retro_checkbox_row(ui, &mut detached, "Run detached (no terminal)").clicked() {
+            draft.run_mode = draft.run_mode.toggled();
+        }
+        Self::retro_checkbox_row(ui, &mut draft.requires_network, "Requires network");
+        ui.add_space(8.0);
+        let mut save = false;
+        let mut close = false;
+        ui.horizontal(|ui| {
+            save = ui.button("Save").clicked();
+            close = ui.button("Close").clicked();
+        });
+        if save {
+            let editor = editor.clone();
+            self.save_catalog_entry_draft(editor.target, &editor.name, &editor.draft);
+            self.apply_status_update(mirror_shell_to_settings(&self.shell_status));
+        }
+        if close {
+            self.settings.edit_details = None;
+        }
+    }
+
     fn draw_settings_user_view_panel(&mut self, ui: &mut egui::Ui) {
         let users = sorted_user_records();
         egui::ScrollArea::vertical().show(ui, |ui| {
@@ -9279,9 +11553,12 @@ impl RobcoNativeApp {
             } = event
             {
                 self.desktop_installer.status = status.clone();
+                let tabs_before = self.desktop_pty_tabs.len();
                 self.open_desktop_pty("Program Installer", &argv);
-                if let Some(pty) = self.terminal_pty.as_mut() {
-                    pty.completion_message = completion_message;
+                if self.desktop_pty_tabs.len() > tabs_before {
+                    if let Some(tab) = self.desktop_pty_tabs.active_mut() {
+                        tab.state.completion_message = completion_message;
+                    }
                 }
             }
         }
@@ -10411,6 +12688,388 @@ impl RobcoNativeApp {
         self.update_desktop_window_state(DesktopWindow::NukeCodes, open);
     }
 
+    fn draw_scheduler_window(&mut self, ctx: &Context) {
+        if !self.scheduler_ui.open || self.desktop_window_is_minimized(DesktopWindow::Scheduler) {
+            return;
+        }
+        let mut open = self.scheduler_ui.open;
+        let maximized = self.desktop_window_is_maximized(DesktopWindow::Scheduler);
+        let restore = self.take_desktop_window_restore_dims(DesktopWindow::Scheduler);
+        let mut header_action = DesktopHeaderAction::None;
+        let mut request = TerminalSchedulerRequest::None;
+        let mut add_job = false;
+        let mut open_log = None;
+        let generation = self.desktop_window_generation(DesktopWindow::Scheduler);
+        let default_size = Self::desktop_default_window_size(DesktopWindow::Scheduler);
+        let mut window = egui::Window::new("Scheduler")
+            .id(Id::new(("native_scheduler", generation)))
+            .open(&mut open)
+            .title_bar(false)
+            .frame(Self::desktop_window_frame())
+            .resizable(true)
+            .min_size([520.0, 300.0])
+            .default_size(default_size);
+        if maximized {
+            let rect = Self::desktop_workspace_rect(ctx);
+            window = window
+                .movable(false)
+                .resizable(false)
+                .fixed_pos(rect.min)
+                .fixed_size(rect.size());
+        } else if let Some((pos, size)) = restore {
+            window = window.current_pos(pos).default_size(size);
+        }
+        let shown = window.show(ctx, |ui| {
+            Self::apply_settings_control_style(ui);
+            header_action = Self::draw_desktop_window_header(ui, "Scheduler", maximized);
+            let state = &mut self.scheduler_ui;
+            let runtime = &self.scheduler;
+            egui::Grid::new("scheduler_new_job")
+                .num_columns(2)
+                .spacing([8.0, 4.0])
+                .show(ui, |ui| {
+                    ui.label("Name:");
+                    ui.add(TextEdit::singleline(&mut state.draft_name).desired_width(260.0));
+                    ui.end_row();
+                    ui.label("Runs:");
+                    ui.add(
+                        TextEdit::singleline(&mut state.draft_target)
+                            .hint_text("app:Name, net:Name, game:Name or a command")
+                            .desired_width(420.0),
+                    );
+                    ui.end_row();
+                    ui.label("When:");
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            TextEdit::singleline(&mut state.draft_schedule)
+                                .hint_text("every 15m, at 03:00, 0 3 * * *")
+                                .desired_width(260.0),
+                        );
+                        ui.checkbox(&mut state.draft_visible, "Open in a window");
+                        if ui.button("Add Job").clicked() {
+                            add_job = true;
+                        }
+                    });
+                    ui.end_row();
+                });
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.selectable_label(!state.show_history, "Jobs").clicked() {
+                    state.show_history = false;
+                }
+                if ui.selectable_label(state.show_history, "History").clicked() {
+                    state.show_history = true;
+                }
+            });
+            ui.separator();
+
+            let schedule = runtime.schedule();
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    if state.show_history {
+                        egui::Grid::new("scheduler_history_grid")
+                            .num_columns(5)
+                            .spacing([16.0, 4.0])
+                            .show(ui, |ui| {
+                                ui.strong("Job");
+                                ui.strong("Started");
+                                ui.strong("Finished");
+                                ui.strong("Result");
+                                ui.label("");
+                                ui.end_row();
+                                for result in schedule.results.iter().rev() {
+                                    ui.label(&result.job_name);
+                                    ui.label(format_job_time(result.started));
+                                    ui.label(format_job_time(result.finished));
+                                    ui.label(result.outcome.label());
+                                    match &result.log {
+                                        Some(log) if log.exists() => {
+                                            if ui.button("Open Log").clicked() {
+                                                open_log = Some(log.clone());
+                                            }
+                                        }
+                                        _ => {
+                                            ui.label("");
+                                        }
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                        if schedule.results.is_empty() {
+                            ui.label("No runs yet.");
+                        }
+                        return;
+                    }
+                    egui::Grid::new("scheduler_jobs_grid")
+                        .num_columns(6)
+                        .spacing([16.0, 4.0])
+                        .show(ui, |ui| {
+                            ui.strong("On");
+                            ui.strong("Name");
+                            ui.strong("When");
+                            ui.strong("Next run");
+                            ui.strong("Last result");
+                            ui.label("");
+                            ui.end_row();
+                            for job in &schedule.jobs {
+                                let mut enabled = job.enabled;
+                                if ui.checkbox(&mut enabled, "").changed() {
+                                    request = TerminalSchedulerRequest::ToggleEnabled(job.id);
+                                }
+                                ui.label(&job.name)
+                                    .on_hover_text(job.target.describe());
+                                ui.label(job.schedule.describe());
+                                ui.label(job_next_run_label(runtime, job));
+                                ui.label(job_last_result_label(runtime, job));
+                                ui.horizontal(|ui| {
+                                    if ui.button("Run Now").clicked() {
+                                        request = TerminalSchedulerRequest::RunNow(job.id);
+                                    }
+                                    let mode = if job.visible { "Window" } else { "Hidden" };
+                                    if ui
+                                        .button(mode)
+                                        .on_hover_text("Toggle between a terminal window and a hidden run with a log")
+                                        .clicked()
+                                    {
+                                        request = TerminalSchedulerRequest::ToggleVisible(job.id);
+                                    }
+                                    if ui.button("Delete").clicked() {
+                                        request = TerminalSchedulerRequest::Remove(job.id);
+                                    }
+                                });
+                                ui.end_row();
+                            }
+                        });
+                    if schedule.jobs.is_empty() {
+                        ui.label("No scheduled jobs.");
+                    }
+                });
+        });
+        let shown_rect = shown.as_ref().map(|inner| inner.response.rect);
+        let shown_contains_pointer = shown
+            .as_ref()
+            .is_some_and(|inner| inner.response.contains_pointer());
+        self.maybe_activate_desktop_window_from_click(
+            ctx,
+            DesktopWindow::Scheduler,
+            shown_contains_pointer,
+        );
+        if add_job {
+            let state = &self.scheduler_ui;
+            match self.scheduler.add_job(
+                &state.draft_name,
+                &state.draft_target,
+                &state.draft_schedule,
+                state.draft_visible,
+                unix_now(),
+            ) {
+                Ok(status) => {
+                    self.shell_status = status;
+                    self.scheduler_ui.draft_name.clear();
+                    self.scheduler_ui.draft_target.clear();
+                    self.scheduler_ui.draft_schedule.clear();
+                    self.scheduler_ui.draft_visible = false;
+                }
+                Err(err) => self.shell_status = err,
+            }
+        }
+        self.apply_scheduler_request(request);
+        if let Some(log) = open_log {
+            self.open_path_in_editor(log);
+        }
+        if !maximized {
+            if let Some(rect) = shown_rect {
+                self.note_desktop_window_rect(DesktopWindow::Scheduler, rect);
+            }
+        }
+        match header_action {
+            DesktopHeaderAction::None => {}
+            DesktopHeaderAction::Close => open = false,
+            DesktopHeaderAction::Minimize => {
+                self.set_desktop_window_minimized(DesktopWindow::Scheduler, true)
+            }
+            DesktopHeaderAction::ToggleMaximize => {
+                self.toggle_desktop_window_maximized(DesktopWindow::Scheduler, shown_rect)
+            }
+        }
+        self.update_desktop_window_state(DesktopWindow::Scheduler, open);
+    }
+
+    fn draw_task_manager_window(&mut self, ctx: &Context) {
+        if !self.task_manager.open || self.desktop_window_is_minimized(DesktopWindow::TaskManager) {
+            return;
+        }
+        self.task_manager.refresh_if_stale();
+        ctx.request_repaint_after(TASK_REFRESH_INTERVAL);
+        let mut open = self.task_manager.open;
+        let maximized = self.desktop_window_is_maximized(DesktopWindow::TaskManager);
+        let restore = self.take_desktop_window_restore_dims(DesktopWindow::TaskManager);
+        let mut header_action = DesktopHeaderAction::None;
+        let mut signal: Option<(u32, TaskSignal)> = None;
+        let mut jump = None;
+        let generation = self.desktop_window_generation(DesktopWindow::TaskManager);
+        let default_size = Self::desktop_default_window_size(DesktopWindow::TaskManager);
+        let mut window = egui::Window::new("Task Manager")
+            .id(Id::new(("native_task_manager", generation)))
+            .open(&mut open)
+            .title_bar(false)
+            .frame(Self::desktop_window_frame())
+            .resizable(true)
+            .min_size([480.0, 280.0])
+            .default_size(default_size);
+        if maximized {
+            let rect = Self::desktop_workspace_rect(ctx);
+            window = window
+                .movable(false)
+                .resizable(false)
+                .fixed_pos(rect.min)
+                .fixed_size(rect.size());
+        } else if let Some((pos, size)) = restore {
+            window = window.current_pos(pos).default_size(size);
+        }
+        let shown = window.show(ctx, |ui| {
+            Self::apply_settings_control_style(ui);
+            header_action = Self::draw_desktop_window_header(ui, "Task Manager", maximized);
+            let state = &mut self.task_manager;
+            ui.horizontal(|ui| {
+                ui.label("Filter:");
+                if ui
+                    .add(TextEdit::singleline(&mut state.filter).desired_width(180.0))
+                    .changed()
+                {
+                    state.selected = 0;
+                }
+                if ui
+                    .checkbox(&mut state.owned_only, "RobCoOS processes only")
+                    .changed()
+                {
+                    state.selected = 0;
+                }
+                if ui.button("Refresh").clicked() {
+                    state.refresh();
+                }
+            });
+            ui.small(format!(
+                "Memory: {} / {}",
+                format_memory(state.used_memory()),
+                format_memory(state.total_memory())
+            ));
+            ui.separator();
+
+            let rows = state.visible_rows();
+            let selected = rows.get(state.selected).cloned();
+            ui.horizontal(|ui| {
+                let enabled = selected.is_some();
+                for task_signal in [
+                    TaskSignal::Term,
+                    TaskSignal::Kill,
+                    TaskSignal::Stop,
+                    TaskSignal::Continue,
+                ] {
+                    if ui
+                        .add_enabled(enabled, egui::Button::new(task_signal.label()))
+                        .clicked()
+                    {
+                        if let Some(row) = &selected {
+                            signal = Some((row.pid, task_signal));
+                        }
+                    }
+                }
+                let pty = selected.as_ref().and_then(|row| row.pty_session());
+                if ui
+                    .add_enabled(pty.is_some(), egui::Button::new("Show PTY"))
+                    .clicked()
+                {
+                    jump = pty;
+                }
+            });
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    egui::Grid::new("task_manager_grid")
+                        .num_columns(5)
+                        .striped(false)
+                        .spacing([16.0, 2.0])
+                        .show(ui, |ui| {
+                            for key in TaskSortKey::ALL {
+                                let marker = if state.sort != key {
+                                    ""
+                                } else if state.descending {
+                                    " v"
+                                } else {
+                                    " ^"
+                                };
+                                if ui
+                                    .add(
+                                        egui::Button::new(
+                                            RichText::new(format!("{}{marker}", key.label()))
+                                                .strong(),
+                                        )
+                                        .frame(false),
+                                    )
+                                    .clicked()
+                                {
+                                    state.sort_by(key);
+                                }
+                            }
+                            ui.strong("Owner");
+                            ui.end_row();
+                            for (idx, row) in rows.iter().enumerate() {
+                                let is_selected = idx == state.selected;
+                                let response =
+                                    ui.selectable_label(is_selected, row.pid.to_string());
+                                let name = ui.selectable_label(is_selected, &row.name);
+                                if response.clicked() || name.clicked() {
+                                    state.selected = idx;
+                                }
+                                if name.double_clicked() {
+                                    jump = row.pty_session();
+                                }
+                                name.on_hover_text(&row.command);
+                                ui.label(format!("{:.1}%", row.cpu));
+                                ui.label(format_memory(row.memory));
+                                ui.label(row.owner.map_or("", |owner| owner.label()));
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+        let shown_rect = shown.as_ref().map(|inner| inner.response.rect);
+        let shown_contains_pointer = shown
+            .as_ref()
+            .is_some_and(|inner| inner.response.contains_pointer());
+        self.maybe_activate_desktop_window_from_click(
+            ctx,
+            DesktopWindow::TaskManager,
+            shown_contains_pointer,
+        );
+        if let Some((pid, task_signal)) = signal {
+            self.send_task_signal(pid, task_signal);
+        }
+        if let Some(session) = jump {
+            self.jump_to_pty_session(session);
+        }
+        if !maximized {
+            if let Some(rect) = shown_rect {
+                self.note_desktop_window_rect(DesktopWindow::TaskManager, rect);
+            }
+        }
+        match header_action {
+            DesktopHeaderAction::None => {}
+            DesktopHeaderAction::Close => open = false,
+            DesktopHeaderAction::Minimize => {
+                self.set_desktop_window_minimized(DesktopWindow::TaskManager, true)
+            }
+            DesktopHeaderAction::ToggleMaximize => {
+                self.toggle_desktop_window_maximized(DesktopWindow::TaskManager, shown_rect)
+            }
+        }
+        self.update_desktop_window_state(DesktopWindow::TaskManager, open);
+    }
+
     fn draw_desktop_pty_window(&mut self, ctx: &Context) {
         if self.desktop_window_is_minimized(DesktopWindow::PtyApp) {
             return;
@@ -10421,13 +13080,29 @@ impl RobcoNativeApp {
         let default_size = Self::desktop_default_window_size(DesktopWindow::PtyApp);
         let default_pos = Self::desktop_default_window_pos(ctx, default_size);
         let pty_focused = self.desktop_active_window == Some(DesktopWindow::PtyApp);
-        let Some(state) = self.terminal_pty.as_mut() else {
+        self.reap_exited_background_pty_tabs();
+        let tab_strip: Vec<DesktopPtyTabStripEntry> = self
+            .desktop_pty_tabs
+            .iter()
+            .map(|tab| DesktopPtyTabStripEntry {
+                id: tab.id,
+                title: native_pty_tab_title(tab),
+                broadcast: self.pty_broadcast.includes(tab.state.session.id()),
+                session_id: tab.state.session.id(),
+            })
+            .collect();
+        let broadcast_enabled = self.pty_broadcast.is_enabled();
+        let Some(tab) = self.desktop_pty_tabs.active_mut() else {
             self.update_desktop_window_state(DesktopWindow::PtyApp, false);
             return;
         };
+        let tab_id = tab.id;
+        let title = native_pty_tab_title(tab);
+        let state = &mut tab.state;
         let mut open = true;
+        let mut close_requested = false;
         let mut header_action = DesktopHeaderAction::None;
-        let title = state.title.clone();
+        let mut tab_action = DesktopPtyTabAction::None;
         let mut event = PtyScreenEvent::None;
         let min_size = Self::native_pty_window_min_size(state);
         let mut window = egui::Window::new(title.clone())
@@ -10456,6 +13131,8 @@ impl RobcoNativeApp {
             // extreme_bg_color and margins, which destabilizes available_size()
             // causing resize oscillation (constant SIGWINCH) for ncurses apps.
             header_action = Self::draw_desktop_window_header(ui, &title, maximized);
+            tab_action =
+                Self::draw_desktop_pty_tab_strip(ui, &tab_strip, tab_id, broadcast_enabled);
             let available = ui.available_size();
             let cols_floor = state.desktop_cols_floor.unwrap_or(40) as usize;
             let rows_floor = state.desktop_rows_floor.unwrap_or(20).saturating_add(1) as usize;
@@ -10482,27 +13159,16 @@ impl RobcoNativeApp {
         // Context menus are attached to specific content widgets inside the
         // window closure, not to the outer Area response (which causes
         // "double use of widget" ID collisions in egui 0.29).
-        let completion_message = state.completion_message.clone();
-        let title_for_exit = state.title.clone();
         let mut desktop_exit_plan: Option<TerminalDesktopPtyExitPlan> = None;
 
         match event {
             PtyScreenEvent::None => {}
-            PtyScreenEvent::CloseRequested => open = false,
+            PtyScreenEvent::CloseRequested => close_requested = true,
             PtyScreenEvent::ProcessExited => {
-                let exit_status = state.session.exit_status();
-                let success = exit_status
-                    .as_ref()
-                    .map(|status| status.success())
-                    .unwrap_or(true);
-                let exit_code = exit_status.as_ref().map(|status| status.exit_code());
-                open = false;
-                desktop_exit_plan = Some(resolve_desktop_pty_exit(
-                    &title_for_exit,
-                    completion_message.as_deref(),
-                    success,
-                    exit_code,
-                ));
+                if let Some(tab) = self.desktop_pty_tabs.remove(tab_id) {
+                    desktop_exit_plan = Some(Self::desktop_pty_tab_exit_plan(tab));
+                }
+                open = !self.desktop_pty_tabs.is_empty();
             }
         }
 
@@ -10522,7 +13188,7 @@ impl RobcoNativeApp {
 
         match header_action {
             DesktopHeaderAction::None => {}
-            DesktopHeaderAction::Close => open = false,
+            DesktopHeaderAction::Close => close_requested = true,
             DesktopHeaderAction::Minimize => {
                 self.set_desktop_window_minimized(DesktopWindow::PtyApp, true)
             }
@@ -10530,7 +13196,160 @@ impl RobcoNativeApp {
                 self.toggle_desktop_window_maximized(DesktopWindow::PtyApp, shown_rect)
             }
         }
+        if close_requested && open {
+            self.request_close_desktop_pty_window();
+            return;
+        }
         self.update_desktop_window_state(DesktopWindow::PtyApp, open);
+        if open {
+            self.apply_desktop_pty_tab_action(tab_action);
+        }
+    }
+
+    fn desktop_pty_tab_exit_plan(
+        mut tab: TerminalTab<NativePtyState>,
+    ) -> TerminalDesktopPtyExitPlan {
+        let title = native_pty_tab_title(&tab);
+        let exit_status = tab.state.session.exit_status();
+        let success = exit_status
+            .as_ref()
+            .map(|status| status.success())
+            .unwrap_or(true);
+        let exit_code = exit_status.as_ref().map(|status| status.exit_code());
+        resolve_desktop_pty_exit(
+            &title,
+            tab.state.completion_message.as_deref(),
+            success,
+            exit_code,
+        )
+    }
+
+    /// Background tabs never see `PtyScreenEvent::ProcessExited`, so drop
+    /// them here once their child is gone.
+    fn reap_exited_background_pty_tabs(&mut self) {
+        let active_id = self.desktop_pty_tabs.active_id();
+        let exited: Vec<TerminalTabId> = self
+            .desktop_pty_tabs
+            .iter_mut()
+            .filter_map(|tab| {
+                (Some(tab.id) != active_id && !tab.state.session.is_alive()).then_some(tab.id)
+            })
+            .collect();
+        for id in exited {
+            if let Some(tab) = self.desktop_pty_tabs.remove(id) {
+                let plan = Self::desktop_pty_tab_exit_plan(tab);
+                self.apply_terminal_desktop_pty_exit_plan(plan);
+            }
+        }
+    }
+
+    fn draw_desktop_pty_tab_strip(
+        ui: &mut egui::Ui,
+        tabs: &[DesktopPtyTabStripEntry],
+        active_id: TerminalTabId,
+        broadcast_enabled: bool,
+    ) -> DesktopPtyTabAction {
+        let palette = current_palette();
+        let mut action = DesktopPtyTabAction::None;
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing.x = 6.0;
+            if broadcast_enabled {
+                let badge = RichText::new("[BROADCAST]")
+                    .color(palette.selected_fg)
+                    .background_color(palette.selected_bg)
+                    .strong()
+                    .monospace();
+                if ui
+                    .add(egui::Button::new(badge).frame(false))
+                    .on_hover_text(
+                        "Input is mirrored to every session marked with *. \
+                         Click or press Ctrl+Shift+B to stop.",
+                    )
+                    .clicked()
+                {
+                    action = DesktopPtyTabAction::ToggleBroadcast;
+                }
+            }
+            for (idx, tab) in tabs.iter().enumerate() {
+                let id = &tab.id;
+                let title = &tab.title;
+                let active = *id == active_id;
+                let marker = if tab.broadcast { "*" } else { "" };
+                let label = format!("[{}]{marker} {title}", idx + 1);
+                let text = if active {
+                    RichText::new(label)
+                        .color(palette.selected_fg)
+                        .background_color(palette.selected_bg)
+                } else {
+                    RichText::new(label).color(palette.fg)
+                };
+                let response = ui.add(egui::Button::new(text.monospace()).frame(false));
+                if response.clicked() {
+                    action = DesktopPtyTabAction::Activate(*id);
+                }
+                if response.middle_clicked() {
+                    action = DesktopPtyTabAction::Close(*id);
+                }
+                response.context_menu(|ui| {
+                    if ui.button("Rename Tab").clicked() {
+                        action = DesktopPtyTabAction::Rename(*id);
+                        ui.close_menu();
+                    }
+                    if ui
+                        .add_enabled(idx > 0, egui::Button::new("Move Left"))
+                        .clicked()
+                    {
+                        action = DesktopPtyTabAction::MoveLeft(*id);
+                        ui.close_menu();
+                    }
+                    if ui
+                        .add_enabled(idx + 1 < tabs.len(), egui::Button::new("Move Right"))
+                        .clicked()
+                    {
+                        action = DesktopPtyTabAction::MoveRight(*id);
+                        ui.close_menu();
+                    }
+                    if ui.button("Close Tab").clicked() {
+                        action = DesktopPtyTabAction::Close(*id);
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    let broadcast_label = if broadcast_enabled {
+                        "Stop Broadcast"
+                    } else {
+                        "Broadcast Input"
+                    };
+                    if ui.button(broadcast_label).clicked() {
+                        action = DesktopPtyTabAction::ToggleBroadcast;
+                        ui.close_menu();
+                    }
+                    let opt_out_label = if tab.broadcast || !broadcast_enabled {
+                        "Exclude From Broadcast"
+                    } else {
+                        "Include In Broadcast"
+                    };
+                    if ui
+                        .add_enabled(broadcast_enabled, egui::Button::new(opt_out_label))
+                        .clicked()
+                    {
+                        action = DesktopPtyTabAction::ToggleBroadcastOptOut(tab.session_id);
+                        ui.close_menu();
+                    }
+                });
+            }
+            if ui
+                .add(
+                    egui::Button::new(RichText::new("[+]").color(palette.fg).monospace())
+                        .frame(false),
+                )
+                .on_hover_text("New Tab (Ctrl+Shift+T)")
+                .clicked()
+            {
+                action = DesktopPtyTabAction::NewTab;
+            }
+        });
+        ui.add_space(2.0);
+        action
     }
 
     fn draw_terminal_mode(&mut self, ctx: &Context) {
@@ -10571,6 +13390,38 @@ impl RobcoNativeApp {
             self.run_file_manager_command(FileManagerCommand::Undo);
         } else if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(Key::Y)) {
             self.run_file_manager_command(FileManagerCommand::Redo);
+        } else if ctx.input(|i| i.modifiers.ctrl && i.modifiers.shift && i.key_pressed(Key::F)) {
+            self.run_file_manager_command(FileManagerCommand::FindFiles);
+        } else if ctx.input(|i| i.modifiers.alt && i.key_pressed(Key::Enter)) {
+            self.run_file_manager_command(FileManagerCommand::Properties);
+        } else if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(Key::B)) {
+            self.run_file_manager_command(FileManagerCommand::AddBookmark);
+        } else if load_desktop_file_manager_settings().dual_pane {
+            self.handle_desktop_commander_shortcuts(ctx);
+        }
+    }
+
+    /// Commander function keys, only while the dual-pane layout is on.
+    fn handle_desktop_commander_shortcuts(&mut self, ctx: &Context) {
+        let command = ctx.input(|i| {
+            if i.key_pressed(Key::F5) {
+                Some(FileManagerCommand::CopyToOtherPane)
+            } else if i.key_pressed(Key::F6) {
+                Some(FileManagerCommand::MoveToOtherPane)
+            } else if i.key_pressed(Key::F7) {
+                Some(FileManagerCommand::NewFolder)
+            } else if i.key_pressed(Key::F8) {
+                Some(FileManagerCommand::Delete)
+            } else {
+                None
+            }
+        });
+        let command = command.or_else(|| {
+            (!ctx.wants_keyboard_input() && ctx.input(|i| i.key_pressed(Key::Tab)))
+                .then_some(FileManagerCommand::SwitchPane)
+        });
+        if let Some(command) = command {
+            self.run_file_manager_command(command);
         }
     }
 }
@@ -10590,16 +13441,60 @@ impl eframe::App for RobcoNativeApp {
         // consume Event::Key and Event::Text from the events list during
         // their show() calls, leaving the PTY with zero events if it runs
         // after them.
+        if self.session.is_some()
+            && self.terminal_flash.is_none()
+            && ctx.input_mut(|i| i.consume_key(Modifiers::CTRL | Modifiers::SHIFT, Key::Space))
+        {
+            self.toggle_command_palette();
+        }
         let mut early_pty_close = false;
-        if self.desktop_mode_open && self.desktop_active_window == Some(DesktopWindow::PtyApp) {
-            if let Some(state) = self.terminal_pty.as_mut() {
+        let mut early_tab_action = DesktopPtyTabAction::None;
+        if self.desktop_mode_open
+            && self.desktop_active_window == Some(DesktopWindow::PtyApp)
+            && self.terminal_prompt.is_none()
+            && !self.palette_open
+        {
+            let tab_count = self.desktop_pty_tabs.len();
+            if let Some(tab) = self.desktop_pty_tabs.active_mut() {
+                let state = &mut tab.state;
                 if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(Key::Q)) {
                     early_pty_close = true;
                 }
+                if ctx.input(|i| i.modifiers.ctrl && i.modifiers.shift && i.key_pressed(Key::T)) {
+                    early_tab_action = DesktopPtyTabAction::NewTab;
+                } else if ctx
+                    .input(|i| i.modifiers.ctrl && i.modifiers.shift && i.key_pressed(Key::B))
+                {
+                    early_tab_action = DesktopPtyTabAction::ToggleBroadcast;
+                } else if ctx
+                    .input(|i| i.modifiers.ctrl && i.modifiers.shift && i.key_pressed(Key::E))
+                {
+                    early_tab_action =
+                        DesktopPtyTabAction::ToggleBroadcastOptOut(state.session.id());
+                } else if ctx
+                    .input(|i| i.modifiers.ctrl && i.modifiers.shift && i.key_pressed(Key::W))
+                {
+                    early_tab_action = DesktopPtyTabAction::Close(tab.id);
+                }
+                let cycle = ctx.input(|i| {
+                    if !i.modifiers.ctrl || tab_count < 2 {
+                        0
+                    } else if i.key_pressed(Key::PageDown) {
+                        1
+                    } else if i.key_pressed(Key::PageUp) {
+                        -1
+                    } else {
+                        0
+                    }
+                });
                 if ctx.input(|i| i.modifiers.ctrl && i.modifiers.shift && i.key_pressed(Key::P)) {
                     state.show_perf_overlay = !state.show_perf_overlay;
                 }
-                handle_pty_input(ctx, &mut state.session);
+                if early_tab_action == DesktopPtyTabAction::None && cycle == 0 {
+                    let inputs = handle_pty_input(ctx, &mut state.session);
+                    state.sent_input.extend(inputs);
+                }
+                self.desktop_pty_tabs.cycle(cycle);
                 // Clear keyboard events so the later draw pass doesn't
                 // double-process them.
                 ctx.input_mut(|i| {
@@ -10613,11 +13508,11 @@ impl eframe::App for RobcoNativeApp {
             }
         }
         if early_pty_close {
-            if let Some(mut pty) = self.terminal_pty.take() {
-                pty.session.terminate();
-            }
-            self.update_desktop_window_state(DesktopWindow::PtyApp, false);
+            self.request_close_desktop_pty_window();
+        } else {
+            self.apply_desktop_pty_tab_action(early_tab_action);
         }
+        self.flush_pty_broadcast();
         apply_native_appearance(ctx);
 
         if let Some(flash) = &self.terminal_flash {
@@ -10633,6 +13528,8 @@ impl eframe::App for RobcoNativeApp {
                     FlashAction::FinishLogin { username, user } => {
                         self.ensure_login_session_entry(&username);
                         self.restore_for_user(&username, &user);
+                        self.autostart
+                            .start(&self.settings.draft.autostart, Instant::now());
                     }
                     _ => {
                         if let Some(plan) =
@@ -10686,6 +13583,14 @@ impl eframe::App for RobcoNativeApp {
             return;
         }
 
+        self.tick_scheduler(ctx);
+        self.poll_file_manager_transfers(ctx);
+        self.poll_file_manager_search(ctx);
+        self.track_file_manager_recent_folder();
+        self.sync_file_manager_watches(ctx);
+        self.tick_autostart(ctx);
+        self.draw_command_palette(ctx);
+
         if !self.desktop_mode_open {
             self.capture_session_switch_shortcuts(ctx);
             if has_native_pending_session_switch() {
@@ -10698,7 +13603,11 @@ impl eframe::App for RobcoNativeApp {
         if !self.desktop_mode_open
             && !matches!(self.terminal_nav.screen, TerminalScreen::PtyApp)
             && !self.editor.open
-            && ctx.input(|i| i.key_pressed(Key::Escape) || i.key_pressed(Key::Tab))
+            && ctx.input(|i| {
+                i.key_pressed(Key::Escape)
+                    || (i.key_pressed(Key::Tab)
+                        && !matches!(self.terminal_nav.screen, TerminalScreen::Commander))
+            })
         {
             self.handle_terminal_back();
         }
@@ -10729,17 +13638,22 @@ impl eframe::App for RobcoNativeApp {
                 TerminalScreen::Logs => self.draw_terminal_logs(ctx),
                 TerminalScreen::Network => self.draw_terminal_network(ctx),
                 TerminalScreen::Games => self.draw_terminal_games(ctx),
+                TerminalScreen::Recent => self.draw_terminal_recent(ctx),
                 TerminalScreen::DonkeyKong => self.draw_terminal_donkey_kong(ctx),
                 TerminalScreen::NukeCodes => self.draw_terminal_nuke_codes(ctx),
                 TerminalScreen::PtyApp => self.draw_terminal_pty(ctx),
                 TerminalScreen::ProgramInstaller => self.draw_terminal_program_installer(ctx),
+                TerminalScreen::TaskManager => self.draw_terminal_task_manager(ctx),
+                TerminalScreen::Scheduler => self.draw_terminal_scheduler(ctx),
                 TerminalScreen::DocumentBrowser => self.draw_terminal_document_browser(ctx),
                 TerminalScreen::Settings => self.draw_terminal_settings(ctx),
                 TerminalScreen::EditMenus => self.draw_terminal_edit_menus(ctx),
                 TerminalScreen::Connections => self.draw_terminal_connections(ctx),
                 TerminalScreen::DefaultApps => self.draw_terminal_default_apps(ctx),
+                TerminalScreen::Autostart => self.draw_terminal_autostart(ctx),
                 TerminalScreen::About => self.draw_terminal_about(ctx),
                 TerminalScreen::UserManagement => self.draw_terminal_user_management(ctx),
+                TerminalScreen::Commander => self.draw_terminal_commander(ctx),
             }
         }
         if self.desktop_mode_open {
@@ -10755,6 +13669,11 @@ impl eframe::App for RobcoNativeApp {
             self.draw_terminal_mode(ctx);
         }
         self.draw_shortcut_properties_window(ctx);
+        self.draw_file_manager_batch_rename_window(ctx);
+        self.draw_file_manager_find_window(ctx);
+        self.draw_file_manager_properties_window(ctx);
+        self.draw_file_manager_checksums_window(ctx);
+        self.draw_file_manager_transfers_window(ctx);
         self.draw_editor_save_as_window(ctx);
         self.draw_terminal_prompt_overlay_global(ctx);
 
@@ -11509,11 +14428,16 @@ mod tests {
         app.spotlight_open = true;
         app.spotlight_query = "demo".to_string();
 
-        app.spotlight_activate_result(&NativeSpotlightResult {
-            name: "demo.txt".to_string(),
-            category: NativeSpotlightCategory::File,
-            path: Some(file_path.clone()),
-        });
+        app.spotlight_activate_result(
+            &Context::default(),
+            &NativeSpotlightResult {
+                name: "demo.txt".to_string(),
+                category: NativeSpotlightCategory::File,
+                path: Some(file_path.clone()),
+                description: None,
+                snippet: None,
+            },
+        );
 
         assert!(!app.spotlight_open);
         assert!(app.file_manager.open);
@@ -1,7 +1,24 @@
 use super::*;
 use crate::config::FileManagerViewMode;
+use crate::native::file_manager_app::{
+    format_bytes_human, format_sum_line, format_timestamp, mode_string, preview_batch_rename,
+    save_search, saved_searches_dir, with_executable, write_sum_file, ChecksumAlgorithm,
+    ChecksumPhase, ChecksumResults, ChecksumTask, CommanderSide, ConflictChoice, FolderSize,
+    PlaceSection, PropertiesKind, RenameCase, SearchKind, TransferState, VerifyStatus,
+    FIND_MODIFIED_WITHIN,
+};
 use crate::native::file_manager_desktop::FILE_MANAGER_APP_TITLE;
 
+/// The focused commander pane's rows and selection, which drive its row
+/// clicks and context menus.
+struct CommanderActivePane<'a> {
+    desktop_model: &'a file_manager_desktop::FileManagerDesktopViewModel,
+    action_selection_paths: &'a [PathBuf],
+    has_editable_selection: bool,
+    has_single_file_selection: bool,
+    has_clipboard: bool,
+}
+
 impl RobcoNativeApp {
     fn attach_file_manager_context_menu(
         action: &mut Option<ContextMenuAction>,
@@ -9,6 +26,7 @@ impl RobcoNativeApp {
         has_selection: bool,
         has_file_selection: bool,
         has_clipboard: bool,
+        in_search: bool,
     ) {
         response.context_menu(|ui| {
             Self::apply_context_menu_style(ui);
@@ -33,6 +51,17 @@ impl RobcoNativeApp {
                 *action = Some(ContextMenuAction::OpenWith);
                 ui.close_menu();
             }
+            if in_search {
+                let reveal = if has_selection {
+                    ui.button("Reveal in Folder")
+                } else {
+                    Self::retro_disabled_button(ui, "Reveal in Folder")
+                };
+                if reveal.clicked() {
+                    *action = Some(ContextMenuAction::RevealInFolder);
+                    ui.close_menu();
+                }
+            }
 
             Self::retro_separator(ui);
 
@@ -115,6 +144,919 @@ impl RobcoNativeApp {
         });
     }
 
+    /// Queued and running transfers. Closing only hides the panel; it comes
+    /// back on its own while a conflict prompt waits for an answer.
+    pub(super) fn draw_file_manager_transfers_window(&mut self, ctx: &Context) {
+        let jobs = self.file_manager_transfers.jobs();
+        let conflict = self.file_manager_transfers.conflict();
+        if jobs.is_empty() {
+            self.file_manager_transfers_open = false;
+            return;
+        }
+        if !self.file_manager_transfers_open && conflict.is_none() {
+            return;
+        }
+        let palette = current_palette();
+        let mut close = false;
+        let mut toggle_pause = None;
+        let mut cancel = None;
+        let mut answer = None;
+        let mut apply_to_all = self.file_manager_transfer_apply_all;
+
+        egui::Window::new("file_manager_transfers_window")
+            .title_bar(false)
+            .resizable(false)
+            .collapsible(false)
+            .frame(Self::desktop_window_frame())
+            .default_width(420.0)
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-16.0, -48.0))
+            .show(ctx, |ui| {
+                Self::apply_settings_control_style(ui);
+                ui.set_width(420.0);
+                if matches!(
+                    Self::draw_desktop_window_header(ui, "Transfers", false),
+                    DesktopHeaderAction::Close
+                ) {
+                    close = true;
+                }
+                for job in &jobs {
+                    ui.add_space(6.0);
+                    let state = match job.state {
+                        TransferState::Running => "",
+                        TransferState::Queued => " [Queued]",
+                        TransferState::Paused => " [Paused]",
+                        TransferState::WaitingForAnswer => " [Waiting]",
+                    };
+                    ui.label(
+                        RichText::new(format!(
+                            "{}{state}",
+                            Self::truncate_file_manager_label(&job.title, 44)
+                        ))
+                        .strong(),
+                    );
+                    if job.state != TransferState::Queued {
+                        let (rect, _) = ui.allocate_exact_size(
+                            egui::vec2(ui.available_width(), 12.0),
+                            egui::Sense::hover(),
+                        );
+                        ui.painter()
+                            .rect_stroke(rect, 0.0, egui::Stroke::new(1.0, palette.fg));
+                        let mut filled = rect.shrink(2.0);
+                        filled.set_width(filled.width() * job.progress.fraction().clamp(0.0, 1.0));
+                        ui.painter().rect_filled(filled, 0.0, palette.fg);
+                        ui.colored_label(palette.dim, job.progress.summary());
+                    }
+                    ui.horizontal(|ui| {
+                        if job.state != TransferState::Queued {
+                            let label = if job.state == TransferState::Paused {
+                                "Resume"
+                            } else {
+                                "Pause"
+                            };
+                            if ui.button(label).clicked() {
+                                toggle_pause = Some(job.id);
+                            }
+                        }
+                        if ui.button("Cancel").clicked() {
+                            cancel = Some(job.id);
+                        }
+                    });
+                }
+                if let Some(conflict) = &conflict {
+                    ui.add_space(6.0);
+                    Self::retro_separator(ui);
+                    let name = conflict
+                        .dst
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    let folder = conflict
+                        .dst
+                        .parent()
+                        .map(|parent| parent.display().to_string())
+                        .unwrap_or_default();
+                    ui.label(
+                        RichText::new(format!(
+                            "\"{}\" already exists in {}.",
+                            Self::truncate_file_manager_label(&name, 32),
+                            Self::truncate_file_manager_label(&folder, 32)
+                        ))
+                        .strong(),
+                    );
+                    ui.colored_label(palette.dim, "Overwritten items are moved to the trash.");
+                    ui.checkbox(&mut apply_to_all, "Apply to all");
+                    ui.horizontal(|ui| {
+                        for (label, choice) in [
+                            ("Skip", ConflictChoice::Skip),
+                            ("Overwrite", ConflictChoice::Overwrite),
+                            ("Keep Both", ConflictChoice::KeepBoth),
+                        ] {
+                            if ui.button(label).clicked() {
+                                answer = Some(choice);
+                            }
+                        }
+                    });
+                }
+            });
+
+        self.file_manager_transfer_apply_all = apply_to_all;
+        if let Some(choice) = answer {
+            self.file_manager_transfers
+                .answer_conflict(choice, apply_to_all);
+            self.file_manager_transfer_apply_all = false;
+        }
+        if let Some(id) = toggle_pause {
+            self.file_manager_transfers.toggle_pause(id);
+        }
+        if let Some(id) = cancel {
+            self.file_manager_transfers.cancel(id);
+        }
+        if close {
+            self.file_manager_transfers_open = false;
+        }
+    }
+
+    pub(super) fn draw_file_manager_batch_rename_window(&mut self, ctx: &Context) {
+        let Some(dialog) = self.file_manager_batch_rename.as_mut() else {
+            return;
+        };
+        let palette = current_palette();
+        let mut close = false;
+        let mut apply = false;
+        let rule = &mut dialog.rule;
+
+        egui::Window::new("file_manager_batch_rename_window")
+            .title_bar(false)
+            .resizable(false)
+            .collapsible(false)
+            .frame(Self::desktop_window_frame())
+            .fixed_size(egui::vec2(560.0, 460.0))
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                Self::apply_settings_control_style(ui);
+                let title = format!("Batch Rename ({} items)", dialog.entries.len());
+                if matches!(
+                    Self::draw_desktop_window_header(ui, &title, false),
+                    DesktopHeaderAction::Close
+                ) {
+                    close = true;
+                }
+                ui.add_space(8.0);
+
+                egui::Grid::new("fm_batch_rename_fields")
+                    .num_columns(2)
+                    .spacing([8.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label("Find");
+                        ui.horizontal(|ui| {
+                            ui.add(TextEdit::singleline(&mut rule.find).desired_width(300.0));
+                            ui.checkbox(&mut rule.use_regex, "Regex");
+                        });
+                        ui.end_row();
+                        ui.label("Replace");
+                        ui.add(TextEdit::singleline(&mut rule.replace).desired_width(300.0));
+                        ui.end_row();
+                        ui.label("Name");
+                        ui.add(
+                            TextEdit::singleline(&mut rule.template)
+                                .desired_width(300.0)
+                                .hint_text("{name} {n:03} {ext} {date} {time}"),
+                        );
+                        ui.end_row();
+                        ui.label("Start at");
+                        ui.add(egui::DragValue::new(&mut rule.start).range(0..=999_999));
+                        ui.end_row();
+                        ui.label("Case");
+                        ui.horizontal(|ui| {
+                            for case in RenameCase::ALL {
+                                ui.selectable_value(&mut rule.case, case, case.label());
+                            }
+                        });
+                        ui.end_row();
+                        ui.checkbox(&mut rule.change_extension, "Extension");
+                        ui.add_enabled(
+                            rule.change_extension,
+                            TextEdit::singleline(&mut rule.extension)
+                                .desired_width(120.0)
+                                .hint_text("none"),
+                        );
+                        ui.end_row();
+                    });
+
+                ui.add_space(6.0);
+                Self::retro_separator(ui);
+                let preview = preview_batch_rename(&dialog.entries, rule);
+                let mut can_apply = false;
+                match &preview {
+                    Ok(rows) => {
+                        let conflicts = rows.iter().filter(|row| row.conflict.is_some()).count();
+                        let changed = rows.iter().filter(|row| row.changed()).count();
+                        can_apply = conflicts == 0 && changed > 0;
+                        egui::ScrollArea::vertical()
+                            .id_salt("fm_batch_rename_preview")
+                            .max_height(200.0)
+                            .auto_shrink([false, false])
+                            .show(ui, |ui| {
+                                egui::Grid::new("fm_batch_rename_rows")
+                                    .num_columns(3)
+                                    .striped(true)
+                                    .show(ui, |ui| {
+                                        ui.label(RichText::new("Current").strong());
+                                        ui.label(RichText::new("New").strong());
+                                        ui.label(RichText::new("Status").strong());
+                                        ui.end_row();
+                                        for row in rows {
+                                            ui.label(Self::truncate_file_manager_label(
+                                                &row.old_name,
+                                                28,
+                                            ));
+                                            ui.label(Self::truncate_file_manager_label(
+                                                &row.new_name,
+                                                28,
+                                            ));
+                                            match &row.conflict {
+                                                Some(conflict) => {
+                                                    ui.label(
+                                                        RichText::new(conflict)
+                                                            .strong()
+                                                            .underline(),
+                                                    );
+                                                }
+                                                None if row.changed() => {
+                                                    ui.label("OK");
+                                                }
+                                                None => {
+                                                    ui.colored_label(palette.dim, "Unchanged");
+                                                }
+                                            }
+                                            ui.end_row();
+                                        }
+                                    });
+                            });
+                        ui.colored_label(
+                            palette.dim,
+                            format!("{changed} to rename, {conflicts} conflict(s)"),
+                        );
+                    }
+                    Err(err) => {
+                        ui.label(RichText::new(err.to_string()).strong());
+                    }
+                }
+
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    let rename = if can_apply {
+                        ui.button("Rename")
+                    } else {
+                        Self::retro_disabled_button(ui, "Rename")
+                    };
+                    if rename.clicked() {
+                        apply = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        close = true;
+                    }
+                });
+            });
+
+        if apply {
+            if let Some(dialog) = self.file_manager_batch_rename.take() {
+                self.shell_status = match self.file_manager_runtime.batch_rename_selected(
+                    &mut self.file_manager,
+                    dialog.entries,
+                    &dialog.rule,
+                ) {
+                    Ok(status) => status,
+                    Err(err) => format!("Batch rename failed: {err}"),
+                };
+            }
+        } else if close {
+            self.file_manager_batch_rename = None;
+        }
+    }
+
+    pub(super) fn draw_file_manager_find_window(&mut self, ctx: &Context) {
+        let Some(dialog) = self.file_manager_find.as_mut() else {
+            return;
+        };
+        let palette = current_palette();
+        let mut close = false;
+        let mut search = false;
+        let mut save = false;
+
+        egui::Window::new("file_manager_find_window")
+            .title_bar(false)
+            .resizable(false)
+            .collapsible(false)
+            .frame(Self::desktop_window_frame())
+            .fixed_size(egui::vec2(560.0, 400.0))
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                Self::apply_settings_control_style(ui);
+                if matches!(
+                    Self::draw_desktop_window_header(ui, "Find Files", false),
+                    DesktopHeaderAction::Close
+                ) {
+                    close = true;
+                }
+                ui.add_space(8.0);
+
+                egui::Grid::new("fm_find_fields")
+                    .num_columns(2)
+                    .spacing([8.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label("Look in");
+                        ui.add(TextEdit::singleline(&mut dialog.root).desired_width(380.0));
+                        ui.end_row();
+                        ui.label("Name");
+                        ui.horizontal(|ui| {
+                            ui.add(
+                                TextEdit::singleline(&mut dialog.name)
+                                    .desired_width(300.0)
+                                    .hint_text("*.txt, report-??.doc or any text"),
+                            );
+                            ui.checkbox(&mut dialog.use_regex, "Regex");
+                        });
+                        ui.end_row();
+                        ui.label("Containing");
+                        ui.add(
+                            TextEdit::singleline(&mut dialog.contents)
+                                .desired_width(380.0)
+                                .hint_text("text inside files"),
+                        );
+                        ui.end_row();
+                        ui.label("Size");
+                        ui.horizontal(|ui| {
+                            ui.add(
+                                TextEdit::singleline(&mut dialog.min_size)
+                                    .desired_width(90.0)
+                                    .hint_text("min, e.g. 10K"),
+                            );
+                            ui.label("to");
+                            ui.add(
+                                TextEdit::singleline(&mut dialog.max_size)
+                                    .desired_width(90.0)
+                                    .hint_text("max, e.g. 2MB"),
+                            );
+                        });
+                        ui.end_row();
+                        ui.label("Modified");
+                        ui.horizontal_wrapped(|ui| {
+                            for (label, days) in FIND_MODIFIED_WITHIN {
+                                ui.selectable_value(&mut dialog.modified_within_days, days, label);
+                            }
+                        });
+                        ui.end_row();
+                        ui.label("Type");
+                        ui.horizontal_wrapped(|ui| {
+                            for kind in SearchKind::ALL {
+                                ui.selectable_value(&mut dialog.kind, kind, kind.label());
+                            }
+                        });
+                        ui.end_row();
+                        ui.label("");
+                        ui.checkbox(&mut dialog.include_hidden, "Include hidden files");
+                        ui.end_row();
+                    });
+
+                if let Some(error) = &dialog.error {
+                    ui.add_space(4.0);
+                    ui.label(RichText::new(error).strong());
+                }
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Search").clicked() {
+                        search = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        close = true;
+                    }
+                });
+                ui.add_space(6.0);
+                Self::retro_separator(ui);
+                ui.colored_label(
+                    palette.dim,
+                    "Saved searches appear under Searches in the tree and open like folders.",
+                );
+                ui.horizontal(|ui| {
+                    ui.label("Save as");
+                    ui.add(TextEdit::singleline(&mut dialog.save_name).desired_width(240.0));
+                    let save_button = if dialog.save_name.trim().is_empty() {
+                        Self::retro_disabled_button(ui, "Save Search")
+                    } else {
+                        ui.button("Save Search")
+                    };
+                    if save_button.clicked() {
+                        save = true;
+                    }
+                });
+            });
+
+        if search || save {
+            let result = dialog.spec().and_then(|spec| {
+                if !save {
+                    let status = format!("Searching {}", spec.describe());
+                    self.file_manager.start_search(spec)?;
+                    return Ok(status);
+                }
+                let path = save_search(&saved_searches_dir(), &dialog.save_name, &spec)?;
+                // Open the saved search right away, like any other folder.
+                self.file_manager.set_cwd(path);
+                if !self.file_manager.is_search_view() {
+                    return Err(anyhow::anyhow!("Saved search could not be started."));
+                }
+                Ok(format!("Saved search {}", dialog.save_name.trim()))
+            });
+            match result {
+                Ok(status) => {
+                    self.shell_status = status;
+                    self.file_manager_find = None;
+                }
+                Err(err) => dialog.error = Some(err.to_string()),
+            }
+        } else if close {
+            self.file_manager_find = None;
+        }
+    }
+
+    /// Properties of one file or folder. Permissions and ownership are
+    /// read-only unless the session is an administrator's.
+    pub(super) fn draw_file_manager_properties_window(&mut self, ctx: &Context) {
+        let can_edit = self.session.as_ref().is_some_and(|s| s.is_admin);
+        let Some(dialog) = self.file_manager_properties.as_mut() else {
+            return;
+        };
+        let palette = current_palette();
+        let mut close = false;
+        let mut apply = false;
+        let mut ok = false;
+        let folder_size = dialog.folder_size.as_ref().map(FolderSize::progress);
+        if folder_size.is_some_and(|progress| !progress.done) {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+
+        egui::Window::new("file_manager_properties_window")
+            .title_bar(false)
+            .resizable(false)
+            .collapsible(false)
+            .frame(Self::desktop_window_frame())
+            .fixed_size(egui::vec2(520.0, 0.0))
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                Self::apply_settings_control_style(ui);
+                if matches!(
+                    Self::draw_desktop_window_header(ui, "Properties", false),
+                    DesktopHeaderAction::Close
+                ) {
+                    close = true;
+                }
+                ui.add_space(8.0);
+                let props = &dialog.properties;
+                let is_folder = props.kind == PropertiesKind::Folder;
+                let has_ownership = props.ownership.is_some();
+                let name = props
+                    .path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| props.path.display().to_string());
+                let size = match folder_size {
+                    Some(progress) => format!(
+                        "{} ({} files, {} folders){}",
+                        format_bytes_human(progress.bytes),
+                        progress.files,
+                        progress.folders,
+                        if progress.done { "" } else { " - counting..." }
+                    ),
+                    None => format!("{} ({} bytes)", format_bytes_human(props.size), props.size),
+                };
+                let mut kind = props.kind.label().to_string();
+                if let Some(target) = &props.link_target {
+                    kind.push_str(&format!(" to {}", target.display()));
+                }
+                egui::Grid::new("fm_properties_info")
+                    .num_columns(2)
+                    .spacing([12.0, 4.0])
+                    .show(ui, |ui| {
+                        for (label, value) in [
+                            ("Name", name),
+                            ("Location", props.path.display().to_string()),
+                            ("Type", kind),
+                            ("MIME type", props.mime.to_string()),
+                            (
+                                "Opens as",
+                                props
+                                    .slot
+                                    .map(|slot| default_app_slot_label(slot).to_string())
+                                    .unwrap_or_else(|| "-".to_string()),
+                            ),
+                            ("Size", size),
+                            ("Modified", format_timestamp(props.modified)),
+                            ("Accessed", format_timestamp(props.accessed)),
+                            ("Created", format_timestamp(props.created)),
+                        ] {
+                            ui.label(RichText::new(label).strong());
+                            ui.label(value);
+                            ui.end_row();
+                        }
+                    });
+
+                if has_ownership {
+                    ui.add_space(6.0);
+                    Self::retro_separator(ui);
+                    egui::Grid::new("fm_properties_owner")
+                        .num_columns(2)
+                        .spacing([12.0, 4.0])
+                        .show(ui, |ui| {
+                            for (label, value) in
+                                [("Owner", &mut dialog.owner), ("Group", &mut dialog.group)]
+                            {
+                                ui.label(RichText::new(label).strong());
+                                if can_edit {
+                                    ui.add(TextEdit::singleline(value).desired_width(200.0));
+                                } else {
+                                    ui.label(value.as_str());
+                                }
+                                ui.end_row();
+                            }
+                        });
+                    ui.add_space(4.0);
+                    let mut mode = dialog.mode;
+                    ui.add_enabled_ui(can_edit, |ui| {
+                        egui::Grid::new("fm_properties_mode")
+                            .num_columns(4)
+                            .spacing([12.0, 4.0])
+                            .show(ui, |ui| {
+                                ui.label("");
+                                for heading in ["Read", "Write", "Execute"] {
+                                    ui.label(RichText::new(heading).strong());
+                                }
+                                ui.end_row();
+                                for (class, shift) in [("Owner", 6), ("Group", 3), ("Others", 0)] {
+                                    ui.label(class);
+                                    for bit in [0o4, 0o2, 0o1] {
+                                        let mask = bit << shift;
+                                        let mut set = mode & mask != 0;
+                                        if ui.checkbox(&mut set, "").changed() {
+                                            mode ^= mask;
+                                        }
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new("Octal").strong());
+                            if ui
+                                .add(TextEdit::singleline(&mut dialog.octal).desired_width(60.0))
+                                .changed()
+                            {
+                                dialog.sync_octal();
+                                mode = dialog.mode;
+                            }
+                            ui.label(mode_string(mode));
+                            if !is_folder {
+                                let mut executable = mode & 0o111 != 0;
+                                if ui.checkbox(&mut executable, "Executable").changed() {
+                                    mode = with_executable(mode, executable);
+                                }
+                            }
+                        });
+                    });
+                    if mode != dialog.mode {
+                        dialog.set_mode(mode);
+                    }
+                    if !can_edit {
+                        ui.colored_label(
+                            palette.dim,
+                            "Only administrators can change permissions and ownership.",
+                        );
+                    }
+                }
+
+                if !is_folder {
+                    ui.add_space(6.0);
+                    Self::retro_separator(ui);
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new("Open with").strong());
+                        let hint = dialog
+                            .extension_default
+                            .clone()
+                            .unwrap_or_else(|| "default for this type".to_string());
+                        ui.add(
+                            TextEdit::singleline(&mut dialog.open_with)
+                                .desired_width(320.0)
+                                .hint_text(hint),
+                        );
+                    });
+                    ui.colored_label(
+                        palette.dim,
+                        "Applies to this file only; leave empty to use the default.",
+                    );
+                }
+
+                if let Some(error) = &dialog.error {
+                    ui.add_space(4.0);
+                    ui.label(RichText::new(error).strong());
+                }
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button("OK").clicked() {
+                        ok = true;
+                    }
+                    if ui.button("Apply").clicked() {
+                        apply = true;
+                    }
+                    if ui.button("Close").clicked() {
+                        close = true;
+                    }
+                });
+            });
+
+        if apply || ok {
+            let settings = load_desktop_file_manager_settings();
+            match dialog.apply(can_edit, &settings) {
+                Ok((status, update)) => {
+                    if let Some(update) = update {
+                        self.apply_file_manager_settings_update(update);
+                    }
+                    self.shell_status = status;
+                    if ok {
+                        self.file_manager_properties = None;
+                    }
+                }
+                Err(err) => dialog.error = Some(err.to_string()),
+            }
+        } else if close {
+            self.file_manager_properties = None;
+        }
+    }
+
+    /// Checksums, sum-file verification and duplicate finding. The job
+    /// runs in the background; closing the window stops it.
+    pub(super) fn draw_file_manager_checksums_window(&mut self, ctx: &Context) {
+        let Some(dialog) = self.file_manager_checksums.as_mut() else {
+            return;
+        };
+        let palette = current_palette();
+        let mut close = false;
+        let mut stop = false;
+        let mut restart = None;
+        let mut save = false;
+        let mut copy = false;
+        let mut toggled = Vec::new();
+        let mut mark_all_but_first = false;
+        let mut clear_marks = false;
+        let mut trash = false;
+        let progress = dialog.job.progress();
+        if !progress.done {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+        let task = dialog.job.task().clone();
+        let algorithm = task.algorithm();
+        let results = dialog.job.results();
+        let groups = dialog.duplicate_groups();
+        let title = match task {
+            ChecksumTask::FindDuplicates { .. } => "Find Duplicates",
+            _ => "Checksums",
+        };
+
+        egui::Window::new("file_manager_checksums_window")
+            .title_bar(false)
+            .resizable(false)
+            .collapsible(false)
+            .frame(Self::desktop_window_frame())
+            .fixed_size(egui::vec2(620.0, 0.0))
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                Self::apply_settings_control_style(ui);
+                if matches!(
+                    Self::draw_desktop_window_header(ui, title, false),
+                    DesktopHeaderAction::Close
+                ) {
+                    close = true;
+                }
+                ui.add_space(8.0);
+                ui.label(
+                    RichText::new(Self::truncate_file_manager_label(&task.title(), 60)).strong(),
+                );
+                if !matches!(task, ChecksumTask::Verify { .. }) {
+                    ui.horizontal(|ui| {
+                        ui.label("Algorithm");
+                        for choice in ChecksumAlgorithm::ALL {
+                            if ui
+                                .selectable_label(choice == algorithm, choice.label())
+                                .clicked()
+                                && choice != algorithm
+                            {
+                                restart = Some(choice);
+                            }
+                        }
+                    });
+                }
+                ui.add_space(4.0);
+                let (rect, _) = ui.allocate_exact_size(
+                    egui::vec2(ui.available_width(), 12.0),
+                    egui::Sense::hover(),
+                );
+                ui.painter()
+                    .rect_stroke(rect, 0.0, egui::Stroke::new(1.0, palette.fg));
+                if progress.phase == ChecksumPhase::Hashing || progress.done {
+                    let mut filled = rect.shrink(2.0);
+                    filled.set_width(filled.width() * progress.fraction().clamp(0.0, 1.0));
+                    ui.painter().rect_filled(filled, 0.0, palette.fg);
+                }
+                ui.colored_label(palette.dim, dialog.job.summary());
+                if let Some(current) = &progress.current {
+                    ui.colored_label(
+                        palette.dim,
+                        Self::truncate_file_manager_label(&current.display().to_string(), 60),
+                    );
+                }
+
+                if let Some(results) = &results {
+                    ui.add_space(6.0);
+                    Self::retro_separator(ui);
+                    egui::ScrollArea::vertical()
+                        .id_salt("fm_checksums_results")
+                        .max_height(280.0)
+                        .auto_shrink([false, true])
+                        .show(ui, |ui| match results {
+                            ChecksumResults::Sums(sums) => {
+                                for sum in sums {
+                                    ui.label(
+                                        RichText::new(Self::truncate_file_manager_label(
+                                            &sum.path.display().to_string(),
+                                            60,
+                                        ))
+                                        .strong(),
+                                    );
+                                    match &sum.hash {
+                                        Ok(hash) => {
+                                            ui.monospace(hash);
+                                        }
+                                        Err(error) => {
+                                            ui.colored_label(palette.dim, error);
+                                        }
+                                    }
+                                }
+                            }
+                            ChecksumResults::Verified(verified) => {
+                                for result in verified {
+                                    let status = match &result.status {
+                                        VerifyStatus::Ok => "OK".to_string(),
+                                        VerifyStatus::Mismatch => "FAILED".to_string(),
+                                        VerifyStatus::Missing => "MISSING".to_string(),
+                                        VerifyStatus::Unreadable(error) => error.clone(),
+                                    };
+                                    let line = format!(
+                                        "[{status}] {}",
+                                        Self::truncate_file_manager_label(
+                                            &result.path.display().to_string(),
+                                            52,
+                                        )
+                                    );
+                                    if result.status == VerifyStatus::Ok {
+                                        ui.label(line);
+                                    } else {
+                                        ui.label(RichText::new(line).strong());
+                                    }
+                                }
+                            }
+                            ChecksumResults::Duplicates(_) => {
+                                if groups.is_empty() {
+                                    ui.colored_label(palette.dim, "No duplicate files found.");
+                                }
+                                for group in &groups {
+                                    ui.add_space(4.0);
+                                    ui.label(
+                                        RichText::new(format!(
+                                            "{} copies of {} - {}",
+                                            group.paths.len(),
+                                            format_bytes_human(group.size),
+                                            &group.hash[..12]
+                                        ))
+                                        .strong(),
+                                    );
+                                    for path in &group.paths {
+                                        let mut marked = dialog.marked.contains(path);
+                                        if ui
+                                            .checkbox(
+                                                &mut marked,
+                                                Self::truncate_file_manager_label(
+                                                    &path.display().to_string(),
+                                                    64,
+                                                ),
+                                            )
+                                            .changed()
+                                        {
+                                            toggled.push(path.clone());
+                                        }
+                                    }
+                                }
+                            }
+                        });
+                }
+
+                if let Some(error) = &dialog.error {
+                    ui.add_space(4.0);
+                    ui.label(RichText::new(error).strong());
+                }
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if !progress.done && ui.button("Stop").clicked() {
+                        stop = true;
+                    }
+                    match &results {
+                        Some(ChecksumResults::Sums(_)) => {
+                            if ui.button("Save Checksum File").clicked() {
+                                save = true;
+                            }
+                            if ui.button("Copy").clicked() {
+                                copy = true;
+                            }
+                        }
+                        Some(ChecksumResults::Duplicates(_)) if !groups.is_empty() => {
+                            if ui.button("Select All But First").clicked() {
+                                mark_all_but_first = true;
+                            }
+                            if ui.button("Clear").clicked() {
+                                clear_marks = true;
+                            }
+                            let trash_button = if dialog.marked.is_empty() {
+                                Self::retro_disabled_button(ui, "Move to Trash")
+                            } else {
+                                ui.button("Move to Trash")
+                            };
+                            if trash_button.clicked() {
+                                trash = true;
+                            }
+                        }
+                        _ => {}
+                    }
+                    if ui.button("Close").clicked() {
+                        close = true;
+                    }
+                });
+            });
+
+        if close {
+            self.file_manager_checksums = None;
+            return;
+        }
+        if stop {
+            dialog.job.cancel();
+        }
+        if let Some(algorithm) = restart {
+            if let Err(err) = dialog.restart(algorithm) {
+                dialog.error = Some(err.to_string());
+            }
+            return;
+        }
+        for path in toggled {
+            dialog.toggle_marked(&path);
+        }
+        if mark_all_but_first {
+            dialog.mark_all_but_first();
+        }
+        if clear_marks {
+            dialog.marked.clear();
+        }
+        if let Some(ChecksumResults::Sums(sums)) = &results {
+            if save {
+                let dir = sums
+                    .first()
+                    .and_then(|sum| sum.path.parent())
+                    .unwrap_or(Path::new("/"));
+                match write_sum_file(dir, algorithm, sums) {
+                    Ok(path) => {
+                        self.shell_status = format!("Saved {}", path.display());
+                    }
+                    Err(err) => dialog.error = Some(err.to_string()),
+                }
+            } else if copy {
+                let text: String = sums
+                    .iter()
+                    .filter_map(|sum| {
+                        let hash = sum.hash.as_ref().ok()?;
+                        Some(format_sum_line(hash, &sum.path.to_string_lossy()))
+                    })
+                    .collect();
+                ctx.copy_text(text);
+                self.shell_status = "Copied checksums.".to_string();
+            }
+        }
+        if trash {
+            match dialog.take_trash_request() {
+                Ok(request) => {
+                    let id = self.queue_file_manager_transfer(request);
+                    if let Some(dialog) = self.file_manager_checksums.as_mut() {
+                        dialog.trash_started(id);
+                    }
+                }
+                Err(err) => dialog.error = Some(err.to_string()),
+            }
+        }
+    }
+
     pub(super) fn preload_file_manager_svg_previews(
         &mut self,
         ctx: &Context,
@@ -338,6 +1280,7 @@ impl RobcoNativeApp {
             has_editable_selection,
             has_single_file_selection,
             has_clipboard,
+            self.file_manager.is_search_view(),
         );
     }
 
@@ -596,10 +1539,75 @@ impl RobcoNativeApp {
             .width_range(140.0..=280.0)
             .default_width(200.0)
             .show_inside(ui, |ui| {
-                ui.label(RichText::new("Locations").strong());
+                let bookmarks: Vec<&Path> = desktop_model
+                    .places
+                    .iter()
+                    .filter(|place| place.section == PlaceSection::Bookmarks)
+                    .map(|place| place.path.as_path())
+                    .collect();
+                let mut open_path = None;
+                let mut bookmark_update = None;
                 egui::ScrollArea::vertical()
                     .id_salt(("native_file_manager_tree", generation))
                     .show(ui, |ui| {
+                        let mut section = None;
+                        for place in &desktop_model.places {
+                            if section != Some(place.section) {
+                                section = Some(place.section);
+                                ui.add_space(4.0);
+                                ui.label(RichText::new(place.section.title()).strong());
+                            }
+                            let response = self.draw_file_manager_sidebar_button(
+                                ui,
+                                &place.label,
+                                &place.path,
+                                place.path == self.file_manager.cwd,
+                                save_picker_mode,
+                            );
+                            if response.clicked() {
+                                open_path = Some(place.path.clone());
+                            }
+                            let path = place.path.to_string_lossy().to_string();
+                            if place.section == PlaceSection::Bookmarks {
+                                response.context_menu(|ui| {
+                                    Self::apply_context_menu_style(ui);
+                                    ui.set_min_width(136.0);
+                                    for (label, update) in [
+                                        (
+                                            "Move Up",
+                                            FileManagerSettingsUpdate::MoveBookmark {
+                                                path: path.clone(),
+                                                up: true,
+                                            },
+                                        ),
+                                        (
+                                            "Move Down",
+                                            FileManagerSettingsUpdate::MoveBookmark {
+                                                path: path.clone(),
+                                                up: false,
+                                            },
+                                        ),
+                                        (
+                                            "Remove Bookmark",
+                                            FileManagerSettingsUpdate::RemoveBookmark {
+                                                path: path.clone(),
+                                            },
+                                        ),
+                                    ] {
+                                        if ui.button(label).clicked() {
+                                            bookmark_update = Some(update);
+                                            ui.close_menu();
+                                        }
+                                    }
+                                });
+                            } else if !bookmarks.contains(&place.path.as_path()) {
+                                Self::attach_add_bookmark_menu(
+                                    &response,
+                                    &path,
+                                    &mut bookmark_update,
+                                );
+                            }
+                        }
                         for item in &desktop_model.tree_items {
                             if item.path.is_none() {
                                 ui.add_space(4.0);
@@ -610,40 +1618,171 @@ impl RobcoNativeApp {
                                 continue;
                             };
                             let selected = Some(path) == self.file_manager.tree_selected.as_ref();
-                            let response = Self::retro_file_manager_button(
+                            let response = self.draw_file_manager_sidebar_button(
                                 ui,
-                                item.line.clone(),
-                                egui::vec2(ui.available_width(), 26.0),
+                                &item.line,
+                                path,
                                 selected,
-                                false,
+                                save_picker_mode,
                             );
-                            let drop_hover = !save_picker_mode
-                                && response
-                                    .dnd_hover_payload::<NativeFileManagerDragPayload>()
-                                    .is_some_and(|payload| {
-                                        Self::file_manager_drop_allowed(&payload.paths, path)
-                                    });
-                            if drop_hover {
-                                ui.painter().rect_stroke(
-                                    response.rect,
-                                    0.0,
-                                    egui::Stroke::new(2.0, current_palette().fg),
-                                );
-                            }
                             if response.clicked() {
                                 self.file_manager.open_selected_tree_path(path.clone());
                             }
-                            if let Some(payload) =
-                                response.dnd_release_payload::<NativeFileManagerDragPayload>()
-                            {
-                                if !save_picker_mode
-                                    && Self::file_manager_drop_allowed(&payload.paths, path)
-                                {
-                                    self.file_manager_handle_drop_to_dir(
-                                        payload.paths.clone(),
-                                        path.clone(),
+                            if !bookmarks.contains(&path.as_path()) {
+                                Self::attach_add_bookmark_menu(
+                                    &response,
+                                    &path.to_string_lossy(),
+                                    &mut bookmark_update,
+                                );
+                            }
+                        }
+                    });
+                if let Some(path) = open_path {
+                    self.file_manager.open_selected_tree_path(path);
+                }
+                if let Some(update) = bookmark_update {
+                    self.apply_file_manager_settings_update(update);
+                }
+            });
+    }
+
+    fn attach_add_bookmark_menu(
+        response: &egui::Response,
+        path: &str,
+        bookmark_update: &mut Option<FileManagerSettingsUpdate>,
+    ) {
+        response.context_menu(|ui| {
+            Self::apply_context_menu_style(ui);
+            ui.set_min_width(136.0);
+            if ui.button("Add Bookmark").clicked() {
+                *bookmark_update = Some(FileManagerSettingsUpdate::AddBookmark {
+                    path: path.to_string(),
+                });
+                ui.close_menu();
+            }
+        });
+    }
+
+    /// One sidebar row; dropping files on it moves them into `path`.
+    fn draw_file_manager_sidebar_button(
+        &mut self,
+        ui: &mut egui::Ui,
+        label: &str,
+        path: &Path,
+        selected: bool,
+        save_picker_mode: bool,
+    ) -> egui::Response {
+        let response = Self::retro_file_manager_button(
+            ui,
+            label.to_string(),
+            egui::vec2(ui.available_width(), 26.0),
+            selected,
+            false,
+        );
+        let drop_hover = !save_picker_mode
+            && response
+                .dnd_hover_payload::<NativeFileManagerDragPayload>()
+                .is_some_and(|payload| Self::file_manager_drop_allowed(&payload.paths, path));
+        if drop_hover {
+            ui.painter().rect_stroke(
+                response.rect,
+                0.0,
+                egui::Stroke::new(2.0, current_palette().fg),
+            );
+        }
+        if let Some(payload) = response.dnd_release_payload::<NativeFileManagerDragPayload>() {
+            if !save_picker_mode && Self::file_manager_drop_allowed(&payload.paths, path) {
+                self.file_manager_handle_drop_to_dir(payload.paths.clone(), path.to_path_buf());
+            }
+        }
+        response
+    }
+
+    pub(super) fn draw_file_manager_preview_panel(
+        &mut self,
+        ui: &mut egui::Ui,
+        generation: u64,
+        desktop_model: &file_manager_desktop::FileManagerDesktopViewModel,
+    ) {
+        let Some(path) = desktop_model.preview_path.as_ref() else {
+            return;
+        };
+        let cached = self.file_manager_previews.get(path);
+        egui::SidePanel::right(Id::new(("fm_preview", generation)))
+            .frame(egui::Frame::none())
+            .width_range(180.0..=420.0)
+            .default_width(260.0)
+            .show_inside(ui, |ui| {
+                let palette = current_palette();
+                let title = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path.display().to_string());
+                ui.label(RichText::new(Self::truncate_file_manager_label(&title, 32)).strong());
+                ui.separator();
+                let Some(cached) = cached else {
+                    ui.colored_label(palette.dim, "Loading preview...");
+                    return;
+                };
+                egui::ScrollArea::both()
+                    .id_salt(("native_file_manager_preview", generation))
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| match &cached.preview {
+                        FilePreview::Text { lines, .. } => {
+                            let font = egui::FontId::monospace(12.0);
+                            for spans in lines {
+                                let mut job = egui::text::LayoutJob::default();
+                                for span in spans {
+                                    let color = match span.kind {
+                                        PreviewTokenKind::Plain | PreviewTokenKind::Keyword => {
+                                            palette.fg
+                                        }
+                                        PreviewTokenKind::String
+                                        | PreviewTokenKind::Number
+                                        | PreviewTokenKind::Comment => palette.dim,
+                                    };
+                                    let mut format = egui::TextFormat::simple(font.clone(), color);
+                                    format.underline = if span.kind == PreviewTokenKind::Keyword {
+                                        egui::Stroke::new(1.0, palette.fg)
+                                    } else {
+                                        egui::Stroke::NONE
+                                    };
+                                    format.italics = span.kind == PreviewTokenKind::Comment;
+                                    job.append(&span.text, 0.0, format);
+                                }
+                                if spans.is_empty() {
+                                    job.append(
+                                        " ",
+                                        0.0,
+                                        egui::TextFormat::simple(font.clone(), palette.fg),
                                     );
                                 }
+                                ui.label(job);
+                            }
+                            for line in cached.preview.summary_lines().iter().skip(lines.len()) {
+                                ui.colored_label(palette.dim, line);
+                            }
+                        }
+                        FilePreview::Hex { .. } => {
+                            for line in cached.preview.summary_lines() {
+                                ui.label(RichText::new(line).monospace().size(11.0));
+                            }
+                        }
+                        FilePreview::Image { .. } => {
+                            if let Some(texture) = &cached.texture {
+                                let size = texture.size_vec2();
+                                let scale = (ui.available_width() / size.x).min(1.0);
+                                let (rect, _) =
+                                    ui.allocate_exact_size(size * scale, egui::Sense::hover());
+                                Self::paint_tinted_texture(ui.painter(), texture, rect, palette.fg);
+                            }
+                            for line in cached.preview.summary_lines() {
+                                ui.colored_label(palette.dim, line);
+                            }
+                        }
+                        _ => {
+                            for line in cached.preview.summary_lines() {
+                                ui.label(line);
                             }
                         }
                     });
@@ -662,6 +1801,21 @@ impl RobcoNativeApp {
         has_single_file_selection: bool,
         has_clipboard: bool,
     ) {
+        if desktop_model.dual_pane {
+            self.draw_file_manager_commander_panes(
+                ctx,
+                ui,
+                generation,
+                CommanderActivePane {
+                    desktop_model,
+                    action_selection_paths,
+                    has_editable_selection,
+                    has_single_file_selection,
+                    has_clipboard,
+                },
+            );
+            return;
+        }
         egui::CentralPanel::default()
             .frame(egui::Frame::none())
             .show_inside(ui, |ui| {
@@ -712,6 +1866,7 @@ impl RobcoNativeApp {
                                     has_editable_selection,
                                     has_single_file_selection,
                                     has_clipboard,
+                                    self.file_manager.is_search_view(),
                                 );
                             });
                     }
@@ -772,10 +1927,136 @@ impl RobcoNativeApp {
                                     has_editable_selection,
                                     has_single_file_selection,
                                     has_clipboard,
+                                    self.file_manager.is_search_view(),
                                 );
                             });
                     }
                 }
             });
     }
+
+    /// Commander layout: the focused pane is the regular file manager state
+    /// and gets the full row interaction; clicking the other pane moves the
+    /// focus there first.
+    fn draw_file_manager_commander_panes(
+        &mut self,
+        ctx: &Context,
+        ui: &mut egui::Ui,
+        generation: u64,
+        pane: CommanderActivePane<'_>,
+    ) {
+        let active_side = self.file_manager_commander.active_side;
+        let inactive_cwd = self.file_manager_commander.inactive.cwd.clone();
+        let inactive_rows = self.file_manager_commander.inactive.rows();
+        let mut focus_other: Option<Option<(PathBuf, bool)>> = None;
+        egui::CentralPanel::default()
+            .frame(egui::Frame::none())
+            .show_inside(ui, |ui| {
+                let palette = current_palette();
+                ui.columns(2, |columns| {
+                    for (side, ui) in [CommanderSide::Left, CommanderSide::Right]
+                        .into_iter()
+                        .zip(columns.iter_mut())
+                    {
+                        let active = side == active_side;
+                        let (cwd, rows) = if active {
+                            (&self.file_manager.cwd, &pane.desktop_model.rows)
+                        } else {
+                            (&inactive_cwd, &inactive_rows)
+                        };
+                        let title =
+                            Self::truncate_file_manager_label(&cwd.display().to_string(), 48);
+                        let title = if active {
+                            RichText::new(title)
+                                .strong()
+                                .color(palette.bg)
+                                .background_color(palette.fg)
+                        } else {
+                            RichText::new(title).color(palette.dim)
+                        };
+                        if ui
+                            .add(egui::Label::new(title).sense(egui::Sense::click()))
+                            .clicked()
+                            && !active
+                        {
+                            focus_other.get_or_insert(None);
+                        }
+                        ui.separator();
+                        egui::ScrollArea::vertical()
+                            .id_salt(("native_file_manager_commander", side.label(), generation))
+                            .auto_shrink([false, false])
+                            .show(ui, |ui| {
+                                for row in rows.clone() {
+                                    let preview = self.svg_preview_texture(&row);
+                                    let selected = if active {
+                                        self.file_manager.is_path_selected(&row.path)
+                                    } else {
+                                        self.file_manager_commander
+                                            .inactive
+                                            .is_path_selected(&row.path)
+                                    };
+                                    let response = Self::retro_file_manager_item(
+                                        ui,
+                                        preview.as_ref(),
+                                        row.icon(),
+                                        &row.label,
+                                        egui::vec2(ui.available_width(), 28.0),
+                                        selected,
+                                        false,
+                                        false,
+                                    );
+                                    if active {
+                                        self.handle_file_manager_row_interaction(
+                                            ctx,
+                                            ui,
+                                            &response,
+                                            &row,
+                                            false,
+                                            pane.action_selection_paths,
+                                            pane.has_editable_selection,
+                                            pane.has_single_file_selection,
+                                            pane.has_clipboard,
+                                        );
+                                    } else if response.double_clicked() {
+                                        focus_other = Some(Some((row.path.clone(), true)));
+                                    } else if response.clicked() || response.secondary_clicked() {
+                                        focus_other = Some(Some((row.path.clone(), false)));
+                                    }
+                                }
+                                let background = ui.allocate_rect(
+                                    ui.available_rect_before_wrap(),
+                                    egui::Sense::click(),
+                                );
+                                if !active {
+                                    if background.clicked() {
+                                        focus_other.get_or_insert(None);
+                                    }
+                                    return;
+                                }
+                                if background.clicked() {
+                                    self.file_manager.clear_multi_selection();
+                                }
+                                Self::attach_file_manager_context_menu(
+                                    &mut self.context_menu_action,
+                                    &background,
+                                    pane.has_editable_selection,
+                                    pane.has_single_file_selection,
+                                    pane.has_clipboard,
+                                    self.file_manager.is_search_view(),
+                                );
+                            });
+                    }
+                });
+            });
+        let Some(target) = focus_other else {
+            return;
+        };
+        self.file_manager_commander.switch(&mut self.file_manager);
+        if let Some((path, activate)) = target {
+            self.file_manager.select(Some(path));
+            if activate {
+                self.file_manager_activate_or_pick();
+            }
+        }
+    }
 }
@@ -0,0 +1,136 @@
+use super::retro_ui::{current_palette, RetroScreen};
+use crate::autostart::AutostartItem;
+use eframe::egui::{self, Context};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalAutostartRequest {
+    None,
+    Back,
+    Add,
+    /// The list in the draft changed and should be saved.
+    Persist,
+}
+
+/// Edits the autostart list in place. Shift+Up/Down moves the selected item
+/// and Left/Right changes its delay (Shift for steps of ten seconds).
+#[allow(clippy::too_many_arguments)]
+pub fn draw_autostart_screen(
+    ctx: &Context,
+    items: &mut Vec<AutostartItem>,
+    selected: &mut usize,
+    shell_status: &str,
+    cols: usize,
+    rows: usize,
+    header_start_row: usize,
+    separator_top_row: usize,
+    title_row: usize,
+    separator_bottom_row: usize,
+    subtitle_row: usize,
+    menu_start_row: usize,
+    status_row: usize,
+    content_col: usize,
+) -> TerminalAutostartRequest {
+    let mut request = TerminalAutostartRequest::None;
+    ctx.input(|i| {
+        let shift = i.modifiers.shift;
+        let step = if shift { 10 } else { 1 };
+        if i.key_pressed(egui::Key::Q) || i.key_pressed(egui::Key::Escape) {
+            request = TerminalAutostartRequest::Back;
+        } else if i.key_pressed(egui::Key::A) {
+            request = TerminalAutostartRequest::Add;
+        } else if i.key_pressed(egui::Key::ArrowDown) {
+            if shift && *selected + 1 < items.len() {
+                items.swap(*selected, *selected + 1);
+                request = TerminalAutostartRequest::Persist;
+            }
+            *selected = (*selected + 1).min(items.len().saturating_sub(1));
+        } else if i.key_pressed(egui::Key::ArrowUp) {
+            if shift && *selected > 0 && *selected < items.len() {
+                items.swap(*selected, *selected - 1);
+                request = TerminalAutostartRequest::Persist;
+            }
+            *selected = selected.saturating_sub(1);
+        } else if let Some(item) = items.get_mut(*selected) {
+            if i.key_pressed(egui::Key::ArrowLeft) {
+                item.adjust_delay(-step);
+                request = TerminalAutostartRequest::Persist;
+            } else if i.key_pressed(egui::Key::ArrowRight) {
+                item.adjust_delay(step);
+                request = TerminalAutostartRequest::Persist;
+            } else if i.key_pressed(egui::Key::Space) || i.key_pressed(egui::Key::E) {
+                item.enabled = !item.enabled;
+                request = TerminalAutostartRequest::Persist;
+            } else if i.key_pressed(egui::Key::M) {
+                item.mode = item.mode.toggled();
+                request = TerminalAutostartRequest::Persist;
+            } else if i.key_pressed(egui::Key::D) || i.key_pressed(egui::Key::Delete) {
+                items.remove(*selected);
+                request = TerminalAutostartRequest::Persist;
+            }
+        }
+    });
+    *selected = (*selected).min(items.len().saturating_sub(1));
+
+    egui::CentralPanel::default()
+        .frame(
+            egui::Frame::none()
+                .fill(current_palette().bg)
+                .inner_margin(0.0),
+        )
+        .show(ctx, |ui| {
+            let palette = current_palette();
+            let (screen, _) = RetroScreen::new(ui, cols, rows);
+            let painter = ui.painter_at(screen.rect);
+            screen.paint_bg(&painter, palette.bg);
+            for (idx, line) in crate::config::HEADER_LINES.iter().enumerate() {
+                screen.centered_text(&painter, header_start_row + idx, line, palette.fg, true);
+            }
+            screen.separator(&painter, separator_top_row, &palette);
+            screen.centered_text(&painter, title_row, "Autostart", palette.fg, true);
+            screen.separator(&painter, separator_bottom_row, &palette);
+            screen.text(
+                &painter,
+                content_col,
+                subtitle_row,
+                "Opened in order after login",
+                palette.dim,
+            );
+
+            let first_row = menu_start_row;
+            let visible = status_row.saturating_sub(first_row + 1).max(1);
+            let offset = selected.saturating_sub(visible - 1);
+            for (line, item) in items.iter().skip(offset).take(visible).enumerate() {
+                let idx = offset + line;
+                let response = screen.selectable_row(
+                    ui,
+                    &painter,
+                    &palette,
+                    content_col,
+                    first_row + line,
+                    &item.summary(),
+                    idx == *selected,
+                );
+                if response.clicked() {
+                    *selected = idx;
+                }
+            }
+            if items.is_empty() {
+                screen.text(
+                    &painter,
+                    content_col,
+                    first_row,
+                    "No autostart items. Press a to add one.",
+                    palette.dim,
+                );
+            }
+
+            let footer = if !shell_status.is_empty() {
+                shell_status
+            } else {
+                "a add  d delete  e enable  m mode  </> delay  shift+arrows move  q back"
+            };
+            screen.text(&painter, content_col, status_row, footer, palette.dim);
+        });
+
+    request
+}
@@ -0,0 +1,563 @@
+use super::desktop_app::{
+    desktop_window_title, DesktopMenuAction, DesktopShellAction, DesktopWindow,
+};
+use super::editor_app::{EditorCommand, EditorTextAlign, EditorTextCommand};
+use super::file_manager::FileManagerCommand;
+use super::menu::{MainMenuAction, TerminalScreen, MAIN_MENU_ENTRIES};
+use crate::config::{FileManagerSortMode, FileManagerViewMode};
+use robcos_native_settings_app::{settings_panel_title, NativeSettingsPanel};
+
+/// Shortcut that toggles the palette in both desktop and terminal mode.
+pub const COMMAND_PALETTE_KEYBINDING: &str = "Ctrl+Shift+Space";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteSessionCommand {
+    ReturnToTerminal,
+    Logout,
+    Shutdown,
+    SwitchTo(usize),
+    CloseActive,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaletteCommand {
+    Shell(DesktopShellAction),
+    Menu(DesktopMenuAction),
+    SettingsPanel(NativeSettingsPanel),
+    TerminalScreen(TerminalScreen),
+    MainMenu(MainMenuAction),
+    Session(PaletteSessionCommand),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaletteEntry {
+    pub label: String,
+    pub keybinding: Option<&'static str>,
+    pub command: PaletteCommand,
+}
+
+/// What the palette may offer right now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaletteContext {
+    pub desktop_mode: bool,
+    pub is_admin: bool,
+    pub editor_open: bool,
+    pub file_manager_open: bool,
+    pub session_count: usize,
+    pub active_session: Option<usize>,
+}
+
+fn entry(
+    label: impl Into<String>,
+    keybinding: Option<&'static str>,
+    command: PaletteCommand,
+) -> PaletteEntry {
+    PaletteEntry {
+        label: label.into(),
+        keybinding,
+        command,
+    }
+}
+
+fn shell_entries() -> Vec<PaletteEntry> {
+    let mut entries: Vec<PaletteEntry> = [
+        DesktopWindow::FileManager,
+        DesktopWindow::Editor,
+        DesktopWindow::Applications,
+        DesktopWindow::Installer,
+        DesktopWindow::TaskManager,
+        DesktopWindow::Scheduler,
+        DesktopWindow::DonkeyKong,
+    ]
+    .into_iter()
+    .map(|window| {
+        entry(
+            format!("Open: {}", desktop_window_title(window, None)),
+            None,
+            PaletteCommand::Shell(DesktopShellAction::OpenWindow(window)),
+        )
+    })
+    .collect();
+    entries.push(entry(
+        "Open: Nuke Codes",
+        None,
+        PaletteCommand::Shell(DesktopShellAction::OpenNukeCodes),
+    ));
+    entries.push(entry(
+        "Open: Terminal",
+        None,
+        PaletteCommand::Shell(DesktopShellAction::OpenDesktopTerminalShell),
+    ));
+    entries.push(entry(
+        "Open: Connections",
+        None,
+        PaletteCommand::Shell(DesktopShellAction::OpenConnectionsSettings),
+    ));
+    entries
+}
+
+fn desktop_menu_entries() -> Vec<PaletteEntry> {
+    vec![
+        entry(
+            "Desktop: Toggle Start Menu",
+            None,
+            PaletteCommand::Menu(DesktopMenuAction::ToggleStartMenu),
+        ),
+        entry(
+            "Window: Close Focused",
+            None,
+            PaletteCommand::Menu(DesktopMenuAction::CloseActiveDesktopWindow),
+        ),
+        entry(
+            "Window: Minimize Focused",
+            None,
+            PaletteCommand::Menu(DesktopMenuAction::MinimizeActiveDesktopWindow),
+        ),
+        entry(
+            "Help: App Manual",
+            None,
+            PaletteCommand::Menu(DesktopMenuAction::OpenManual {
+                path: "README.md",
+                status_label: "App Manual",
+            }),
+        ),
+        entry(
+            "Help: User Manual",
+            None,
+            PaletteCommand::Menu(DesktopMenuAction::OpenManual {
+                path: "USER_MANUAL.md",
+                status_label: "User Manual",
+            }),
+        ),
+    ]
+}
+
+fn editor_entries() -> Vec<PaletteEntry> {
+    let commands: [(&str, Option<&'static str>, EditorCommand); 14] = [
+        ("Save", Some("Ctrl+S"), EditorCommand::Save),
+        ("Save As", None, EditorCommand::SaveAs),
+        ("New Document", Some("Ctrl+N"), EditorCommand::NewDocument),
+        ("Find", Some("Ctrl+F"), EditorCommand::OpenFind),
+        (
+            "Find & Replace",
+            Some("Ctrl+H"),
+            EditorCommand::OpenFindReplace,
+        ),
+        ("Close Find", Some("Esc"), EditorCommand::CloseFind),
+        ("Toggle Word Wrap", None, EditorCommand::ToggleWordWrap),
+        (
+            "Toggle Line Numbers",
+            None,
+            EditorCommand::ToggleLineNumbers,
+        ),
+        (
+            "Font Larger",
+            Some("Ctrl++"),
+            EditorCommand::IncreaseFontSize,
+        ),
+        (
+            "Font Smaller",
+            Some("Ctrl+-"),
+            EditorCommand::DecreaseFontSize,
+        ),
+        ("Reset Font", None, EditorCommand::ResetFontSize),
+        (
+            "Align Left",
+            None,
+            EditorCommand::SetTextAlign(EditorTextAlign::Left),
+        ),
+        (
+            "Align Center",
+            None,
+            EditorCommand::SetTextAlign(EditorTextAlign::Center),
+        ),
+        (
+            "Align Right",
+            None,
+            EditorCommand::SetTextAlign(EditorTextAlign::Right),
+        ),
+    ];
+    let text_commands: [(&str, &'static str, EditorTextCommand); 6] = [
+        ("Undo", "Ctrl+Z", EditorTextCommand::Undo),
+        ("Redo", "Ctrl+Y", EditorTextCommand::Redo),
+        ("Cut", "Ctrl+X", EditorTextCommand::Cut),
+        ("Copy", "Ctrl+C", EditorTextCommand::Copy),
+        ("Paste", "Ctrl+V", EditorTextCommand::Paste),
+        ("Select All", "Ctrl+A", EditorTextCommand::SelectAll),
+    ];
+    commands
+        .into_iter()
+        .map(|(label, keybinding, command)| {
+            entry(
+                format!("Editor: {label}"),
+                keybinding,
+                PaletteCommand::Menu(DesktopMenuAction::EditorCommand(command)),
+            )
+        })
+        .chain(
+            text_commands
+                .into_iter()
+                .map(|(label, keybinding, command)| {
+                    entry(
+                        format!("Editor: {label}"),
+                        Some(keybinding),
+                        PaletteCommand::Menu(DesktopMenuAction::EditorTextCommand(command)),
+                    )
+                }),
+        )
+        .collect()
+}
+
+fn file_manager_entries() -> Vec<PaletteEntry> {
+    let commands: [(&str, Option<&'static str>, FileManagerCommand); 44] = [
+        (
+            "Open Selected",
+            Some("Enter"),
+            FileManagerCommand::OpenSelected,
+        ),
+        ("Clear Search", None, FileManagerCommand::ClearSearch),
+        (
+            "New Folder",
+            Some("Ctrl+Shift+N"),
+            FileManagerCommand::NewFolder,
+        ),
+        ("New Tab", None, FileManagerCommand::NewTab),
+        ("Previous Tab", None, FileManagerCommand::PreviousTab),
+        ("Next Tab", None, FileManagerCommand::NextTab),
+        ("Close Tab", None, FileManagerCommand::CloseTab),
+        ("Home", None, FileManagerCommand::OpenHome),
+        ("Go Up", None, FileManagerCommand::GoUp),
+        ("Copy", Some("Ctrl+C"), FileManagerCommand::Copy),
+        ("Cut", Some("Ctrl+X"), FileManagerCommand::Cut),
+        ("Paste", Some("Ctrl+V"), FileManagerCommand::Paste),
+        ("Duplicate", Some("Ctrl+D"), FileManagerCommand::Duplicate),
+        ("Rename", Some("F2"), FileManagerCommand::Rename),
+        ("Batch Rename", None, FileManagerCommand::BatchRename),
+        ("Move To", Some("Ctrl+Shift+M"), FileManagerCommand::Move),
+        ("Delete", Some("Delete"), FileManagerCommand::Delete),
+        ("Compress", None, FileManagerCommand::Compress),
+        ("Extract Selected", None, FileManagerCommand::Extract),
+        ("Extract All", None, FileManagerCommand::ExtractAll),
+        ("Open Trash", None, FileManagerCommand::OpenTrash),
+        (
+            "Restore From Trash",
+            None,
+            FileManagerCommand::RestoreFromTrash,
+        ),
+        ("Empty Trash", None, FileManagerCommand::EmptyTrash),
+        ("Undo", Some("Ctrl+Z"), FileManagerCommand::Undo),
+        ("Redo", Some("Ctrl+Y"), FileManagerCommand::Redo),
+        (
+            "Toggle Tree Panel",
+            None,
+            FileManagerCommand::ToggleTreePanel,
+        ),
+        (
+            "Toggle Preview Panel",
+            None,
+            FileManagerCommand::TogglePreviewPanel,
+        ),
+        ("Toggle Dual Pane", None, FileManagerCommand::ToggleDualPane),
+        (
+            "Copy To Other Pane",
+            Some("F5"),
+            FileManagerCommand::CopyToOtherPane,
+        ),
+        (
+            "Move To Other Pane",
+            Some("F6"),
+            FileManagerCommand::MoveToOtherPane,
+        ),
+        ("Switch Pane", Some("Tab"), FileManagerCommand::SwitchPane),
+        ("Show Transfers", None, FileManagerCommand::ShowTransfers),
+        (
+            "Find Files...",
+            Some("Ctrl+Shift+F"),
+            FileManagerCommand::FindFiles,
+        ),
+        ("Stop Search", None, FileManagerCommand::StopSearch),
+        ("Reveal in Folder", None, FileManagerCommand::RevealInFolder),
+        (
+            "Properties...",
+            Some("Alt+Enter"),
+            FileManagerCommand::Properties,
+        ),
+        ("Checksums...", None, FileManagerCommand::Checksums),
+        (
+            "Find Duplicates...",
+            None,
+            FileManagerCommand::FindDuplicates,
+        ),
+        (
+            "Add Bookmark",
+            Some("Ctrl+B"),
+            FileManagerCommand::AddBookmark,
+        ),
+        (
+            "Toggle Hidden Files",
+            None,
+            FileManagerCommand::ToggleHiddenFiles,
+        ),
+        (
+            "Grid View",
+            None,
+            FileManagerCommand::SetViewMode(FileManagerViewMode::Grid),
+        ),
+        (
+            "List View",
+            None,
+            FileManagerCommand::SetViewMode(FileManagerViewMode::List),
+        ),
+        (
+            "Sort by Name",
+            None,
+            FileManagerCommand::SetSortMode(FileManagerSortMode::Name),
+        ),
+        (
+            "Sort by Type",
+            None,
+            FileManagerCommand::SetSortMode(FileManagerSortMode::Type),
+        ),
+    ];
+    commands
+        .into_iter()
+        .map(|(label, keybinding, command)| {
+            entry(
+                format!("File Manager: {label}"),
+                keybinding,
+                PaletteCommand::Menu(DesktopMenuAction::FileManagerCommand(command)),
+            )
+        })
+        .collect()
+}
+
+fn desktop_settings_entries(is_admin: bool) -> Vec<PaletteEntry> {
+    let mut panels = vec![
+        NativeSettingsPanel::Home,
+        NativeSettingsPanel::General,
+        NativeSettingsPanel::Appearance,
+        NativeSettingsPanel::DefaultApps,
+        NativeSettingsPanel::Autostart,
+        NativeSettingsPanel::Connections,
+        NativeSettingsPanel::CliProfiles,
+        NativeSettingsPanel::EditMenus,
+        NativeSettingsPanel::About,
+    ];
+    if is_admin {
+        panels.push(NativeSettingsPanel::UserManagement);
+    }
+    panels
+        .into_iter()
+        .map(|panel| {
+            let label = if panel == NativeSettingsPanel::Home {
+                "Settings: Home".to_string()
+            } else {
+                format!("Settings: {}", settings_panel_title(panel))
+            };
+            entry(label, None, PaletteCommand::SettingsPanel(panel))
+        })
+        .collect()
+}
+
+fn terminal_settings_entries(is_admin: bool) -> Vec<PaletteEntry> {
+    let mut screens = vec![
+        ("Settings: Home", TerminalScreen::Settings),
+        ("Settings: Default Apps", TerminalScreen::DefaultApps),
+        ("Settings: Autostart", TerminalScreen::Autostart),
+        ("Settings: Connections", TerminalScreen::Connections),
+        ("Settings: Edit Menus", TerminalScreen::EditMenus),
+        ("Settings: About", TerminalScreen::About),
+    ];
+    if is_admin {
+        screens.push(("Settings: User Management", TerminalScreen::UserManagement));
+    }
+    screens
+        .into_iter()
+        .map(|(label, screen)| entry(label, None, PaletteCommand::TerminalScreen(screen)))
+        .collect()
+}
+
+fn main_menu_entries() -> Vec<PaletteEntry> {
+    MAIN_MENU_ENTRIES
+        .iter()
+        .filter_map(|menu_entry| {
+            let action = menu_entry.action?;
+            // Logout is listed with the session commands and Settings with
+            // the individual settings screens.
+            if matches!(action, MainMenuAction::Logout | MainMenuAction::Settings) {
+                return None;
+            }
+            Some(entry(
+                format!("Main Menu: {}", menu_entry.label),
+                None,
+                PaletteCommand::MainMenu(action),
+            ))
+        })
+        .collect()
+}
+
+fn session_entries(context: &PaletteContext) -> Vec<PaletteEntry> {
+    let mut entries = Vec::new();
+    if context.desktop_mode {
+        entries.push(entry(
+            "Session: Return to Terminal",
+            None,
+            PaletteCommand::Session(PaletteSessionCommand::ReturnToTerminal),
+        ));
+    } else {
+        const SWITCH_KEYS: [&str; 9] = [
+            "Ctrl+Q 1", "Ctrl+Q 2", "Ctrl+Q 3", "Ctrl+Q 4", "Ctrl+Q 5", "Ctrl+Q 6", "Ctrl+Q 7",
+            "Ctrl+Q 8", "Ctrl+Q 9",
+        ];
+        // Session switches are only applied while the terminal shell is up.
+        for (idx, keybinding) in SWITCH_KEYS
+            .into_iter()
+            .enumerate()
+            .take(context.session_count)
+        {
+            if context.active_session == Some(idx) {
+                continue;
+            }
+            entries.push(entry(
+                format!("Session: Switch to Session {}", idx + 1),
+                Some(keybinding),
+                PaletteCommand::Session(PaletteSessionCommand::SwitchTo(idx)),
+            ));
+        }
+        if context.session_count > 1 {
+            entries.push(entry(
+                "Session: Close Current Session",
+                Some("Ctrl+Q W"),
+                PaletteCommand::Session(PaletteSessionCommand::CloseActive),
+            ));
+        }
+    }
+    entries.push(entry(
+        "Session: Logout",
+        None,
+        PaletteCommand::Session(PaletteSessionCommand::Logout),
+    ));
+    entries.push(entry(
+        "Session: Shutdown",
+        None,
+        PaletteCommand::Session(PaletteSessionCommand::Shutdown),
+    ));
+    entries
+}
+
+/// Every command the palette can run in `context`, in display order.
+pub fn palette_entries(context: &PaletteContext) -> Vec<PaletteEntry> {
+    let mut entries = Vec::new();
+    if context.desktop_mode {
+        entries.extend(shell_entries());
+        entries.extend(desktop_menu_entries());
+        entries.extend(desktop_settings_entries(context.is_admin));
+    } else {
+        entries.extend(main_menu_entries());
+        entries.extend(terminal_settings_entries(context.is_admin));
+    }
+    if context.editor_open {
+        entries.extend(editor_entries());
+    }
+    if context.file_manager_open {
+        entries.extend(file_manager_entries());
+    }
+    entries.extend(session_entries(context));
+    entries
+}
+
+/// Case-insensitive subsequence match. Consecutive characters and matches at
+/// the start of a word score higher; `None` when `query` does not match.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0i64;
+    let mut next = 0usize;
+    let mut previous: Option<usize> = None;
+    for wanted in query {
+        let found = (next..text.len()).find(|idx| text[*idx] == wanted)?;
+        score += 1;
+        let word_start = found == 0 || !text[found - 1].is_alphanumeric();
+        if word_start {
+            score += 8;
+        }
+        match previous {
+            Some(prev) if found == prev + 1 => score += 5,
+            Some(prev) => score -= (found - prev - 1).min(5) as i64,
+            None => score -= found.min(10) as i64 / 2,
+        }
+        previous = Some(found);
+        next = found + 1;
+    }
+    Some(score)
+}
+
+/// Entries matching `query`, best match first. Ties keep display order.
+pub fn filter_palette_entries(entries: &[PaletteEntry], query: &str) -> Vec<PaletteEntry> {
+    let mut scored: Vec<(i64, &PaletteEntry)> = entries
+        .iter()
+        .filter_map(|entry| fuzzy_score(query, &entry.label).map(|score| (score, entry)))
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, entry)| entry.clone()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(desktop_mode: bool) -> PaletteContext {
+        PaletteContext {
+            desktop_mode,
+            is_admin: false,
+            editor_open: true,
+            file_manager_open: false,
+            session_count: 2,
+            active_session: Some(0),
+        }
+    }
+
+    #[test]
+    fn fuzzy_score_prefers_word_starts_and_rejects_missing_letters() {
+        assert!(fuzzy_score("fms", "File Manager: Save").is_some());
+        assert!(fuzzy_score("xyz", "Editor: Save").is_none());
+        assert!(
+            fuzzy_score("save", "Editor: Save").unwrap()
+                > fuzzy_score("save", "Session: Switch to Session 2").unwrap_or(i64::MIN)
+        );
+    }
+
+    #[test]
+    fn palette_lists_mode_specific_commands_with_keybindings() {
+        let desktop = palette_entries(&context(true));
+        let save = filter_palette_entries(&desktop, "editor save");
+        assert_eq!(save[0].label, "Editor: Save");
+        assert_eq!(save[0].keybinding, Some("Ctrl+S"));
+        assert!(desktop.iter().all(|entry| !matches!(
+            entry.command,
+            PaletteCommand::Session(PaletteSessionCommand::SwitchTo(_))
+        )));
+        assert!(desktop.iter().all(|entry| entry.command
+            != PaletteCommand::SettingsPanel(NativeSettingsPanel::UserManagement)));
+
+        let terminal = palette_entries(&context(false));
+        let switch = filter_palette_entries(&terminal, "switch");
+        assert_eq!(switch.len(), 1);
+        assert_eq!(
+            switch[0].command,
+            PaletteCommand::Session(PaletteSessionCommand::SwitchTo(1))
+        );
+        assert_eq!(switch[0].keybinding, Some("Ctrl+Q 2"));
+        assert!(terminal.iter().all(|entry| !matches!(
+            entry.command,
+            PaletteCommand::Menu(DesktopMenuAction::FileManagerCommand(_))
+        )));
+    }
+}
@@ -0,0 +1,187 @@
+use super::file_manager::{FileManagerCommand, NativeFileManagerState};
+use super::file_manager_app::{CommanderPanes, CommanderSide};
+use super::retro_ui::{current_palette, RetroScreen};
+use crate::config::HEADER_LINES;
+use eframe::egui::{self, Context};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TerminalCommanderRequest {
+    None,
+    Command(FileManagerCommand),
+    /// Enter on the cursor row: change into a folder or open a file.
+    Activate,
+    /// A row of the focused pane was clicked.
+    Select(PathBuf),
+    /// A row of the other pane was clicked; focus that pane and select it.
+    FocusOther(PathBuf),
+}
+
+fn move_cursor(active: &mut NativeFileManagerState, delta: isize) {
+    let rows = active.rows();
+    if rows.is_empty() {
+        return;
+    }
+    let current = active
+        .selected
+        .as_ref()
+        .and_then(|selected| rows.iter().position(|row| &row.path == selected))
+        .unwrap_or(0);
+    let next = current
+        .saturating_add_signed(delta)
+        .min(rows.len().saturating_sub(1));
+    active.select(Some(rows[next].path.clone()));
+}
+
+/// Norton Commander style screen: two folder listings side by side. The
+/// focused pane is the regular file manager state, so the cursor is its
+/// selection and every key maps to an existing file manager command.
+#[allow(clippy::too_many_arguments)]
+pub fn draw_terminal_commander(
+    ctx: &Context,
+    active: &mut NativeFileManagerState,
+    panes: &CommanderPanes,
+    shell_status: &str,
+    cols: usize,
+    rows: usize,
+    header_start_row: usize,
+    separator_top_row: usize,
+    title_row: usize,
+    separator_bottom_row: usize,
+    subtitle_row: usize,
+    menu_start_row: usize,
+    status_row: usize,
+    status_row_alt: usize,
+    content_col: usize,
+) -> TerminalCommanderRequest {
+    let mut request = TerminalCommanderRequest::None;
+    ctx.input(|i| {
+        if i.key_pressed(egui::Key::ArrowUp) {
+            move_cursor(active, -1);
+        } else if i.key_pressed(egui::Key::ArrowDown) {
+            move_cursor(active, 1);
+        } else if i.key_pressed(egui::Key::Enter) {
+            request = TerminalCommanderRequest::Activate;
+        } else if i.key_pressed(egui::Key::Backspace) {
+            active.up();
+        } else if i.key_pressed(egui::Key::Tab) {
+            request = TerminalCommanderRequest::Command(FileManagerCommand::SwitchPane);
+        } else if i.key_pressed(egui::Key::F5) {
+            request = TerminalCommanderRequest::Command(FileManagerCommand::CopyToOtherPane);
+        } else if i.key_pressed(egui::Key::F6) {
+            request = TerminalCommanderRequest::Command(FileManagerCommand::MoveToOtherPane);
+        } else if i.key_pressed(egui::Key::F7) {
+            request = TerminalCommanderRequest::Command(FileManagerCommand::NewFolder);
+        } else if i.key_pressed(egui::Key::F8) {
+            request = TerminalCommanderRequest::Command(FileManagerCommand::Delete);
+        } else if i.modifiers.command && i.key_pressed(egui::Key::Z) {
+            request = TerminalCommanderRequest::Command(FileManagerCommand::Undo);
+        }
+    });
+    active.ensure_selection_valid();
+
+    egui::CentralPanel::default()
+        .frame(
+            egui::Frame::none()
+                .fill(current_palette().bg)
+                .inner_margin(0.0),
+        )
+        .show(ctx, |ui| {
+            let palette = current_palette();
+            let (screen, _) = RetroScreen::new(ui, cols, rows);
+            let painter = ui.painter_at(screen.rect);
+            screen.paint_bg(&painter, palette.bg);
+            for (idx, line) in HEADER_LINES.iter().enumerate() {
+                screen.centered_text(&painter, header_start_row + idx, line, palette.fg, true);
+            }
+            screen.separator(&painter, separator_top_row, &palette);
+            screen.centered_text(&painter, title_row, "File Commander", palette.fg, true);
+            screen.separator(&painter, separator_bottom_row, &palette);
+
+            let pane_width = cols.saturating_sub(content_col) / 2;
+            let visible = status_row.saturating_sub(menu_start_row + 1).max(1);
+            for (side, state) in panes.sides(active) {
+                let is_active = side == panes.active_side;
+                let col = match side {
+                    CommanderSide::Left => content_col,
+                    CommanderSide::Right => content_col + pane_width,
+                };
+                let width = pane_width.saturating_sub(2);
+                let cwd: String = state.cwd.display().to_string();
+                let cwd: String = cwd
+                    .chars()
+                    .skip(cwd.chars().count().saturating_sub(width))
+                    .collect();
+                if is_active {
+                    screen.underlined_text(&painter, col, subtitle_row, &cwd, palette.fg);
+                } else {
+                    screen.text(&painter, col, subtitle_row, &cwd, palette.dim);
+                }
+                let entries = state.rows();
+                let cursor = state
+                    .selected
+                    .as_ref()
+                    .and_then(|selected| entries.iter().position(|row| &row.path == selected))
+                    .unwrap_or(0);
+                let offset = cursor.saturating_sub(visible - 1);
+                for (line, entry) in entries.iter().skip(offset).take(visible).enumerate() {
+                    let idx = offset + line;
+                    let marker = if idx == cursor { ">" } else { " " };
+                    let name = if entry.is_dir && !entry.is_parent_dir() {
+                        format!("{}/", entry.label)
+                    } else {
+                        entry.label.clone()
+                    };
+                    let text: String = format!("{marker} {name}").chars().take(width).collect();
+                    let row = menu_start_row + line;
+                    let response = if is_active {
+                        screen.selectable_row(
+                            ui,
+                            &painter,
+                            &palette,
+                            col,
+                            row,
+                            &text,
+                            idx == cursor,
+                        )
+                    } else {
+                        screen.text(&painter, col, row, &text, palette.dim);
+                        ui.interact(
+                            screen.row_rect(col, row, text.chars().count()),
+                            ui.id().with(("commander_row", side.label(), row)),
+                            egui::Sense::click(),
+                        )
+                    };
+                    if !response.clicked() {
+                        continue;
+                    }
+                    request = if !is_active {
+                        TerminalCommanderRequest::FocusOther(entry.path.clone())
+                    } else if idx == cursor {
+                        TerminalCommanderRequest::Activate
+                    } else {
+                        TerminalCommanderRequest::Select(entry.path.clone())
+                    };
+                }
+            }
+
+            screen.text(
+                &painter,
+                content_col,
+                status_row,
+                "Tab switch | Enter open | Bksp up | F5 copy | F6 move | F7 mkdir | F8 delete | Ctrl+Z undo",
+                palette.dim,
+            );
+            if !shell_status.is_empty() {
+                screen.text(
+                    &painter,
+                    content_col,
+                    status_row_alt,
+                    shell_status,
+                    palette.dim,
+                );
+            }
+        });
+
+    request
+}
@@ -275,6 +275,8 @@ pub fn taskbar_window_order() -> &'static [DesktopWindow] {
         DesktopWindow::DonkeyKong,
         DesktopWindow::NukeCodes,
         DesktopWindow::Installer,
+        DesktopWindow::TaskManager,
+        DesktopWindow::Scheduler,
         DesktopWindow::PtyApp,
     ]
 }
@@ -336,6 +338,9 @@ pub fn hosted_app_for_window(window: Option<DesktopWindow>) -> DesktopHostedApp
         Some(DesktopWindow::TerminalMode) => DesktopHostedApp::Terminal,
         Some(DesktopWindow::PtyApp) => DesktopHostedApp::PtyApp,
         Some(DesktopWindow::Installer) => DesktopHostedApp::Installer,
+        Some(DesktopWindow::TaskManager) | Some(DesktopWindow::Scheduler) => {
+            DesktopHostedApp::Utility
+        }
         None => DesktopHostedApp::Desktop,
     }
 }
@@ -349,6 +354,8 @@ pub fn desktop_window_title(window: DesktopWindow, pty_title: Option<&str>) -> S
         DesktopWindow::DonkeyKong => BUILTIN_DONKEY_KONG_GAME.to_string(),
         DesktopWindow::NukeCodes => "Nuke Codes".to_string(),
         DesktopWindow::Installer => "Program Installer".to_string(),
+        DesktopWindow::TaskManager => "Task Manager".to_string(),
+        DesktopWindow::Scheduler => "Scheduler".to_string(),
         DesktopWindow::TerminalMode => "Terminal".to_string(),
         DesktopWindow::PtyApp => pty_title.unwrap_or("PTY App").to_string(),
     }
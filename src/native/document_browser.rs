@@ -1,16 +1,32 @@
 use super::file_manager::NativeFileManagerState;
+use super::file_manager_app::Place;
 use super::retro_ui::{current_palette, RetroScreen};
 use crate::config::HEADER_LINES;
+use crate::doc_index::ContentMatch;
 use eframe::egui::{self, Context};
 pub use robcos_native_document_browser_app::{
-    activate_browser_selection, browser_rows, TerminalDocumentBrowserRequest,
+    activate_browser_selection, browser_rows, DocumentBrowserRow, TerminalDocumentBrowserRequest,
 };
+use std::path::PathBuf;
 
+/// Narrowest terminal that still gets the preview column.
+const PREVIEW_MIN_COLS: usize = 60;
+
+/// The row whose preview the browser shows, skipping `..` and placeholders.
+pub fn browser_preview_path(rows: &[DocumentBrowserRow], selected_idx: usize) -> Option<PathBuf> {
+    rows.get(selected_idx)
+        .filter(|row| row.label != "../")
+        .and_then(|row| row.path.clone())
+}
+
+/// `preview` holds plain lines for the selected entry; they are drawn in a
+/// column on the right half when the terminal is wide enough.
 #[allow(clippy::too_many_arguments)]
 pub fn draw_terminal_document_browser(
     ctx: &Context,
     file_manager: &NativeFileManagerState,
     selected_idx: &mut usize,
+    preview: Option<&[String]>,
     shell_status: &str,
     cols: usize,
     rows: usize,
@@ -62,6 +78,13 @@ pub fn draw_terminal_document_browser(
                 &file_manager.cwd.display().to_string(),
                 palette.fg,
             );
+            let preview = preview.filter(|_| cols >= PREVIEW_MIN_COLS);
+            let preview_col = cols / 2;
+            let label_width = if preview.is_some() {
+                preview_col.saturating_sub(content_col + 2)
+            } else {
+                usize::MAX
+            };
             let mut row = menu_start_row;
             for (idx, row_data) in rows_data.iter().enumerate() {
                 let selected = idx == *selected_idx;
@@ -70,6 +93,7 @@ pub fn draw_terminal_document_browser(
                 } else {
                     format!("    {}", row_data.label)
                 };
+                let text: String = text.chars().take(label_width).collect();
                 let response = screen.selectable_row(
                     ui,
                     &painter,
@@ -85,11 +109,25 @@ pub fn draw_terminal_document_browser(
                 }
                 row += 1;
             }
+            if let Some(lines) = preview {
+                let width = cols.saturating_sub(preview_col + 1);
+                let visible = status_row.saturating_sub(menu_start_row + 1);
+                for (offset, line) in lines.iter().take(visible).enumerate() {
+                    let shown: String = line.chars().take(width).collect();
+                    screen.text(
+                        &painter,
+                        preview_col,
+                        menu_start_row + offset,
+                        &shown,
+                        palette.dim,
+                    );
+                }
+            }
             screen.text(
                 &painter,
                 content_col,
                 status_row,
-                "Enter open | Tab back | Up/Down move",
+                "Enter open | / search text | J places | P preview | Tab back | Up/Down move",
                 palette.dim,
             );
             if !shell_status.is_empty() {
@@ -105,3 +143,303 @@ pub fn draw_terminal_document_browser(
 
     activated
 }
+
+/// Content search results shown in place of the folder listing.
+#[derive(Debug, Clone, Default)]
+pub struct DocumentSearchView {
+    pub query: String,
+    pub matches: Vec<ContentMatch>,
+    pub selected: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DocumentSearchRequest {
+    None,
+    NewSearch,
+    Open(PathBuf),
+}
+
+/// Lists content matches two rows each: the file name and a snippet with
+/// the match shown in inverse colors.
+#[allow(clippy::too_many_arguments)]
+pub fn draw_terminal_document_search(
+    ctx: &Context,
+    view: &mut DocumentSearchView,
+    shell_status: &str,
+    cols: usize,
+    rows: usize,
+    header_start_row: usize,
+    separator_top_row: usize,
+    title_row: usize,
+    separator_bottom_row: usize,
+    subtitle_row: usize,
+    menu_start_row: usize,
+    status_row: usize,
+    status_row_alt: usize,
+    content_col: usize,
+) -> DocumentSearchRequest {
+    let count = view.matches.len();
+    let mut request = DocumentSearchRequest::None;
+    ctx.input(|i| {
+        if i.key_pressed(egui::Key::ArrowUp) {
+            view.selected = view.selected.saturating_sub(1);
+        } else if i.key_pressed(egui::Key::ArrowDown) {
+            view.selected = (view.selected + 1).min(count.saturating_sub(1));
+        } else if i.key_pressed(egui::Key::Slash) {
+            request = DocumentSearchRequest::NewSearch;
+        } else if i.key_pressed(egui::Key::Enter) || i.key_pressed(egui::Key::Space) {
+            if let Some(hit) = view.matches.get(view.selected) {
+                request = DocumentSearchRequest::Open(hit.path.clone());
+            }
+        }
+    });
+    view.selected = view.selected.min(count.saturating_sub(1));
+
+    egui::CentralPanel::default()
+        .frame(
+            egui::Frame::none()
+                .fill(current_palette().bg)
+                .inner_margin(0.0),
+        )
+        .show(ctx, |ui| {
+            let palette = current_palette();
+            let (screen, _) = RetroScreen::new(ui, cols, rows);
+            let painter = ui.painter_at(screen.rect);
+            screen.paint_bg(&painter, palette.bg);
+            for (idx, line) in HEADER_LINES.iter().enumerate() {
+                screen.centered_text(&painter, header_start_row + idx, line, palette.fg, true);
+            }
+            screen.separator(&painter, separator_top_row, &palette);
+            screen.centered_text(&painter, title_row, "Search Documents", palette.fg, true);
+            screen.separator(&painter, separator_bottom_row, &palette);
+            screen.underlined_text(
+                &painter,
+                content_col,
+                subtitle_row,
+                &format!("\"{}\" - {count} documents", view.query),
+                palette.fg,
+            );
+
+            let snippet_col = content_col + 6;
+            let width = cols.saturating_sub(snippet_col + 1);
+            let visible = (status_row.saturating_sub(menu_start_row + 1) / 2).max(1);
+            let offset = view.selected.saturating_sub(visible - 1);
+            for (line, hit) in view.matches.iter().skip(offset).take(visible).enumerate() {
+                let idx = offset + line;
+                let row = menu_start_row + line * 2;
+                let selected = idx == view.selected;
+                let name = hit
+                    .path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let text = if selected {
+                    format!("  > {name}")
+                } else {
+                    format!("    {name}")
+                };
+                let response = screen.selectable_row(
+                    ui,
+                    &painter,
+                    &palette,
+                    content_col,
+                    row,
+                    &text,
+                    selected,
+                );
+                if response.clicked() {
+                    view.selected = idx;
+                    request = DocumentSearchRequest::Open(hit.path.clone());
+                }
+
+                let snippet = &hit.snippet;
+                let start = snippet.text[..snippet.highlight.start].chars().count();
+                let len = snippet.text[snippet.highlight.clone()]
+                    .chars()
+                    .count()
+                    .min(width);
+                // Scroll long lines so the match stays in view.
+                let skip = (start + len).saturating_sub(width);
+                let shown: String = snippet.text.chars().skip(skip).take(width).collect();
+                screen.text(&painter, snippet_col, row + 1, &shown, palette.dim);
+                let match_col = snippet_col + start - skip;
+                let match_len = len.min(width.saturating_sub(start - skip));
+                if match_len > 0 {
+                    let cell = screen.row_rect(match_col, row + 1, match_len);
+                    painter.rect_filled(
+                        screen.text_band_rect(row + 1, cell.left(), cell.width()),
+                        0.0,
+                        palette.fg,
+                    );
+                    let matched: String =
+                        shown.chars().skip(start - skip).take(match_len).collect();
+                    screen.text(&painter, match_col, row + 1, &matched, palette.bg);
+                }
+            }
+            if view.matches.is_empty() {
+                screen.text(
+                    &painter,
+                    content_col,
+                    menu_start_row,
+                    "No documents contain that text.",
+                    palette.dim,
+                );
+            }
+
+            screen.text(
+                &painter,
+                content_col,
+                status_row,
+                "Enter open | / new search | Tab folders | Up/Down move",
+                palette.dim,
+            );
+            if !shell_status.is_empty() {
+                screen.text(
+                    &painter,
+                    content_col,
+                    status_row_alt,
+                    shell_status,
+                    palette.dim,
+                );
+            }
+        });
+
+    request
+}
+
+/// Places jump list shown in place of the folder listing.
+#[derive(Debug, Clone, Default)]
+pub struct PlacesJumpView {
+    pub places: Vec<Place>,
+    pub selected: usize,
+}
+
+/// Lists places under their section titles; returns the folder to open.
+#[allow(clippy::too_many_arguments)]
+pub fn draw_terminal_places_jump(
+    ctx: &Context,
+    view: &mut PlacesJumpView,
+    shell_status: &str,
+    cols: usize,
+    rows: usize,
+    header_start_row: usize,
+    separator_top_row: usize,
+    title_row: usize,
+    separator_bottom_row: usize,
+    subtitle_row: usize,
+    menu_start_row: usize,
+    status_row: usize,
+    status_row_alt: usize,
+    content_col: usize,
+) -> Option<PathBuf> {
+    let count = view.places.len();
+    let mut opened = None;
+    ctx.input(|i| {
+        if i.key_pressed(egui::Key::ArrowUp) {
+            view.selected = view.selected.saturating_sub(1);
+        } else if i.key_pressed(egui::Key::ArrowDown) {
+            view.selected = (view.selected + 1).min(count.saturating_sub(1));
+        } else if i.key_pressed(egui::Key::Enter) || i.key_pressed(egui::Key::Space) {
+            opened = view
+                .places
+                .get(view.selected)
+                .map(|place| place.path.clone());
+        }
+    });
+    view.selected = view.selected.min(count.saturating_sub(1));
+
+    // Section titles take a line of their own, so lay out every line first
+    // and scroll that list to keep the selection visible.
+    let mut lines: Vec<(Option<usize>, String)> = Vec::new();
+    for (idx, place) in view.places.iter().enumerate() {
+        if idx == 0 || view.places[idx - 1].section != place.section {
+            lines.push((None, place.section.title().to_string()));
+        }
+        lines.push((Some(idx), place.label.clone()));
+    }
+    let visible = status_row.saturating_sub(menu_start_row + 1).max(1);
+    let selected_line = lines
+        .iter()
+        .position(|(idx, _)| *idx == Some(view.selected))
+        .unwrap_or(0);
+    let offset = selected_line.saturating_sub(visible - 1);
+
+    egui::CentralPanel::default()
+        .frame(
+            egui::Frame::none()
+                .fill(current_palette().bg)
+                .inner_margin(0.0),
+        )
+        .show(ctx, |ui| {
+            let palette = current_palette();
+            let (screen, _) = RetroScreen::new(ui, cols, rows);
+            let painter = ui.painter_at(screen.rect);
+            screen.paint_bg(&painter, palette.bg);
+            for (idx, line) in HEADER_LINES.iter().enumerate() {
+                screen.centered_text(&painter, header_start_row + idx, line, palette.fg, true);
+            }
+            screen.separator(&painter, separator_top_row, &palette);
+            screen.centered_text(&painter, title_row, "Places", palette.fg, true);
+            screen.separator(&painter, separator_bottom_row, &palette);
+            screen.underlined_text(
+                &painter,
+                content_col,
+                subtitle_row,
+                "Jump to a folder",
+                palette.fg,
+            );
+            for (line, (idx, label)) in lines.iter().skip(offset).take(visible).enumerate() {
+                let row = menu_start_row + line;
+                let Some(idx) = *idx else {
+                    screen.text(&painter, content_col, row, label, palette.dim);
+                    continue;
+                };
+                let selected = idx == view.selected;
+                let text = if selected {
+                    format!("  > {label}")
+                } else {
+                    format!("    {label}")
+                };
+                let response = screen.selectable_row(
+                    ui,
+                    &painter,
+                    &palette,
+                    content_col,
+                    row,
+                    &text,
+                    selected,
+                );
+                if response.clicked() {
+                    view.selected = idx;
+                    opened = Some(view.places[idx].path.clone());
+                }
+            }
+            if view.places.is_empty() {
+                screen.text(
+                    &painter,
+                    content_col,
+                    menu_start_row,
+                    "No places available.",
+                    palette.dim,
+                );
+            }
+            screen.text(
+                &painter,
+                content_col,
+                status_row,
+                "Enter open | Tab folders | Up/Down move",
+                palette.dim,
+            );
+            if !shell_status.is_empty() {
+                screen.text(
+                    &painter,
+                    content_col,
+                    status_row_alt,
+                    shell_status,
+                    palette.dim,
+                );
+            }
+        });
+
+    opened
+}
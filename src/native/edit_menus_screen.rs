@@ -1,8 +1,12 @@
+use super::desktop_launcher_service::CatalogEntryDraft;
 use super::menu::draw_terminal_menu_screen;
+use crate::catalog::CatalogRunMode;
+use crate::launcher::{launch_args_from_text, launch_args_to_text};
 use eframe::egui::Context;
 pub use robcos_native_edit_menus_app::{
     apply_edit_menus_activation, apply_edit_menus_selected_idx, build_edit_menus_view_model,
-    EditMenuTarget, EditMenusEntries, TerminalEditMenusRequest, TerminalEditMenusState,
+    EditMenuDetailField, EditMenuEntryDetails, EditMenuTarget, EditMenusEntries,
+    TerminalEditMenusRequest, TerminalEditMenusState,
 };
 
 #[allow(clippy::too_many_arguments)]
@@ -49,6 +53,43 @@ pub fn draw_edit_menus_screen(
     apply_edit_menus_activation(state, entries, activated)
 }
 
+/// Details-view values of a draft. The environment is shown on one line as
+/// shell-quoted `KEY=VALUE` words.
+pub fn entry_details_from_draft(draft: &CatalogEntryDraft) -> EditMenuEntryDetails {
+    let environment: Vec<String> = draft.environment.lines().map(str::to_string).collect();
+    EditMenuEntryDetails {
+        command: draft.command.clone(),
+        description: draft.description.clone(),
+        icon: draft.icon.clone(),
+        tags: draft.tags.clone(),
+        working_dir: draft.working_dir.clone(),
+        arguments: draft.arguments.clone(),
+        environment: launch_args_to_text(&environment),
+        pty_profile: draft.pty_profile.clone(),
+        run_in_pty: draft.run_mode == CatalogRunMode::Pty,
+        requires_network: draft.requires_network,
+    }
+}
+
+/// Stores a prompt answer (or flips a toggle) in the draft.
+pub fn apply_entry_detail(draft: &mut CatalogEntryDraft, field: EditMenuDetailField, value: &str) {
+    let value = value.trim().to_string();
+    match field {
+        EditMenuDetailField::Command => draft.command = value,
+        EditMenuDetailField::Description => draft.description = value,
+        EditMenuDetailField::Icon => draft.icon = value,
+        EditMenuDetailField::Tags => draft.tags = value,
+        EditMenuDetailField::WorkingDirectory => draft.working_dir = value,
+        EditMenuDetailField::Arguments => draft.arguments = value,
+        EditMenuDetailField::Environment => {
+            draft.environment = launch_args_from_text(&value).join("\n");
+        }
+        EditMenuDetailField::PtyProfile => draft.pty_profile = value,
+        EditMenuDetailField::RunMode => draft.run_mode = draft.run_mode.toggled(),
+        EditMenuDetailField::RequiresNetwork => draft.requires_network = !draft.requires_network,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -75,6 +116,8 @@ mod tests {
                 documents: &[],
                 network: &[],
                 games: &[],
+                details: None,
+                desktop_apps: &[],
             },
             Some(0),
         );
@@ -85,6 +128,8 @@ mod tests {
                 documents: &[],
                 network: &[],
                 games: &[],
+                details: None,
+                desktop_apps: &[],
             },
             true,
             true,
@@ -102,10 +147,30 @@ mod tests {
                 documents: &[],
                 network: &[],
                 games: &[],
+                details: None,
+                desktop_apps: &[],
             },
             true,
             true,
         );
         assert!(model.items.iter().any(|item| item == "Edit Documents"));
     }
+
+    #[test]
+    fn environment_detail_round_trips_through_one_line() {
+        let mut draft = CatalogEntryDraft::default();
+        apply_entry_detail(
+            &mut draft,
+            EditMenuDetailField::Environment,
+            "LANG=C 'GREETING=hello world'",
+        );
+        assert_eq!(draft.environment, "LANG=C\nGREETING=hello world");
+        assert_eq!(
+            entry_details_from_draft(&draft).environment,
+            "LANG=C 'GREETING=hello world'"
+        );
+
+        apply_entry_detail(&mut draft, EditMenuDetailField::RunMode, "");
+        assert_eq!(draft.run_mode, CatalogRunMode::Detached);
+    }
 }
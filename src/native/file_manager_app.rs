@@ -1,48 +1,398 @@
-#[cfg(test)]
-use super::file_manager::FileEntryRow;
-use super::file_manager::{FileManagerCommand, NativeFileManagerState};
+use super::file_manager::{FileEntryRow, FileManagerCommand, NativeFileManagerState};
 pub use super::file_manager_prompt::{
-    apply_prompt_outcome, open_with_cleared_default_status, open_with_removed_saved_status,
-    open_with_set_default_status, prompt_request_for_command, FileManagerPromptAction,
-    FileManagerPromptRequest,
+    apply_prompt_outcome, compress_prompt_request, open_with_cleared_default_status,
+    open_with_removed_saved_status, open_with_set_default_status, prompt_request_for_command,
+    trash_prompt_request_for_command, FileManagerPromptAction, FileManagerPromptRequest,
 };
 #[cfg(test)]
 pub use super::file_manager_prompt::{resolve_prompt_outcome, FileManagerPromptResolution};
+use super::shared_file_manager_settings::{
+    open_with_default_for_extension, open_with_override_for_path,
+};
 #[cfg(test)]
 pub use super::shared_file_manager_settings::{
-    open_with_default_for_extension, open_with_history_for_extension, push_open_with_history,
-    record_open_with_command_in_settings, remove_open_with_command_in_settings,
-    replace_open_with_command_in_settings, set_open_with_default_in_settings,
-    sync_open_with_settings_to_draft,
+    open_with_history_for_extension, push_open_with_history, record_open_with_command_in_settings,
+    remove_open_with_command_in_settings, replace_open_with_command_in_settings,
+    set_open_with_default_in_settings, sync_open_with_settings_to_draft,
 };
 pub use super::shared_file_manager_settings::{
     FileManagerDisplaySettingsUpdate, FileManagerSettingsUpdate,
 };
 #[cfg(test)]
-use crate::config::{DesktopFileManagerSettings, FileManagerViewMode};
+use crate::config::FileManagerViewMode;
+use crate::config::{get_settings, DesktopFileManagerSettings};
 use anyhow::Result;
-use std::path::Path;
-#[cfg(test)]
-use std::path::PathBuf;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
 
-#[cfg(test)]
-use robcos_native_file_manager_app::FileManagerAction;
+pub use robcos_native_file_manager_app::batch_rename::{
+    preview_batch_rename, BatchRenameRule, RenameCase,
+};
+pub use robcos_native_file_manager_app::checksum::{
+    format_sum_line, is_checksum_file, write_sum_file, ChecksumAlgorithm, ChecksumJob,
+    ChecksumPhase, ChecksumResults, ChecksumTask, DuplicateGroup, VerifyStatus,
+};
+pub use robcos_native_file_manager_app::commander::{
+    run_commander_command, CommanderCommand, CommanderOutcome, CommanderPanes, CommanderSide,
+};
+pub use robcos_native_file_manager_app::places::{
+    folder_places, is_bookmarked, Place, PlaceSection,
+};
+pub use robcos_native_file_manager_app::properties::{
+    format_timestamp, mode_string, parse_octal_mode, resolve_group, resolve_user, set_mode,
+    set_owner, with_executable, FileProperties, FolderSize, PropertiesKind,
+};
+pub use robcos_native_file_manager_app::search::{
+    parse_size, save_search, saved_searches_dir, FileSearch, SearchKind, SearchSpec,
+};
+pub use robcos_native_file_manager_app::transfers::{
+    ConflictChoice, TransferOutcome, TransferQueue, TransferRequest, TransferState,
+};
+pub use robcos_native_file_manager_app::watch::{DirWatcher, WATCH_DEBOUNCE};
 pub use robcos_native_file_manager_app::{
-    commit_picker_selection, open_target_for_file_manager_action, open_with_extension_key,
-    prepare_open_with_launch, selected_file, selection_activation_for_selected_path,
-    FileManagerClipboardMode, FileManagerEditRuntime, FileManagerOpenTarget, FileManagerPickMode,
-    FileManagerPickerCommit, FileManagerSelectionActivation, NativeFileManagerDragPayload,
-    OpenWithLaunchRequest,
+    commit_picker_selection, format_bytes_human, open_target_for_file_manager_action,
+    open_with_extension_key, prepare_open_with_launch, selected_file,
+    selection_activation_for_selected_path, FileManagerAction, FileManagerClipboardMode,
+    FileManagerEditRuntime, FileManagerOpenTarget, FileManagerPickMode, FileManagerPickerCommit,
+    FileManagerSelectionActivation, NativeFileManagerDragPayload, OpenWithLaunchRequest,
 };
 #[cfg(test)]
 pub use robcos_native_file_manager_app::{open_with_state_for_path, FileManagerClipboardItem};
 
+/// Open batch rename dialog: the entries it was opened for and the rule
+/// being edited.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchRenameDialog {
+    pub entries: Vec<FileEntryRow>,
+    pub rule: BatchRenameRule,
+}
+
+/// Age choices offered by the Find Files dialog, in days.
+pub const FIND_MODIFIED_WITHIN: [(&str, Option<u64>); 5] = [
+    ("Any time", None),
+    ("Today", Some(1)),
+    ("Past week", Some(7)),
+    ("Past month", Some(30)),
+    ("Past year", Some(365)),
+];
+
+/// Open "Find Files" dialog. Sizes stay as typed until the search starts so
+/// the fields can hold half-finished input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FindFilesDialog {
+    pub root: String,
+    pub name: String,
+    pub use_regex: bool,
+    pub contents: String,
+    pub min_size: String,
+    pub max_size: String,
+    pub modified_within_days: Option<u64>,
+    pub kind: SearchKind,
+    pub include_hidden: bool,
+    /// Name for "Save Search"; the search is stored under this name.
+    pub save_name: String,
+    pub error: Option<String>,
+}
+
+impl FindFilesDialog {
+    /// Starts from the search being shown, if any, so it can be refined or
+    /// saved; otherwise searches the current folder.
+    pub fn new(file_manager: &NativeFileManagerState, show_hidden: bool) -> Self {
+        let spec = match &file_manager.search {
+            Some(search) => search.spec().clone(),
+            None => SearchSpec {
+                include_hidden: show_hidden,
+                ..SearchSpec::new(file_manager.cwd.clone())
+            },
+        };
+        let size = |value: Option<u64>| value.map(|v| v.to_string()).unwrap_or_default();
+        Self {
+            root: spec.root.display().to_string(),
+            name: spec.name,
+            use_regex: spec.use_regex,
+            contents: spec.contents,
+            min_size: size(spec.min_size),
+            max_size: size(spec.max_size),
+            modified_within_days: spec.modified_within_days,
+            kind: spec.kind,
+            include_hidden: spec.include_hidden,
+            save_name: String::new(),
+            error: None,
+        }
+    }
+
+    pub fn spec(&self) -> Result<SearchSpec> {
+        let root = self.root.trim();
+        if root.is_empty() {
+            return Err(anyhow::anyhow!("Choose a folder to search."));
+        }
+        Ok(SearchSpec {
+            root: PathBuf::from(root),
+            name: self.name.trim().to_string(),
+            use_regex: self.use_regex,
+            contents: self.contents.clone(),
+            min_size: parse_size(&self.min_size)?,
+            max_size: parse_size(&self.max_size)?,
+            modified_within_days: self.modified_within_days,
+            kind: self.kind,
+            include_hidden: self.include_hidden,
+        })
+    }
+}
+
+/// Open "Properties" dialog. Permission and owner fields are edited here
+/// and only written on Apply.
+#[derive(Debug)]
+pub struct PropertiesDialog {
+    pub properties: FileProperties,
+    /// Recursive size, for folders.
+    pub folder_size: Option<FolderSize>,
+    pub mode: u32,
+    /// Octal text kept beside `mode` so it can hold half-typed input.
+    pub octal: String,
+    pub owner: String,
+    pub group: String,
+    pub ext_key: String,
+    /// Per-file open-with command; empty falls back to the extension default.
+    pub open_with: String,
+    pub extension_default: Option<String>,
+    pub error: Option<String>,
+}
+
+impl PropertiesDialog {
+    pub fn open(path: &Path, settings: &DesktopFileManagerSettings) -> Result<Self> {
+        let properties = FileProperties::read(path)?;
+        let folder_size = (properties.kind == PropertiesKind::Folder)
+            .then(|| FolderSize::start(path.to_path_buf()));
+        let mut dialog = Self {
+            folder_size,
+            mode: 0,
+            octal: String::new(),
+            owner: String::new(),
+            group: String::new(),
+            open_with: open_with_override_for_path(settings, &path.to_string_lossy())
+                .unwrap_or_default(),
+            extension_default: open_with_default_for_extension(
+                settings,
+                &open_with_extension_key(path),
+            ),
+            ext_key: open_with_extension_key(path),
+            error: None,
+            properties,
+        };
+        dialog.reset_ownership_fields();
+        Ok(dialog)
+    }
+
+    fn reset_ownership_fields(&mut self) {
+        if let Some(ownership) = &self.properties.ownership {
+            self.mode = ownership.mode;
+            self.octal = format!("{:04o}", ownership.mode);
+            self.owner = ownership.owner.clone();
+            self.group = ownership.group.clone();
+        }
+    }
+
+    /// Updates the mode from a checkbox and keeps the octal text in step.
+    pub fn set_mode(&mut self, mode: u32) {
+        self.mode = mode & 0o7777;
+        self.octal = format!("{:04o}", self.mode);
+    }
+
+    /// Takes the octal text once it parses; half-typed text is left alone.
+    pub fn sync_octal(&mut self) {
+        if let Ok(mode) = parse_octal_mode(&self.octal) {
+            self.mode = mode & 0o7777;
+        }
+    }
+
+    /// Writes the changed fields. Permissions and ownership are only
+    /// touched when `can_edit` is set; the open-with override is returned
+    /// as a settings update for the caller to persist.
+    pub fn apply(
+        &mut self,
+        can_edit: bool,
+        settings: &DesktopFileManagerSettings,
+    ) -> Result<(String, Option<FileManagerSettingsUpdate>)> {
+        let path = self.properties.path.clone();
+        let mut changed = Vec::new();
+        if let (true, Some(ownership)) = (can_edit, self.properties.ownership.clone()) {
+            let mode = parse_octal_mode(&self.octal)?;
+            let owner = self.owner.trim();
+            let group = self.group.trim();
+            if owner != ownership.owner || group != ownership.group {
+                set_owner(&path, resolve_user(owner)?, resolve_group(group)?)?;
+                changed.push("owner");
+            }
+            // chown can clear setuid/setgid, so compare against a fresh read.
+            let current = FileProperties::read(&path)?
+                .ownership
+                .map_or(ownership.mode, |ownership| ownership.mode);
+            if mode != current {
+                set_mode(&path, mode)?;
+                changed.push("permissions");
+            }
+        }
+        let key = path.to_string_lossy().to_string();
+        let command = self.open_with.trim();
+        let update = (open_with_override_for_path(settings, &key).unwrap_or_default() != command)
+            .then(|| {
+                changed.push("open with");
+                FileManagerSettingsUpdate::SetOpenWithOverride {
+                    path: key,
+                    ext_key: self.ext_key.clone(),
+                    command: (!command.is_empty()).then(|| command.to_string()),
+                }
+            });
+        self.properties = FileProperties::read(&path)?;
+        self.reset_ownership_fields();
+        self.error = None;
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string());
+        let status = if changed.is_empty() {
+            format!("No changes to {name}")
+        } else {
+            format!("Updated {} of {name}", changed.join(", "))
+        };
+        Ok((status, update))
+    }
+}
+
+/// Open "Checksums" or "Find Duplicates" dialog, showing a [`ChecksumJob`].
+/// Duplicate copies ticked here go to the trash as a queued transfer.
+#[derive(Debug)]
+pub struct ChecksumDialog {
+    pub job: ChecksumJob,
+    /// Duplicate copies ticked for the trash.
+    pub marked: BTreeSet<PathBuf>,
+    /// Copies the trash job moved to the trash, hidden from the groups.
+    pub trashed: BTreeSet<PathBuf>,
+    /// Transfer id of the trash job still running.
+    pub trash_job: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl ChecksumDialog {
+    pub fn start(task: ChecksumTask) -> Result<Self> {
+        Ok(Self {
+            job: ChecksumJob::start(task)?,
+            marked: BTreeSet::new(),
+            trashed: BTreeSet::new(),
+            trash_job: None,
+            error: None,
+        })
+    }
+
+    /// Runs the same task again with another algorithm.
+    pub fn restart(&mut self, algorithm: ChecksumAlgorithm) -> Result<()> {
+        *self = Self::start(self.job.task().with_algorithm(algorithm))?;
+        Ok(())
+    }
+
+    /// Duplicate groups that still have more than one copy left.
+    pub fn duplicate_groups(&self) -> Vec<DuplicateGroup> {
+        let Some(ChecksumResults::Duplicates(groups)) = self.job.results() else {
+            return Vec::new();
+        };
+        groups
+            .into_iter()
+            .filter_map(|mut group| {
+                group.paths.retain(|path| !self.trashed.contains(path));
+                (group.paths.len() > 1).then_some(group)
+            })
+            .collect()
+    }
+
+    pub fn toggle_marked(&mut self, path: &Path) {
+        if !self.marked.remove(path) {
+            self.marked.insert(path.to_path_buf());
+        }
+    }
+
+    /// Ticks every copy but the first (shallowest) one in each group.
+    pub fn mark_all_but_first(&mut self) {
+        self.marked = self
+            .duplicate_groups()
+            .into_iter()
+            .flat_map(|group| group.paths.into_iter().skip(1))
+            .collect();
+    }
+
+    /// Clears the ticks and returns the transfer that trashes the ticked
+    /// copies. At least one copy of every file must stay. The copies leave
+    /// the groups once the job reports them trashed, see
+    /// [`Self::finish_trash`].
+    pub fn take_trash_request(&mut self) -> Result<TransferRequest> {
+        if self.trash_job.is_some() {
+            return Err(anyhow::anyhow!(
+                "Wait until the last copies are in the trash."
+            ));
+        }
+        let groups = self.duplicate_groups();
+        if self.marked.is_empty() {
+            return Err(anyhow::anyhow!("Tick the copies to move to the trash."));
+        }
+        if let Some(group) = groups
+            .iter()
+            .find(|group| group.paths.iter().all(|path| self.marked.contains(path)))
+        {
+            let name = group
+                .paths
+                .first()
+                .and_then(|path| path.file_name())
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            return Err(anyhow::anyhow!("Keep at least one copy of {name}."));
+        }
+        let sources: Vec<PathBuf> = std::mem::take(&mut self.marked).into_iter().collect();
+        self.error = None;
+        Ok(TransferRequest::trash(sources))
+    }
+
+    /// Remembers the queued trash job so its outcome can be matched.
+    pub fn trash_started(&mut self, id: u64) {
+        self.trash_job = Some(id);
+    }
+
+    /// Hides the copies the trash job actually trashed. Outcomes of other
+    /// jobs are ignored.
+    pub fn finish_trash(&mut self, outcome: &TransferOutcome) {
+        if self.trash_job != Some(outcome.id) {
+            return;
+        }
+        self.trash_job = None;
+        self.trashed
+            .extend(outcome.ops.iter().filter_map(|op| match op {
+                robcos_native_file_manager_app::FileManagerEditOp::Trashed { original, .. } => {
+                    Some(original.clone())
+                }
+                _ => None,
+            }));
+        if let Some(err) = &outcome.error {
+            self.error = Some(err.clone());
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FileManagerCommandRequest {
     None,
     ActivateSelection,
     OpenPrompt(FileManagerPromptRequest),
+    OpenBatchRename(Vec<FileEntryRow>),
+    OpenFindFiles(FindFilesDialog),
+    OpenProperties(PathBuf),
+    OpenChecksums(ChecksumTask),
+    Commander(CommanderCommand),
+    QueueTransfer(TransferRequest),
+    ShowTransfers,
     ApplyDisplaySettings(FileManagerDisplaySettingsUpdate),
+    ApplySettings {
+        update: FileManagerSettingsUpdate,
+        status: String,
+    },
     ReportStatus(String),
 }
 
@@ -93,10 +443,14 @@ pub fn run_command(
             &file_manager.selected_rows_for_action(),
             FileManagerClipboardMode::Cut,
         )),
-        FileManagerCommand::Paste => command_status_request(runtime.paste_clipboard(file_manager)),
+        FileManagerCommand::Paste => transfer_request(runtime.paste_request(file_manager)),
         FileManagerCommand::Duplicate => command_status_request(
             runtime.duplicate_selected(file_manager, file_manager.selected_rows_for_action()),
         ),
+        FileManagerCommand::Rename if file_manager.selected_rows_for_action().len() > 1 => {
+            batch_rename_request(file_manager)
+        }
+        FileManagerCommand::BatchRename => batch_rename_request(file_manager),
         FileManagerCommand::Rename | FileManagerCommand::Move => {
             match prompt_request_for_command(command, file_manager.selected_row()) {
                 Ok(Some(request)) => FileManagerCommandRequest::OpenPrompt(request),
@@ -104,14 +458,135 @@ pub fn run_command(
                 Err(status) => FileManagerCommandRequest::ReportStatus(status),
             }
         }
-        FileManagerCommand::Delete => command_status_request(
-            runtime.delete_selected(file_manager, file_manager.selected_rows_for_action()),
+        FileManagerCommand::Delete if file_manager.is_trash_view() => {
+            trash_prompt_request(command, file_manager)
+        }
+        FileManagerCommand::Delete => transfer_request(FileManagerEditRuntime::trash_request(
+            &file_manager.selected_rows_for_action(),
+        )),
+        FileManagerCommand::Compress => match compress_prompt_request(file_manager) {
+            Ok(request) => FileManagerCommandRequest::OpenPrompt(request),
+            Err(status) => FileManagerCommandRequest::ReportStatus(status),
+        },
+        FileManagerCommand::Extract => transfer_request(FileManagerEditRuntime::extract_request(
+            file_manager,
+            &file_manager.selected_rows_for_action(),
+        )),
+        FileManagerCommand::ExtractAll => {
+            transfer_request(FileManagerEditRuntime::extract_all_request(
+                file_manager,
+                &file_manager.selected_rows_for_action(),
+            ))
+        }
+        FileManagerCommand::OpenTrash => match file_manager.open_trash() {
+            Ok(()) => FileManagerCommandRequest::None,
+            Err(err) => {
+                FileManagerCommandRequest::ReportStatus(format!("Open Trash failed: {err}"))
+            }
+        },
+        FileManagerCommand::RestoreFromTrash => command_status_request(
+            runtime.restore_selected(file_manager, file_manager.selected_rows_for_action()),
         ),
+        FileManagerCommand::EmptyTrash => trash_prompt_request(command, file_manager),
         FileManagerCommand::Undo => command_status_request(runtime.undo(file_manager)),
         FileManagerCommand::Redo => command_status_request(runtime.redo(file_manager)),
         FileManagerCommand::ToggleTreePanel => FileManagerCommandRequest::ApplyDisplaySettings(
             FileManagerDisplaySettingsUpdate::ToggleTreePanel,
         ),
+        FileManagerCommand::TogglePreviewPanel => FileManagerCommandRequest::ApplyDisplaySettings(
+            FileManagerDisplaySettingsUpdate::TogglePreviewPanel,
+        ),
+        FileManagerCommand::ToggleDualPane => FileManagerCommandRequest::ApplyDisplaySettings(
+            FileManagerDisplaySettingsUpdate::ToggleDualPane,
+        ),
+        FileManagerCommand::CopyToOtherPane => {
+            FileManagerCommandRequest::Commander(CommanderCommand::CopyToOtherPane)
+        }
+        FileManagerCommand::MoveToOtherPane => {
+            FileManagerCommandRequest::Commander(CommanderCommand::MoveToOtherPane)
+        }
+        FileManagerCommand::SwitchPane => {
+            FileManagerCommandRequest::Commander(CommanderCommand::SwitchPane)
+        }
+        FileManagerCommand::ShowTransfers => FileManagerCommandRequest::ShowTransfers,
+        FileManagerCommand::FindFiles => {
+            FileManagerCommandRequest::OpenFindFiles(FindFilesDialog::new(
+                file_manager,
+                get_settings().desktop_file_manager.show_hidden_files,
+            ))
+        }
+        FileManagerCommand::StopSearch => {
+            FileManagerCommandRequest::ReportStatus(if file_manager.stop_search() {
+                "Search stopped.".to_string()
+            } else {
+                "No search is running.".to_string()
+            })
+        }
+        FileManagerCommand::RevealInFolder => {
+            command_status_request(file_manager.reveal_selected())
+        }
+        FileManagerCommand::Properties => {
+            let path = match file_manager.selected_rows_for_action().as_slice() {
+                [row] => row.path.clone(),
+                [] => file_manager.cwd.clone(),
+                _ => {
+                    return FileManagerCommandRequest::ReportStatus(
+                        "Select a single item to see its properties.".to_string(),
+                    )
+                }
+            };
+            FileManagerCommandRequest::OpenProperties(path)
+        }
+        FileManagerCommand::Checksums => {
+            let rows = file_manager.selected_rows_for_action();
+            let task = match rows.as_slice() {
+                [row] if is_checksum_file(&row.path) => ChecksumTask::Verify {
+                    sum_file: row.path.clone(),
+                },
+                rows => ChecksumTask::Compute {
+                    paths: rows
+                        .iter()
+                        .filter(|row| !row.is_dir)
+                        .map(|row| row.path.clone())
+                        .collect(),
+                    algorithm: ChecksumAlgorithm::default(),
+                },
+            };
+            if matches!(&task, ChecksumTask::Compute { paths, .. } if paths.is_empty()) {
+                return FileManagerCommandRequest::ReportStatus(
+                    "Select files to checksum.".to_string(),
+                );
+            }
+            FileManagerCommandRequest::OpenChecksums(task)
+        }
+        FileManagerCommand::FindDuplicates => {
+            let root = match file_manager.selected_rows_for_action().as_slice() {
+                [row] if row.is_dir => row.path.clone(),
+                _ => file_manager.cwd.clone(),
+            };
+            FileManagerCommandRequest::OpenChecksums(ChecksumTask::FindDuplicates {
+                root,
+                include_hidden: get_settings().desktop_file_manager.show_hidden_files,
+                algorithm: ChecksumAlgorithm::Blake3,
+            })
+        }
+        FileManagerCommand::AddBookmark => {
+            let path = file_manager.bookmark_target();
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.display().to_string());
+            if is_bookmarked(&path) {
+                FileManagerCommandRequest::ReportStatus(format!("{name} is already bookmarked."))
+            } else {
+                FileManagerCommandRequest::ApplySettings {
+                    update: FileManagerSettingsUpdate::AddBookmark {
+                        path: path.to_string_lossy().to_string(),
+                    },
+                    status: format!("Bookmarked {name}"),
+                }
+            }
+        }
         FileManagerCommand::ToggleHiddenFiles => FileManagerCommandRequest::ApplyDisplaySettings(
             FileManagerDisplaySettingsUpdate::ToggleHiddenFiles,
         ),
@@ -124,6 +599,32 @@ pub fn run_command(
     }
 }
 
+fn batch_rename_request(file_manager: &NativeFileManagerState) -> FileManagerCommandRequest {
+    let entries = file_manager.selected_rows_for_action();
+    if entries.is_empty() {
+        FileManagerCommandRequest::ReportStatus("Select items to rename.".to_string())
+    } else {
+        FileManagerCommandRequest::OpenBatchRename(entries)
+    }
+}
+
+fn trash_prompt_request(
+    command: FileManagerCommand,
+    file_manager: &NativeFileManagerState,
+) -> FileManagerCommandRequest {
+    match trash_prompt_request_for_command(command, file_manager) {
+        Ok(request) => FileManagerCommandRequest::OpenPrompt(request),
+        Err(status) => FileManagerCommandRequest::ReportStatus(status),
+    }
+}
+
+fn transfer_request(result: Result<TransferRequest>) -> FileManagerCommandRequest {
+    match result {
+        Ok(request) => FileManagerCommandRequest::QueueTransfer(request),
+        Err(err) => FileManagerCommandRequest::ReportStatus(format!("File action failed: {err}")),
+    }
+}
+
 fn command_status_request(result: Result<String>) -> FileManagerCommandRequest {
     match result {
         Ok(message) => FileManagerCommandRequest::ReportStatus(message),
@@ -528,6 +1029,324 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rename_with_multi_selection_opens_batch_rename() {
+        let temp = TempDirGuard::new("run_command_batch_rename");
+        let a = temp.path.join("a.txt");
+        let b = temp.path.join("b.txt");
+        std::fs::write(&a, "a").expect("write a");
+        std::fs::write(&b, "b").expect("write b");
+        let mut file_manager = NativeFileManagerState::new(temp.path.clone());
+        file_manager.toggle_selected_path(&a);
+        file_manager.toggle_selected_path(&b);
+        let mut runtime = FileManagerEditRuntime::default();
+
+        match run_command(
+            FileManagerCommand::Rename,
+            &mut file_manager,
+            &mut runtime,
+            &temp.path,
+        ) {
+            FileManagerCommandRequest::OpenBatchRename(entries) => {
+                let paths: Vec<PathBuf> = entries.into_iter().map(|entry| entry.path).collect();
+                assert_eq!(paths, vec![a, b]);
+            }
+            other => panic!("expected batch rename, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn commander_commands_map_to_commander_requests() {
+        let temp = TempDirGuard::new("run_command_commander");
+        let mut file_manager = NativeFileManagerState::new(temp.path.clone());
+        let mut runtime = FileManagerEditRuntime::default();
+
+        for (command, expected) in [
+            (
+                FileManagerCommand::CopyToOtherPane,
+                CommanderCommand::CopyToOtherPane,
+            ),
+            (
+                FileManagerCommand::MoveToOtherPane,
+                CommanderCommand::MoveToOtherPane,
+            ),
+            (FileManagerCommand::SwitchPane, CommanderCommand::SwitchPane),
+        ] {
+            assert_eq!(
+                run_command(command, &mut file_manager, &mut runtime, &temp.path),
+                FileManagerCommandRequest::Commander(expected)
+            );
+        }
+        assert_eq!(
+            run_command(
+                FileManagerCommand::ToggleDualPane,
+                &mut file_manager,
+                &mut runtime,
+                &temp.path,
+            ),
+            FileManagerCommandRequest::ApplyDisplaySettings(
+                FileManagerDisplaySettingsUpdate::ToggleDualPane
+            )
+        );
+    }
+
+    #[test]
+    fn paste_and_delete_queue_background_transfers() {
+        let temp = TempDirGuard::new("run_command_transfers");
+        let file_path = temp.path.join("demo.txt");
+        std::fs::write(&file_path, "demo").expect("write temp file");
+        let mut file_manager = NativeFileManagerState::new(temp.path.clone());
+        file_manager.select(Some(file_path.clone()));
+        let mut runtime = FileManagerEditRuntime::default();
+
+        run_command(
+            FileManagerCommand::Copy,
+            &mut file_manager,
+            &mut runtime,
+            &temp.path,
+        );
+        assert_eq!(
+            run_command(
+                FileManagerCommand::Paste,
+                &mut file_manager,
+                &mut runtime,
+                &temp.path,
+            ),
+            FileManagerCommandRequest::QueueTransfer(TransferRequest::copy(
+                vec![file_path.clone()],
+                temp.path.clone(),
+            ))
+        );
+        assert_eq!(
+            run_command(
+                FileManagerCommand::Delete,
+                &mut file_manager,
+                &mut runtime,
+                &temp.path,
+            ),
+            FileManagerCommandRequest::QueueTransfer(TransferRequest::trash(vec![file_path]))
+        );
+        assert!(temp.path.join("demo.txt").exists());
+    }
+
+    #[test]
+    fn find_files_opens_dialog_for_current_folder_and_parses_filters() {
+        let temp = TempDirGuard::new("run_command_find");
+        let mut file_manager = NativeFileManagerState::new(temp.path.clone());
+        let mut runtime = FileManagerEditRuntime::default();
+
+        let FileManagerCommandRequest::OpenFindFiles(mut dialog) = run_command(
+            FileManagerCommand::FindFiles,
+            &mut file_manager,
+            &mut runtime,
+            &temp.path,
+        ) else {
+            panic!("expected the find dialog");
+        };
+        assert_eq!(PathBuf::from(&dialog.root), temp.path);
+
+        dialog.name = " *.md ".to_string();
+        dialog.min_size = "2K".to_string();
+        dialog.kind = SearchKind::Documents;
+        let spec = dialog.spec().expect("valid filters");
+        assert_eq!(spec.name, "*.md");
+        assert_eq!(spec.min_size, Some(2048));
+        assert_eq!(spec.max_size, None);
+        assert_eq!(spec.kind, SearchKind::Documents);
+
+        dialog.max_size = "lots".to_string();
+        assert!(dialog.spec().is_err());
+        assert_eq!(
+            run_command(
+                FileManagerCommand::StopSearch,
+                &mut file_manager,
+                &mut runtime,
+                &temp.path,
+            ),
+            FileManagerCommandRequest::ReportStatus("No search is running.".to_string())
+        );
+    }
+
+    #[test]
+    fn properties_dialog_applies_mode_and_open_with_override() {
+        let temp = TempDirGuard::new("run_command_properties");
+        let script = temp.path.join("build.sh");
+        std::fs::write(&script, "echo hi").unwrap();
+        let mut file_manager = NativeFileManagerState::new(temp.path.clone());
+        let mut runtime = FileManagerEditRuntime::default();
+        file_manager.select(Some(script.clone()));
+        assert_eq!(
+            run_command(
+                FileManagerCommand::Properties,
+                &mut file_manager,
+                &mut runtime,
+                &temp.path,
+            ),
+            FileManagerCommandRequest::OpenProperties(script.clone())
+        );
+
+        let mut settings = DesktopFileManagerSettings::default();
+        let mut dialog = PropertiesDialog::open(&script, &settings).unwrap();
+        assert_eq!(dialog.properties.mime, "application/x-shellscript");
+        assert_eq!(dialog.ext_key, "sh");
+        dialog.open_with = " bash ".to_string();
+        let (_, update) = dialog.apply(false, &settings).unwrap();
+        update.expect("override update").apply(&mut settings);
+        assert_eq!(
+            settings
+                .open_with_by_path
+                .get(&script.to_string_lossy().to_string()),
+            Some(&"bash".to_string())
+        );
+        assert_eq!(
+            open_with_history_for_extension(&settings, "sh"),
+            vec!["bash".to_string()]
+        );
+
+        #[cfg(unix)]
+        {
+            dialog.set_mode(0o600);
+            dialog.octal = "69".to_string();
+            dialog.sync_octal();
+            assert_eq!(dialog.mode, 0o600);
+            let (status, update) = dialog.apply(false, &settings).unwrap();
+            assert_eq!(status, "No changes to build.sh");
+            assert!(update.is_none());
+            dialog.set_mode(with_executable(0o640, true));
+            let (status, _) = dialog.apply(true, &settings).unwrap();
+            assert_eq!(status, "Updated permissions of build.sh");
+            assert_eq!(
+                dialog.properties.ownership.as_ref().map(|o| o.mode),
+                Some(0o750)
+            );
+            assert_eq!(dialog.octal, "0750");
+        }
+
+        dialog.open_with.clear();
+        let (_, update) = dialog.apply(false, &settings).unwrap();
+        update.expect("clear update").apply(&mut settings);
+        assert!(settings.open_with_by_path.is_empty());
+    }
+
+    #[test]
+    fn duplicate_dialog_trashes_ticked_copies_but_keeps_one() {
+        let temp = TempDirGuard::new("run_command_duplicates");
+        let keep = temp.path.join("a.txt");
+        let copy = temp.path.join("b.txt");
+        std::fs::write(&keep, "same").unwrap();
+        std::fs::write(&copy, "same").unwrap();
+        let mut file_manager = NativeFileManagerState::new(temp.path.clone());
+        let mut runtime = FileManagerEditRuntime::default();
+        file_manager.select(Some(keep.clone()));
+        assert!(matches!(
+            run_command(
+                FileManagerCommand::Checksums,
+                &mut file_manager,
+                &mut runtime,
+                &temp.path,
+            ),
+            FileManagerCommandRequest::OpenChecksums(ChecksumTask::Compute { ref paths, .. })
+                if *paths == vec![keep.clone()]
+        ));
+        file_manager.select(None);
+        let FileManagerCommandRequest::OpenChecksums(task) = run_command(
+            FileManagerCommand::FindDuplicates,
+            &mut file_manager,
+            &mut runtime,
+            &temp.path,
+        ) else {
+            panic!("expected a duplicate search");
+        };
+
+        let mut dialog = ChecksumDialog::start(task).unwrap();
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+        while dialog.job.is_running() {
+            assert!(std::time::Instant::now() < deadline);
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(dialog.duplicate_groups().len(), 1);
+        dialog.toggle_marked(&keep);
+        dialog.toggle_marked(&copy);
+        assert!(dialog.take_trash_request().is_err());
+        dialog.mark_all_but_first();
+        assert_eq!(
+            dialog.take_trash_request().unwrap(),
+            TransferRequest::trash(vec![copy.clone()])
+        );
+        dialog.trash_started(7);
+        // Nothing is hidden until the job says the copy is in the trash.
+        assert_eq!(dialog.duplicate_groups().len(), 1);
+        dialog.toggle_marked(&keep);
+        assert!(dialog.take_trash_request().is_err());
+
+        let mut queue = TransferQueue::new(robcos_native_file_manager_app::trash::Trash::at(
+            temp.path.join("trash"),
+        ));
+        let id = queue.enqueue(TransferRequest::trash(vec![copy.clone()]));
+        let outcome = loop {
+            if let Some(outcome) = queue.poll() {
+                break outcome;
+            }
+            assert!(std::time::Instant::now() < deadline);
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        };
+        dialog.finish_trash(&outcome);
+        assert_eq!(dialog.duplicate_groups().len(), 1);
+        dialog.trash_started(id);
+        dialog.finish_trash(&outcome);
+        assert!(dialog.duplicate_groups().is_empty());
+        assert_eq!(dialog.trash_job, None);
+    }
+
+    #[test]
+    fn add_bookmark_pins_selected_folder_and_bookmarks_reorder() {
+        let temp = TempDirGuard::new("run_command_bookmark");
+        let projects = temp.path.join("projects");
+        std::fs::create_dir_all(&projects).unwrap();
+        std::fs::write(temp.path.join("notes.txt"), "notes").unwrap();
+        let mut file_manager = NativeFileManagerState::new(temp.path.clone());
+        let mut runtime = FileManagerEditRuntime::default();
+        let mut bookmark = |file_manager: &mut NativeFileManagerState| {
+            let FileManagerCommandRequest::ApplySettings { update, status } = run_command(
+                FileManagerCommand::AddBookmark,
+                file_manager,
+                &mut runtime,
+                &temp.path,
+            ) else {
+                panic!("expected a settings update");
+            };
+            (update, status)
+        };
+
+        file_manager.select(Some(projects.clone()));
+        let (update, status) = bookmark(&mut file_manager);
+        assert_eq!(status, "Bookmarked projects");
+        let mut settings = DesktopFileManagerSettings::default();
+        update.apply(&mut settings);
+
+        file_manager.select(Some(temp.path.join("notes.txt")));
+        let (update, _) = bookmark(&mut file_manager);
+        update.apply(&mut settings);
+        update.apply(&mut settings);
+        let cwd = temp.path.to_string_lossy().to_string();
+        let projects = projects.to_string_lossy().to_string();
+        assert_eq!(settings.bookmarks, vec![projects.clone(), cwd.clone()]);
+
+        FileManagerSettingsUpdate::MoveBookmark {
+            path: cwd.clone(),
+            up: true,
+        }
+        .apply(&mut settings);
+        assert_eq!(settings.bookmarks, vec![cwd.clone(), projects.clone()]);
+        FileManagerSettingsUpdate::MoveBookmark {
+            path: cwd.clone(),
+            up: true,
+        }
+        .apply(&mut settings);
+        FileManagerSettingsUpdate::RemoveBookmark { path: projects }.apply(&mut settings);
+        assert_eq!(settings.bookmarks, vec![cwd]);
+    }
+
     #[test]
     fn selected_file_requires_exactly_one_file_entry() {
         let temp = TempDirGuard::new("selected_file");
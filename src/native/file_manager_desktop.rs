@@ -1,5 +1,8 @@
 use super::file_manager::{FileEntryRow, FileManagerCommand, FileTreeItem, NativeFileManagerState};
 use crate::config::{DesktopFileManagerSettings, FileManagerViewMode};
+use robcos_native_file_manager_app::format_bytes_human;
+use robcos_native_file_manager_app::places::{places, Place};
+use robcos_native_file_manager_app::trash::Trash;
 use std::path::PathBuf;
 
 pub const FILE_MANAGER_APP_TITLE: &str = "My Computer";
@@ -17,6 +20,12 @@ pub enum FileManagerDesktopFooterAction {
     CancelIconPicker,
     ChooseWallpaper,
     CancelWallpaperPicker,
+    RestoreFromTrash,
+    DeleteFromTrash,
+    EmptyTrash,
+    RevealInFolder,
+    StopSearch,
+    FindFiles,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -79,6 +88,9 @@ pub struct FileManagerDesktopStatus {
     pub has_editable_selection: bool,
     pub has_single_file_selection: bool,
     pub has_clipboard: bool,
+    pub trash_origin: Option<String>,
+    /// Folder of the selected search result.
+    pub search_location: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -95,12 +107,22 @@ pub struct FileManagerDesktopViewModel {
     pub tabs: Vec<FileManagerDesktopTab>,
     pub drives: Vec<FileManagerDesktopDrive>,
     pub rows: Vec<FileEntryRow>,
+    /// Places sidebar entries, listed above the folder tree.
+    pub places: Vec<Place>,
     pub tree_items: Vec<FileTreeItem>,
     pub current_drive_label: Option<String>,
     pub path_label: String,
     pub search_query: String,
     pub show_tree_panel: bool,
+    /// What the preview pane shows: the selected entry, or the current
+    /// folder when nothing is selected. `None` while the pane is hidden.
+    pub preview_path: Option<PathBuf>,
+    /// Commander layout; pickers always use the single pane.
+    pub dual_pane: bool,
     pub view_mode: FileManagerViewMode,
+    pub in_trash: bool,
+    pub in_search: bool,
+    pub search_running: bool,
     pub status: FileManagerDesktopStatus,
 }
 
@@ -134,11 +156,50 @@ pub fn build_desktop_view_model(
 ) -> FileManagerDesktopViewModel {
     let action_mode =
         desktop_action_mode(save_as_input, picking_icon_for_shortcut, picking_wallpaper);
+    let in_trash =
+        action_mode == FileManagerDesktopActionMode::Normal && file_manager.is_trash_view();
+    let mut trash_origin = None;
+    let mut search_location = None;
+    let search = file_manager
+        .search
+        .as_ref()
+        .filter(|_| action_mode == FileManagerDesktopActionMode::Normal);
     let path_label = match &action_mode {
         FileManagerDesktopActionMode::SavePicker { .. } => {
             format!("Save Folder: {}", file_manager.cwd.display())
         }
-        _ => format!("Path: {}", file_manager.cwd.display()),
+        _ if in_trash => {
+            let trash = Trash::user();
+            trash_origin = file_manager
+                .selected_row()
+                .and_then(|row| trash.entry(&row.label))
+                .map(|entry| match entry.original_path {
+                    Some(path) => format!("From: {}", path.display()),
+                    None => "From: unknown location".to_string(),
+                });
+            let entries = trash.entries();
+            let size: u64 = entries.iter().map(|entry| entry.size).sum();
+            format!(
+                "Trash: {} item(s), {}",
+                entries.len(),
+                format_bytes_human(size)
+            )
+        }
+        _ => match search {
+            Some(search) => {
+                search_location = file_manager
+                    .selected_row()
+                    .filter(|row| !row.is_parent_dir())
+                    .and_then(|row| row.path.parent().map(|parent| parent.display().to_string()))
+                    .map(|parent| format!("In: {parent}"));
+                format!(
+                    "Search: {} ({})",
+                    search.spec().describe(),
+                    search.summary()
+                )
+            }
+            None => format!("Path: {}", file_manager.cwd.display()),
+        },
     };
     let current_drive = file_manager.current_drive_root();
     FileManagerDesktopViewModel {
@@ -161,6 +222,11 @@ pub fn build_desktop_view_model(
             })
             .collect(),
         rows: rows.to_vec(),
+        places: if settings.show_tree_panel {
+            places()
+        } else {
+            Vec::new()
+        },
         tree_items: file_manager.tree_items(),
         current_drive_label: current_drive
             .as_ref()
@@ -168,7 +234,22 @@ pub fn build_desktop_view_model(
         path_label,
         search_query: file_manager.search_query.clone(),
         show_tree_panel: settings.show_tree_panel,
+        preview_path: settings.show_preview_panel.then(|| {
+            file_manager
+                .selected
+                .as_ref()
+                .and_then(|selected| {
+                    rows.iter()
+                        .find(|row| &row.path == selected && !row.is_parent_dir())
+                })
+                .map(|row| row.path.clone())
+                .unwrap_or_else(|| file_manager.cwd.clone())
+        }),
+        dual_pane: settings.dual_pane && action_mode == FileManagerDesktopActionMode::Normal,
         view_mode: settings.view_mode,
+        in_trash,
+        in_search: search.is_some(),
+        search_running: search.is_some_and(|search| search.is_running()),
         status: FileManagerDesktopStatus {
             row_count: rows.len(),
             selected_count,
@@ -184,6 +265,8 @@ pub fn build_desktop_view_model(
             has_editable_selection,
             has_single_file_selection,
             has_clipboard,
+            trash_origin,
+            search_location,
         },
         action_mode,
     }
@@ -293,6 +376,73 @@ pub fn build_footer_model(model: &FileManagerDesktopViewModel) -> FileManagerDes
             ],
             file_name: None,
         },
+        FileManagerDesktopActionMode::Normal if model.in_trash => FileManagerDesktopFooterModel {
+            status_items: [
+                format!("{} item(s)", model.status.row_count),
+                format!("{} selected", model.status.selected_count),
+            ]
+            .into_iter()
+            .chain(model.status.trash_origin.clone())
+            .collect(),
+            leading_buttons: vec![],
+            trailing_buttons: vec![
+                FileManagerDesktopFooterButton {
+                    action: FileManagerDesktopFooterAction::RestoreFromTrash,
+                    label: "Restore",
+                },
+                FileManagerDesktopFooterButton {
+                    action: FileManagerDesktopFooterAction::DeleteFromTrash,
+                    label: "Delete",
+                },
+                FileManagerDesktopFooterButton {
+                    action: FileManagerDesktopFooterAction::EmptyTrash,
+                    label: "Empty Trash",
+                },
+                FileManagerDesktopFooterButton {
+                    action: FileManagerDesktopFooterAction::OpenHome,
+                    label: "Home",
+                },
+            ],
+            file_name: None,
+        },
+        FileManagerDesktopActionMode::Normal if model.in_search => FileManagerDesktopFooterModel {
+            status_items: [
+                format!(
+                    "{} found",
+                    model.rows.iter().filter(|row| !row.is_parent_dir()).count()
+                ),
+                format!("{} selected", model.status.selected_count),
+            ]
+            .into_iter()
+            .chain(model.status.search_location.clone())
+            .collect(),
+            leading_buttons: vec![],
+            trailing_buttons: [
+                FileManagerDesktopFooterButton {
+                    action: FileManagerDesktopFooterAction::OpenSelected,
+                    label: "Open",
+                },
+                FileManagerDesktopFooterButton {
+                    action: FileManagerDesktopFooterAction::RevealInFolder,
+                    label: "Reveal",
+                },
+                FileManagerDesktopFooterButton {
+                    action: FileManagerDesktopFooterAction::FindFiles,
+                    label: "Refine",
+                },
+            ]
+            .into_iter()
+            .chain(
+                model
+                    .search_running
+                    .then_some(FileManagerDesktopFooterButton {
+                        action: FileManagerDesktopFooterAction::StopSearch,
+                        label: "Stop",
+                    }),
+            )
+            .collect(),
+            file_name: None,
+        },
         FileManagerDesktopActionMode::Normal => FileManagerDesktopFooterModel {
             status_items: vec![
                 format!("{} item(s)", model.status.row_count),
@@ -361,6 +511,24 @@ pub fn resolve_footer_action(
         FileManagerDesktopFooterAction::CancelWallpaperPicker => {
             FileManagerDesktopFooterRequest::CancelWallpaperPicker
         }
+        FileManagerDesktopFooterAction::RestoreFromTrash => {
+            FileManagerDesktopFooterRequest::RunCommand(FileManagerCommand::RestoreFromTrash)
+        }
+        FileManagerDesktopFooterAction::DeleteFromTrash => {
+            FileManagerDesktopFooterRequest::RunCommand(FileManagerCommand::Delete)
+        }
+        FileManagerDesktopFooterAction::EmptyTrash => {
+            FileManagerDesktopFooterRequest::RunCommand(FileManagerCommand::EmptyTrash)
+        }
+        FileManagerDesktopFooterAction::RevealInFolder => {
+            FileManagerDesktopFooterRequest::RunCommand(FileManagerCommand::RevealInFolder)
+        }
+        FileManagerDesktopFooterAction::StopSearch => {
+            FileManagerDesktopFooterRequest::RunCommand(FileManagerCommand::StopSearch)
+        }
+        FileManagerDesktopFooterAction::FindFiles => {
+            FileManagerDesktopFooterRequest::RunCommand(FileManagerCommand::FindFiles)
+        }
     }
 }
 
@@ -413,6 +581,66 @@ mod tests {
         assert!(!model.drives.is_empty());
         assert!(model.rows.is_empty());
         assert!(!model.tree_items.is_empty());
+        assert_eq!(model.preview_path, None);
+    }
+
+    #[test]
+    fn build_desktop_view_model_previews_selection_or_current_folder() {
+        let settings = DesktopFileManagerSettings {
+            show_preview_panel: true,
+            ..DesktopFileManagerSettings::default()
+        };
+        let cwd = std::env::temp_dir();
+        let mut file_manager = NativeFileManagerState::new(cwd.clone());
+        let row = FileEntryRow {
+            path: cwd.join("notes.txt"),
+            label: "notes.txt".to_string(),
+            is_dir: false,
+        };
+        let build = |file_manager: &NativeFileManagerState| {
+            build_desktop_view_model(
+                file_manager,
+                &settings,
+                std::slice::from_ref(&row),
+                0,
+                false,
+                false,
+                false,
+                None,
+                None,
+                false,
+            )
+        };
+
+        assert_eq!(build(&file_manager).preview_path, Some(cwd.clone()));
+        file_manager.selected = Some(row.path.clone());
+        assert_eq!(build(&file_manager).preview_path, Some(row.path.clone()));
+    }
+
+    #[test]
+    fn build_desktop_view_model_keeps_pickers_single_pane() {
+        let settings = DesktopFileManagerSettings {
+            dual_pane: true,
+            ..DesktopFileManagerSettings::default()
+        };
+        let file_manager = NativeFileManagerState::new(PathBuf::from("/"));
+        let build = |picking_wallpaper: bool| {
+            build_desktop_view_model(
+                &file_manager,
+                &settings,
+                &[],
+                0,
+                false,
+                false,
+                false,
+                None,
+                None,
+                picking_wallpaper,
+            )
+        };
+
+        assert!(build(false).dual_pane);
+        assert!(!build(true).dual_pane);
     }
 
     #[test]
@@ -473,6 +701,83 @@ mod tests {
         assert_eq!(footer.trailing_buttons[2].label, "Open");
     }
 
+    #[test]
+    fn build_footer_model_for_trash_view_offers_restore_and_empty() {
+        let settings = DesktopFileManagerSettings::default();
+        let file_manager = NativeFileManagerState::new(PathBuf::from("/"));
+        let mut model = build_desktop_view_model(
+            &file_manager,
+            &settings,
+            &[],
+            1,
+            true,
+            true,
+            false,
+            None,
+            None,
+            false,
+        );
+        model.in_trash = true;
+        model.status.trash_origin = Some("From: /home/user/notes.txt".to_string());
+        let footer = build_footer_model(&model);
+
+        assert_eq!(
+            footer.status_items.last().map(String::as_str),
+            Some("From: /home/user/notes.txt")
+        );
+        let labels: Vec<&str> = footer.trailing_buttons.iter().map(|b| b.label).collect();
+        assert_eq!(labels, vec!["Restore", "Delete", "Empty Trash", "Home"]);
+        assert_eq!(
+            resolve_footer_action(FileManagerDesktopFooterAction::EmptyTrash),
+            FileManagerDesktopFooterRequest::RunCommand(FileManagerCommand::EmptyTrash)
+        );
+    }
+
+    #[test]
+    fn build_footer_model_for_search_results_offers_reveal_and_stop() {
+        let settings = DesktopFileManagerSettings::default();
+        let file_manager = NativeFileManagerState::new(PathBuf::from("/"));
+        let rows = vec![
+            FileEntryRow {
+                path: PathBuf::from("/"),
+                label: "..".to_string(),
+                is_dir: true,
+            },
+            FileEntryRow {
+                path: PathBuf::from("/home/user/notes.txt"),
+                label: "notes.txt".to_string(),
+                is_dir: false,
+            },
+        ];
+        let mut model = build_desktop_view_model(
+            &file_manager,
+            &settings,
+            &rows,
+            1,
+            true,
+            true,
+            false,
+            None,
+            None,
+            false,
+        );
+        model.in_search = true;
+        model.search_running = true;
+        model.status.search_location = Some("In: /home/user".to_string());
+        let footer = build_footer_model(&model);
+
+        assert_eq!(
+            footer.status_items,
+            vec!["1 found", "1 selected", "In: /home/user"]
+        );
+        let labels: Vec<&str> = footer.trailing_buttons.iter().map(|b| b.label).collect();
+        assert_eq!(labels, vec!["Open", "Reveal", "Refine", "Stop"]);
+        assert_eq!(
+            resolve_footer_action(FileManagerDesktopFooterAction::RevealInFolder),
+            FileManagerDesktopFooterRequest::RunCommand(FileManagerCommand::RevealInFolder)
+        );
+    }
+
     #[test]
     fn resolve_footer_action_routes_to_workflow_requests() {
         assert_eq!(
@@ -8,6 +8,7 @@ use super::file_manager_app::{
     FileManagerClipboardItem,
 };
 use crate::config::{DesktopFileManagerSettings, FileManagerSortMode, FileManagerViewMode};
+use robcos_native_file_manager_app::archive::is_archive_file;
 use robcos_native_file_manager_app::{
     open_with_state_for_path, selected_file, FileManagerClipboardMode, FileManagerEditRuntime,
 };
@@ -195,6 +196,55 @@ pub fn build_file_manager_menu_section(
                 label: "Home".to_string(),
                 action: DesktopMenuAction::FileManagerCommand(FileManagerCommand::OpenHome),
             });
+            items.push(DesktopMenuItem::Action {
+                label: "Trash".to_string(),
+                action: DesktopMenuAction::FileManagerCommand(FileManagerCommand::OpenTrash),
+            });
+            if file_manager.is_trash_view() {
+                items.push(DesktopMenuItem::Action {
+                    label: "Empty Trash".to_string(),
+                    action: DesktopMenuAction::FileManagerCommand(FileManagerCommand::EmptyTrash),
+                });
+            }
+            items.push(DesktopMenuItem::Separator);
+            items.push(DesktopMenuItem::Action {
+                label: "Find Files...   Ctrl+Shift+F".to_string(),
+                action: DesktopMenuAction::FileManagerCommand(FileManagerCommand::FindFiles),
+            });
+            items.push(DesktopMenuItem::Action {
+                label: "Properties...   Alt+Enter".to_string(),
+                action: DesktopMenuAction::FileManagerCommand(FileManagerCommand::Properties),
+            });
+            items.push(DesktopMenuItem::Action {
+                label: "Checksums...".to_string(),
+                action: DesktopMenuAction::FileManagerCommand(FileManagerCommand::Checksums),
+            });
+            items.push(DesktopMenuItem::Action {
+                label: "Find Duplicates...".to_string(),
+                action: DesktopMenuAction::FileManagerCommand(FileManagerCommand::FindDuplicates),
+            });
+            items.push(DesktopMenuItem::Action {
+                label: "Add Bookmark   Ctrl+B".to_string(),
+                action: DesktopMenuAction::FileManagerCommand(FileManagerCommand::AddBookmark),
+            });
+            if let Some(search) = &file_manager.search {
+                if has_selection {
+                    items.push(DesktopMenuItem::Action {
+                        label: "Reveal in Folder".to_string(),
+                        action: DesktopMenuAction::FileManagerCommand(
+                            FileManagerCommand::RevealInFolder,
+                        ),
+                    });
+                }
+                if search.is_running() {
+                    items.push(DesktopMenuItem::Action {
+                        label: "Stop Search".to_string(),
+                        action: DesktopMenuAction::FileManagerCommand(
+                            FileManagerCommand::StopSearch,
+                        ),
+                    });
+                }
+            }
             if has_selection {
                 items.push(DesktopMenuItem::Separator);
                 items.push(DesktopMenuItem::Label {
@@ -244,10 +294,65 @@ pub fn build_file_manager_menu_section(
                         action: DesktopMenuAction::FileManagerCommand(FileManagerCommand::Move),
                     },
                     DesktopMenuItem::Action {
-                        label: "Delete".to_string(),
+                        label: if file_manager.is_trash_view() {
+                            "Delete Permanently".to_string()
+                        } else {
+                            "Delete".to_string()
+                        },
                         action: DesktopMenuAction::FileManagerCommand(FileManagerCommand::Delete),
                     },
                 ]);
+                if selection_count > 1 {
+                    items.push(DesktopMenuItem::Action {
+                        label: "Batch Rename...".to_string(),
+                        action: DesktopMenuAction::FileManagerCommand(
+                            FileManagerCommand::BatchRename,
+                        ),
+                    });
+                }
+                if fm.dual_pane {
+                    items.extend([
+                        DesktopMenuItem::Action {
+                            label: "Copy to Other Pane".to_string(),
+                            action: DesktopMenuAction::FileManagerCommand(
+                                FileManagerCommand::CopyToOtherPane,
+                            ),
+                        },
+                        DesktopMenuItem::Action {
+                            label: "Move to Other Pane".to_string(),
+                            action: DesktopMenuAction::FileManagerCommand(
+                                FileManagerCommand::MoveToOtherPane,
+                            ),
+                        },
+                    ]);
+                }
+                if file_manager.is_trash_view() {
+                    items.push(DesktopMenuItem::Action {
+                        label: "Restore".to_string(),
+                        action: DesktopMenuAction::FileManagerCommand(
+                            FileManagerCommand::RestoreFromTrash,
+                        ),
+                    });
+                }
+                if file_manager.is_archive_view() {
+                    items.push(DesktopMenuItem::Action {
+                        label: "Extract Selected".to_string(),
+                        action: DesktopMenuAction::FileManagerCommand(FileManagerCommand::Extract),
+                    });
+                } else {
+                    items.push(DesktopMenuItem::Action {
+                        label: "Compress...".to_string(),
+                        action: DesktopMenuAction::FileManagerCommand(FileManagerCommand::Compress),
+                    });
+                }
+            }
+            if file_manager.is_archive_view()
+                || matches!(selected_entries.as_slice(), [entry] if is_archive_file(&entry.path))
+            {
+                items.push(DesktopMenuItem::Action {
+                    label: "Extract All".to_string(),
+                    action: DesktopMenuAction::FileManagerCommand(FileManagerCommand::ExtractAll),
+                });
             }
             if has_clipboard {
                 items.push(DesktopMenuItem::Action {
@@ -288,6 +393,32 @@ pub fn build_file_manager_menu_section(
                         FileManagerCommand::ToggleTreePanel,
                     ),
                 },
+                DesktopMenuItem::Action {
+                    label: if fm.show_preview_panel {
+                        "Hide Preview".to_string()
+                    } else {
+                        "Show Preview".to_string()
+                    },
+                    action: DesktopMenuAction::FileManagerCommand(
+                        FileManagerCommand::TogglePreviewPanel,
+                    ),
+                },
+                DesktopMenuItem::Action {
+                    label: if fm.dual_pane {
+                        "Dual Pane [Active]".to_string()
+                    } else {
+                        "Dual Pane".to_string()
+                    },
+                    action: DesktopMenuAction::FileManagerCommand(
+                        FileManagerCommand::ToggleDualPane,
+                    ),
+                },
+                DesktopMenuItem::Action {
+                    label: "Transfers".to_string(),
+                    action: DesktopMenuAction::FileManagerCommand(
+                        FileManagerCommand::ShowTransfers,
+                    ),
+                },
                 DesktopMenuItem::Action {
                     label: if fm.view_mode == FileManagerViewMode::Grid {
                         "Grid View [Active]".to_string()
@@ -0,0 +1,176 @@
+use eframe::egui::{self, Context, TextureHandle};
+pub use robcos_native_file_manager_app::preview::{build_preview, FilePreview, PreviewTokenKind};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::SystemTime;
+
+const PREVIEW_CACHE_LIMIT: usize = 64;
+const PREVIEW_IMAGE_MAX_PX: u32 = 320;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PreviewStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl PreviewStamp {
+    fn of(path: &Path) -> Self {
+        let meta = std::fs::metadata(path).ok();
+        Self {
+            modified: meta.as_ref().and_then(|meta| meta.modified().ok()),
+            len: meta.map(|meta| meta.len()).unwrap_or(0),
+        }
+    }
+}
+
+pub struct CachedPreview {
+    pub preview: FilePreview,
+    /// Grayscale thumbnail meant to be painted tinted with the palette.
+    pub texture: Option<TextureHandle>,
+}
+
+struct PreviewResult {
+    path: PathBuf,
+    stamp: PreviewStamp,
+    preview: FilePreview,
+    image: Option<egui::ColorImage>,
+}
+
+/// Builds previews on worker threads and keeps the most recent ones until
+/// the file changes on disk.
+pub struct FilePreviewCache {
+    entries: HashMap<PathBuf, (PreviewStamp, Arc<CachedPreview>)>,
+    order: VecDeque<PathBuf>,
+    pending: HashSet<PathBuf>,
+    tx: Sender<PreviewResult>,
+    rx: Receiver<PreviewResult>,
+}
+
+impl Default for FilePreviewCache {
+    fn default() -> Self {
+        let (tx, rx) = mpsc::channel();
+        Self {
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            pending: HashSet::new(),
+            tx,
+            rx,
+        }
+    }
+}
+
+impl FilePreviewCache {
+    /// Moves finished previews into the cache, uploading image thumbnails.
+    pub fn poll(&mut self, ctx: &Context) {
+        while let Ok(result) = self.rx.try_recv() {
+            self.pending.remove(&result.path);
+            let texture = result.image.map(|image| {
+                ctx.load_texture(
+                    format!("fm_preview:{}", result.path.display()),
+                    image,
+                    egui::TextureOptions::LINEAR,
+                )
+            });
+            let cached = Arc::new(CachedPreview {
+                preview: result.preview,
+                texture,
+            });
+            if self
+                .entries
+                .insert(result.path.clone(), (result.stamp, cached))
+                .is_none()
+            {
+                self.order.push_back(result.path);
+            }
+            while self.order.len() > PREVIEW_CACHE_LIMIT {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.entries.remove(&oldest);
+                }
+            }
+        }
+        if !self.pending.is_empty() {
+            ctx.request_repaint();
+        }
+    }
+
+    /// Returns the cached preview for `path`, starting a build when there is
+    /// none or the file changed. Returns `None` while the first build runs.
+    pub fn get(&mut self, path: &Path) -> Option<Arc<CachedPreview>> {
+        let stamp = PreviewStamp::of(path);
+        let cached = self.entries.get(path);
+        if cached.is_some_and(|(cached_stamp, _)| *cached_stamp == stamp) {
+            return cached.map(|(_, preview)| preview.clone());
+        }
+        if self.pending.insert(path.to_path_buf()) {
+            let tx = self.tx.clone();
+            let path = path.to_path_buf();
+            thread::spawn(move || {
+                let preview = build_preview(&path);
+                let image = if matches!(preview, FilePreview::Image { .. }) {
+                    decode_thumbnail(&path)
+                } else {
+                    None
+                };
+                let _ = tx.send(PreviewResult {
+                    path,
+                    stamp,
+                    preview,
+                    image,
+                });
+            });
+        }
+        // Keep showing the stale preview until the new one arrives.
+        cached.map(|(_, preview)| preview.clone())
+    }
+}
+
+/// Decodes an image into a grayscale thumbnail so it can be tinted with the
+/// theme color like the wallpaper.
+fn decode_thumbnail(path: &Path) -> Option<egui::ColorImage> {
+    let bytes = std::fs::read(path).ok()?;
+    let is_svg = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+    let (width, height, pixels) = if is_svg {
+        let tree = usvg::Tree::from_data(&bytes, &usvg::Options::default()).ok()?;
+        let natural = tree.size();
+        let scale = PREVIEW_IMAGE_MAX_PX as f32 / natural.width().max(natural.height());
+        let width = ((natural.width() * scale).round() as u32).max(1);
+        let height = ((natural.height() * scale).round() as u32).max(1);
+        let mut pixmap = resvg::tiny_skia::Pixmap::new(width, height)?;
+        resvg::render(
+            &tree,
+            resvg::tiny_skia::Transform::from_scale(scale, scale),
+            &mut pixmap.as_mut(),
+        );
+        let pixels = pixmap
+            .pixels()
+            .iter()
+            .map(|pixel| {
+                let pixel = pixel.demultiply();
+                [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+            })
+            .collect::<Vec<_>>();
+        (width, height, pixels)
+    } else {
+        let image = image::load_from_memory(&bytes)
+            .ok()?
+            .thumbnail(PREVIEW_IMAGE_MAX_PX, PREVIEW_IMAGE_MAX_PX)
+            .into_rgba8();
+        let (width, height) = image.dimensions();
+        (width, height, image.pixels().map(|pixel| pixel.0).collect())
+    };
+    let mut rgba = Vec::with_capacity(pixels.len() * 4);
+    for [r, g, b, a] in pixels {
+        let luma = ((r as u16 * 77 + g as u16 * 150 + b as u16 * 29) / 256) as u8;
+        rgba.extend_from_slice(&[luma, luma, luma, a]);
+    }
+    Some(egui::ColorImage::from_rgba_unmultiplied(
+        [width as usize, height as usize],
+        &rgba,
+    ))
+}
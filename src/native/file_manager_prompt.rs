@@ -4,8 +4,10 @@ use super::prompt_flow::PromptOutcome;
 use super::shared_file_manager_settings::FileManagerSettingsUpdate;
 use crate::default_apps::parse_custom_command_line;
 use anyhow::{anyhow, Result};
+use robcos_native_file_manager_app::transfers::TransferRequest;
+use robcos_native_file_manager_app::trash::Trash;
 use robcos_native_file_manager_app::{
-    prepare_open_with_launch, FileManagerEditRuntime, OpenWithLaunchRequest,
+    default_archive_name, prepare_open_with_launch, FileManagerEditRuntime, OpenWithLaunchRequest,
 };
 use std::path::PathBuf;
 
@@ -19,6 +21,7 @@ pub enum FileManagerSelectionPromptKind {
 pub enum FileManagerPromptAction {
     Launch(OpenWithLaunchRequest),
     ApplySettingsUpdate(FileManagerSettingsUpdate),
+    QueueTransfer(TransferRequest),
     ReportStatus(String),
 }
 
@@ -45,6 +48,14 @@ pub enum FileManagerPromptResolution {
         previous: String,
         command: String,
     },
+    Compress {
+        paths: Vec<PathBuf>,
+        name: String,
+    },
+    EmptyTrash,
+    DeleteFromTrash {
+        paths: Vec<PathBuf>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -66,6 +77,14 @@ pub enum FileManagerPromptRequest {
         ext_key: String,
         previous: String,
     },
+    Compress {
+        paths: Vec<PathBuf>,
+        name: String,
+    },
+    EmptyTrash,
+    DeleteFromTrash {
+        paths: Vec<PathBuf>,
+    },
 }
 
 impl FileManagerPromptRequest {
@@ -102,6 +121,9 @@ impl FileManagerPromptRequest {
         match self {
             Self::Rename { .. } => "Rename".to_string(),
             Self::Move { .. } => "Move To".to_string(),
+            Self::Compress { .. } => "Compress".to_string(),
+            Self::EmptyTrash => "Empty Trash".to_string(),
+            Self::DeleteFromTrash { .. } => "Delete Permanently".to_string(),
             Self::OpenWithNewCommand { ext_key, .. }
             | Self::OpenWithEditCommand { ext_key, .. } => {
                 format!(
@@ -116,6 +138,27 @@ impl FileManagerPromptRequest {
         match self {
             Self::Rename { label, .. } => format!("Rename {} to:", label),
             Self::Move { .. } => "Move to (dir or full path):".to_string(),
+            Self::Compress { paths, .. } => format!(
+                "Archive name for {} item(s) (.zip, .tar, .tar.gz, .tar.xz):",
+                paths.len()
+            ),
+            Self::EmptyTrash => {
+                "Permanently delete everything in the trash, including items other programs \
+                 moved there? This cannot be undone."
+                    .to_string()
+            }
+            Self::DeleteFromTrash { paths } => match paths.as_slice() {
+                [path] => format!(
+                    "Permanently delete {}? This cannot be undone.",
+                    path.file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_else(|| path.display().to_string())
+                ),
+                _ => format!(
+                    "Permanently delete {} items? This cannot be undone.",
+                    paths.len()
+                ),
+            },
             Self::OpenWithNewCommand {
                 ext_key,
                 make_default,
@@ -141,7 +184,11 @@ impl FileManagerPromptRequest {
         match self {
             Self::Rename { label, .. } => label.clone(),
             Self::OpenWithEditCommand { previous, .. } => previous.clone(),
-            Self::Move { .. } | Self::OpenWithNewCommand { .. } => String::new(),
+            Self::Compress { name, .. } => name.clone(),
+            Self::Move { .. }
+            | Self::OpenWithNewCommand { .. }
+            | Self::EmptyTrash
+            | Self::DeleteFromTrash { .. } => String::new(),
         }
     }
 
@@ -169,9 +216,20 @@ impl FileManagerPromptRequest {
                 ext_key: ext_key.clone(),
                 previous: previous.clone(),
             },
+            Self::Compress { paths, .. } => TerminalPromptAction::FileManagerCompress {
+                paths: paths.clone(),
+            },
+            Self::EmptyTrash => TerminalPromptAction::FileManagerEmptyTrash,
+            Self::DeleteFromTrash { paths } => TerminalPromptAction::FileManagerDeleteFromTrash {
+                paths: paths.clone(),
+            },
+        };
+        let kind = match self {
+            Self::EmptyTrash | Self::DeleteFromTrash { .. } => TerminalPromptKind::Confirm,
+            _ => TerminalPromptKind::Input,
         };
         TerminalPrompt {
-            kind: TerminalPromptKind::Input,
+            kind,
             title: self.title(),
             prompt: self.prompt(),
             buffer: self.initial_buffer(),
@@ -240,6 +298,39 @@ pub fn prompt_request_for_command(
     }
 }
 
+pub fn compress_prompt_request(
+    file_manager: &NativeFileManagerState,
+) -> Result<FileManagerPromptRequest, String> {
+    let entries = file_manager.selected_rows_for_action();
+    if entries.is_empty() {
+        return Err("Select a file or folder first.".to_string());
+    }
+    Ok(FileManagerPromptRequest::Compress {
+        name: default_archive_name(&file_manager.cwd, &entries),
+        paths: entries.into_iter().map(|entry| entry.path).collect(),
+    })
+}
+
+pub fn trash_prompt_request_for_command(
+    command: FileManagerCommand,
+    file_manager: &NativeFileManagerState,
+) -> Result<FileManagerPromptRequest, String> {
+    match command {
+        FileManagerCommand::EmptyTrash => Ok(FileManagerPromptRequest::EmptyTrash),
+        _ => {
+            let paths: Vec<PathBuf> = file_manager
+                .selected_rows_for_action()
+                .into_iter()
+                .map(|row| row.path)
+                .collect();
+            if paths.is_empty() {
+                return Err("Select an item in the trash first.".to_string());
+            }
+            Ok(FileManagerPromptRequest::DeleteFromTrash { paths })
+        }
+    }
+}
+
 pub fn resolve_prompt_outcome(
     outcome: &PromptOutcome,
 ) -> Option<Result<FileManagerPromptResolution, String>> {
@@ -300,6 +391,27 @@ pub fn resolve_prompt_outcome(
                 command,
             }))
         }
+        PromptOutcome::FileManagerCompress { paths, name } => {
+            if name.trim().is_empty() {
+                return Some(Err("Compress canceled.".to_string()));
+            }
+            Some(Ok(FileManagerPromptResolution::Compress {
+                paths: paths.clone(),
+                name: name.clone(),
+            }))
+        }
+        PromptOutcome::FileManagerEmptyTrash { confirmed } => Some(if *confirmed {
+            Ok(FileManagerPromptResolution::EmptyTrash)
+        } else {
+            Err("Empty Trash canceled.".to_string())
+        }),
+        PromptOutcome::FileManagerDeleteFromTrash { paths, confirmed } => Some(if *confirmed {
+            Ok(FileManagerPromptResolution::DeleteFromTrash {
+                paths: paths.clone(),
+            })
+        } else {
+            Err("Delete canceled.".to_string())
+        }),
         _ => None,
     }
 }
@@ -379,14 +491,31 @@ pub fn apply_prompt_outcome(
                 FileManagerPromptAction::ReportStatus(open_with_updated_saved_status(&ext_key)),
             ]
         }
+        Ok(FileManagerPromptResolution::Compress { paths, name }) => {
+            match FileManagerEditRuntime::compress_request(file_manager, paths, name) {
+                Ok(request) => vec![FileManagerPromptAction::QueueTransfer(request)],
+                Err(err) => vec![FileManagerPromptAction::ReportStatus(format!(
+                    "File action failed: {err}"
+                ))],
+            }
+        }
+        Ok(FileManagerPromptResolution::EmptyTrash) => {
+            vec![FileManagerPromptAction::ReportStatus(prompt_edit_status(
+                Ok(()),
+                |_| runtime.empty_trash(file_manager, &Trash::user()),
+            ))]
+        }
+        Ok(FileManagerPromptResolution::DeleteFromTrash { paths }) => {
+            vec![FileManagerPromptAction::ReportStatus(prompt_edit_status(
+                Ok(()),
+                |_| FileManagerEditRuntime::delete_from_trash(file_manager, &paths, &Trash::user()),
+            ))]
+        }
         Err(status) => vec![FileManagerPromptAction::ReportStatus(status)],
     })
 }
 
-fn prompt_edit_status(
-    entry: Result<FileEntryRow>,
-    apply: impl FnOnce(FileEntryRow) -> Result<String>,
-) -> String {
+fn prompt_edit_status<T>(entry: Result<T>, apply: impl FnOnce(T) -> Result<String>) -> String {
     match entry.and_then(apply) {
         Ok(message) => message,
         Err(err) => format!("File action failed: {err}"),
@@ -7,17 +7,18 @@ pub use robcos_native_terminal_app::{
     resolve_embedded_pty_exit, resolve_hacking_screen_event, resolve_login_password_submission,
     resolve_login_selection_plan, resolve_main_menu_action, resolve_terminal_back_action,
     resolve_terminal_flash_action, resolve_user_password_confirm_prompt,
-    resolve_user_password_first_prompt, selectable_menu_count, terminal_command_launch_plan,
-    terminal_runtime_defaults, terminal_screen_open_plan, terminal_settings_refresh_plan,
-    terminal_shell_launch_plan, user_management_screen_for_mode, LoginMenuRow, MainMenuAction,
-    MainMenuSelectionAction, SettingsChoiceOverlay, TerminalBackAction, TerminalBackContext,
-    TerminalDesktopPtyExitPlan, TerminalEmbeddedPtyExitPlan, TerminalFlashActionPlan,
-    TerminalFlashPtyLaunchPlan, TerminalHackingPlan, TerminalHackingUiEvent,
-    TerminalLoginPasswordPlan, TerminalLoginScreenMode, TerminalLoginSelectionPlan,
-    TerminalLoginState, TerminalLoginSubmitAction, TerminalNavigationState, TerminalPtyLaunchPlan,
-    TerminalScreen, TerminalScreenOpenPlan, TerminalSelectionIndexTarget, TerminalShellSurface,
-    TerminalUserManagementPromptPlan, TerminalUserPasswordFlow, UserManagementExecutionPlan,
-    UserManagementMode, MAIN_MENU_ENTRIES,
+    resolve_user_password_first_prompt, selectable_menu_count, terminal_catalog_launch_plan,
+    terminal_command_launch_plan, terminal_runtime_defaults, terminal_screen_open_plan,
+    terminal_settings_refresh_plan, terminal_shell_launch_plan, user_management_screen_for_mode,
+    LoginMenuRow, MainMenuAction, MainMenuSelectionAction, SettingsChoiceOverlay,
+    TerminalBackAction, TerminalBackContext, TerminalDesktopPtyExitPlan,
+    TerminalEmbeddedPtyExitPlan, TerminalFlashActionPlan, TerminalFlashPtyLaunchPlan,
+    TerminalHackingPlan, TerminalHackingUiEvent, TerminalLoginPasswordPlan,
+    TerminalLoginScreenMode, TerminalLoginSelectionPlan, TerminalLoginState,
+    TerminalLoginSubmitAction, TerminalNavigationState, TerminalPtyLaunchPlan, TerminalScreen,
+    TerminalScreenOpenPlan, TerminalSelectionIndexTarget, TerminalShellSurface, TerminalTab,
+    TerminalTabId, TerminalTabs, TerminalUserManagementPromptPlan, TerminalUserPasswordFlow,
+    UserManagementExecutionPlan, UserManagementMode, MAIN_MENU_ENTRIES,
 };
 
 fn selectable_row_indices(items: &[String]) -> Vec<usize> {
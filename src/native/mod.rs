@@ -1,5 +1,8 @@
 mod about_screen;
 pub mod app;
+mod autostart_screen;
+mod command_palette;
+mod commander_screen;
 mod connections_screen;
 mod data;
 mod default_apps_screen;
@@ -12,6 +15,7 @@ mod file_manager;
 mod file_manager_app;
 mod file_manager_desktop;
 mod file_manager_menu;
+mod file_manager_preview;
 mod file_manager_prompt;
 mod hacking_screen;
 mod installer_screen;
@@ -22,13 +26,17 @@ mod prompt;
 mod prompt_flow;
 mod pty_screen;
 mod retro_ui;
+mod scheduler_screen;
 mod settings_screen;
 mod shell_screen;
+mod task_manager_screen;
 
 pub use robcos_native_services::{
-    desktop_connections_service, desktop_default_apps_service, desktop_documents_service,
-    desktop_file_service, desktop_launcher_service, desktop_search_service,
-    desktop_session_service, desktop_settings_service, desktop_shortcuts_service,
+    desktop_app_import_service, desktop_autostart_service, desktop_connections_service,
+    desktop_default_apps_service, desktop_document_index_service, desktop_documents_service,
+    desktop_file_service, desktop_history_service, desktop_launcher_service,
+    desktop_scheduler_service, desktop_search_service, desktop_session_service,
+    desktop_settings_service, desktop_shortcuts_service, desktop_spotlight_service,
     desktop_status_service, desktop_surface_service, desktop_user_service,
     shared_file_manager_settings, shared_types,
 };
@@ -1,4 +1,4 @@
-use super::edit_menus_screen::EditMenuTarget;
+use super::edit_menus_screen::{EditMenuDetailField, EditMenuTarget};
 use super::retro_ui::{current_palette, RetroScreen};
 pub use super::shared_types::FlashAction;
 use crate::config::ConnectionKind;
@@ -6,6 +6,7 @@ use crate::config::HEADER_LINES;
 use crate::connections::NetworkMenuGroup;
 use crate::default_apps::DefaultAppSlot;
 use crate::native::installer_screen::{InstallerMenuTarget, InstallerPackageAction};
+use crate::native::menu::TerminalTabId;
 use eframe::egui::{self, Align2, Context, Pos2};
 use std::path::PathBuf;
 use std::time::Instant;
@@ -72,6 +73,17 @@ pub enum TerminalPromptAction {
     },
     InstallerSearch,
     InstallerFilter,
+    TaskManagerFilter,
+    SchedulerJobName,
+    SchedulerJobTarget {
+        name: String,
+    },
+    SchedulerJobSchedule {
+        name: String,
+        target: String,
+    },
+    AutostartTarget,
+    DocumentContentSearch,
     InstallerDisplayName {
         pkg: String,
         target: InstallerMenuTarget,
@@ -91,6 +103,11 @@ pub enum TerminalPromptAction {
     EditMenuAddCategoryPath {
         name: String,
     },
+    EditMenuEntryDetail {
+        target: EditMenuTarget,
+        name: String,
+        field: EditMenuDetailField,
+    },
     FileManagerRename {
         path: PathBuf,
     },
@@ -107,11 +124,25 @@ pub enum TerminalPromptAction {
         ext_key: String,
         previous: String,
     },
+    FileManagerCompress {
+        paths: Vec<PathBuf>,
+    },
+    FileManagerEmptyTrash,
+    FileManagerDeleteFromTrash {
+        paths: Vec<PathBuf>,
+    },
     ConfirmEditMenuDelete {
         target: EditMenuTarget,
         name: String,
     },
     NewLogName,
+    RenamePtyTab {
+        tab_id: TerminalTabId,
+    },
+    ConfirmClosePtyTab {
+        tab_id: TerminalTabId,
+    },
+    ConfirmClosePtyWindow,
     Noop,
 }
 
@@ -1,9 +1,10 @@
-use super::edit_menus_screen::EditMenuTarget;
+use super::edit_menus_screen::{EditMenuDetailField, EditMenuTarget};
 use super::prompt::{TerminalPrompt, TerminalPromptAction, TerminalPromptKind};
 use crate::config::ConnectionKind;
 use crate::connections::NetworkMenuGroup;
 use crate::default_apps::DefaultAppSlot;
 use crate::native::installer_screen::{InstallerMenuTarget, InstallerPackageAction};
+use crate::native::menu::TerminalTabId;
 use eframe::egui::{self, Context, Key};
 use std::path::PathBuf;
 
@@ -65,6 +66,19 @@ pub enum PromptOutcome {
     },
     InstallerSearch(String),
     InstallerFilter(String),
+    TaskManagerFilter(String),
+    SchedulerJobName(String),
+    SchedulerJobTarget {
+        name: String,
+        target: String,
+    },
+    SchedulerJobSchedule {
+        name: String,
+        target: String,
+        schedule: String,
+    },
+    AutostartTarget(String),
+    DocumentContentSearch(String),
     InstallerDisplayName {
         pkg: String,
         target: InstallerMenuTarget,
@@ -89,6 +103,12 @@ pub enum PromptOutcome {
         name: String,
         path: String,
     },
+    EditMenuEntryDetail {
+        target: EditMenuTarget,
+        name: String,
+        field: EditMenuDetailField,
+        value: String,
+    },
     FileManagerRename {
         path: PathBuf,
         name: String,
@@ -109,12 +129,34 @@ pub enum PromptOutcome {
         previous: String,
         command: String,
     },
+    FileManagerCompress {
+        paths: Vec<PathBuf>,
+        name: String,
+    },
+    FileManagerEmptyTrash {
+        confirmed: bool,
+    },
+    FileManagerDeleteFromTrash {
+        paths: Vec<PathBuf>,
+        confirmed: bool,
+    },
     ConfirmEditMenuDelete {
         target: EditMenuTarget,
         name: String,
         confirmed: bool,
     },
     NewLogName(String),
+    RenamePtyTab {
+        tab_id: TerminalTabId,
+        name: String,
+    },
+    ConfirmClosePtyTab {
+        tab_id: TerminalTabId,
+        confirmed: bool,
+    },
+    ConfirmClosePtyWindow {
+        confirmed: bool,
+    },
     Noop,
 }
 
@@ -222,6 +264,31 @@ pub fn handle_prompt_input(ctx: &Context, mut prompt: TerminalPrompt) -> PromptO
                     TerminalPromptAction::InstallerFilter => {
                         PromptOutcome::InstallerFilter(prompt.buffer)
                     }
+                    TerminalPromptAction::TaskManagerFilter => {
+                        PromptOutcome::TaskManagerFilter(prompt.buffer)
+                    }
+                    TerminalPromptAction::SchedulerJobName => {
+                        PromptOutcome::SchedulerJobName(prompt.buffer)
+                    }
+                    TerminalPromptAction::SchedulerJobTarget { name } => {
+                        PromptOutcome::SchedulerJobTarget {
+                            name,
+                            target: prompt.buffer,
+                        }
+                    }
+                    TerminalPromptAction::SchedulerJobSchedule { name, target } => {
+                        PromptOutcome::SchedulerJobSchedule {
+                            name,
+                            target,
+                            schedule: prompt.buffer,
+                        }
+                    }
+                    TerminalPromptAction::AutostartTarget => {
+                        PromptOutcome::AutostartTarget(prompt.buffer)
+                    }
+                    TerminalPromptAction::DocumentContentSearch => {
+                        PromptOutcome::DocumentContentSearch(prompt.buffer)
+                    }
                     TerminalPromptAction::InstallerDisplayName { pkg, target } => {
                         PromptOutcome::InstallerDisplayName {
                             pkg,
@@ -251,6 +318,16 @@ pub fn handle_prompt_input(ctx: &Context, mut prompt: TerminalPrompt) -> PromptO
                             path: prompt.buffer,
                         }
                     }
+                    TerminalPromptAction::EditMenuEntryDetail {
+                        target,
+                        name,
+                        field,
+                    } => PromptOutcome::EditMenuEntryDetail {
+                        target,
+                        name,
+                        field,
+                        value: prompt.buffer,
+                    },
                     TerminalPromptAction::FileManagerRename { path } => {
                         PromptOutcome::FileManagerRename {
                             path,
@@ -283,12 +360,26 @@ pub fn handle_prompt_input(ctx: &Context, mut prompt: TerminalPrompt) -> PromptO
                         previous,
                         command: prompt.buffer,
                     },
+                    TerminalPromptAction::FileManagerCompress { paths } => {
+                        PromptOutcome::FileManagerCompress {
+                            paths,
+                            name: prompt.buffer,
+                        }
+                    }
                     TerminalPromptAction::NewLogName => PromptOutcome::NewLogName(prompt.buffer),
+                    TerminalPromptAction::RenamePtyTab { tab_id } => PromptOutcome::RenamePtyTab {
+                        tab_id,
+                        name: prompt.buffer,
+                    },
                     TerminalPromptAction::Noop => PromptOutcome::Noop,
                     TerminalPromptAction::ConfirmDeleteUser { .. }
                     | TerminalPromptAction::ConfirmToggleAdmin { .. }
                     | TerminalPromptAction::ConfirmInstallerAction { .. }
-                    | TerminalPromptAction::ConfirmEditMenuDelete { .. } => PromptOutcome::Cancel,
+                    | TerminalPromptAction::ConfirmEditMenuDelete { .. }
+                    | TerminalPromptAction::FileManagerEmptyTrash
+                    | TerminalPromptAction::FileManagerDeleteFromTrash { .. }
+                    | TerminalPromptAction::ConfirmClosePtyTab { .. }
+                    | TerminalPromptAction::ConfirmClosePtyWindow => PromptOutcome::Cancel,
                 };
             }
             PromptOutcome::Continue(prompt)
@@ -331,6 +422,28 @@ pub fn handle_prompt_input(ctx: &Context, mut prompt: TerminalPrompt) -> PromptO
                             confirmed: prompt.confirm_yes,
                         }
                     }
+                    TerminalPromptAction::FileManagerEmptyTrash => {
+                        PromptOutcome::FileManagerEmptyTrash {
+                            confirmed: prompt.confirm_yes,
+                        }
+                    }
+                    TerminalPromptAction::FileManagerDeleteFromTrash { paths } => {
+                        PromptOutcome::FileManagerDeleteFromTrash {
+                            paths,
+                            confirmed: prompt.confirm_yes,
+                        }
+                    }
+                    TerminalPromptAction::ConfirmClosePtyTab { tab_id } => {
+                        PromptOutcome::ConfirmClosePtyTab {
+                            tab_id,
+                            confirmed: prompt.confirm_yes,
+                        }
+                    }
+                    TerminalPromptAction::ConfirmClosePtyWindow => {
+                        PromptOutcome::ConfirmClosePtyWindow {
+                            confirmed: prompt.confirm_yes,
+                        }
+                    }
                     _ => PromptOutcome::Noop,
                 };
             }
@@ -1,12 +1,16 @@
-use super::menu::TerminalScreen;
+use super::menu::{TerminalScreen, TerminalTab, TerminalTabs};
 use super::retro_ui::{
     current_palette, RetroPalette, RetroScreen, FIXED_PTY_CELL_H, FIXED_PTY_CELL_W,
 };
-use crate::pty::{PtyLaunchOptions, PtySession, PtyStyledCell};
+use crate::pty::{
+    cursor_blink_on, until_next_cursor_blink, CommittedFrame, PtyInput, PtyLaunchOptions,
+    PtySession, PtyStyledCell,
+};
 use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEventKind};
 use eframe::egui::{self, Align2, Color32, Context, FontId, Key, Pos2, Rect, Stroke};
 use ratatui::style::Color;
-use std::time::{Duration, Instant};
+use std::sync::Arc;
+use std::time::Instant;
 
 const MAX_NATIVE_PTY_COLS: usize = 240;
 const MAX_NATIVE_PTY_ROWS: usize = 80;
@@ -34,12 +38,84 @@ pub struct NativePtyState {
     pub fixed_cell_h: Option<f32>,
     pub fixed_font_scale: Option<f32>,
     pub fixed_font_width_divisor: Option<f32>,
-    prev_plain_lines: Vec<String>,
-    prev_plain_cursor: Option<(u16, u16)>,
+    frame: CommittedFrame,
+    row_shapes: RowShapeCache,
     plain_texture: PlainTextureRenderer,
     perf: PtyPerfStats,
     pub show_perf_overlay: bool,
-    idle_frames: u32,
+    /// Input sent to the session by the last draw, for broadcast mirroring.
+    pub sent_input: Vec<PtyInput>,
+}
+
+/// Tabs of the desktop terminal window.
+pub type NativePtyTabs = TerminalTabs<NativePtyState>;
+
+/// Label for a desktop terminal tab, following the child's OSC 0/2 title.
+pub fn native_pty_tab_title(tab: &TerminalTab<NativePtyState>) -> String {
+    tab.display_title(tab.state.session.window_title().as_deref())
+}
+
+/// Layout inputs that invalidate every cached row when they change.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct RowLayoutKey {
+    plain: bool,
+    smooth: bool,
+    cols: usize,
+    row_offset: usize,
+    cell: egui::Vec2,
+    font_size: f32,
+    fg: Color32,
+    bg: Color32,
+}
+
+struct CachedRowShapes {
+    epoch: u64,
+    origin: Pos2,
+    shapes: Vec<egui::Shape>,
+}
+
+/// Shaped rows keyed by the committed frame's row epochs.  Unchanged rows
+/// are replayed (translated if the window moved) instead of re-laid out.
+#[derive(Default)]
+struct RowShapeCache {
+    key: Option<RowLayoutKey>,
+    rows: Vec<Option<CachedRowShapes>>,
+}
+
+impl RowShapeCache {
+    fn prepare(&mut self, key: RowLayoutKey, rows: usize) {
+        if self.key != Some(key) {
+            self.key = Some(key);
+            self.rows.clear();
+        }
+        self.rows.resize_with(rows, || None);
+    }
+
+    /// Cached shapes for `row` if its epoch still matches, moved to `origin`.
+    fn get(&self, row: usize, epoch: u64, origin: Pos2) -> Option<Vec<egui::Shape>> {
+        let cached = self.rows.get(row)?.as_ref()?;
+        if cached.epoch != epoch {
+            return None;
+        }
+        let mut shapes = cached.shapes.clone();
+        let delta = origin - cached.origin;
+        if delta != egui::Vec2::ZERO {
+            for shape in &mut shapes {
+                shape.translate(delta);
+            }
+        }
+        Some(shapes)
+    }
+
+    fn put(&mut self, row: usize, epoch: u64, origin: Pos2, shapes: Vec<egui::Shape>) {
+        if let Some(slot) = self.rows.get_mut(row) {
+            *slot = Some(CachedRowShapes {
+                epoch,
+                origin,
+                shapes,
+            });
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -104,6 +180,7 @@ struct PlainTextureRenderer {
     cell_w_px: usize,
     cell_h_px: usize,
     font_px: f32,
+    row_epochs: Vec<u64>,
 }
 
 impl Default for PlainTextureRenderer {
@@ -117,6 +194,7 @@ impl Default for PlainTextureRenderer {
             cell_w_px: 0,
             cell_h_px: 0,
             font_px: 0.0,
+            row_epochs: Vec::new(),
         }
     }
 }
@@ -172,12 +250,12 @@ pub fn spawn_embedded_pty_with_options(
         fixed_cell_h: None,
         fixed_font_scale: None,
         fixed_font_width_divisor: None,
-        prev_plain_lines: Vec::new(),
-        prev_plain_cursor: None,
+        frame: CommittedFrame::blank(0, 0),
+        row_shapes: RowShapeCache::default(),
         plain_texture: PlainTextureRenderer::default(),
         perf: PtyPerfStats::default(),
         show_perf_overlay: false,
-        idle_frames: 0,
+        sent_input: Vec::new(),
     })
 }
 
@@ -287,25 +365,24 @@ pub fn draw_embedded_pty_in_ui_sized(
     // built-in guard (no-op if same) so this is safe to call every frame.
     state.session.resize(pty_cols, pty_rows);
     let input_activity = if focused {
-        handle_pty_input(ctx, &mut state.session)
+        let inputs = handle_pty_input(ctx, &mut state.session);
+        let had_input = !inputs.is_empty();
+        state.sent_input.extend(inputs);
+        had_input
     } else {
         false
     };
     let input_ms = input_started.elapsed().as_secs_f32() * 1000.0;
     let output_activity = state.session.take_output_activity();
-    // Always repaint at 60fps while PTY is alive.  Activity detection is
-    // still used for perf overlay stats, but the repaint cadence is fixed
-    // so we never miss frames or show half-updated ncurses screens.
-    // When there's been no activity for a while, drop to a slower cadence.
-    let idle_secs = if !input_activity && !output_activity {
-        state.idle_frames = state.idle_frames.saturating_add(1);
-        state.idle_frames as f32 / 60.0
-    } else {
-        state.idle_frames = 0;
-        0.0
-    };
-    let tick_ms = if idle_secs > 2.0 { 200 } else { 16 };
-    ctx.request_repaint_after(Duration::from_millis(tick_ms));
+    // Repaint is event driven: the reader thread wakes egui when it commits
+    // new output, input events repaint on their own, and the only timer is
+    // the cursor blink.  An idle PTY window therefore costs nothing.
+    if !state.session.has_output_waker() {
+        let waker_ctx = ctx.clone();
+        state
+            .session
+            .set_output_waker(Arc::new(move || waker_ctx.request_repaint()));
+    }
 
     if !state.session.is_alive() {
         return PtyScreenEvent::ProcessExited;
@@ -314,7 +391,6 @@ pub fn draw_embedded_pty_in_ui_sized(
         crate::config::get_settings().cli_acs_mode,
         crate::config::CliAcsMode::Unicode
     );
-    let mut snapshot_ms = 0.0f32;
     let mut dirty_stats = DirtyStats {
         total_rows: pty_rows as usize,
         total_cells: pty_rows as usize * pty_cols as usize,
@@ -370,12 +446,16 @@ pub fn draw_embedded_pty_in_ui_sized(
         );
     }
 
-    // ── Fetch committed display frame ──────────────────────────────────
-    // The reader thread builds this snapshot after each coalesced I/O
-    // batch, so it's guaranteed to be consistent (no mid-update tears).
+    // ── Sync committed display frame ───────────────────────────────────
+    // The reader thread commits damaged rows after each coalesced I/O
+    // batch; only rows whose epoch moved are copied into our mirror.
     let started = Instant::now();
-    let frame = state.session.committed_frame();
-    snapshot_ms += started.elapsed().as_secs_f32() * 1000.0;
+    let synced_rows = state.session.sync_frame(&mut state.frame);
+    let snapshot_ms = started.elapsed().as_secs_f32() * 1000.0;
+    if let Some(rows) = synced_rows {
+        dirty_stats.changed_rows = rows;
+        dirty_stats.changed_cells = rows * pty_cols as usize;
+    }
 
     let plain_fast = state.session.prefers_plain_render();
 
@@ -390,227 +470,104 @@ pub fn draw_embedded_pty_in_ui_sized(
     let content_painter = painter.with_clip_rect(content_rect);
 
     // Clamp iteration to the minimum of committed frame and display dims.
-    let render_cols = (frame.cols as usize).min(pty_cols as usize);
-    let render_rows_count = (frame.rows as usize).min(pty_rows as usize);
-
-    if plain_fast {
-        let snap = &frame.plain;
-        let smoothed_lines = if smooth_borders && plain_lines_have_ascii_borders(&snap.lines) {
-            let mut lines = snap.lines.clone();
-            smooth_ascii_borders_in_plain_lines(&mut lines);
-            Some(lines)
-        } else {
-            None
-        };
-        let lines_ref: &[String] = smoothed_lines.as_deref().unwrap_or(&snap.lines);
-        let dirty_rows = collect_dirty_rows(&state.prev_plain_lines, lines_ref, render_rows_count);
-        if state.show_perf_overlay {
-            dirty_stats = diff_plain_snapshot(
-                &state.prev_plain_lines,
-                lines_ref,
-                state.prev_plain_cursor,
-                (snap.cursor_row, snap.cursor_col),
-                render_cols,
-                render_rows_count,
-            );
-        }
-        let mut row_requires_cell_draw = vec![false; render_rows_count];
-        for (row_idx, row) in frame
-            .styled
-            .cells
-            .iter()
-            .enumerate()
-            .take(render_rows_count)
-        {
-            row_requires_cell_draw[row_idx] = row.iter().take(render_cols).any(|cell| {
-                let (fg, _bg) = resolve_cell_colors(*cell);
-                cell.ch != ' ' && (fg != palette.fg || cell.bold || cell.italic || cell.underline)
-            });
-        }
-        let use_texture = std::env::var("ROBCOS_NATIVE_PTY_TEXTURE")
+    let render_cols = (state.frame.cols as usize).min(pty_cols as usize);
+    let render_rows_count = (state.frame.rows as usize).min(pty_rows as usize);
+    let glyph_advance = screen.row_rect(0, row_offset, 1).width().max(1.0);
+    let x_origin = content_rect.left();
+
+    let use_texture = plain_fast
+        && std::env::var("ROBCOS_NATIVE_PTY_TEXTURE")
             .ok()
             .map(|v| matches!(v.as_str(), "1" | "true" | "TRUE" | "on" | "ON"))
             .unwrap_or(false);
-        let allow_texture = !row_requires_cell_draw.iter().any(|needs| *needs);
-        let texture_drawn = use_texture
-            && allow_texture
-            && render_plain_texture_if_possible(
-                state,
-                ctx,
-                &content_painter,
-                &screen,
-                content_rect,
-                &palette,
-                lines_ref,
-                dirty_rows.as_slice(),
-            );
-        let glyph_advance = screen.row_rect(0, row_offset, 1).width().max(1.0);
-        let x_origin = content_rect.left();
-        // Background colors from the styled cells of the committed frame.
-        for (row_idx, row) in frame
-            .styled
-            .cells
-            .iter()
-            .enumerate()
-            .take(render_rows_count)
-        {
-            for (col_idx, cell) in row.iter().enumerate().take(render_cols) {
-                let (_fg, bg) = resolve_cell_colors(*cell);
-                if bg == palette.bg {
-                    continue;
-                }
-                let x = screen.snap_value(x_origin + col_idx as f32 * glyph_advance);
-                let rect = screen.text_band_rect(
-                    row_idx + row_offset,
-                    x,
-                    screen.snap_value(glyph_advance.ceil()),
-                );
-                content_painter.rect_filled(rect, 0.0, bg);
-            }
-        }
-        if !texture_drawn {
-            for (row_idx, line) in lines_ref.iter().enumerate().take(render_rows_count) {
-                if row_requires_cell_draw[row_idx] {
-                    continue;
-                }
-                let clipped: String = line
-                    .trim_end_matches(' ')
-                    .chars()
-                    .take(render_cols)
-                    .collect();
-                if clipped.is_empty() {
-                    continue;
-                }
-                let y = screen.row_text_top(row_idx + row_offset);
-                content_painter.text(
-                    Pos2::new(x_origin, y),
-                    Align2::LEFT_TOP,
-                    clipped,
-                    screen.font().clone(),
-                    palette.fg,
-                );
-            }
-        }
-        for (row_idx, row) in frame
-            .styled
-            .cells
-            .iter()
-            .enumerate()
-            .take(render_rows_count)
-        {
-            let force_row_cells = row_requires_cell_draw[row_idx];
-            for (col_idx, cell) in row.iter().enumerate().take(render_cols) {
-                let (fg, _bg) = resolve_cell_colors(*cell);
-                if cell.ch == ' ' {
-                    continue;
-                }
-                if !force_row_cells
-                    && fg == palette.fg
-                    && !cell.bold
-                    && !cell.italic
-                    && !cell.underline
-                {
-                    continue;
-                }
-                let x = screen.snap_value(x_origin + col_idx as f32 * glyph_advance);
-                let y = screen.row_text_top(row_idx + row_offset);
-                content_painter.text(
-                    Pos2::new(x, y),
-                    Align2::LEFT_TOP,
-                    cell.ch.to_string(),
-                    screen.font().clone(),
-                    fg,
-                );
-                if cell.bold {
-                    content_painter.text(
-                        Pos2::new(x + 0.7, y),
-                        Align2::LEFT_TOP,
-                        cell.ch.to_string(),
-                        screen.font().clone(),
-                        fg,
-                    );
-                }
-                if cell.underline {
-                    let rect = screen.text_band_rect(
-                        row_idx + row_offset,
-                        x,
-                        screen.snap_value(glyph_advance.ceil()),
-                    );
-                    let uy = rect.bottom() - 2.0;
-                    content_painter.line_segment(
-                        [Pos2::new(rect.left(), uy), Pos2::new(rect.right(), uy)],
-                        Stroke::new(1.0, fg),
-                    );
-                }
-            }
-        }
-        state.prev_plain_lines = lines_ref[..render_rows_count.min(lines_ref.len())].to_vec();
-        state.prev_plain_cursor = Some((snap.cursor_row, snap.cursor_col));
-        if !snap.cursor_hidden {
-            let row = snap.cursor_row as usize + row_offset;
-            let col = snap.cursor_col as usize;
-            let cursor_x = screen.snap_value(x_origin + col as f32 * glyph_advance);
-            let cursor_rect =
-                screen.text_band_rect(row, cursor_x, screen.snap_value(glyph_advance.ceil()));
-            let ch = lines_ref
-                .get(row.saturating_sub(row_offset))
-                .and_then(|line| line.chars().nth(col))
-                .unwrap_or(' ');
-            content_painter.rect_filled(cursor_rect, 0.0, palette.fg);
-            if ch != ' ' {
-                content_painter.text(
-                    Pos2::new(cursor_x, screen.row_text_top(row)),
-                    Align2::LEFT_TOP,
-                    ch.to_string(),
-                    screen.font().clone(),
-                    palette.bg,
-                );
-            }
-        }
-    } else {
-        let snapshot = &frame.styled;
+    let texture_drawn = use_texture
+        && draw_plain_texture_rows(
+            state,
+            ctx,
+            &content_painter,
+            &screen,
+            content_rect,
+            &palette,
+            smooth_borders,
+            render_cols,
+            render_rows_count,
+        );
 
-        if state.show_perf_overlay {
-            dirty_stats.changed_rows = dirty_stats.total_rows;
-            dirty_stats.changed_cells = dirty_stats.total_cells;
-        }
-        state.prev_plain_lines.clear();
-        state.prev_plain_cursor = None;
-        for (row_idx, row) in snapshot.cells.iter().enumerate().take(render_rows_count) {
-            for (col_idx, cell) in row.iter().enumerate().take(render_cols) {
-                let mut cell_to_draw = *cell;
-                if smooth_borders {
-                    cell_to_draw.ch = smooth_border_char_from_snapshot(
-                        &snapshot.cells,
-                        row_idx,
-                        col_idx,
-                        cell.ch,
-                    );
-                }
-                let border_conn = if smooth_borders {
-                    vector_border_connections(&snapshot.cells, row_idx, col_idx, cell_to_draw.ch)
-                } else {
-                    None
-                };
-                draw_cell(
-                    &screen,
-                    &content_painter,
-                    col_idx,
-                    row_idx + row_offset,
-                    &cell_to_draw,
-                    border_conn,
-                );
+    if !texture_drawn {
+        state.row_shapes.prepare(
+            RowLayoutKey {
+                plain: plain_fast,
+                smooth: smooth_borders,
+                cols: render_cols,
+                row_offset,
+                cell: screen.row_rect(0, row_offset, 1).size(),
+                font_size: screen.font().size,
+                fg: palette.fg,
+                bg: palette.bg,
+            },
+            render_rows_count,
+        );
+        let origin = screen.rect.min;
+        let mut smoothed_lines: Option<Vec<String>> = None;
+        for row_idx in 0..render_rows_count {
+            let epoch = state.frame.row_epochs[row_idx];
+            if let Some(shapes) = state.row_shapes.get(row_idx, epoch, origin) {
+                content_painter.extend(shapes);
+                continue;
             }
+            let shapes = if plain_fast {
+                let lines = smoothed_lines.get_or_insert_with(|| {
+                    let mut lines = state.frame.plain.lines.clone();
+                    if smooth_borders && plain_lines_have_ascii_borders(&lines) {
+                        smooth_ascii_borders_in_plain_lines(&mut lines);
+                    }
+                    lines
+                });
+                ctx.fonts(|fonts| {
+                    plain_row_shapes(
+                        fonts,
+                        &screen,
+                        &palette,
+                        row_idx,
+                        row_offset,
+                        x_origin,
+                        glyph_advance,
+                        &lines[row_idx],
+                        &state.frame.styled.cells[row_idx],
+                        render_cols,
+                    )
+                })
+            } else {
+                ctx.fonts(|fonts| {
+                    styled_row_shapes(
+                        fonts,
+                        &screen,
+                        &state.frame.styled.cells,
+                        row_idx,
+                        row_offset,
+                        render_cols,
+                        smooth_borders,
+                    )
+                })
+            };
+            content_painter.extend(shapes.iter().cloned());
+            state.row_shapes.put(row_idx, epoch, origin, shapes);
         }
+    }
 
-        if !snapshot.cursor_hidden {
+    let snapshot = &state.frame.styled;
+    if !snapshot.cursor_hidden && (snapshot.cursor_row as usize) < render_rows_count {
+        ctx.request_repaint_after(until_next_cursor_blink());
+        if cursor_blink_on() {
             let row = snapshot.cursor_row as usize + row_offset;
             let col = snapshot.cursor_col as usize;
-            let cursor_rect = screen.row_rect(col, row, 1);
+            let cursor_x = screen.snap_value(x_origin + col as f32 * glyph_advance);
+            let cursor_rect =
+                screen.text_band_rect(row, cursor_x, screen.snap_value(glyph_advance.ceil()));
             let cell = snapshot
                 .cells
                 .get(row.saturating_sub(row_offset))
-                .and_then(|line| line.get(snapshot.cursor_col as usize))
+                .and_then(|line| line.get(col))
                 .copied()
                 .unwrap_or(PtyStyledCell {
                     ch: ' ',
@@ -621,17 +578,19 @@ pub fn draw_embedded_pty_in_ui_sized(
                     underline: false,
                     reversed: false,
                 });
-            let (cursor_fg, cursor_bg) = resolve_cell_colors(cell);
-            let fill = cursor_fg;
-            let text_color = cursor_bg;
-            content_painter.rect_filled(cursor_rect, 0.0, fill);
+            let (cursor_fg, cursor_bg) = if plain_fast {
+                (palette.fg, palette.bg)
+            } else {
+                resolve_cell_colors(cell)
+            };
+            content_painter.rect_filled(cursor_rect, 0.0, cursor_fg);
             if cell.ch != ' ' {
                 content_painter.text(
-                    cursor_rect.left_top(),
+                    Pos2::new(cursor_x, screen.row_text_top(row)),
                     Align2::LEFT_TOP,
                     cell.ch.to_string(),
                     screen.font().clone(),
-                    text_color,
+                    cursor_bg,
                 );
             }
         }
@@ -658,9 +617,140 @@ pub fn draw_embedded_pty_in_ui_sized(
     PtyScreenEvent::None
 }
 
-fn draw_cell(
+/// Shapes for one row in fast plain mode: background runs, then either the
+/// whole line as one text shape or per-cell glyphs when the row carries
+/// color or attributes.
+#[allow(clippy::too_many_arguments)]
+fn plain_row_shapes(
+    fonts: &egui::epaint::Fonts,
     screen: &RetroScreen,
-    painter: &egui::Painter,
+    palette: &RetroPalette,
+    row_idx: usize,
+    row_offset: usize,
+    x_origin: f32,
+    glyph_advance: f32,
+    line: &str,
+    cells: &[PtyStyledCell],
+    render_cols: usize,
+) -> Vec<egui::Shape> {
+    let mut shapes = Vec::new();
+    let requires_cell_draw = cells.iter().take(render_cols).any(|cell| {
+        let (fg, _bg) = resolve_cell_colors(*cell);
+        cell.ch != ' ' && (fg != palette.fg || cell.bold || cell.italic || cell.underline)
+    });
+    for (col_idx, cell) in cells.iter().enumerate().take(render_cols) {
+        let (_fg, bg) = resolve_cell_colors(*cell);
+        if bg == palette.bg {
+            continue;
+        }
+        let x = screen.snap_value(x_origin + col_idx as f32 * glyph_advance);
+        let rect = screen.text_band_rect(
+            row_idx + row_offset,
+            x,
+            screen.snap_value(glyph_advance.ceil()),
+        );
+        shapes.push(egui::Shape::rect_filled(rect, 0.0, bg));
+    }
+    let y = screen.row_text_top(row_idx + row_offset);
+    if !requires_cell_draw {
+        let clipped: String = line
+            .trim_end_matches(' ')
+            .chars()
+            .take(render_cols)
+            .collect();
+        if !clipped.is_empty() {
+            shapes.push(egui::Shape::text(
+                fonts,
+                Pos2::new(x_origin, y),
+                Align2::LEFT_TOP,
+                clipped,
+                screen.font().clone(),
+                palette.fg,
+            ));
+        }
+        return shapes;
+    }
+    for (col_idx, cell) in cells.iter().enumerate().take(render_cols) {
+        if cell.ch == ' ' {
+            continue;
+        }
+        let (fg, _bg) = resolve_cell_colors(*cell);
+        let x = screen.snap_value(x_origin + col_idx as f32 * glyph_advance);
+        shapes.push(egui::Shape::text(
+            fonts,
+            Pos2::new(x, y),
+            Align2::LEFT_TOP,
+            cell.ch.to_string(),
+            screen.font().clone(),
+            fg,
+        ));
+        if cell.bold {
+            shapes.push(egui::Shape::text(
+                fonts,
+                Pos2::new(x + 0.7, y),
+                Align2::LEFT_TOP,
+                cell.ch.to_string(),
+                screen.font().clone(),
+                fg,
+            ));
+        }
+        if cell.underline {
+            let rect = screen.text_band_rect(
+                row_idx + row_offset,
+                x,
+                screen.snap_value(glyph_advance.ceil()),
+            );
+            let uy = rect.bottom() - 2.0;
+            shapes.push(egui::Shape::line_segment(
+                [Pos2::new(rect.left(), uy), Pos2::new(rect.right(), uy)],
+                Stroke::new(1.0, fg),
+            ));
+        }
+    }
+    shapes
+}
+
+/// Shapes for one row in styled mode, one cell at a time.
+fn styled_row_shapes(
+    fonts: &egui::epaint::Fonts,
+    screen: &RetroScreen,
+    cells: &[Vec<PtyStyledCell>],
+    row_idx: usize,
+    row_offset: usize,
+    render_cols: usize,
+    smooth_borders: bool,
+) -> Vec<egui::Shape> {
+    let mut shapes = Vec::new();
+    let Some(row) = cells.get(row_idx) else {
+        return shapes;
+    };
+    for (col_idx, cell) in row.iter().enumerate().take(render_cols) {
+        let mut cell_to_draw = *cell;
+        if smooth_borders {
+            cell_to_draw.ch = smooth_border_char_from_snapshot(cells, row_idx, col_idx, cell.ch);
+        }
+        let border_conn = if smooth_borders {
+            vector_border_connections(cells, row_idx, col_idx, cell_to_draw.ch)
+        } else {
+            None
+        };
+        cell_shapes(
+            fonts,
+            screen,
+            &mut shapes,
+            col_idx,
+            row_idx + row_offset,
+            &cell_to_draw,
+            border_conn,
+        );
+    }
+    shapes
+}
+
+fn cell_shapes(
+    fonts: &egui::epaint::Fonts,
+    screen: &RetroScreen,
+    shapes: &mut Vec<egui::Shape>,
     col: usize,
     row: usize,
     cell: &PtyStyledCell,
@@ -669,35 +759,37 @@ fn draw_cell(
     let rect = screen.row_rect(col, row, 1);
     let (fg, bg) = resolve_cell_colors(*cell);
     if bg != Color32::BLACK {
-        painter.rect_filled(rect, 0.0, bg);
+        shapes.push(egui::Shape::rect_filled(rect, 0.0, bg));
     }
     if let Some(conn) = border_conn {
-        draw_vector_border_cell(painter, rect, conn, fg, cell.bold);
+        vector_border_shapes(shapes, rect, conn, fg, cell.bold);
     } else if cell.ch != ' ' {
         let italic_x = if cell.italic { 0.25 } else { 0.0 };
-        painter.text(
+        shapes.push(egui::Shape::text(
+            fonts,
             Pos2::new(rect.left() + italic_x, rect.top()),
             Align2::LEFT_TOP,
             cell.ch.to_string(),
             screen.font().clone(),
             fg,
-        );
+        ));
         if cell.bold {
-            painter.text(
+            shapes.push(egui::Shape::text(
+                fonts,
                 Pos2::new(rect.left() + 0.7, rect.top()),
                 Align2::LEFT_TOP,
                 cell.ch.to_string(),
                 screen.font().clone(),
                 fg,
-            );
+            ));
         }
     }
     if cell.underline {
         let y = rect.bottom() - 2.0;
-        painter.line_segment(
+        shapes.push(egui::Shape::line_segment(
             [Pos2::new(rect.left(), y), Pos2::new(rect.right(), y)],
             egui::Stroke::new(1.0, fg),
-        );
+        ));
     }
 }
 
@@ -957,36 +1049,46 @@ mod tests {
     }
 }
 
-pub fn handle_pty_input(ctx: &Context, session: &mut PtySession) -> bool {
-    let mut had_input = false;
+/// Sends this frame's keyboard and clipboard input to `session` and returns
+/// what was sent, so broadcast mode can mirror it to other sessions.
+pub fn handle_pty_input(ctx: &Context, session: &mut PtySession) -> Vec<PtyInput> {
+    let mut inputs = Vec::new();
     let events = ctx.input(|i| i.events.clone());
     // Track whether any Event::Text arrived this frame.  When it does
     // (e.g. terminal mode where the CentralPanel is focused), we skip
     // the Key→char fallback to avoid double-sending characters.
     let had_text_event = events.iter().any(|e| matches!(e, egui::Event::Text(_)));
+    // With kitty "report all keys" every printable key goes out as an escape
+    // code from its Key event, so the matching Text event must be dropped.
+    let report_all_keys = session.kitty_keyboard_flags() & crate::pty::KITTY_REPORT_ALL_KEYS != 0;
+    let had_printable_key = events.iter().any(|e| {
+        matches!(
+            e,
+            egui::Event::Key { key, pressed: true, .. } if key_to_char(*key, false).is_some()
+        )
+    });
     for event in events {
         match event {
             egui::Event::Paste(text) => {
                 if !text.is_empty() {
-                    session.send_paste(&text);
-                    had_input = true;
+                    inputs.push(PtyInput::Paste(text));
                 }
             }
             egui::Event::Text(text) => {
+                if report_all_keys && had_printable_key {
+                    continue;
+                }
                 if !text.is_empty() {
-                    session.write(text.as_bytes());
-                    had_input = true;
+                    inputs.push(PtyInput::Text(text));
                 }
             }
             // egui intercepts Ctrl+X/C as Cut/Copy before emitting Key events.
-            // Convert them back to control bytes for the PTY.
+            // Convert them back to key presses for the PTY.
             egui::Event::Cut => {
-                session.write(&[0x18]); // Ctrl+X
-                had_input = true;
+                inputs.push(PtyInput::key(KeyCode::Char('x'), KeyModifiers::CONTROL));
             }
             egui::Event::Copy => {
-                session.write(&[0x03]); // Ctrl+C
-                had_input = true;
+                inputs.push(PtyInput::key(KeyCode::Char('c'), KeyModifiers::CONTROL));
             }
             egui::Event::Key {
                 key,
@@ -1001,116 +1103,34 @@ pub fn handle_pty_input(ctx: &Context, session: &mut PtySession) -> bool {
                     ctx.send_viewport_cmd(egui::ViewportCommand::RequestPaste);
                     continue;
                 }
-                // ── Direct byte writing for all keys ─────────────────
-                // We write PTY bytes directly rather than going through
-                // send_key(), which locks the parser to check application
-                // cursor mode.  Only arrow keys actually need that check.
-
-                // Ctrl+letter → control byte (0x01..0x1A)
-                if modifiers.ctrl && !modifiers.alt {
-                    if let Some(ch) = key_to_char(key, false) {
-                        let lc = ch.to_ascii_lowercase();
-                        if lc.is_ascii_lowercase() {
-                            let ctrl_byte = (lc as u8) - b'a' + 1;
-                            session.write(&[ctrl_byte]);
-                            had_input = true;
-                            continue;
-                        }
-                    }
-                }
-
-                // Escape → ESC byte
-                if key == Key::Escape {
-                    session.write(b"\x1b");
-                    had_input = true;
-                    continue;
-                }
 
-                // Simple control keys that don't depend on terminal mode
-                match key {
-                    Key::Enter => {
-                        session.write(b"\r");
-                        had_input = true;
-                        continue;
-                    }
-                    Key::Tab => {
-                        session.write(b"\t");
-                        had_input = true;
-                        continue;
-                    }
-                    Key::Backspace => {
-                        session.write(b"\x7f");
-                        had_input = true;
-                        continue;
-                    }
-                    Key::Delete => {
-                        session.write(b"\x1b[3~");
-                        had_input = true;
-                        continue;
-                    }
-                    Key::Home => {
-                        session.write(b"\x1b[H");
-                        had_input = true;
-                        continue;
-                    }
-                    Key::End => {
-                        session.write(b"\x1b[F");
-                        had_input = true;
-                        continue;
-                    }
-                    Key::PageUp => {
-                        session.write(b"\x1b[5~");
-                        had_input = true;
-                        continue;
-                    }
-                    Key::PageDown => {
-                        session.write(b"\x1b[6~");
-                        had_input = true;
-                        continue;
-                    }
-                    Key::Insert => {
-                        session.write(b"\x1b[2~");
-                        had_input = true;
-                        continue;
-                    }
-                    _ => {}
-                }
-
-                // Arrow keys — these need application_cursor check via send_key
-                if matches!(
-                    key,
-                    Key::ArrowUp | Key::ArrowDown | Key::ArrowLeft | Key::ArrowRight
-                ) {
-                    if let Some((code, mods)) = map_key_event(key, modifiers) {
-                        session.send_key(code, mods);
-                        had_input = true;
-                    }
-                    continue;
-                }
-
-                // Printable key fallback (desktop mode: no Event::Text without focus)
-                if !modifiers.ctrl && !modifiers.alt && !modifiers.command {
-                    if !had_text_event {
-                        if let Some(ch) = key_to_char(key, modifiers.shift) {
-                            let mut tmp = [0u8; 4];
-                            let s = ch.encode_utf8(&mut tmp);
-                            session.write(s.as_bytes());
-                            had_input = true;
+                // Plain printable keys normally arrive as Event::Text; the
+                // Key event is only a fallback (desktop mode: no Event::Text
+                // without focus) unless the child wants every key encoded.
+                let plain = !modifiers.ctrl && !modifiers.alt && !modifiers.command;
+                if plain && !report_all_keys {
+                    if let Some(ch) = key_to_char(key, modifiers.shift) {
+                        if !had_text_event {
+                            inputs.push(PtyInput::Text(ch.to_string()));
                         }
+                        continue;
                     }
-                    continue;
                 }
 
-                // Fallback for anything else (Alt+key, etc.)
+                // Everything else goes through the session's key encoder so
+                // launch options (Alt-as-Meta, backspace) and the modes the
+                // child negotiated (DECCKM, kitty keyboard) apply.
                 if let Some((code, mods)) = map_key_event(key, modifiers) {
-                    session.send_key(code, mods);
-                    had_input = true;
+                    inputs.push(PtyInput::key(code, mods));
                 }
             }
             _ => {}
         }
     }
-    had_input
+    for input in &inputs {
+        session.send_input(input);
+    }
+    inputs
 }
 
 /// Convert an egui Key to a printable char for the PTY fallback path.
@@ -1231,8 +1251,8 @@ fn vector_border_connections(
     Some(conn)
 }
 
-fn draw_vector_border_cell(
-    painter: &egui::Painter,
+fn vector_border_shapes(
+    shapes: &mut Vec<egui::Shape>,
     rect: Rect,
     conn: LineConnections,
     color: Color32,
@@ -1250,50 +1270,50 @@ fn draw_vector_border_cell(
     .max(1.0);
     let half = thickness * 0.5;
     if conn.left {
-        painter.rect_filled(
+        shapes.push(egui::Shape::rect_filled(
             Rect::from_min_max(
                 Pos2::new(rect.left() - overscan, cy - half),
                 Pos2::new(cx + half, cy + half),
             ),
             0.0,
             color,
-        );
+        ));
     }
     if conn.right {
-        painter.rect_filled(
+        shapes.push(egui::Shape::rect_filled(
             Rect::from_min_max(
                 Pos2::new(cx - half, cy - half),
                 Pos2::new(rect.right() + overscan, cy + half),
             ),
             0.0,
             color,
-        );
+        ));
     }
     if conn.up {
-        painter.rect_filled(
+        shapes.push(egui::Shape::rect_filled(
             Rect::from_min_max(
                 Pos2::new(cx - half, rect.top() - overscan),
                 Pos2::new(cx + half, cy + half),
             ),
             0.0,
             color,
-        );
+        ));
     }
     if conn.down {
-        painter.rect_filled(
+        shapes.push(egui::Shape::rect_filled(
             Rect::from_min_max(
                 Pos2::new(cx - half, cy - half),
                 Pos2::new(cx + half, rect.bottom() + overscan),
             ),
             0.0,
             color,
-        );
+        ));
     }
-    painter.rect_filled(
+    shapes.push(egui::Shape::rect_filled(
         Rect::from_center_size(Pos2::new(cx, cy), egui::vec2(thickness, thickness)),
         0.0,
         color,
-    );
+    ));
 }
 
 fn snapshot_char(cells: &[Vec<PtyStyledCell>], row: isize, col: isize) -> char {
@@ -1476,18 +1496,104 @@ fn map_key_event(key: Key, modifiers: egui::Modifiers) -> Option<(KeyCode, KeyMo
         Key::Num7 => KeyCode::Char('7'),
         Key::Num8 => KeyCode::Char('8'),
         Key::Num9 => KeyCode::Char('9'),
-        _ => return None,
+        Key::F1 => KeyCode::F(1),
+        Key::F2 => KeyCode::F(2),
+        Key::F3 => KeyCode::F(3),
+        Key::F4 => KeyCode::F(4),
+        Key::F5 => KeyCode::F(5),
+        Key::F6 => KeyCode::F(6),
+        Key::F7 => KeyCode::F(7),
+        Key::F8 => KeyCode::F(8),
+        Key::F9 => KeyCode::F(9),
+        Key::F10 => KeyCode::F(10),
+        Key::F11 => KeyCode::F(11),
+        Key::F12 => KeyCode::F(12),
+        _ => KeyCode::Char(key_to_char(key, false)?),
+    };
+    // Report the shifted character, as crossterm does for terminal input.
+    let code = match code {
+        KeyCode::Char(_) if modifiers.shift => KeyCode::Char(key_to_char(key, true)?),
+        code => code,
     };
     Some((code, mods))
 }
 
-fn collect_dirty_rows(prev_lines: &[String], next_lines: &[String], rows: usize) -> Vec<usize> {
-    (0..rows)
-        .filter(|row| {
-            prev_lines.get(*row).map(String::as_str).unwrap_or("")
-                != next_lines.get(*row).map(String::as_str).unwrap_or("")
-        })
-        .collect()
+/// Opt-in (`ROBCOS_NATIVE_PTY_TEXTURE`) plain renderer that rasterizes rows
+/// into a texture.  Returns false when a row needs per-cell styling, in
+/// which case the caller falls back to cached row shapes.
+#[allow(clippy::too_many_arguments)]
+fn draw_plain_texture_rows(
+    state: &mut NativePtyState,
+    ctx: &Context,
+    painter: &egui::Painter,
+    screen: &RetroScreen,
+    content_rect: Rect,
+    palette: &RetroPalette,
+    smooth_borders: bool,
+    render_cols: usize,
+    render_rows: usize,
+) -> bool {
+    let needs_cell_draw = state
+        .frame
+        .styled
+        .cells
+        .iter()
+        .take(render_rows)
+        .any(|row| {
+            row.iter().take(render_cols).any(|cell| {
+                let (fg, _bg) = resolve_cell_colors(*cell);
+                cell.ch != ' ' && (fg != palette.fg || cell.bold || cell.italic || cell.underline)
+            })
+        });
+    if needs_cell_draw {
+        return false;
+    }
+    let mut lines = state.frame.plain.lines.clone();
+    if smooth_borders && plain_lines_have_ascii_borders(&lines) {
+        smooth_ascii_borders_in_plain_lines(&mut lines);
+    }
+    let dirty_rows: Vec<usize> = (0..render_rows)
+        .filter(|row| state.plain_texture.row_epochs.get(*row) != state.frame.row_epochs.get(*row))
+        .collect();
+    let glyph_advance = content_rect.width() / render_cols.max(1) as f32;
+    for (row_idx, row) in state
+        .frame
+        .styled
+        .cells
+        .iter()
+        .enumerate()
+        .take(render_rows)
+    {
+        for (col_idx, cell) in row.iter().enumerate().take(render_cols) {
+            let (_fg, bg) = resolve_cell_colors(*cell);
+            if bg == palette.bg {
+                continue;
+            }
+            let x = screen.snap_value(content_rect.left() + col_idx as f32 * glyph_advance);
+            let rect = Rect::from_min_size(
+                Pos2::new(
+                    x,
+                    content_rect.top() + row_idx as f32 * screen.row_rect(0, 0, 1).height(),
+                ),
+                egui::vec2(glyph_advance.ceil(), screen.row_rect(0, 0, 1).height()),
+            );
+            painter.rect_filled(rect, 0.0, bg);
+        }
+    }
+    let drawn = render_plain_texture_if_possible(
+        state,
+        ctx,
+        painter,
+        screen,
+        content_rect,
+        palette,
+        &lines[..render_rows.min(lines.len())],
+        &dirty_rows,
+    );
+    if drawn {
+        state.plain_texture.row_epochs = state.frame.row_epochs.clone();
+    }
+    drawn
 }
 
 fn render_plain_texture_if_possible(
@@ -1735,59 +1841,6 @@ fn try_load_retro_font_bytes() -> Option<Vec<u8>> {
     None
 }
 
-fn diff_plain_snapshot(
-    prev_lines: &[String],
-    next_lines: &[String],
-    prev_cursor: Option<(u16, u16)>,
-    next_cursor: (u16, u16),
-    cols: usize,
-    rows: usize,
-) -> DirtyStats {
-    let mut changed_rows = vec![false; rows];
-    let mut changed_cells = 0usize;
-    for row in 0..rows {
-        let prev = prev_lines.get(row).map(String::as_str).unwrap_or("");
-        let next = next_lines.get(row).map(String::as_str).unwrap_or("");
-        let row_changed_cells = diff_plain_row_cells(prev, next, cols);
-        if row_changed_cells > 0 {
-            changed_rows[row] = true;
-            changed_cells = changed_cells.saturating_add(row_changed_cells);
-        }
-    }
-    if prev_cursor != Some(next_cursor) {
-        if let Some((r, _)) = prev_cursor {
-            let r = r as usize;
-            if r < rows {
-                changed_rows[r] = true;
-                changed_cells = changed_cells.saturating_add(1);
-            }
-        }
-        let r = next_cursor.0 as usize;
-        if r < rows {
-            changed_rows[r] = true;
-            changed_cells = changed_cells.saturating_add(1);
-        }
-    }
-    DirtyStats {
-        changed_rows: changed_rows.into_iter().filter(|changed| *changed).count(),
-        changed_cells: changed_cells.min(rows.saturating_mul(cols)),
-        total_rows: rows,
-        total_cells: rows.saturating_mul(cols),
-    }
-}
-
-fn diff_plain_row_cells(prev: &str, next: &str, cols: usize) -> usize {
-    let mut changed = 0usize;
-    let mut prev_chars = prev.chars();
-    let mut next_chars = next.chars();
-    for _ in 0..cols {
-        if prev_chars.next().unwrap_or(' ') != next_chars.next().unwrap_or(' ') {
-            changed = changed.saturating_add(1);
-        }
-    }
-    changed
-}
-
 fn draw_perf_overlay(
     screen: &RetroScreen,
     painter: &egui::Painter,
@@ -0,0 +1,249 @@
+use super::retro_ui::{current_palette, RetroScreen};
+use crate::native::desktop_scheduler_service::SchedulerRuntime;
+use crate::scheduler::{format_job_time, ScheduledJob};
+use eframe::egui::{self, Context};
+
+const NAME_WIDTH: usize = 18;
+const SCHEDULE_WIDTH: usize = 16;
+const TIME_WIDTH: usize = 16;
+
+/// Scheduler view state shared by the terminal screen and the desktop
+/// window, plus the desktop window's new-job form.
+#[derive(Debug, Clone, Default)]
+pub struct SchedulerUiState {
+    pub open: bool,
+    pub selected: usize,
+    pub show_history: bool,
+    pub draft_name: String,
+    pub draft_target: String,
+    pub draft_schedule: String,
+    pub draft_visible: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalSchedulerRequest {
+    None,
+    Back,
+    Add,
+    Remove(u64),
+    ToggleEnabled(u64),
+    ToggleVisible(u64),
+    RunNow(u64),
+}
+
+fn fit(text: &str, width: usize) -> String {
+    let mut fitted: String = text.chars().take(width).collect();
+    let len = fitted.chars().count();
+    fitted.extend(std::iter::repeat_n(' ', width - len));
+    fitted
+}
+
+pub fn job_next_run_label(runtime: &SchedulerRuntime, job: &ScheduledJob) -> String {
+    if runtime.is_running(job.id) {
+        "running".to_string()
+    } else if !job.enabled {
+        "paused".to_string()
+    } else {
+        job.next_run()
+            .map(format_job_time)
+            .unwrap_or_else(|| "-".to_string())
+    }
+}
+
+pub fn job_last_result_label(runtime: &SchedulerRuntime, job: &ScheduledJob) -> String {
+    runtime
+        .schedule()
+        .last_result(job.id)
+        .map(|result| result.outcome.label())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn job_line(runtime: &SchedulerRuntime, job: &ScheduledJob) -> String {
+    format!(
+        "{} {} {} {} {}",
+        if job.visible { "W" } else { "H" },
+        fit(&job.name, NAME_WIDTH),
+        fit(&job.schedule.describe(), SCHEDULE_WIDTH),
+        fit(&job_next_run_label(runtime, job), TIME_WIDTH),
+        job_last_result_label(runtime, job)
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn draw_scheduler_screen(
+    ctx: &Context,
+    state: &mut SchedulerUiState,
+    runtime: &SchedulerRuntime,
+    shell_status: &str,
+    cols: usize,
+    rows: usize,
+    header_start_row: usize,
+    separator_top_row: usize,
+    title_row: usize,
+    separator_bottom_row: usize,
+    subtitle_row: usize,
+    menu_start_row: usize,
+    status_row: usize,
+    content_col: usize,
+) -> TerminalSchedulerRequest {
+    let schedule = runtime.schedule();
+    let count = if state.show_history {
+        schedule.results.len()
+    } else {
+        schedule.jobs.len()
+    };
+    let mut request = TerminalSchedulerRequest::None;
+    ctx.input(|i| {
+        if i.key_pressed(egui::Key::ArrowDown) {
+            state.selected = (state.selected + 1).min(count.saturating_sub(1));
+        } else if i.key_pressed(egui::Key::ArrowUp) {
+            state.selected = state.selected.saturating_sub(1);
+        } else if i.key_pressed(egui::Key::Q) || i.key_pressed(egui::Key::Escape) {
+            request = TerminalSchedulerRequest::Back;
+        } else if i.key_pressed(egui::Key::H) {
+            state.show_history = !state.show_history;
+            state.selected = 0;
+        } else if state.show_history {
+            // The history is read-only.
+        } else if i.key_pressed(egui::Key::A) {
+            request = TerminalSchedulerRequest::Add;
+        } else if let Some(job) = schedule.jobs.get(state.selected) {
+            if i.key_pressed(egui::Key::D) || i.key_pressed(egui::Key::Delete) {
+                request = TerminalSchedulerRequest::Remove(job.id);
+            } else if i.key_pressed(egui::Key::Space) || i.key_pressed(egui::Key::E) {
+                request = TerminalSchedulerRequest::ToggleEnabled(job.id);
+            } else if i.key_pressed(egui::Key::W) {
+                request = TerminalSchedulerRequest::ToggleVisible(job.id);
+            } else if i.key_pressed(egui::Key::R) || i.key_pressed(egui::Key::Enter) {
+                request = TerminalSchedulerRequest::RunNow(job.id);
+            }
+        }
+    });
+    state.selected = state.selected.min(count.saturating_sub(1));
+
+    egui::CentralPanel::default()
+        .frame(
+            egui::Frame::none()
+                .fill(current_palette().bg)
+                .inner_margin(0.0),
+        )
+        .show(ctx, |ui| {
+            let palette = current_palette();
+            let (screen, _) = RetroScreen::new(ui, cols, rows);
+            let painter = ui.painter_at(screen.rect);
+            screen.paint_bg(&painter, palette.bg);
+            for (idx, line) in crate::config::HEADER_LINES.iter().enumerate() {
+                screen.centered_text(&painter, header_start_row + idx, line, palette.fg, true);
+            }
+            screen.separator(&painter, separator_top_row, &palette);
+            screen.centered_text(&painter, title_row, "Scheduler", palette.fg, true);
+            screen.separator(&painter, separator_bottom_row, &palette);
+
+            let subtitle = if state.show_history {
+                format!("Run history ({} results)", schedule.results.len())
+            } else {
+                format!("{} jobs | H hidden, W window", schedule.jobs.len())
+            };
+            screen.text(&painter, content_col, subtitle_row, &subtitle, palette.dim);
+
+            let header = if state.show_history {
+                format!(
+                    "{} {} Result",
+                    fit("Job", NAME_WIDTH),
+                    fit("Started", TIME_WIDTH)
+                )
+            } else {
+                format!(
+                    "  {} {} {} Last",
+                    fit("Name", NAME_WIDTH),
+                    fit("Schedule", SCHEDULE_WIDTH),
+                    fit("Next run", TIME_WIDTH)
+                )
+            };
+            screen.text(&painter, content_col, menu_start_row, &header, palette.fg);
+
+            let lines: Vec<String> = if state.show_history {
+                schedule
+                    .results
+                    .iter()
+                    .rev()
+                    .map(|result| {
+                        format!(
+                            "{} {} {}",
+                            fit(&result.job_name, NAME_WIDTH),
+                            fit(&format_job_time(result.started), TIME_WIDTH),
+                            result.outcome.label()
+                        )
+                    })
+                    .collect()
+            } else {
+                schedule
+                    .jobs
+                    .iter()
+                    .map(|job| job_line(runtime, job))
+                    .collect()
+            };
+            let first_row = menu_start_row + 1;
+            // Leave room for the detail line of the selected job.
+            let visible = status_row.saturating_sub(first_row + 3).max(1);
+            let offset = state.selected.saturating_sub(visible - 1);
+            for (line, text) in lines.iter().skip(offset).take(visible).enumerate() {
+                let idx = offset + line;
+                let response = screen.selectable_row(
+                    ui,
+                    &painter,
+                    &palette,
+                    content_col,
+                    first_row + line,
+                    text,
+                    idx == state.selected,
+                );
+                if response.clicked() {
+                    state.selected = idx;
+                }
+            }
+            if lines.is_empty() {
+                let empty = if state.show_history {
+                    "No runs yet."
+                } else {
+                    "No scheduled jobs. Press a to add one."
+                };
+                screen.text(&painter, content_col, first_row, empty, palette.dim);
+            }
+
+            let detail = if state.show_history {
+                schedule
+                    .results
+                    .iter()
+                    .rev()
+                    .nth(state.selected)
+                    .and_then(|result| result.log.as_ref())
+                    .map(|log| format!("Log: {}", log.display()))
+            } else {
+                schedule
+                    .jobs
+                    .get(state.selected)
+                    .map(|job| format!("Runs: {}", job.target.describe()))
+            };
+            if let Some(detail) = detail {
+                screen.text(
+                    &painter,
+                    content_col,
+                    status_row.saturating_sub(2),
+                    &detail,
+                    palette.dim,
+                );
+            }
+
+            let footer = if !shell_status.is_empty() {
+                shell_status
+            } else if state.show_history {
+                "h jobs  q back"
+            } else {
+                "a add  d delete  e enable  w window/hidden  r run now  h history  q back"
+            };
+            screen.text(&painter, content_col, status_row, footer, palette.dim);
+        });
+
+    request
+}
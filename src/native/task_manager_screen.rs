@@ -0,0 +1,166 @@
+use super::retro_ui::{current_palette, RetroScreen};
+use crate::pty::PtySessionId;
+use eframe::egui::{self, Context};
+pub use robcos_native_task_manager_app::{
+    format_memory, task_header_line, task_row_line, TaskManagerState, TaskSignal, TaskSortKey,
+    TASK_REFRESH_INTERVAL,
+};
+
+const NAME_WIDTH: usize = 22;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalTaskManagerRequest {
+    None,
+    Back,
+    EditFilter,
+    Signal { pid: u32, signal: TaskSignal },
+    JumpToPty(PtySessionId),
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn draw_task_manager_screen(
+    ctx: &Context,
+    state: &mut TaskManagerState,
+    shell_status: &str,
+    cols: usize,
+    rows: usize,
+    header_start_row: usize,
+    separator_top_row: usize,
+    title_row: usize,
+    separator_bottom_row: usize,
+    subtitle_row: usize,
+    menu_start_row: usize,
+    status_row: usize,
+    content_col: usize,
+) -> TerminalTaskManagerRequest {
+    state.refresh_if_stale();
+    ctx.request_repaint_after(TASK_REFRESH_INTERVAL);
+
+    let tasks = state.visible_rows();
+    let mut request = TerminalTaskManagerRequest::None;
+    ctx.input(|i| {
+        if i.key_pressed(egui::Key::ArrowDown) {
+            state.selected = (state.selected + 1).min(tasks.len().saturating_sub(1));
+        } else if i.key_pressed(egui::Key::ArrowUp) {
+            state.selected = state.selected.saturating_sub(1);
+        } else if i.key_pressed(egui::Key::PageDown) {
+            state.selected = (state.selected + 10).min(tasks.len().saturating_sub(1));
+        } else if i.key_pressed(egui::Key::PageUp) {
+            state.selected = state.selected.saturating_sub(10);
+        } else if i.key_pressed(egui::Key::Q) {
+            request = TerminalTaskManagerRequest::Back;
+        } else if i.key_pressed(egui::Key::S) {
+            state.cycle_sort();
+        } else if i.key_pressed(egui::Key::R) {
+            state.descending = !state.descending;
+        } else if i.key_pressed(egui::Key::O) {
+            state.owned_only = !state.owned_only;
+            state.selected = 0;
+        } else if i.key_pressed(egui::Key::Slash) || i.key_pressed(egui::Key::F) {
+            request = TerminalTaskManagerRequest::EditFilter;
+        } else if let Some(row) = tasks.get(state.selected) {
+            let signal = if i.key_pressed(egui::Key::T) {
+                Some(TaskSignal::Term)
+            } else if i.key_pressed(egui::Key::K) {
+                Some(TaskSignal::Kill)
+            } else if i.key_pressed(egui::Key::P) {
+                Some(TaskSignal::Stop)
+            } else if i.key_pressed(egui::Key::C) {
+                Some(TaskSignal::Continue)
+            } else {
+                None
+            };
+            if let Some(signal) = signal {
+                request = TerminalTaskManagerRequest::Signal {
+                    pid: row.pid,
+                    signal,
+                };
+            } else if i.key_pressed(egui::Key::Enter) {
+                if let Some(session) = row.pty_session() {
+                    request = TerminalTaskManagerRequest::JumpToPty(session);
+                }
+            }
+        }
+    });
+    state.selected = state.selected.min(tasks.len().saturating_sub(1));
+
+    egui::CentralPanel::default()
+        .frame(
+            egui::Frame::none()
+                .fill(current_palette().bg)
+                .inner_margin(0.0),
+        )
+        .show(ctx, |ui| {
+            let palette = current_palette();
+            let (screen, _) = RetroScreen::new(ui, cols, rows);
+            let painter = ui.painter_at(screen.rect);
+            screen.paint_bg(&painter, palette.bg);
+            for (idx, line) in crate::config::HEADER_LINES.iter().enumerate() {
+                screen.centered_text(&painter, header_start_row + idx, line, palette.fg, true);
+            }
+            screen.separator(&painter, separator_top_row, &palette);
+            screen.centered_text(&painter, title_row, "Task Manager", palette.fg, true);
+            screen.separator(&painter, separator_bottom_row, &palette);
+
+            let direction = if state.descending { "desc" } else { "asc" };
+            let scope = if state.owned_only {
+                "RobCoOS only"
+            } else {
+                "All"
+            };
+            let mut subtitle = format!(
+                "{scope} | Sort: {} {direction} | Mem: {} / {}",
+                state.sort.label(),
+                format_memory(state.used_memory()),
+                format_memory(state.total_memory())
+            );
+            if !state.filter.is_empty() {
+                subtitle.push_str(&format!(" | Filter: {}", state.filter));
+            }
+            screen.text(&painter, content_col, subtitle_row, &subtitle, palette.dim);
+
+            screen.text(
+                &painter,
+                content_col,
+                menu_start_row,
+                &task_header_line(NAME_WIDTH),
+                palette.fg,
+            );
+            let first_row = menu_start_row + 1;
+            let visible = status_row.saturating_sub(first_row + 1).max(1);
+            let offset = state.selected.saturating_sub(visible - 1);
+            for (line, row) in tasks.iter().skip(offset).take(visible).enumerate() {
+                let idx = offset + line;
+                let response = screen.selectable_row(
+                    ui,
+                    &painter,
+                    &palette,
+                    content_col,
+                    first_row + line,
+                    &task_row_line(row, NAME_WIDTH),
+                    idx == state.selected,
+                );
+                if response.clicked() {
+                    state.selected = idx;
+                }
+            }
+            if tasks.is_empty() {
+                screen.text(
+                    &painter,
+                    content_col,
+                    first_row,
+                    "No matching processes.",
+                    palette.dim,
+                );
+            }
+
+            let footer = if shell_status.is_empty() {
+                "s sort  r reverse  o owned  / filter  t term  k kill  p stop  c cont  Enter jump  q back"
+            } else {
+                shell_status
+            };
+            screen.text(&painter, content_col, status_row, footer, palette.dim);
+        });
+
+    request
+}